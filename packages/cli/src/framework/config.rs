@@ -1,8 +1,11 @@
+use anyhow::{bail, Context, Result};
 use config::Map;
 use data_doc_derive::GetDataDocs;
 use getset::Getters;
 use serde::{Deserialize, Serialize};
 
+use crate::support::keystore;
+
 #[derive(Serialize, Deserialize, Getters, Debug, GetDataDocs)]
 #[get = "pub"]
 pub struct GlobalConfig {
@@ -30,6 +33,56 @@ pub struct GlobalConfig {
 
     /// Predefined account used for interacting with the chain
     accounts: Map<String, Account>,
+
+    /// User defined command aliases, mapping alias name to beaker arguments, eg.
+    /// `deploy-counter = "wasm deploy counter --signer-account test1 --network {0}"`.
+    /// Placeholders `{0}`, `{1}`, ... are substituted with arguments following the alias,
+    /// the rest of the arguments are appended. Built-in commands can not be shadowed.
+    #[serde(default)]
+    alias: Map<String, String>,
+}
+
+impl GlobalConfig {
+    /// Gas price on `network`, its own `gas_price` if set, otherwise the global one
+    pub fn network_gas_price(&self, network: &str) -> &str {
+        self.networks
+            .get(network)
+            .and_then(|n| n.gas_price.as_deref())
+            .unwrap_or(&self.gas_price)
+    }
+
+    /// Check that every account's key is well formed, so that a malformed key fails before any tx is built
+    pub fn validate_accounts(&self) -> Result<()> {
+        for (name, account) in &self.accounts {
+            if let Account::FromPrivateKey { private_key, .. } = account {
+                keystore::validate_private_key(private_key)
+                    .with_context(|| format!("Invalid private key of account `{name}`"))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that `ca_cert` is only set where it applies, the rpc client trusts webpki roots only
+    /// so an https rpc endpoint behind the custom CA would fail on every rpc request
+    pub fn validate_networks(&self) -> Result<()> {
+        for (name, network) in &self.networks {
+            if network.ca_cert.is_some() && network.rpc_endpoint.starts_with("https") {
+                bail!("`ca_cert` of network `{name}` only applies to its grpc endpoint, but its rpc endpoint `{}` is https, use an http rpc endpoint or one trusted by webpki roots", network.rpc_endpoint);
+            }
+        }
+        Ok(())
+    }
+
+    /// Every network served by `sim` instead of its own nodes, keeping only its address book
+    pub fn with_simulated_networks(mut self, sim: &Network) -> Self {
+        for network in self.networks.values_mut() {
+            *network = Network {
+                address_book: network.address_book.clone(),
+                ..sim.clone()
+            };
+        }
+        self
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, GetDataDocs)]
@@ -55,6 +108,167 @@ pub struct Network {
 
     /// Endpoint for rpc
     rpc_endpoint: String,
+
+    /// Mark the network as production, broadcasting to it requires explicit confirmation (or `--yes`)
+    /// after displaying signer, fee and messages of the transaction
+    #[serde(default)]
+    production: bool,
+
+    /// Named addresses on the network, usable anywhere an address is expected by prefixing the name with `@`, eg.
+    /// `address_book = { treasury = "osmo1..." }` allows `--admin @treasury`
+    #[serde(default)]
+    address_book: Map<String, String>,
+
+    /// Number of times a transaction is re-signed with refreshed account sequence and rebroadcasted
+    /// when it fails due to account sequence mismatch, eg. when other tools use the same account concurrently
+    #[serde(default = "default_sequence_mismatch_retries")]
+    sequence_mismatch_retries: u32,
+
+    /// How broadcasted transaction is confirmed, `block` waits for commit within the broadcast request while
+    /// `sync` returns after check_tx and polls for the committed transaction.
+    /// Defaults to `sync` on local networks and `block` on shared networks
+    #[serde(default)]
+    #[getset(skip)]
+    broadcast_mode: Option<BroadcastMode>,
+
+    /// Bech32 prefix of account addresses on the network, shown by `key show --all-networks`.
+    /// Global `account_prefix` is used if not specified
+    #[serde(default)]
+    account_prefix: Option<String>,
+
+    /// Coin type of the network's HD derivation path, eg. `60` for Injective and Evmos, `529` for Secret or `330` for Terra.
+    /// Signing keys for the network and `key show --all-networks` derive with it unless the account sets its own
+    /// `derivation_path` or `coin_type`. Coin type of the global `derivation_path` is used if not specified
+    #[serde(default)]
+    coin_type: Option<u32>,
+
+    /// HTTP proxy to reach rpc and grpc endpoints through, eg. `http://proxy.corp:3128`.
+    /// `HTTPS_PROXY` / `HTTP_PROXY` (respecting `NO_PROXY`) are used if not specified
+    #[serde(default)]
+    proxy: Option<String>,
+
+    /// Path to PEM encoded CA certificate to trust for the grpc endpoint in addition to system roots,
+    /// eg. for endpoints behind a corporate TLS-inspecting gateway.
+    /// The rpc client can not trust a custom CA, so config with `ca_cert` and an https `rpc_endpoint` is rejected
+    #[serde(default)]
+    ca_cert: Option<String>,
+
+    /// Seconds to wait for the grpc connection to be established before failing
+    #[serde(default = "default_connect_timeout")]
+    connect_timeout: u64,
+
+    /// Seconds to wait for each grpc or rpc request before failing
+    #[serde(default = "default_request_timeout")]
+    request_timeout: u64,
+
+    /// Signing algorithm of accounts on the network, `eth_secp256k1` for chains with Ethereum-style keys
+    /// like Injective and Evmos, which sign keccak256 of the sign doc and derive addresses with keccak
+    #[serde(default)]
+    key_algo: KeyAlgo,
+
+    /// Type url of the signer's public key when `key_algo` is `eth_secp256k1`, defaults to
+    /// `/ethermint.crypto.v1.ethsecp256k1.PubKey`, Injective uses `/injective.crypto.v1beta1.ethsecp256k1.PubKey`
+    #[serde(default)]
+    pub_key_type: Option<String>,
+
+    /// Gas price on the network, eg. `0.1inj`. Global `gas_price` is used if not specified
+    #[serde(default)]
+    gas_price: Option<String>,
+
+    /// Gas prices of additional fee denoms accepted by the network, eg. `["0.0025ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"]`.
+    /// Fee is paid in one of them when `--fee-denom` is specified, `gas_price` is used otherwise
+    #[serde(default)]
+    fee_denoms: Vec<String>,
+
+    /// Maximum number of requests per second sent to each of the network's endpoints, unlimited if not specified.
+    /// Keeps batch commands like `migrate-all` and `query-batch` from tripping public nodes' rate limiters mid-run
+    #[serde(default)]
+    rate_limit: Option<f64>,
+
+    /// Milliseconds to wait between consecutive transactions broadcasted to the network within one invocation,
+    /// eg. by `migrate-all` batches or multi-network deploys
+    #[serde(default)]
+    tx_delay: u64,
+
+    /// Multiplier applied to the chain's current base fee, used as gas price instead of `gas_price` where the chain
+    /// has a fee market (osmosis `x/txfees` EIP-1559 base fee or `x/feemarket`), so txs keep up during congestion.
+    /// `0` always uses the static `gas_price`
+    #[serde(default = "default_base_fee_multiplier")]
+    base_fee_multiplier: f64,
+
+    /// How transactions are signed for the network, `amino_json` for chains or signers that only support
+    /// legacy amino json sign docs. Overridden by the signer account's own `sign_mode`
+    #[serde(default)]
+    sign_mode: SignMode,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, GetDataDocs)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyAlgo {
+    /// Cosmos SDK default, sha256 signatures and ripemd160(sha256(pubkey)) addresses
+    Secp256k1,
+
+    /// Ethereum-style keys, keccak256 signatures and the last 20 bytes of keccak256(pubkey) as address
+    EthSecp256k1,
+}
+
+impl Default for KeyAlgo {
+    fn default() -> Self {
+        KeyAlgo::Secp256k1
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, GetDataDocs)]
+#[serde(rename_all = "snake_case")]
+pub enum SignMode {
+    /// `SIGN_MODE_DIRECT`, signing the protobuf encoded tx
+    Direct,
+
+    /// `SIGN_MODE_LEGACY_AMINO_JSON`, signing the amino json sign doc, as required by Ledger's Cosmos app
+    AminoJson,
+}
+
+impl Default for SignMode {
+    fn default() -> Self {
+        SignMode::Direct
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, GetDataDocs)]
+#[serde(rename_all = "snake_case")]
+pub enum BroadcastMode {
+    /// Broadcast with `broadcast_tx_commit`, confirmation is returned once the tx is committed
+    Block,
+
+    /// Broadcast with `broadcast_tx_sync` and poll for the tx until it is committed
+    Sync,
+}
+
+impl Network {
+    /// Broadcast mode of the network, local chains produce blocks quickly enough to poll for the tx
+    pub fn broadcast_mode(&self) -> BroadcastMode {
+        match (self.broadcast_mode, &self.network_variant) {
+            (Some(mode), _) => mode,
+            (None, NetworkVariant::Local) => BroadcastMode::Sync,
+            (None, NetworkVariant::Shared) => BroadcastMode::Block,
+        }
+    }
+}
+
+fn default_sequence_mismatch_retries() -> u32 {
+    3
+}
+
+fn default_connect_timeout() -> u64 {
+    10
+}
+
+fn default_request_timeout() -> u64 {
+    60
+}
+
+fn default_base_fee_multiplier() -> f64 {
+    1.5
 }
 
 #[derive(Serialize, Deserialize, Debug, GetDataDocs)]
@@ -62,13 +276,126 @@ pub struct Network {
 pub enum Account {
     /// Used for specifying account from mnemonic, eg.
     /// `{ mnemonic = "satisfy adjust timber high purchase tuition stool faith fine install that you unaware feed domain license impose boss human eager hat rent enjoy dawn" }`
-    /// For testing only, for production or wallet with fair amount of coins on mainnet, don't specify these information in plain text
-    FromMnemonic { mnemonic: String },
+    /// For testing only, for production or wallet with fair amount of coins on mainnet, don't specify these information in plain text.
+    /// `derivation_path` or `coin_type` overrides the global `derivation_path` for this account only, eg.
+    /// `{ mnemonic = "...", derivation_path = "m/44'/118'/0'/0/3" }` or `{ mnemonic = "...", coin_type = 330 }`.
+    /// Wordlist `language` of the mnemonic is detected if not specified.
+    /// Any account can set `sign_mode` or `key_algo`, overriding the network's, eg. `{ mnemonic = "...", sign_mode = "amino_json" }`
+    /// or `{ private_key = "...", key_algo = "eth_secp256k1" }`.
+    FromMnemonic {
+        mnemonic: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        language: Option<MnemonicLanguage>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        derivation_path: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        coin_type: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sign_mode: Option<SignMode>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        key_algo: Option<KeyAlgo>,
+    },
 
     /// Used for specifying account from private key, eg.
     /// `{ private_key = "SNI8xBejBnTpB6JAPxCfCC2S4ZeCPQLmpCPGrrjkEgQ=" }`
+    /// The key can be hex (optionally `0x` prefixed), base64 or armored as exported by `<chain daemon> keys export`,
+    /// passphrase of armored key is prompted for or read from `BEAKER_KEY_PASSPHRASE`. Malformed keys are rejected when loading config.
     /// For testing only, for production or wallet with fair amount of coins on mainnet, don't specify these information in plain text
-    FromPrivateKey { private_key: String },
+    FromPrivateKey {
+        private_key: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sign_mode: Option<SignMode>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        key_algo: Option<KeyAlgo>,
+    },
+
+    /// Used for specifying account whose private key never leaves a remote signing service, eg.
+    /// `{ remote_signer = { backend = "aws_kms", key_id = "alias/deployer", region = "us-east-1" } }`
+    FromRemoteSigner {
+        remote_signer: RemoteSigner,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sign_mode: Option<SignMode>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        key_algo: Option<KeyAlgo>,
+    },
+}
+
+impl Account {
+    /// Account from `phrase` without overrides, its language is detected and the rest taken from config
+    pub fn from_mnemonic(phrase: &str) -> Self {
+        Account::FromMnemonic {
+            mnemonic: phrase.to_string(),
+            language: None,
+            derivation_path: None,
+            coin_type: None,
+            sign_mode: None,
+            key_algo: None,
+        }
+    }
+
+    /// Sign mode of the account itself, the network's is used if not specified
+    pub fn sign_mode(&self) -> Option<SignMode> {
+        match self {
+            Account::FromMnemonic { sign_mode, .. }
+            | Account::FromPrivateKey { sign_mode, .. }
+            | Account::FromRemoteSigner { sign_mode, .. } => *sign_mode,
+        }
+    }
+
+    /// Key algorithm of the account itself, the network's is used if not specified
+    pub fn key_algo(&self) -> Option<KeyAlgo> {
+        match self {
+            Account::FromMnemonic { key_algo, .. }
+            | Account::FromPrivateKey { key_algo, .. }
+            | Account::FromRemoteSigner { key_algo, .. } => *key_algo,
+        }
+    }
+}
+
+/// Standard BIP-39 wordlists
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, GetDataDocs)]
+#[serde(rename_all = "snake_case")]
+pub enum MnemonicLanguage {
+    English,
+    SimplifiedChinese,
+    TraditionalChinese,
+    Czech,
+    French,
+    Italian,
+    Japanese,
+    Korean,
+    Spanish,
+}
+
+impl From<MnemonicLanguage> for bip39::Language {
+    fn from(language: MnemonicLanguage) -> Self {
+        match language {
+            MnemonicLanguage::English => bip39::Language::English,
+            MnemonicLanguage::SimplifiedChinese => bip39::Language::SimplifiedChinese,
+            MnemonicLanguage::TraditionalChinese => bip39::Language::TraditionalChinese,
+            MnemonicLanguage::Czech => bip39::Language::Czech,
+            MnemonicLanguage::French => bip39::Language::French,
+            MnemonicLanguage::Italian => bip39::Language::Italian,
+            MnemonicLanguage::Japanese => bip39::Language::Japanese,
+            MnemonicLanguage::Korean => bip39::Language::Korean,
+            MnemonicLanguage::Spanish => bip39::Language::Spanish,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, GetDataDocs)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum RemoteSigner {
+    /// AWS KMS asymmetric key with `ECC_SECG_P256K1` key spec, signing goes through the installed `aws` cli
+    /// and its configured credentials
+    AwsKms {
+        key_id: String,
+        region: Option<String>,
+    },
+
+    /// HashiCorp Vault transit key of secp256k1 type, signing goes through the installed `vault` cli
+    /// and its configured token. `mount` defaults to `transit`
+    VaultTransit { key: String, mount: Option<String> },
 }
 
 // TODO: make no assumption about osmosis later
@@ -87,7 +414,25 @@ impl Default for GlobalConfig {
                         chain_id: "localosmosis".into(),
                         network_variant: NetworkVariant::Local,
                         grpc_endpoint: "http://localhost:9090".into(),
-                        rpc_endpoint: "http://localhost:26657".into()
+                        rpc_endpoint: "http://localhost:26657".into(),
+                        production: false,
+                        address_book: Map::new(),
+                        sequence_mismatch_retries: default_sequence_mismatch_retries(),
+                        broadcast_mode: None,
+                        account_prefix: None,
+                        coin_type: None,
+                        proxy: None,
+                        ca_cert: None,
+                        connect_timeout: default_connect_timeout(),
+                        request_timeout: default_request_timeout(),
+                        key_algo: KeyAlgo::default(),
+                        pub_key_type: None,
+                        gas_price: None,
+                        fee_denoms: vec![],
+                        rate_limit: None,
+                        tx_delay: 0,
+                        base_fee_multiplier: default_base_fee_multiplier(),
+                        sign_mode: SignMode::default(),
                     }
                 ),
                 (
@@ -97,6 +442,24 @@ impl Default for GlobalConfig {
                         network_variant: NetworkVariant::Shared,
                         grpc_endpoint: "https://grpc-test.osmosis.zone:9090".into(),
                         rpc_endpoint: "https://rpc-test.osmosis.zone".into(),
+                        production: false,
+                        address_book: Map::new(),
+                        sequence_mismatch_retries: default_sequence_mismatch_retries(),
+                        broadcast_mode: None,
+                        account_prefix: None,
+                        coin_type: None,
+                        proxy: None,
+                        ca_cert: None,
+                        connect_timeout: default_connect_timeout(),
+                        request_timeout: default_request_timeout(),
+                        key_algo: KeyAlgo::default(),
+                        pub_key_type: None,
+                        gas_price: None,
+                        fee_denoms: vec![],
+                        rate_limit: None,
+                        tx_delay: 0,
+                        base_fee_multiplier: default_base_fee_multiplier(),
+                        sign_mode: SignMode::default(),
                     }
                 ),
                 (
@@ -106,22 +469,41 @@ impl Default for GlobalConfig {
                         network_variant: NetworkVariant::Shared,
                         grpc_endpoint: "https://grpc.osmosis.zone:9090".into(),
                         rpc_endpoint: "https://rpc.osmosis.zone".into(),
+                        production: true,
+                        address_book: Map::new(),
+                        sequence_mismatch_retries: default_sequence_mismatch_retries(),
+                        broadcast_mode: None,
+                        account_prefix: None,
+                        coin_type: None,
+                        proxy: None,
+                        ca_cert: None,
+                        connect_timeout: default_connect_timeout(),
+                        request_timeout: default_request_timeout(),
+                        key_algo: KeyAlgo::default(),
+                        pub_key_type: None,
+                        gas_price: None,
+                        fee_denoms: vec![],
+                        rate_limit: None,
+                        tx_delay: 0,
+                        base_fee_multiplier: default_base_fee_multiplier(),
+                        sign_mode: SignMode::default(),
                     }
                 )
             ]),
             accounts: Map::from([
-                ("validator".into(), Account::FromMnemonic { mnemonic: "satisfy adjust timber high purchase tuition stool faith fine install that you unaware feed domain license impose boss human eager hat rent enjoy dawn".into() }),
-                ("test1".into(), Account::FromMnemonic { mnemonic: "notice oak worry limit wrap speak medal online prefer cluster roof addict wrist behave treat actual wasp year salad speed social layer crew genius".into() }),
-                ("test2".into(), Account::FromMnemonic { mnemonic: "quality vacuum heart guard buzz spike sight swarm shove special gym robust assume sudden deposit grid alcohol choice devote leader tilt noodle tide penalty".into() }),
-                ("test3".into(), Account::FromMnemonic { mnemonic: "symbol force gallery make bulk round subway violin worry mixture penalty kingdom boring survey tool fringe patrol sausage hard admit remember broken alien absorb".into() }),
-                ("test4".into(), Account::FromMnemonic { mnemonic: "bounce success option birth apple portion aunt rural episode solution hockey pencil lend session cause hedgehog slender journey system canvas decorate razor catch empty".into() }),
-                ("test5".into(), Account::FromMnemonic { mnemonic: "second render cat sing soup reward cluster island bench diet lumber grocery repeat balcony perfect diesel stumble piano distance caught occur example ozone loyal".into() }),
-                ("test6".into(), Account::FromMnemonic { mnemonic: "spatial forest elevator battle also spoon fun skirt flight initial nasty transfer glory palm drama gossip remove fan joke shove label dune debate quick".into() }),
-                ("test7".into(), Account::FromMnemonic { mnemonic: "noble width taxi input there patrol clown public spell aunt wish punch moment will misery eight excess arena pen turtle minimum grain vague inmate".into() }),
-                ("test8".into(), Account::FromMnemonic { mnemonic: "cream sport mango believe inhale text fish rely elegant below earth april wall rug ritual blossom cherry detail length blind digital proof identify ride".into() }),
-                ("test9".into(), Account::FromMnemonic { mnemonic: "index light average senior silent limit usual local involve delay update rack cause inmate wall render magnet common feature laundry exact casual resource hundred".into() }),
-                ("test10".into(), Account::FromMnemonic { mnemonic: "prefer forget visit mistake mixture feel eyebrow autumn shop pair address airport diesel street pass vague innocent poem method awful require hurry unhappy shoulder".into() })
+                ("validator".into(), Account::from_mnemonic("satisfy adjust timber high purchase tuition stool faith fine install that you unaware feed domain license impose boss human eager hat rent enjoy dawn")),
+                ("test1".into(), Account::from_mnemonic("notice oak worry limit wrap speak medal online prefer cluster roof addict wrist behave treat actual wasp year salad speed social layer crew genius")),
+                ("test2".into(), Account::from_mnemonic("quality vacuum heart guard buzz spike sight swarm shove special gym robust assume sudden deposit grid alcohol choice devote leader tilt noodle tide penalty")),
+                ("test3".into(), Account::from_mnemonic("symbol force gallery make bulk round subway violin worry mixture penalty kingdom boring survey tool fringe patrol sausage hard admit remember broken alien absorb")),
+                ("test4".into(), Account::from_mnemonic("bounce success option birth apple portion aunt rural episode solution hockey pencil lend session cause hedgehog slender journey system canvas decorate razor catch empty")),
+                ("test5".into(), Account::from_mnemonic("second render cat sing soup reward cluster island bench diet lumber grocery repeat balcony perfect diesel stumble piano distance caught occur example ozone loyal")),
+                ("test6".into(), Account::from_mnemonic("spatial forest elevator battle also spoon fun skirt flight initial nasty transfer glory palm drama gossip remove fan joke shove label dune debate quick")),
+                ("test7".into(), Account::from_mnemonic("noble width taxi input there patrol clown public spell aunt wish punch moment will misery eight excess arena pen turtle minimum grain vague inmate")),
+                ("test8".into(), Account::from_mnemonic("cream sport mango believe inhale text fish rely elegant below earth april wall rug ritual blossom cherry detail length blind digital proof identify ride")),
+                ("test9".into(), Account::from_mnemonic("index light average senior silent limit usual local involve delay update rack cause inmate wall render magnet common feature laundry exact casual resource hundred")),
+                ("test10".into(), Account::from_mnemonic("prefer forget visit mistake mixture feel eyebrow autumn shop pair address airport diesel street pass vague innocent poem method awful require hurry unhappy shoulder"))
             ]),
+            alias: Map::new(),
         }
     }
 }
@@ -1,11 +1,15 @@
 use anyhow::{anyhow, Context as ErrContext, Result};
 use config::Config;
 use serde::{Deserialize, Serialize};
-use std::{env, path::PathBuf};
+use std::{env, path::PathBuf, sync::Arc};
 
-use super::config::GlobalConfig;
+use super::config::{GlobalConfig, Network};
+use crate::support::backend;
+use crate::support::chain_client::ChainClient;
+use crate::support::cosmos::Client;
+use crate::support::error::{Error, ErrorKind};
 
-pub trait Context<'a, Cfg>: Send
+pub trait Context<'a, Cfg>: Send + Sync
 where
     Cfg: Serialize + Deserialize<'a> + Default,
 {
@@ -24,10 +28,13 @@ where
             }
         }
 
-        Err(anyhow!(
-            "Config file `{}` not found in all the ancestor paths",
-            self.config_file_name()
-        ))
+        Err(anyhow!(Error::new(
+            ErrorKind::Config,
+            format!(
+                "Config file `{}` not found in all the ancestor paths",
+                self.config_file_name()
+            )
+        )))
     }
 
     fn root(&self) -> Result<PathBuf> {
@@ -48,6 +55,17 @@ where
             .with_context(|| "Unable to deserialize configuration.")
     }
 
+    /// Connections, keys and account info shared by all operations of this context.
+    /// Contexts that do not hold one get a fresh, unshared client on every call
+    fn chain_client(&self) -> Arc<ChainClient> {
+        Arc::new(ChainClient::default())
+    }
+
+    /// Client of `network` sharing this context's connections and caches
+    fn client(&self, network: Network) -> Client {
+        Client::new(network, self.chain_client())
+    }
+
     fn global_config(&self) -> Result<GlobalConfig> {
         let conf = Config::builder().add_source(Config::try_from(&GlobalConfig::default())?);
         let conf = match self.config_file_path() {
@@ -56,6 +74,14 @@ where
         };
         conf.build()?
             .try_deserialize::<GlobalConfig>()
+            .map(backend::apply)
             .with_context(|| "Unable to deserialize configuration.")
+            .and_then(|global_config| {
+                global_config
+                    .validate_accounts()
+                    .and_then(|_| global_config.validate_networks())
+                    .map_err(|e| anyhow!(Error::new(ErrorKind::Config, format!("{e:#}"))))?;
+                Ok(global_config)
+            })
     }
 }
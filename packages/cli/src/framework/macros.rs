@@ -23,25 +23,50 @@ macro_rules! config_impl {
 #[macro_export]
 macro_rules! context {
     ($ctx:ident, config={ $key:ident: $cfg:ident }) => {
-        struct $ctx {}
+        struct $ctx {
+            chain_client: std::sync::Arc<$crate::support::chain_client::ChainClient>,
+        }
         impl $ctx {
             fn new() -> Self {
-                $ctx {}
+                $ctx {
+                    chain_client: std::sync::Arc::new(
+                        $crate::support::chain_client::ChainClient::new(),
+                    ),
+                }
             }
         }
         impl<'a> Context<'a, $cfg> for $ctx {
             framework::macros::config_impl!($key, $cfg);
+
+            fn chain_client(&self) -> std::sync::Arc<$crate::support::chain_client::ChainClient> {
+                self.chain_client.clone()
+            }
         }
     };
 
     ($ctx:ident, config={ $key:ident: $cfg:ident }, config_file=$cfg_file:expr) => {
-        struct $ctx {}
+        struct $ctx {
+            chain_client: std::sync::Arc<$crate::support::chain_client::ChainClient>,
+        }
+        impl $ctx {
+            fn new() -> Self {
+                $ctx {
+                    chain_client: std::sync::Arc::new(
+                        $crate::support::chain_client::ChainClient::new(),
+                    ),
+                }
+            }
+        }
         impl<'a> Context<'a, $cfg> for $ctx {
             fn config_file_name(&self) -> String {
                 $cfg_file.to_string()
             }
 
             framework::macros::config_impl!($key, $cfg);
+
+            fn chain_client(&self) -> std::sync::Arc<$crate::support::chain_client::ChainClient> {
+                self.chain_client.clone()
+            }
         }
     };
 
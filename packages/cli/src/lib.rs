@@ -28,16 +28,37 @@ mod modules;
 mod support;
 
 use anyhow::{Context as _, Result};
-use clap::{AppSettings, Parser, Subcommand};
+use clap::{AppSettings, CommandFactory, Parser, Subcommand};
 use config::Config;
 use data_doc_derive::GetDataDocs;
+use modules::account::{AccountCmd, AccountConfig, AccountModule};
+use modules::audit_log::AuditLogCmd;
+use modules::authz::{AuthzCmd, AuthzConfig, AuthzModule};
+use modules::bench::BenchCmd;
+use modules::cw::{Cw20Cmd, Cw20Module, Cw721Cmd, Cw721Module, CwConfig};
+use modules::deploy::DeployCmd;
+use modules::gov::{GovCmd, GovConfig, GovModule};
 use modules::key::entrypoint::{KeyCmd, KeyModule};
+use modules::localnet::genesis::GoDuration;
+use modules::localnet::{LocalnetCmd, LocalnetConfig, LocalnetModule};
+use modules::release::{ReleaseCmd, ReleaseConfig, ReleaseModule};
+use modules::self_update::SelfCmd;
+use modules::state::{StateCmd, StateConfig, StateModule};
+use modules::test::{TestCmd, TestConfig, TestModule};
+use modules::tokenfactory::{TokenfactoryCmd, TokenfactoryConfig, TokenfactoryModule};
+use modules::tx::{TxCmd, TxConfig, TxModule};
 use serde::{Deserialize, Serialize};
+use support::backend::Backend;
+use support::completion::{completion_script, list_candidates, CompletionList};
 use support::node::run_npx;
+use support::output::OutputFormat;
 
 pub use framework::{config::GlobalConfig, Context, Module};
 pub use modules::wasm::{WasmCmd, WasmConfig, WasmModule};
 pub use modules::workspace::{WorkspaceCmd, WorkspaceConfig, WorkspaceModule};
+pub use support::error::{exit_code, report, ErrorKind};
+#[cfg(feature = "mock-chain")]
+pub use support::mock_chain::{MockAccount, MockChain, MockCode, MockContract, MockEvent, MockTx};
 
 use crate::modules::key::config::KeyConfig;
 
@@ -49,6 +70,91 @@ pub struct Cli {
     // config: Option<PathBuf>,
     #[clap(subcommand)]
     pub command: Commands,
+
+    /// Never prompt for missing inputs, fail instead (eg. when running in CI)
+    #[clap(long, global = true)]
+    pub no_input: bool,
+
+    /// Non-interactive mode for CI, also enabled by `BEAKER_CI=1`: implies `--no-input`,
+    /// disables progress animation and colors. See exit codes in the README
+    #[clap(long, global = true)]
+    pub ci: bool,
+
+    /// Agree to all prompts
+    #[clap(short, long, global = true)]
+    pub yes: bool,
+
+    /// Deadline of the whole command, eg. `120s` or `5m`, after which it fails with a timeout error
+    #[clap(long, global = true)]
+    pub timeout: Option<GoDuration>,
+
+    /// Output format of command results
+    #[clap(long, global = true, arg_enum, default_value = "table")]
+    pub output: OutputFormat,
+
+    /// Where queries and txs go, `sim` runs them against an in-process simulated chain saved in
+    /// `.beaker/sim` instead of the network's nodes, for fast iteration on contract logic
+    #[clap(long, global = true, arg_enum, default_value = "node")]
+    pub backend: Backend,
+}
+
+impl Cli {
+    /// Parse command line arguments after expanding user defined alias
+    pub fn parse_with_alias() -> Result<Self> {
+        let args = std::env::args().collect::<Vec<String>>();
+        let aliases = WasmContext::new()
+            .global_config()
+            .map(|c| c.alias().clone())
+            .unwrap_or_default();
+
+        let cmd = Cli::command();
+        let switches = cmd
+            .get_arguments()
+            .filter(|a| !a.is_takes_value_set())
+            .flat_map(|a| {
+                let long = a.get_long().map(|l| format!("--{l}"));
+                let short = a.get_short().map(|s| format!("-{s}"));
+                long.into_iter().chain(short)
+            })
+            .collect::<Vec<String>>();
+        let args = support::alias::expand_alias(
+            args,
+            &aliases,
+            |name| cmd.find_subcommand(name).is_some(),
+            |arg| switches.iter().any(|s| s == arg),
+        )?;
+
+        Ok(Cli::parse_from(args))
+    }
+
+    /// CI mode is on by `--ci` or `BEAKER_CI` set to anything but empty, `0` or `false`
+    pub fn ci(&self) -> bool {
+        self.ci
+            || std::env::var("BEAKER_CI")
+                .map(|v| !matches!(v.as_str(), "" | "0" | "false"))
+                .unwrap_or(false)
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let ci = self.ci();
+        if ci {
+            console::set_colors_enabled(false);
+            console::set_colors_enabled_stderr(false);
+        }
+        support::progress::set_plain(ci);
+        if let Some(timeout) = self.timeout {
+            support::future::set_deadline(timeout.0);
+        }
+        support::prompt::set_no_input(self.no_input || ci);
+        support::prompt::set_assume_yes(self.yes);
+        support::output::set_output_format(self.output);
+        if self.backend == Backend::Sim {
+            let ctx = WasmContext::new();
+            support::backend::start_sim(&ctx.root()?, &ctx.global_config()?)?;
+        }
+        let result = execute(&self.command);
+        result.and(support::backend::persist())
+    }
 }
 
 // === APP DEFINITION ===
@@ -68,11 +174,124 @@ pub enum Commands {
         #[clap(subcommand)]
         cmd: KeyCmd,
     },
+    /// Inspecting and managing funds of accounts
+    Account {
+        #[clap(subcommand)]
+        cmd: AccountCmd,
+    },
+    /// Granting and using authorization to act on behalf of other accounts
+    Authz {
+        #[clap(subcommand)]
+        cmd: AuthzCmd,
+    },
+    /// Submitting, voting on and following governance proposals
+    Gov {
+        #[clap(subcommand)]
+        cmd: GovCmd,
+    },
+    /// Managing deployment state of the project
+    State {
+        #[clap(subcommand)]
+        cmd: StateCmd,
+    },
+    /// Testing deployed contracts
+    Test {
+        #[clap(subcommand)]
+        cmd: TestCmd,
+    },
+    /// Promoting recorded deployments between networks
+    Deploy {
+        #[clap(subcommand)]
+        cmd: DeployCmd,
+    },
+    /// Tagging deployments in git history and publishing releases
+    Release {
+        #[clap(subcommand)]
+        cmd: ReleaseCmd,
+    },
+    /// Managing the local chain for development
+    Localnet {
+        #[clap(subcommand)]
+        cmd: LocalnetCmd,
+    },
+    /// Creating and managing Osmosis tokenfactory denoms
+    Tokenfactory {
+        #[clap(subcommand)]
+        cmd: TokenfactoryCmd,
+    },
+    /// Querying and transferring cw20 tokens of any contract
+    Cw20 {
+        #[clap(subcommand)]
+        cmd: Cw20Cmd,
+    },
+    /// Querying and transferring cw721 tokens of any contract
+    Cw721 {
+        #[clap(subcommand)]
+        cmd: Cw721Cmd,
+    },
+    /// Load testing contracts on local networks
+    Bench {
+        #[clap(subcommand)]
+        cmd: BenchCmd,
+    },
+    /// Showing and verifying the tamper-evident log of every transaction signed from this project
+    AuditLog {
+        #[clap(subcommand)]
+        cmd: AuditLogCmd,
+    },
+    /// Composing and broadcasting transactions
+    Tx {
+        #[clap(subcommand)]
+        cmd: TxCmd,
+    },
+    /// Live dashboard of deployed contracts with their code ids, admins, balances and last activity
+    Dashboard {
+        /// Networks to show, all networks with deployed contracts if not set
+        #[clap(short, long)]
+        network: Vec<String>,
+
+        /// Number of seconds between refreshes
+        #[clap(long, default_value = "10")]
+        interval: u64,
+    },
+    /// Managing the beaker installation itself
+    #[clap(name = "self")]
+    SelfManage {
+        #[clap(subcommand)]
+        cmd: SelfCmd,
+    },
     /// Launch interactive console for interacting with the project
     Console {
         #[clap(short, long, default_value = "local")]
         network: String,
     },
+    /// Serve query, execute, deploy and state operations as json-rpc over HTTP on localhost,
+    /// keeping connections and keys warm between requests
+    Serve {
+        /// Port to listen on, only bound to 127.0.0.1
+        #[clap(short, long, default_value = "4545")]
+        port: u16,
+
+        /// Token required as `Authorization: Bearer <token>` on every request, defaults to `BEAKER_SERVE_TOKEN`,
+        /// generated and printed at startup if neither is set
+        #[clap(long)]
+        token: Option<String>,
+
+        /// Broadcast execute and deploy requests to networks with `production = true`,
+        /// which are refused otherwise since nobody confirms the txs
+        #[clap(long)]
+        allow_production: bool,
+    },
+    /// Generate shell completion script, including networks, accounts and contracts of the current project
+    Completion {
+        /// Shell to generate completion script for
+        #[clap(arg_enum, required_unless_present = "list")]
+        shell: Option<clap_complete::Shell>,
+
+        /// List completion candidates from the project's config and state, used by the completion script
+        #[clap(long, arg_enum, hide = true)]
+        list: Option<CompletionList>,
+    },
 }
 
 #[derive(Serialize, Deserialize, GetDataDocs)]
@@ -118,6 +337,15 @@ fn console(network: &str) -> Result<()> {
     )
 }
 
+fn completion(shell: &Option<clap_complete::Shell>, list: &Option<CompletionList>) -> Result<()> {
+    if let Some(list) = list {
+        println!("{}", list_candidates(&WasmContext::new(), list).join("\n"));
+    } else if let Some(shell) = shell {
+        print!("{}", completion_script(*shell, &mut Cli::command()));
+    }
+    Ok(())
+}
+
 #[cfg(debug_assertions)]
 fn beaker_console() -> String {
     "beaker-console".to_string()
@@ -133,7 +361,17 @@ context!(
     WasmContext, config = { wasm: WasmConfig };
     WorkspaceContext, config = { workspace: WorkspaceConfig };
     ConsoleContext, config = { console: ConsoleConfig };
-    KeyContext, config = { key: KeyConfig }
+    KeyContext, config = { key: KeyConfig };
+    AccountContext, config = { account: AccountConfig };
+    AuthzContext, config = { authz: AuthzConfig };
+    GovContext, config = { gov: GovConfig };
+    StateContext, config = { state: StateConfig };
+    TestContext, config = { test: TestConfig };
+    ReleaseContext, config = { release: ReleaseConfig };
+    LocalnetContext, config = { localnet: LocalnetConfig };
+    TokenfactoryContext, config = { tokenfactory: TokenfactoryConfig };
+    CwContext, config = { cw: CwConfig };
+    TxContext, config = { tx: TxConfig }
 );
 
 pub fn execute(cmd: &Commands) -> Result<()> {
@@ -141,7 +379,41 @@ pub fn execute(cmd: &Commands) -> Result<()> {
         Commands::Wasm { cmd } => WasmModule::execute(WasmContext::new(), cmd),
         Commands::Workspace(cmd) => WorkspaceModule::execute(WorkspaceContext::new(), cmd),
         Commands::Console { network } => console(network),
+        Commands::Serve {
+            port,
+            token,
+            allow_production,
+        } => modules::serve::serve(
+            &WasmContext::new(),
+            *port,
+            token
+                .clone()
+                .or_else(|| std::env::var("BEAKER_SERVE_TOKEN").ok())
+                .as_ref(),
+            *allow_production,
+        ),
+        Commands::Dashboard { network, interval } => {
+            modules::dashboard::dashboard(&WasmContext::new(), network, *interval).map(|_| ())
+        }
+        Commands::Completion { shell, list } => completion(shell, list),
         Commands::Key { cmd } => KeyModule::execute(KeyContext::new(), cmd),
+        Commands::Account { cmd } => AccountModule::execute(AccountContext::new(), cmd),
+        Commands::Authz { cmd } => AuthzModule::execute(AuthzContext::new(), cmd),
+        Commands::Gov { cmd } => GovModule::execute(GovContext::new(), cmd),
+        Commands::State { cmd } => StateModule::execute(StateContext::new(), cmd),
+        Commands::Test { cmd } => TestModule::execute(TestContext::new(), cmd),
+        Commands::Deploy { cmd } => modules::deploy::execute(&WasmContext::new(), cmd),
+        Commands::Release { cmd } => ReleaseModule::execute(ReleaseContext::new(), cmd),
+        Commands::Localnet { cmd } => LocalnetModule::execute(LocalnetContext::new(), cmd),
+        Commands::Tokenfactory { cmd } => {
+            TokenfactoryModule::execute(TokenfactoryContext::new(), cmd)
+        }
+        Commands::Cw20 { cmd } => Cw20Module::execute(CwContext::new(), cmd),
+        Commands::Cw721 { cmd } => Cw721Module::execute(CwContext::new(), cmd),
+        Commands::Tx { cmd } => TxModule::execute(TxContext::new(), cmd),
+        Commands::Bench { cmd } => modules::bench::execute(&WasmContext::new(), cmd),
+        Commands::AuditLog { cmd } => modules::audit_log::execute(&WasmContext::new(), cmd),
+        Commands::SelfManage { cmd } => modules::self_update::execute(cmd),
     }
 }
 
@@ -186,6 +458,8 @@ contract_dir = "whatever""#;
                 contract_name: "counter".to_string(),
                 target_dir: None,
                 version: None,
+                ibc: false,
+                generator_args: vec![],
             },
         })
         .unwrap();
@@ -196,4 +470,15 @@ contract_dir = "whatever""#;
         temp.child("dapp/whatever/counter/Cargo.toml")
             .assert(predicate::path::exists());
     }
+
+    #[test]
+    fn accept_yes_before_and_after_subcommand() {
+        for args in [
+            vec!["beaker", "-y", "key", "delete", "deployer"],
+            vec!["beaker", "key", "delete", "deployer", "-y"],
+            vec!["beaker", "wasm", "deploy", "counter", "--yes"],
+        ] {
+            assert!(Cli::try_parse_from(&args).is_ok(), "{args:?}");
+        }
+    }
 }
@@ -1,7 +1,8 @@
-use beaker::{execute, Cli};
-use clap::Parser;
+use beaker::{exit_code, report, Cli};
 
-fn main() -> Result<(), anyhow::Error> {
-    let cli = Cli::parse();
-    execute(&cli.command)
+fn main() {
+    if let Err(err) = Cli::parse_with_alias().and_then(|cli| cli.run()) {
+        report(&err);
+        std::process::exit(exit_code(&err));
+    }
 }
@@ -0,0 +1,15 @@
+use config::Map;
+use data_doc_derive::GetDataDocs;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::support::coin::DenomMetadata;
+
+#[derive(Serialize, Deserialize, GetDataDocs, Default)]
+pub struct AccountConfig {
+    /// Display unit of base denoms, eg. `"ibc/27394FB..." = { display = "ATOM", exponent = 6 }`.
+    /// Well-known denoms like `uosmo` are displayed in their display denom if not specified,
+    /// other denoms are displayed as is.
+    #[serde(default)]
+    pub denom_metadata: Map<String, DenomMetadata>,
+}
@@ -0,0 +1,86 @@
+use super::config::AccountConfig;
+use super::ops;
+use crate::framework::{Context, Module};
+use crate::modules::wasm::args::BaseTxArgs;
+use crate::support::gas::Gas;
+use anyhow::Result;
+use clap::Subcommand;
+
+#[derive(Subcommand, Debug)]
+pub enum AccountCmd {
+    /// List balances of all denoms held by the account
+    Balance {
+        /// Name of predefined account, address or address book entry (eg. `@treasury`)
+        account: String,
+
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Send coins from the signer
+    Send {
+        /// Recipient, name of predefined account, address or address book entry (eg. `@treasury`)
+        to: String,
+
+        /// Coins to send, eg. `1000uosmo` or `1000uosmo,20uion`
+        amount: String,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// List denoms that fee can be paid in, eg. IBC denoms accepted through fee abstraction,
+    /// with gas price equivalent to the configured `gas_price`
+    FeeTokens {
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+}
+
+pub struct AccountModule {}
+
+impl<'a> Module<'a, AccountConfig, AccountCmd, anyhow::Error> for AccountModule {
+    fn execute<Ctx: Context<'a, AccountConfig>>(
+        ctx: Ctx,
+        cmd: &AccountCmd,
+    ) -> Result<(), anyhow::Error> {
+        match cmd {
+            AccountCmd::Balance { account, network } => {
+                ops::balance(&ctx, account, network)?;
+                Ok(())
+            }
+            AccountCmd::FeeTokens { network } => {
+                ops::fee_tokens(&ctx, network)?;
+                Ok(())
+            }
+            AccountCmd::Send {
+                to,
+                amount,
+                base_tx_args,
+            } => {
+                let BaseTxArgs {
+                    network,
+                    signer_args,
+                    gas_args,
+                    timeout_height,
+                }: &BaseTxArgs = base_tx_args;
+
+                ops::send(
+                    &ctx,
+                    to,
+                    amount,
+                    network,
+                    {
+                        let global_conf = ctx.global_config()?;
+                        &Gas::from_args(
+                            gas_args,
+                            global_conf.network_gas_price(network),
+                            global_conf.gas_adjustment(),
+                        )?
+                    },
+                    timeout_height,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
+                )?;
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod config;
+pub mod entrypoint;
+pub mod ops;
+
+pub use crate::modules::account::config::AccountConfig;
+pub use crate::modules::account::entrypoint::{AccountCmd, AccountModule};
@@ -0,0 +1,249 @@
+use super::config::AccountConfig;
+use crate::attrs_format;
+use crate::framework::config::Network;
+use crate::framework::Context;
+use crate::support::address_book::resolve_account_address;
+use crate::support::coin::{display_coin_with_metadata, Coins};
+use crate::support::fee_abstraction::convert_gas_price;
+use crate::support::future::block;
+use crate::support::gas::{Gas, GasPrice};
+use crate::support::gas_ledger::GasLedger;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::output;
+use anyhow::{anyhow, Context as _, Result};
+use cosmrs::bank::MsgSend;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::Msg;
+use cosmrs::AccountId;
+use serde::Serialize;
+
+/// Resolve predefined account name into its address, otherwise treat it as address or address book entry
+fn resolve_account<'a, Ctx: Context<'a, AccountConfig>>(
+    ctx: &Ctx,
+    network_info: &Network,
+    account: &str,
+) -> Result<AccountId> {
+    resolve_account_address(&ctx.global_config()?, network_info, account)
+}
+
+pub fn balance<'a, Ctx: Context<'a, AccountConfig>>(
+    ctx: &Ctx,
+    account: &str,
+    network: &str,
+) -> Result<BalanceResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let address = resolve_account(ctx, &network_info, account)?;
+    let denom_metadata = ctx.config()?.denom_metadata;
+
+    let balances = block(ctx.client(network_info).all_balances(address.to_string()))?
+        .into_iter()
+        .map(|(denom, amount)| Balance {
+            display: display_coin_with_metadata(&amount, &denom, &denom_metadata),
+            denom,
+            amount,
+        })
+        .collect();
+
+    let balance_response = BalanceResponse {
+        address: address.to_string(),
+        balances,
+    };
+    balance_response.log();
+
+    Ok(balance_response)
+}
+
+pub fn send<'a, Ctx: Context<'a, AccountConfig>>(
+    ctx: &Ctx,
+    to: &str,
+    amount: &str,
+    network: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<SendResponse> {
+    let global_config = ctx.global_config()?;
+    let account_prefix = global_config.account_prefix().as_str();
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let to_address = resolve_account(ctx, &network_info, to)?;
+    let coins = amount.parse::<Coins>()?;
+
+    let client = ctx
+        .client(network_info)
+        .to_signing_client(signing_key, account_prefix)
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, None));
+
+    let msg_send = MsgSend {
+        from_address: client.signer_account_id(),
+        to_address: to_address.clone(),
+        amount: coins.into(),
+    }
+    .to_any()
+    .map_err(|e| anyhow!(e))?;
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg_send], gas, "", timeout_height)
+            .await?;
+
+        let send_response = SendResponse {
+            tx_hash: response.hash.to_string(),
+            from: client.signer_account_id().to_string(),
+            to: to_address.to_string(),
+            amount: amount.to_string(),
+        };
+        send_response.log();
+
+        Ok(send_response)
+    })
+}
+
+#[derive(Serialize)]
+pub struct SendResponse {
+    pub tx_hash: String,
+    pub from: String,
+    pub to: String,
+    pub amount: String,
+}
+
+impl OpResponseDisplay for SendResponse {
+    fn headline() -> &'static str {
+        "Coins sent successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | tx_hash, from, to, amount }
+    }
+}
+
+/// Denoms fee can be paid in on `network`, with gas price equivalent to the configured `gas_price`
+pub fn fee_tokens<'a, Ctx: Context<'a, AccountConfig>>(
+    ctx: &Ctx,
+    network: &str,
+) -> Result<Vec<FeeTokenPrice>> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+    let gas_price = global_config
+        .network_gas_price(network)
+        .parse::<GasPrice>()?;
+
+    let mut fee_tokens = vec![FeeTokenPrice {
+        denom: gas_price.denom.to_string(),
+        gas_price: gas_price.amount,
+        source: FeeTokenSource::GasPrice,
+    }];
+    for price in network_info.fee_denoms() {
+        let price = price.parse::<GasPrice>()?;
+        fee_tokens.push(FeeTokenPrice {
+            denom: price.denom.to_string(),
+            gas_price: price.amount,
+            source: FeeTokenSource::FeeDenoms,
+        });
+    }
+
+    let client = ctx.client(network_info);
+    block(async {
+        for token in client.fee_tokens().await? {
+            if fee_tokens.iter().any(|t| t.denom == token.denom) {
+                continue;
+            }
+            let (base_denom, spot_price) = client.fee_token_spot_price(&token.denom).await?;
+            if base_denom != gas_price.denom.as_ref() {
+                continue;
+            }
+            fee_tokens.push(FeeTokenPrice {
+                gas_price: convert_gas_price(&gas_price, &token.denom, spot_price)?.amount,
+                denom: token.denom,
+                source: FeeTokenSource::Txfees,
+            });
+        }
+        anyhow::Ok(())
+    })?;
+
+    output::print(&fee_tokens, || {
+        fee_tokens
+            .iter()
+            .map(|t| {
+                let source = match t.source {
+                    FeeTokenSource::GasPrice => "gas_price",
+                    FeeTokenSource::FeeDenoms => "fee_denoms",
+                    FeeTokenSource::Txfees => "x/txfees spot price",
+                };
+                format!("{}{}  ({source})", t.gas_price, t.denom)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    Ok(fee_tokens)
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeTokenSource {
+    /// Global `gas_price`
+    GasPrice,
+    /// Network's `fee_denoms`
+    FeeDenoms,
+    /// Converted from `gas_price` by spot price of the fee token on chain
+    Txfees,
+}
+
+#[derive(Serialize)]
+pub struct FeeTokenPrice {
+    pub denom: String,
+    pub gas_price: f64,
+    pub source: FeeTokenSource,
+}
+
+#[derive(Serialize)]
+pub struct Balance {
+    pub denom: String,
+    pub amount: String,
+    pub display: String,
+}
+
+#[derive(Serialize)]
+pub struct BalanceResponse {
+    pub address: String,
+    pub balances: Vec<Balance>,
+}
+
+impl OpResponseDisplay for BalanceResponse {
+    fn headline() -> &'static str {
+        "Balances"
+    }
+    fn attrs(&self) -> Vec<String> {
+        let mut attrs = vec![format!("    ├── address: {}", self.address)];
+        if self.balances.is_empty() {
+            attrs.push("    └── balances: –".to_string());
+            return attrs;
+        }
+
+        attrs.push("    └── balances".to_string());
+        let last = self.balances.len() - 1;
+        attrs.extend(self.balances.iter().enumerate().map(|(i, b)| {
+            let branch = if i == last { "└──" } else { "├──" };
+            if b.display == format!("{}{}", b.amount, b.denom) {
+                format!("          {branch} {}", b.display)
+            } else {
+                format!("          {branch} {} ({}{})", b.display, b.amount, b.denom)
+            }
+        }));
+        attrs
+    }
+}
@@ -0,0 +1,44 @@
+pub mod ops;
+
+use anyhow::Result;
+use clap::Subcommand;
+
+use crate::framework::Context;
+use crate::modules::wasm::WasmConfig;
+
+#[derive(Subcommand, Debug)]
+pub enum AuditLogCmd {
+    /// Show transactions signed from this project, oldest first
+    Show {
+        /// Only show transactions on this chain id
+        #[clap(long)]
+        chain_id: Option<String>,
+
+        /// Only show transactions signed by this address
+        #[clap(long)]
+        signer: Option<String>,
+
+        /// Only show the last `n` matching transactions
+        #[clap(long)]
+        last: Option<usize>,
+    },
+    /// Check that no entry of the audit log is modified, removed or reordered
+    Verify,
+}
+
+pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(ctx: &Ctx, cmd: &AuditLogCmd) -> Result<()> {
+    match cmd {
+        AuditLogCmd::Show {
+            chain_id,
+            signer,
+            last,
+        } => {
+            ops::show(ctx, chain_id.as_deref(), signer.as_deref(), *last)?;
+            Ok(())
+        }
+        AuditLogCmd::Verify => {
+            ops::verify(ctx)?;
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use anyhow::{Context as _, Result};
+use serde::Serialize;
+
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::wasm::WasmConfig;
+use crate::support::audit_log::{self, AuditEntry, AUDIT_LOG_FILE};
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::output;
+use crate::support::state::STATE_DIR;
+
+fn log_path<'a, Ctx: Context<'a, WasmConfig>>(ctx: &Ctx) -> Result<PathBuf> {
+    Ok(ctx.root()?.join(STATE_DIR).join(AUDIT_LOG_FILE))
+}
+
+pub fn show<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    chain_id: Option<&str>,
+    signer: Option<&str>,
+    last: Option<usize>,
+) -> Result<Vec<AuditEntry>> {
+    let mut entries = audit_log::load(&log_path(ctx)?)?
+        .into_iter()
+        .filter(|e| chain_id.map_or(true, |c| e.chain_id == c))
+        .filter(|e| signer.map_or(true, |s| e.signer == s))
+        .collect::<Vec<_>>();
+    if let Some(last) = last {
+        entries = entries.split_off(entries.len().saturating_sub(last));
+    }
+
+    output::print(&entries, || {
+        if entries.is_empty() {
+            return "No transactions recorded".to_string();
+        }
+        entries
+            .iter()
+            .map(|e| {
+                let status = if e.code == 0 {
+                    "ok".to_string()
+                } else {
+                    format!("failed ({})", e.code)
+                };
+                let mut lines = vec![format!(
+                    "#{} {} {} by {} on {} from {}@{}",
+                    e.seq, e.tx_hash, status, e.signer, e.chain_id, e.user, e.host
+                )];
+                lines.extend(e.msgs.iter().map(|m| format!("    • {}", m.summary)));
+                lines.push(format!("    fee: {}", e.fee.join(", ")));
+                lines.join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    Ok(entries)
+}
+
+pub fn verify<'a, Ctx: Context<'a, WasmConfig>>(ctx: &Ctx) -> Result<VerifyAuditLogResponse> {
+    let path = log_path(ctx)?;
+    let entries = audit_log::load(&path)?;
+    let count = audit_log::verify(&entries)
+        .with_context(|| format!("Audit log `{}` is tampered with", path.display()))?;
+
+    let response = VerifyAuditLogResponse {
+        file: path.to_string_lossy().to_string(),
+        entries: count,
+        head: entries
+            .last()
+            .map(|e| e.hash.clone())
+            .unwrap_or_else(|| audit_log::GENESIS_HASH.to_string()),
+    };
+    response.log();
+    Ok(response)
+}
+
+#[derive(Serialize)]
+pub struct VerifyAuditLogResponse {
+    pub file: String,
+    pub entries: usize,
+    /// Hash of the last entry, keep it elsewhere to also detect truncation of the log
+    pub head: String,
+}
+
+impl OpResponseDisplay for VerifyAuditLogResponse {
+    fn headline() -> &'static str {
+        "Audit log is intact"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | file, entries, head }
+    }
+}
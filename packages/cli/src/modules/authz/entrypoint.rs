@@ -0,0 +1,71 @@
+use super::config::AuthzConfig;
+use super::ops;
+use crate::framework::{Context, Module};
+use crate::modules::wasm::args::BaseTxArgs;
+use crate::support::gas::Gas;
+use anyhow::Result;
+use clap::Subcommand;
+
+#[derive(Subcommand, Debug)]
+pub enum AuthzCmd {
+    /// Grant generic authorization for the grantee to execute a message type on behalf of the signer
+    Grant {
+        /// Address of the account that is allowed to execute on the signer's behalf
+        grantee: String,
+
+        /// Type url of the message that is allowed to be executed
+        #[clap(long, default_value = "/cosmwasm.wasm.v1.MsgExecuteContract")]
+        msg_type_url: String,
+
+        /// Number of seconds from now until the grant expires, the grant never expires if not specified
+        #[clap(long)]
+        expire_in: Option<u64>,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+}
+
+pub struct AuthzModule {}
+
+impl<'a> Module<'a, AuthzConfig, AuthzCmd, anyhow::Error> for AuthzModule {
+    fn execute<Ctx: Context<'a, AuthzConfig>>(
+        ctx: Ctx,
+        cmd: &AuthzCmd,
+    ) -> Result<(), anyhow::Error> {
+        match cmd {
+            AuthzCmd::Grant {
+                grantee,
+                msg_type_url,
+                expire_in,
+                base_tx_args,
+            } => {
+                let BaseTxArgs {
+                    network,
+                    signer_args,
+                    gas_args,
+                    timeout_height,
+                }: &BaseTxArgs = base_tx_args;
+
+                ops::grant(
+                    &ctx,
+                    grantee,
+                    msg_type_url,
+                    expire_in,
+                    network,
+                    {
+                        let global_conf = ctx.global_config()?;
+                        &Gas::from_args(
+                            gas_args,
+                            global_conf.network_gas_price(network),
+                            global_conf.gas_adjustment(),
+                        )?
+                    },
+                    timeout_height,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
+                )?;
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod config;
+pub mod entrypoint;
+pub mod ops;
+
+pub use crate::modules::authz::config::AuthzConfig;
+pub use crate::modules::authz::entrypoint::{AuthzCmd, AuthzModule};
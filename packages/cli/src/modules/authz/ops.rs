@@ -0,0 +1,90 @@
+use super::config::AuthzConfig;
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::support::address_book::resolve_address;
+use crate::support::authz::generic_grant;
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
+use crate::support::ops_response::OpResponseDisplay;
+use anyhow::{Context as _, Result};
+use cosmrs::crypto::secp256k1::SigningKey;
+use serde::Serialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[allow(clippy::too_many_arguments)]
+pub fn grant<'a, Ctx: Context<'a, AuthzConfig>>(
+    ctx: &Ctx,
+    grantee: &str,
+    msg_type_url: &str,
+    expire_in: &Option<u64>,
+    network: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<GrantResponse> {
+    let global_config = ctx.global_config()?;
+    let account_prefix = global_config.account_prefix().as_str();
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let grantee = resolve_address(&network_info, grantee)?;
+    let client = ctx
+        .client(network_info)
+        .to_signing_client(signing_key, account_prefix)
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, None));
+
+    let expiration = expire_in
+        .map(|secs| -> Result<Duration> {
+            Ok(SystemTime::now().duration_since(UNIX_EPOCH)? + Duration::from_secs(secs))
+        })
+        .transpose()?;
+
+    let msg_grant = generic_grant(
+        &client.signer_account_id(),
+        &grantee,
+        msg_type_url,
+        expiration,
+    )?;
+
+    block(async {
+        let _response = client
+            .sign_and_broadcast(vec![msg_grant], gas, "", timeout_height)
+            .await?;
+
+        let grant_response = GrantResponse {
+            granter: client.signer_account_id().to_string(),
+            grantee: grantee.to_string(),
+            msg_type_url: msg_type_url.to_string(),
+            expiration: expiration
+                .map(|d| format!("{} (unix)", d.as_secs()))
+                .unwrap_or_else(|| "–".to_string()),
+        };
+
+        grant_response.log();
+
+        Ok(grant_response)
+    })
+}
+
+#[allow(dead_code)]
+#[derive(Serialize)]
+pub struct GrantResponse {
+    pub granter: String,
+    pub grantee: String,
+    pub msg_type_url: String,
+    pub expiration: String,
+}
+
+impl OpResponseDisplay for GrantResponse {
+    fn headline() -> &'static str {
+        "Authorization granted successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | granter, grantee, msg_type_url, expiration }
+    }
+}
@@ -0,0 +1,388 @@
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use cosmrs::bank::MsgSend;
+use cosmrs::cosmwasm::MsgExecuteContract;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::{self, Msg};
+use cosmrs::{AccountId, Coin};
+use serde::Serialize;
+use tokio::time::Instant;
+
+use crate::framework::config::NetworkVariant;
+use crate::framework::Context;
+use crate::modules::wasm::WasmConfig;
+use crate::support::coin::Coins;
+use crate::support::cosmos::SigningClient;
+use crate::support::future::block;
+use crate::support::gas::{Gas, GasPrice};
+use crate::support::msg::msg_bytes;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::progress::Progress;
+use crate::support::state::State;
+
+/// Balance given to each generated account when `--fund` is not specified, in the gas price denom
+const DEFAULT_FUND: u128 = 1_000_000_000;
+
+/// Number of distinct errors listed in the report
+const MAX_REPORTED_ERRORS: usize = 5;
+
+/// How hard to load the contract
+pub struct Load {
+    /// Txs per second across all accounts
+    pub rate: f64,
+    /// Txs to send in total
+    pub count: usize,
+    /// Accounts sending txs concurrently
+    pub accounts: usize,
+}
+
+/// Outcome of a single tx
+struct Sample {
+    latency: Duration,
+    gas_used: u64,
+    error: Option<String>,
+}
+
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+pub struct Distribution {
+    pub min: u64,
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub max: u64,
+}
+
+impl Distribution {
+    /// Nearest-rank percentiles of `values`, all zero if there are none
+    fn of(mut values: Vec<u64>) -> Self {
+        if values.is_empty() {
+            return Distribution::default();
+        }
+        values.sort_unstable();
+        let percentile = |p: usize| values[((values.len() * p + 99) / 100).max(1) - 1];
+        Distribution {
+            min: values[0],
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: values[values.len() - 1],
+        }
+    }
+}
+
+impl std::fmt::Display for Distribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {} / p50 {} / p90 {} / p99 {} / max {}",
+            self.min, self.p50, self.p90, self.p99, self.max
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    raw: Option<&String>,
+    funds: Coins,
+    load: &Load,
+    fund: Option<&str>,
+    network: &str,
+    timeout_height: &u32,
+    gas: &Gas,
+    signing_key: SigningKey,
+) -> Result<BenchResponse> {
+    let global_config = ctx.global_config()?;
+    let account_prefix = global_config.account_prefix().as_str();
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    if *network_info.network_variant() != NetworkVariant::Local {
+        bail!("`beaker bench` only runs against local networks, `{network}` is shared");
+    }
+    if load.rate <= 0.0 || load.count == 0 || load.accounts == 0 {
+        bail!("`--rate`, `--count` and `--accounts` must be greater than 0");
+    }
+
+    let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
+    let contract = state
+        .get_ref(network, contract_name)?
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?
+        .parse::<AccountId>()
+        .map_err(|e| anyhow!(e))?;
+    let msg = msg_bytes(
+        raw,
+        ctx.root()?
+            .join("contracts")
+            .join(contract_name)
+            .join("execute-msgs")
+            .join(format!("{label}.json")),
+        "execute",
+    )?;
+    let funds: Vec<Coin> = funds.into();
+    let fund: Vec<Coin> = match fund {
+        Some(fund) => fund.parse::<Coins>()?,
+        None => format!(
+            "{DEFAULT_FUND}{}",
+            global_config
+                .network_gas_price(network)
+                .parse::<GasPrice>()?
+                .denom
+        )
+        .parse::<Coins>()?,
+    }
+    .into();
+
+    let client = ctx.client(network_info);
+    let funder = client.to_signing_client(signing_key, account_prefix);
+    let senders = (0..load.accounts)
+        .map(|_| client.to_signing_client(SigningKey::random(), account_prefix))
+        .collect::<Vec<SigningClient>>();
+
+    let execute_msg = |sender: &SigningClient| {
+        MsgExecuteContract {
+            sender: sender.signer_account_id(),
+            contract: contract.clone(),
+            msg: msg.clone(),
+            funds: funds.clone(),
+        }
+        .to_any()
+        .map_err(|e| anyhow!(e))
+    };
+
+    let (samples, elapsed) = block(async {
+        let sends = senders
+            .iter()
+            .map(|sender| {
+                MsgSend {
+                    from_address: funder.signer_account_id(),
+                    to_address: sender.signer_account_id(),
+                    amount: fund.clone(),
+                }
+                .to_any()
+                .map_err(|e| anyhow!(e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        funder
+            .sign_and_broadcast(sends, gas, "", timeout_height)
+            .await
+            .with_context(|| "Unable to fund generated accounts")?;
+
+        let accounts =
+            futures::future::try_join_all(senders.iter().map(|s| s.signer_account())).await?;
+
+        // every tx carries the same msg, so the fee estimated once is reused for all of them
+        let fee = senders[0]
+            .estimate_fee(
+                gas.clone(),
+                &accounts[0],
+                tx::Body::new(vec![execute_msg(&senders[0])?], "", *timeout_height),
+            )
+            .await?;
+
+        let progress = Progress::spinner(&format!(
+            "Sending {} txs from {} accounts at {} tx/s",
+            load.count, load.accounts, load.rate
+        ));
+        let next = Cell::new(0usize);
+        let samples = RefCell::new(Vec::with_capacity(load.count));
+        let interval = Duration::from_secs_f64(1.0 / load.rate);
+        let start = Instant::now();
+
+        let workers = senders.iter().zip(accounts).map(|(sender, mut account)| {
+            let (next, samples, fee, client) = (&next, &samples, &fee, &client);
+            async move {
+                let msg = execute_msg(sender)?;
+                loop {
+                    let i = next.get();
+                    if i >= load.count {
+                        return anyhow::Ok(());
+                    }
+                    next.set(i + 1);
+                    tokio::time::sleep_until(start + interval.mul_f64(i as f64)).await;
+
+                    let tx_body = tx::Body::new(vec![msg.clone()], "", *timeout_height);
+                    let sent = Instant::now();
+                    let result = sender
+                        .broadcast_unattended(&tx_body, fee.clone(), &account)
+                        .await;
+                    let latency = sent.elapsed();
+
+                    let (sample, sequence_used) = match result {
+                        Ok(response) if response.check_tx.code.is_err() => (
+                            Sample {
+                                latency,
+                                gas_used: 0,
+                                error: Some(response.check_tx.log.to_string()),
+                            },
+                            false,
+                        ),
+                        Ok(response) => (
+                            Sample {
+                                latency,
+                                gas_used: response.deliver_tx.gas_used.value(),
+                                error: response
+                                    .deliver_tx
+                                    .code
+                                    .is_err()
+                                    .then(|| response.deliver_tx.log.to_string()),
+                            },
+                            true,
+                        ),
+                        Err(e) => (
+                            Sample {
+                                latency,
+                                gas_used: 0,
+                                error: Some(format!("{e:#}")),
+                            },
+                            false,
+                        ),
+                    };
+                    if sequence_used {
+                        account.sequence += 1;
+                    } else {
+                        // the tx may or may not have reached the mempool, so the sequence is queried again
+                        account = client
+                            .account(sender.signer_account_id().as_ref())
+                            .await
+                            .unwrap_or(account);
+                    }
+                    samples.borrow_mut().push(sample);
+                }
+            }
+        });
+        let result = futures::future::try_join_all(workers).await;
+        let elapsed = start.elapsed();
+        progress.end_with(result)?;
+
+        anyhow::Ok((samples.into_inner(), elapsed))
+    })?;
+
+    let succeeded = samples.iter().filter(|s| s.error.is_none()).count();
+    let mut errors = BTreeMap::<&str, usize>::new();
+    for error in samples.iter().filter_map(|s| s.error.as_deref()) {
+        *errors.entry(error).or_default() += 1;
+    }
+    let mut errors = errors.into_iter().collect::<Vec<_>>();
+    errors.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let response = BenchResponse {
+        contract_address: contract.to_string(),
+        accounts: load.accounts,
+        sent: samples.len(),
+        succeeded,
+        failed: samples.len() - succeeded,
+        duration_secs: elapsed.as_secs_f64(),
+        target_rate: load.rate,
+        throughput: succeeded as f64 / elapsed.as_secs_f64(),
+        latency_ms: Distribution::of(
+            samples
+                .iter()
+                .map(|s| s.latency.as_millis() as u64)
+                .collect(),
+        ),
+        gas_used: Distribution::of(
+            samples
+                .iter()
+                .filter(|s| s.error.is_none())
+                .map(|s| s.gas_used)
+                .collect(),
+        ),
+        errors: errors
+            .into_iter()
+            .take(MAX_REPORTED_ERRORS)
+            .map(|(error, n)| format!("{n}x {error}"))
+            .collect(),
+    };
+    response.log();
+    Ok(response)
+}
+
+#[derive(Serialize, Debug)]
+pub struct BenchResponse {
+    pub contract_address: String,
+    pub accounts: usize,
+    pub sent: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub duration_secs: f64,
+    /// Txs per second that were requested
+    pub target_rate: f64,
+    /// Successful txs per second that were achieved
+    pub throughput: f64,
+    /// Time from broadcast until the tx is committed
+    pub latency_ms: Distribution,
+    /// Gas used by successful txs
+    pub gas_used: Distribution,
+    /// Most frequent errors with their number of occurrences
+    pub errors: Vec<String>,
+}
+
+impl OpResponseDisplay for BenchResponse {
+    fn headline() -> &'static str {
+        "Benchmark finished"
+    }
+    fn attrs(&self) -> Vec<String> {
+        let mut attrs = vec![
+            format!("    ├── contract_address: {}", self.contract_address),
+            format!("    ├── accounts: {}", self.accounts),
+            format!(
+                "    ├── txs: {} sent, {} succeeded, {} failed in {:.1}s",
+                self.sent, self.succeeded, self.failed, self.duration_secs
+            ),
+            format!(
+                "    ├── throughput: {:.2} tx/s (target {} tx/s)",
+                self.throughput, self.target_rate
+            ),
+            format!("    ├── latency_ms: {}", self.latency_ms),
+        ];
+        if self.errors.is_empty() {
+            attrs.push(format!("    └── gas_used: {}", self.gas_used));
+        } else {
+            attrs.push(format!("    ├── gas_used: {}", self.gas_used));
+            attrs.push("    └── errors:".to_string());
+            attrs.extend(self.errors.iter().map(|e| format!("          • {e}")));
+        }
+        attrs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        assert_eq!(Distribution::of(vec![]), Distribution::default());
+        assert_eq!(
+            Distribution::of((1..=100).rev().collect()),
+            Distribution {
+                min: 1,
+                p50: 50,
+                p90: 90,
+                p99: 99,
+                max: 100,
+            }
+        );
+        assert_eq!(
+            Distribution::of(vec![7]),
+            Distribution {
+                min: 7,
+                p50: 7,
+                p90: 7,
+                p99: 7,
+                max: 7,
+            }
+        );
+    }
+}
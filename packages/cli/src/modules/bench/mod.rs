@@ -0,0 +1,93 @@
+pub mod execute;
+
+use anyhow::Result;
+use clap::Subcommand;
+
+use crate::framework::Context;
+use crate::modules::wasm::args::{BaseTxArgs, MsgArgs};
+use crate::modules::wasm::WasmConfig;
+use crate::support::gas::Gas;
+
+#[derive(Subcommand, Debug)]
+pub enum BenchCmd {
+    /// Send execute msgs to a contract from a pool of generated, funded accounts at a steady rate,
+    /// then report throughput, latency and gas used
+    Execute {
+        contract_name: String,
+
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        #[clap(flatten)]
+        msg_args: MsgArgs,
+
+        /// Funds sent with every msg
+        #[clap(short, long)]
+        funds: Option<String>,
+
+        /// Txs per second to send across all accounts
+        #[clap(long, default_value = "10")]
+        rate: f64,
+
+        /// Number of txs to send in total
+        #[clap(long, default_value = "100")]
+        count: usize,
+
+        /// Number of generated accounts, each sends its txs one after another
+        #[clap(long, default_value = "10")]
+        accounts: usize,
+
+        /// Coins the signer sends to each generated account, defaults to `1000000000` of the gas price denom
+        #[clap(long)]
+        fund: Option<String>,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+}
+
+pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(ctx: &Ctx, cmd: &BenchCmd) -> Result<()> {
+    match cmd {
+        BenchCmd::Execute {
+            contract_name,
+            label,
+            msg_args,
+            funds,
+            rate,
+            count,
+            accounts,
+            fund,
+            base_tx_args,
+        } => {
+            let BaseTxArgs {
+                network,
+                signer_args,
+                gas_args,
+                timeout_height,
+            } = base_tx_args;
+            let global_config = ctx.global_config()?;
+            execute::execute(
+                ctx,
+                contract_name,
+                label,
+                msg_args.raw_msg()?.as_ref(),
+                funds.as_deref().try_into()?,
+                &execute::Load {
+                    rate: *rate,
+                    count: *count,
+                    accounts: *accounts,
+                },
+                fund.as_deref(),
+                network,
+                timeout_height,
+                &Gas::from_args(
+                    gas_args,
+                    global_config.network_gas_price(network),
+                    global_config.gas_adjustment(),
+                )?,
+                signer_args.private_key(&ctx.chain_client(), &global_config, network)?,
+            )?;
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use super::config::CwConfig;
+use super::ops;
+use crate::framework::{Context, Module};
+use crate::modules::wasm::args::BaseTxArgs;
+use crate::support::gas::Gas;
+use anyhow::Result;
+use clap::Subcommand;
+use cosmrs::crypto::secp256k1::SigningKey;
+
+#[derive(Subcommand, Debug)]
+pub enum Cw20Cmd {
+    /// Query cw20 balance of an address
+    Balance {
+        /// Token contract, as address, address book entry (eg. `@usdc`) or contract name in state
+        contract: String,
+
+        /// Address or address book entry to query balance of
+        address: String,
+
+        /// Label of the token contract when referenced by contract name
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Transfer cw20 tokens from the signer
+    Transfer {
+        /// Token contract, as address, address book entry (eg. `@usdc`) or contract name in state
+        contract: String,
+
+        /// Recipient address or address book entry
+        recipient: String,
+
+        /// Amount in base units, eg. `1000000`
+        amount: String,
+
+        /// Transfer tokens of the granter via `MsgExec` instead, requires prior authz grant to the signer
+        #[clap(long = "as", value_name = "GRANTER")]
+        granter: Option<String>,
+
+        /// Label of the token contract when referenced by contract name
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Mint cw20 tokens, the signer must be the minter
+    Mint {
+        /// Token contract, as address, address book entry (eg. `@usdc`) or contract name in state
+        contract: String,
+
+        /// Amount in base units, eg. `1000000`
+        amount: String,
+
+        /// Recipient address or address book entry, the signer if not specified
+        #[clap(long)]
+        to: Option<String>,
+
+        /// Label of the token contract when referenced by contract name
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Cw721Cmd {
+    /// Query owner and approvals of a cw721 token
+    OwnerOf {
+        /// Nft contract, as address, address book entry (eg. `@collection`) or contract name in state
+        contract: String,
+
+        token_id: String,
+
+        /// Label of the nft contract when referenced by contract name
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Transfer a cw721 token owned by the signer
+    Transfer {
+        /// Nft contract, as address, address book entry (eg. `@collection`) or contract name in state
+        contract: String,
+
+        /// Recipient address or address book entry
+        recipient: String,
+
+        token_id: String,
+
+        /// Transfer a token owned by the granter via `MsgExec` instead, requires prior authz grant to the signer
+        #[clap(long = "as", value_name = "GRANTER")]
+        granter: Option<String>,
+
+        /// Label of the nft contract when referenced by contract name
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+}
+
+/// Gas and signer of tx commands
+fn tx_setup<'a, Ctx: Context<'a, CwConfig>>(
+    ctx: &Ctx,
+    base_tx_args: &BaseTxArgs,
+) -> Result<(Gas, SigningKey)> {
+    let global_conf = ctx.global_config()?;
+    let gas = Gas::from_args(
+        &base_tx_args.gas_args,
+        global_conf.network_gas_price(&base_tx_args.network),
+        global_conf.gas_adjustment(),
+    )?;
+    let signing_key = base_tx_args.signer_args.private_key(
+        &ctx.chain_client(),
+        &global_conf,
+        &base_tx_args.network,
+    )?;
+    Ok((gas, signing_key))
+}
+
+pub struct Cw20Module {}
+
+impl<'a> Module<'a, CwConfig, Cw20Cmd, anyhow::Error> for Cw20Module {
+    fn execute<Ctx: Context<'a, CwConfig>>(ctx: Ctx, cmd: &Cw20Cmd) -> Result<(), anyhow::Error> {
+        match cmd {
+            Cw20Cmd::Balance {
+                contract,
+                address,
+                label,
+                network,
+            } => {
+                ops::cw20_balance(&ctx, contract, label, address, network)?;
+            }
+            Cw20Cmd::Transfer {
+                contract,
+                recipient,
+                amount,
+                granter,
+                label,
+                base_tx_args,
+            } => {
+                let (gas, signing_key) = tx_setup(&ctx, base_tx_args)?;
+                ops::cw20_transfer(
+                    &ctx,
+                    contract,
+                    label,
+                    recipient,
+                    amount,
+                    granter.as_deref(),
+                    &base_tx_args.network,
+                    &gas,
+                    &base_tx_args.timeout_height,
+                    signing_key,
+                )?;
+            }
+            Cw20Cmd::Mint {
+                contract,
+                amount,
+                to,
+                label,
+                base_tx_args,
+            } => {
+                let (gas, signing_key) = tx_setup(&ctx, base_tx_args)?;
+                ops::cw20_mint(
+                    &ctx,
+                    contract,
+                    label,
+                    to.as_deref(),
+                    amount,
+                    &base_tx_args.network,
+                    &gas,
+                    &base_tx_args.timeout_height,
+                    signing_key,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct Cw721Module {}
+
+impl<'a> Module<'a, CwConfig, Cw721Cmd, anyhow::Error> for Cw721Module {
+    fn execute<Ctx: Context<'a, CwConfig>>(ctx: Ctx, cmd: &Cw721Cmd) -> Result<(), anyhow::Error> {
+        match cmd {
+            Cw721Cmd::OwnerOf {
+                contract,
+                token_id,
+                label,
+                network,
+            } => {
+                ops::cw721_owner_of(&ctx, contract, label, token_id, network)?;
+            }
+            Cw721Cmd::Transfer {
+                contract,
+                recipient,
+                token_id,
+                granter,
+                label,
+                base_tx_args,
+            } => {
+                let (gas, signing_key) = tx_setup(&ctx, base_tx_args)?;
+                ops::cw721_transfer(
+                    &ctx,
+                    contract,
+                    label,
+                    recipient,
+                    token_id,
+                    granter.as_deref(),
+                    &base_tx_args.network,
+                    &gas,
+                    &base_tx_args.timeout_height,
+                    signing_key,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
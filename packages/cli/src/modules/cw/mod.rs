@@ -0,0 +1,6 @@
+pub mod config;
+pub mod entrypoint;
+pub mod ops;
+
+pub use crate::modules::cw::config::CwConfig;
+pub use crate::modules::cw::entrypoint::{Cw20Cmd, Cw20Module, Cw721Cmd, Cw721Module};
@@ -0,0 +1,439 @@
+use super::config::CwConfig;
+use crate::attrs_format;
+use crate::framework::config::Network;
+use crate::framework::Context;
+use crate::support::address_book::resolve_address;
+use crate::support::authz::wrap_exec;
+use crate::support::cosmos::{Client, SigningClient};
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::State;
+use anyhow::{anyhow, Context as _, Result};
+use cosmrs::cosmwasm::MsgExecuteContract;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::Msg;
+use cosmrs::AccountId;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+fn network_info<'a, Ctx: Context<'a, CwConfig>>(ctx: &Ctx, network: &str) -> Result<Network> {
+    let global_config = ctx.global_config()?;
+    Ok(global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned())
+}
+
+/// Resolve token contract, which is an address, address book entry (`@name`)
+/// or contract name with `label` in state
+fn resolve_contract<'a, Ctx: Context<'a, CwConfig>>(
+    ctx: &Ctx,
+    network_info: &Network,
+    network: &str,
+    contract: &str,
+    label: &str,
+) -> Result<AccountId> {
+    if contract.starts_with('@') || contract.parse::<AccountId>().is_ok() {
+        return resolve_address(network_info, contract);
+    }
+
+    State::load_by_network(network_info.clone(), ctx.root()?)?
+        .get_ref(network, contract)?
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract}:{label}"))?
+        .parse::<AccountId>()
+        .map_err(|e| anyhow!(e))
+}
+
+fn query<T: for<'de> Deserialize<'de>>(
+    client: &Client,
+    contract: &AccountId,
+    msg: Value,
+) -> Result<T> {
+    let res = block(client.query_smart(contract.to_string(), msg.to_string().into_bytes()))?;
+    serde_json::from_slice(&res)
+        .with_context(|| format!("Unexpected response of `{msg}` from {contract}"))
+}
+
+fn signing_client<'a, Ctx: Context<'a, CwConfig>>(
+    ctx: &Ctx,
+    network: &str,
+    signing_key: SigningKey,
+) -> Result<SigningClient> {
+    Ok(ctx
+        .client(network_info(ctx, network)?)
+        .to_signing_client(signing_key, ctx.global_config()?.account_prefix())
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, None)))
+}
+
+/// Execute `msg` on `contract` without funds, on behalf of `granter` via `MsgExec` if specified, returns tx hash
+fn execute(
+    client: &SigningClient,
+    contract: &AccountId,
+    msg: Value,
+    granter: Option<AccountId>,
+    gas: &Gas,
+    timeout_height: &u32,
+) -> Result<String> {
+    let msg = MsgExecuteContract {
+        sender: granter
+            .clone()
+            .unwrap_or_else(|| client.signer_account_id()),
+        contract: contract.clone(),
+        msg: msg.to_string().into_bytes(),
+        funds: vec![],
+    }
+    .to_any()
+    .map_err(|e| anyhow!(e))?;
+    let msg = if granter.is_some() {
+        wrap_exec(&client.signer_account_id(), vec![msg])?
+    } else {
+        msg
+    };
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg], gas, "", timeout_height)
+            .await?;
+        Ok(response.hash.to_string())
+    })
+}
+
+/// `Uint128` amount, serialized as string as cw20 expects
+fn parse_amount(amount: &str) -> Result<String> {
+    amount
+        .parse::<u128>()
+        .map(|a| a.to_string())
+        .with_context(|| format!("Invalid amount `{amount}`, expected an integer in base units"))
+}
+
+/// Amount in base units displayed with `decimals`, eg. `1500000` with 6 decimals as `1.5`
+fn display_amount(amount: &str, decimals: u32) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+    let padded = format!("{amount:0>width$}", width = decimals as usize + 1);
+    let (int, frac) = padded.split_at(padded.len() - decimals as usize);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        int.to_string()
+    } else {
+        format!("{int}.{frac}")
+    }
+}
+
+#[derive(Deserialize)]
+struct Cw20Balance {
+    balance: String,
+}
+
+#[derive(Deserialize)]
+struct Cw20TokenInfo {
+    symbol: String,
+    decimals: u32,
+}
+
+pub fn cw20_balance<'a, Ctx: Context<'a, CwConfig>>(
+    ctx: &Ctx,
+    contract: &str,
+    label: &str,
+    address: &str,
+    network: &str,
+) -> Result<Cw20BalanceResponse> {
+    let network_info = network_info(ctx, network)?;
+    let contract = resolve_contract(ctx, &network_info, network, contract, label)?;
+    let address = resolve_address(&network_info, address)?;
+
+    let client = ctx.client(network_info);
+    let balance: Cw20Balance = query(
+        &client,
+        &contract,
+        json!({ "balance": { "address": address.to_string() } }),
+    )?;
+    let token_info: Cw20TokenInfo = query(&client, &contract, json!({ "token_info": {} }))?;
+
+    let balance_response = Cw20BalanceResponse {
+        contract: contract.to_string(),
+        address: address.to_string(),
+        display: format!(
+            "{} {}",
+            display_amount(&balance.balance, token_info.decimals),
+            token_info.symbol
+        ),
+        balance: balance.balance,
+    };
+    balance_response.log();
+
+    Ok(balance_response)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn cw20_transfer<'a, Ctx: Context<'a, CwConfig>>(
+    ctx: &Ctx,
+    contract: &str,
+    label: &str,
+    recipient: &str,
+    amount: &str,
+    granter: Option<&str>,
+    network: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<Cw20TransferResponse> {
+    let network_info = network_info(ctx, network)?;
+    let contract = resolve_contract(ctx, &network_info, network, contract, label)?;
+    let recipient = resolve_address(&network_info, recipient)?;
+    let granter = granter
+        .map(|g| resolve_address(&network_info, g))
+        .transpose()?;
+    let amount = parse_amount(amount)?;
+
+    let tx_hash = execute(
+        &signing_client(ctx, network, signing_key)?,
+        &contract,
+        json!({ "transfer": { "recipient": recipient.to_string(), "amount": amount } }),
+        granter,
+        gas,
+        timeout_height,
+    )?;
+
+    let transfer_response = Cw20TransferResponse {
+        contract: contract.to_string(),
+        recipient: recipient.to_string(),
+        amount,
+        tx_hash,
+    };
+    transfer_response.log();
+
+    Ok(transfer_response)
+}
+
+/// Mint cw20 tokens, the signer must be the minter of the token
+#[allow(clippy::too_many_arguments)]
+pub fn cw20_mint<'a, Ctx: Context<'a, CwConfig>>(
+    ctx: &Ctx,
+    contract: &str,
+    label: &str,
+    recipient: Option<&str>,
+    amount: &str,
+    network: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<Cw20MintResponse> {
+    let network_info = network_info(ctx, network)?;
+    let contract = resolve_contract(ctx, &network_info, network, contract, label)?;
+    let client = signing_client(ctx, network, signing_key)?;
+    let recipient = match recipient {
+        Some(recipient) => resolve_address(&network_info, recipient)?.to_string(),
+        None => client.signer_account_id().to_string(),
+    };
+    let amount = parse_amount(amount)?;
+
+    let tx_hash = execute(
+        &client,
+        &contract,
+        json!({ "mint": { "recipient": recipient, "amount": amount } }),
+        None,
+        gas,
+        timeout_height,
+    )?;
+
+    let mint_response = Cw20MintResponse {
+        contract: contract.to_string(),
+        recipient,
+        amount,
+        tx_hash,
+    };
+    mint_response.log();
+
+    Ok(mint_response)
+}
+
+#[derive(Deserialize)]
+struct Cw721Owner {
+    owner: String,
+    #[serde(default)]
+    approvals: Vec<Cw721Approval>,
+}
+
+#[derive(Deserialize)]
+struct Cw721Approval {
+    spender: String,
+}
+
+pub fn cw721_owner_of<'a, Ctx: Context<'a, CwConfig>>(
+    ctx: &Ctx,
+    contract: &str,
+    label: &str,
+    token_id: &str,
+    network: &str,
+) -> Result<Cw721OwnerResponse> {
+    let network_info = network_info(ctx, network)?;
+    let contract = resolve_contract(ctx, &network_info, network, contract, label)?;
+
+    let owner: Cw721Owner = query(
+        &ctx.client(network_info),
+        &contract,
+        json!({ "owner_of": { "token_id": token_id } }),
+    )?;
+
+    let owner_response = Cw721OwnerResponse {
+        contract: contract.to_string(),
+        token_id: token_id.to_string(),
+        owner: owner.owner,
+        approvals: owner
+            .approvals
+            .into_iter()
+            .map(|a| a.spender)
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+    owner_response.log();
+
+    Ok(owner_response)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn cw721_transfer<'a, Ctx: Context<'a, CwConfig>>(
+    ctx: &Ctx,
+    contract: &str,
+    label: &str,
+    recipient: &str,
+    token_id: &str,
+    granter: Option<&str>,
+    network: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<Cw721TransferResponse> {
+    let network_info = network_info(ctx, network)?;
+    let contract = resolve_contract(ctx, &network_info, network, contract, label)?;
+    let recipient = resolve_address(&network_info, recipient)?;
+    let granter = granter
+        .map(|g| resolve_address(&network_info, g))
+        .transpose()?;
+
+    let tx_hash = execute(
+        &signing_client(ctx, network, signing_key)?,
+        &contract,
+        json!({ "transfer_nft": { "recipient": recipient.to_string(), "token_id": token_id } }),
+        granter,
+        gas,
+        timeout_height,
+    )?;
+
+    let transfer_response = Cw721TransferResponse {
+        contract: contract.to_string(),
+        recipient: recipient.to_string(),
+        token_id: token_id.to_string(),
+        tx_hash,
+    };
+    transfer_response.log();
+
+    Ok(transfer_response)
+}
+
+#[derive(Serialize)]
+pub struct Cw20BalanceResponse {
+    pub contract: String,
+    pub address: String,
+    pub balance: String,
+    pub display: String,
+}
+
+impl OpResponseDisplay for Cw20BalanceResponse {
+    fn headline() -> &'static str {
+        "Cw20 balance"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | contract, address, balance, display }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Cw20TransferResponse {
+    pub contract: String,
+    pub recipient: String,
+    pub amount: String,
+    pub tx_hash: String,
+}
+
+impl OpResponseDisplay for Cw20TransferResponse {
+    fn headline() -> &'static str {
+        "Cw20 tokens sent successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | contract, recipient, amount, tx_hash }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Cw20MintResponse {
+    pub contract: String,
+    pub recipient: String,
+    pub amount: String,
+    pub tx_hash: String,
+}
+
+impl OpResponseDisplay for Cw20MintResponse {
+    fn headline() -> &'static str {
+        "Cw20 tokens minted successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | contract, recipient, amount, tx_hash }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Cw721OwnerResponse {
+    pub contract: String,
+    pub token_id: String,
+    pub owner: String,
+    /// Spenders approved to transfer the token, comma separated
+    pub approvals: String,
+}
+
+impl OpResponseDisplay for Cw721OwnerResponse {
+    fn headline() -> &'static str {
+        "Cw721 token owner"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | contract, token_id, owner, approvals }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Cw721TransferResponse {
+    pub contract: String,
+    pub recipient: String,
+    pub token_id: String,
+    pub tx_hash: String,
+}
+
+impl OpResponseDisplay for Cw721TransferResponse {
+    fn headline() -> &'static str {
+        "Cw721 token transferred successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | contract, recipient, token_id, tx_hash }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_amount_with_decimals() {
+        assert_eq!(display_amount("1500000", 6), "1.5");
+        assert_eq!(display_amount("25", 6), "0.000025");
+        assert_eq!(display_amount("3000000", 6), "3");
+        assert_eq!(display_amount("42", 0), "42");
+        assert!(parse_amount("1.5").is_err());
+    }
+}
@@ -0,0 +1,369 @@
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use anyhow::{Context as _, Result};
+use console::{style, Key, Term};
+use cosmrs::rpc::query::{EventType, Query};
+use cosmrs::rpc::Order;
+use serde::Serialize;
+
+use crate::framework::config::Network;
+use crate::framework::Context;
+use crate::modules::wasm::ops::events::{contract_events, ContractEvent};
+use crate::modules::wasm::WasmConfig;
+use crate::support::cosmos::Client;
+use crate::support::future::block;
+use crate::support::output::{self, output_format, OutputFormat};
+use crate::support::state::State;
+
+/// Number of recent txs shown when drilling down into a contract
+const RECENT_TXS_LIMIT: u8 = 10;
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContractRow {
+    pub network: String,
+    pub contract: String,
+    pub label: String,
+    pub address: String,
+    pub code_id: Option<u64>,
+    pub admin: Option<String>,
+    pub balances: Vec<String>,
+    /// Height of the latest tx that emitted the contract's events
+    pub last_activity: Option<u64>,
+    /// Reason the chain could not be queried for this contract
+    pub error: Option<String>,
+}
+
+fn network_infos<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    networks: &[String],
+) -> Result<Vec<(String, Network)>> {
+    let global_config = ctx.global_config()?;
+    let names = if networks.is_empty() {
+        global_config.networks().keys().cloned().collect()
+    } else {
+        networks.to_vec()
+    };
+    names
+        .into_iter()
+        .map(|name| {
+            let network = global_config
+                .networks()
+                .get(&name)
+                .with_context(|| format!("Unable to find network config: {name}"))?
+                .to_owned();
+            Ok((name, network))
+        })
+        .collect()
+}
+
+/// Every instance recorded in state on `networks`, all networks with deployments if empty
+fn contract_rows<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    networks: &[(String, Network)],
+) -> Result<Vec<ContractRow>> {
+    let mut rows = vec![];
+    for (name, network) in networks {
+        let state = match State::load_by_network(network.clone(), ctx.root()?) {
+            Ok(state) => state,
+            Err(_) => continue,
+        };
+        for (contract, wasm_ref) in state.contracts(name) {
+            for (label, address) in wasm_ref.addresses() {
+                rows.push(ContractRow {
+                    network: name.clone(),
+                    contract: contract.clone(),
+                    label: label.clone(),
+                    address: address.clone(),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+    Ok(rows)
+}
+
+fn contract_query(address: &str) -> Query {
+    Query::from(EventType::Tx).and_eq("wasm._contract_address", address)
+}
+
+async fn fetch_row(client: &Client, row: &mut ContractRow) -> Result<()> {
+    let info = client.contract_info(row.address.clone()).await?;
+    row.code_id = Some(info.code_id);
+    row.admin = Some(info.admin).filter(|a| !a.is_empty());
+    row.balances = client
+        .all_balances(row.address.clone())
+        .await?
+        .into_iter()
+        .map(|(denom, amount)| format!("{amount}{denom}"))
+        .collect();
+
+    let res = client
+        .tx_search(contract_query(&row.address), 1, Order::Descending)
+        .await?;
+    row.last_activity = res.txs.first().map(|tx| tx.height.value());
+    Ok(())
+}
+
+/// Query code id, admin, balances and last activity of every row, a failing network only marks its rows
+fn refresh(clients: &[(String, Client)], rows: &mut [ContractRow]) -> Result<()> {
+    block(async {
+        for row in rows.iter_mut() {
+            if let Some((_, client)) = clients.iter().find(|(name, _)| name == &row.network) {
+                row.error = fetch_row(client, row).await.err().map(|e| format!("{e:#}"));
+            }
+        }
+        Ok(())
+    })
+}
+
+fn recent_events(client: &Client, address: &str) -> Result<Vec<ContractEvent>> {
+    block(async {
+        let res = client
+            .tx_search(contract_query(address), RECENT_TXS_LIMIT, Order::Descending)
+            .await?;
+        Ok(res
+            .txs
+            .iter()
+            .flat_map(|tx| {
+                contract_events(
+                    address,
+                    tx.height.value(),
+                    &tx.hash.to_string(),
+                    &tx.tx_result.events,
+                )
+            })
+            .collect())
+    })
+}
+
+fn or_dash<T: ToString>(value: &Option<T>) -> String {
+    value
+        .as_ref()
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "–".to_string())
+}
+
+/// Lines of the dashboard table, the `selected` row is marked
+pub fn render(rows: &[ContractRow], selected: usize) -> Vec<String> {
+    let cells = rows
+        .iter()
+        .map(|row| {
+            [
+                row.network.clone(),
+                format!("{}:{}", row.contract, row.label),
+                or_dash(&row.code_id),
+                or_dash(&row.admin),
+                if row.balances.is_empty() {
+                    "–".to_string()
+                } else {
+                    row.balances.join(",")
+                },
+                row.last_activity
+                    .map(|h| format!("#{h}"))
+                    .unwrap_or_else(|| "–".to_string()),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let header = [
+        "network",
+        "contract",
+        "code_id",
+        "admin",
+        "balances",
+        "last_activity",
+    ]
+    .map(str::to_string);
+    let widths = (0..header.len())
+        .map(|i| {
+            cells
+                .iter()
+                .chain(std::iter::once(&header))
+                .map(|c| c[i].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    let line = |cells: &[String; 6]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{c:<w$}", w = *w))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![format!("  {}", style(line(&header)).bold())];
+    for (i, (row, cells)) in rows.iter().zip(&cells).enumerate() {
+        let marker = if i == selected { "›" } else { " " };
+        let text = line(cells);
+        lines.push(match &row.error {
+            Some(e) => format!("{marker} {} {}", style(text).dim(), style(e).red()),
+            None if i == selected => format!("{marker} {}", style(text).cyan()),
+            None => format!("{marker} {text}"),
+        });
+    }
+    lines
+}
+
+fn draw(term: &Term, rows: &[ContractRow], selected: usize, interval: u64) -> Result<()> {
+    term.clear_screen()?;
+    term.write_line(&format!(
+        "  {} refreshing every {interval}s  ↑/↓ select  enter recent events  r refresh  q quit\n",
+        style("Beaker dashboard").green().bold()
+    ))?;
+    if rows.is_empty() {
+        term.write_line("  No deployed contracts found in state")?;
+    }
+    for line in render(rows, selected) {
+        term.write_line(&line)?;
+    }
+    Ok(())
+}
+
+fn draw_events(term: &Term, row: &ContractRow, events: Result<Vec<ContractEvent>>) -> Result<()> {
+    term.clear_screen()?;
+    term.write_line(&format!(
+        "  {} {}:{} on {} ({})  any key to go back\n",
+        style("Recent events of").green().bold(),
+        row.contract,
+        row.label,
+        row.network,
+        row.address
+    ))?;
+    match events {
+        Ok(events) if events.is_empty() => term.write_line("  No recent events")?,
+        Ok(events) => {
+            for event in events {
+                let attributes = event
+                    .attributes
+                    .iter()
+                    .filter(|(k, _)| k != "_contract_address")
+                    .map(|(k, v)| format!("{}={v}", style(k).dim()))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                term.write_line(&format!(
+                    "  {} {} {}: {attributes}",
+                    style(format!("#{}", event.height)).bold(),
+                    style(&event.tx_hash[..event.tx_hash.len().min(12)]).dim(),
+                    style(&event.r#type).cyan().bold(),
+                ))?;
+            }
+        }
+        Err(e) => term.write_line(&format!("  {}", style(format!("{e:#}")).red()))?,
+    }
+    Ok(())
+}
+
+/// Live dashboard of contracts deployed on `networks`, refreshed every `interval` seconds.
+/// Prints a single snapshot when not attended by a user or when output format is not table.
+pub fn dashboard<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    networks: &[String],
+    interval: u64,
+) -> Result<Vec<ContractRow>> {
+    let networks = network_infos(ctx, networks)?;
+    let mut rows = contract_rows(ctx, &networks)?;
+    let clients = networks
+        .iter()
+        .map(|(name, network)| (name.clone(), ctx.client(network.clone())))
+        .collect::<Vec<_>>();
+    refresh(&clients, &mut rows)?;
+
+    let term = Term::stdout();
+    if !term.is_term() || !matches!(output_format(), OutputFormat::Table) {
+        output::print(&rows, || render(&rows, usize::MAX).join("\n"));
+        return Ok(rows);
+    }
+
+    let (keys, key_events) = mpsc::channel();
+    std::thread::spawn(move || {
+        let term = Term::stdout();
+        while let Ok(key) = term.read_key() {
+            if keys.send(key).is_err() {
+                break;
+            }
+        }
+    });
+
+    let interval = interval.max(1);
+    let mut selected = 0;
+    term.hide_cursor()?;
+    let result = loop {
+        if let Err(e) = draw(&term, &rows, selected, interval) {
+            break Err(e);
+        }
+        match key_events.recv_timeout(Duration::from_secs(interval)) {
+            Ok(Key::Char('q')) | Ok(Key::Escape) => break Ok(()),
+            Ok(Key::Char('r')) | Err(RecvTimeoutError::Timeout) => {
+                if let Err(e) = refresh(&clients, &mut rows) {
+                    break Err(e);
+                }
+            }
+            Ok(Key::ArrowUp) | Ok(Key::Char('k')) => selected = selected.saturating_sub(1),
+            Ok(Key::ArrowDown) | Ok(Key::Char('j')) => {
+                selected = (selected + 1).min(rows.len().saturating_sub(1))
+            }
+            Ok(Key::Enter) => {
+                if let Some(row) = rows.get(selected) {
+                    let client = &clients
+                        .iter()
+                        .find(|(name, _)| name == &row.network)
+                        .expect("rows are collected from the networks")
+                        .1;
+                    if let Err(e) = draw_events(&term, row, recent_events(client, &row.address)) {
+                        break Err(e);
+                    }
+                    if key_events.recv().is_err() {
+                        break Ok(());
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(RecvTimeoutError::Disconnected) => break Ok(()),
+        }
+    };
+    term.show_cursor()?;
+    term.clear_screen()?;
+
+    result.map(|_| rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_rows_aligned() {
+        console::set_colors_enabled(false);
+        let rows = vec![
+            ContractRow {
+                network: "local".to_string(),
+                contract: "counter".to_string(),
+                label: "default".to_string(),
+                code_id: Some(1),
+                balances: vec!["100uosmo".to_string()],
+                last_activity: Some(42),
+                ..Default::default()
+            },
+            ContractRow {
+                network: "testnet".to_string(),
+                contract: "counter".to_string(),
+                label: "v2".to_string(),
+                error: Some("unreachable".to_string()),
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            render(&rows, 0),
+            vec![
+                "  network  contract         code_id  admin  balances  last_activity",
+                "› local    counter:default  1        –      100uosmo  #42",
+                "  testnet  counter:v2       –        –      –         – unreachable",
+            ]
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::ContractCodeHistoryOperationType;
+use cosmrs::AccountId;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::framework::Context;
+use crate::modules::wasm::WasmConfig;
+use crate::support::future::block;
+use crate::support::output;
+use crate::support::state::State;
+
+#[derive(clap::ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Mermaid flowchart, renders in GitHub markdown
+    Mermaid,
+    /// Graphviz DOT
+    Dot,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct GraphNode {
+    /// `contract:label` for contracts recorded in state, otherwise the address
+    pub id: String,
+    pub address: String,
+    /// `false` for contracts that are referenced but not deployed from this project
+    pub recorded: bool,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    /// `instantiates` if `from` created `to`, otherwise the msg field holding the address of `to`
+    pub relation: String,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct DeploymentGraph {
+    pub network: String,
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl DeploymentGraph {
+    fn node_id(&mut self, address: &str) -> String {
+        if let Some(node) = self.nodes.iter().find(|n| n.address == address) {
+            return node.id.clone();
+        }
+        self.nodes.push(GraphNode {
+            id: address.to_string(),
+            address: address.to_string(),
+            recorded: false,
+        });
+        address.to_string()
+    }
+}
+
+fn is_contract_address(s: &str) -> bool {
+    s.parse::<AccountId>()
+        .map(|id| id.to_bytes().len() == 32)
+        .unwrap_or(false)
+}
+
+/// Contract addresses in `msg` with the dotted path of object keys leading to them, array indices left out
+fn referenced_addresses(msg: &Value) -> Vec<(String, String)> {
+    fn walk(value: &Value, path: &mut Vec<String>, found: &mut Vec<(String, String)>) {
+        match value {
+            Value::String(s) if is_contract_address(s) => {
+                let field = if path.is_empty() {
+                    "msg".to_string()
+                } else {
+                    path.join(".")
+                };
+                found.push((field, s.clone()));
+            }
+            Value::Array(values) => values.iter().for_each(|v| walk(v, path, found)),
+            Value::Object(map) => {
+                for (key, v) in map {
+                    path.push(key.clone());
+                    walk(v, path, found);
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut found = vec![];
+    walk(msg, &mut vec![], &mut found);
+    found
+}
+
+fn short_address(address: &str) -> String {
+    if address.len() <= 20 {
+        return address.to_string();
+    }
+    format!("{}…{}", &address[..12], &address[address.len() - 6..])
+}
+
+pub fn render_mermaid(graph: &DeploymentGraph) -> String {
+    let index = |id: &str| graph.nodes.iter().position(|n| n.id == id).unwrap_or(0);
+    let mut lines = vec!["flowchart LR".to_string()];
+    for (i, node) in graph.nodes.iter().enumerate() {
+        lines.push(if node.recorded {
+            format!(
+                "    n{i}[\"{}<br/>{}\"]",
+                node.id,
+                short_address(&node.address)
+            )
+        } else {
+            format!("    n{i}([\"{}\"])", short_address(&node.address))
+        });
+    }
+    for edge in &graph.edges {
+        lines.push(format!(
+            "    n{} -->|{}| n{}",
+            index(&edge.from),
+            edge.relation,
+            index(&edge.to)
+        ));
+    }
+    lines.join("\n")
+}
+
+pub fn render_dot(graph: &DeploymentGraph) -> String {
+    let mut lines = vec![
+        format!("digraph \"{}\" {{", graph.network),
+        "    rankdir=LR;".to_string(),
+        "    node [shape=box];".to_string(),
+    ];
+    for node in &graph.nodes {
+        lines.push(if node.recorded {
+            format!(
+                "    \"{}\" [label=\"{}\\n{}\"];",
+                node.id,
+                node.id,
+                short_address(&node.address)
+            )
+        } else {
+            format!(
+                "    \"{}\" [label=\"{}\", style=dashed];",
+                node.id,
+                short_address(&node.address)
+            )
+        });
+    }
+    for edge in &graph.edges {
+        lines.push(format!(
+            "    \"{}\" -> \"{}\" [label=\"{}\"];",
+            edge.from, edge.to, edge.relation
+        ));
+    }
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+/// Graph of the contracts recorded on `network` and the contracts they reference. A contract references
+/// every contract address found in its instantiate and migrate msgs, taken from its code history on chain,
+/// and is referenced by the contract that instantiated it, e.g. a factory by its instances.
+pub fn build<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    network: &str,
+) -> Result<DeploymentGraph> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+    let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
+    let client = ctx.client(network_info);
+
+    let mut graph = DeploymentGraph {
+        network: network.to_string(),
+        ..Default::default()
+    };
+    for (contract, wasm_ref) in state.contracts(network) {
+        for (label, address) in wasm_ref.addresses() {
+            graph.nodes.push(GraphNode {
+                id: format!("{contract}:{label}"),
+                address: address.clone(),
+                recorded: true,
+            });
+        }
+    }
+
+    let recorded = graph.nodes.clone();
+    let edges = block(async {
+        let mut edges = BTreeSet::new();
+        for node in &recorded {
+            let creator = client.contract_info(node.address.clone()).await?.creator;
+            if is_contract_address(&creator) && creator != node.address {
+                edges.insert((creator, node.address.clone(), "instantiates".to_string()));
+            }
+
+            let history = client
+                .contract_history(node.address.clone())
+                .await
+                .with_context(|| format!("Unable to fetch code history of {}", node.id))?;
+            let msgs = history.iter().filter(|h| {
+                h.operation == ContractCodeHistoryOperationType::Init as i32
+                    || h.operation == ContractCodeHistoryOperationType::Migrate as i32
+            });
+            for h in msgs {
+                let msg = match serde_json::from_slice::<Value>(&h.msg) {
+                    Ok(msg) => msg,
+                    Err(_) => continue,
+                };
+                for (field, address) in referenced_addresses(&msg) {
+                    if address != node.address {
+                        edges.insert((node.address.clone(), address, field));
+                    }
+                }
+            }
+        }
+        anyhow::Ok(edges)
+    })?;
+
+    for (from, to, relation) in edges {
+        let edge = GraphEdge {
+            from: graph.node_id(&from),
+            to: graph.node_id(&to),
+            relation,
+        };
+        graph.edges.push(edge);
+    }
+    graph.edges.sort();
+
+    Ok(graph)
+}
+
+/// Render the contract graph of `network`, printing it or writing it to `out`
+pub fn graph<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    network: &str,
+    format: GraphFormat,
+    out: Option<&Path>,
+) -> Result<DeploymentGraph> {
+    let graph = build(ctx, network)?;
+    let rendered = match format {
+        GraphFormat::Mermaid => render_mermaid(&graph),
+        GraphFormat::Dot => render_dot(&graph),
+    };
+    match out {
+        Some(out) => {
+            std::fs::write(out, format!("{rendered}\n"))
+                .with_context(|| format!("Unable to write `{}`", out.display()))?;
+            println!(
+                "Contract graph of `{network}` ({} contracts, {} references) written to `{}`",
+                graph.nodes.len(),
+                graph.edges.len(),
+                out.display()
+            );
+        }
+        None => output::print(&graph, || rendered),
+    }
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const PAIR: &str = "osmo1al5fn369treqkz9mcxdlqg5vpsjmmkmcw8vqh562ezencqctx6vqqdh3f6";
+    const WALLET: &str = "osmo1ar2yq5y88kagvk48c9c2knxwvnvsswdrerndlx";
+
+    #[test]
+    fn finds_contract_addresses_by_field() {
+        let msg = json!({
+            "owner": WALLET,
+            "adapters": [{ "address": PAIR }],
+            "config": { "pair": PAIR, "fee": "0.003" },
+        });
+        assert_eq!(
+            referenced_addresses(&msg),
+            vec![
+                ("adapters.address".to_string(), PAIR.to_string()),
+                ("config.pair".to_string(), PAIR.to_string()),
+            ]
+        );
+        assert_eq!(
+            referenced_addresses(&json!(PAIR)),
+            vec![("msg".to_string(), PAIR.to_string())]
+        );
+    }
+
+    #[test]
+    fn renders_nodes_and_edges() {
+        let graph = DeploymentGraph {
+            network: "testnet".to_string(),
+            nodes: vec![
+                GraphNode {
+                    id: "router:default".to_string(),
+                    address: "osmo1router".to_string(),
+                    recorded: true,
+                },
+                GraphNode {
+                    id: PAIR.to_string(),
+                    address: PAIR.to_string(),
+                    recorded: false,
+                },
+            ],
+            edges: vec![GraphEdge {
+                from: "router:default".to_string(),
+                to: PAIR.to_string(),
+                relation: "adapters.address".to_string(),
+            }],
+        };
+
+        assert_eq!(
+            render_mermaid(&graph),
+            [
+                "flowchart LR",
+                "    n0[\"router:default<br/>osmo1router\"]",
+                "    n1([\"osmo1al5fn36…qdh3f6\"])",
+                "    n0 -->|adapters.address| n1",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            render_dot(&graph),
+            [
+                "digraph \"testnet\" {",
+                "    rankdir=LR;",
+                "    node [shape=box];",
+                "    \"router:default\" [label=\"router:default\\nosmo1router\"];",
+                &format!("    \"{PAIR}\" [label=\"osmo1al5fn36…qdh3f6\", style=dashed];"),
+                &format!("    \"router:default\" -> \"{PAIR}\" [label=\"adapters.address\"];"),
+                "}",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,99 @@
+pub mod graph;
+pub mod replay;
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Subcommand;
+
+use self::graph::GraphFormat;
+use crate::framework::Context;
+use crate::modules::wasm::WasmConfig;
+use crate::support::gas::{Gas, GasArgs};
+use crate::support::signer::SignerArgs;
+
+#[derive(Subcommand, Debug)]
+pub enum DeployCmd {
+    /// Replay stores, instantiations and migrations recorded on one network onto another,
+    /// substituting addresses of the replayed contracts in msgs
+    Replay {
+        /// Network the deployment is recorded on
+        #[clap(long)]
+        from_network: String,
+
+        /// Network to replay the deployment onto
+        #[clap(long)]
+        to_network: String,
+
+        /// Only replay these contracts, all contracts recorded on `--from-network` if not specified
+        #[clap(short, long)]
+        contract: Vec<String>,
+
+        /// Show the replay plan without broadcasting anything
+        #[clap(long)]
+        dry_run: bool,
+
+        #[clap(flatten)]
+        gas_args: GasArgs,
+
+        #[clap(flatten)]
+        signer_args: SignerArgs,
+
+        /// Specifies a block timeout height to prevent the tx from being committed past a certain height
+        #[clap(short, long, default_value = "0")]
+        timeout_height: u32,
+    },
+    /// Render which recorded contracts reference which, from their instantiate and migrate msgs
+    /// and the contracts that instantiated them
+    Graph {
+        /// Network the deployment is recorded on
+        #[clap(short, long, default_value = "local")]
+        network: String,
+
+        #[clap(long, arg_enum, default_value = "mermaid")]
+        format: GraphFormat,
+
+        /// Write the graph to this file instead of printing it
+        #[clap(short, long)]
+        out: Option<PathBuf>,
+    },
+}
+
+pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(ctx: &Ctx, cmd: &DeployCmd) -> Result<()> {
+    match cmd {
+        DeployCmd::Replay {
+            from_network,
+            to_network,
+            contract,
+            dry_run,
+            gas_args,
+            signer_args,
+            timeout_height,
+        } => {
+            let global_config = ctx.global_config()?;
+            replay::replay(
+                ctx,
+                from_network,
+                to_network,
+                contract,
+                *dry_run,
+                &Gas::from_args(
+                    gas_args,
+                    global_config.network_gas_price(to_network),
+                    global_config.gas_adjustment(),
+                )?,
+                timeout_height,
+                || signer_args.private_key(&ctx.chain_client(), &global_config, to_network),
+            )?;
+            Ok(())
+        }
+        DeployCmd::Graph {
+            network,
+            format,
+            out,
+        } => {
+            graph::graph(ctx, network, *format, out.as_deref())?;
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,380 @@
+use anyhow::{bail, Context as _, Result};
+use config::Map;
+use console::style;
+use cosmos_sdk_proto::cosmwasm::wasm::v1::ContractCodeHistoryOperationType;
+use cosmrs::crypto::secp256k1::SigningKey;
+use serde::Serialize;
+
+use crate::framework::Context;
+use crate::modules::wasm::{ops, WasmConfig};
+use crate::support::coin::Coins;
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::output;
+use crate::support::prompt;
+use crate::support::state::{Action, State};
+use crate::support::wasm::WasmSource;
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ReplayStep {
+    Store {
+        contract: String,
+        source_code_id: u64,
+        git_commit: Option<String>,
+    },
+    Instantiate {
+        contract: String,
+        label: String,
+        source_code_id: u64,
+        source_address: String,
+        /// `None` if the source contract had no admin
+        admin: Option<String>,
+        msg: String,
+    },
+    Migrate {
+        contract: String,
+        label: String,
+        source_code_id: u64,
+        msg: String,
+    },
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct ReplayResult {
+    pub steps: Vec<ReplayStep>,
+    /// Source code id to code id stored on the target network
+    pub code_ids: Map<String, u64>,
+    /// Source contract address to address instantiated on the target network
+    pub addresses: Map<String, String>,
+}
+
+/// Replace every source contract address in `msg` with its counterpart on the target network
+fn substitute_addresses(msg: &str, addresses: &Map<String, String>) -> String {
+    addresses
+        .iter()
+        .fold(msg.to_string(), |msg, (from, to)| msg.replace(from, to))
+}
+
+/// Steps re-executing the recorded stores, instantiations and migrations of `contracts` on `from_network`
+/// in the order they happened. Rolled back migrations and rollbacks are left out, so the target ends up on
+/// the same release chain. Msgs are taken from the contracts' code history on chain.
+fn plan<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    from_network: &str,
+    contracts: &[String],
+) -> Result<Vec<ReplayStep>> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(from_network)
+        .with_context(|| format!("Unable to find network config: {from_network}"))?
+        .to_owned();
+    let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
+    let client = ctx.client(network_info);
+
+    let mut entries = vec![];
+    for (contract, wasm_ref) in state.contracts(from_network) {
+        if !contracts.is_empty() && !contracts.contains(&contract) {
+            continue;
+        }
+        for (i, entry) in wasm_ref.history().iter().enumerate() {
+            if wasm_ref.is_rolled_back(i) || matches!(entry.action(), Action::Rollback) {
+                continue;
+            }
+            entries.push((contract.clone(), wasm_ref.clone(), entry.clone()));
+        }
+    }
+    entries.sort_by_key(|(_, _, entry)| *entry.timestamp());
+
+    block(async {
+        let mut histories = Map::new();
+        let mut steps = vec![];
+        for (contract, wasm_ref, entry) in entries {
+            let label = entry.label().clone();
+            let address = || {
+                wasm_ref.addresses().get(&label).cloned().with_context(|| {
+                    format!("No address recorded for {contract}:{label} on `{from_network}`")
+                })
+            };
+            let step = match entry.action() {
+                Action::Store => ReplayStep::Store {
+                    contract: contract.clone(),
+                    source_code_id: *entry.code_id(),
+                    git_commit: entry.git_commit().clone(),
+                },
+                Action::Instantiate | Action::Migrate => {
+                    let address = address()?;
+                    if !histories.contains_key(&address) {
+                        let history = client.contract_history(address.clone()).await?;
+                        histories.insert(address.clone(), history);
+                    }
+                    let history = &histories[&address];
+                    let operation = match entry.action() {
+                        Action::Instantiate => ContractCodeHistoryOperationType::Init,
+                        _ => ContractCodeHistoryOperationType::Migrate,
+                    };
+                    let msg = history
+                        .iter()
+                        .find(|h| h.operation == operation as i32 && h.code_id == *entry.code_id())
+                        .map(|h| String::from_utf8_lossy(&h.msg).to_string())
+                        .with_context(|| {
+                            format!(
+                                "Unable to find msg of code id {} in history of {contract}:{label}",
+                                entry.code_id()
+                            )
+                        })?;
+
+                    if matches!(entry.action(), Action::Instantiate) {
+                        let admin = client.contract_info(address.clone()).await?.admin;
+                        ReplayStep::Instantiate {
+                            contract: contract.clone(),
+                            label,
+                            source_code_id: *entry.code_id(),
+                            source_address: address,
+                            admin: (!admin.is_empty()).then(|| admin),
+                            msg,
+                        }
+                    } else {
+                        ReplayStep::Migrate {
+                            contract: contract.clone(),
+                            label,
+                            source_code_id: *entry.code_id(),
+                            msg,
+                        }
+                    }
+                }
+                Action::Rollback => continue,
+            };
+            steps.push(step);
+        }
+        anyhow::Ok(steps)
+    })
+}
+
+fn print_plan(steps: &[ReplayStep], from_network: &str, to_network: &str) {
+    output::print(steps, || {
+        let mut lines = vec![format!(
+            "Replay plan from `{from_network}` to `{to_network}`, source addresses in msgs are substituted"
+        )];
+        lines.extend(steps.iter().enumerate().map(|(i, step)| {
+            let n = i + 1;
+            match step {
+                ReplayStep::Store {
+                    contract,
+                    source_code_id,
+                    ..
+                } => format!("  {n}. store {contract} (code id {source_code_id})"),
+                ReplayStep::Instantiate {
+                    contract,
+                    label,
+                    source_code_id,
+                    msg,
+                    ..
+                } => format!(
+                    "  {n}. instantiate {contract}:{label} from code id {source_code_id} with {msg}"
+                ),
+                ReplayStep::Migrate {
+                    contract,
+                    label,
+                    source_code_id,
+                    msg,
+                } => format!(
+                    "  {n}. migrate {contract}:{label} to code id {source_code_id} with {msg}"
+                ),
+            }
+        }));
+        lines.join("\n")
+    });
+}
+
+/// Re-execute the deployment recorded on `from_network` against `to_network`, after showing the plan
+#[allow(clippy::too_many_arguments)]
+pub fn replay<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    from_network: &str,
+    to_network: &str,
+    contracts: &[String],
+    dry_run: bool,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: impl Fn() -> Result<SigningKey>,
+) -> Result<ReplayResult> {
+    if from_network == to_network {
+        bail!("`--from-network` and `--to-network` must be different");
+    }
+    let global_config = ctx.global_config()?;
+    let to_network_info = global_config
+        .networks()
+        .get(to_network)
+        .with_context(|| format!("Unable to find network config: {to_network}"))?
+        .to_owned();
+    let from_client = ctx.client(
+        global_config
+            .networks()
+            .get(from_network)
+            .with_context(|| format!("Unable to find network config: {from_network}"))?
+            .to_owned(),
+    );
+
+    let steps = plan(ctx, from_network, contracts)?;
+    print_plan(&steps, from_network, to_network);
+    if steps.is_empty() {
+        bail!("Nothing recorded on `{from_network}` to replay");
+    }
+
+    let target = State::load_by_network(to_network_info.clone(), ctx.root()?)?;
+    for step in &steps {
+        if let ReplayStep::Instantiate {
+            contract, label, ..
+        } = step
+        {
+            if let Ok(wasm_ref) = target.get_ref(to_network, contract) {
+                if wasm_ref.addresses().contains_key(label) {
+                    bail!("{contract}:{label} is already deployed on `{to_network}`");
+                }
+            }
+        }
+    }
+
+    let mut result = ReplayResult {
+        steps: steps.clone(),
+        ..Default::default()
+    };
+    if dry_run {
+        return Ok(result);
+    }
+    if !prompt::confirm(&format!(
+        "> Replay {} steps onto `{to_network}`?",
+        steps.len()
+    ))? {
+        bail!("Replay onto `{to_network}` aborted");
+    }
+
+    // point the target's code id at the replayed code before instantiating or migrating with it
+    let use_code_id = |contract: &str, source_code_id: u64, code_ids: &Map<String, u64>| {
+        let code_id = *code_ids.get(&source_code_id.to_string()).with_context(|| {
+            format!("Code id {source_code_id} of {contract} was not stored by the replay")
+        })?;
+        State::update_state_file(
+            to_network_info.network_variant(),
+            ctx.root()?,
+            &|s: &State| s.update_code_id(to_network, contract, &code_id),
+        )
+    };
+
+    for (i, step) in steps.into_iter().enumerate() {
+        eprintln!(
+            "  {} step {}/{}",
+            style("‣").cyan(),
+            i + 1,
+            result.steps.len()
+        );
+        match step {
+            ReplayStep::Store {
+                contract,
+                source_code_id,
+                git_commit,
+            } => {
+                let wasm = block(from_client.code(source_code_id))?;
+                let res = ops::store_code(
+                    ctx,
+                    &contract,
+                    to_network,
+                    &false,
+                    &None,
+                    gas,
+                    timeout_height,
+                    signing_key()?,
+                    WasmSource::Code { wasm, git_commit },
+                )?;
+                result
+                    .code_ids
+                    .insert(source_code_id.to_string(), res.code_id);
+            }
+            ReplayStep::Instantiate {
+                contract,
+                label,
+                source_code_id,
+                source_address,
+                admin,
+                msg,
+            } => {
+                use_code_id(&contract, source_code_id, &result.code_ids)?;
+                let admin = match admin {
+                    Some(_) => ctx
+                        .config()?
+                        .default_admin
+                        .or_else(|| Some("signer".to_string())),
+                    None => Some("none".to_string()),
+                };
+                let res = ops::instantiate(
+                    ctx,
+                    &contract,
+                    &label,
+                    None,
+                    false,
+                    Some(&substitute_addresses(&msg, &result.addresses)),
+                    admin.as_ref(),
+                    None,
+                    true,
+                    true,
+                    Coins::default(),
+                    to_network,
+                    timeout_height,
+                    gas,
+                    signing_key()?,
+                )?;
+                result
+                    .addresses
+                    .insert(source_address, res.contract_address);
+            }
+            ReplayStep::Migrate {
+                contract,
+                label,
+                source_code_id,
+                msg,
+            } => {
+                use_code_id(&contract, source_code_id, &result.code_ids)?;
+                ops::migrate(
+                    ctx,
+                    &contract,
+                    &label,
+                    Some(&substitute_addresses(&msg, &result.addresses)),
+                    true,
+                    true,
+                    to_network,
+                    timeout_height,
+                    gas,
+                    signing_key()?,
+                    false,
+                )?;
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitute_source_addresses() {
+        let addresses = [
+            ("osmo1source", "osmo1target"),
+            ("osmo1other", "osmo1replayed"),
+        ]
+        .into_iter()
+        .map(|(from, to)| (from.to_string(), to.to_string()))
+        .collect::<Map<_, _>>();
+        assert_eq!(
+            substitute_addresses(
+                r#"{"pair":"osmo1source","owners":["osmo1other","osmo1unknown"]}"#,
+                &addresses
+            ),
+            r#"{"pair":"osmo1target","owners":["osmo1replayed","osmo1unknown"]}"#
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use data_doc_derive::GetDataDocs;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Serialize, Deserialize, GetDataDocs, Default)]
+pub struct GovConfig {
+    /// Binary `FileDescriptorSet` files always loaded for encoding proposal contents unknown to beaker,
+    /// relative to project root, eg. `["proto/descriptors.bin"]`
+    #[serde(default)]
+    pub descriptor_sets: Vec<String>,
+}
@@ -0,0 +1,170 @@
+use super::config::GovConfig;
+use super::ops;
+use crate::framework::{Context, Module};
+use crate::modules::wasm::args::BaseTxArgs;
+use crate::support::gas::{Gas, GasArgs};
+use anyhow::Result;
+use clap::Subcommand;
+
+#[derive(Subcommand, Debug)]
+pub enum GovCmd {
+    /// Submit proposal of any content type from a proposal file
+    Submit {
+        /// Json, json5 or yaml file with proposal `content` (its type url as `@type`) and optional `deposit`
+        proposal_file: String,
+
+        /// Binary `FileDescriptorSet` for encoding content unknown to beaker, loaded in addition to `gov.descriptor_sets` config
+        #[clap(long)]
+        descriptor_set: Vec<String>,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Deposit to proposal that is in deposit period
+    Deposit {
+        proposal_id: u64,
+
+        /// Amount to deposit, eg. `10000000uosmo`
+        #[clap(long)]
+        amount: String,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Vote on proposal
+    Vote {
+        proposal_id: u64,
+
+        /// Vote option, one of: yes, no, no_with_veto, abstain
+        #[clap(short, long)]
+        option: String,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Show proposal with its status, deposit and tally
+    Show {
+        proposal_id: u64,
+
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Wait until the proposal is passed, rejected or failed, exit with error unless it is passed
+    Wait {
+        proposal_id: u64,
+
+        #[clap(short, long, default_value = "local")]
+        network: String,
+
+        /// Number of seconds to wait before giving up
+        #[clap(long, default_value = "3600")]
+        wait_timeout: u64,
+
+        /// Number of seconds between status checks
+        #[clap(long, default_value = "5")]
+        interval: u64,
+    },
+}
+
+pub struct GovModule {}
+
+impl<'a> Module<'a, GovConfig, GovCmd, anyhow::Error> for GovModule {
+    fn execute<Ctx: Context<'a, GovConfig>>(ctx: Ctx, cmd: &GovCmd) -> Result<(), anyhow::Error> {
+        let gas = |gas_args: &GasArgs, network: &str| -> Result<Gas> {
+            let global_conf = ctx.global_config()?;
+            Gas::from_args(
+                gas_args,
+                global_conf.network_gas_price(network),
+                global_conf.gas_adjustment(),
+            )
+        };
+
+        match cmd {
+            GovCmd::Submit {
+                proposal_file,
+                descriptor_set,
+                base_tx_args,
+            } => {
+                let BaseTxArgs {
+                    network,
+                    signer_args,
+                    gas_args,
+                    timeout_height,
+                }: &BaseTxArgs = base_tx_args;
+
+                ops::submit(
+                    &ctx,
+                    proposal_file,
+                    descriptor_set,
+                    network,
+                    &gas(gas_args, network)?,
+                    timeout_height,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
+                )?;
+                Ok(())
+            }
+            GovCmd::Deposit {
+                proposal_id,
+                amount,
+                base_tx_args,
+            } => {
+                let BaseTxArgs {
+                    network,
+                    signer_args,
+                    gas_args,
+                    timeout_height,
+                }: &BaseTxArgs = base_tx_args;
+
+                ops::deposit(
+                    &ctx,
+                    *proposal_id,
+                    amount,
+                    network,
+                    &gas(gas_args, network)?,
+                    timeout_height,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
+                )?;
+                Ok(())
+            }
+            GovCmd::Vote {
+                proposal_id,
+                option,
+                base_tx_args,
+            } => {
+                let BaseTxArgs {
+                    network,
+                    signer_args,
+                    gas_args,
+                    timeout_height,
+                }: &BaseTxArgs = base_tx_args;
+
+                ops::vote(
+                    &ctx,
+                    *proposal_id,
+                    option,
+                    network,
+                    &gas(gas_args, network)?,
+                    timeout_height,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
+                )?;
+                Ok(())
+            }
+            GovCmd::Show {
+                proposal_id,
+                network,
+            } => {
+                ops::show(&ctx, *proposal_id, network)?;
+                Ok(())
+            }
+            GovCmd::Wait {
+                proposal_id,
+                network,
+                wait_timeout,
+                interval,
+            } => {
+                ops::wait(&ctx, *proposal_id, network, *wait_timeout, *interval)?;
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod config;
+pub mod entrypoint;
+pub mod ops;
+
+pub use crate::modules::gov::config::GovConfig;
+pub use crate::modules::gov::entrypoint::{GovCmd, GovModule};
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgDeposit;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::Any;
+use serde::Serialize;
+
+use super::signing_client;
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::gov::config::GovConfig;
+use crate::support::coin::Coins;
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::proto::MessageExt;
+
+pub fn deposit<'a, Ctx: Context<'a, GovConfig>>(
+    ctx: &Ctx,
+    proposal_id: u64,
+    amount: &str,
+    network: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<DepositResponse> {
+    let client = signing_client(ctx, network, signing_key)?;
+
+    let msg_deposit = MsgDeposit {
+        proposal_id,
+        depositor: client.signer_account_id().to_string(),
+        amount: amount.parse::<Coins>()?.into(),
+    };
+    let msg_deposit = Any {
+        type_url: "/cosmos.gov.v1beta1.MsgDeposit".to_owned(),
+        value: msg_deposit.to_bytes()?,
+    };
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg_deposit], gas, "", timeout_height)
+            .await?;
+
+        let deposit_response = DepositResponse {
+            proposal_id,
+            amount: amount.to_string(),
+            tx_hash: response.hash.to_string(),
+        };
+        deposit_response.log();
+
+        Ok(deposit_response)
+    })
+}
+
+#[derive(Serialize)]
+pub struct DepositResponse {
+    pub proposal_id: u64,
+    pub amount: String,
+    pub tx_hash: String,
+}
+
+impl OpResponseDisplay for DepositResponse {
+    fn headline() -> &'static str {
+        "Deposited to proposal successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | proposal_id, amount, tx_hash }
+    }
+}
@@ -0,0 +1,37 @@
+pub mod deposit;
+pub mod show;
+pub mod submit;
+pub mod vote;
+pub mod wait;
+
+pub use deposit::deposit;
+pub use show::show;
+pub use submit::submit;
+pub use vote::vote;
+pub use wait::wait;
+
+use anyhow::{Context as _, Result};
+use cosmrs::crypto::secp256k1::SigningKey;
+
+use crate::framework::Context;
+use crate::modules::gov::config::GovConfig;
+use crate::support::cosmos::SigningClient;
+use crate::support::gas_ledger::GasLedger;
+
+fn signing_client<'a, Ctx: Context<'a, GovConfig>>(
+    ctx: &Ctx,
+    network: &str,
+    signing_key: SigningKey,
+) -> Result<SigningClient> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    Ok(ctx
+        .client(network_info)
+        .to_signing_client(signing_key, global_config.account_prefix())
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, None)))
+}
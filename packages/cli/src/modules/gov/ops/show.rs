@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use anyhow::{Context as _, Result};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::ProposalStatus;
+use cosmrs::bip32::secp256k1::pkcs8::der::DateTime;
+use prost::Message;
+use serde::Serialize;
+
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::gov::config::GovConfig;
+use crate::support::future::block;
+use crate::support::gov::status_name;
+use crate::support::ops_response::OpResponseDisplay;
+
+/// Fields every v1beta1 proposal content starts with, other fields are skipped while decoding
+#[derive(Clone, PartialEq, prost::Message)]
+struct ContentHeader {
+    #[prost(string, tag = "1")]
+    title: String,
+    #[prost(string, tag = "2")]
+    description: String,
+}
+
+fn coins_str(coins: &[Coin]) -> String {
+    coins
+        .iter()
+        .map(|c| format!("{}{}", c.amount, c.denom))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+fn time_str(ts: Option<prost_types::Timestamp>) -> String {
+    ts.and_then(|ts| {
+        DateTime::from_unix_duration(Duration::new(ts.seconds as u64, ts.nanos as u32)).ok()
+    })
+    .map(|d| d.to_string())
+    .unwrap_or_else(|| "–".to_string())
+}
+
+/// Show proposal of any content type, tally is the live one while the proposal is in voting period
+pub fn show<'a, Ctx: Context<'a, GovConfig>>(
+    ctx: &Ctx,
+    proposal_id: u64,
+    network: &str,
+) -> Result<ShowResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+    let client = ctx.client(network_info);
+
+    block(async {
+        let proposal = client.proposal(&proposal_id).await?;
+        let content = proposal.content.unwrap_or_default();
+        let header = ContentHeader::decode(content.value.as_slice()).unwrap_or_default();
+
+        let tally =
+            if ProposalStatus::from_i32(proposal.status) == Some(ProposalStatus::VotingPeriod) {
+                client.tally_result(&proposal_id).await?
+            } else {
+                proposal.final_tally_result.unwrap_or_default()
+            };
+        let min_deposit = client.gov_params_deposit().await?.min_deposit;
+
+        let show_response = ShowResponse {
+            proposal_id,
+            content_type: content.type_url,
+            title: header.title,
+            description: header.description,
+            status: status_name(proposal.status).to_string(),
+            total_deposit: format!(
+                "{} (min_deposit: {})",
+                coins_str(&proposal.total_deposit),
+                coins_str(&min_deposit)
+            ),
+            yes: tally.yes,
+            no: tally.no,
+            no_with_veto: tally.no_with_veto,
+            abstain: tally.abstain,
+            submit_time: time_str(proposal.submit_time),
+            deposit_end_time: time_str(proposal.deposit_end_time),
+            voting_end_time: time_str(proposal.voting_end_time),
+        };
+        show_response.log();
+
+        Ok(show_response)
+    })
+}
+
+#[derive(Serialize)]
+pub struct ShowResponse {
+    pub proposal_id: u64,
+    pub content_type: String,
+    pub title: String,
+    pub description: String,
+    pub status: String,
+    pub total_deposit: String,
+    pub yes: String,
+    pub no: String,
+    pub no_with_veto: String,
+    pub abstain: String,
+    pub submit_time: String,
+    pub deposit_end_time: String,
+    pub voting_end_time: String,
+}
+
+impl OpResponseDisplay for ShowResponse {
+    fn headline() -> &'static str {
+        "Proposal found!"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | proposal_id, content_type, title, description, status, total_deposit,
+        yes, no, no_with_veto, abstain, submit_time, deposit_end_time, voting_end_time }
+    }
+}
@@ -0,0 +1,413 @@
+use std::fmt::Display;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::{Context as _, Result};
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::CommunityPoolSpendProposal;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::{MsgSubmitProposal, TextProposal};
+use cosmos_sdk_proto::cosmos::params::v1beta1::{ParamChange, ParameterChangeProposal};
+use cosmos_sdk_proto::cosmos::upgrade::v1beta1::{Plan, SoftwareUpgradeProposal};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    ClearAdminProposal, ExecuteContractProposal, MigrateContractProposal, UpdateAdminProposal,
+};
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::Any;
+use prost_reflect::DescriptorPool;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use super::signing_client;
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::gov::config::GovConfig;
+use crate::modules::tx::ops::raw::{encode_any, load_descriptors};
+use crate::support::coin::Coins;
+use crate::support::cosmos::ResponseValuePicker;
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::msg::to_json_bytes;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::proto::MessageExt;
+
+/// Proposal file for `gov submit`, written in json, json5 or yaml
+#[derive(Deserialize, Debug)]
+pub struct ProposalFile {
+    /// Proposal content with its type url as `@type`
+    pub content: Value,
+    /// Initial deposit, eg. `10000000uosmo`
+    #[serde(default)]
+    pub deposit: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ParamChangeJson {
+    subspace: String,
+    key: String,
+    /// Amino json of the value, non string values are serialized as is
+    value: Value,
+}
+
+#[derive(Deserialize, Debug)]
+struct PlanJson {
+    name: String,
+    #[serde(deserialize_with = "number")]
+    height: i64,
+    #[serde(default)]
+    info: String,
+}
+
+/// Proposal contents encoded without descriptor sets
+#[derive(Deserialize, Debug)]
+#[serde(tag = "@type")]
+enum KnownContent {
+    #[serde(rename = "/cosmos.gov.v1beta1.TextProposal")]
+    Text { title: String, description: String },
+    #[serde(rename = "/cosmos.params.v1beta1.ParameterChangeProposal")]
+    ParameterChange {
+        title: String,
+        description: String,
+        changes: Vec<ParamChangeJson>,
+    },
+    #[serde(rename = "/cosmos.distribution.v1beta1.CommunityPoolSpendProposal")]
+    CommunityPoolSpend {
+        title: String,
+        description: String,
+        recipient: String,
+        amount: String,
+    },
+    #[serde(rename = "/cosmos.upgrade.v1beta1.SoftwareUpgradeProposal")]
+    SoftwareUpgrade {
+        title: String,
+        description: String,
+        plan: PlanJson,
+    },
+    #[serde(rename = "/cosmwasm.wasm.v1.MigrateContractProposal")]
+    MigrateContract {
+        title: String,
+        description: String,
+        contract: String,
+        #[serde(deserialize_with = "number")]
+        code_id: u64,
+        msg: Value,
+    },
+    #[serde(rename = "/cosmwasm.wasm.v1.ExecuteContractProposal")]
+    ExecuteContract {
+        title: String,
+        description: String,
+        run_as: String,
+        contract: String,
+        msg: Value,
+        #[serde(default)]
+        funds: Option<String>,
+    },
+    #[serde(rename = "/cosmwasm.wasm.v1.UpdateAdminProposal")]
+    UpdateAdmin {
+        title: String,
+        description: String,
+        new_admin: String,
+        contract: String,
+    },
+    #[serde(rename = "/cosmwasm.wasm.v1.ClearAdminProposal")]
+    ClearAdmin {
+        title: String,
+        description: String,
+        contract: String,
+    },
+}
+
+const KNOWN_CONTENT_TYPES: &[&str] = &[
+    "/cosmos.gov.v1beta1.TextProposal",
+    "/cosmos.params.v1beta1.ParameterChangeProposal",
+    "/cosmos.distribution.v1beta1.CommunityPoolSpendProposal",
+    "/cosmos.upgrade.v1beta1.SoftwareUpgradeProposal",
+    "/cosmwasm.wasm.v1.MigrateContractProposal",
+    "/cosmwasm.wasm.v1.ExecuteContractProposal",
+    "/cosmwasm.wasm.v1.UpdateAdminProposal",
+    "/cosmwasm.wasm.v1.ClearAdminProposal",
+];
+
+/// Integer written either as json number or string, as proto json does for 64 bit integers
+fn number<'de, D: Deserializer<'de>, T: FromStr>(deserializer: D) -> Result<T, D::Error>
+where
+    T::Err: Display,
+{
+    match Value::deserialize(deserializer)? {
+        Value::String(s) => s.parse(),
+        v => v.to_string().parse(),
+    }
+    .map_err(de::Error::custom)
+}
+
+impl KnownContent {
+    fn into_any(self, type_url: &str) -> Result<Any> {
+        let value = match self {
+            KnownContent::Text { title, description } => {
+                TextProposal { title, description }.to_bytes()?
+            }
+            KnownContent::ParameterChange {
+                title,
+                description,
+                changes,
+            } => ParameterChangeProposal {
+                title,
+                description,
+                changes: changes
+                    .into_iter()
+                    .map(|c| ParamChange {
+                        subspace: c.subspace,
+                        key: c.key,
+                        value: match c.value {
+                            Value::String(s) => s,
+                            v => v.to_string(),
+                        },
+                    })
+                    .collect(),
+            }
+            .to_bytes()?,
+            KnownContent::CommunityPoolSpend {
+                title,
+                description,
+                recipient,
+                amount,
+            } => CommunityPoolSpendProposal {
+                title,
+                description,
+                recipient,
+                amount: amount.parse::<Coins>()?.into(),
+            }
+            .to_bytes()?,
+            KnownContent::SoftwareUpgrade {
+                title,
+                description,
+                plan,
+            } => SoftwareUpgradeProposal {
+                title,
+                description,
+                plan: Some(Plan {
+                    name: plan.name,
+                    height: plan.height,
+                    info: plan.info,
+                    ..Default::default()
+                }),
+            }
+            .to_bytes()?,
+            KnownContent::MigrateContract {
+                title,
+                description,
+                contract,
+                code_id,
+                msg,
+            } => MigrateContractProposal {
+                title,
+                description,
+                contract,
+                code_id,
+                msg: serde_json::to_vec(&msg)?,
+            }
+            .to_bytes()?,
+            KnownContent::ExecuteContract {
+                title,
+                description,
+                run_as,
+                contract,
+                msg,
+                funds,
+            } => ExecuteContractProposal {
+                title,
+                description,
+                run_as,
+                contract,
+                msg: serde_json::to_vec(&msg)?,
+                funds: Coins::try_from(funds.as_deref())?.into(),
+            }
+            .to_bytes()?,
+            KnownContent::UpdateAdmin {
+                title,
+                description,
+                new_admin,
+                contract,
+            } => UpdateAdminProposal {
+                title,
+                description,
+                new_admin,
+                contract,
+            }
+            .to_bytes()?,
+            KnownContent::ClearAdmin {
+                title,
+                description,
+                contract,
+            } => ClearAdminProposal {
+                title,
+                description,
+                contract,
+            }
+            .to_bytes()?,
+        };
+
+        Ok(Any {
+            type_url: type_url.to_string(),
+            value,
+        })
+    }
+}
+
+/// Encode proposal content json into `Any`. Common cosmos-sdk and wasm proposals are known to beaker,
+/// any other type is encoded with the loaded descriptor sets.
+pub fn encode_content(content: &Value, pool: &DescriptorPool) -> Result<Any> {
+    let type_url = content
+        .get("@type")
+        .and_then(Value::as_str)
+        .with_context(|| "Proposal content must specify its type url as `@type`")?;
+
+    if KNOWN_CONTENT_TYPES.contains(&type_url) {
+        return serde_json::from_value::<KnownContent>(content.clone())
+            .with_context(|| format!("Invalid `{type_url}` proposal content"))?
+            .into_any(type_url);
+    }
+
+    let mut fields = content.clone();
+    if let Some(fields) = fields.as_object_mut() {
+        fields.remove("@type");
+    }
+    encode_any(pool, type_url, &fields.to_string())
+}
+
+/// Submit proposal of any content type from proposal file
+#[allow(clippy::too_many_arguments)]
+pub fn submit<'a, Ctx: Context<'a, GovConfig>>(
+    ctx: &Ctx,
+    proposal_file: &str,
+    descriptor_sets: &[String],
+    network: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<SubmitResponse> {
+    let proposal = fs::read_to_string(proposal_file)
+        .map_err(anyhow::Error::from)
+        .and_then(|s| to_json_bytes(&s))
+        .and_then(|b| serde_json::from_slice::<ProposalFile>(&b).map_err(anyhow::Error::from))
+        .with_context(|| format!("Unable to read proposal from `{proposal_file}`"))?;
+
+    let root = ctx.root()?;
+    let descriptor_sets = ctx
+        .config()?
+        .descriptor_sets
+        .iter()
+        .map(|p| root.join(p))
+        .chain(descriptor_sets.iter().map(PathBuf::from))
+        .collect::<Vec<PathBuf>>();
+    let pool = load_descriptors(&descriptor_sets)?;
+    let content = encode_content(&proposal.content, &pool)?;
+    let content_type = content.type_url.clone();
+
+    let client = signing_client(ctx, network, signing_key)?;
+    let msg_submit_proposal = MsgSubmitProposal {
+        content: Some(content),
+        initial_deposit: Coins::try_from(proposal.deposit.as_deref())?.into(),
+        proposer: client.signer_account_id().to_string(),
+    };
+    let msg_submit_proposal = Any {
+        type_url: "/cosmos.gov.v1beta1.MsgSubmitProposal".to_owned(),
+        value: msg_submit_proposal.to_bytes()?,
+    };
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg_submit_proposal], gas, "", timeout_height)
+            .await?;
+
+        let submit_response = SubmitResponse {
+            proposal_id: response
+                .pick("submit_proposal", "proposal_id")
+                .to_string()
+                .parse()?,
+            content_type,
+            deposit: proposal.deposit.unwrap_or_else(|| "-".to_string()),
+            tx_hash: response.hash.to_string(),
+        };
+        submit_response.log();
+
+        Ok(submit_response)
+    })
+}
+
+#[derive(Serialize)]
+pub struct SubmitResponse {
+    pub proposal_id: u64,
+    pub content_type: String,
+    pub deposit: String,
+    pub tx_hash: String,
+}
+
+impl OpResponseDisplay for SubmitResponse {
+    fn headline() -> &'static str {
+        "Proposal has been submitted!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | proposal_id, content_type, deposit, tx_hash }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+    use serde_json::json;
+
+    #[test]
+    fn encode_known_contents() {
+        let pool = DescriptorPool::new();
+
+        let any = encode_content(
+            &json!({
+                "@type": "/cosmos.params.v1beta1.ParameterChangeProposal",
+                "title": "Raise max gas",
+                "description": "",
+                "changes": [{ "subspace": "baseapp", "key": "BlockParams", "value": { "max_gas": "100" } }]
+            }),
+            &pool,
+        )
+        .unwrap();
+        assert_eq!(
+            any.type_url,
+            "/cosmos.params.v1beta1.ParameterChangeProposal"
+        );
+        let proposal = ParameterChangeProposal::decode(any.value.as_slice()).unwrap();
+        assert_eq!(proposal.changes[0].value, r#"{"max_gas":"100"}"#);
+
+        let any = encode_content(
+            &json!({
+                "@type": "/cosmwasm.wasm.v1.MigrateContractProposal",
+                "title": "Migrate counter",
+                "description": "",
+                "contract": "osmo1counter",
+                "code_id": "7",
+                "msg": { "reset": {} }
+            }),
+            &pool,
+        )
+        .unwrap();
+        let proposal = MigrateContractProposal::decode(any.value.as_slice()).unwrap();
+        assert_eq!(proposal.code_id, 7);
+        assert_eq!(proposal.msg, br#"{"reset":{}}"#.to_vec());
+    }
+
+    #[test]
+    fn reject_unknown_or_untyped_contents() {
+        let pool = DescriptorPool::new();
+        assert!(encode_content(&json!({ "title": "no type" }), &pool).is_err());
+        assert!(encode_content(
+            &json!({ "@type": "/osmosis.poolincentives.v1beta1.UpdatePoolIncentivesProposal" }),
+            &pool
+        )
+        .is_err());
+        assert!(encode_content(
+            &json!({ "@type": "/cosmos.gov.v1beta1.TextProposal", "title": "missing description" }),
+            &pool
+        )
+        .is_err());
+    }
+}
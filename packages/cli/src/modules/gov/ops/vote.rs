@@ -0,0 +1,68 @@
+use anyhow::Result;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgVote;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::Any;
+use serde::Serialize;
+
+use super::signing_client;
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::gov::config::GovConfig;
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::gov::vote_option;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::proto::MessageExt;
+
+pub fn vote<'a, Ctx: Context<'a, GovConfig>>(
+    ctx: &Ctx,
+    proposal_id: u64,
+    option: &str,
+    network: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<VoteResponse> {
+    let client = signing_client(ctx, network, signing_key)?;
+
+    let msg_vote = MsgVote {
+        proposal_id,
+        voter: client.signer_account_id().to_string(),
+        option: vote_option(option)?.into(),
+    };
+    let msg_vote = Any {
+        type_url: "/cosmos.gov.v1beta1.MsgVote".to_owned(),
+        value: msg_vote.to_bytes()?,
+    };
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg_vote], gas, "", timeout_height)
+            .await?;
+
+        let vote_response = VoteResponse {
+            proposal_id,
+            option: option.to_string(),
+            tx_hash: response.hash.to_string(),
+        };
+        vote_response.log();
+
+        Ok(vote_response)
+    })
+}
+
+#[derive(Serialize)]
+pub struct VoteResponse {
+    pub proposal_id: u64,
+    pub option: String,
+    pub tx_hash: String,
+}
+
+impl OpResponseDisplay for VoteResponse {
+    fn headline() -> &'static str {
+        "Voted successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | proposal_id, option, tx_hash }
+    }
+}
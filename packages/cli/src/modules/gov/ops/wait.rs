@@ -0,0 +1,71 @@
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::gov::config::GovConfig;
+use crate::support::future::block;
+use crate::support::gov::{status_name, wait_for_proposal};
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::progress::Progress;
+use anyhow::{bail, Context as _, Result};
+use cosmos_sdk_proto::cosmos::gov::v1beta1::ProposalStatus;
+use serde::Serialize;
+use std::time::Duration;
+
+pub fn wait<'a, Ctx: Context<'a, GovConfig>>(
+    ctx: &Ctx,
+    proposal_id: u64,
+    network: &str,
+    timeout: u64,
+    interval: u64,
+) -> Result<WaitResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = ctx.client(network_info);
+    let progress = Progress::spinner(&format!("Waiting for proposal {proposal_id}"));
+    let proposal = progress.end_with(block(wait_for_proposal(
+        &client,
+        proposal_id,
+        Duration::from_secs(interval.max(1)),
+        Duration::from_secs(timeout),
+    )))?;
+
+    let tally = proposal.final_tally_result.unwrap_or_default();
+    let wait_response = WaitResponse {
+        proposal_id,
+        status: status_name(proposal.status).to_string(),
+        yes: tally.yes,
+        no: tally.no,
+        no_with_veto: tally.no_with_veto,
+        abstain: tally.abstain,
+    };
+    wait_response.log();
+
+    if ProposalStatus::from_i32(proposal.status) != Some(ProposalStatus::Passed) {
+        bail!("Proposal {proposal_id} is {}", wait_response.status);
+    }
+
+    Ok(wait_response)
+}
+
+#[derive(Serialize)]
+pub struct WaitResponse {
+    pub proposal_id: u64,
+    pub status: String,
+    pub yes: String,
+    pub no: String,
+    pub no_with_veto: String,
+    pub abstain: String,
+}
+
+impl OpResponseDisplay for WaitResponse {
+    fn headline() -> &'static str {
+        "Proposal voting ended"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | proposal_id, status, yes, no, no_with_veto, abstain }
+    }
+}
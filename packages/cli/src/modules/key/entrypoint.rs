@@ -1,13 +1,26 @@
 use super::config::{KeyConfig, SERVICE};
+use crate::framework::config::{GlobalConfig, KeyAlgo, Network};
 use crate::framework::{Context, Module};
-use crate::support::signer::SigningKeyExt;
+use crate::support::adr36::{self, SignedMessage};
+use crate::support::chain_client::ChainClient;
+use crate::support::eth_key;
+use crate::support::keystore::{read_key_file, ImportedKey, PASSPHRASE_ENV};
+use crate::support::output;
+use crate::support::prompt;
+use crate::support::signer::{
+    account_private_key_with, keyring_private_key_with, network_address, network_keyring_key,
+    override_derivation_path,
+};
 use anyhow::{Context as _, Ok, Result};
 use clap::Subcommand;
+use console::style;
 use cosmrs::bip32;
 use cosmrs::bip32::secp256k1::elliptic_curve::rand_core::OsRng;
 use cosmrs::crypto::secp256k1::SigningKey;
-use dialoguer::Confirm;
 use keyring::Entry;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 #[derive(Subcommand, Debug)]
 pub enum KeyCmd {
@@ -19,8 +32,8 @@ pub enum KeyCmd {
         /// Mnemonic string to store as an entry
         mnemonic: String,
 
-        /// Agree to all prompts
-        #[clap(short, long)]
+        /// Agree to all prompts, same as the global `--yes`
+        #[clap(short, long, hide = true)]
         yes: bool,
     },
     /// Delete existing key
@@ -29,8 +42,8 @@ pub enum KeyCmd {
         /// Name of the key to create or update
         name: String,
 
-        /// Agree to all prompts
-        #[clap(short, long)]
+        /// Agree to all prompts, same as the global `--yes`
+        #[clap(short, long, hide = true)]
         yes: bool,
     },
     /// Get address from keyring's stored key
@@ -38,6 +51,10 @@ pub enum KeyCmd {
     Address {
         /// Name of the key to create or update
         name: String,
+
+        /// Network whose coin type, key algorithm and prefix the address is derived with
+        #[clap(short, long, default_value = "local")]
+        network: String,
     },
     /// Generate new mnemonic
     #[clap(alias = "gen")]
@@ -49,10 +66,107 @@ pub enum KeyCmd {
         #[clap(long)]
         show: bool,
 
-        /// Agree to all prompts
-        #[clap(short, long)]
+        /// Agree to all prompts, same as the global `--yes`
+        #[clap(short, long, hide = true)]
+        yes: bool,
+    },
+    /// Import key from armored private key, cosmjs / Keplr serialized wallet, encrypted keystore JSON
+    /// or file containing plain mnemonic / private key
+    Import {
+        /// Name of the key to create or update
+        name: String,
+
+        /// Key file to import, passphrase is prompted for or read from `BEAKER_KEY_PASSPHRASE` if the file is encrypted
+        #[clap(long)]
+        file: PathBuf,
+
+        /// Network whose coin type, key algorithm and prefix the printed address is derived with
+        #[clap(short, long, default_value = "local")]
+        network: String,
+
+        /// Agree to all prompts, same as the global `--yes`
+        #[clap(short, long, hide = true)]
         yes: bool,
     },
+    /// Show address of predefined account or keyring key under multiple bech32 prefixes
+    Show {
+        /// Name of predefined account or keyring key
+        name: String,
+
+        /// Comma separated bech32 prefixes, eg. `juno,osmo,neutron`
+        #[clap(long, use_value_delimiter = true)]
+        prefix: Vec<String>,
+
+        /// Show address on every configured network, derived with the network's prefix and coin type
+        #[clap(long)]
+        all_networks: bool,
+    },
+    /// Sign arbitrary message off-chain (ADR-36), verifiable by `beaker key verify` or Keplr's `verifyArbitrary`
+    Sign {
+        /// Name of predefined account or keyring key to sign with
+        name: String,
+
+        /// Message to sign, either text or path to file containing it
+        #[clap(long)]
+        message: String,
+
+        /// Network whose coin type, key algorithm and prefix the key is derived with
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Verify ADR-36 signature of arbitrary message
+    Verify {
+        /// Message that was signed, either text or path to file containing it
+        #[clap(long)]
+        message: String,
+
+        /// Signature json produced by `beaker key sign`, either inline or path to file containing it
+        #[clap(long)]
+        signature: String,
+    },
+}
+
+#[derive(Serialize)]
+struct ShownAddress {
+    network: Option<String>,
+    prefix: String,
+    address: String,
+}
+
+/// Signing key of predefined account, or keyring key if no account is named `name`
+fn named_signing_key(
+    chain: &ChainClient,
+    global_config: &GlobalConfig,
+    name: &str,
+    derivation_path: &str,
+    key_algo: KeyAlgo,
+) -> Result<SigningKey> {
+    match global_config.accounts().get(name) {
+        Some(account) => account_private_key_with(chain, account, derivation_path, key_algo),
+        None => {
+            let secret = Entry::new(SERVICE, name).get_password().with_context(|| {
+                format!("`{name}` is neither a predefined account nor a keyring key")
+            })?;
+            keyring_private_key_with(chain, &secret, derivation_path, key_algo)
+        }
+    }
+}
+
+fn network_info<'c>(global_config: &'c GlobalConfig, network: &str) -> Result<&'c Network> {
+    global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))
+}
+
+/// Content of file at `arg` if it exists, otherwise `arg` itself
+fn text_or_file(arg: &str) -> Result<Vec<u8>> {
+    let path = Path::new(arg);
+    if path.is_file() {
+        fs::read(path).with_context(|| format!("Unable to read `{arg}`"))
+    } else {
+        Ok(arg.as_bytes().to_vec())
+    }
 }
 
 pub struct KeyModule {}
@@ -65,48 +179,57 @@ impl<'a> Module<'a, KeyConfig, KeyCmd, anyhow::Error> for KeyModule {
                 mnemonic,
                 yes,
             } => {
+                if *yes {
+                    prompt::set_assume_yes(true);
+                }
                 let entry = keyring::Entry::new(SERVICE, name);
-                let global_config = ctx.global_config()?;
-                let derivation_path = global_config.derivation_path();
-
-                SigningKey::from_mnemonic(mnemonic, derivation_path)
+                bip39::Mnemonic::parse(mnemonic)
                     .with_context(|| "Invalid phrase, if word length is not 24, please consider using 24-words mnemonic")?;
 
-                confirm_override(SERVICE, name, *yes)?;
+                confirm_override(SERVICE, name, prompt::assume_yes())?;
                 entry
                     .set_password(mnemonic)
                     .with_context(|| "Unable to set key")
             }
             KeyCmd::Delete { name, yes } => {
+                if *yes {
+                    prompt::set_assume_yes(true);
+                }
                 let entry = keyring::Entry::new(SERVICE, name);
 
-                confirm_deletion(SERVICE, name, *yes)?;
+                confirm_deletion(SERVICE, name, prompt::assume_yes())?;
                 entry
                     .delete_password()
                     .with_context(|| "Unable to delete key")
             }
-            KeyCmd::Address { name } => {
+            KeyCmd::Address { name, network } => {
                 let entry = keyring::Entry::new(SERVICE, name);
                 let global_config = ctx.global_config()?;
+                let network_info = network_info(&global_config, network)?;
 
-                let mnemonic = entry.get_password()?;
-                let derivation_path = global_config.derivation_path();
-                let address = SigningKey::from_mnemonic(&mnemonic, derivation_path)?
-                    .public_key()
-                    .account_id(global_config.account_prefix())
-                    .unwrap()
-                    .to_string();
+                let secret = entry.get_password()?;
+                let key = network_keyring_key(
+                    &ctx.chain_client(),
+                    &global_config,
+                    &secret,
+                    network_info,
+                )?;
+                let address =
+                    network_address(&ctx.chain_client(), &global_config, &key, network_info)?;
 
                 println!("{}", address);
                 Ok(())
             }
             KeyCmd::Generate { name, show, yes } => {
+                if *yes {
+                    prompt::set_assume_yes(true);
+                }
                 let mnemonic = bip32::Mnemonic::random(OsRng, bip32::Language::English);
                 let mnemonic = mnemonic.phrase();
 
                 let entry = keyring::Entry::new(SERVICE, name);
 
-                confirm_override(SERVICE, name, *yes)?;
+                confirm_override(SERVICE, name, prompt::assume_yes())?;
 
                 if *show {
                     println!("{}", mnemonic);
@@ -116,11 +239,167 @@ impl<'a> Module<'a, KeyConfig, KeyCmd, anyhow::Error> for KeyModule {
                     .set_password(mnemonic)
                     .with_context(|| "Unable to set key")
             }
+            KeyCmd::Import {
+                name,
+                file,
+                network,
+                yes,
+            } => {
+                if *yes {
+                    prompt::set_assume_yes(true);
+                }
+                let global_config = ctx.global_config()?;
+                let network_info = network_info(&global_config, network)?;
+                let key = read_key_file(file, *network_info.key_algo(), || {
+                    prompt::password("> Passphrase of the key file", PASSPHRASE_ENV)
+                })?;
+
+                if let ImportedKey::Mnemonic {
+                    hd_path: Some(hd_path),
+                    ..
+                } = &key
+                {
+                    let derivation_path = override_derivation_path(
+                        global_config.derivation_path(),
+                        None,
+                        *network_info.coin_type(),
+                    )?;
+                    if *hd_path != derivation_path {
+                        eprintln!(
+                            "  {} key was derived with `{hd_path}` while `{network}` derives with `{derivation_path}`, addresses might differ from the original wallet",
+                            style("WARNING:").yellow().bold(),
+                        );
+                    }
+                }
+
+                let secret = key.to_keyring_secret();
+                let address = network_address(
+                    &ctx.chain_client(),
+                    &global_config,
+                    &network_keyring_key(
+                        &ctx.chain_client(),
+                        &global_config,
+                        &secret,
+                        network_info,
+                    )?,
+                    network_info,
+                )?;
+
+                confirm_override(SERVICE, name, prompt::assume_yes())?;
+                keyring::Entry::new(SERVICE, name)
+                    .set_password(&secret)
+                    .with_context(|| "Unable to set key")?;
+
+                println!("{}", address);
+                Ok(())
+            }
+            KeyCmd::Show {
+                name,
+                prefix,
+                all_networks,
+            } => {
+                let global_config = ctx.global_config()?;
+                let derive = |coin_type: Option<u32>,
+                              prefix: &str,
+                              key_algo: KeyAlgo|
+                 -> Result<String> {
+                    let derivation_path =
+                        override_derivation_path(global_config.derivation_path(), None, coin_type)?;
+                    let chain = ctx.chain_client();
+                    let public_key = named_signing_key(
+                        &chain,
+                        &global_config,
+                        name,
+                        &derivation_path,
+                        key_algo,
+                    )?
+                    .public_key();
+                    let key_algo = chain.key_algo(&public_key.to_bytes()).unwrap_or(key_algo);
+                    Ok(eth_key::account_id(&public_key, prefix, key_algo)?.to_string())
+                };
+
+                let mut addresses = vec![];
+                for p in prefix {
+                    addresses.push(ShownAddress {
+                        network: None,
+                        prefix: p.clone(),
+                        address: derive(None, p, KeyAlgo::Secp256k1)?,
+                    });
+                }
+                if *all_networks {
+                    for (network, info) in global_config.networks() {
+                        let prefix = info
+                            .account_prefix()
+                            .clone()
+                            .unwrap_or_else(|| global_config.account_prefix().clone());
+                        addresses.push(ShownAddress {
+                            network: Some(network.clone()),
+                            address: derive(*info.coin_type(), &prefix, *info.key_algo())?,
+                            prefix,
+                        });
+                    }
+                }
+                if addresses.is_empty() {
+                    let prefix = global_config.account_prefix();
+                    addresses.push(ShownAddress {
+                        network: None,
+                        prefix: prefix.clone(),
+                        address: derive(None, prefix, KeyAlgo::Secp256k1)?,
+                    });
+                }
+
+                output::print(&addresses, || {
+                    addresses
+                        .iter()
+                        .map(|a| match &a.network {
+                            Some(network) => format!("{network} ({}): {}", a.prefix, a.address),
+                            None => format!("{}: {}", a.prefix, a.address),
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                });
+                Ok(())
+            }
+            KeyCmd::Sign {
+                name,
+                message,
+                network,
+            } => {
+                let global_config = ctx.global_config()?;
+                let network_info = network_info(&global_config, network)?;
+                let signing_key = named_signing_key(
+                    &ctx.chain_client(),
+                    &global_config,
+                    name,
+                    &override_derivation_path(
+                        global_config.derivation_path(),
+                        None,
+                        *network_info.coin_type(),
+                    )?,
+                    *network_info.key_algo(),
+                )?;
+
+                let signed = adr36::sign(
+                    &signing_key,
+                    global_config.account_prefix(),
+                    &text_or_file(message)?,
+                )?;
+                println!("{}", serde_json::to_string_pretty(&signed)?);
+                Ok(())
+            }
+            KeyCmd::Verify { message, signature } => {
+                let signed: SignedMessage = serde_json::from_slice(&text_or_file(signature)?)
+                    .with_context(|| "Invalid signature json")?;
+                adr36::verify(&signed, &text_or_file(message)?)?;
+
+                println!("Signature is valid, signed by {}", signed.signer);
+                Ok(())
+            }
         }
     }
 }
 
-fn confirm_override(service: &str, name: &str, yes: bool) -> Result<bool, std::io::Error> {
+fn confirm_override(service: &str, name: &str, yes: bool) -> Result<bool> {
     let entry = Entry::new(service, name);
     let exists = entry.get_password().is_ok();
 
@@ -128,15 +407,13 @@ fn confirm_override(service: &str, name: &str, yes: bool) -> Result<bool, std::i
         return core::result::Result::Ok(true);
     }
 
-    Confirm::new()
-        .with_prompt(format!(
-            " > Key with name `{}` already exists. Do you want to override?",
-            name
-        ))
-        .interact()
+    prompt::confirm(&format!(
+        " > Key with name `{}` already exists. Do you want to override?",
+        name
+    ))
 }
 
-fn confirm_deletion(service: &str, name: &str, yes: bool) -> Result<bool, std::io::Error> {
+fn confirm_deletion(service: &str, name: &str, yes: bool) -> Result<bool> {
     let entry = Entry::new(service, name);
     let exists = entry.get_password().is_ok();
 
@@ -144,7 +421,5 @@ fn confirm_deletion(service: &str, name: &str, yes: bool) -> Result<bool, std::i
         return core::result::Result::Ok(true);
     }
 
-    Confirm::new()
-        .with_prompt(format!(" > Do you want to delete `{}`?", name))
-        .interact()
+    prompt::confirm(&format!(" > Do you want to delete `{}`?", name))
 }
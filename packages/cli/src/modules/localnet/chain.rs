@@ -0,0 +1,204 @@
+use anyhow::{bail, Result};
+use data_doc_derive::GetDataDocs;
+use serde::{Deserialize, Serialize};
+
+use super::config::LocalnetConfig;
+
+#[derive(Serialize, Deserialize, GetDataDocs, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Chain {
+    Osmosis,
+    Juno,
+    Wasmd,
+    /// No defaults, image, binary, data dir, chain id and denom must be specified
+    Custom,
+}
+
+impl Default for Chain {
+    fn default() -> Self {
+        Chain::Osmosis
+    }
+}
+
+struct Preset {
+    image: &'static str,
+    binary: &'static str,
+    data_dir: &'static str,
+    chain_id: &'static str,
+    denom: &'static str,
+}
+
+impl Chain {
+    fn preset(&self) -> Option<Preset> {
+        match self {
+            Chain::Osmosis => Some(Preset {
+                image: "osmolabs/osmosis:12.2.0",
+                binary: "osmosisd",
+                data_dir: "/osmosis/.osmosisd",
+                chain_id: "localosmosis",
+                denom: "uosmo",
+            }),
+            Chain::Juno => Some(Preset {
+                image: "ghcr.io/cosmoscontracts/juno:11.0.0",
+                binary: "junod",
+                data_dir: "/juno/.juno",
+                chain_id: "localjuno",
+                denom: "ujuno",
+            }),
+            Chain::Wasmd => Some(Preset {
+                image: "cosmwasm/wasmd:v0.30.0",
+                binary: "wasmd",
+                data_dir: "/wasmd/.wasmd",
+                chain_id: "localwasmd",
+                denom: "ustake",
+            }),
+            Chain::Custom => None,
+        }
+    }
+}
+
+/// Commands of cosmos-sdk v0.45 / v0.46 based chains
+const INIT: &str = "{binary} init {moniker} --chain-id {chain_id} --home {home}";
+const KEYS_ADD: &str = "{binary} keys add {key} --keyring-backend test --home {home}";
+const KEYS_SHOW: &str = "{binary} keys show {key} -a --keyring-backend test --home {home}";
+const ADD_GENESIS_ACCOUNT: &str = "{binary} add-genesis-account {address} {coins} --home {home}";
+const GENTX: &str = "{binary} gentx {key} {amount} --chain-id {chain_id} --keyring-backend test --home {home} --output-document {output}";
+const COLLECT_GENTXS: &str = "{binary} collect-gentxs --gentx-dir {gentx_dir} --home {home}";
+const START: &str = "{binary} start --home {home}";
+
+/// Local chain settings with preset defaults resolved
+pub struct ChainProfile {
+    pub image: String,
+    pub binary: String,
+    pub data_dir: String,
+    pub chain_id: String,
+    pub denom: String,
+    pub init: String,
+    pub keys_add: String,
+    pub keys_show: String,
+    pub add_genesis_account: String,
+    pub gentx: String,
+    pub collect_gentxs: String,
+    pub start: String,
+}
+
+impl ChainProfile {
+    pub fn resolve(conf: &LocalnetConfig) -> Result<Self> {
+        let preset = conf.chain.preset();
+        let value = |field: &str, configured: &Option<String>, preset: Option<&'static str>| match (
+            configured, preset,
+        ) {
+            (Some(v), _) => Ok(v.clone()),
+            (None, Some(p)) => Ok(p.to_string()),
+            (None, None) => bail!("`localnet.{field}` is required for custom chain"),
+        };
+        let commands = &conf.commands;
+        let template = |configured: &Option<String>, default: &str| {
+            configured.clone().unwrap_or_else(|| default.to_string())
+        };
+
+        Ok(ChainProfile {
+            image: value("image", &conf.image, preset.as_ref().map(|p| p.image))?,
+            binary: value("binary", &conf.binary, preset.as_ref().map(|p| p.binary))?,
+            data_dir: value(
+                "data_dir",
+                &conf.data_dir,
+                preset.as_ref().map(|p| p.data_dir),
+            )?,
+            chain_id: value(
+                "chain_id",
+                &conf.chain_id,
+                preset.as_ref().map(|p| p.chain_id),
+            )?,
+            denom: value("denom", &conf.denom, preset.as_ref().map(|p| p.denom))?,
+            init: template(&commands.init, INIT),
+            keys_add: template(&commands.keys_add, KEYS_ADD),
+            keys_show: template(&commands.keys_show, KEYS_SHOW),
+            add_genesis_account: template(&commands.add_genesis_account, ADD_GENESIS_ACCOUNT),
+            gentx: template(&commands.gentx, GENTX),
+            collect_gentxs: template(&commands.collect_gentxs, COLLECT_GENTXS),
+            start: template(&commands.start, START),
+        })
+    }
+
+    /// Render command template, `{binary}` and `{chain_id}` are always available
+    pub fn render(&self, template: &str, vars: &[(&str, &str)]) -> String {
+        [
+            ("binary", self.binary.as_str()),
+            ("chain_id", self.chain_id.as_str()),
+        ]
+        .iter()
+        .chain(vars)
+        .fold(template.to_string(), |cmd, (k, v)| {
+            cmd.replace(&format!("{{{k}}}"), v)
+        })
+    }
+
+    /// Amount without denom is in the chain's denom, eg. `100` -> `100uosmo`
+    pub fn coins(&self, amount: &str) -> String {
+        if !amount.is_empty() && amount.chars().all(|c| c.is_ascii_digit()) {
+            format!("{amount}{}", self.denom)
+        } else {
+            amount.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_preset_with_overrides() {
+        let conf = LocalnetConfig {
+            chain: Chain::Juno,
+            image: Some("ghcr.io/cosmoscontracts/juno:12.0.0".to_string()),
+            ..Default::default()
+        };
+        let profile = ChainProfile::resolve(&conf).unwrap();
+
+        assert_eq!(profile.image, "ghcr.io/cosmoscontracts/juno:12.0.0");
+        assert_eq!(profile.binary, "junod");
+        assert_eq!(profile.denom, "ujuno");
+        assert_eq!(profile.coins("100"), "100ujuno");
+        assert_eq!(profile.coins("100uatom"), "100uatom");
+    }
+
+    #[test]
+    fn custom_chain_requires_all_settings() {
+        let conf = LocalnetConfig {
+            chain: Chain::Custom,
+            image: Some("neutron-org/neutron:latest".to_string()),
+            ..Default::default()
+        };
+        assert!(ChainProfile::resolve(&conf).is_err());
+    }
+
+    #[test]
+    fn render_command_template() {
+        let mut conf = LocalnetConfig::default();
+        conf.commands.add_genesis_account = Some(
+            "{binary} genesis add-genesis-account {address} {coins} --home {home}".to_string(),
+        );
+        let profile = ChainProfile::resolve(&conf).unwrap();
+
+        assert_eq!(
+            profile.render(
+                &profile.add_genesis_account,
+                &[
+                    ("address", "osmo1abc"),
+                    ("coins", "1uosmo"),
+                    ("home", "/nodes/validator")
+                ]
+            ),
+            "osmosisd genesis add-genesis-account osmo1abc 1uosmo --home /nodes/validator"
+        );
+        assert_eq!(
+            profile.render(
+                &profile.init,
+                &[("moniker", "val2"), ("home", "/nodes/val2")]
+            ),
+            "osmosisd init val2 --chain-id localosmosis --home /nodes/val2"
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use config::Map;
+use data_doc::{DataDoc, GetDataDocs};
+use data_doc_derive::GetDataDocs;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::chain::Chain;
+
+#[derive(Serialize, Deserialize, GetDataDocs)]
+pub struct LocalnetConfig {
+    /// Name of the docker container running the local chain.
+    /// When started by `beaker localnet start`, it is the container of the first validator.
+    pub container: String,
+
+    /// Chain preset providing defaults for image, binary, home, chain id, denom and commands,
+    /// one of "osmosis", "juno", "wasmd" or "custom" for specifying all of them
+    #[serde(default)]
+    pub chain: Chain,
+
+    /// Home directory of the chain inside the container, which is captured by snapshots.
+    /// It must be a docker volume or bind mount, as it is for validators started by `beaker localnet start`.
+    #[serde(default)]
+    pub data_dir: Option<String>,
+
+    /// Docker image containing the chain binary, used by `beaker localnet start`
+    #[serde(default)]
+    pub image: Option<String>,
+
+    /// Chain binary inside the image
+    #[serde(default)]
+    pub binary: Option<String>,
+
+    #[serde(default)]
+    pub chain_id: Option<String>,
+
+    /// Staking and fee denom of the local chain
+    #[serde(default)]
+    pub denom: Option<String>,
+
+    /// Templates of chain binary commands run by `beaker localnet start`, for images whose cli differs from the preset, eg.
+    /// `{ add_genesis_account = "{binary} genesis add-genesis-account {address} {coins} --home {home}" }`.
+    /// Available placeholders are `{binary}`, `{home}`, `{chain_id}`, `{moniker}`, `{key}`, `{address}`,
+    /// `{coins}`, `{amount}`, `{output}` and `{gentx_dir}`
+    #[serde(default)]
+    pub commands: CommandTemplates,
+
+    /// Validators started by `beaker localnet start`, each runs in its own container.
+    /// Validator named after an account in global config uses its mnemonic.
+    /// Amount without denom is in the chain's denom, eg.
+    /// `[{ name = "validator", stake = "100000000" }, { name = "val2", stake = "50000000" }]`
+    #[serde(default = "default_validators")]
+    pub validators: Vec<LocalnetValidator>,
+
+    /// Overrides of genesis values by their dot separated path, applied before the first start
+    /// and before validator accounts are added, eg.
+    /// `{ "app_state.gov.voting_params.voting_period" = "30s", "app_state.wasm.params.code_upload_access" = { permission = "Everybody" } }`
+    #[serde(default)]
+    pub genesis: Map<String, GenesisValue>,
+
+    /// Genesis file, relative to project root, used instead of the one generated by the chain binary.
+    /// Validators and overrides are still applied on top of it.
+    #[serde(default)]
+    pub genesis_file: Option<String>,
+
+    /// Genesis balance of every account in global `accounts` config, so they can sign right after the first start.
+    /// Amount without denom is in the chain's denom.
+    #[serde(default = "default_account_balance")]
+    pub account_balance: String,
+
+    /// Genesis balances overriding `account_balance` for specific accounts, eg. `{ test1 = "1uosmo" }`
+    #[serde(default)]
+    pub account_balances: Map<String, String>,
+
+    /// Names of keys in OS keyring (`beaker key`) to fund in genesis with `account_balance`,
+    /// keyring can not be listed so they have to be named explicitly
+    #[serde(default)]
+    pub keyring_accounts: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, GetDataDocs, Clone, Debug)]
+pub struct LocalnetValidator {
+    pub name: String,
+
+    /// Self delegation of the validator, which determines its voting power
+    pub stake: String,
+
+    /// Genesis balance of the validator, defaults to stake plus 10000000000 for paying fees
+    #[serde(default)]
+    pub balance: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, GetDataDocs, Clone, Debug, Default)]
+pub struct CommandTemplates {
+    pub init: Option<String>,
+    pub keys_add: Option<String>,
+    pub keys_show: Option<String>,
+    pub add_genesis_account: Option<String>,
+    pub gentx: Option<String>,
+    pub collect_gentxs: Option<String>,
+    pub start: Option<String>,
+}
+
+/// Any json value
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(transparent)]
+pub struct GenesisValue(pub serde_json::Value);
+
+impl GetDataDocs for GenesisValue {
+    fn get_data_docs() -> Vec<DataDoc> {
+        vec![]
+    }
+}
+
+fn default_account_balance() -> String {
+    "100000000000".to_string()
+}
+
+fn default_validators() -> Vec<LocalnetValidator> {
+    vec![LocalnetValidator {
+        name: "validator".to_string(),
+        stake: "100000000000".to_string(),
+        balance: None,
+    }]
+}
+
+impl Default for LocalnetConfig {
+    fn default() -> Self {
+        Self {
+            container: "localosmosis".to_string(),
+            chain: Chain::default(),
+            data_dir: None,
+            image: None,
+            binary: None,
+            chain_id: None,
+            denom: None,
+            commands: CommandTemplates::default(),
+            validators: default_validators(),
+            genesis: Map::new(),
+            genesis_file: None,
+            account_balance: default_account_balance(),
+            account_balances: Map::new(),
+            keyring_accounts: vec![],
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::support::command::run_command;
+use crate::support::progress::Progress;
+use anyhow::{bail, Context, Result};
+
+/// Image used for working with the data directory, the chain image itself may not have the tools
+const HELPER_IMAGE: &str = "busybox";
+
+pub fn is_running(container: &str) -> Result<bool> {
+    let output = Command::new("docker")
+        .args(["inspect", "--format", "{{.State.Running}}", container])
+        .output()
+        .with_context(|| "Unable to run `docker`, make sure it is installed")?;
+    if !output.status.success() {
+        bail!("Unable to find container `{container}`, make sure the local chain is created");
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim() == "true")
+}
+
+pub fn stop(container: &str) -> Result<()> {
+    Progress::spinner(&format!("Stopping {container}")).end_with(run_command(
+        Command::new("docker").args(["stop", container]),
+    ))
+}
+
+pub fn start(container: &str) -> Result<()> {
+    Progress::spinner(&format!("Starting {container}")).end_with(run_command(
+        Command::new("docker").args(["start", container]),
+    ))
+}
+
+/// Run `f` with the container stopped, so that the data directory is consistent,
+/// and start it again afterwards if it was running
+pub fn with_stopped<T>(container: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let was_running = is_running(container)?;
+    if was_running {
+        stop(container)?;
+    }
+
+    let result = f();
+
+    if was_running {
+        start(container)?;
+    }
+    result
+}
+
+fn temp_path(name: &str) -> Result<PathBuf> {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+    Ok(std::env::temp_dir().join(format!("beaker-{nanos}-{name}")))
+}
+
+/// Read file from the container, works whether the container is running or not
+pub fn read_file(container: &str, path: &str) -> Result<String> {
+    let tmp = temp_path("read")?;
+    run_command(Command::new("docker").args([
+        "cp",
+        &format!("{container}:{path}"),
+        &tmp.to_string_lossy(),
+    ]))
+    .with_context(|| format!("Unable to copy `{path}` from container `{container}`"))?;
+    let content = fs::read_to_string(&tmp);
+    fs::remove_file(&tmp).ok();
+    Ok(content?)
+}
+
+/// Write file into the container, works whether the container is running or not
+pub fn write_file(container: &str, path: &str, content: &str) -> Result<()> {
+    let tmp = temp_path("write")?;
+    fs::write(&tmp, content)?;
+    let result = run_command(Command::new("docker").args([
+        "cp",
+        &tmp.to_string_lossy(),
+        &format!("{container}:{path}"),
+    ]))
+    .with_context(|| format!("Unable to copy `{path}` into container `{container}`"));
+    fs::remove_file(&tmp).ok();
+    result
+}
+
+/// Run shell script in helper container with volumes of the chain container and additional `-v` mounts
+pub fn run_with_volumes(container: &str, mounts: &[String], script: &str) -> Result<()> {
+    let mut cmd = Command::new("docker");
+    cmd.args(["run", "--rm", "--volumes-from", container]);
+    for mount in mounts {
+        cmd.args(["-v", mount]);
+    }
+    run_command(cmd.args([HELPER_IMAGE, "sh", "-c", script]))
+}
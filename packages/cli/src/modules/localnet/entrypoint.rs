@@ -0,0 +1,128 @@
+use super::config::LocalnetConfig;
+use super::genesis::GoDuration;
+use super::ops;
+use crate::framework::{Context, Module};
+use anyhow::Result;
+use clap::Subcommand;
+
+#[derive(Subcommand, Debug)]
+pub enum LocalnetCmd {
+    /// Start validators declared in `localnet.validators` with docker compose, initializing the chain on first start
+    Start {
+        /// Discard existing chain data and start over from a new genesis
+        #[clap(long)]
+        fresh: bool,
+    },
+    /// Stop validators started by `beaker localnet start`, chain data is kept
+    Stop,
+    /// Print logs of the local chain container
+    Logs {
+        /// Keep streaming new logs until interrupted
+        #[clap(short, long)]
+        follow: bool,
+
+        /// Only print lines containing the pattern (case insensitive), repeat to match any of them
+        #[clap(long)]
+        filter: Vec<String>,
+
+        /// Number of lines from the end of the logs to start from
+        #[clap(long, default_value = "100")]
+        tail: String,
+    },
+    /// Save the chain's data directory as a named snapshot, to be restored later
+    Snapshot {
+        name: String,
+
+        /// Overwrite existing snapshot with the same name
+        #[clap(long)]
+        force: bool,
+    },
+    /// Reset the chain's data directory to a snapshot
+    Restore { name: String },
+    /// Wait until the chain has produced the number of blocks, for height based logic only:
+    /// block time follows the validators' clocks, use `localnet timing` to shorten time based periods instead
+    Advance {
+        #[clap(long)]
+        blocks: u64,
+
+        #[clap(short, long, default_value = "local")]
+        network: String,
+
+        /// Number of seconds to wait before giving up
+        #[clap(long, default_value = "600")]
+        wait_timeout: u64,
+    },
+    /// Shorten block time, voting period or unbonding time so that time dependent logic can be exercised quickly
+    Timing {
+        /// Time between blocks (tendermint's `timeout_commit`), eg. `200ms`
+        #[clap(long)]
+        block_time: Option<GoDuration>,
+
+        /// Gov deposit and voting period, eg. `30s`, requires `--reset`
+        #[clap(long)]
+        voting_period: Option<GoDuration>,
+
+        /// Staking unbonding time, eg. `2m`, requires `--reset`
+        #[clap(long)]
+        unbonding_time: Option<GoDuration>,
+
+        /// Restart the chain from genesis, which is required for genesis parameters to take effect
+        #[clap(long)]
+        reset: bool,
+    },
+}
+
+pub struct LocalnetModule {}
+
+impl<'a> Module<'a, LocalnetConfig, LocalnetCmd, anyhow::Error> for LocalnetModule {
+    fn execute<Ctx: Context<'a, LocalnetConfig>>(
+        ctx: Ctx,
+        cmd: &LocalnetCmd,
+    ) -> Result<(), anyhow::Error> {
+        match cmd {
+            LocalnetCmd::Start { fresh } => {
+                ops::start(&ctx, *fresh)?;
+                Ok(())
+            }
+            LocalnetCmd::Stop => ops::stop(&ctx),
+            LocalnetCmd::Logs {
+                follow,
+                filter,
+                tail,
+            } => ops::logs(&ctx, *follow, filter, tail),
+            LocalnetCmd::Snapshot { name, force } => {
+                ops::snapshot(&ctx, name, *force)?;
+                Ok(())
+            }
+            LocalnetCmd::Restore { name } => {
+                ops::restore(&ctx, name)?;
+                Ok(())
+            }
+            LocalnetCmd::Advance {
+                blocks,
+                network,
+                wait_timeout,
+            } => {
+                ops::advance(&ctx, *blocks, network, *wait_timeout)?;
+                Ok(())
+            }
+            LocalnetCmd::Timing {
+                block_time,
+                voting_period,
+                unbonding_time,
+                reset,
+            } => {
+                ops::timing(
+                    &ctx,
+                    &ops::TimingArgs {
+                        block_time: *block_time,
+                        voting_period: *voting_period,
+                        unbonding_time: *unbonding_time,
+                        reset: *reset,
+                    },
+                )?;
+                Ok(())
+            }
+        }
+    }
+}
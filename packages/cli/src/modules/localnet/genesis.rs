@@ -0,0 +1,245 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+pub const GENESIS_FILE: &str = "config/genesis.json";
+pub const CONFIG_FILE: &str = "config/config.toml";
+
+pub const VOTING_PERIOD: &str = "app_state.gov.voting_params.voting_period";
+pub const MAX_DEPOSIT_PERIOD: &str = "app_state.gov.deposit_params.max_deposit_period";
+pub const UNBONDING_TIME: &str = "app_state.staking.params.unbonding_time";
+/// Gov periods of sdk v0.47+ chains, replacing `voting_params` and `deposit_params`
+const GOV_PARAMS_VOTING_PERIOD: &str = "app_state.gov.params.voting_period";
+const GOV_PARAMS_MAX_DEPOSIT_PERIOD: &str = "app_state.gov.params.max_deposit_period";
+
+/// Voting and deposit period of a generated localnet genesis, so that proposals pass within a deploy
+pub const LOCALNET_VOTING_PERIOD: Duration = Duration::from_secs(20);
+
+/// Duration in go format as used by genesis and tendermint config, eg. `500ms`, `30s`, `5m`, `1h`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GoDuration(pub Duration);
+
+impl FromStr for GoDuration {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split = s
+            .find(|c: char| !c.is_ascii_digit())
+            .with_context(|| format!("`{s}` has no unit, eg. `30s`"))?;
+        let (amount, unit) = s.split_at(split);
+        let amount: u64 = amount
+            .parse()
+            .with_context(|| format!("`{s}` does not contain valid amount"))?;
+        let duration = match unit {
+            "ms" => Duration::from_millis(amount),
+            "s" => Duration::from_secs(amount),
+            "m" => Duration::from_secs(amount * 60),
+            "h" => Duration::from_secs(amount * 60 * 60),
+            "d" => Duration::from_secs(amount * 60 * 60 * 24),
+            _ => bail!("Unknown duration unit `{unit}`, expected one of ms, s, m, h, d"),
+        };
+        Ok(GoDuration(duration))
+    }
+}
+
+impl GoDuration {
+    /// Proto json duration, which only accepts seconds, eg. `172800s`
+    pub fn to_proto_json(self) -> String {
+        format!("{}s", self.0.as_secs())
+    }
+
+    pub fn to_go(self) -> String {
+        format!("{}ms", self.0.as_millis())
+    }
+}
+
+/// Set value at dot separated path, eg. `app_state.gov.voting_params.voting_period`,
+/// intermediate objects are created when missing
+pub fn set_path(genesis: &mut Value, path: &str, value: Value) -> Result<()> {
+    let mut current = genesis;
+    for key in path.split('.') {
+        current = match current {
+            Value::Object(map) => map
+                .entry(key.to_string())
+                .or_insert_with(|| Value::Object(Default::default())),
+            Value::Array(items) => {
+                let index: usize = key
+                    .parse()
+                    .with_context(|| format!("`{key}` of `{path}` is not an array index"))?;
+                items
+                    .get_mut(index)
+                    .with_context(|| format!("Index `{key}` of `{path}` is out of bounds"))?
+            }
+            _ => bail!("Unable to set `{path}`, `{key}` is not in an object or array"),
+        };
+    }
+    *current = value;
+    Ok(())
+}
+
+/// Set voting and max deposit period to `period` in whichever gov params layout the genesis has
+pub fn set_gov_periods(genesis: &mut Value, period: GoDuration) -> Result<()> {
+    for path in [
+        VOTING_PERIOD,
+        MAX_DEPOSIT_PERIOD,
+        GOV_PARAMS_VOTING_PERIOD,
+        GOV_PARAMS_MAX_DEPOSIT_PERIOD,
+    ] {
+        if genesis
+            .pointer(&format!("/{}", path.replace('.', "/")))
+            .is_some()
+        {
+            set_path(genesis, path, Value::String(period.to_proto_json()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace every string value equal to `from` denom, used for replacing default `stake` denom of `init`
+pub fn replace_denom(genesis: &mut Value, from: &str, to: &str) {
+    match genesis {
+        Value::String(s) if s == from => *s = to.to_string(),
+        Value::Array(items) => items.iter_mut().for_each(|v| replace_denom(v, from, to)),
+        Value::Object(map) => map.values_mut().for_each(|v| replace_denom(v, from, to)),
+        _ => {}
+    }
+}
+
+/// Set `timeout_commit` in tendermint's config.toml, which determines block time
+pub fn set_timeout_commit(config: &str, timeout_commit: &GoDuration) -> Result<String> {
+    let mut config: toml::Value = toml::from_str(config)?;
+    let consensus = config
+        .get_mut("consensus")
+        .and_then(|c| c.as_table_mut())
+        .with_context(|| "Unable to find `[consensus]` in config.toml")?;
+    consensus.insert(
+        "timeout_commit".to_string(),
+        toml::Value::String(timeout_commit.to_go()),
+    );
+    Ok(toml::to_string(&config)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_go_duration() {
+        assert_eq!(
+            "500ms".parse::<GoDuration>().unwrap().0,
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            "2h".parse::<GoDuration>().unwrap().0,
+            Duration::from_secs(7200)
+        );
+        assert_eq!("30s".parse::<GoDuration>().unwrap().to_proto_json(), "30s");
+        assert_eq!("1m".parse::<GoDuration>().unwrap().to_go(), "60000ms");
+        assert!("30".parse::<GoDuration>().is_err());
+        assert!("30y".parse::<GoDuration>().is_err());
+    }
+
+    #[test]
+    fn set_genesis_path() {
+        let mut genesis = json!({
+            "app_state": {
+                "gov": { "voting_params": { "voting_period": "172800s" } },
+                "bank": { "balances": [{ "address": "osmo1a" }] }
+            }
+        });
+        set_path(&mut genesis, VOTING_PERIOD, json!("30s")).unwrap();
+        set_path(&mut genesis, UNBONDING_TIME, json!("60s")).unwrap();
+        set_path(
+            &mut genesis,
+            "app_state.bank.balances.0.address",
+            json!("osmo1b"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            genesis["app_state"]["gov"]["voting_params"]["voting_period"],
+            "30s"
+        );
+        assert_eq!(
+            genesis["app_state"]["staking"]["params"]["unbonding_time"],
+            "60s"
+        );
+        assert_eq!(
+            genesis["app_state"]["bank"]["balances"][0]["address"],
+            "osmo1b"
+        );
+        assert!(set_path(
+            &mut genesis,
+            "app_state.bank.balances.5.address",
+            json!("x")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn set_gov_periods_of_either_layout() {
+        let mut v1beta1 = json!({
+            "app_state": { "gov": {
+                "voting_params": { "voting_period": "172800s" },
+                "deposit_params": { "max_deposit_period": "172800s" }
+            } }
+        });
+        set_gov_periods(&mut v1beta1, GoDuration(LOCALNET_VOTING_PERIOD)).unwrap();
+        assert_eq!(
+            v1beta1["app_state"]["gov"]["voting_params"]["voting_period"],
+            "20s"
+        );
+        assert_eq!(
+            v1beta1["app_state"]["gov"]["deposit_params"]["max_deposit_period"],
+            "20s"
+        );
+        assert!(v1beta1["app_state"]["gov"].get("params").is_none());
+
+        let mut v1 = json!({
+            "app_state": { "gov": { "params": { "voting_period": "172800s", "max_deposit_period": "172800s" } } }
+        });
+        set_gov_periods(&mut v1, GoDuration(LOCALNET_VOTING_PERIOD)).unwrap();
+        assert_eq!(v1["app_state"]["gov"]["params"]["voting_period"], "20s");
+        assert!(v1["app_state"]["gov"].get("voting_params").is_none());
+    }
+
+    #[test]
+    fn replace_default_denom() {
+        let mut genesis = json!({
+            "app_state": {
+                "staking": { "params": { "bond_denom": "stake" } },
+                "gov": { "deposit_params": { "min_deposit": [{ "denom": "stake", "amount": "10000000" }] } },
+                "mint": { "params": { "mint_denom": "stake", "note": "stake is kept in other strings" } }
+            }
+        });
+        replace_denom(&mut genesis, "stake", "uosmo");
+
+        assert_eq!(
+            genesis["app_state"]["staking"]["params"]["bond_denom"],
+            "uosmo"
+        );
+        assert_eq!(
+            genesis["app_state"]["gov"]["deposit_params"]["min_deposit"][0]["denom"],
+            "uosmo"
+        );
+        assert_eq!(
+            genesis["app_state"]["mint"]["params"]["mint_denom"],
+            "uosmo"
+        );
+        assert_eq!(
+            genesis["app_state"]["mint"]["params"]["note"],
+            "stake is kept in other strings"
+        );
+    }
+
+    #[test]
+    fn set_block_time() {
+        let config = "moniker = \"node\"\n\n[consensus]\ntimeout_commit = \"5s\"\n";
+        let updated = set_timeout_commit(config, &"200ms".parse().unwrap()).unwrap();
+        assert!(updated.contains("timeout_commit = \"200ms\""));
+        assert!(updated.contains("moniker = \"node\""));
+    }
+}
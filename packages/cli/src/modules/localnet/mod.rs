@@ -0,0 +1,10 @@
+pub mod chain;
+pub mod config;
+pub mod container;
+pub mod entrypoint;
+pub mod genesis;
+pub mod node;
+pub mod ops;
+
+pub use crate::modules::localnet::config::LocalnetConfig;
+pub use crate::modules::localnet::entrypoint::{LocalnetCmd, LocalnetModule};
@@ -0,0 +1,224 @@
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+pub const P2P_PORT: u16 = 26656;
+pub const RPC_PORT: u16 = 26657;
+pub const API_PORT: u16 = 1317;
+pub const GRPC_PORT: u16 = 9090;
+
+/// Extra genesis balance of validators on top of their stake, for paying fees
+const VALIDATOR_FEE_BALANCE: u128 = 10_000_000_000;
+
+/// Tendermint node id from `node_key.json`, which is hex of the first 20 bytes of sha256 of ed25519 public key
+pub fn node_id(node_key_json: &str) -> Result<String> {
+    let node_key: Value = serde_json::from_str(node_key_json)?;
+    let key = base64::decode(
+        node_key
+            .pointer("/priv_key/value")
+            .and_then(|v| v.as_str())
+            .with_context(|| "Unable to find private key in node_key.json")?,
+    )?;
+    if key.len() != 64 {
+        bail!("Unexpected ed25519 private key length in node_key.json");
+    }
+    // ed25519 private key is `seed || public key`
+    Ok(hex::encode(&Sha256::digest(&key[32..])[..20]))
+}
+
+/// Coin with amount increased by `extra`, eg. `100uosmo` + 5 -> `105uosmo`
+pub fn add_amount(coin: &str, extra: u128) -> Result<String> {
+    let split = coin
+        .find(|c: char| !c.is_ascii_digit())
+        .with_context(|| format!("Unable to parse `{coin}` as Coin."))?;
+    let (amount, denom) = coin.split_at(split);
+    let amount: u128 = amount
+        .parse()
+        .with_context(|| format!("`{coin}` does not contain valid amount"))?;
+    Ok(format!("{}{denom}", amount + extra))
+}
+
+pub fn validator_balance(stake: &str, balance: &Option<String>) -> Result<String> {
+    match balance {
+        Some(balance) => Ok(balance.clone()),
+        None => add_amount(stake, VALIDATOR_FEE_BALANCE),
+    }
+}
+
+fn table<'a>(value: &'a mut toml::Value, key: &str) -> Result<&'a mut toml::value::Table> {
+    value
+        .as_table_mut()
+        .with_context(|| "Invalid toml")?
+        .entry(key.to_string())
+        .or_insert_with(|| toml::Value::Table(Default::default()))
+        .as_table_mut()
+        .with_context(|| format!("`{key}` is not a table"))
+}
+
+/// Make rpc reachable from the host and connect the node to its peers
+pub fn configure_node(config: &str, persistent_peers: &[String]) -> Result<String> {
+    let mut config: toml::Value = toml::from_str(config)?;
+    table(&mut config, "rpc")?.insert(
+        "laddr".to_string(),
+        toml::Value::String(format!("tcp://0.0.0.0:{RPC_PORT}")),
+    );
+    let p2p = table(&mut config, "p2p")?;
+    p2p.insert(
+        "persistent_peers".to_string(),
+        toml::Value::String(persistent_peers.join(",")),
+    );
+    p2p.insert("addr_book_strict".to_string(), toml::Value::Boolean(false));
+    p2p.insert("allow_duplicate_ip".to_string(), toml::Value::Boolean(true));
+    Ok(toml::to_string(&config)?)
+}
+
+/// Enable api and grpc on all interfaces and accept zero fee txs
+pub fn configure_app(app: &str, denom: &str) -> Result<String> {
+    let mut app: toml::Value = toml::from_str(app)?;
+    app.as_table_mut().with_context(|| "Invalid toml")?.insert(
+        "minimum-gas-prices".to_string(),
+        toml::Value::String(format!("0{denom}")),
+    );
+    let api = table(&mut app, "api")?;
+    api.insert("enable".to_string(), toml::Value::Boolean(true));
+    api.insert(
+        "address".to_string(),
+        toml::Value::String(format!("tcp://0.0.0.0:{API_PORT}")),
+    );
+    let grpc = table(&mut app, "grpc")?;
+    grpc.insert("enable".to_string(), toml::Value::Boolean(true));
+    grpc.insert(
+        "address".to_string(),
+        toml::Value::String(format!("0.0.0.0:{GRPC_PORT}")),
+    );
+    Ok(toml::to_string(&app)?)
+}
+
+pub struct ComposeNode {
+    pub service: String,
+    pub container: String,
+    pub home: String,
+    /// Only the first validator exposes its endpoints to the host
+    pub expose_ports: bool,
+}
+
+/// docker compose file running every node from its home in `./nodes/<service>`
+pub fn compose_file(
+    image: &str,
+    data_dir: &str,
+    start_command: &str,
+    user: Option<&str>,
+    nodes: &[ComposeNode],
+) -> Result<String> {
+    let services = nodes
+        .iter()
+        .map(|n| {
+            let mut service = json!({
+                "image": image,
+                "container_name": n.container,
+                "entrypoint": ["sh", "-c"],
+                "command": [start_command],
+                "volumes": [format!("./nodes/{}:{data_dir}", n.home)],
+            });
+            if let Some(user) = user {
+                service["user"] = json!(user);
+            }
+            if n.expose_ports {
+                service["ports"] = json!([
+                    format!("{RPC_PORT}:{RPC_PORT}"),
+                    format!("{API_PORT}:{API_PORT}"),
+                    format!("{GRPC_PORT}:{GRPC_PORT}"),
+                ]);
+            }
+            (n.service.clone(), service)
+        })
+        .collect::<serde_json::Map<String, Value>>();
+
+    Ok(serde_yaml::to_string(&json!({ "services": services }))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_node_id() {
+        let key = [vec![1u8; 32], vec![2u8; 32]].concat();
+        let node_key = json!({
+            "priv_key": { "type": "tendermint/PrivKeyEd25519", "value": base64::encode(&key) }
+        })
+        .to_string();
+
+        assert_eq!(
+            node_id(&node_key).unwrap(),
+            hex::encode(&Sha256::digest([2u8; 32])[..20])
+        );
+        assert!(node_id(r#"{"priv_key":{"value":"AAAA"}}"#).is_err());
+    }
+
+    #[test]
+    fn add_fee_balance() {
+        assert_eq!(add_amount("100uosmo", 5).unwrap(), "105uosmo");
+        assert_eq!(
+            validator_balance("100uosmo", &None).unwrap(),
+            "10000000100uosmo"
+        );
+        assert_eq!(
+            validator_balance("100uosmo", &Some("1uosmo".to_string())).unwrap(),
+            "1uosmo"
+        );
+        assert!(add_amount("uosmo", 1).is_err());
+    }
+
+    #[test]
+    fn configure_node_and_app() {
+        let config = configure_node(
+            "[rpc]\nladdr = \"tcp://127.0.0.1:26657\"\n[p2p]\npersistent_peers = \"\"\n",
+            &["abc@val2:26656".to_string()],
+        )
+        .unwrap();
+        assert!(config.contains("laddr = \"tcp://0.0.0.0:26657\""));
+        assert!(config.contains("persistent_peers = \"abc@val2:26656\""));
+
+        let app = configure_app(
+            "minimum-gas-prices = \"\"\n[api]\nenable = false\n",
+            "uosmo",
+        )
+        .unwrap();
+        assert!(app.contains("minimum-gas-prices = \"0uosmo\""));
+        assert!(app.contains("enable = true"));
+    }
+
+    #[test]
+    fn compose_only_exposes_first_node() {
+        let compose = compose_file(
+            "osmolabs/osmosis:12.2.0",
+            "/osmosis/.osmosisd",
+            "osmosisd start --home /osmosis/.osmosisd",
+            None,
+            &[
+                ComposeNode {
+                    service: "validator".to_string(),
+                    container: "localosmosis".to_string(),
+                    home: "validator".to_string(),
+                    expose_ports: true,
+                },
+                ComposeNode {
+                    service: "val2".to_string(),
+                    container: "localosmosis-val2".to_string(),
+                    home: "val2".to_string(),
+                    expose_ports: false,
+                },
+            ],
+        )
+        .unwrap();
+        let compose: Value = serde_yaml::from_str(&compose).unwrap();
+
+        assert_eq!(compose["services"]["validator"]["ports"][0], "26657:26657");
+        assert!(compose["services"]["val2"].get("ports").is_none());
+        assert_eq!(
+            compose["services"]["val2"]["volumes"][0],
+            "./nodes/val2:/osmosis/.osmosisd"
+        );
+    }
+}
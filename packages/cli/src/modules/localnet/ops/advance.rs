@@ -0,0 +1,82 @@
+use std::time::{Duration, Instant};
+
+use crate::attrs_format;
+use crate::framework::config::Network;
+use crate::framework::Context;
+use crate::modules::localnet::config::LocalnetConfig;
+use crate::support::future::block;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::progress::Progress;
+use crate::support::transport::rpc_request;
+use anyhow::{bail, Context as _, Result};
+use cosmrs::rpc::{self, Client as _};
+use serde::Serialize;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+async fn latest_height(network: &Network, client: &rpc::HttpClient) -> Result<u64> {
+    Ok(rpc_request(network, client.status())
+        .await
+        .with_context(|| "Unable to query status of the local chain, make sure it is running")?
+        .sync_info
+        .latest_block_height
+        .value())
+}
+
+/// Wait until the chain has produced `blocks` more blocks,
+/// combine with `localnet timing --block-time` to get through them quickly.
+/// Block time is the median of the validators' clocks, so chain time keeps moving at wall-clock speed,
+/// even moving `genesis_time` back would only affect the first block after a reset
+pub fn advance<'a, Ctx: Context<'a, LocalnetConfig>>(
+    ctx: &Ctx,
+    blocks: u64,
+    network: &str,
+    timeout: u64,
+) -> Result<AdvanceResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+    let client = ctx.client(network_info.clone()).rpc_client()?;
+
+    let progress = Progress::spinner(&format!("Waiting for {blocks} blocks"));
+    let heights = progress.end_with(block(async {
+        let from_height = latest_height(&network_info, &client).await?;
+        let target = from_height + blocks;
+        let started = Instant::now();
+        loop {
+            let height = latest_height(&network_info, &client).await?;
+            if height >= target {
+                return Ok((from_height, height));
+            }
+            if started.elapsed() > Duration::from_secs(timeout) {
+                bail!("Timed out at height {height} while waiting for height {target}");
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }))?;
+
+    let advance_response = AdvanceResponse {
+        from_height: heights.0,
+        to_height: heights.1,
+    };
+    advance_response.log();
+    Ok(advance_response)
+}
+
+#[derive(Serialize)]
+pub struct AdvanceResponse {
+    pub from_height: u64,
+    pub to_height: u64,
+}
+
+impl OpResponseDisplay for AdvanceResponse {
+    fn headline() -> &'static str {
+        "Localnet advanced"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | from_height, to_height }
+    }
+}
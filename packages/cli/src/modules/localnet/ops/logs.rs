@@ -0,0 +1,88 @@
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::thread;
+
+use crate::framework::Context;
+use crate::modules::localnet::config::LocalnetConfig;
+use anyhow::{bail, Context as _, Result};
+use console::style;
+
+/// Whether the log line contains any of the filters, every line matches when there is no filter
+pub fn matches_filters(line: &str, filters: &[String]) -> bool {
+    let line = line.to_lowercase();
+    filters.is_empty() || filters.iter().any(|f| line.contains(&f.to_lowercase()))
+}
+
+/// Colorize tendermint log level, eg. `3:04PM ERR failed to execute message ...`
+fn colorize(line: &str) -> String {
+    let level = [" ERR ", " WRN ", " INF ", " DBG "]
+        .into_iter()
+        .find(|l| line.contains(l));
+    match level {
+        Some(" ERR ") => style(line).red().to_string(),
+        Some(" WRN ") => style(line).yellow().to_string(),
+        Some(" DBG ") => style(line).dim().to_string(),
+        _ => line.to_string(),
+    }
+}
+
+fn print_lines(reader: impl Read + Send + 'static, filters: Vec<String>) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        BufReader::new(reader)
+            .lines()
+            .flatten()
+            .filter(|line| matches_filters(line, &filters))
+            .for_each(|line| println!("{}", colorize(&line)));
+    })
+}
+
+pub fn logs<'a, Ctx: Context<'a, LocalnetConfig>>(
+    ctx: &Ctx,
+    follow: bool,
+    filters: &[String],
+    tail: &str,
+) -> Result<()> {
+    let container = ctx.config()?.container;
+
+    let mut cmd = Command::new("docker");
+    cmd.args(["logs", "--tail", tail]);
+    if follow {
+        cmd.arg("--follow");
+    }
+    let mut child = cmd
+        .arg(&container)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| "Unable to run `docker`, make sure it is installed")?;
+
+    // chain logs are written to both stdout and stderr of the container
+    let stdout = print_lines(child.stdout.take().unwrap(), filters.to_vec());
+    let stderr = print_lines(child.stderr.take().unwrap(), filters.to_vec());
+    stdout.join().ok();
+    stderr.join().ok();
+
+    if !child.wait()?.success() {
+        bail!(
+            "Unable to read logs of container `{container}`, make sure the local chain is running"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_any_filter_case_insensitive() {
+        let line = "3:04PM ERR failed to execute message; message index: 0: Error parsing into type counter::msg::ExecuteMsg module=wasm";
+        assert!(matches_filters(line, &[]));
+        assert!(matches_filters(line, &["WASM".to_string()]));
+        assert!(matches_filters(
+            line,
+            &["bank".to_string(), "err".to_string()]
+        ));
+        assert!(!matches_filters(line, &["bank".to_string()]));
+    }
+}
@@ -0,0 +1,11 @@
+pub mod advance;
+pub mod logs;
+pub mod snapshot;
+pub mod start;
+pub mod timing;
+
+pub use advance::advance;
+pub use logs::logs;
+pub use snapshot::{restore, snapshot};
+pub use start::{start, stop};
+pub use timing::{timing, TimingArgs};
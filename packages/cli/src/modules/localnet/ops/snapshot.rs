@@ -0,0 +1,160 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::localnet::chain::ChainProfile;
+use crate::modules::localnet::config::LocalnetConfig;
+use crate::modules::localnet::container::{run_with_volumes, with_stopped};
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::progress::Progress;
+use crate::support::state::STATE_DIR;
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+pub fn snapshot_dir(root: &Path) -> PathBuf {
+    root.join(STATE_DIR).join("localnet").join("snapshots")
+}
+
+fn snapshot_file(name: &str) -> String {
+    format!("{name}.tar.gz")
+}
+
+pub fn snapshot<'a, Ctx: Context<'a, LocalnetConfig>>(
+    ctx: &Ctx,
+    name: &str,
+    force: bool,
+) -> Result<SnapshotResponse> {
+    let conf = ctx.config()?;
+    let data_dir = ChainProfile::resolve(&conf)?.data_dir;
+    let container = conf.container;
+    let snapshot_dir = snapshot_dir(&ctx.root()?);
+    let file = snapshot_file(name);
+    let path = snapshot_dir.join(&file);
+
+    if path.exists() && !force {
+        bail!("Snapshot `{name}` already exists, use `--force` to overwrite it");
+    }
+    fs::create_dir_all(&snapshot_dir)?;
+    let snapshot_dir = snapshot_dir.canonicalize()?;
+
+    with_stopped(&container, || {
+        Progress::spinner(&format!("Archiving {data_dir}")).end_with(run_with_volumes(
+            &container,
+            &[format!("{}:/snapshots", snapshot_dir.to_string_lossy())],
+            &format!("tar czf /snapshots/{file} -C {data_dir} ."),
+        ))
+    })?;
+
+    let snapshot_response = SnapshotResponse {
+        name: name.to_string(),
+        container,
+        path: path.to_string_lossy().to_string(),
+    };
+    snapshot_response.log();
+    Ok(snapshot_response)
+}
+
+pub fn restore<'a, Ctx: Context<'a, LocalnetConfig>>(
+    ctx: &Ctx,
+    name: &str,
+) -> Result<RestoreResponse> {
+    let conf = ctx.config()?;
+    let data_dir = ChainProfile::resolve(&conf)?.data_dir;
+    let container = conf.container;
+    let snapshot_dir = snapshot_dir(&ctx.root()?);
+    let file = snapshot_file(name);
+
+    if !snapshot_dir.join(&file).exists() {
+        bail!(
+            "Unable to find snapshot `{name}`, available snapshots: {}",
+            list(&snapshot_dir)?.join(", ")
+        );
+    }
+    let snapshot_dir = snapshot_dir.canonicalize()?;
+
+    with_stopped(&container, || {
+        Progress::spinner(&format!("Restoring {data_dir}")).end_with(run_with_volumes(
+            &container,
+            &[format!("{}:/snapshots", snapshot_dir.to_string_lossy())],
+            &format!(
+                "find {data_dir} -mindepth 1 -delete && tar xzf /snapshots/{file} -C {data_dir}"
+            ),
+        ))
+    })?;
+
+    let restore_response = RestoreResponse {
+        name: name.to_string(),
+        container,
+    };
+    restore_response.log();
+    Ok(restore_response)
+}
+
+/// Names of existing snapshots
+pub fn list(snapshot_dir: &Path) -> Result<Vec<String>> {
+    if !snapshot_dir.exists() {
+        return Ok(vec![]);
+    }
+    let mut names = fs::read_dir(snapshot_dir)?
+        .flatten()
+        .filter_map(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .strip_suffix(".tar.gz")
+                .map(|n| n.to_string())
+        })
+        .collect::<Vec<String>>();
+    names.sort();
+    Ok(names)
+}
+
+#[derive(Serialize)]
+pub struct SnapshotResponse {
+    pub name: String,
+    pub container: String,
+    pub path: String,
+}
+
+impl OpResponseDisplay for SnapshotResponse {
+    fn headline() -> &'static str {
+        "Localnet snapshot created successfully!! 📸 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | name, container, path }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RestoreResponse {
+    pub name: String,
+    pub container: String,
+}
+
+impl OpResponseDisplay for RestoreResponse {
+    fn headline() -> &'static str {
+        "Localnet snapshot restored successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | name, container }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+
+    #[test]
+    fn list_snapshots() {
+        let root = TempDir::new().unwrap();
+        let dir = snapshot_dir(root.path());
+        assert!(list(&dir).unwrap().is_empty());
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("proposal-passed.tar.gz"), "").unwrap();
+        fs::write(dir.join("funded.tar.gz"), "").unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+        assert_eq!(list(&dir).unwrap(), vec!["funded", "proposal-passed"]);
+    }
+}
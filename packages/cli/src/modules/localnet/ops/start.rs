@@ -0,0 +1,435 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::framework::config::Account;
+use crate::framework::Context;
+use crate::modules::key::config::SERVICE;
+use crate::modules::localnet::chain::ChainProfile;
+use crate::modules::localnet::config::LocalnetConfig;
+use crate::modules::localnet::genesis::{
+    replace_denom, set_gov_periods, set_path, GoDuration, GENESIS_FILE, LOCALNET_VOTING_PERIOD,
+};
+use crate::modules::localnet::node::{
+    compose_file, configure_app, configure_node, node_id, validator_balance, ComposeNode, P2P_PORT,
+    RPC_PORT,
+};
+use crate::support::chain_cache::ChainCache;
+use crate::support::command::run_command;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::progress::Progress;
+use crate::support::signer::{network_account_key, network_address, network_keyring_key};
+use crate::support::state::STATE_DIR;
+use anyhow::{bail, Context as _, Result};
+use keyring::Entry;
+use serde::Serialize;
+use serde_json::Value;
+
+const COMPOSE_FILE: &str = "docker-compose.yml";
+
+/// Denom set by `init`, replaced by the configured denom
+const INIT_DENOM: &str = "stake";
+
+/// Network served by the localnet, its `coin_type` and `key_algo` apply to prefunded accounts
+const LOCAL_NETWORK: &str = "local";
+
+pub fn localnet_dir(root: &Path) -> PathBuf {
+    root.join(STATE_DIR).join("localnet")
+}
+
+fn nodes_dir(root: &Path) -> PathBuf {
+    localnet_dir(root).join("nodes")
+}
+
+/// Run chain processes as the current user so that node homes stay editable from the host
+#[cfg(unix)]
+fn docker_user(dir: &Path) -> Result<Option<String>> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = fs::metadata(dir)?;
+    Ok(Some(format!("{}:{}", metadata.uid(), metadata.gid())))
+}
+
+#[cfg(not(unix))]
+fn docker_user(_dir: &Path) -> Result<Option<String>> {
+    Ok(None)
+}
+
+/// Run shell script with the chain image, node homes are mounted at `/nodes`
+fn run_script(profile: &ChainProfile, nodes_dir: &Path, message: &str, script: &str) -> Result<()> {
+    let mut cmd = Command::new("docker");
+    cmd.args(["run", "--rm"]);
+    if let Some(user) = docker_user(nodes_dir)? {
+        cmd.args(["--user", &user]);
+    }
+    cmd.args([
+        "-v",
+        &format!("{}:/nodes", nodes_dir.to_string_lossy()),
+        "--entrypoint",
+        "sh",
+        &profile.image,
+        "-c",
+        &format!("set -e\n{script}"),
+    ]);
+    Progress::stream(message).end_with(run_command(&mut cmd))
+}
+
+fn compose(root: &Path, container: &str, args: &[&str]) -> Result<()> {
+    run_command(
+        Command::new("docker")
+            .args(["compose", "-p", container, "-f"])
+            .arg(localnet_dir(root).join(COMPOSE_FILE))
+            .args(args),
+    )
+}
+
+fn validate(conf: &LocalnetConfig) -> Result<()> {
+    if conf.validators.is_empty() {
+        bail!("At least one validator is required in `localnet.validators`");
+    }
+    let mut names = HashSet::new();
+    for v in &conf.validators {
+        if v.name.is_empty()
+            || !v
+                .name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            bail!(
+                "Invalid validator name `{}`, only lowercase letters, digits and `-` are allowed",
+                v.name
+            );
+        }
+        if !names.insert(&v.name) {
+            bail!("Duplicated validator name `{}`", v.name);
+        }
+    }
+    Ok(())
+}
+
+fn container_name(conf: &LocalnetConfig, index: usize, name: &str) -> String {
+    if index == 0 {
+        conf.container.clone()
+    } else {
+        format!("{}-{name}", conf.container)
+    }
+}
+
+/// Genesis before validators are added, either the custom genesis file or the one generated by `init`
+/// with configured denom and short gov periods, with overrides applied on top
+fn base_genesis<'a, Ctx: Context<'a, LocalnetConfig>>(
+    ctx: &Ctx,
+    conf: &LocalnetConfig,
+    profile: &ChainProfile,
+    init_genesis: &str,
+) -> Result<Value> {
+    let mut genesis: Value = match &conf.genesis_file {
+        Some(file) => {
+            let path = ctx.root()?.join(file);
+            let mut genesis: Value =
+                serde_json::from_str(&fs::read_to_string(&path).with_context(|| {
+                    format!("Unable to read genesis file `{}`", path.display())
+                })?)
+                .with_context(|| format!("`{}` is not a valid genesis file", path.display()))?;
+            set_path(
+                &mut genesis,
+                "chain_id",
+                Value::String(profile.chain_id.clone()),
+            )?;
+            genesis
+        }
+        None => {
+            let mut genesis: Value = serde_json::from_str(init_genesis)?;
+            replace_denom(&mut genesis, INIT_DENOM, &profile.denom);
+            set_gov_periods(&mut genesis, GoDuration(LOCALNET_VOTING_PERIOD))?;
+            genesis
+        }
+    };
+
+    for (path, value) in &conf.genesis {
+        set_path(&mut genesis, path.trim_start_matches("$."), value.0.clone())
+            .with_context(|| format!("Unable to apply genesis override `{path}`"))?;
+    }
+    Ok(genesis)
+}
+
+/// Name, address and genesis balance of accounts from global config and keyring, except validators which are funded on their own
+fn prefunded_accounts<'a, Ctx: Context<'a, LocalnetConfig>>(
+    ctx: &Ctx,
+    conf: &LocalnetConfig,
+    profile: &ChainProfile,
+) -> Result<Vec<(String, String, String)>> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(LOCAL_NETWORK)
+        .with_context(|| format!("Unable to find network config: {LOCAL_NETWORK}"))?;
+    let is_validator = |name: &str| conf.validators.iter().any(|v| v.name == name);
+    let balance = |name: &str| {
+        profile.coins(
+            conf.account_balances
+                .get(name)
+                .unwrap_or(&conf.account_balance),
+        )
+    };
+
+    let mut keys = vec![];
+    for (name, account) in global_config.accounts() {
+        // remote signers are not contacted just for funding
+        if is_validator(name) || matches!(account, Account::FromRemoteSigner { .. }) {
+            continue;
+        }
+        keys.push((
+            name.clone(),
+            network_account_key(&ctx.chain_client(), &global_config, account, network_info)?,
+        ));
+    }
+    for name in conf.keyring_accounts.iter().filter(|n| !is_validator(n)) {
+        let secret = Entry::new(SERVICE, name)
+            .get_password()
+            .with_context(|| format!("Unable to find key `{name}` in keyring"))?;
+        keys.push((
+            name.clone(),
+            network_keyring_key(&ctx.chain_client(), &global_config, &secret, network_info)?,
+        ));
+    }
+
+    keys.into_iter()
+        .map(|(name, key)| {
+            let address = network_address(&ctx.chain_client(), &global_config, &key, network_info)?;
+            Ok((name.clone(), address.to_string(), balance(&name)))
+        })
+        .collect()
+}
+
+/// Initialize home of every validator, build genesis with their gentxs and connect them as peers
+fn init_nodes<'a, Ctx: Context<'a, LocalnetConfig>>(
+    ctx: &Ctx,
+    conf: &LocalnetConfig,
+    profile: &ChainProfile,
+    nodes_dir: &Path,
+) -> Result<()> {
+    let global_config = ctx.global_config()?;
+    let primary = &conf.validators[0].name;
+    let primary_home = format!("/nodes/{primary}");
+    fs::create_dir_all(nodes_dir)?;
+
+    let mut init_script = vec![];
+    for v in &conf.validators {
+        let home = format!("/nodes/{}", v.name);
+        let vars = [
+            ("moniker", v.name.as_str()),
+            ("key", &v.name),
+            ("home", &home),
+        ];
+        init_script.push(format!(
+            "{} > /dev/null 2>&1",
+            profile.render(&profile.init, &vars)
+        ));
+        let keys_add = profile.render(&profile.keys_add, &vars);
+        init_script.push(match global_config.accounts().get(&v.name) {
+            Some(Account::FromMnemonic { mnemonic, .. }) => {
+                format!("echo '{mnemonic}' | {keys_add} --recover > /dev/null")
+            }
+            _ => format!("{keys_add} > /dev/null 2>&1"),
+        });
+    }
+    run_script(
+        profile,
+        nodes_dir,
+        "Initializing validators",
+        &init_script.join("\n"),
+    )?;
+
+    let genesis_path = nodes_dir.join(primary).join(GENESIS_FILE);
+    let genesis = base_genesis(ctx, conf, profile, &fs::read_to_string(&genesis_path)?)?;
+    fs::write(&genesis_path, serde_json::to_string_pretty(&genesis)?)?;
+
+    let mut gentx_script = vec!["mkdir -p /nodes/gentxs".to_string()];
+    for (_, address, balance) in prefunded_accounts(ctx, conf, profile)? {
+        gentx_script.push(profile.render(
+            &profile.add_genesis_account,
+            &[
+                ("address", &address),
+                ("coins", &balance),
+                ("home", &primary_home),
+            ],
+        ));
+    }
+    for v in &conf.validators {
+        let home = format!("/nodes/{}", v.name);
+        let address = format!(
+            "$({})",
+            profile.render(&profile.keys_show, &[("key", &v.name), ("home", &home)])
+        );
+        let balance = validator_balance(
+            &profile.coins(&v.stake),
+            &v.balance.as_ref().map(|b| profile.coins(b)),
+        )?;
+        gentx_script.push(profile.render(
+            &profile.add_genesis_account,
+            &[
+                ("address", &address),
+                ("coins", &balance),
+                ("home", &primary_home),
+            ],
+        ));
+    }
+    for v in &conf.validators {
+        let home = format!("/nodes/{}", v.name);
+        if &v.name != primary {
+            gentx_script.push(format!(
+                "cp {primary_home}/{GENESIS_FILE} {home}/{GENESIS_FILE}"
+            ));
+        }
+        let output = format!("/nodes/gentxs/{}.json", v.name);
+        let gentx = profile.render(
+            &profile.gentx,
+            &[
+                ("key", &v.name),
+                ("amount", &profile.coins(&v.stake)),
+                ("home", &home),
+                ("output", &output),
+            ],
+        );
+        gentx_script.push(format!("{gentx} > /dev/null 2>&1"));
+    }
+    let collect_gentxs = profile.render(
+        &profile.collect_gentxs,
+        &[("gentx_dir", "/nodes/gentxs"), ("home", &primary_home)],
+    );
+    gentx_script.push(format!("{collect_gentxs} > /dev/null 2>&1"));
+    for v in conf.validators.iter().filter(|v| &v.name != primary) {
+        gentx_script.push(format!(
+            "cp {primary_home}/{GENESIS_FILE} /nodes/{}/{GENESIS_FILE}",
+            v.name
+        ));
+    }
+    run_script(
+        profile,
+        nodes_dir,
+        "Collecting gentxs",
+        &gentx_script.join("\n"),
+    )?;
+
+    let peers = conf
+        .validators
+        .iter()
+        .map(|v| {
+            let node_key =
+                fs::read_to_string(nodes_dir.join(&v.name).join("config/node_key.json"))?;
+            Ok(format!("{}@{}:{P2P_PORT}", node_id(&node_key)?, v.name))
+        })
+        .collect::<Result<Vec<String>>>()?;
+    for (i, v) in conf.validators.iter().enumerate() {
+        let config_dir = nodes_dir.join(&v.name).join("config");
+        let other_peers = peers
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, p)| p.clone())
+            .collect::<Vec<String>>();
+
+        let config_path = config_dir.join("config.toml");
+        let config = configure_node(&fs::read_to_string(&config_path)?, &other_peers)?;
+        fs::write(&config_path, config)?;
+
+        let app_path = config_dir.join("app.toml");
+        let app = configure_app(&fs::read_to_string(&app_path)?, &profile.denom)?;
+        fs::write(&app_path, app)?;
+    }
+    Ok(())
+}
+
+pub fn start<'a, Ctx: Context<'a, LocalnetConfig>>(
+    ctx: &Ctx,
+    fresh: bool,
+) -> Result<StartResponse> {
+    let conf = ctx.config()?;
+    let profile = ChainProfile::resolve(&conf)?;
+    let root = ctx.root()?;
+    validate(&conf)?;
+
+    let nodes_dir = nodes_dir(&root);
+    if fresh && nodes_dir.exists() {
+        if localnet_dir(&root).join(COMPOSE_FILE).exists() {
+            compose(&root, &conf.container, &["down"])?;
+        }
+        fs::remove_dir_all(&nodes_dir)?;
+        ChainCache::clear(&root)?;
+    }
+
+    let primary = &conf.validators[0].name;
+    let initialized = nodes_dir.join(primary).join(GENESIS_FILE).exists();
+    if !initialized {
+        init_nodes(ctx, &conf, &profile, &nodes_dir)?;
+    }
+
+    let nodes = conf
+        .validators
+        .iter()
+        .enumerate()
+        .map(|(i, v)| ComposeNode {
+            service: v.name.clone(),
+            container: container_name(&conf, i, &v.name),
+            home: v.name.clone(),
+            expose_ports: i == 0,
+        })
+        .collect::<Vec<ComposeNode>>();
+    fs::write(
+        localnet_dir(&root).join(COMPOSE_FILE),
+        compose_file(
+            &profile.image,
+            &profile.data_dir,
+            &profile.render(&profile.start, &[("home", &profile.data_dir)]),
+            docker_user(&nodes_dir)?.as_deref(),
+            &nodes,
+        )?,
+    )?;
+    Progress::stream("Starting localnet").end_with(compose(
+        &root,
+        &conf.container,
+        &["up", "-d"],
+    ))?;
+
+    let start_response = StartResponse {
+        chain_id: profile.chain_id.clone(),
+        validators: nodes.iter().map(|n| n.container.clone()).collect(),
+        rpc_endpoint: format!("http://localhost:{RPC_PORT}"),
+        initialized: !initialized,
+    };
+    start_response.log();
+    Ok(start_response)
+}
+
+pub fn stop<'a, Ctx: Context<'a, LocalnetConfig>>(ctx: &Ctx) -> Result<()> {
+    let conf = ctx.config()?;
+    let root = ctx.root()?;
+    if !localnet_dir(&root).join(COMPOSE_FILE).exists() {
+        bail!("Localnet is not started by beaker, run `beaker localnet start` first");
+    }
+    Progress::stream("Stopping localnet").end_with(compose(&root, &conf.container, &["down"]))
+}
+
+#[derive(Serialize)]
+pub struct StartResponse {
+    pub chain_id: String,
+    pub validators: Vec<String>,
+    pub rpc_endpoint: String,
+    /// Whether the chain is initialized from a new genesis by this start
+    pub initialized: bool,
+}
+
+impl OpResponseDisplay for StartResponse {
+    fn headline() -> &'static str {
+        "Localnet started successfully!! 🚀 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        vec![
+            format!("    ├── chain_id: {}", self.chain_id),
+            format!("    ├── validators: {}", self.validators.join(", ")),
+            format!("    ├── rpc_endpoint: {}", self.rpc_endpoint),
+            format!("    └── initialized: {}", self.initialized),
+        ]
+    }
+}
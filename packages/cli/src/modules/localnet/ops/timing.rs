@@ -0,0 +1,127 @@
+use crate::framework::Context;
+use crate::modules::localnet::chain::ChainProfile;
+use crate::modules::localnet::config::LocalnetConfig;
+use crate::modules::localnet::container::{read_file, run_with_volumes, with_stopped, write_file};
+use crate::modules::localnet::genesis::{
+    set_path, set_timeout_commit, GoDuration, CONFIG_FILE, GENESIS_FILE, MAX_DEPOSIT_PERIOD,
+    UNBONDING_TIME, VOTING_PERIOD,
+};
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::progress::Progress;
+use anyhow::{bail, Result};
+use console::style;
+use serde::Serialize;
+use serde_json::Value;
+
+pub struct TimingArgs {
+    pub block_time: Option<GoDuration>,
+    pub voting_period: Option<GoDuration>,
+    pub unbonding_time: Option<GoDuration>,
+    pub reset: bool,
+}
+
+/// Wipe chain data so that the chain starts over from (possibly modified) genesis,
+/// equivalent of `unsafe-reset-all`
+pub fn reset_chain_data(container: &str, data_dir: &str) -> Result<()> {
+    Progress::spinner("Resetting chain to genesis").end_with(run_with_volumes(
+        container,
+        &[],
+        &format!(
+            r#"find {data_dir}/data -mindepth 1 -delete && rm -rf {data_dir}/wasm && echo '{{"height":"0","round":0,"step":0}}' > {data_dir}/data/priv_validator_state.json"#
+        ),
+    ))?;
+    eprintln!(
+        "  {} local deployment state in `.beaker/state.local.json` no longer matches the chain",
+        style("WARNING:").yellow().bold()
+    );
+    Ok(())
+}
+
+pub fn timing<'a, Ctx: Context<'a, LocalnetConfig>>(
+    ctx: &Ctx,
+    args: &TimingArgs,
+) -> Result<TimingResponse> {
+    let conf = ctx.config()?;
+    let data_dir = ChainProfile::resolve(&conf)?.data_dir;
+    let container = conf.container;
+
+    let genesis_overrides = [
+        (VOTING_PERIOD, args.voting_period),
+        (MAX_DEPOSIT_PERIOD, args.voting_period),
+        (UNBONDING_TIME, args.unbonding_time),
+    ]
+    .into_iter()
+    .filter_map(|(path, d)| d.map(|d| (path, d)))
+    .collect::<Vec<_>>();
+
+    if args.block_time.is_none() && genesis_overrides.is_empty() {
+        bail!("Nothing to change, specify at least one of `--block-time`, `--voting-period` or `--unbonding-time`");
+    }
+    if !genesis_overrides.is_empty() && !args.reset {
+        bail!("Voting period and unbonding time only take effect from genesis, pass `--reset` to restart the chain from genesis (use `beaker localnet snapshot` first to keep the current state)");
+    }
+
+    with_stopped(&container, || {
+        if let Some(block_time) = &args.block_time {
+            let path = format!("{data_dir}/{CONFIG_FILE}");
+            let config = set_timeout_commit(&read_file(&container, &path)?, block_time)?;
+            write_file(&container, &path, &config)?;
+        }
+
+        if !genesis_overrides.is_empty() {
+            let path = format!("{data_dir}/{GENESIS_FILE}");
+            let mut genesis: Value = serde_json::from_str(&read_file(&container, &path)?)?;
+            for (json_path, duration) in &genesis_overrides {
+                set_path(
+                    &mut genesis,
+                    json_path,
+                    Value::String(duration.to_proto_json()),
+                )?;
+            }
+            write_file(&container, &path, &serde_json::to_string_pretty(&genesis)?)?;
+            reset_chain_data(&container, &data_dir)?;
+        }
+        Ok(())
+    })?;
+
+    let timing_response = TimingResponse {
+        container,
+        block_time: args.block_time.map(GoDuration::to_go),
+        voting_period: args.voting_period.map(GoDuration::to_proto_json),
+        unbonding_time: args.unbonding_time.map(GoDuration::to_proto_json),
+        reset: !genesis_overrides.is_empty(),
+    };
+    timing_response.log();
+    Ok(timing_response)
+}
+
+#[derive(Serialize)]
+pub struct TimingResponse {
+    pub container: String,
+    pub block_time: Option<String>,
+    pub voting_period: Option<String>,
+    pub unbonding_time: Option<String>,
+    pub reset: bool,
+}
+
+impl OpResponseDisplay for TimingResponse {
+    fn headline() -> &'static str {
+        "Localnet timing updated successfully!! ⏱ "
+    }
+    fn attrs(&self) -> Vec<String> {
+        let or_unchanged = |v: &Option<String>| v.clone().unwrap_or_else(|| "–".to_string());
+        vec![
+            format!("    ├── container: {}", self.container),
+            format!("    ├── block_time: {}", or_unchanged(&self.block_time)),
+            format!(
+                "    ├── voting_period: {}",
+                or_unchanged(&self.voting_period)
+            ),
+            format!(
+                "    ├── unbonding_time: {}",
+                or_unchanged(&self.unbonding_time)
+            ),
+            format!("    └── reset: {}", self.reset),
+        ]
+    }
+}
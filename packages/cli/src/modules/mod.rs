@@ -1,3 +1,19 @@
+pub mod account;
+pub mod audit_log;
+pub mod authz;
+pub mod bench;
+pub mod cw;
+pub mod dashboard;
+pub mod deploy;
+pub mod gov;
 pub mod key;
+pub mod localnet;
+pub mod release;
+pub mod self_update;
+pub mod serve;
+pub mod state;
+pub mod test;
+pub mod tokenfactory;
+pub mod tx;
 pub mod wasm;
 pub mod workspace;
@@ -0,0 +1,89 @@
+use super::config::ReleaseConfig;
+use super::ops;
+use crate::framework::{Context, Module};
+use anyhow::Result;
+use clap::Subcommand;
+
+#[derive(Subcommand, Debug)]
+pub enum ReleaseCmd {
+    /// Create annotated git tag on the current commit embedding deployed checksums, code ids and addresses
+    Tag {
+        /// Name of the tag
+        tag: String,
+
+        /// Network whose deployment is recorded in the tag
+        #[clap(short, long, default_value = "mainnet")]
+        network: String,
+
+        /// Message to put above the deployment record
+        #[clap(short, long)]
+        message: Option<String>,
+    },
+    /// Bump crate versions to `version`, check that committed schemas are up to date, build and checksum artifacts,
+    /// commit and tag `v<version>`, then after confirmation push them, publish library crates to crates.io
+    /// in dependency order and create a github release with the artifacts attached
+    Publish {
+        /// Version to release, eg. `1.2.0`
+        version: String,
+
+        /// Crates with this feature are published to crates.io, along with the workspace crates they depend on
+        #[clap(long, default_value = "library")]
+        library_feature: String,
+
+        /// Git remote to push the release commit and tag to
+        #[clap(long, default_value = "origin")]
+        remote: String,
+
+        /// Github repository to create the release in, eg. `owner/repo`, `gh` picks it from git remotes if not specified
+        #[clap(long)]
+        repo: Option<String>,
+
+        /// Build artifacts with the arm64 optimizer image
+        #[clap(long)]
+        aarch64: bool,
+
+        /// Check schemas, build and run `cargo publish --dry-run` only,
+        /// without bumping versions, committing, tagging, publishing or creating the github release
+        #[clap(long)]
+        dry_run: bool,
+    },
+}
+
+pub struct ReleaseModule {}
+
+impl<'a> Module<'a, ReleaseConfig, ReleaseCmd, anyhow::Error> for ReleaseModule {
+    fn execute<Ctx: Context<'a, ReleaseConfig>>(
+        ctx: Ctx,
+        cmd: &ReleaseCmd,
+    ) -> Result<(), anyhow::Error> {
+        match cmd {
+            ReleaseCmd::Tag {
+                tag,
+                network,
+                message,
+            } => {
+                ops::tag(&ctx, tag, network, message.as_deref())?;
+                Ok(())
+            }
+            ReleaseCmd::Publish {
+                version,
+                library_feature,
+                remote,
+                repo,
+                aarch64,
+                dry_run,
+            } => {
+                ops::publish(
+                    &ctx,
+                    version,
+                    library_feature,
+                    remote,
+                    repo.as_deref(),
+                    *aarch64,
+                    *dry_run,
+                )?;
+                Ok(())
+            }
+        }
+    }
+}
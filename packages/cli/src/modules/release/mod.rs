@@ -0,0 +1,6 @@
+pub mod config;
+pub mod entrypoint;
+pub mod ops;
+
+pub use crate::modules::release::config::ReleaseConfig;
+pub use crate::modules::release::entrypoint::{ReleaseCmd, ReleaseModule};
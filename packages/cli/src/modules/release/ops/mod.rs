@@ -0,0 +1,5 @@
+pub mod publish;
+pub mod tag;
+
+pub use publish::publish;
+pub use tag::tag;
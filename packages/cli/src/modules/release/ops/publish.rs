@@ -0,0 +1,394 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context as _, Result};
+use regex::Regex;
+use serde::Serialize;
+
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::release::config::ReleaseConfig;
+use crate::modules::wasm::artifact_registry::{write_checksums, CHECKSUMS_FILE};
+use crate::support::cargo_workspace::member_dirs;
+use crate::support::command::run_command;
+use crate::support::git::{
+    changed_files, commit_tracked, create_annotated_tag, ensure_clean, push_with_tag,
+};
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::progress::Progress;
+use crate::support::prompt;
+use crate::support::wasm::{checksum, wasm_dir};
+
+/// Crate of the cargo workspace
+#[derive(Debug, Clone)]
+struct LocalCrate {
+    name: String,
+    dir: PathBuf,
+    /// Has the feature that makes it importable as a library
+    library: bool,
+    /// Not `publish = false`
+    publishable: bool,
+    /// Names of workspace crates it depends on by path
+    local_deps: Vec<String>,
+}
+
+/// Members of the workspace at `root`
+fn workspace_crates(root: &Path, library_feature: &str) -> Result<Vec<LocalCrate>> {
+    let dirs = member_dirs(root)?;
+
+    let mut crates = vec![];
+    for dir in dirs {
+        let manifest: toml::Value = toml::from_str(&fs::read_to_string(dir.join("Cargo.toml"))?)
+            .with_context(|| format!("Invalid `{}/Cargo.toml`", dir.display()))?;
+        let package = manifest
+            .get("package")
+            .with_context(|| format!("`{}/Cargo.toml` has no `[package]`", dir.display()))?;
+        crates.push(LocalCrate {
+            name: package
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            library: manifest
+                .get("features")
+                .and_then(|f| f.get(library_feature))
+                .is_some(),
+            publishable: package.get("publish").and_then(|p| p.as_bool()) != Some(false),
+            local_deps: manifest
+                .get("dependencies")
+                .and_then(|d| d.as_table())
+                .into_iter()
+                .flatten()
+                .filter(|(_, dep)| dep.get("path").is_some())
+                .map(|(name, dep)| {
+                    dep.get("package")
+                        .and_then(|p| p.as_str())
+                        .unwrap_or(name)
+                        .to_string()
+                })
+                .collect(),
+            dir,
+        });
+    }
+    Ok(crates)
+}
+
+/// Library crates and the workspace crates they depend on, each after its dependencies
+fn publish_order(crates: &[LocalCrate]) -> Result<Vec<&LocalCrate>> {
+    fn visit<'c>(
+        name: &str,
+        crates: &'c [LocalCrate],
+        visiting: &mut Vec<String>,
+        order: &mut Vec<&'c LocalCrate>,
+    ) -> Result<()> {
+        if order.iter().any(|c| c.name == name) {
+            return Ok(());
+        }
+        if visiting.iter().any(|n| n == name) {
+            bail!(
+                "Workspace crates depend on each other in a cycle: {} -> {name}",
+                visiting.join(" -> ")
+            );
+        }
+        let krate = match crates.iter().find(|c| c.name == name) {
+            Some(krate) => krate,
+            None => return Ok(()),
+        };
+        if !krate.publishable {
+            bail!(
+                "`{name}` is a dependency of published crate `{}` but has `publish = false`",
+                visiting.last().map(String::as_str).unwrap_or_default()
+            );
+        }
+        visiting.push(name.to_string());
+        for dep in &krate.local_deps {
+            visit(dep, crates, visiting, order)?;
+        }
+        visiting.pop();
+        order.push(krate);
+        Ok(())
+    }
+
+    let mut order = vec![];
+    for krate in crates.iter().filter(|c| c.library && c.publishable) {
+        visit(&krate.name, crates, &mut vec![], &mut order)?;
+    }
+    Ok(order)
+}
+
+/// Set `version` in `section` (eg. `package`) and the version requirement of path dependencies on `local_crates`,
+/// editing lines in place so that formatting and comments are kept
+fn bump_manifest(manifest: &str, section: &str, local_crates: &[&str], version: &str) -> String {
+    let header = format!("[{section}]");
+    let version_line = Regex::new(r#"^(\s*version\s*=\s*")[^"]*(".*)$"#).unwrap();
+    let dep_lines = local_crates
+        .iter()
+        .map(|name| {
+            Regex::new(&format!(
+                r#"^(\s*{}\s*=\s*\{{.*\bversion\s*=\s*")[^"]*(".*)$"#,
+                regex::escape(name)
+            ))
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mut current = String::new();
+    let mut lines = manifest
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with('[') {
+                current = line.trim().to_string();
+                return line.to_string();
+            }
+            if current == header && version_line.is_match(line) {
+                return version_line
+                    .replace(line, format!("${{1}}{version}${{2}}"))
+                    .to_string();
+            }
+            if current.ends_with("dependencies]") && line.contains("path") {
+                if let Some(re) = dep_lines.iter().find(|re| re.is_match(line)) {
+                    return re
+                        .replace(line, format!("${{1}}{version}${{2}}"))
+                        .to_string();
+                }
+            }
+            line.to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if manifest.ends_with('\n') {
+        lines.push('\n');
+    }
+    lines
+}
+
+/// Contracts with committed schemas whose regenerated schemas differ, run after `cargo schema` in each of them
+fn regenerate_schemas(root: &Path, crates: &[LocalCrate]) -> Result<Vec<String>> {
+    let mut outdated = vec![];
+    for krate in crates.iter().filter(|c| c.dir.join("schema").exists()) {
+        Progress::stream(&format!("Generating schema of {}", krate.name)).end_with(run_command(
+            Command::new("cargo").arg("schema").current_dir(&krate.dir),
+        ))?;
+        let schema_dir = krate.dir.join("schema");
+        if !changed_files(root, schema_dir.strip_prefix(root).unwrap_or(&schema_dir))?.is_empty() {
+            outdated.push(krate.name.clone());
+        }
+    }
+    Ok(outdated)
+}
+
+pub fn publish<'a, Ctx: Context<'a, ReleaseConfig>>(
+    ctx: &Ctx,
+    version: &str,
+    library_feature: &str,
+    remote: &str,
+    repo: Option<&str>,
+    aarch64: bool,
+    dry_run: bool,
+) -> Result<PublishResponse> {
+    let root = ctx.root()?;
+    let version = version.trim_start_matches('v');
+    if !Regex::new(r"^\d+\.\d+\.\d+([-+].+)?$")
+        .unwrap()
+        .is_match(version)
+    {
+        bail!("`{version}` is not a semver version, eg. `1.2.0`");
+    }
+    let tag = format!("v{version}");
+
+    ensure_clean(&root, &format!("releasing `{tag}`"))?;
+
+    let crates = workspace_crates(&root, library_feature)?;
+    let order = publish_order(&crates)?;
+
+    let outdated = regenerate_schemas(&root, &crates)?;
+    if !outdated.is_empty() {
+        bail!(
+            "Committed schemas of {} are outdated, commit the regenerated schemas before releasing",
+            outdated.join(", ")
+        );
+    }
+
+    if !dry_run {
+        let names = crates.iter().map(|c| c.name.as_str()).collect::<Vec<_>>();
+        // crates with `version.workspace = true` take the version of the workspace manifest
+        let mut manifests = vec![(root.join("Cargo.toml"), "workspace.package")];
+        manifests.extend(crates.iter().map(|c| (c.dir.join("Cargo.toml"), "package")));
+        for (manifest, section) in manifests {
+            let content = fs::read_to_string(&manifest)?;
+            fs::write(&manifest, bump_manifest(&content, section, &names, version))?;
+        }
+        // schemas embed the contract version
+        regenerate_schemas(&root, &crates)?;
+    }
+
+    let mut build = Command::new(std::env::current_exe()?);
+    build.current_dir(&root).args(["wasm", "build"]);
+    if aarch64 {
+        build.arg("--aarch64");
+    }
+    run_command(&mut build)?;
+
+    let dir = wasm_dir(&root, &false);
+    let mut wasm_files = fs::read_dir(&dir)
+        .with_context(|| "No artifacts found after build")?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().map(|ext| ext == "wasm").unwrap_or(false))
+        .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+        .collect::<Vec<String>>();
+    wasm_files.sort();
+    write_checksums(&dir, &wasm_files)?;
+    let mut checksums = vec![];
+    for file in &wasm_files {
+        checksums.push(format!("{}  {file}", checksum(&fs::read(dir.join(file))?)));
+    }
+
+    let commit = if dry_run {
+        None
+    } else {
+        let commit = commit_tracked(&root, &format!("Release {tag}"))?;
+        create_annotated_tag(
+            &root,
+            &tag,
+            &format!("Release {tag}\n\n{}", checksums.join("\n")),
+        )?;
+        Some(commit)
+    };
+
+    if !dry_run {
+        let crates_io = match order.iter().map(|c| c.name.as_str()).collect::<Vec<_>>() {
+            names if names.is_empty() => String::new(),
+            names => format!(", publish {} to crates.io", names.join(", ")),
+        };
+        if !prompt::confirm(&format!(
+            "> Push `{tag}` to `{remote}`{crates_io} and create the github release?"
+        ))? {
+            bail!("Release `{tag}` aborted, the release commit and tag are kept locally");
+        }
+        // crates.io releases can not be taken back, so the tag they are published from is pushed first
+        push_with_tag(&root, remote, &tag)?;
+    }
+
+    for krate in &order {
+        let mut cargo = Command::new("cargo");
+        cargo.current_dir(&krate.dir).arg("publish");
+        if dry_run {
+            cargo.args(["--dry-run", "--allow-dirty"]);
+        }
+        Progress::stream(&format!("Publishing {} to crates.io", krate.name))
+            .end_with(run_command(&mut cargo))?;
+    }
+
+    if !dry_run {
+        let mut gh = Command::new("gh");
+        gh.current_dir(&dir)
+            .args([
+                "release",
+                "create",
+                &tag,
+                "--title",
+                &tag,
+                "--generate-notes",
+            ])
+            .args(&wasm_files)
+            .arg(CHECKSUMS_FILE);
+        if let Some(repo) = repo {
+            gh.args(["--repo", repo]);
+        }
+        Progress::stream(&format!("Creating github release {tag}"))
+            .end_with(run_command(&mut gh))
+            .with_context(|| {
+                "Unable to create github release, make sure `gh` is installed and authenticated"
+            })?;
+    }
+
+    let publish_response = PublishResponse {
+        tag,
+        commit: commit.unwrap_or_else(|| "none (dry run)".to_string()),
+        crates: order
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" -> "),
+        artifacts: checksums.join(", "),
+    };
+    publish_response.log();
+
+    Ok(publish_response)
+}
+
+#[derive(Serialize)]
+pub struct PublishResponse {
+    pub tag: String,
+    pub commit: String,
+    /// Crates published to crates.io, in publishing order
+    pub crates: String,
+    /// `<sha256>  <file>` of every wasm attached to the github release
+    pub artifacts: String,
+}
+
+impl OpResponseDisplay for PublishResponse {
+    fn headline() -> &'static str {
+        "Release published successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | tag, commit, crates, artifacts }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn krate(name: &str, library: bool, local_deps: &[&str]) -> LocalCrate {
+        LocalCrate {
+            name: name.to_string(),
+            dir: PathBuf::from(name),
+            library,
+            publishable: true,
+            local_deps: local_deps.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn publish_dependencies_first() {
+        let crates = vec![
+            krate("router", true, &["pair", "common"]),
+            krate("pair", true, &["common"]),
+            krate("common", false, &[]),
+            krate("faucet", false, &[]),
+        ];
+        let order = publish_order(&crates)
+            .unwrap()
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec!["common", "pair", "router"]);
+
+        let cyclic = vec![krate("a", true, &["b"]), krate("b", false, &["a"])];
+        assert!(publish_order(&cyclic).is_err());
+    }
+
+    #[test]
+    fn bump_package_and_path_dependencies() {
+        let manifest = r#"[package]
+name = "router"
+version = "0.1.0" # bumped on release
+
+[dependencies]
+pair = { path = "../pair", version = "0.1.0", features = ["library"] }
+cw2 = { version = "0.13.2" }
+
+[dev-dependencies]
+version = "unrelated"
+"#;
+        let bumped = bump_manifest(manifest, "package", &["pair"], "0.2.0");
+        assert!(bumped.contains("version = \"0.2.0\" # bumped on release"));
+        assert!(bumped
+            .contains(r#"pair = { path = "../pair", version = "0.2.0", features = ["library"] }"#));
+        assert!(bumped.contains(r#"cw2 = { version = "0.13.2" }"#));
+        assert!(bumped.contains(r#"version = "unrelated""#));
+        assert!(bumped.ends_with('\n'));
+    }
+}
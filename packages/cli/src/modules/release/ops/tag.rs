@@ -0,0 +1,97 @@
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::release::config::ReleaseConfig;
+use crate::support::git::create_annotated_tag;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::{Action, State, WasmRef};
+use anyhow::{bail, Context as _, Result};
+use config::Map;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct ReleasedContract {
+    pub code_id: Option<u64>,
+    /// Checksum of the stored code, if it was stored by beaker
+    pub checksum: Option<String>,
+    pub addresses: Map<String, String>,
+}
+
+impl From<&WasmRef> for ReleasedContract {
+    fn from(wasm_ref: &WasmRef) -> Self {
+        let checksum = wasm_ref
+            .history()
+            .iter()
+            .rev()
+            .find(|e| *e.action() == Action::Store && Some(*e.code_id()) == *wasm_ref.code_id())
+            .and_then(|e| e.checksum().clone());
+
+        ReleasedContract {
+            code_id: *wasm_ref.code_id(),
+            checksum,
+            addresses: wasm_ref.addresses().clone(),
+        }
+    }
+}
+
+pub fn tag<'a, Ctx: Context<'a, ReleaseConfig>>(
+    ctx: &Ctx,
+    tag: &str,
+    network: &str,
+    message: Option<&str>,
+) -> Result<TagResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let contracts = State::load_by_network(network_info, ctx.root()?)
+        .map(|s| s.contracts(network))
+        .unwrap_or_default()
+        .iter()
+        .map(|(name, wasm_ref)| (name.clone(), ReleasedContract::from(wasm_ref)))
+        .collect::<Map<_, _>>();
+    if contracts.is_empty() {
+        bail!("No deployment found on `{network}` to record in release `{tag}`");
+    }
+
+    let record = serde_json::to_string_pretty(&serde_json::json!({
+        "network": network,
+        "contracts": contracts,
+    }))?;
+    let message = format!(
+        "{}\n\n{record}",
+        message
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| format!("Release {tag}"))
+    );
+    let commit = create_annotated_tag(&ctx.root()?, tag, &message)?;
+
+    let tag_response = TagResponse {
+        tag: tag.to_string(),
+        commit,
+        network: network.to_string(),
+        contracts: contracts.keys().cloned().collect::<Vec<_>>().join(", "),
+    };
+    tag_response.log();
+
+    Ok(tag_response)
+}
+
+#[derive(Serialize)]
+pub struct TagResponse {
+    pub tag: String,
+    pub commit: String,
+    pub network: String,
+    pub contracts: String,
+}
+
+impl OpResponseDisplay for TagResponse {
+    fn headline() -> &'static str {
+        "Release tagged successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | tag, commit, network, contracts }
+    }
+}
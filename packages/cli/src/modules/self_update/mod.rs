@@ -0,0 +1,231 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context as _, Result};
+use clap::Subcommand;
+use console::style;
+use serde::{Deserialize, Serialize};
+
+use crate::attrs_format;
+use crate::modules::wasm::artifact_registry::CHECKSUMS_FILE;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::prompt;
+use crate::support::wasm::checksum;
+
+/// Github repository beaker releases are published to
+const RELEASE_REPO: &str = "osmosis-labs/beaker";
+
+#[derive(Subcommand, Debug)]
+pub enum SelfCmd {
+    /// Replace the running beaker with the binary of the latest (or pinned) github release for this platform,
+    /// after verifying its sha256 against the `checksums.txt` attached to the release by `beaker release publish`
+    Update {
+        /// Release version to install, eg. `0.1.2`, latest release if not specified
+        #[clap(long)]
+        version: Option<String>,
+    },
+}
+
+pub fn execute(cmd: &SelfCmd) -> Result<()> {
+    match cmd {
+        SelfCmd::Update { version } => {
+            update(version.as_deref())?;
+            Ok(())
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct Release {
+    tag_name: String,
+    assets: Vec<Asset>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+impl Release {
+    fn asset(&self, name: &str) -> Result<&Asset> {
+        self.assets
+            .iter()
+            .find(|a| a.name == name)
+            .with_context(|| format!("Release {} has no `{name}` asset", self.tag_name))
+    }
+}
+
+fn release_url(version: Option<&str>) -> String {
+    match version {
+        Some(version) => format!(
+            "https://api.github.com/repos/{RELEASE_REPO}/releases/tags/v{}",
+            version.trim_start_matches('v')
+        ),
+        None => format!("https://api.github.com/repos/{RELEASE_REPO}/releases/latest"),
+    }
+}
+
+/// Name of the release binary for `os` and `arch` as in `std::env::consts`, eg. `beaker-x86_64-unknown-linux-gnu`
+fn platform_asset(os: &str, arch: &str) -> Result<String> {
+    let target = match (os, arch) {
+        ("linux", "x86_64" | "aarch64") => format!("{arch}-unknown-linux-gnu"),
+        ("macos", "x86_64" | "aarch64") => format!("{arch}-apple-darwin"),
+        ("windows", "x86_64") => return Ok(format!("beaker-{arch}-pc-windows-msvc.exe")),
+        _ => bail!(
+            "No release binary is published for {os} {arch}, install with `cargo install beaker`"
+        ),
+    };
+    Ok(format!("beaker-{target}"))
+}
+
+/// Checksum of `asset` from `sha256sum` formatted checksums file
+fn expected_checksum(checksums: &str, asset: &str) -> Result<String> {
+    checksums
+        .lines()
+        .filter_map(|line| line.split_once(char::is_whitespace))
+        .find(|(_, name)| name.trim().trim_start_matches('*') == asset)
+        .map(|(sum, _)| sum.to_lowercase())
+        .with_context(|| format!("No checksum found for `{asset}` in `{CHECKSUMS_FILE}`"))
+}
+
+fn fetch(url: &str) -> Result<Vec<u8>> {
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location"])
+        .args(["--header", "Accept: application/vnd.github+json"])
+        .args(["--header", "User-Agent: beaker"])
+        .arg(url)
+        .output()
+        .with_context(|| "Unable to run `curl`, make sure it is installed")?;
+    if !output.status.success() {
+        bail!(
+            "Unable to fetch `{url}`: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
+
+/// Swap `binary` in place of `exe`, the running executable can be renamed but not overwritten on windows
+fn replace_executable(exe: &Path, binary: &[u8]) -> Result<()> {
+    let file_name = exe
+        .file_name()
+        .context("Unable to determine executable file name")?
+        .to_string_lossy();
+    let staged = exe.with_file_name(format!(".{file_name}.new"));
+    fs::write(&staged, binary)
+        .with_context(|| format!("Unable to write to `{}`", staged.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+    #[cfg(windows)]
+    {
+        let old = exe.with_file_name(format!(".{file_name}.old"));
+        let _ = fs::remove_file(&old);
+        fs::rename(exe, &old)?;
+    }
+
+    fs::rename(&staged, exe).with_context(|| format!("Unable to replace `{}`", exe.display()))
+}
+
+pub fn update(version: Option<&str>) -> Result<SelfUpdateResponse> {
+    let current_version = env!("CARGO_PKG_VERSION");
+    let release: Release = serde_json::from_slice(&fetch(&release_url(version))?)
+        .with_context(|| "Unable to parse github release")?;
+    let release_version = release.tag_name.trim_start_matches('v').to_string();
+    let exe: PathBuf = std::env::current_exe()?.canonicalize()?;
+
+    if release_version == current_version {
+        eprintln!(
+            "  {} beaker {current_version} is already installed",
+            style("✔").green()
+        );
+        return Ok(SelfUpdateResponse {
+            previous_version: current_version.to_string(),
+            version: release_version,
+            path: exe.to_string_lossy().to_string(),
+        });
+    }
+
+    let asset_name = platform_asset(std::env::consts::OS, std::env::consts::ARCH)?;
+    let asset = release.asset(&asset_name)?;
+    let checksums =
+        String::from_utf8(fetch(&release.asset(CHECKSUMS_FILE)?.browser_download_url)?)?;
+    let expected = expected_checksum(&checksums, &asset_name)?;
+
+    let binary = fetch(&asset.browser_download_url)?;
+    let actual = checksum(&binary);
+    if actual != expected {
+        bail!("Downloaded `{asset_name}` is rejected: checksum mismatch, expected {expected}, got {actual}");
+    }
+
+    if !prompt::confirm(&format!(
+        "> Replace beaker {current_version} at `{}` with {release_version}?",
+        exe.display()
+    ))? {
+        bail!("Update is cancelled");
+    }
+    replace_executable(&exe, &binary)?;
+
+    let response = SelfUpdateResponse {
+        previous_version: current_version.to_string(),
+        version: release_version,
+        path: exe.to_string_lossy().to_string(),
+    };
+    response.log();
+    Ok(response)
+}
+
+#[derive(Serialize)]
+pub struct SelfUpdateResponse {
+    pub previous_version: String,
+    pub version: String,
+    pub path: String,
+}
+
+impl OpResponseDisplay for SelfUpdateResponse {
+    fn headline() -> &'static str {
+        "Beaker updated successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | previous_version, version, path }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_release_assets() {
+        assert_eq!(
+            release_url(Some("v0.1.2")),
+            "https://api.github.com/repos/osmosis-labs/beaker/releases/tags/v0.1.2"
+        );
+        assert_eq!(
+            platform_asset("linux", "x86_64").unwrap(),
+            "beaker-x86_64-unknown-linux-gnu"
+        );
+        assert_eq!(
+            platform_asset("macos", "aarch64").unwrap(),
+            "beaker-aarch64-apple-darwin"
+        );
+        assert!(platform_asset("freebsd", "x86_64").is_err());
+
+        let checksums =
+            "ABC123  beaker-x86_64-unknown-linux-gnu\ndef456 *beaker-aarch64-apple-darwin\n";
+        assert_eq!(
+            expected_checksum(checksums, "beaker-x86_64-unknown-linux-gnu").unwrap(),
+            "abc123"
+        );
+        assert_eq!(
+            expected_checksum(checksums, "beaker-aarch64-apple-darwin").unwrap(),
+            "def456"
+        );
+        assert!(expected_checksum(checksums, "beaker-x86_64-apple-darwin").is_err());
+    }
+}
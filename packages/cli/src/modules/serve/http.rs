@@ -0,0 +1,113 @@
+use std::io::{BufRead, Write};
+use std::net::TcpStream;
+
+use anyhow::{bail, Context, Result};
+
+/// Request bodies larger than this are rejected
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub authorization: Option<String>,
+    /// Set by browsers on cross-origin requests, never by local clients
+    pub origin: Option<String>,
+    pub content_type: Option<String>,
+    /// Size of the body, which is left unread until the request is authorized
+    pub content_length: usize,
+}
+
+/// Read request line and headers of a single HTTP/1.1 request, only `Content-Length` framed bodies are supported
+pub fn read_head(reader: &mut impl BufRead) -> Result<Request> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => bail!("Invalid request line: `{}`", request_line.trim()),
+    };
+
+    let mut content_length = 0;
+    let mut authorization = None;
+    let mut origin = None;
+    let mut content_type = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            match name.trim().to_lowercase().as_str() {
+                "content-length" => {
+                    content_length = value.parse().with_context(|| "Invalid Content-Length")?
+                }
+                "authorization" => authorization = Some(value.to_string()),
+                "origin" => origin = Some(value.to_string()),
+                "content-type" => content_type = Some(value.to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    if content_length > MAX_BODY {
+        bail!("Request body of {content_length} bytes exceeds the {MAX_BODY} bytes limit");
+    }
+
+    Ok(Request {
+        method,
+        path,
+        authorization,
+        origin,
+        content_type,
+        content_length,
+    })
+}
+
+/// Read the body of `req` following its head
+pub fn read_body(reader: &mut impl BufRead, req: &Request) -> Result<Vec<u8>> {
+    let mut body = vec![0; req.content_length];
+    reader.read_exact(&mut body)?;
+    Ok(body)
+}
+
+pub fn write_response(stream: &mut TcpStream, status: &str, body: &[u8]) -> Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_post_request() {
+        let raw = "POST / HTTP/1.1\r\nHost: localhost\r\ncontent-length: 17\r\nAuthorization: Bearer secret\r\nContent-Type: application/json\r\n\r\n{\"method\":\"ping\"}";
+
+        let mut reader = raw.as_bytes();
+        let req = read_head(&mut reader).unwrap();
+        assert_eq!(
+            req,
+            Request {
+                method: "POST".to_string(),
+                path: "/".to_string(),
+                authorization: Some("Bearer secret".to_string()),
+                origin: None,
+                content_type: Some("application/json".to_string()),
+                content_length: 17,
+            }
+        );
+        assert_eq!(
+            read_body(&mut reader, &req).unwrap(),
+            b"{\"method\":\"ping\"}".to_vec()
+        );
+        assert!(read_head(&mut "\r\n".as_bytes()).is_err());
+    }
+}
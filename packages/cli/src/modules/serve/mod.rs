@@ -0,0 +1,148 @@
+pub mod http;
+pub mod rpc;
+
+use std::io::BufReader;
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::time::Duration;
+
+use anyhow::{Context as _, Result};
+use console::style;
+use cosmrs::bip32::secp256k1::elliptic_curve::rand_core::{OsRng, RngCore};
+
+use crate::framework::Context;
+use crate::modules::wasm::WasmConfig;
+use crate::support::prompt;
+
+/// Time a client has to send its request and read the response, so a stalled client
+/// can not hold up the requests queued behind it
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Serve json-rpc requests on `127.0.0.1:<port>` until the process is stopped.
+/// Requests are handled one at a time, sharing connections, derived keys and state across requests.
+/// A token is generated when none is given, since any local process can reach the port.
+/// Txs to `production` networks are refused unless `allow_production` is set, as nobody confirms them.
+pub fn serve<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    port: u16,
+    token: Option<&String>,
+    allow_production: bool,
+) -> Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .with_context(|| format!("Unable to listen on 127.0.0.1:{port}"))?;
+
+    // nobody is attending the prompts of requests, authorization is the token
+    prompt::set_no_input(true);
+    prompt::set_assume_yes(true);
+
+    let token = match token {
+        Some(token) => token.clone(),
+        None => {
+            let token = generate_token();
+            eprintln!(
+                "  {} no `--token` set, generated token: {}",
+                style("‣").cyan(),
+                style(&token).bold()
+            );
+            token
+        }
+    };
+    eprintln!(
+        "  {} serving json-rpc on http://127.0.0.1:{port}, methods: query, execute, deploy, state",
+        style("‣").cyan()
+    );
+
+    for stream in listener.incoming() {
+        let result = stream
+            .map_err(anyhow::Error::from)
+            .and_then(|stream| handle_connection(ctx, stream, &token, allow_production));
+        if let Err(e) = result {
+            eprintln!("  {} {e:#}", style("✘").red());
+        }
+    }
+    Ok(())
+}
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Compare without short-circuiting, so that response time does not reveal how much of the token matched
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn is_json(content_type: Option<&str>) -> bool {
+    content_type
+        .and_then(|c| c.split(';').next())
+        .map(|c| c.trim().eq_ignore_ascii_case("application/json"))
+        .unwrap_or(false)
+}
+
+fn handle_connection<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    mut stream: TcpStream,
+    token: &str,
+    allow_production: bool,
+) -> Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let req = match http::read_head(&mut reader) {
+        Ok(req) => req,
+        Err(e) => {
+            http::write_response(&mut stream, "400 Bad Request", b"{}")?;
+            return Err(e);
+        }
+    };
+
+    let authorized = req
+        .authorization
+        .as_deref()
+        .and_then(|a| a.strip_prefix("Bearer "))
+        .map(|t| constant_time_eq(t.as_bytes(), token.as_bytes()))
+        .unwrap_or(false);
+    if !authorized {
+        return http::write_response(&mut stream, "401 Unauthorized", b"{}");
+    }
+    // browsers send `Origin` on cross-origin requests, pages must not reach the server
+    if req.origin.is_some() {
+        return http::write_response(&mut stream, "403 Forbidden", b"{}");
+    }
+    if req.method != "POST" || req.path != "/" {
+        return http::write_response(&mut stream, "404 Not Found", b"{}");
+    }
+    if !is_json(req.content_type.as_deref()) {
+        return http::write_response(&mut stream, "415 Unsupported Media Type", b"{}");
+    }
+
+    let body = http::read_body(&mut reader, &req)?;
+    let res = rpc::handle(ctx, &body, allow_production);
+    http::write_response(&mut stream, "200 OK", &serde_json::to_vec(&res)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_tokens() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+        assert_eq!(generate_token().len(), 64);
+    }
+
+    #[test]
+    fn accept_only_json_content_type() {
+        assert!(is_json(Some("application/json")));
+        assert!(is_json(Some("Application/JSON; charset=utf-8")));
+        assert!(!is_json(Some("text/plain")));
+        assert!(!is_json(None));
+    }
+}
@@ -0,0 +1,319 @@
+use anyhow::{anyhow, Context as _, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::framework::config::GlobalConfig;
+use crate::framework::Context;
+use crate::modules::state::ops::export::address_map;
+use crate::modules::wasm::{ops, WasmConfig};
+use crate::support::error::{Error, ErrorKind, ErrorReport};
+use crate::support::gas::{Gas, GasArgs};
+use crate::support::permission::admin_or_default;
+use crate::support::signer::SignerArgs;
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Operation failed, `data` contains the same report as `--output json` prints on failure
+const OPERATION_FAILED: i64 = -32000;
+
+#[derive(Deserialize, Debug)]
+pub struct RpcRequest {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RpcResponse {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RpcError {
+    code: i64,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<ErrorReport>,
+}
+
+impl RpcResponse {
+    fn result(id: Value, result: Value) -> Self {
+        RpcResponse {
+            jsonrpc: "2.0",
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    fn error(id: Value, code: i64, message: String, data: Option<ErrorReport>) -> Self {
+        RpcResponse {
+            jsonrpc: "2.0",
+            id,
+            result: None,
+            error: Some(RpcError {
+                code,
+                message,
+                data,
+            }),
+        }
+    }
+}
+
+fn default_label() -> String {
+    "default".to_string()
+}
+
+fn default_network() -> String {
+    "local".to_string()
+}
+
+#[derive(Deserialize)]
+struct QueryParams {
+    contract: String,
+    #[serde(default = "default_label")]
+    label: String,
+    msg: Value,
+    #[serde(default = "default_network")]
+    network: String,
+}
+
+#[derive(Deserialize)]
+struct ExecuteParams {
+    contract: String,
+    #[serde(default = "default_label")]
+    label: String,
+    msg: Value,
+    funds: Option<String>,
+    #[serde(default = "default_network")]
+    network: String,
+    #[serde(default)]
+    simulate: bool,
+    #[serde(default)]
+    timeout_height: u32,
+    #[serde(flatten)]
+    gas: GasArgs,
+    #[serde(flatten)]
+    signer: SignerArgs,
+}
+
+#[derive(Deserialize)]
+struct DeployParams {
+    contract: String,
+    #[serde(default = "default_label")]
+    label: String,
+    msg: Value,
+    funds: Option<String>,
+    admin: Option<String>,
+    #[serde(default)]
+    no_admin: bool,
+    #[serde(default = "default_network")]
+    network: String,
+    /// Deploying from the daemon uses already built artifacts unless asked otherwise
+    #[serde(default = "no_rebuild_default")]
+    no_rebuild: bool,
+    #[serde(default)]
+    no_wasm_opt: bool,
+    #[serde(default)]
+    timeout_height: u32,
+    #[serde(flatten)]
+    gas: GasArgs,
+    #[serde(flatten)]
+    signer: SignerArgs,
+}
+
+fn no_rebuild_default() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct StateParams {
+    #[serde(default = "default_network")]
+    network: String,
+}
+
+/// Handle a json-rpc request body, failures of the operation are reported as json-rpc errors
+pub fn handle<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    body: &[u8],
+    allow_production: bool,
+) -> RpcResponse {
+    let req = match serde_json::from_slice::<RpcRequest>(body) {
+        Ok(req) => req,
+        Err(e) => return RpcResponse::error(Value::Null, PARSE_ERROR, e.to_string(), None),
+    };
+
+    let result = match req.method.as_str() {
+        "query" => params(req.params).map(|p| query(ctx, p)),
+        "execute" => params(req.params).map(|p| execute(ctx, p, allow_production)),
+        "deploy" => params(req.params).map(|p| deploy(ctx, p, allow_production)),
+        "state" => params(req.params).map(|p| state(ctx, p)),
+        method => {
+            let message = format!("Method `{method}` not found");
+            return RpcResponse::error(req.id, METHOD_NOT_FOUND, message, None);
+        }
+    };
+
+    match result {
+        Err(e) => RpcResponse::error(req.id, INVALID_PARAMS, e.to_string(), None),
+        Ok(Ok(value)) => RpcResponse::result(req.id, value),
+        Ok(Err(e)) => RpcResponse::error(
+            req.id,
+            OPERATION_FAILED,
+            format!("{e:#}"),
+            Some(ErrorReport::from(&e)),
+        ),
+    }
+}
+
+fn params<T: serde::de::DeserializeOwned>(params: Value) -> serde_json::Result<T> {
+    serde_json::from_value(params)
+}
+
+fn query<'a, Ctx: Context<'a, WasmConfig>>(ctx: &Ctx, p: QueryParams) -> Result<Value> {
+    let res = ops::query(
+        ctx,
+        &p.contract,
+        &p.label,
+        Some(&p.msg.to_string()),
+        None,
+        false,
+        &p.network,
+    )?;
+    Ok(serde_json::to_value(res)?)
+}
+
+/// Txs are confirmed for the operator at startup with `--allow-production`, nobody attends the prompt per tx
+fn ensure_broadcast_allowed(
+    global_config: &GlobalConfig,
+    network: &str,
+    allow_production: bool,
+) -> Result<()> {
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?;
+    if *network_info.production() && !allow_production {
+        return Err(anyhow!(Error::new(
+            ErrorKind::Config,
+            format!("Network `{network}` is a production network, start `beaker serve` with `--allow-production` to broadcast to it"),
+        )));
+    }
+    Ok(())
+}
+
+fn execute<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    p: ExecuteParams,
+    allow_production: bool,
+) -> Result<Value> {
+    let global_config = ctx.global_config()?;
+    if !p.simulate {
+        ensure_broadcast_allowed(&global_config, &p.network, allow_production)?;
+    }
+    let res = ops::execute(
+        ctx,
+        &p.contract,
+        &p.label,
+        Some(&p.msg.to_string()),
+        p.funds.as_deref().try_into()?,
+        None,
+        &p.network,
+        &p.timeout_height,
+        &Gas::from_args(
+            &p.gas,
+            global_config.network_gas_price(&p.network),
+            global_config.gas_adjustment(),
+        )?,
+        p.signer
+            .private_key(&ctx.chain_client(), &global_config, &p.network)?,
+        p.simulate,
+    )?;
+    Ok(serde_json::to_value(res)?)
+}
+
+fn deploy<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    p: DeployParams,
+    allow_production: bool,
+) -> Result<Value> {
+    let global_config = ctx.global_config()?;
+    ensure_broadcast_allowed(&global_config, &p.network, allow_production)?;
+    let res = ops::deploy(
+        ctx,
+        &p.contract,
+        &p.label,
+        None,
+        false,
+        Some(&p.msg.to_string()),
+        &None,
+        admin_or_default(&p.admin, p.no_admin, &ctx.config()?.default_admin).as_ref(),
+        p.funds.as_deref().try_into()?,
+        &p.network,
+        &p.timeout_height,
+        &Gas::from_args(
+            &p.gas,
+            global_config.network_gas_price(&p.network),
+            global_config.gas_adjustment(),
+        )?,
+        p.signer
+            .private_key(&ctx.chain_client(), &global_config, &p.network)?,
+        p.signer
+            .private_key(&ctx.chain_client(), &global_config, &p.network)?,
+        &p.no_rebuild,
+        &p.no_wasm_opt,
+        &false,
+    )?;
+    Ok(serde_json::to_value(res)?)
+}
+
+fn state<'a, Ctx: Context<'a, WasmConfig>>(ctx: &Ctx, p: StateParams) -> Result<Value> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(&p.network)
+        .with_context(|| format!("Unable to find network config: {}", p.network))?
+        .clone();
+    Ok(serde_json::to_value(address_map(
+        ctx.root()?,
+        &p.network,
+        network_info,
+    )?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct WasmContext {}
+    impl<'a> Context<'a, WasmConfig> for WasmContext {}
+
+    #[test]
+    fn reject_malformed_requests() {
+        let ctx = WasmContext {};
+
+        let res = serde_json::to_value(handle(&ctx, b"not json", false)).unwrap();
+        assert_eq!(res["error"]["code"], PARSE_ERROR);
+
+        let res =
+            serde_json::to_value(handle(&ctx, br#"{"id":1,"method":"migrate"}"#, false)).unwrap();
+        assert_eq!(res["id"], 1);
+        assert_eq!(res["error"]["code"], METHOD_NOT_FOUND);
+
+        let res = serde_json::to_value(handle(
+            &ctx,
+            br#"{"id":2,"method":"query","params":{"label":"default"}}"#,
+            false,
+        ))
+        .unwrap();
+        assert_eq!(res["error"]["code"], INVALID_PARAMS);
+    }
+}
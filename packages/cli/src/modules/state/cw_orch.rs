@@ -0,0 +1,121 @@
+use config::Map;
+use serde::{Deserialize, Serialize};
+
+use crate::support::state::WasmRef;
+
+const CODE_IDS_KEY: &str = "code_ids";
+
+/// State of a single chain in cw-orchestrator's layout:
+/// `{ "code_ids": { <contract>: <code_id> }, <deployment_id>: { <contract>: <address> } }`
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default)]
+pub struct CwOrchChainState {
+    #[serde(default)]
+    pub code_ids: Map<String, u64>,
+
+    #[serde(flatten)]
+    pub deployments: Map<String, Map<String, String>>,
+}
+
+/// cw-orchestrator's state file: `{ <chain_id>: CwOrchChainState }`
+pub type CwOrchState = Map<String, CwOrchChainState>;
+
+/// Convert contracts of a network to cw-orchestrator's chain state,
+/// beaker's contract label is used as cw-orchestrator's deployment id.
+pub fn to_cw_orch(contracts: &Map<String, WasmRef>) -> CwOrchChainState {
+    let mut chain_state = CwOrchChainState::default();
+
+    for (contract_name, wasm_ref) in contracts {
+        if let Some(code_id) = wasm_ref.code_id() {
+            chain_state
+                .code_ids
+                .insert(contract_name.to_string(), *code_id);
+        }
+        for (label, address) in wasm_ref.addresses() {
+            chain_state
+                .deployments
+                .entry(label.to_string())
+                .or_default()
+                .insert(contract_name.to_string(), address.to_string());
+        }
+    }
+
+    chain_state
+}
+
+/// Convert cw-orchestrator's chain state back to beaker's contracts, merging into `existing` contracts.
+pub fn from_cw_orch(
+    chain_state: &CwOrchChainState,
+    existing: &Map<String, WasmRef>,
+) -> Map<String, WasmRef> {
+    let mut contracts = existing.clone();
+
+    for (contract_name, code_id) in &chain_state.code_ids {
+        let wasm_ref = contracts.entry(contract_name.to_string()).or_default();
+        *wasm_ref = wasm_ref.with_code_id(Some(*code_id));
+    }
+
+    for (deployment_id, addresses) in &chain_state.deployments {
+        if deployment_id == CODE_IDS_KEY {
+            continue;
+        }
+        for (contract_name, address) in addresses {
+            let wasm_ref = contracts.entry(contract_name.to_string()).or_default();
+            *wasm_ref = wasm_ref.with_address(deployment_id, address);
+        }
+    }
+
+    contracts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_cw_orch_state() {
+        let state: CwOrchState = serde_json::from_str(
+            r#"
+            {
+                "osmo-test-4": {
+                    "code_ids": { "counter": 12 },
+                    "default": { "counter": "osmo1counter" },
+                    "other": { "counter": "osmo1other" }
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let chain_state = state.get("osmo-test-4").unwrap();
+
+        assert_eq!(chain_state.code_ids.get("counter"), Some(&12));
+        assert_eq!(chain_state.deployments.len(), 2);
+    }
+
+    #[test]
+    fn roundtrip_contracts() {
+        let contracts = Map::from([(
+            "counter".to_string(),
+            WasmRef::default()
+                .with_code_id(Some(1))
+                .with_address("default", "osmo1counter")
+                .with_address("other", "osmo1other"),
+        )]);
+
+        let chain_state = to_cw_orch(&contracts);
+        assert_eq!(
+            chain_state.code_ids,
+            Map::from([("counter".to_string(), 1)])
+        );
+        assert_eq!(
+            chain_state.deployments.get("other"),
+            Some(&Map::from([(
+                "counter".to_string(),
+                "osmo1other".to_string()
+            )]))
+        );
+
+        assert_eq!(from_cw_orch(&chain_state, &Map::new()), contracts);
+    }
+}
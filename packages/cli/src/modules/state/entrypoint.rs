@@ -0,0 +1,107 @@
+use super::config::StateConfig;
+use super::ops;
+use crate::framework::{Context, Module};
+use anyhow::Result;
+use clap::Subcommand;
+use std::path::PathBuf;
+
+#[derive(clap::ArgEnum, Clone, Debug)]
+pub enum StateFormat {
+    /// cw-orchestrator's `state.json` layout
+    CwOrch,
+}
+
+#[derive(clap::ArgEnum, Clone, Debug)]
+pub enum ExportFormat {
+    /// cw-orchestrator's `state.json` layout
+    CwOrch,
+    /// `COUNTER_ADDRESS=...` style variables of a single network, for frontend builds and bots
+    Env,
+    /// Code ids and addresses by network and contract
+    Json,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum StateCmd {
+    /// Show the timeline of stores, instantiations and migrations of a contract
+    History {
+        /// Name of the contract
+        contract_name: String,
+
+        /// Only show history on this network
+        #[clap(short, long)]
+        network: Option<String>,
+    },
+    /// Compare code checksums, code ids, admins and cw2 versions of deployed contracts across networks,
+    /// highlighting where they drift from the first network
+    Diff {
+        /// Networks to compare, comma separated (eg. `testnet,mainnet`)
+        #[clap(long, use_value_delimiter = true, required = true)]
+        networks: Vec<String>,
+
+        /// Only compare these contracts, all contracts deployed on any of the networks if not specified
+        #[clap(short, long)]
+        contract: Vec<String>,
+    },
+    /// Summarize gas used and fees paid by transactions broadcasted by beaker, per network and contract
+    GasSummary {
+        /// Only summarize transactions on this network
+        #[clap(short, long)]
+        network: Option<String>,
+    },
+    /// Export deployment state (code ids and contract addresses) of all configured networks
+    Export {
+        /// Format of the exported state
+        #[clap(long, arg_enum, default_value = "cw-orch")]
+        format: ExportFormat,
+
+        /// Only export state of this network, required for `env` format
+        #[clap(short, long)]
+        network: Option<String>,
+
+        /// Prefix of variable names in `env` format, eg. `NEXT_PUBLIC_`
+        #[clap(long, default_value = "")]
+        env_prefix: String,
+
+        /// File to write exported state to, print to stdout if not specified
+        #[clap(short, long)]
+        out: Option<PathBuf>,
+    },
+    /// Import deployment state from other tooling into beaker's state files
+    Import {
+        /// File containing the state to import
+        file: PathBuf,
+
+        /// Format of the imported state
+        #[clap(long, arg_enum, default_value = "cw-orch")]
+        format: StateFormat,
+    },
+}
+
+pub struct StateModule {}
+
+impl<'a> Module<'a, StateConfig, StateCmd, anyhow::Error> for StateModule {
+    fn execute<Ctx: Context<'a, StateConfig>>(
+        ctx: Ctx,
+        cmd: &StateCmd,
+    ) -> Result<(), anyhow::Error> {
+        match cmd {
+            StateCmd::History {
+                contract_name,
+                network,
+            } => ops::history(&ctx, contract_name, network.as_ref()),
+            StateCmd::Diff { networks, contract } => {
+                ops::diff(&ctx, networks, contract)?;
+                Ok(())
+            }
+            StateCmd::GasSummary { network } => ops::gas_summary(&ctx, network.as_ref()),
+            StateCmd::Export {
+                format,
+                network,
+                env_prefix,
+                out,
+            } => ops::export(&ctx, format, network.as_ref(), env_prefix, out.as_ref()),
+            StateCmd::Import { file, format } => ops::import(&ctx, file, format),
+        }
+    }
+}
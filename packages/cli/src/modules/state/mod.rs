@@ -0,0 +1,7 @@
+pub mod config;
+pub mod cw_orch;
+pub mod entrypoint;
+pub mod ops;
+
+pub use crate::modules::state::config::StateConfig;
+pub use crate::modules::state::entrypoint::{StateCmd, StateModule};
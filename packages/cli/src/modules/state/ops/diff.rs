@@ -0,0 +1,304 @@
+use anyhow::{bail, Context as _, Result};
+use config::Map;
+use console::style;
+use serde::Serialize;
+
+use crate::framework::Context;
+use crate::modules::state::config::StateConfig;
+use crate::modules::wasm::ops::version::{Cw2ContractVersion, CW2_CONTRACT_INFO_KEY};
+use crate::support::future::block;
+use crate::support::output;
+use crate::support::state::{Action, HistoryEntry, State};
+
+/// Deployment of a `contract:label` on a network, as it is on chain
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+pub struct Deployment {
+    pub address: String,
+    pub code_id: u64,
+    /// sha256 of the running code
+    pub checksum: String,
+    /// `None` if the contract is immutable
+    pub admin: Option<String>,
+    /// `None` if the contract does not set cw2 contract info
+    pub cw2: Option<Cw2ContractVersion>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ContractDiff {
+    pub contract: String,
+    pub label: String,
+    /// Deployment by network, `None` if it is not deployed there
+    pub networks: Map<String, Option<Deployment>>,
+    /// Differences from the first network, empty if every network runs the same code
+    pub drift: Vec<String>,
+}
+
+/// Checksums of the codes stored for a contract in order
+fn stored_checksums(history: &[HistoryEntry]) -> Vec<&str> {
+    let mut checksums: Vec<&str> = vec![];
+    for entry in history {
+        if !matches!(entry.action(), Action::Store) {
+            continue;
+        }
+        if let Some(checksum) = entry.checksum() {
+            if checksums.last() != Some(&checksum.as_str()) {
+                checksums.push(checksum);
+            }
+        }
+    }
+    checksums
+}
+
+/// Differences of every network from the first one. `baseline_checksums` are the checksums stored on the
+/// first network in order, telling how many stores behind a network running an older code is.
+fn drift(networks: &Map<String, Option<Deployment>>, baseline_checksums: &[&str]) -> Vec<String> {
+    let mut entries = networks.iter();
+    let (baseline_network, baseline) = match entries.next() {
+        Some(first) => first,
+        None => return vec![],
+    };
+
+    let mut drift = vec![];
+    for (network, deployment) in entries {
+        let (baseline, deployment) = match (baseline, deployment) {
+            (None, None) => continue,
+            (Some(_), None) => {
+                drift.push(format!("not deployed on {network}"));
+                continue;
+            }
+            (None, Some(_)) => {
+                drift.push(format!("not deployed on {baseline_network}"));
+                continue;
+            }
+            (Some(baseline), Some(deployment)) => (baseline, deployment),
+        };
+
+        if deployment.checksum != baseline.checksum {
+            let position = baseline_checksums
+                .iter()
+                .position(|c| *c == deployment.checksum);
+            let current = baseline_checksums
+                .iter()
+                .position(|c| *c == baseline.checksum);
+            drift.push(match (position, current) {
+                (Some(p), Some(c)) if p < c => format!(
+                    "{network} runs different code from {baseline_network}, {} store{} behind",
+                    c - p,
+                    if c - p == 1 { "" } else { "s" }
+                ),
+                (Some(p), Some(c)) if p > c => format!(
+                    "{network} runs different code from {baseline_network}, {} store{} ahead",
+                    p - c,
+                    if p - c == 1 { "" } else { "s" }
+                ),
+                _ => format!("{network} runs different code from {baseline_network}"),
+            });
+        }
+
+        let version = |d: &Deployment| d.cw2.as_ref().map(|c| c.version.clone());
+        if version(deployment) != version(baseline) {
+            drift.push(format!(
+                "cw2 version {} on {network}, {} on {baseline_network}",
+                version(deployment).unwrap_or_else(|| "–".to_string()),
+                version(baseline).unwrap_or_else(|| "–".to_string()),
+            ));
+        }
+
+        match (&baseline.admin, &deployment.admin) {
+            (Some(_), None) => drift.push(format!(
+                "immutable on {network}, migratable on {baseline_network}"
+            )),
+            (None, Some(_)) => drift.push(format!(
+                "migratable on {network}, immutable on {baseline_network}"
+            )),
+            _ => {}
+        }
+    }
+    drift
+}
+
+/// Compare code checksums, code ids, admins and cw2 versions of the contracts deployed on `networks`,
+/// as they are on chain. The first network is the one the others are compared to.
+pub fn diff<'a, Ctx: Context<'a, StateConfig>>(
+    ctx: &Ctx,
+    networks: &[String],
+    contracts: &[String],
+) -> Result<Vec<ContractDiff>> {
+    if networks.len() < 2 {
+        bail!("At least two networks are required to diff, eg. `--networks testnet,mainnet`");
+    }
+    let global_config = ctx.global_config()?;
+    let root = ctx.root()?;
+
+    let mut states = vec![];
+    for network in networks {
+        let network_info = global_config
+            .networks()
+            .get(network)
+            .with_context(|| format!("Unable to find network config: {network}"))?
+            .to_owned();
+        let state = State::load_by_network(network_info.clone(), root.clone())?;
+        states.push((network.clone(), ctx.client(network_info), state));
+    }
+
+    let mut targets: Vec<(String, String)> = vec![];
+    for (network, _, state) in &states {
+        for (contract, wasm_ref) in state.contracts(network) {
+            if !contracts.is_empty() && !contracts.contains(&contract) {
+                continue;
+            }
+            for label in wasm_ref.addresses().keys() {
+                let target = (contract.clone(), label.clone());
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+        }
+    }
+    targets.sort();
+
+    let diffs = block(async {
+        let mut diffs = vec![];
+        for (contract, label) in targets {
+            let mut deployments = Map::new();
+            for (network, client, state) in &states {
+                let address = state
+                    .get_ref(network, &contract)
+                    .ok()
+                    .and_then(|wasm_ref| wasm_ref.addresses().get(&label).cloned());
+                let deployment = match address {
+                    Some(address) => {
+                        let info = client.contract_info(address.clone()).await?;
+                        let code_info = client.code_info(info.code_id).await?;
+                        let cw2 = client
+                            .query_raw(address.clone(), CW2_CONTRACT_INFO_KEY.to_vec())
+                            .await?
+                            .and_then(|raw| serde_json::from_slice(&raw).ok());
+                        Some(Deployment {
+                            address,
+                            code_id: info.code_id,
+                            checksum: hex::encode(code_info.data_hash),
+                            admin: (!info.admin.is_empty()).then(|| info.admin),
+                            cw2,
+                        })
+                    }
+                    None => None,
+                };
+                deployments.insert(network.clone(), deployment);
+            }
+
+            let (baseline_network, _, baseline_state) = &states[0];
+            let baseline_history = baseline_state
+                .get_ref(baseline_network, &contract)
+                .map(|wasm_ref| wasm_ref.history().clone())
+                .unwrap_or_default();
+            diffs.push(ContractDiff {
+                drift: drift(&deployments, &stored_checksums(&baseline_history)),
+                contract,
+                label,
+                networks: deployments,
+            });
+        }
+        anyhow::Ok(diffs)
+    })?;
+
+    output::print(&diffs, || display_diffs(&diffs, networks));
+    Ok(diffs)
+}
+
+fn display_deployment(deployment: &Option<Deployment>) -> String {
+    match deployment {
+        None => "–".to_string(),
+        Some(d) => format!(
+            "code_id: {}, checksum: {}, admin: {}, cw2: {}",
+            d.code_id,
+            &d.checksum[..d.checksum.len().min(12)],
+            d.admin.as_deref().unwrap_or("–"),
+            d.cw2
+                .as_ref()
+                .map(|c| format!("{} {}", c.contract, c.version))
+                .unwrap_or_else(|| "–".to_string())
+        ),
+    }
+}
+
+fn display_diffs(diffs: &[ContractDiff], networks: &[String]) -> String {
+    if diffs.is_empty() {
+        return format!("No deployment found on {}", networks.join(", "));
+    }
+
+    let mut lines = vec![];
+    for diff in diffs {
+        let status = if diff.drift.is_empty() {
+            style("in sync").green()
+        } else {
+            style("drifted").yellow()
+        };
+        lines.push(format!("  {}:{} ({status})", diff.contract, diff.label));
+        for (network, deployment) in &diff.networks {
+            lines.push(format!(
+                "    ├── {network}: {}",
+                display_deployment(deployment)
+            ));
+        }
+        for drift in &diff.drift {
+            lines.push(format!(
+                "    │     {}",
+                style(format!("• {drift}")).yellow()
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployment(checksum: &str, version: &str, admin: Option<&str>) -> Option<Deployment> {
+        Some(Deployment {
+            checksum: checksum.to_string(),
+            admin: admin.map(str::to_string),
+            cw2: Some(Cw2ContractVersion {
+                contract: "crates.io:counter".to_string(),
+                version: version.to_string(),
+            }),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn report_drift_from_first_network() {
+        let stored = ["aa", "bb", "cc"];
+
+        let in_sync = Map::from([
+            ("testnet".to_string(), deployment("cc", "0.4.0", Some("a"))),
+            ("mainnet".to_string(), deployment("cc", "0.4.0", Some("b"))),
+        ]);
+        assert!(drift(&in_sync, &stored).is_empty());
+
+        let behind = Map::from([
+            ("testnet".to_string(), deployment("cc", "0.4.0", Some("a"))),
+            ("mainnet".to_string(), deployment("aa", "0.2.0", None)),
+            ("localnet".to_string(), None),
+        ]);
+        assert_eq!(
+            drift(&behind, &stored),
+            vec![
+                "mainnet runs different code from testnet, 2 stores behind",
+                "cw2 version 0.2.0 on mainnet, 0.4.0 on testnet",
+                "immutable on mainnet, migratable on testnet",
+                "not deployed on localnet",
+            ]
+        );
+
+        let unknown = Map::from([
+            ("testnet".to_string(), deployment("bb", "0.3.0", None)),
+            ("mainnet".to_string(), deployment("dd", "0.3.0", None)),
+        ]);
+        assert_eq!(
+            drift(&unknown, &stored),
+            vec!["mainnet runs different code from testnet"]
+        );
+    }
+}
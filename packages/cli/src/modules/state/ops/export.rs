@@ -0,0 +1,209 @@
+use crate::framework::config::Network;
+use crate::framework::Context;
+use crate::modules::state::config::StateConfig;
+use crate::modules::state::cw_orch::{to_cw_orch, CwOrchState};
+use crate::modules::state::entrypoint::ExportFormat;
+use crate::support::state::{State, WasmRef};
+use anyhow::{bail, Context as _, Result};
+use config::Map;
+use serde::Serialize;
+use std::{fs, path::PathBuf};
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct ExportedContract {
+    pub code_id: Option<u64>,
+    /// Address by label
+    pub addresses: Map<String, String>,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct ExportedNetwork {
+    pub chain_id: String,
+    pub contracts: Map<String, ExportedContract>,
+}
+
+/// Name of environment variable, eg. `("counter", Some("v2"), "ADDRESS")` -> `COUNTER_V2_ADDRESS`
+fn env_name(prefix: &str, contract_name: &str, label: Option<&str>, suffix: &str) -> String {
+    let name = [Some(contract_name), label, Some(suffix)]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("_")
+        .to_uppercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    format!("{prefix}{name}")
+}
+
+/// `<CONTRACT>_CODE_ID` and `<CONTRACT>[_<LABEL>]_ADDRESS` lines, label is omitted for `default`
+fn to_env(prefix: &str, contracts: &Map<String, WasmRef>) -> String {
+    let mut lines = vec![];
+    for (contract_name, wasm_ref) in contracts {
+        if let Some(code_id) = wasm_ref.code_id() {
+            lines.push(format!(
+                "{}={code_id}",
+                env_name(prefix, contract_name, None, "CODE_ID")
+            ));
+        }
+        for (label, address) in wasm_ref.addresses() {
+            let label = Some(label.as_str()).filter(|l| *l != "default");
+            lines.push(format!(
+                "{}={address}",
+                env_name(prefix, contract_name, label, "ADDRESS")
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+fn to_exported(chain_id: &str, contracts: &Map<String, WasmRef>) -> ExportedNetwork {
+    ExportedNetwork {
+        chain_id: chain_id.to_string(),
+        contracts: contracts
+            .iter()
+            .map(|(name, wasm_ref)| {
+                (
+                    name.clone(),
+                    ExportedContract {
+                        code_id: *wasm_ref.code_id(),
+                        addresses: wasm_ref.addresses().clone(),
+                    },
+                )
+            })
+            .collect(),
+    }
+}
+
+/// Code ids and addresses of contracts deployed on `network`, keyed by contract name
+pub fn address_map(
+    root: PathBuf,
+    network: &str,
+    network_info: Network,
+) -> Result<Map<String, ExportedContract>> {
+    let contracts =
+        State::load_by_network_if_exists(network_info.clone(), root)?.contracts(network);
+    Ok(to_exported(network_info.chain_id(), &contracts).contracts)
+}
+
+pub fn export<'a, Ctx: Context<'a, StateConfig>>(
+    ctx: &Ctx,
+    format: &ExportFormat,
+    network: Option<&String>,
+    env_prefix: &str,
+    out: Option<&PathBuf>,
+) -> Result<()> {
+    let global_config = ctx.global_config()?;
+    let root = ctx.root()?;
+
+    if let Some(network) = network {
+        if !global_config.networks().contains_key(network) {
+            bail!("Unable to find network config: {network}");
+        }
+    }
+
+    // contracts of each selected network which has any deployment
+    let mut deployments = vec![];
+    for (name, network_info) in global_config.networks() {
+        if network.map(|n| n != name).unwrap_or(false) {
+            continue;
+        }
+        let contracts =
+            State::load_by_network_if_exists(network_info.clone(), root.clone())?.contracts(name);
+        if !contracts.is_empty() {
+            deployments.push((name, network_info, contracts));
+        }
+    }
+
+    let content = match format {
+        ExportFormat::CwOrch => {
+            let mut cw_orch_state = CwOrchState::new();
+            let mut exported_by: Map<&str, &str> = Map::new();
+            for (network, network_info, contracts) in &deployments {
+                let chain_id = network_info.chain_id().as_str();
+                if let Some(other) = exported_by.insert(chain_id, network.as_str()) {
+                    bail!("Networks `{other}` and `{network}` are both deployed to chain id `{chain_id}`, export them separately with `--network`");
+                }
+                cw_orch_state.insert(chain_id.to_string(), to_cw_orch(contracts));
+            }
+            serde_json::to_string_pretty(&cw_orch_state)?
+        }
+        ExportFormat::Env => {
+            if network.is_none() {
+                bail!("`--network` is required for `env` format, since variable names do not include network");
+            }
+            deployments
+                .first()
+                .map(|(_, _, contracts)| to_env(env_prefix, contracts))
+                .unwrap_or_default()
+        }
+        ExportFormat::Json => match network {
+            Some(_) => serde_json::to_string_pretty(&deployments.first().map(
+                |(_, network_info, contracts)| to_exported(network_info.chain_id(), contracts),
+            ))?,
+            None => serde_json::to_string_pretty(
+                &deployments
+                    .iter()
+                    .map(|(network, network_info, contracts)| {
+                        (
+                            network.to_string(),
+                            to_exported(network_info.chain_id(), contracts),
+                        )
+                    })
+                    .collect::<Map<String, ExportedNetwork>>(),
+            )?,
+        },
+    };
+
+    match out {
+        Some(path) => fs::write(path, content)
+            .with_context(|| format!("Unable to write to `{}`", path.to_string_lossy())),
+        None => {
+            println!("{content}");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_env_variables() {
+        let contracts = Map::from([(
+            "cw-counter".to_string(),
+            WasmRef::default()
+                .with_code_id(Some(4))
+                .with_address("default", "osmo1default")
+                .with_address("v2", "osmo1v2"),
+        )]);
+
+        assert_eq!(
+            to_env("NEXT_PUBLIC_", &contracts),
+            "NEXT_PUBLIC_CW_COUNTER_CODE_ID=4\n\
+             NEXT_PUBLIC_CW_COUNTER_ADDRESS=osmo1default\n\
+             NEXT_PUBLIC_CW_COUNTER_V2_ADDRESS=osmo1v2"
+        );
+    }
+
+    #[test]
+    fn export_json_map() {
+        let contracts = Map::from([(
+            "counter".to_string(),
+            WasmRef::default()
+                .with_code_id(Some(1))
+                .with_address("default", "osmo1abc"),
+        )]);
+
+        assert_eq!(
+            serde_json::to_value(to_exported("localosmosis", &contracts)).unwrap(),
+            serde_json::json!({
+                "chain_id": "localosmosis",
+                "contracts": {
+                    "counter": { "code_id": 1, "addresses": { "default": "osmo1abc" } }
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use crate::framework::Context;
+use crate::modules::state::config::StateConfig;
+use crate::support::coin::display_coin;
+use crate::support::gas_ledger::{load_entries, summarize, GasSummary};
+use crate::support::output;
+use anyhow::Result;
+use config::Map;
+
+pub fn gas_summary<'a, Ctx: Context<'a, StateConfig>>(
+    ctx: &Ctx,
+    network: Option<&String>,
+) -> Result<()> {
+    let entries = load_entries(&ctx.root()?)?
+        .into_iter()
+        .filter(|e| network.map(|n| n == &e.network).unwrap_or(true))
+        .collect::<Vec<_>>();
+
+    let summary = summarize(&entries);
+    output::print(&summary, || display_summary(&summary));
+    Ok(())
+}
+
+fn display_fees(fees: &Map<String, u128>) -> String {
+    if fees.is_empty() {
+        return "–".to_string();
+    }
+    fees.iter()
+        .map(|(denom, amount)| display_coin(&amount.to_string(), denom))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn display_tx_count(s: &GasSummary) -> String {
+    match s.failed_tx_count {
+        0 => format!("{} tx", s.tx_count),
+        failed => format!("{} tx ({failed} failed)", s.tx_count),
+    }
+}
+
+fn display_summary(summary: &Map<String, Map<String, GasSummary>>) -> String {
+    if summary.is_empty() {
+        return "No transaction recorded in gas ledger".to_string();
+    }
+
+    let mut lines = vec![];
+    for (network, contracts) in summary {
+        let mut total = GasSummary::default();
+        lines.push(format!("  {network}"));
+        for (contract, s) in contracts {
+            lines.push(format!(
+                "    ├── {contract}: {}, gas used {} / wanted {}, fees {}",
+                display_tx_count(s),
+                s.gas_used,
+                s.gas_wanted,
+                display_fees(&s.fees)
+            ));
+            total.tx_count += s.tx_count;
+            total.failed_tx_count += s.failed_tx_count;
+            total.gas_used += s.gas_used;
+            total.gas_wanted += s.gas_wanted;
+            for (denom, amount) in &s.fees {
+                *total.fees.entry(denom.clone()).or_default() += amount;
+            }
+        }
+        lines.push(format!(
+            "    └── total: {}, gas used {} / wanted {}, fees {}",
+            display_tx_count(&total),
+            total.gas_used,
+            total.gas_wanted,
+            display_fees(&total.fees)
+        ));
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,98 @@
+use crate::framework::Context;
+use crate::modules::state::config::StateConfig;
+use crate::support::output;
+use crate::support::state::{HistoryEntry, State, WasmRef};
+use anyhow::{bail, Result};
+use config::Map;
+use cosmrs::tendermint::Time;
+
+pub fn history<'a, Ctx: Context<'a, StateConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    network: Option<&String>,
+) -> Result<()> {
+    let global_config = ctx.global_config()?;
+    let root = ctx.root()?;
+
+    if let Some(network) = network {
+        if !global_config.networks().contains_key(network) {
+            bail!("Unable to find network config: {network}");
+        }
+    }
+
+    let mut wasm_refs: Map<String, WasmRef> = Map::new();
+    for (network_name, network_info) in global_config.networks() {
+        if network.map(|n| n != network_name).unwrap_or(false) {
+            continue;
+        }
+
+        let wasm_ref = State::load_by_network(network_info.clone(), root.clone())
+            .and_then(|s| s.get_ref(network_name, contract_name))
+            .unwrap_or_default();
+
+        if !wasm_ref.history().is_empty() {
+            wasm_refs.insert(network_name.to_string(), wasm_ref);
+        }
+    }
+
+    let histories: Map<&String, &Vec<HistoryEntry>> = wasm_refs
+        .iter()
+        .map(|(network, wasm_ref)| (network, wasm_ref.history()))
+        .collect();
+    output::print(&histories, || display_histories(contract_name, &wasm_refs));
+    Ok(())
+}
+
+pub fn display_time(timestamp: u64) -> String {
+    if timestamp == 0 {
+        return "–".to_string();
+    }
+    Time::from_unix_timestamp(timestamp as i64, 0)
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_else(|_| timestamp.to_string())
+}
+
+fn display_entry(entry: &HistoryEntry, rolled_back: bool) -> String {
+    let mut details = vec![format!("code_id: {}", entry.code_id())];
+    if !entry.label().is_empty() {
+        details.push(format!("label: {}", entry.label()));
+    }
+    if let Some(checksum) = entry.checksum() {
+        details.push(format!("checksum: {checksum}"));
+    }
+    if let Some(git_commit) = entry.git_commit() {
+        details.push(format!("commit: {git_commit}"));
+    }
+    if let Some(tx_hash) = entry.tx_hash() {
+        details.push(format!("tx: {tx_hash}"));
+    }
+
+    let action = format!("{:?}", entry.action()).to_lowercase();
+    let rolled_back = if rolled_back { " (rolled back)" } else { "" };
+
+    format!(
+        "{} {action}{rolled_back} [{}]",
+        display_time(*entry.timestamp()),
+        details.join(", ")
+    )
+}
+
+fn display_histories(contract_name: &str, wasm_refs: &Map<String, WasmRef>) -> String {
+    if wasm_refs.is_empty() {
+        return format!("No history found for `{contract_name}`");
+    }
+
+    let mut lines = vec![];
+    for (network, wasm_ref) in wasm_refs {
+        lines.push(format!("  {network}"));
+        let last = wasm_ref.history().len() - 1;
+        for (i, entry) in wasm_ref.history().iter().enumerate() {
+            let branch = if i == last { "└──" } else { "├──" };
+            lines.push(format!(
+                "    {branch} {}",
+                display_entry(entry, wasm_ref.is_rolled_back(i))
+            ));
+        }
+    }
+    lines.join("\n")
+}
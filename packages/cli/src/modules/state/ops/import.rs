@@ -0,0 +1,61 @@
+use crate::framework::Context;
+use crate::modules::state::config::StateConfig;
+use crate::modules::state::cw_orch::{from_cw_orch, CwOrchState};
+use crate::modules::state::entrypoint::StateFormat;
+use crate::support::state::State;
+use anyhow::{Context as _, Result};
+use console::style;
+use std::{fs, path::PathBuf};
+
+pub fn import<'a, Ctx: Context<'a, StateConfig>>(
+    ctx: &Ctx,
+    file: &PathBuf,
+    format: &StateFormat,
+) -> Result<()> {
+    let global_config = ctx.global_config()?;
+    let file_str = file.to_string_lossy();
+    let content =
+        fs::read_to_string(file).with_context(|| format!("Unable to read from `{file_str}`"))?;
+
+    match format {
+        StateFormat::CwOrch => {
+            let cw_orch_state: CwOrchState = serde_json::from_str(&content)
+                .with_context(|| format!("Unable to deserialize `{file_str}` as cw-orch state"))?;
+
+            for (chain_id, chain_state) in &cw_orch_state {
+                let networks = global_config
+                    .networks()
+                    .iter()
+                    .filter(|(_, n)| n.chain_id() == chain_id)
+                    .collect::<Vec<_>>();
+                if networks.is_empty() {
+                    println!(
+                        "  {} no network is configured with chain id `{chain_id}`, skipping",
+                        style("WARNING:").yellow().bold()
+                    );
+                    continue;
+                }
+
+                for (network, network_info) in networks {
+                    State::update_state_file(
+                        network_info.network_variant(),
+                        ctx.root()?,
+                        &|s: &State| -> State {
+                            from_cw_orch(chain_state, &s.contracts(network))
+                                .into_iter()
+                                .fold(s.clone(), |s, (contract_name, wasm_ref)| {
+                                    s.insert_ref(network, &contract_name, wasm_ref)
+                                })
+                        },
+                    )?;
+
+                    println!(
+                        "  Imported state of chain id `{chain_id}` for network `{}`",
+                        style(network).bold()
+                    );
+                }
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,11 @@
+pub mod diff;
+pub mod export;
+pub mod gas_summary;
+pub mod history;
+pub mod import;
+
+pub use diff::diff;
+pub use export::export;
+pub use gas_summary::gas_summary;
+pub use history::history;
+pub use import::import;
@@ -0,0 +1,42 @@
+use config::Map;
+use data_doc_derive::GetDataDocs;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Serialize, Deserialize, GetDataDocs)]
+pub struct TestConfig {
+    /// Directory golden files of `test snapshot` are recorded in, relative to workspace root
+    pub snapshot_dir: String,
+
+    /// Queries recorded by `test snapshot`, by snapshot name, eg.
+    /// `count = { contract = "counter", msg = '{ "get_count": {} }' }`.
+    /// `label` defaults to `default`, `msg` could be written in json, json5 or yaml
+    #[serde(default)]
+    pub snapshots: Map<String, QuerySnapshot>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, GetDataDocs)]
+pub struct QuerySnapshot {
+    /// Contract name in state
+    pub contract: String,
+
+    /// Label of the contract instance
+    #[serde(default = "default_label")]
+    pub label: String,
+
+    /// Query msg
+    pub msg: String,
+}
+
+fn default_label() -> String {
+    "default".to_string()
+}
+
+impl Default for TestConfig {
+    fn default() -> Self {
+        Self {
+            snapshot_dir: "snapshots".to_string(),
+            snapshots: Map::new(),
+        }
+    }
+}
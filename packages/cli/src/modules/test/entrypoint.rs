@@ -0,0 +1,39 @@
+use super::config::TestConfig;
+use super::ops;
+use crate::framework::{Context, Module};
+use anyhow::Result;
+use clap::Subcommand;
+
+#[derive(Subcommand, Debug)]
+pub enum TestCmd {
+    /// Run the queries configured in `test.snapshots` and compare their responses with recorded golden files,
+    /// failing on any difference. Snapshots without golden file are recorded
+    Snapshot {
+        /// Only run these snapshots, all configured snapshots if not specified
+        names: Vec<String>,
+
+        #[clap(short, long, default_value = "local")]
+        network: String,
+
+        /// Overwrite golden files of changed snapshots with the current responses instead of failing
+        #[clap(long)]
+        update: bool,
+    },
+}
+
+pub struct TestModule {}
+
+impl<'a> Module<'a, TestConfig, TestCmd, anyhow::Error> for TestModule {
+    fn execute<Ctx: Context<'a, TestConfig>>(ctx: Ctx, cmd: &TestCmd) -> Result<(), anyhow::Error> {
+        match cmd {
+            TestCmd::Snapshot {
+                names,
+                network,
+                update,
+            } => {
+                ops::snapshot(&ctx, names, network, *update)?;
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod config;
+pub mod entrypoint;
+pub mod ops;
+
+pub use crate::modules::test::config::TestConfig;
+pub use crate::modules::test::entrypoint::{TestCmd, TestModule};
@@ -0,0 +1,3 @@
+pub mod snapshot;
+
+pub use snapshot::snapshot;
@@ -0,0 +1,252 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context as _, Result};
+use console::style;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::framework::Context;
+use crate::modules::test::config::{QuerySnapshot, TestConfig};
+use crate::support::cosmos::Client;
+use crate::support::future::block;
+use crate::support::msg::to_json_bytes;
+use crate::support::output;
+use crate::support::state::State;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotStatus {
+    /// Response is the same as the golden file
+    Matched,
+    /// Golden file did not exist and is recorded
+    Recorded,
+    /// Golden file is overwritten with the changed response
+    Updated,
+    /// Response differs from the golden file
+    Changed,
+    /// Query could not be run
+    Failed,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SnapshotResult {
+    pub name: String,
+    pub status: SnapshotStatus,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub differences: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Differences between `expected` and `actual` as `<path>: <expected> -> <actual>` lines
+pub fn json_diff(expected: Option<&Value>, actual: Option<&Value>, path: &str) -> Vec<String> {
+    let show = |v: Option<&Value>| {
+        v.map(Value::to_string)
+            .unwrap_or_else(|| "(missing)".into())
+    };
+    match (expected, actual) {
+        (Some(Value::Object(e)), Some(Value::Object(a))) => e
+            .keys()
+            .chain(a.keys().filter(|k| !e.contains_key(*k)))
+            .flat_map(|k| json_diff(e.get(k), a.get(k), &format!("{path}.{k}")))
+            .collect(),
+        (Some(Value::Array(e)), Some(Value::Array(a))) => (0..e.len().max(a.len()))
+            .flat_map(|i| json_diff(e.get(i), a.get(i), &format!("{path}[{i}]")))
+            .collect(),
+        (e, a) if e == a => vec![],
+        (e, a) => vec![format!(
+            "{}: {} -> {}",
+            if path.is_empty() { "." } else { path },
+            show(e),
+            show(a)
+        )],
+    }
+}
+
+async fn query(
+    client: &Client,
+    state: &State,
+    network: &str,
+    snapshot: &QuerySnapshot,
+) -> Result<Value> {
+    let address = state
+        .get_ref(network, &snapshot.contract)?
+        .addresses()
+        .get(&snapshot.label)
+        .with_context(|| {
+            format!(
+                "Unable to retrieve contract for {}:{}",
+                snapshot.contract, snapshot.label
+            )
+        })?
+        .to_owned();
+    let msg = to_json_bytes(&snapshot.msg).with_context(|| "Invalid snapshot query msg")?;
+    let response = client.query_smart(address, msg).await?;
+    serde_json::from_slice(&response).with_context(|| "Unable to deserialize response")
+}
+
+/// Compare `actual` response with the golden file at `path`, recording it if missing or if `update` is set
+fn compare(name: &str, path: &Path, actual: &Value, update: bool) -> Result<SnapshotResult> {
+    let recorded = format!("{}\n", serde_json::to_string_pretty(actual)?);
+    let result = |status, differences| SnapshotResult {
+        name: name.to_string(),
+        status,
+        differences,
+        error: None,
+    };
+
+    if !path.exists() {
+        fs::create_dir_all(path.parent().expect("golden file is in snapshot dir"))?;
+        fs::write(path, recorded)?;
+        return Ok(result(SnapshotStatus::Recorded, vec![]));
+    }
+
+    let expected: Value = serde_json::from_str(&fs::read_to_string(path)?)
+        .with_context(|| format!("Invalid golden file `{}`", path.display()))?;
+    let differences = json_diff(Some(&expected), Some(actual), "");
+    if differences.is_empty() {
+        Ok(result(SnapshotStatus::Matched, vec![]))
+    } else if update {
+        fs::write(path, recorded)?;
+        Ok(result(SnapshotStatus::Updated, differences))
+    } else {
+        Ok(result(SnapshotStatus::Changed, differences))
+    }
+}
+
+fn print_results(results: &[SnapshotResult]) {
+    output::print(results, || {
+        results
+            .iter()
+            .flat_map(|r| {
+                let status = match r.status {
+                    SnapshotStatus::Matched => style("matched").green(),
+                    SnapshotStatus::Recorded => style("recorded").cyan(),
+                    SnapshotStatus::Updated => style("updated").yellow(),
+                    SnapshotStatus::Changed => style("changed").red().bold(),
+                    SnapshotStatus::Failed => style("failed").red().bold(),
+                };
+                std::iter::once(format!("  {}: {status}", r.name))
+                    .chain(r.differences.iter().map(|d| format!("    {d}")))
+                    .chain(r.error.iter().map(|e| format!("    {}", style(e).red())))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+}
+
+/// Run the configured snapshot queries against contracts deployed on `network` and compare
+/// their responses with golden files in `snapshot_dir`, failing if any response changed
+pub fn snapshot<'a, Ctx: Context<'a, TestConfig>>(
+    ctx: &Ctx,
+    names: &[String],
+    network: &str,
+    update: bool,
+) -> Result<Vec<SnapshotResult>> {
+    let config = ctx.config()?;
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    if let Some(unknown) = names.iter().find(|n| !config.snapshots.contains_key(*n)) {
+        bail!("Snapshot `{unknown}` is not configured in `test.snapshots`");
+    }
+    let snapshots = config
+        .snapshots
+        .iter()
+        .filter(|(name, _)| names.is_empty() || names.contains(name))
+        .collect::<Vec<_>>();
+    if snapshots.is_empty() {
+        bail!("No snapshots are configured in `test.snapshots`");
+    }
+
+    let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
+    let client = ctx.client(network_info);
+    let snapshot_dir = ctx.root()?.join(&config.snapshot_dir);
+
+    let results = block(async {
+        let mut results = vec![];
+        for (name, snapshot) in snapshots {
+            let result = match query(&client, &state, network, snapshot).await {
+                Ok(actual) => compare(
+                    name,
+                    &snapshot_dir.join(format!("{name}.json")),
+                    &actual,
+                    update,
+                )?,
+                Err(e) => SnapshotResult {
+                    name: name.clone(),
+                    status: SnapshotStatus::Failed,
+                    differences: vec![],
+                    error: Some(format!("{e:#}")),
+                },
+            };
+            results.push(result);
+        }
+        anyhow::Ok(results)
+    })?;
+    print_results(&results);
+
+    let failed = results
+        .iter()
+        .filter(|r| matches!(r.status, SnapshotStatus::Changed | SnapshotStatus::Failed))
+        .count();
+    if failed > 0 {
+        bail!(
+            "{failed} of {} snapshots failed, run with `--update` to accept changed responses",
+            results.len()
+        );
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_json_by_path() {
+        let expected = json!({ "count": 1, "owner": "osmo1a", "items": [1, 2] });
+        assert!(json_diff(Some(&expected), Some(&expected), "").is_empty());
+
+        let actual = json!({ "count": 2, "items": [1], "paused": false });
+        assert_eq!(
+            json_diff(Some(&expected), Some(&actual), ""),
+            vec![
+                ".count: 1 -> 2",
+                ".items[1]: 2 -> (missing)",
+                ".owner: \"osmo1a\" -> (missing)",
+                ".paused: (missing) -> false",
+            ]
+        );
+        assert_eq!(
+            json_diff(Some(&json!(1)), Some(&json!("1")), ""),
+            vec![".: 1 -> \"1\""]
+        );
+    }
+
+    #[test]
+    fn record_and_compare_golden_files() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let path = temp.path().join("snapshots/count.json");
+
+        let recorded = compare("count", &path, &json!({ "count": 1 }), false).unwrap();
+        assert_eq!(recorded.status, SnapshotStatus::Recorded);
+        let matched = compare("count", &path, &json!({ "count": 1 }), false).unwrap();
+        assert_eq!(matched.status, SnapshotStatus::Matched);
+
+        let changed = compare("count", &path, &json!({ "count": 2 }), false).unwrap();
+        assert_eq!(changed.status, SnapshotStatus::Changed);
+        assert_eq!(changed.differences, vec![".count: 1 -> 2"]);
+
+        let updated = compare("count", &path, &json!({ "count": 2 }), true).unwrap();
+        assert_eq!(updated.status, SnapshotStatus::Updated);
+        let matched = compare("count", &path, &json!({ "count": 2 }), false).unwrap();
+        assert_eq!(matched.status, SnapshotStatus::Matched);
+    }
+}
@@ -0,0 +1,149 @@
+use super::config::TokenfactoryConfig;
+use super::ops;
+use crate::framework::{Context, Module};
+use crate::modules::wasm::args::BaseTxArgs;
+use crate::support::gas::Gas;
+use anyhow::Result;
+use clap::Subcommand;
+
+#[derive(Subcommand, Debug)]
+pub enum TokenfactoryCmd {
+    /// Create `factory/<signer>/<subdenom>` denom administered by the signer
+    CreateDenom {
+        subdenom: String,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Mint tokenfactory denom administered by the signer
+    Mint {
+        /// Coin to mint, eg. `1000factory/osmo1.../mytoken`
+        amount: String,
+
+        /// Recipient address or address book entry, the signer if not specified
+        #[clap(long)]
+        to: Option<String>,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Burn tokenfactory denom administered by the signer
+    Burn {
+        /// Coin to burn, eg. `1000factory/osmo1.../mytoken`
+        amount: String,
+
+        /// Address or address book entry to burn from, the signer if not specified
+        #[clap(long)]
+        from: Option<String>,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Set bank metadata of tokenfactory denom administered by the signer
+    SetMetadata {
+        denom: String,
+
+        /// Display denom, eg. `mytoken`
+        #[clap(long)]
+        display: String,
+
+        /// Number of decimals of the display denom
+        #[clap(long, default_value = "6")]
+        exponent: u32,
+
+        #[clap(long)]
+        symbol: Option<String>,
+
+        #[clap(long)]
+        name: Option<String>,
+
+        #[clap(long, default_value = "")]
+        description: String,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+}
+
+pub struct TokenfactoryModule {}
+
+impl<'a> Module<'a, TokenfactoryConfig, TokenfactoryCmd, anyhow::Error> for TokenfactoryModule {
+    fn execute<Ctx: Context<'a, TokenfactoryConfig>>(
+        ctx: Ctx,
+        cmd: &TokenfactoryCmd,
+    ) -> Result<(), anyhow::Error> {
+        let base_tx_args = match cmd {
+            TokenfactoryCmd::CreateDenom { base_tx_args, .. }
+            | TokenfactoryCmd::Mint { base_tx_args, .. }
+            | TokenfactoryCmd::Burn { base_tx_args, .. }
+            | TokenfactoryCmd::SetMetadata { base_tx_args, .. } => base_tx_args,
+        };
+        let BaseTxArgs {
+            network,
+            signer_args,
+            gas_args,
+            timeout_height,
+        }: &BaseTxArgs = base_tx_args;
+        let global_conf = ctx.global_config()?;
+        let gas = Gas::from_args(
+            gas_args,
+            global_conf.network_gas_price(network),
+            global_conf.gas_adjustment(),
+        )?;
+        let signing_key = signer_args.private_key(&ctx.chain_client(), &global_conf, network)?;
+
+        match cmd {
+            TokenfactoryCmd::CreateDenom { subdenom, .. } => {
+                ops::create_denom(&ctx, subdenom, network, &gas, timeout_height, signing_key)?;
+            }
+            TokenfactoryCmd::Mint { amount, to, .. } => {
+                ops::mint(
+                    &ctx,
+                    amount,
+                    to.as_deref(),
+                    network,
+                    &gas,
+                    timeout_height,
+                    signing_key,
+                )?;
+            }
+            TokenfactoryCmd::Burn { amount, from, .. } => {
+                ops::burn(
+                    &ctx,
+                    amount,
+                    from.as_deref(),
+                    network,
+                    &gas,
+                    timeout_height,
+                    signing_key,
+                )?;
+            }
+            TokenfactoryCmd::SetMetadata {
+                denom,
+                display,
+                exponent,
+                symbol,
+                name,
+                description,
+                ..
+            } => {
+                ops::set_metadata(
+                    &ctx,
+                    ops::DenomMetadataArgs {
+                        denom,
+                        display,
+                        exponent: *exponent,
+                        symbol: symbol.as_deref().unwrap_or(display),
+                        name: name.as_deref().unwrap_or(display),
+                        description,
+                    },
+                    network,
+                    &gas,
+                    timeout_height,
+                    signing_key,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+pub mod config;
+pub mod entrypoint;
+pub mod ops;
+pub mod proto;
+
+pub use crate::modules::tokenfactory::config::TokenfactoryConfig;
+pub use crate::modules::tokenfactory::entrypoint::{TokenfactoryCmd, TokenfactoryModule};
@@ -0,0 +1,321 @@
+use super::config::TokenfactoryConfig;
+use super::proto::{
+    MsgBurn, MsgCreateDenom, MsgMint, MsgSetDenomMetadata, MSG_BURN, MSG_CREATE_DENOM, MSG_MINT,
+    MSG_SET_DENOM_METADATA,
+};
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::support::address_book::resolve_address;
+use crate::support::coin::CoinFromStr;
+use crate::support::cosmos::{ResponseValuePicker, SigningClient};
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::proto::MessageExt;
+use anyhow::{Context as _, Result};
+use cosmos_sdk_proto::cosmos::bank::v1beta1::{DenomUnit, Metadata};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::Any;
+use serde::Serialize;
+
+fn signing_client<'a, Ctx: Context<'a, TokenfactoryConfig>>(
+    ctx: &Ctx,
+    network: &str,
+    signing_key: SigningKey,
+) -> Result<SigningClient> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    Ok(ctx
+        .client(network_info)
+        .to_signing_client(signing_key, global_config.account_prefix())
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, None)))
+}
+
+fn parse_coin(amount: &str) -> Result<Coin> {
+    let coin = amount.parse::<CoinFromStr>()?.inner().to_owned();
+    Ok(Coin {
+        denom: coin.denom.to_string(),
+        amount: coin.amount.to_string(),
+    })
+}
+
+/// Resolve optional target address, defaults to the signer
+fn target_address<'a, Ctx: Context<'a, TokenfactoryConfig>>(
+    ctx: &Ctx,
+    network: &str,
+    client: &SigningClient,
+    target: Option<&str>,
+) -> Result<String> {
+    match target {
+        Some(target) => {
+            let global_config = ctx.global_config()?;
+            let network_info = global_config
+                .networks()
+                .get(network)
+                .with_context(|| format!("Unable to find network config: {network}"))?;
+            Ok(resolve_address(network_info, target)?.to_string())
+        }
+        None => Ok(client.signer_account_id().to_string()),
+    }
+}
+
+pub fn create_denom<'a, Ctx: Context<'a, TokenfactoryConfig>>(
+    ctx: &Ctx,
+    subdenom: &str,
+    network: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<CreateDenomResponse> {
+    let client = signing_client(ctx, network, signing_key)?;
+
+    let msg = MsgCreateDenom {
+        sender: client.signer_account_id().to_string(),
+        subdenom: subdenom.to_string(),
+    };
+    let msg = Any {
+        type_url: MSG_CREATE_DENOM.to_string(),
+        value: msg.to_bytes()?,
+    };
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg], gas, "", timeout_height)
+            .await?;
+
+        let create_denom_response = CreateDenomResponse {
+            denom: response.pick("create_denom", "new_token_denom").to_string(),
+            creator: client.signer_account_id().to_string(),
+            tx_hash: response.hash.to_string(),
+        };
+        create_denom_response.log();
+
+        Ok(create_denom_response)
+    })
+}
+
+pub fn mint<'a, Ctx: Context<'a, TokenfactoryConfig>>(
+    ctx: &Ctx,
+    amount: &str,
+    to: Option<&str>,
+    network: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<MintResponse> {
+    let client = signing_client(ctx, network, signing_key)?;
+    let address = target_address(ctx, network, &client, to)?;
+
+    let msg = MsgMint {
+        sender: client.signer_account_id().to_string(),
+        amount: Some(parse_coin(amount)?),
+        mint_to_address: address.clone(),
+    };
+    let msg = Any {
+        type_url: MSG_MINT.to_string(),
+        value: msg.to_bytes()?,
+    };
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg], gas, "", timeout_height)
+            .await?;
+
+        let mint_response = MintResponse {
+            amount: amount.to_string(),
+            to: address,
+            tx_hash: response.hash.to_string(),
+        };
+        mint_response.log();
+
+        Ok(mint_response)
+    })
+}
+
+pub fn burn<'a, Ctx: Context<'a, TokenfactoryConfig>>(
+    ctx: &Ctx,
+    amount: &str,
+    from: Option<&str>,
+    network: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<BurnResponse> {
+    let client = signing_client(ctx, network, signing_key)?;
+    let address = target_address(ctx, network, &client, from)?;
+
+    let msg = MsgBurn {
+        sender: client.signer_account_id().to_string(),
+        amount: Some(parse_coin(amount)?),
+        burn_from_address: address.clone(),
+    };
+    let msg = Any {
+        type_url: MSG_BURN.to_string(),
+        value: msg.to_bytes()?,
+    };
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg], gas, "", timeout_height)
+            .await?;
+
+        let burn_response = BurnResponse {
+            amount: amount.to_string(),
+            from: address,
+            tx_hash: response.hash.to_string(),
+        };
+        burn_response.log();
+
+        Ok(burn_response)
+    })
+}
+
+pub struct DenomMetadataArgs<'a> {
+    pub denom: &'a str,
+    pub display: &'a str,
+    pub exponent: u32,
+    pub symbol: &'a str,
+    pub name: &'a str,
+    pub description: &'a str,
+}
+
+impl DenomMetadataArgs<'_> {
+    /// Bank metadata with base denom unit and display denom unit with given exponent
+    pub fn to_metadata(&self) -> Metadata {
+        let mut denom_units = vec![DenomUnit {
+            denom: self.denom.to_string(),
+            exponent: 0,
+            aliases: vec![],
+        }];
+        if self.exponent > 0 {
+            denom_units.push(DenomUnit {
+                denom: self.display.to_string(),
+                exponent: self.exponent,
+                aliases: vec![],
+            });
+        }
+
+        Metadata {
+            description: self.description.to_string(),
+            denom_units,
+            base: self.denom.to_string(),
+            display: if self.exponent > 0 {
+                self.display.to_string()
+            } else {
+                self.denom.to_string()
+            },
+            name: self.name.to_string(),
+            symbol: self.symbol.to_string(),
+        }
+    }
+}
+
+pub fn set_metadata<'a, Ctx: Context<'a, TokenfactoryConfig>>(
+    ctx: &Ctx,
+    args: DenomMetadataArgs,
+    network: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<SetMetadataResponse> {
+    let client = signing_client(ctx, network, signing_key)?;
+
+    let msg = MsgSetDenomMetadata {
+        sender: client.signer_account_id().to_string(),
+        metadata: Some(args.to_metadata()),
+    };
+    let msg = Any {
+        type_url: MSG_SET_DENOM_METADATA.to_string(),
+        value: msg.to_bytes()?,
+    };
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(vec![msg], gas, "", timeout_height)
+            .await?;
+
+        let set_metadata_response = SetMetadataResponse {
+            denom: args.denom.to_string(),
+            display: args.display.to_string(),
+            exponent: args.exponent,
+            symbol: args.symbol.to_string(),
+            tx_hash: response.hash.to_string(),
+        };
+        set_metadata_response.log();
+
+        Ok(set_metadata_response)
+    })
+}
+
+#[derive(Serialize)]
+pub struct CreateDenomResponse {
+    pub denom: String,
+    pub creator: String,
+    pub tx_hash: String,
+}
+
+impl OpResponseDisplay for CreateDenomResponse {
+    fn headline() -> &'static str {
+        "Denom created successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | denom, creator, tx_hash }
+    }
+}
+
+#[derive(Serialize)]
+pub struct MintResponse {
+    pub amount: String,
+    pub to: String,
+    pub tx_hash: String,
+}
+
+impl OpResponseDisplay for MintResponse {
+    fn headline() -> &'static str {
+        "Minted successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | amount, to, tx_hash }
+    }
+}
+
+#[derive(Serialize)]
+pub struct BurnResponse {
+    pub amount: String,
+    pub from: String,
+    pub tx_hash: String,
+}
+
+impl OpResponseDisplay for BurnResponse {
+    fn headline() -> &'static str {
+        "Burned successfully!! 🔥 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | amount, from, tx_hash }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SetMetadataResponse {
+    pub denom: String,
+    pub display: String,
+    pub exponent: u32,
+    pub symbol: String,
+    pub tx_hash: String,
+}
+
+impl OpResponseDisplay for SetMetadataResponse {
+    fn headline() -> &'static str {
+        "Denom metadata set successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | denom, display, exponent, symbol, tx_hash }
+    }
+}
@@ -0,0 +1,45 @@
+//! Osmosis `x/tokenfactory` messages, which are not part of cosmos-sdk-proto
+
+use cosmos_sdk_proto::cosmos::bank::v1beta1::Metadata;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+
+pub const MSG_CREATE_DENOM: &str = "/osmosis.tokenfactory.v1beta1.MsgCreateDenom";
+pub const MSG_MINT: &str = "/osmosis.tokenfactory.v1beta1.MsgMint";
+pub const MSG_BURN: &str = "/osmosis.tokenfactory.v1beta1.MsgBurn";
+pub const MSG_SET_DENOM_METADATA: &str = "/osmosis.tokenfactory.v1beta1.MsgSetDenomMetadata";
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MsgCreateDenom {
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    #[prost(string, tag = "2")]
+    pub subdenom: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MsgMint {
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    #[prost(message, optional, tag = "2")]
+    pub amount: Option<Coin>,
+    #[prost(string, tag = "3")]
+    pub mint_to_address: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MsgBurn {
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    #[prost(message, optional, tag = "2")]
+    pub amount: Option<Coin>,
+    #[prost(string, tag = "3")]
+    pub burn_from_address: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MsgSetDenomMetadata {
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    #[prost(message, optional, tag = "2")]
+    pub metadata: Option<Metadata>,
+}
@@ -0,0 +1,383 @@
+use super::config::TxConfig;
+use super::ops::{self, StagedMsg};
+use crate::framework::{Context, Module};
+use crate::modules::wasm::args::{BaseTxArgs, MsgArgs};
+use crate::support::gas::{Gas, GasArgs};
+use crate::support::msg::to_json_bytes;
+use anyhow::{Context as _, Result};
+use clap::Subcommand;
+use std::path::Path;
+
+#[derive(Subcommand, Debug)]
+pub enum ComposeCmd {
+    /// Stage storing code of the contract
+    StoreCode {
+        contract_name: String,
+
+        /// If set, store the unoptimized code (only use in dev)
+        #[clap(long)]
+        no_wasm_opt: bool,
+    },
+    /// Stage instantiating the contract, code must already be stored unless `--code-id` is specified
+    Instantiate {
+        contract_name: String,
+
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        /// Code id to instantiate, use code id of the contract from state if not specified
+        #[clap(long)]
+        code_id: Option<u64>,
+
+        #[clap(flatten)]
+        msg_args: MsgArgs,
+
+        /// Admin of the contract, "signer", bech32 address or address book entry
+        #[clap(long)]
+        admin: Option<String>,
+
+        #[clap(short, long)]
+        funds: Option<String>,
+    },
+    /// Stage executing the contract
+    Execute {
+        contract_name: String,
+
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        #[clap(flatten)]
+        msg_args: MsgArgs,
+
+        #[clap(short, long)]
+        funds: Option<String>,
+
+        /// Execute on behalf of the granter address via `MsgExec`, requires prior authz grant to the signer
+        #[clap(long = "as", value_name = "GRANTER")]
+        granter: Option<String>,
+    },
+    /// Stage sending coins from the signer
+    BankSend {
+        /// Recipient address or address book entry
+        to: String,
+
+        /// Coins to send, eg. `1000uosmo`
+        amount: String,
+    },
+    /// Stage granting generic authorization to the grantee
+    AuthzGrant {
+        grantee: String,
+
+        #[clap(long, default_value = "/cosmwasm.wasm.v1.MsgExecuteContract")]
+        msg_type_url: String,
+
+        /// Number of seconds from now until the grant expires
+        #[clap(long)]
+        expire_in: Option<u64>,
+    },
+    /// Stage chain native msg encoded from its proto json, eg. `/osmosis.gamm.v1beta1.MsgSwapExactAmountIn`.
+    /// `"{{signer}}"` strings in the msg are replaced by the signer address when broadcasting
+    Stargate {
+        /// Type url of the msg
+        type_url: String,
+
+        #[clap(flatten)]
+        msg_args: MsgArgs,
+
+        /// Binary `FileDescriptorSet` containing the msg type, loaded in addition to `tx.descriptor_sets` config
+        #[clap(long)]
+        descriptor_set: Vec<String>,
+    },
+    /// Show staged msgs
+    Show,
+    /// Discard all staged msgs
+    Clear,
+    /// Sign and broadcast all staged msgs atomically in a single transaction
+    Broadcast {
+        /// Memo of the transaction
+        #[clap(long, default_value = "")]
+        memo: String,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TxCmd {
+    /// Compose multiple msgs into one transaction, msgs are staged in state dir until broadcasted
+    Compose {
+        #[clap(subcommand)]
+        cmd: ComposeCmd,
+    },
+    /// Sign and broadcast arbitrary msgs encoded from their proto json, for msgs beaker has no command for
+    Raw {
+        /// Type url of the msg, eg. `/osmosis.gamm.v1beta1.MsgSwapExactAmountIn`, repeat for multiple msgs
+        #[clap(long, required = true)]
+        type_url: Vec<String>,
+
+        /// Proto json of the msg, paired with `--type-url` of the same position
+        #[clap(long, required = true)]
+        value_file: Vec<String>,
+
+        /// Binary `FileDescriptorSet` containing the msg types, loaded in addition to `tx.descriptor_sets` config
+        #[clap(long)]
+        descriptor_set: Vec<String>,
+
+        /// Memo of the transaction
+        #[clap(long, default_value = "")]
+        memo: String,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Decode raw tx into readable json, eg. for verifying offline signed or multisig txs before broadcasting
+    Decode {
+        /// Raw tx as base64 or hex string, or file containing it (binary, base64 or hex)
+        tx: String,
+
+        /// Binary `FileDescriptorSet` for decoding msgs unknown to beaker, loaded in addition to `tx.descriptor_sets` config
+        #[clap(long)]
+        descriptor_set: Vec<String>,
+    },
+    /// Estimate gas and fee of a tx by simulating it unsigned, no signing key of the sender is needed
+    EstimateFee {
+        /// Tx json, eg. from `<chain binary> tx ... --generate-only`, or json list of msgs with `@type`
+        #[clap(long, required_unless_present = "composed")]
+        msg_file: Option<String>,
+
+        /// Estimate msgs staged with `beaker tx compose` instead
+        #[clap(long, conflicts_with = "msg_file")]
+        composed: bool,
+
+        /// Sender of the tx: bech32 address, address book entry or account name
+        #[clap(long)]
+        from: String,
+
+        /// Binary `FileDescriptorSet` for encoding msgs unknown to beaker, loaded in addition to `tx.descriptor_sets` config
+        #[clap(long)]
+        descriptor_set: Vec<String>,
+
+        #[clap(short, long, default_value = "local")]
+        network: String,
+
+        #[clap(flatten)]
+        gas_args: GasArgs,
+    },
+    /// Show committed tx by its hash with decoded msgs, events, result and gas usage
+    Show {
+        hash: String,
+
+        #[clap(short, long, default_value = "local")]
+        network: String,
+
+        /// Binary `FileDescriptorSet` for decoding msgs unknown to beaker, loaded in addition to `tx.descriptor_sets` config
+        #[clap(long)]
+        descriptor_set: Vec<String>,
+    },
+}
+
+pub struct TxModule {}
+
+impl<'a> Module<'a, TxConfig, TxCmd, anyhow::Error> for TxModule {
+    fn execute<Ctx: Context<'a, TxConfig>>(ctx: Ctx, cmd: &TxCmd) -> Result<(), anyhow::Error> {
+        match cmd {
+            TxCmd::Compose { cmd } => compose(ctx, cmd),
+            TxCmd::Raw {
+                type_url,
+                value_file,
+                descriptor_set,
+                memo,
+                base_tx_args,
+            } => {
+                let BaseTxArgs {
+                    network,
+                    signer_args,
+                    gas_args,
+                    timeout_height,
+                }: &BaseTxArgs = base_tx_args;
+                let global_conf = ctx.global_config()?;
+
+                ops::broadcast_raw(
+                    &ctx,
+                    type_url,
+                    value_file,
+                    descriptor_set,
+                    network,
+                    memo,
+                    &Gas::from_args(
+                        gas_args,
+                        global_conf.network_gas_price(network),
+                        global_conf.gas_adjustment(),
+                    )?,
+                    timeout_height,
+                    signer_args.private_key(&ctx.chain_client(), &global_conf, network)?,
+                )?;
+                Ok(())
+            }
+            TxCmd::Decode { tx, descriptor_set } => {
+                ops::decode(&ctx, tx, descriptor_set)?;
+                Ok(())
+            }
+            TxCmd::EstimateFee {
+                msg_file,
+                composed: _,
+                from,
+                descriptor_set,
+                network,
+                gas_args,
+            } => {
+                let global_conf = ctx.global_config()?;
+                let source = match msg_file {
+                    Some(file) => ops::EstimateSource::File(Path::new(file)),
+                    None => ops::EstimateSource::Composed,
+                };
+                ops::estimate_fee(
+                    &ctx,
+                    source,
+                    from,
+                    descriptor_set,
+                    network,
+                    &Gas::from_args(
+                        gas_args,
+                        global_conf.network_gas_price(network),
+                        global_conf.gas_adjustment(),
+                    )?,
+                )?;
+                Ok(())
+            }
+            TxCmd::Show {
+                hash,
+                network,
+                descriptor_set,
+            } => {
+                ops::show(&ctx, hash, network, descriptor_set)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+fn compose<'a, Ctx: Context<'a, TxConfig>>(ctx: Ctx, cmd: &ComposeCmd) -> Result<()> {
+    match cmd {
+        ComposeCmd::StoreCode {
+            contract_name,
+            no_wasm_opt,
+        } => ops::stage(
+            &ctx,
+            StagedMsg::StoreCode {
+                contract_name: contract_name.clone(),
+                no_wasm_opt: *no_wasm_opt,
+            },
+        ),
+        ComposeCmd::Instantiate {
+            contract_name,
+            label,
+            code_id,
+            msg_args,
+            admin,
+            funds,
+        } => {
+            let msg = StagedMsg::resolve_msg(
+                &ctx.root()?,
+                contract_name,
+                label,
+                msg_args.raw_msg()?.as_ref(),
+                "instantiate",
+            )?;
+            ops::stage(
+                &ctx,
+                StagedMsg::Instantiate {
+                    contract_name: contract_name.clone(),
+                    label: label.clone(),
+                    code_id: *code_id,
+                    msg,
+                    admin: admin.clone(),
+                    funds: funds.clone(),
+                },
+            )
+        }
+        ComposeCmd::Execute {
+            contract_name,
+            label,
+            msg_args,
+            funds,
+            granter,
+        } => {
+            let msg = StagedMsg::resolve_msg(
+                &ctx.root()?,
+                contract_name,
+                label,
+                msg_args.raw_msg()?.as_ref(),
+                "execute",
+            )?;
+            ops::stage(
+                &ctx,
+                StagedMsg::Execute {
+                    contract_name: contract_name.clone(),
+                    label: label.clone(),
+                    msg,
+                    funds: funds.clone(),
+                    granter: granter.clone(),
+                },
+            )
+        }
+        ComposeCmd::BankSend { to, amount } => ops::stage(
+            &ctx,
+            StagedMsg::BankSend {
+                to: to.clone(),
+                amount: amount.clone(),
+            },
+        ),
+        ComposeCmd::AuthzGrant {
+            grantee,
+            msg_type_url,
+            expire_in,
+        } => ops::stage(
+            &ctx,
+            StagedMsg::AuthzGrant {
+                grantee: grantee.clone(),
+                msg_type_url: msg_type_url.clone(),
+                expire_in: *expire_in,
+            },
+        ),
+        ComposeCmd::Stargate {
+            type_url,
+            msg_args,
+            descriptor_set,
+        } => {
+            let msg = msg_args.raw_msg()?.with_context(|| {
+                "Proto json of the msg is required, use `--raw` or `--msg-file`"
+            })?;
+            let value = serde_json::from_slice(&to_json_bytes(&msg)?)?;
+            ops::stage_stargate(&ctx, type_url, value, descriptor_set)
+        }
+        ComposeCmd::Show => ops::show_composed(&ctx),
+        ComposeCmd::Clear => ops::clear_composed(&ctx),
+        ComposeCmd::Broadcast { memo, base_tx_args } => {
+            let BaseTxArgs {
+                network,
+                signer_args,
+                gas_args,
+                timeout_height,
+            }: &BaseTxArgs = base_tx_args;
+
+            ops::broadcast_composed(
+                &ctx,
+                network,
+                memo,
+                {
+                    let global_conf = ctx.global_config()?;
+                    &Gas::from_args(
+                        gas_args,
+                        global_conf.network_gas_price(network),
+                        global_conf.gas_adjustment(),
+                    )?
+                },
+                timeout_height,
+                signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
+            )?;
+            Ok(())
+        }
+    }
+}
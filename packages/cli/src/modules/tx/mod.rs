@@ -0,0 +1,6 @@
+pub mod config;
+pub mod entrypoint;
+pub mod ops;
+
+pub use crate::modules::tx::config::TxConfig;
+pub use crate::modules::tx::entrypoint::{TxCmd, TxModule};
@@ -0,0 +1,601 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::framework::config::Network;
+use crate::framework::Context;
+use crate::modules::tx::config::TxConfig;
+use crate::modules::tx::ops::raw::{encode_any, load_descriptors};
+use crate::support::address_book::resolve_address;
+use crate::support::authz::{generic_grant, wrap_exec};
+use crate::support::coin::Coins;
+use crate::support::cosmos::ResponseValuePicker;
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
+use crate::support::msg::msg_bytes;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::output;
+use crate::support::permission::compute_admin;
+use crate::support::state::{State, STATE_DIR};
+use crate::support::wasm::read_wasm;
+use anyhow::{anyhow, bail, Context as _, Result};
+use cosmrs::bank::MsgSend;
+use cosmrs::cosmwasm::{MsgExecuteContract, MsgInstantiateContract, MsgStoreCode};
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::Msg;
+use cosmrs::{AccountId, Any};
+use prost_reflect::DescriptorPool;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const COMPOSE_FILE: &str = "tx_compose.json";
+
+/// Placeholder in proto json of staged chain msgs, replaced by the signer address when broadcasting
+pub const SIGNER_PLACEHOLDER: &str = "{{signer}}";
+
+/// Message staged for composed transaction, converted to the actual message only when broadcasting
+/// since signer is not known while staging.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StagedMsg {
+    StoreCode {
+        contract_name: String,
+        no_wasm_opt: bool,
+    },
+    Instantiate {
+        contract_name: String,
+        label: String,
+        code_id: Option<u64>,
+        msg: serde_json::Value,
+        admin: Option<String>,
+        funds: Option<String>,
+    },
+    Execute {
+        contract_name: String,
+        label: String,
+        msg: serde_json::Value,
+        funds: Option<String>,
+        /// Executed on behalf of the granter via `MsgExec` if specified
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        granter: Option<String>,
+    },
+    BankSend {
+        to: String,
+        amount: String,
+    },
+    AuthzGrant {
+        grantee: String,
+        msg_type_url: String,
+        expire_in: Option<u64>,
+    },
+    /// Chain native msg (eg. gamm swap, tokenfactory, distribution) encoded from its proto json
+    Stargate {
+        type_url: String,
+        value: Value,
+        /// Descriptor sets given when staging, loaded in addition to `tx.descriptor_sets` config
+        descriptor_sets: Vec<String>,
+    },
+}
+
+impl StagedMsg {
+    fn describe(&self) -> String {
+        match self {
+            StagedMsg::StoreCode { contract_name, .. } => format!("store_code {contract_name}"),
+            StagedMsg::Instantiate {
+                contract_name,
+                label,
+                code_id,
+                ..
+            } => format!(
+                "instantiate {contract_name}:{label}{}",
+                code_id
+                    .map(|id| format!(" (code_id: {id})"))
+                    .unwrap_or_default()
+            ),
+            StagedMsg::Execute {
+                contract_name,
+                label,
+                msg,
+                granter,
+                ..
+            } => format!(
+                "execute {contract_name}:{label} {msg}{}",
+                granter
+                    .as_ref()
+                    .map(|g| format!(" (as {g})"))
+                    .unwrap_or_default()
+            ),
+            StagedMsg::BankSend { to, amount } => format!("bank send {amount} to {to}"),
+            StagedMsg::AuthzGrant {
+                grantee,
+                msg_type_url,
+                ..
+            } => format!("authz grant {msg_type_url} to {grantee}"),
+            StagedMsg::Stargate { type_url, .. } => format!("stargate {type_url}"),
+        }
+    }
+
+    /// Resolve json msg of instantiate / execute while staging, so that later changes of msg files
+    /// do not affect what is going to be broadcasted
+    pub fn resolve_msg(
+        root: &Path,
+        contract_name: &str,
+        label: &str,
+        raw: Option<&String>,
+        action: &str,
+    ) -> Result<serde_json::Value> {
+        let msg = msg_bytes(
+            raw,
+            root.join("contracts")
+                .join(contract_name)
+                .join(format!("{action}-msgs"))
+                .join(format!("{label}.json")),
+            action,
+        )?;
+        Ok(serde_json::from_slice(&msg)?)
+    }
+
+    fn to_any(
+        &self,
+        root: &Path,
+        network: &str,
+        network_info: &Network,
+        signer: &AccountId,
+        pool: &DescriptorPool,
+    ) -> Result<Any> {
+        let state = || State::load_by_network(network_info.clone(), root.to_path_buf());
+
+        let msg = match self {
+            StagedMsg::StoreCode {
+                contract_name,
+                no_wasm_opt,
+            } => MsgStoreCode {
+                sender: signer.clone(),
+                wasm_byte_code: read_wasm(
+                    root.to_path_buf(),
+                    contract_name.replace('-', "_").as_str(),
+                    no_wasm_opt,
+                )?,
+                instantiate_permission: None,
+            }
+            .to_any(),
+            StagedMsg::Instantiate {
+                contract_name,
+                label,
+                code_id,
+                msg,
+                admin,
+                funds,
+            } => {
+                let code_id = match code_id {
+                    Some(code_id) => *code_id,
+                    None => state()?
+                        .get_ref(network, contract_name)?
+                        .code_id()
+                        .with_context(|| {
+                            format!("No code_id found for `{contract_name}` on `{network}`, stored code is required before instantiate could be composed")
+                        })?,
+                };
+                MsgInstantiateContract {
+                    sender: signer.clone(),
+                    admin: compute_admin(admin.as_ref(), signer.clone(), network_info)?,
+                    code_id,
+                    label: Some(label.clone()),
+                    msg: serde_json::to_vec(msg)?,
+                    funds: Coins::try_from(funds.as_deref())?.into(),
+                }
+                .to_any()
+            }
+            StagedMsg::Execute {
+                contract_name,
+                label,
+                msg,
+                funds,
+                granter,
+            } => {
+                let contract = state()?
+                    .get_ref(network, contract_name)?
+                    .addresses()
+                    .get(label)
+                    .with_context(|| {
+                        format!("Unable to retrieve contract for {contract_name}:{label}")
+                    })?
+                    .parse::<AccountId>()
+                    .map_err(|e| anyhow!(e))?;
+                let granter = granter
+                    .as_ref()
+                    .map(|g| resolve_address(network_info, g))
+                    .transpose()?;
+                let msg = MsgExecuteContract {
+                    sender: granter.clone().unwrap_or_else(|| signer.clone()),
+                    contract,
+                    msg: serde_json::to_vec(msg)?,
+                    funds: Coins::try_from(funds.as_deref())?.into(),
+                }
+                .to_any()
+                .map_err(|e| anyhow!(e))?;
+                return match granter {
+                    Some(_) => wrap_exec(signer, vec![msg]),
+                    None => Ok(msg),
+                };
+            }
+            StagedMsg::BankSend { to, amount } => MsgSend {
+                from_address: signer.clone(),
+                to_address: resolve_address(network_info, to)?,
+                amount: amount.parse::<Coins>()?.into(),
+            }
+            .to_any(),
+            StagedMsg::AuthzGrant {
+                grantee,
+                msg_type_url,
+                expire_in,
+            } => {
+                let expiration = expire_in
+                    .map(|secs| -> Result<Duration> {
+                        Ok(SystemTime::now().duration_since(UNIX_EPOCH)?
+                            + Duration::from_secs(secs))
+                    })
+                    .transpose()?;
+                return generic_grant(
+                    signer,
+                    &resolve_address(network_info, grantee)?,
+                    msg_type_url,
+                    expiration,
+                );
+            }
+            StagedMsg::Stargate {
+                type_url, value, ..
+            } => {
+                let value = replace_signer(value, &signer.to_string());
+                return encode_any(pool, type_url, &value.to_string());
+            }
+        };
+
+        msg.map_err(|e| anyhow!(e))
+    }
+}
+
+/// `value` with every `"{{signer}}"` string replaced by `signer`
+fn replace_signer(value: &Value, signer: &str) -> Value {
+    match value {
+        Value::String(s) if s == SIGNER_PLACEHOLDER => Value::String(signer.to_string()),
+        Value::Array(values) => {
+            Value::Array(values.iter().map(|v| replace_signer(v, signer)).collect())
+        }
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), replace_signer(v, signer)))
+                .collect(),
+        ),
+        v => v.clone(),
+    }
+}
+
+/// Descriptor pool of `tx.descriptor_sets` config and descriptor sets of staged chain msgs,
+/// empty if no chain msg is staged
+fn staged_descriptors<'a, Ctx: Context<'a, TxConfig>>(
+    ctx: &Ctx,
+    root: &Path,
+    staged: &[StagedMsg],
+) -> Result<DescriptorPool> {
+    let staged_sets = staged
+        .iter()
+        .filter_map(|m| match m {
+            StagedMsg::Stargate {
+                descriptor_sets, ..
+            } => Some(descriptor_sets),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if staged_sets.is_empty() {
+        return Ok(DescriptorPool::new());
+    }
+
+    let mut paths: Vec<PathBuf> = vec![];
+    let config_sets = ctx.config()?.descriptor_sets.iter().map(|p| root.join(p));
+    let staged_sets = staged_sets.into_iter().flatten().map(PathBuf::from);
+    for path in config_sets.chain(staged_sets) {
+        // the same file can't be added to the pool twice
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    load_descriptors(&paths)
+}
+
+/// Stage chain native msg after checking that it encodes with the loaded descriptors,
+/// `"{{signer}}"` strings in `value` are replaced by the signer address when broadcasting
+pub fn stage_stargate<'a, Ctx: Context<'a, TxConfig>>(
+    ctx: &Ctx,
+    type_url: &str,
+    value: Value,
+    descriptor_sets: &[String],
+) -> Result<()> {
+    let descriptor_sets = descriptor_sets
+        .iter()
+        .map(|p| {
+            fs::canonicalize(p)
+                .map(|p| p.to_string_lossy().to_string())
+                .with_context(|| format!("Unable to find descriptor set `{p}`"))
+        })
+        .collect::<Result<Vec<String>>>()?;
+    let msg = StagedMsg::Stargate {
+        type_url: type_url.to_string(),
+        value: value.clone(),
+        descriptor_sets,
+    };
+
+    // `"{{signer}}"` is a valid string value, so the msg encodes the same as when broadcasting
+    let root = ctx.root()?;
+    let pool = staged_descriptors(ctx, &root, std::slice::from_ref(&msg))?;
+    encode_any(&pool, type_url, &value.to_string())?;
+
+    stage(ctx, msg)
+}
+
+fn compose_file(root: &Path) -> PathBuf {
+    root.join(STATE_DIR).join(COMPOSE_FILE)
+}
+
+fn load_staged(root: &Path) -> Result<Vec<StagedMsg>> {
+    let path = compose_file(root);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&path)?;
+    serde_json::from_str(&content).with_context(|| {
+        format!(
+            "Unable to read staged msgs from `{}`",
+            path.to_string_lossy()
+        )
+    })
+}
+
+fn save_staged(root: &Path, msgs: &[StagedMsg]) -> Result<()> {
+    fs::create_dir_all(root.join(STATE_DIR))?;
+    fs::write(compose_file(root), serde_json::to_string_pretty(msgs)?)?;
+    Ok(())
+}
+
+fn display_staged(msgs: &[StagedMsg]) -> String {
+    if msgs.is_empty() {
+        return "No staged msg".to_string();
+    }
+    let mut lines = vec!["  Staged msgs".to_string()];
+    lines.extend(
+        msgs.iter()
+            .enumerate()
+            .map(|(i, m)| format!("    {}. {}", i + 1, m.describe())),
+    );
+    lines.join("\n")
+}
+
+pub fn stage<'a, Ctx: Context<'a, TxConfig>>(ctx: &Ctx, msg: StagedMsg) -> Result<()> {
+    let root = ctx.root()?;
+    let mut msgs = load_staged(&root)?;
+    msgs.push(msg);
+    save_staged(&root, &msgs)?;
+
+    output::print(&msgs, || display_staged(&msgs));
+    Ok(())
+}
+
+pub fn show_composed<'a, Ctx: Context<'a, TxConfig>>(ctx: &Ctx) -> Result<()> {
+    let msgs = load_staged(&ctx.root()?)?;
+    output::print(&msgs, || display_staged(&msgs));
+    Ok(())
+}
+
+pub fn clear_composed<'a, Ctx: Context<'a, TxConfig>>(ctx: &Ctx) -> Result<()> {
+    let path = compose_file(&ctx.root()?);
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Actual msgs of `staged` as sent by `signer`
+fn staged_to_any<'a, Ctx: Context<'a, TxConfig>>(
+    ctx: &Ctx,
+    staged: &[StagedMsg],
+    network: &str,
+    network_info: &Network,
+    signer: &AccountId,
+) -> Result<Vec<Any>> {
+    let root = ctx.root()?;
+    let pool = staged_descriptors(ctx, &root, staged)?;
+    staged
+        .iter()
+        .map(|m| m.to_any(&root, network, network_info, signer, &pool))
+        .collect()
+}
+
+/// Staged msgs as sent by `signer`, error if nothing is staged
+pub fn composed_msgs<'a, Ctx: Context<'a, TxConfig>>(
+    ctx: &Ctx,
+    network: &str,
+    network_info: &Network,
+    signer: &AccountId,
+) -> Result<Vec<Any>> {
+    let staged = load_staged(&ctx.root()?)?;
+    if staged.is_empty() {
+        bail!("No staged msg, stage msgs with `beaker tx compose <msg>` first");
+    }
+    staged_to_any(ctx, &staged, network, network_info, signer)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn broadcast_composed<'a, Ctx: Context<'a, TxConfig>>(
+    ctx: &Ctx,
+    network: &str,
+    memo: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<ComposedTxResponse> {
+    let global_config = ctx.global_config()?;
+    let account_prefix = global_config.account_prefix().as_str();
+    let root = ctx.root()?;
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let staged = load_staged(&root)?;
+    if staged.is_empty() {
+        bail!("No staged msg to broadcast, stage msgs with `beaker tx compose <msg>` first");
+    }
+
+    let client = ctx
+        .client(network_info.clone())
+        .to_signing_client(signing_key, account_prefix)
+        .with_gas_ledger(GasLedger::new(root.clone(), network, None));
+    let msgs = staged_to_any(
+        ctx,
+        &staged,
+        network,
+        &network_info,
+        &client.signer_account_id(),
+    )?;
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(msgs, gas, memo, timeout_height)
+            .await?;
+
+        // events are emitted in msg order, match them with staged msgs of the same kind
+        let code_ids = response
+            .pick_all("store_code", "code_id")
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>();
+        let addresses = response
+            .pick_all("instantiate", "_contract_address")
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>();
+
+        State::update_state_file(
+            network_info.network_variant(),
+            root.clone(),
+            &|s: &State| {
+                let mut s = s.clone();
+                let mut code_ids = code_ids.iter();
+                let mut addresses = addresses.iter();
+                for m in &staged {
+                    match m {
+                        StagedMsg::StoreCode { contract_name, .. } => {
+                            if let Some(Ok(code_id)) = code_ids.next().map(|id| id.parse::<u64>()) {
+                                s = s.update_code_id(network, contract_name, &code_id);
+                            }
+                        }
+                        StagedMsg::Instantiate {
+                            contract_name,
+                            label,
+                            ..
+                        } => {
+                            if let Some(address) = addresses.next() {
+                                s = s.update_address(network, contract_name, label, address);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                s
+            },
+        )?;
+
+        let composed_response = ComposedTxResponse {
+            tx_hash: response.hash.to_string(),
+            msgs: staged.iter().map(|m| m.describe()).collect(),
+            code_ids,
+            contract_addresses: addresses,
+        };
+
+        fs::remove_file(compose_file(&root))?;
+        composed_response.log();
+
+        Ok(composed_response)
+    })
+}
+
+#[derive(Serialize)]
+pub struct ComposedTxResponse {
+    pub tx_hash: String,
+    pub msgs: Vec<String>,
+    pub code_ids: Vec<String>,
+    pub contract_addresses: Vec<String>,
+}
+
+impl OpResponseDisplay for ComposedTxResponse {
+    fn headline() -> &'static str {
+        "Composed transaction broadcasted successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        vec![
+            format!("    ├── tx_hash: {}", self.tx_hash),
+            format!("    ├── msgs: {}", self.msgs.join("; ")),
+            format!("    ├── code_ids: {}", self.code_ids.join(", ")),
+            format!(
+                "    └── contract_addresses: {}",
+                self.contract_addresses.join(", ")
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn replace_signer_placeholder() {
+        let value = json!({
+            "sender": "{{signer}}",
+            "routes": [{ "pool_id": "1", "token_out_denom": "uion" }],
+            "token_in": { "denom": "uosmo", "amount": "1000" },
+            "memo": "sent by {{signer}}"
+        });
+        assert_eq!(
+            replace_signer(&value, "osmo1signer"),
+            json!({
+                "sender": "osmo1signer",
+                "routes": [{ "pool_id": "1", "token_out_denom": "uion" }],
+                "token_in": { "denom": "uosmo", "amount": "1000" },
+                "memo": "sent by {{signer}}"
+            })
+        );
+    }
+
+    #[test]
+    fn staged_execute_granter_is_optional() {
+        let staged: StagedMsg = serde_json::from_value(json!({
+            "type": "execute",
+            "contract_name": "counter",
+            "label": "default",
+            "msg": { "increment": {} },
+            "funds": null
+        }))
+        .unwrap();
+        assert_eq!(
+            staged.describe(),
+            r#"execute counter:default {"increment":{}}"#
+        );
+
+        let staged = StagedMsg::Execute {
+            contract_name: "counter".to_string(),
+            label: "default".to_string(),
+            msg: json!({ "increment": {} }),
+            funds: None,
+            granter: Some("@treasury".to_string()),
+        };
+        assert_eq!(
+            staged.describe(),
+            r#"execute counter:default {"increment":{}} (as @treasury)"#
+        );
+    }
+}
@@ -0,0 +1,452 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::framework::Context;
+use crate::modules::tokenfactory::proto::{
+    MsgBurn, MsgCreateDenom, MsgMint, MsgSetDenomMetadata, MSG_BURN, MSG_CREATE_DENOM, MSG_MINT,
+    MSG_SET_DENOM_METADATA,
+};
+use crate::modules::tx::config::TxConfig;
+use crate::modules::tx::ops::raw::load_descriptors;
+use crate::support::output;
+use crate::support::wasm::checksum;
+use anyhow::{Context as _, Result};
+use cosmos_sdk_proto::cosmos::authz::v1beta1::{MsgExec, MsgGrant, MsgRevoke};
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::cosmos::crypto::multisig::LegacyAminoPubKey;
+use cosmos_sdk_proto::cosmos::crypto::secp256k1::PubKey;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::{MsgDeposit, MsgSubmitProposal, MsgVote};
+use cosmos_sdk_proto::cosmos::tx::signing::v1beta1::SignMode;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{mode_info, AuthInfo, ModeInfo, TxBody, TxRaw};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract, MsgStoreCode,
+    MsgUpdateAdmin,
+};
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+#[derive(Serialize, Debug)]
+pub struct DecodedTx {
+    pub msgs: Vec<Value>,
+    pub memo: String,
+    pub timeout_height: u64,
+    pub signer_infos: Vec<DecodedSignerInfo>,
+    pub fee: DecodedFee,
+    /// Base64 encoded signatures, in the order of signer infos
+    pub signatures: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DecodedSignerInfo {
+    pub public_key: Value,
+    pub mode_info: Value,
+    pub sequence: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DecodedFee {
+    pub amount: Vec<String>,
+    pub gas_limit: u64,
+    pub payer: String,
+    pub granter: String,
+}
+
+pub fn decode<'a, Ctx: Context<'a, TxConfig>>(
+    ctx: &Ctx,
+    tx: &str,
+    descriptor_sets: &[String],
+) -> Result<DecodedTx> {
+    let root = ctx.root()?;
+    let descriptor_sets = ctx
+        .config()?
+        .descriptor_sets
+        .iter()
+        .map(|p| root.join(p))
+        .chain(descriptor_sets.iter().map(PathBuf::from))
+        .collect::<Vec<PathBuf>>();
+    let pool = load_descriptors(&descriptor_sets)?;
+
+    let decoded = decode_tx(&read_tx_bytes(tx)?, &pool)?;
+    output::print(&decoded, || {
+        serde_json::to_string_pretty(&decoded).unwrap_or_default()
+    });
+
+    Ok(decoded)
+}
+
+/// Read raw tx bytes from file (binary, base64 or hex) or from base64 or hex string
+pub fn read_tx_bytes(input: &str) -> Result<Vec<u8>> {
+    let content = if Path::new(input).is_file() {
+        let bytes = fs::read(input).with_context(|| format!("Unable to read `{input}`"))?;
+        match String::from_utf8(bytes.clone()) {
+            Ok(s) => s,
+            Err(_) => return Ok(bytes),
+        }
+    } else {
+        input.to_string()
+    };
+
+    let content = content.trim();
+    let is_hex = content.len() % 2 == 0 && content.chars().all(|c| c.is_ascii_hexdigit());
+    if is_hex {
+        return hex::decode(content).with_context(|| "Unable to decode tx as hex");
+    }
+    base64::decode(content)
+        .with_context(|| "Unable to decode tx, expected a file, base64 or hex encoded tx bytes")
+}
+
+pub fn decode_tx(bytes: &[u8], pool: &DescriptorPool) -> Result<DecodedTx> {
+    let tx_raw = TxRaw::decode(bytes).with_context(|| "Unable to decode tx")?;
+    let body =
+        TxBody::decode(tx_raw.body_bytes.as_slice()).with_context(|| "Unable to decode tx body")?;
+    let auth_info = AuthInfo::decode(tx_raw.auth_info_bytes.as_slice())
+        .with_context(|| "Unable to decode tx auth info")?;
+    let fee = auth_info.fee.unwrap_or_default();
+
+    Ok(DecodedTx {
+        msgs: body.messages.iter().map(|m| msg_json(m, pool)).collect(),
+        memo: body.memo,
+        timeout_height: body.timeout_height,
+        signer_infos: auth_info
+            .signer_infos
+            .into_iter()
+            .map(|s| DecodedSignerInfo {
+                public_key: s
+                    .public_key
+                    .as_ref()
+                    .map(public_key_json)
+                    .unwrap_or(Value::Null),
+                mode_info: s
+                    .mode_info
+                    .as_ref()
+                    .map(mode_info_json)
+                    .unwrap_or(Value::Null),
+                sequence: s.sequence,
+            })
+            .collect(),
+        fee: DecodedFee {
+            amount: coins(&fee.amount),
+            gas_limit: fee.gas_limit,
+            payer: fee.payer,
+            granter: fee.granter,
+        },
+        signatures: tx_raw.signatures.iter().map(base64::encode).collect(),
+    })
+}
+
+fn coins<'a>(coins: impl IntoIterator<Item = &'a Coin>) -> Vec<String> {
+    coins
+        .into_iter()
+        .map(|c| format!("{}{}", c.amount, c.denom))
+        .collect()
+}
+
+/// Contract msgs are json, keep them readable when possible
+fn contract_msg(msg: &[u8]) -> Value {
+    serde_json::from_slice(msg).unwrap_or_else(|_| Value::String(base64::encode(msg)))
+}
+
+fn public_key_json(key: &prost_types::Any) -> Value {
+    match key.type_url.as_str() {
+        "/cosmos.crypto.secp256k1.PubKey" => match PubKey::decode(key.value.as_slice()) {
+            Ok(k) => json!({ "@type": key.type_url, "key": base64::encode(k.key) }),
+            Err(_) => unknown_json(&key.type_url, &key.value),
+        },
+        "/cosmos.crypto.multisig.LegacyAminoPubKey" => {
+            match LegacyAminoPubKey::decode(key.value.as_slice()) {
+                Ok(k) => json!({
+                    "@type": key.type_url,
+                    "threshold": k.threshold,
+                    "public_keys": k.public_keys.iter().map(public_key_json).collect::<Vec<Value>>(),
+                }),
+                Err(_) => unknown_json(&key.type_url, &key.value),
+            }
+        }
+        _ => unknown_json(&key.type_url, &key.value),
+    }
+}
+
+fn mode_info_json(mode_info: &ModeInfo) -> Value {
+    let sign_mode = |mode: i32| {
+        SignMode::from_i32(mode)
+            .map(|m| format!("{m:?}"))
+            .unwrap_or_else(|| mode.to_string())
+    };
+    match &mode_info.sum {
+        Some(mode_info::Sum::Single(single)) => {
+            json!({ "single": { "mode": sign_mode(single.mode) } })
+        }
+        Some(mode_info::Sum::Multi(multi)) => json!({
+            "multi": {
+                "bitarray": multi.bitarray.as_ref().map(|b| json!({
+                    "extra_bits_stored": b.extra_bits_stored,
+                    "elems": base64::encode(&b.elems),
+                })),
+                "mode_infos": multi.mode_infos.iter().map(mode_info_json).collect::<Vec<Value>>(),
+            }
+        }),
+        None => Value::Null,
+    }
+}
+
+fn unknown_json(type_url: &str, value: &[u8]) -> Value {
+    json!({ "@type": type_url, "value": base64::encode(value) })
+}
+
+/// Readable json of the msg if its type url is known to beaker or found in loaded descriptors,
+/// otherwise the base64 encoded value
+pub fn msg_json(msg: &prost_types::Any, pool: &DescriptorPool) -> Value {
+    known_msg_json(msg)
+        .or_else(|| descriptor_msg_json(msg, pool))
+        .unwrap_or_else(|| unknown_json(&msg.type_url, &msg.value))
+}
+
+fn descriptor_msg_json(msg: &prost_types::Any, pool: &DescriptorPool) -> Option<Value> {
+    let descriptor = pool.get_message_by_name(msg.type_url.trim_start_matches('/'))?;
+    let decoded = DynamicMessage::decode(descriptor, msg.value.as_slice()).ok()?;
+    let mut value = serde_json::to_value(&decoded).ok()?;
+    value
+        .as_object_mut()?
+        .insert("@type".to_string(), Value::String(msg.type_url.clone()));
+    Some(value)
+}
+
+fn known_msg_json(msg: &prost_types::Any) -> Option<Value> {
+    let value = msg.value.as_slice();
+    let mut fields = match msg.type_url.as_str() {
+        "/cosmos.bank.v1beta1.MsgSend" => MsgSend::decode(value).ok().map(|m| {
+            json!({
+                "from_address": m.from_address,
+                "to_address": m.to_address,
+                "amount": coins(&m.amount),
+            })
+        })?,
+        "/cosmwasm.wasm.v1.MsgStoreCode" => MsgStoreCode::decode(value).ok().map(|m| {
+            json!({
+                "sender": m.sender,
+                "wasm_byte_code_size": m.wasm_byte_code.len(),
+                "wasm_checksum": checksum(&m.wasm_byte_code),
+            })
+        })?,
+        "/cosmwasm.wasm.v1.MsgInstantiateContract" => {
+            MsgInstantiateContract::decode(value).ok().map(|m| {
+                json!({
+                    "sender": m.sender,
+                    "admin": m.admin,
+                    "code_id": m.code_id,
+                    "label": m.label,
+                    "msg": contract_msg(&m.msg),
+                    "funds": coins(&m.funds),
+                })
+            })?
+        }
+        "/cosmwasm.wasm.v1.MsgExecuteContract" => MsgExecuteContract::decode(value).ok().map(|m| {
+            json!({
+                "sender": m.sender,
+                "contract": m.contract,
+                "msg": contract_msg(&m.msg),
+                "funds": coins(&m.funds),
+            })
+        })?,
+        "/cosmwasm.wasm.v1.MsgMigrateContract" => MsgMigrateContract::decode(value).ok().map(|m| {
+            json!({
+                "sender": m.sender,
+                "contract": m.contract,
+                "code_id": m.code_id,
+                "msg": contract_msg(&m.msg),
+            })
+        })?,
+        "/cosmwasm.wasm.v1.MsgUpdateAdmin" => MsgUpdateAdmin::decode(value).ok().map(|m| {
+            json!({
+                "sender": m.sender,
+                "new_admin": m.new_admin,
+                "contract": m.contract,
+            })
+        })?,
+        "/cosmwasm.wasm.v1.MsgClearAdmin" => MsgClearAdmin::decode(value).ok().map(|m| {
+            json!({
+                "sender": m.sender,
+                "contract": m.contract,
+            })
+        })?,
+        "/cosmos.authz.v1beta1.MsgGrant" => MsgGrant::decode(value).ok().map(|m| {
+            let grant = m.grant.unwrap_or_default();
+            json!({
+                "granter": m.granter,
+                "grantee": m.grantee,
+                "authorization": grant.authorization.map(|a| unknown_json(&a.type_url, &a.value)),
+                "expiration": grant.expiration.map(|e| e.seconds),
+            })
+        })?,
+        "/cosmos.authz.v1beta1.MsgRevoke" => MsgRevoke::decode(value).ok().map(|m| {
+            json!({
+                "granter": m.granter,
+                "grantee": m.grantee,
+                "msg_type_url": m.msg_type_url,
+            })
+        })?,
+        "/cosmos.authz.v1beta1.MsgExec" => MsgExec::decode(value).ok().map(|m| {
+            json!({
+                "grantee": m.grantee,
+                "msgs": m
+                    .msgs
+                    .iter()
+                    .map(|m| known_msg_json(m).unwrap_or_else(|| unknown_json(&m.type_url, &m.value)))
+                    .collect::<Vec<Value>>(),
+            })
+        })?,
+        "/cosmos.gov.v1beta1.MsgSubmitProposal" => MsgSubmitProposal::decode(value).ok().map(|m| {
+            json!({
+                "proposer": m.proposer,
+                "content_type": m.content.map(|c| c.type_url),
+                "initial_deposit": coins(&m.initial_deposit),
+            })
+        })?,
+        "/cosmos.gov.v1beta1.MsgDeposit" => MsgDeposit::decode(value).ok().map(|m| {
+            json!({
+                "proposal_id": m.proposal_id,
+                "depositor": m.depositor,
+                "amount": coins(&m.amount),
+            })
+        })?,
+        "/cosmos.gov.v1beta1.MsgVote" => MsgVote::decode(value).ok().map(|m| {
+            json!({
+                "proposal_id": m.proposal_id,
+                "voter": m.voter,
+                "option": m.option,
+            })
+        })?,
+        MSG_CREATE_DENOM => MsgCreateDenom::decode(value).ok().map(|m| {
+            json!({
+                "sender": m.sender,
+                "subdenom": m.subdenom,
+            })
+        })?,
+        MSG_MINT => MsgMint::decode(value).ok().map(|m| {
+            json!({
+                "sender": m.sender,
+                "amount": coins(&m.amount),
+                "mint_to_address": m.mint_to_address,
+            })
+        })?,
+        MSG_BURN => MsgBurn::decode(value).ok().map(|m| {
+            json!({
+                "sender": m.sender,
+                "amount": coins(&m.amount),
+                "burn_from_address": m.burn_from_address,
+            })
+        })?,
+        MSG_SET_DENOM_METADATA => MsgSetDenomMetadata::decode(value).ok().map(|m| {
+            let metadata = m.metadata.unwrap_or_default();
+            json!({
+                "sender": m.sender,
+                "base": metadata.base,
+                "display": metadata.display,
+                "name": metadata.name,
+                "symbol": metadata.symbol,
+            })
+        })?,
+        _ => return None,
+    };
+
+    fields
+        .as_object_mut()?
+        .insert("@type".to_string(), Value::String(msg.type_url.clone()));
+    Some(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::{Fee, SignerInfo};
+
+    fn tx_bytes() -> Vec<u8> {
+        let execute = MsgExecuteContract {
+            sender: "osmo1sender".to_string(),
+            contract: "osmo1contract".to_string(),
+            msg: br#"{"increment":{}}"#.to_vec(),
+            funds: vec![],
+        };
+        let body = TxBody {
+            messages: vec![
+                prost_types::Any {
+                    type_url: "/cosmwasm.wasm.v1.MsgExecuteContract".to_string(),
+                    value: execute.encode_to_vec(),
+                },
+                prost_types::Any {
+                    type_url: "/example.v1.MsgUnknown".to_string(),
+                    value: vec![1, 2, 3],
+                },
+            ],
+            memo: "hello".to_string(),
+            ..Default::default()
+        };
+        let auth_info = AuthInfo {
+            signer_infos: vec![SignerInfo {
+                public_key: Some(prost_types::Any {
+                    type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+                    value: PubKey { key: vec![2; 33] }.encode_to_vec(),
+                }),
+                mode_info: Some(ModeInfo {
+                    sum: Some(mode_info::Sum::Single(mode_info::Single {
+                        mode: SignMode::Direct as i32,
+                    })),
+                }),
+                sequence: 7,
+            }],
+            fee: Some(Fee {
+                amount: vec![Coin {
+                    denom: "uosmo".to_string(),
+                    amount: "2500".to_string(),
+                }],
+                gas_limit: 200000,
+                ..Default::default()
+            }),
+        };
+        TxRaw {
+            body_bytes: body.encode_to_vec(),
+            auth_info_bytes: auth_info.encode_to_vec(),
+            signatures: vec![vec![9; 64]],
+        }
+        .encode_to_vec()
+    }
+
+    #[test]
+    fn decode_tx_with_known_and_unknown_msgs() {
+        let decoded = decode_tx(&tx_bytes(), &DescriptorPool::new()).unwrap();
+
+        assert_eq!(
+            decoded.msgs,
+            vec![
+                json!({
+                    "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
+                    "sender": "osmo1sender",
+                    "contract": "osmo1contract",
+                    "msg": { "increment": {} },
+                    "funds": [],
+                }),
+                json!({ "@type": "/example.v1.MsgUnknown", "value": base64::encode([1, 2, 3]) }),
+            ]
+        );
+        assert_eq!(decoded.memo, "hello");
+        assert_eq!(decoded.signer_infos[0].sequence, 7);
+        assert_eq!(
+            decoded.signer_infos[0].mode_info,
+            json!({ "single": { "mode": "Direct" } })
+        );
+        assert_eq!(decoded.fee.amount, vec!["2500uosmo".to_string()]);
+        assert_eq!(decoded.fee.gas_limit, 200000);
+        assert_eq!(decoded.signatures, vec![base64::encode([9; 64])]);
+    }
+
+    #[test]
+    fn read_tx_bytes_from_hex_and_base64() {
+        let bytes = tx_bytes();
+        assert_eq!(read_tx_bytes(&hex::encode(&bytes)).unwrap(), bytes);
+        assert_eq!(read_tx_bytes(&base64::encode(&bytes)).unwrap(), bytes);
+        assert!(read_tx_bytes("not a tx!").is_err());
+    }
+}
@@ -0,0 +1,328 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::framework::Context;
+use crate::modules::tx::config::TxConfig;
+use crate::modules::tx::ops::compose::composed_msgs;
+use crate::modules::tx::ops::raw::{encode_any, load_descriptors};
+use crate::support::address_book::resolve_account_address;
+use crate::support::coin::display_coin;
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::proto::MessageExt;
+use anyhow::{bail, Context as _, Result};
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract, MsgStoreCode,
+};
+use cosmrs::Any;
+use prost_reflect::DescriptorPool;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Msgs to estimate fee of
+pub enum EstimateSource<'a> {
+    /// Tx json, as printed by `<chain binary> tx ... --generate-only`, or its list of msgs
+    File(&'a Path),
+    /// Msgs staged with `beaker tx compose`
+    Composed,
+}
+
+fn str_field(msg: &Value, name: &str) -> String {
+    msg.get(name)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// `uint64` fields are strings in proto json, plain numbers are accepted too
+fn u64_field(msg: &Value, name: &str) -> Result<u64> {
+    match msg.get(name) {
+        Some(Value::String(s)) => s
+            .parse()
+            .with_context(|| format!("`{name}` must be an integer, got `{s}`")),
+        Some(Value::Number(n)) => n
+            .as_u64()
+            .with_context(|| format!("`{name}` must be an integer, got `{n}`")),
+        _ => bail!("Missing `{name}`"),
+    }
+}
+
+fn coins_field(msg: &Value, name: &str) -> Vec<Coin> {
+    msg.get(name)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|c| Coin {
+            denom: str_field(c, "denom"),
+            amount: str_field(c, "amount"),
+        })
+        .collect()
+}
+
+/// Contract msg, embedded as json by the chain binaries or as base64 in proto json
+fn contract_msg_field(msg: &Value) -> Result<Vec<u8>> {
+    match msg.get("msg") {
+        Some(Value::String(s)) => {
+            base64::decode(s).with_context(|| "`msg` must be a json object or base64 string")
+        }
+        Some(json) => Ok(serde_json::to_vec(json)?),
+        None => bail!("Missing `msg`"),
+    }
+}
+
+/// Encode msg json with `@type`, wasm and bank msgs are built in, others are encoded with `pool`
+fn encode_msg(pool: &DescriptorPool, msg: &Value) -> Result<Any> {
+    let type_url = msg
+        .get("@type")
+        .and_then(Value::as_str)
+        .with_context(|| format!("Msg has no `@type`: {msg}"))?;
+
+    let value = match type_url {
+        "/cosmwasm.wasm.v1.MsgExecuteContract" => MsgExecuteContract {
+            sender: str_field(msg, "sender"),
+            contract: str_field(msg, "contract"),
+            msg: contract_msg_field(msg)?,
+            funds: coins_field(msg, "funds"),
+        }
+        .to_bytes()?,
+        "/cosmwasm.wasm.v1.MsgInstantiateContract" => MsgInstantiateContract {
+            sender: str_field(msg, "sender"),
+            admin: str_field(msg, "admin"),
+            code_id: u64_field(msg, "code_id")?,
+            label: str_field(msg, "label"),
+            msg: contract_msg_field(msg)?,
+            funds: coins_field(msg, "funds"),
+        }
+        .to_bytes()?,
+        "/cosmwasm.wasm.v1.MsgMigrateContract" => MsgMigrateContract {
+            sender: str_field(msg, "sender"),
+            contract: str_field(msg, "contract"),
+            code_id: u64_field(msg, "code_id")?,
+            msg: contract_msg_field(msg)?,
+        }
+        .to_bytes()?,
+        "/cosmwasm.wasm.v1.MsgStoreCode" => MsgStoreCode {
+            sender: str_field(msg, "sender"),
+            wasm_byte_code: base64::decode(str_field(msg, "wasm_byte_code"))
+                .with_context(|| "`wasm_byte_code` must be base64 encoded")?,
+            instantiate_permission: None,
+        }
+        .to_bytes()?,
+        "/cosmos.bank.v1beta1.MsgSend" => MsgSend {
+            from_address: str_field(msg, "from_address"),
+            to_address: str_field(msg, "to_address"),
+            amount: coins_field(msg, "amount"),
+        }
+        .to_bytes()?,
+        _ => {
+            let mut value = msg.clone();
+            if let Value::Object(fields) = &mut value {
+                fields.remove("@type");
+            }
+            return encode_any(pool, type_url, &value.to_string());
+        }
+    };
+
+    Ok(Any {
+        type_url: type_url.to_string(),
+        value,
+    })
+}
+
+/// Msgs and memo of tx json: a full tx with `body`, `{"messages": [...]}` or a list of msgs
+fn parse_tx_json(pool: &DescriptorPool, tx: &Value) -> Result<(Vec<Any>, String)> {
+    let body = tx.get("body").unwrap_or(tx);
+    let (msgs, memo) = match body {
+        Value::Array(msgs) => (msgs, String::new()),
+        body => (
+            body.get("messages")
+                .and_then(Value::as_array)
+                .with_context(|| "Tx json has no `messages`")?,
+            str_field(body, "memo"),
+        ),
+    };
+    if msgs.is_empty() {
+        bail!("Tx json has no msgs");
+    }
+
+    let msgs = msgs
+        .iter()
+        .map(|m| encode_msg(pool, m))
+        .collect::<Result<Vec<Any>>>()?;
+    Ok((msgs, memo))
+}
+
+/// Simulate msgs sent by `from` and price the gas, nothing is signed so no signer key is needed
+pub fn estimate_fee<'a, Ctx: Context<'a, TxConfig>>(
+    ctx: &Ctx,
+    source: EstimateSource,
+    from: &str,
+    descriptor_sets: &[String],
+    network: &str,
+    gas: &Gas,
+) -> Result<EstimateFeeResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+    let signer = resolve_account_address(&global_config, &network_info, from)?;
+
+    let (msgs, memo) = match source {
+        EstimateSource::File(path) => {
+            let root = ctx.root()?;
+            let descriptor_sets = ctx
+                .config()?
+                .descriptor_sets
+                .iter()
+                .map(|p| root.join(p))
+                .chain(descriptor_sets.iter().map(PathBuf::from))
+                .collect::<Vec<PathBuf>>();
+            let pool = load_descriptors(&descriptor_sets)?;
+            let tx: Value = serde_json::from_str(
+                &fs::read_to_string(path)
+                    .with_context(|| format!("Unable to read `{}`", path.display()))?,
+            )
+            .with_context(|| format!("`{}` is not valid json", path.display()))?;
+            parse_tx_json(&pool, &tx)?
+        }
+        EstimateSource::Composed => (
+            composed_msgs(ctx, network, &network_info, &signer)?,
+            String::new(),
+        ),
+    };
+    let type_urls = msgs.iter().map(|m| m.type_url.clone()).collect();
+
+    let client = ctx.client(network_info);
+    let response = block(async {
+        let (gas_price, fee_denom) = match gas {
+            Gas::Specified(fee) => (
+                None,
+                fee.amount
+                    .first()
+                    .map(|c| c.denom.to_string())
+                    .with_context(|| "`--gas` has no amount")?,
+            ),
+            Gas::Auto {
+                gas_price,
+                fee_denom,
+                ..
+            } => {
+                let price = client
+                    .fee_gas_price(gas_price, fee_denom.as_deref())
+                    .await?;
+                let denom = price.denom.to_string();
+                (Some(price), denom)
+            }
+        };
+
+        let simulation = client
+            .simulate_unsigned(msgs, &memo, &0, signer.as_ref(), &fee_denom)
+            .await?;
+        let gas_used = simulation.gas_info.unwrap_or_default().gas_used;
+
+        let (gas_limit, amount) = match (gas, &gas_price) {
+            (Gas::Specified(fee), _) => (
+                fee.gas_limit.into(),
+                fee.amount
+                    .first()
+                    .map(|c| c.amount.to_string())
+                    .unwrap_or_default(),
+            ),
+            (Gas::Auto { gas_adjustment, .. }, Some(price)) => {
+                let gas_limit = ((gas_used as f64) * gas_adjustment).ceil();
+                (
+                    gas_limit as u64,
+                    ((gas_limit * price.amount).ceil() as u64).to_string(),
+                )
+            }
+            (Gas::Auto { .. }, None) => unreachable!("gas price is resolved for auto gas"),
+        };
+
+        anyhow::Ok(EstimateFeeResponse {
+            network: network.to_string(),
+            signer: signer.to_string(),
+            msgs: type_urls,
+            gas_used,
+            gas_limit,
+            gas_price: gas_price
+                .map(|p| format!("{}{}", p.amount, p.denom))
+                .unwrap_or_default(),
+            fee_display: display_coin(&amount, &fee_denom),
+            fee: format!("{amount}{fee_denom}"),
+        })
+    })?;
+
+    response.log();
+    Ok(response)
+}
+
+#[derive(Serialize, Debug)]
+pub struct EstimateFeeResponse {
+    pub network: String,
+    pub signer: String,
+    /// Type urls of the simulated msgs
+    pub msgs: Vec<String>,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    /// Gas price the fee is computed with, empty if fee is given by `--gas`
+    pub gas_price: String,
+    /// Fee in base denom, eg. `1500uosmo`
+    pub fee: String,
+    /// Fee in display denom, eg. `0.0015 OSMO`
+    pub fee_display: String,
+}
+
+impl OpResponseDisplay for EstimateFeeResponse {
+    fn headline() -> &'static str {
+        "Fee estimated, nothing is signed or broadcasted"
+    }
+    fn attrs(&self) -> Vec<String> {
+        vec![
+            format!("    ├── network: {}", self.network),
+            format!("    ├── signer: {}", self.signer),
+            format!("    ├── msgs: {}", self.msgs.join(", ")),
+            format!("    ├── gas_used: {}", self.gas_used),
+            format!("    ├── gas_limit: {}", self.gas_limit),
+            format!("    ├── gas_price: {}", self.gas_price),
+            format!("    └── fee: {} ({})", self.fee, self.fee_display),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+    use serde_json::json;
+
+    #[test]
+    fn parse_generated_tx() {
+        let tx = json!({
+            "body": {
+                "messages": [{
+                    "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
+                    "sender": "osmo1sender",
+                    "contract": "osmo1contract",
+                    "msg": { "increment": {} },
+                    "funds": [{ "denom": "uosmo", "amount": "10" }]
+                }],
+                "memo": "budget"
+            },
+            "auth_info": {},
+            "signatures": []
+        });
+        let (msgs, memo) = parse_tx_json(&DescriptorPool::new(), &tx).unwrap();
+        assert_eq!(memo, "budget");
+        let msg = MsgExecuteContract::decode(msgs[0].value.as_slice()).unwrap();
+        assert_eq!(msg.msg, br#"{"increment":{}}"#.to_vec());
+        assert_eq!(msg.funds[0].amount, "10");
+
+        let unknown = json!([{ "@type": "/osmosis.gamm.v1beta1.MsgSwapExactAmountIn" }]);
+        assert!(parse_tx_json(&DescriptorPool::new(), &unknown).is_err());
+    }
+}
@@ -0,0 +1,13 @@
+pub mod compose;
+pub mod decode;
+pub mod estimate_fee;
+pub mod raw;
+pub mod show;
+
+pub use compose::{
+    broadcast_composed, clear_composed, show_composed, stage, stage_stargate, StagedMsg,
+};
+pub use decode::decode;
+pub use estimate_fee::{estimate_fee, EstimateSource};
+pub use raw::broadcast_raw;
+pub use show::show;
@@ -0,0 +1,209 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::framework::Context;
+use crate::modules::tx::config::TxConfig;
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
+use crate::support::ops_response::OpResponseDisplay;
+use anyhow::{bail, Context as _, Result};
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::Any;
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage};
+use serde::Serialize;
+
+/// Build descriptor pool from binary `FileDescriptorSet` files,
+/// eg. produced by `buf build -o descriptors.bin` or `protoc --include_imports --descriptor_set_out`
+pub fn load_descriptors<P: AsRef<Path>>(paths: &[P]) -> Result<DescriptorPool> {
+    let mut pool = DescriptorPool::new();
+    for path in paths {
+        let path = path.as_ref();
+        let bytes = fs::read(path)
+            .with_context(|| format!("Unable to read descriptor set `{}`", path.display()))?;
+        let file_descriptor_set = prost_types::FileDescriptorSet::decode(bytes.as_slice())
+            .with_context(|| format!("`{}` is not a valid descriptor set", path.display()))?;
+        pool.add_file_descriptor_set(file_descriptor_set)
+            .with_context(|| format!("Unable to load descriptor set `{}`", path.display()))?;
+    }
+    Ok(pool)
+}
+
+/// Encode proto json value of message with the type url into `Any`
+pub fn encode_any(pool: &DescriptorPool, type_url: &str, json: &str) -> Result<Any> {
+    let name = type_url.trim_start_matches('/');
+    let descriptor = pool.get_message_by_name(name).with_context(|| {
+        format!("Unable to find `{name}` in loaded descriptors, add its descriptor set with `--descriptor-set`")
+    })?;
+
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let msg = DynamicMessage::deserialize(descriptor, &mut deserializer)
+        .with_context(|| format!("Unable to encode `{type_url}` from json"))?;
+    deserializer.end()?;
+
+    Ok(Any {
+        type_url: format!("/{name}"),
+        value: msg.encode_to_vec(),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn broadcast_raw<'a, Ctx: Context<'a, TxConfig>>(
+    ctx: &Ctx,
+    type_urls: &[String],
+    value_files: &[String],
+    descriptor_sets: &[String],
+    network: &str,
+    memo: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+    signing_key: SigningKey,
+) -> Result<RawTxResponse> {
+    if type_urls.len() != value_files.len() {
+        bail!(
+            "Each `--type-url` must be paired with a `--value-file`, got {} type urls and {} value files",
+            type_urls.len(),
+            value_files.len()
+        );
+    }
+
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let root = ctx.root()?;
+    let descriptor_sets = ctx
+        .config()?
+        .descriptor_sets
+        .iter()
+        .map(|p| root.join(p))
+        .chain(descriptor_sets.iter().map(PathBuf::from))
+        .collect::<Vec<PathBuf>>();
+    let pool = load_descriptors(&descriptor_sets)?;
+
+    let msgs = type_urls
+        .iter()
+        .zip(value_files)
+        .map(|(type_url, value_file)| {
+            let json = fs::read_to_string(value_file)
+                .with_context(|| format!("Unable to read `{value_file}`"))?;
+            encode_any(&pool, type_url, &json)
+        })
+        .collect::<Result<Vec<Any>>>()?;
+
+    let client = ctx
+        .client(network_info)
+        .to_signing_client(signing_key, global_config.account_prefix())
+        .with_gas_ledger(GasLedger::new(root, network, None));
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(msgs, gas, memo, timeout_height)
+            .await?;
+
+        let raw_response = RawTxResponse {
+            tx_hash: response.hash.to_string(),
+            msgs: type_urls.to_vec(),
+        };
+        raw_response.log();
+
+        Ok(raw_response)
+    })
+}
+
+#[derive(Serialize)]
+pub struct RawTxResponse {
+    pub tx_hash: String,
+    pub msgs: Vec<String>,
+}
+
+impl OpResponseDisplay for RawTxResponse {
+    fn headline() -> &'static str {
+        "Transaction broadcasted successfully!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        vec![
+            format!("    ├── tx_hash: {}", self.tx_hash),
+            format!("    └── msgs: {}", self.msgs.join("; ")),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::field_descriptor_proto::{Label, Type};
+    use prost_types::{
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+    };
+
+    fn field(name: &str, number: i32, r#type: Type) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(r#type as i32),
+            json_name: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn pool() -> DescriptorPool {
+        let mut pool = DescriptorPool::new();
+        pool.add_file_descriptor_set(FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("example/v1/tx.proto".to_string()),
+                package: Some("example.v1".to_string()),
+                message_type: vec![DescriptorProto {
+                    name: Some("MsgDoSomething".to_string()),
+                    field: vec![
+                        field("sender", 1, Type::String),
+                        field("amount", 2, Type::Uint64),
+                    ],
+                    ..Default::default()
+                }],
+                syntax: Some("proto3".to_string()),
+                ..Default::default()
+            }],
+        })
+        .unwrap();
+        pool
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct MsgDoSomething {
+        #[prost(string, tag = "1")]
+        sender: String,
+        #[prost(uint64, tag = "2")]
+        amount: u64,
+    }
+
+    #[test]
+    fn encode_any_from_json() {
+        let any = encode_any(
+            &pool(),
+            "/example.v1.MsgDoSomething",
+            r#"{"sender": "osmo1abc", "amount": "42"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(any.type_url, "/example.v1.MsgDoSomething");
+        assert_eq!(
+            MsgDoSomething::decode(any.value.as_slice()).unwrap(),
+            MsgDoSomething {
+                sender: "osmo1abc".to_string(),
+                amount: 42
+            }
+        );
+    }
+
+    #[test]
+    fn reject_unknown_type_url_and_field() {
+        assert!(encode_any(&pool(), "/example.v1.MsgUnknown", "{}").is_err());
+        assert!(encode_any(&pool(), "/example.v1.MsgDoSomething", r#"{"nope": 1}"#).is_err());
+    }
+}
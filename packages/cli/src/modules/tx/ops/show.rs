@@ -0,0 +1,137 @@
+use std::path::PathBuf;
+
+use crate::framework::Context;
+use crate::modules::tx::config::TxConfig;
+use crate::modules::tx::ops::decode::{decode_tx, DecodedTx};
+use crate::modules::tx::ops::raw::load_descriptors;
+use crate::support::future::block;
+use crate::support::output;
+use anyhow::{Context as _, Result};
+use console::style;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct TxEvent {
+    pub r#type: String,
+    pub attributes: Vec<(String, String)>,
+}
+
+#[derive(Serialize)]
+pub struct ShowTxResponse {
+    pub tx_hash: String,
+    pub height: u64,
+    pub success: bool,
+    pub code: u32,
+    pub codespace: String,
+    pub log: String,
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+    pub tx: DecodedTx,
+    pub events: Vec<TxEvent>,
+}
+
+pub fn show<'a, Ctx: Context<'a, TxConfig>>(
+    ctx: &Ctx,
+    hash: &str,
+    network: &str,
+    descriptor_sets: &[String],
+) -> Result<ShowTxResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let root = ctx.root()?;
+    let descriptor_sets = ctx
+        .config()?
+        .descriptor_sets
+        .iter()
+        .map(|p| root.join(p))
+        .chain(descriptor_sets.iter().map(PathBuf::from))
+        .collect::<Vec<PathBuf>>();
+    let pool = load_descriptors(&descriptor_sets)?;
+
+    let res = block(ctx.client(network_info).tx(hash))?;
+    let result = res.tx_result;
+
+    let response = ShowTxResponse {
+        tx_hash: res.hash.to_string(),
+        height: res.height.value(),
+        success: result.code.is_ok(),
+        code: result.code.value(),
+        codespace: result.codespace.to_string(),
+        log: result.log.to_string(),
+        gas_wanted: result.gas_wanted.value(),
+        gas_used: result.gas_used.value(),
+        tx: decode_tx(res.tx.as_bytes(), &pool)?,
+        events: result
+            .events
+            .iter()
+            .map(|e| TxEvent {
+                r#type: e.type_str.clone(),
+                attributes: e
+                    .attributes
+                    .iter()
+                    .map(|a| (a.key.to_string(), a.value.to_string()))
+                    .collect(),
+            })
+            .collect(),
+    };
+
+    output::print(&response, || display_tx(&response));
+    Ok(response)
+}
+
+fn display_tx(res: &ShowTxResponse) -> String {
+    let status = if res.success {
+        style("success").green().bold().to_string()
+    } else {
+        style(format!("failed (code {} {})", res.code, res.codespace))
+            .red()
+            .bold()
+            .to_string()
+    };
+
+    let mut lines = vec![
+        format!("Transaction {}", style(&res.tx_hash).bold()),
+        format!("    ├── status: {status}"),
+        format!("    ├── height: {}", res.height),
+        format!("    ├── gas: {} / {}", res.gas_used, res.gas_wanted),
+        format!("    ├── fee: {}", res.tx.fee.amount.join(", ")),
+        format!(
+            "    ├── memo: {}",
+            if res.tx.memo.is_empty() {
+                "–"
+            } else {
+                &res.tx.memo
+            }
+        ),
+    ];
+    if !res.success {
+        lines.push(format!("    ├── log: {}", res.log));
+    }
+
+    lines.push("    ├── msgs".to_string());
+    let last = res.tx.msgs.len().saturating_sub(1);
+    lines.extend(res.tx.msgs.iter().enumerate().map(|(i, m)| {
+        let branch = if i == last { "└──" } else { "├──" };
+        format!("    │     {branch} {m}")
+    }));
+
+    lines.push("    └── events".to_string());
+    let last = res.events.len().saturating_sub(1);
+    lines.extend(res.events.iter().enumerate().map(|(i, e)| {
+        let attributes = e
+            .attributes
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let branch = if i == last { "└──" } else { "├──" };
+        format!("          {branch} {}: {attributes}", e.r#type)
+    }));
+
+    lines.join("\n")
+}
@@ -1,3 +1,6 @@
+use std::{fs, io::Read, path::PathBuf};
+
+use anyhow::{Context, Result};
 use clap::Parser;
 
 use crate::support::{gas::GasArgs, signer::SignerArgs};
@@ -18,3 +21,56 @@ pub struct BaseTxArgs {
     #[clap(short, long, default_value = "0")]
     pub timeout_height: u32,
 }
+
+#[derive(Debug, Parser, Clone)]
+pub struct MsgArgs {
+    /// Raw json string to use as msg
+    #[clap(short, long)]
+    pub raw: Option<String>,
+
+    /// File containing msg as json, json5 or yaml, use `-` to read from stdin
+    #[clap(long, conflicts_with = "raw")]
+    pub msg_file: Option<PathBuf>,
+}
+
+impl MsgArgs {
+    /// Msg content from `--raw` or `--msg-file`, `None` if neither is specified
+    pub fn raw_msg(&self) -> Result<Option<String>> {
+        match (&self.raw, &self.msg_file) {
+            (Some(raw), _) => Ok(Some(raw.clone())),
+            (None, Some(path)) if path.as_os_str() == "-" => {
+                let mut msg = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut msg)
+                    .with_context(|| "Unable to read msg from stdin")?;
+                Ok(Some(msg))
+            }
+            (None, Some(path)) => fs::read_to_string(path)
+                .with_context(|| format!("Unable to read msg file `{}`", path.to_string_lossy()))
+                .map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+#[derive(Debug, Parser, Clone)]
+pub struct PageArgs {
+    /// Maximum number of entries to fetch, following pagination until reached
+    #[clap(long)]
+    pub limit: Option<u64>,
+
+    /// Fetch every page, this is the default unless `--limit` is given
+    #[clap(long, conflicts_with = "limit")]
+    pub all: bool,
+}
+
+impl PageArgs {
+    /// Limit to fetch up to, `None` to fetch every page
+    pub fn limit(&self) -> Option<u64> {
+        if self.all {
+            None
+        } else {
+            self.limit
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use std::fs;
+use std::path::{Component, Path};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use super::config::ArtifactRegistry;
+use crate::support::command::run_command;
+use crate::support::wasm::checksum;
+
+pub const CHECKSUMS_FILE: &str = "checksums.txt";
+pub const METADATA_FILE: &str = "metadata.json";
+/// Checksums of pushed artifacts, kept apart from the optimizer's `checksums.txt`
+pub const MANIFEST_FILE: &str = "artifacts.sha256";
+
+/// Tags become object key prefixes and local directory names, so they are restricted to the OCI tag charset
+pub fn validate_tag(tag: &str) -> Result<()> {
+    let valid = !tag.is_empty()
+        && tag.len() <= 128
+        && !tag.starts_with(['.', '-'])
+        && tag
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+    if !valid {
+        bail!("Invalid artifact tag `{tag}`, only letters, digits, `.`, `_` and `-` are allowed and it must not start with `.` or `-`");
+    }
+    Ok(())
+}
+
+/// Only a plain file name can be read from or written into artifact directories,
+/// paths like `../x` or `/x` in a pulled manifest are rejected
+fn plain_file_name(file: &str) -> Result<&str> {
+    let mut components = Path::new(file).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Ok(file),
+        _ => bail!("Invalid artifact file name `{file}`, expected a plain file name"),
+    }
+}
+
+fn bucket_url(scheme: &str, bucket: &str, prefix: &Option<String>, tag: &str) -> String {
+    match prefix.as_deref().map(|p| p.trim_matches('/')) {
+        Some(prefix) if !prefix.is_empty() => format!("{scheme}://{bucket}/{prefix}/{tag}/"),
+        _ => format!("{scheme}://{bucket}/{tag}/"),
+    }
+}
+
+/// Upload `files` in `dir` to the registry under `tag`
+pub fn push(registry: &ArtifactRegistry, dir: &Path, files: &[String], tag: &str) -> Result<()> {
+    match registry {
+        ArtifactRegistry::S3 { bucket, prefix } => {
+            for file in files {
+                run_command(Command::new("aws").args(["s3", "cp"]).args([
+                    &dir.join(file).to_string_lossy().to_string(),
+                    &format!("{}{file}", bucket_url("s3", bucket, prefix, tag)),
+                ]))?;
+            }
+            Ok(())
+        }
+        ArtifactRegistry::Gcs { bucket, prefix } => run_command(
+            Command::new("gsutil")
+                .args(["-m", "cp"])
+                .args(files.iter().map(|f| dir.join(f)))
+                .arg(bucket_url("gs", bucket, prefix, tag)),
+        ),
+        ArtifactRegistry::Oci { repository } => run_command(
+            Command::new("oras")
+                .current_dir(dir)
+                .args(["push", &format!("{repository}:{tag}")])
+                .args(files),
+        ),
+    }
+}
+
+/// Download all files under `tag` from the registry into `dir`
+pub fn pull(registry: &ArtifactRegistry, tag: &str, dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    let dir_str = dir.to_string_lossy().to_string();
+    match registry {
+        ArtifactRegistry::S3 { bucket, prefix } => run_command(
+            Command::new("aws")
+                .args(["s3", "cp", "--recursive"])
+                .args([&bucket_url("s3", bucket, prefix, tag), &dir_str]),
+        ),
+        ArtifactRegistry::Gcs { bucket, prefix } => {
+            run_command(Command::new("gsutil").args(["-m", "cp"]).args([
+                &format!("{}*", bucket_url("gs", bucket, prefix, tag)),
+                &dir_str,
+            ]))
+        }
+        ArtifactRegistry::Oci { repository } => run_command(
+            Command::new("oras")
+                .args(["pull", &format!("{repository}:{tag}")])
+                .args(["--output", &dir_str]),
+        ),
+    }
+}
+
+fn write_sums(dir: &Path, files: &[String], sums_file: &str) -> Result<()> {
+    let mut lines = vec![];
+    for file in files {
+        lines.push(format!("{}  {file}", checksum(&fs::read(dir.join(file))?)));
+    }
+    fs::write(dir.join(sums_file), lines.join("\n") + "\n")
+        .with_context(|| format!("Unable to write `{sums_file}`"))
+}
+
+/// `sha256sum` compatible checksums of `files` in `dir`, same format as produced by rust-optimizer
+pub fn write_checksums(dir: &Path, files: &[String]) -> Result<()> {
+    write_sums(dir, files, CHECKSUMS_FILE)
+}
+
+/// Checksums of `files` in `dir` to be pushed along with them, in the same format as `checksums.txt`
+pub fn write_manifest(dir: &Path, files: &[String]) -> Result<()> {
+    write_sums(dir, files, MANIFEST_FILE)
+}
+
+/// Verify files in `dir` against its manifest, returns verified file names
+pub fn verify_checksums(dir: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(dir.join(MANIFEST_FILE))
+        .with_context(|| format!("`{MANIFEST_FILE}` not found in pulled artifacts"))?;
+
+    let mut verified = vec![];
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let (expected, file) = line
+            .split_once(char::is_whitespace)
+            .with_context(|| format!("Invalid checksum line `{line}`"))?;
+        let file = plain_file_name(file.trim().trim_start_matches('*'))?;
+        let actual =
+            checksum(&fs::read(dir.join(file)).with_context(|| format!("`{file}` not found"))?);
+        if actual != expected {
+            bail!("Checksum mismatch for `{file}`: expected {expected}, got {actual}");
+        }
+        verified.push(file.to_string());
+    }
+    Ok(verified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn bucket_url_with_and_without_prefix() {
+        assert_eq!(
+            bucket_url("s3", "bucket", &Some("/counter/".to_string()), "v1"),
+            "s3://bucket/counter/v1/"
+        );
+        assert_eq!(bucket_url("gs", "bucket", &None, "v1"), "gs://bucket/v1/");
+    }
+
+    #[test]
+    fn write_and_verify_checksums() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.child("counter.wasm")
+            .write_binary(&[0, 97, 115, 109])
+            .unwrap();
+        let files = vec!["counter.wasm".to_string()];
+
+        write_manifest(temp.path(), &files).unwrap();
+        assert_eq!(verify_checksums(temp.path()).unwrap(), files);
+
+        temp.child("counter.wasm").write_binary(&[1]).unwrap();
+        assert!(verify_checksums(temp.path()).is_err());
+    }
+
+    #[test]
+    fn reject_paths_in_manifest() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        for file in ["../counter.wasm", "/etc/passwd", "wasm/counter.wasm", "."] {
+            temp.child(MANIFEST_FILE)
+                .write_str(&format!("{}  {file}\n", checksum(&[])))
+                .unwrap();
+            assert!(verify_checksums(temp.path()).is_err(), "{file}");
+        }
+    }
+
+    #[test]
+    fn validate_tag_charset() {
+        for tag in ["v1.0.0", "3f2a9c1", "release_2-rc.1"] {
+            assert!(validate_tag(tag).is_ok(), "{tag}");
+        }
+        for tag in ["", "../v1", "v1/..", ".hidden", "-rf", "v 1"] {
+            assert!(validate_tag(tag).is_err(), "{tag}");
+        }
+    }
+}
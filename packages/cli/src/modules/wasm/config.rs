@@ -1,17 +1,142 @@
+use config::Map;
 use data_doc_derive::GetDataDocs;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::support::state::Action;
+
 #[derive(Serialize, Deserialize, GetDataDocs)]
 pub struct WasmConfig {
     /// Directory for storing contracts
     pub contract_dir: String,
 
-    /// Reference to contract template repository
+    /// Reference to contract template: git repo url, local path,
+    /// or name of a favorite defined in the user's cargo-generate config
     pub template_repo: String,
 
+    /// Extra flags passed through to cargo-generate when generating new contract,
+    /// eg. `["--define", "minimal=true"]`
+    #[serde(default)]
+    pub template_args: Vec<String>,
+
+    /// Reference to contract template used by `wasm new --ibc`, with ibc channel and packet entry points
+    #[serde(default = "default_ibc_template_repo")]
+    pub ibc_template_repo: String,
+
+    /// Subfolder of `ibc_template_repo` to generate from, root of the repo if not specified
+    #[serde(default = "default_ibc_template_subfolder")]
+    pub ibc_template_subfolder: Option<String>,
+
     /// Version of rust-optimizer
     pub optimizer_version: String,
+
+    /// Cargo target directory shared by all contract builds, relative to workspace root if not absolute,
+    /// eg. `"../target"` for sharing compiled dependencies with sibling workspaces.
+    /// Optimizer builds use a docker volume named after this directory, so they share their cache the same way.
+    #[serde(default)]
+    pub target_dir: Option<String>,
+
+    /// Feature combinations built by `wasm build --matrix`, per contract, eg.
+    /// `counter = [{ suffix = "juno", features = ["juno"] }, { suffix = "lib", features = ["library"] }]`.
+    /// Each variant produces `<contract>-<suffix>.wasm`.
+    #[serde(default)]
+    pub build_matrix: Map<String, Vec<BuildVariant>>,
+
+    /// Admin of instantiated contract when `--admin` is not specified.
+    /// Use "signer" for setting tx signer as admin, bech32 address or address book entry (eg. "@treasury") for custom admin,
+    /// or "none" for contract without admin, which can never be migrated.
+    #[serde(default)]
+    pub default_admin: Option<String>,
+
+    /// Remote store for publishing optimized artifacts, eg.
+    /// `{ backend = "s3", bucket = "my-artifacts", prefix = "counter" }`
+    #[serde(default)]
+    pub artifact_registry: Option<ArtifactRegistry>,
+
+    /// Webhooks notified after store, instantiate or migrate succeeds or fails, eg.
+    /// `[{ url = "https://hooks.slack.com/services/...", format = "slack", networks = ["testnet", "mainnet"] }]`
+    #[serde(default)]
+    pub notifications: Vec<Notification>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, GetDataDocs)]
+pub struct Notification {
+    /// Url the payload is POSTed to with the installed `curl`
+    pub url: String,
+
+    /// Shape of the posted payload
+    #[serde(default)]
+    pub format: NotificationFormat,
+
+    /// Actions to notify on, any of `store`, `instantiate` and `migrate`, all of them if empty
+    #[serde(default)]
+    pub on: Vec<Action>,
+
+    /// Networks to notify on, all networks if empty
+    #[serde(default)]
+    pub networks: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, GetDataDocs)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationFormat {
+    /// Deployment event as json object with `action`, `status`, `network`, `contract`, `label`,
+    /// `code_id`, `address`, `tx_hash`, `actor` and `error`
+    Json,
+
+    /// Message for Slack incoming webhooks, also accepted by Mattermost and Discord's `/slack` webhooks
+    Slack,
+}
+
+impl Default for NotificationFormat {
+    fn default() -> Self {
+        NotificationFormat::Json
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, GetDataDocs)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum ArtifactRegistry {
+    /// S3 bucket, accessed through the installed `aws` cli and its configured credentials
+    S3 {
+        bucket: String,
+        prefix: Option<String>,
+    },
+
+    /// GCS bucket, accessed through the installed `gsutil` cli and its configured credentials
+    Gcs {
+        bucket: String,
+        prefix: Option<String>,
+    },
+
+    /// OCI registry repository (eg. `ghcr.io/org/contracts`), accessed through the installed `oras` cli
+    Oci { repository: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, GetDataDocs)]
+pub struct BuildVariant {
+    /// Suffix of the produced artifact name
+    pub suffix: String,
+
+    /// Cargo features enabled for this variant
+    #[serde(default)]
+    pub features: Vec<String>,
+
+    /// Whether default features of the contract are enabled, `true` if not specified
+    #[serde(default = "default_true")]
+    pub default_features: bool,
+}
+
+fn default_ibc_template_repo() -> String {
+    "https://github.com/osmosis-labs/beaker.git".to_string()
+}
+
+fn default_ibc_template_subfolder() -> Option<String> {
+    Some("templates/ibc-contract".to_string())
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl Default for WasmConfig {
@@ -19,7 +144,15 @@ impl Default for WasmConfig {
         Self {
             contract_dir: "contracts".to_string(),
             template_repo: "https://github.com/osmosis-labs/cw-minimal-template".to_string(),
+            template_args: vec![],
+            ibc_template_repo: default_ibc_template_repo(),
+            ibc_template_subfolder: default_ibc_template_subfolder(),
             optimizer_version: "0.12.8".to_string(),
+            target_dir: None,
+            build_matrix: Map::new(),
+            default_admin: None,
+            artifact_registry: None,
+            notifications: vec![],
         }
     }
 }
@@ -1,19 +1,26 @@
 use std::env;
 use std::fmt::Formatter;
+use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
 use std::str::FromStr;
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context as _, Result};
 use clap::Subcommand;
 use console::style;
 use derive_new::new;
 
 use crate::framework::{Context, Module};
+use crate::modules::state::ops::export::address_map;
 use crate::support::command::run_command;
 use crate::support::gas::Gas;
+use crate::support::git;
+use crate::support::permission::admin_or_default;
+use crate::support::prompt;
+use crate::support::storage::KeySpec;
 
-use super::{args::BaseTxArgs, config::WasmConfig, proposal::entrypoint::ProposalCmd};
+use super::args::{BaseTxArgs, MsgArgs, PageArgs};
+use super::{config::WasmConfig, proposal::entrypoint::ProposalCmd};
 use super::{ops, proposal};
 
 #[derive(clap::ArgEnum, Clone, Debug)]
@@ -58,9 +65,16 @@ pub enum WasmCmd {
         /// Path to store generated contract
         #[clap(short, long)]
         target_dir: Option<PathBuf>,
-        /// Template's version, using main branch if not specified
+        /// Template's version, using default branch of the template if not specified
         #[clap(short, long)]
         version: Option<String>,
+        /// Generate from `ibc_template_repo`, which has ibc channel and packet entry points,
+        /// a stub packet protocol and tests walking through the channel lifecycle
+        #[clap(long)]
+        ibc: bool,
+        /// Extra flags passed through to cargo-generate, eg. `-- --define minimal=true`
+        #[clap(last = true)]
+        generator_args: Vec<String>,
     },
     /// Build .wasm for storing contract code on the blockchain
     Build {
@@ -70,6 +84,9 @@ pub enum WasmCmd {
         /// Option for m1 user for wasm optimization, FOR TESTING ONLY, PRODUCTION BUILD SHOULD USE INTEL BUILD
         #[clap(short, long)]
         aarch64: bool,
+        /// Build every feature combination configured in `build_matrix` into suffixed artifacts
+        #[clap(long)]
+        matrix: bool,
     },
     /// Store .wasm on chain for later initialization
     StoreCode {
@@ -84,9 +101,32 @@ pub enum WasmCmd {
         #[clap(long)]
         permit_instantiate_only: Option<String>,
 
+        /// Refuse to proceed unless the working tree is clean and checked out at this git tag
+        #[clap(long)]
+        verify_tag: Option<String>,
+
+        /// Download wasm from url (eg. GitHub release asset) instead of using local build
+        #[clap(long, requires = "expect-checksum")]
+        from_url: Option<String>,
+
+        /// Expected sha256 checksum of the wasm downloaded with `--from-url`
+        #[clap(long)]
+        expect_checksum: Option<String>,
+
         #[clap(flatten)]
         base_tx_args: BaseTxArgs,
     },
+    /// Publish optimized artifacts with checksums and build metadata to the configured `artifact_registry`
+    PushArtifacts {
+        /// Tag to publish artifacts under, current git commit by default
+        #[clap(long)]
+        tag: Option<String>,
+    },
+    /// Download and verify published artifacts from the configured `artifact_registry` for `store-code`
+    PullArtifacts {
+        /// Tag of the published artifacts
+        tag: String,
+    },
     TsGen {
         /// Name of the contract to store
         contract_name: String,
@@ -102,6 +142,14 @@ pub enum WasmCmd {
         /// Code output directory
         #[clap(long, default_value = "yarn")]
         node_package_manager: NodePackageManager,
+
+        /// Also generate React Query hooks (`use<Contract>Query`, `use<Contract>Execute`) bound to deployed addresses
+        #[clap(long)]
+        react_query: bool,
+
+        /// Network of the deployed addresses bundled into the sdk
+        #[clap(short, long, default_value = "local")]
+        network: String,
     },
     /// Update admin that can migrate contract
     UpdateAdmin {
@@ -140,16 +188,24 @@ pub enum WasmCmd {
         #[clap(short, long, default_value = "default")]
         label: String,
 
-        /// Raw json string to use as instantiate msg
-        #[clap(short, long)]
-        raw: Option<String>,
+        #[clap(flatten)]
+        msg_args: MsgArgs,
 
         /// Specifying admin required for contract migration.
         /// Use "signer" for setting tx signer as admin.
         /// Use bech32 address (eg. "osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks") for custom admin.
+        /// Falls back to `default_admin` in config if not specified.
         #[clap(long)]
         admin: Option<String>,
 
+        /// Instantiate contract without admin, contract will not be able to be migrated
+        #[clap(long, conflicts_with = "admin")]
+        no_admin: bool,
+
+        /// Hex encoded salt to instantiate with instantiate2, the address is then predictable with `predict-address`
+        #[clap(long)]
+        salt: Option<String>,
+
         /// Funds to send to instantiated contract
         #[clap(short, long)]
         funds: Option<String>,
@@ -158,8 +214,18 @@ pub enum WasmCmd {
         #[clap(long)]
         no_proposal_sync: bool,
 
-        /// Agree to all prompts
-        #[clap(short, long)]
+        /// Label recorded on chain, `--label` if set, otherwise generated from contract name, version and network.
+        /// Set it to `default` to keep the on-chain label of beaker <= 0.1.0
+        #[clap(long)]
+        chain_label: Option<String>,
+
+        /// Fail instead of warn if a contract with the same on-chain label is already instantiated by the signer,
+        /// or if that can not be checked
+        #[clap(long)]
+        strict: bool,
+
+        /// Agree to all prompts, same as the global `--yes`
+        #[clap(short, long, hide = true)]
         yes: bool,
 
         #[clap(flatten)]
@@ -173,21 +239,84 @@ pub enum WasmCmd {
         #[clap(short, long, default_value = "default")]
         label: String,
 
-        /// Raw json string to use as instantiate msg
-        #[clap(short, long)]
-        raw: Option<String>,
+        #[clap(flatten)]
+        msg_args: MsgArgs,
 
         /// Skip the check for proposal's updated code_id
         #[clap(long)]
         no_proposal_sync: bool,
 
-        /// Agree to all prompts
-        #[clap(short, long)]
+        /// Compare schemas and cw2 version of the code on chain with the new code and
+        /// simulate the migration without broadcasting
+        #[clap(long)]
+        dry_run: bool,
+
+        /// Agree to all prompts, same as the global `--yes`
+        #[clap(short, long, hide = true)]
         yes: bool,
 
         #[clap(flatten)]
         base_tx_args: BaseTxArgs,
     },
+    /// Plan a migration: diff execute / query schemas and cw2 version of the code on chain with the new code,
+    /// then scaffold a migrate msg stub and a checklist
+    MigratePlan {
+        /// Name of the contract to migrate
+        contract_name: String,
+
+        /// Label of the instantiated contract to migrate
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        /// Code id stored on chain or path to local .wasm file to migrate to,
+        /// the contract's optimized artifact if not specified
+        #[clap(long)]
+        to: Option<String>,
+
+        /// Name of the network the contract is deployed on, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Migrate contract back to the code id of its previous release recorded in state
+    Rollback {
+        /// Name of the contract to rollback
+        contract_name: String,
+        /// Label of the instantiated contract to rollback
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        /// Migrate msg for the rollback, `{}` if not specified
+        #[clap(flatten)]
+        msg_args: MsgArgs,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Migrate all contracts instantiated from a code id that the signer is admin of
+    MigrateAll {
+        /// Code id that contracts are currently instantiated from
+        #[clap(long)]
+        from_code_id: u64,
+
+        /// Code id to migrate contracts to
+        #[clap(long)]
+        to_code_id: u64,
+
+        /// Migrate msg used for every contract, `{}` if not specified
+        #[clap(flatten)]
+        msg_args: MsgArgs,
+
+        /// Number of contracts to migrate per transaction
+        #[clap(long, default_value = "50")]
+        batch_size: usize,
+
+        /// Only list contracts that would be migrated without broadcasting
+        #[clap(long)]
+        dry_run: bool,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
     /// Build, Optimize, Store code, and instantiate contract
     Deploy {
         /// Name of the contract to deploy
@@ -197,9 +326,8 @@ pub enum WasmCmd {
         #[clap(short, long, default_value = "default")]
         label: String,
 
-        /// Raw json string to use as instantiate msg
-        #[clap(short, long)]
-        raw: Option<String>,
+        #[clap(flatten)]
+        msg_args: MsgArgs,
 
         /// Restricting the code to be able to instantiate only by given address, no restriction by default
         #[clap(long)]
@@ -208,9 +336,14 @@ pub enum WasmCmd {
         /// Specifying admin required for contract migration.
         /// Use "signer" for setting tx signer as admin.
         /// Use bech32 address (eg. "osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks") for custom admin.
+        /// Falls back to `default_admin` in config if not specified.
         #[clap(long)]
         admin: Option<String>,
 
+        /// Instantiate contract without admin, contract will not be able to be migrated
+        #[clap(long, conflicts_with = "admin")]
+        no_admin: bool,
+
         /// Funds to send to instantiated contract
         #[clap(short, long)]
         funds: Option<String>,
@@ -223,6 +356,32 @@ pub enum WasmCmd {
         #[clap(long)]
         no_wasm_opt: bool,
 
+        /// Deploy the same build to multiple networks concurrently, comma separated (eg. `testnet,mainnet`), overrides `--network`
+        #[clap(long, use_value_delimiter = true)]
+        networks: Vec<String>,
+
+        /// Label recorded on chain, `--label` if set, otherwise generated from contract name, version and network.
+        /// Set it to `default` to keep the on-chain label of beaker <= 0.1.0
+        #[clap(long)]
+        chain_label: Option<String>,
+
+        /// Fail instead of warn if a contract with the same on-chain label is already instantiated by the signer,
+        /// or if that can not be checked
+        #[clap(long)]
+        strict: bool,
+
+        /// Refuse to proceed unless the working tree is clean and checked out at this git tag
+        #[clap(long)]
+        verify_tag: Option<String>,
+
+        /// Refuse to proceed if toolchain, the deployed artifact or network differ from `Beaker.lock`, which is not updated
+        #[clap(long)]
+        locked: bool,
+
+        /// Agree to all prompts, same as the global `--yes`
+        #[clap(short, long, hide = true)]
+        yes: bool,
+
         #[clap(flatten)]
         base_tx_args: BaseTxArgs,
     },
@@ -235,9 +394,8 @@ pub enum WasmCmd {
         #[clap(short, long, default_value = "default")]
         label: String,
 
-        /// Raw json string to use as instantiate msg
-        #[clap(short, long)]
-        raw: Option<String>,
+        #[clap(flatten)]
+        msg_args: MsgArgs,
 
         /// Use existing .wasm file to deploy if set to true
         #[clap(long)]
@@ -251,6 +409,10 @@ pub enum WasmCmd {
         #[clap(long)]
         permit_instantiate_only: Option<String>,
 
+        /// Agree to all prompts, same as the global `--yes`
+        #[clap(short, long, hide = true)]
+        yes: bool,
+
         #[clap(flatten)]
         base_tx_args: BaseTxArgs,
     },
@@ -265,12 +427,72 @@ pub enum WasmCmd {
         #[clap(short, long, default_value = "default")]
         label: String,
 
+        #[clap(flatten)]
+        msg_args: MsgArgs,
+
         #[clap(short, long)]
-        raw: Option<String>,
+        funds: Option<String>,
+
+        /// Execute on behalf of the granter address via `MsgExec`, requires prior authz grant to the signer
+        #[clap(long = "as", value_name = "GRANTER")]
+        granter: Option<String>,
+
+        /// Run the msg through simulation and show expected gas, events and response data without broadcasting
+        #[clap(long)]
+        simulate: bool,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Execute one msg per csv row, rendered from a template with `{{column}}` placeholders, eg. for airdrops.
+    /// Msgs are batched into txs and progress is recorded, rerunning the same command resumes after a failure
+    ExecuteBulk {
+        contract_name: String,
+
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        /// Path to csv file with a header row naming the columns
+        #[clap(long)]
+        csv: PathBuf,
+
+        /// Execute msg with `{{column}}` placeholders, eg. `{"transfer":{"recipient":"{{addr}}","amount":"{{amt}}"}}`
+        #[clap(long)]
+        template: String,
 
+        /// Funds sent with every msg
         #[clap(short, long)]
         funds: Option<String>,
 
+        /// Execute on behalf of the granter address via `MsgExec`, requires prior authz grant to the signer
+        #[clap(long = "as", value_name = "GRANTER")]
+        granter: Option<String>,
+
+        /// Maximum number of msgs per transaction
+        #[clap(long, default_value = "100")]
+        batch_size: usize,
+
+        /// Render msgs and count txs without broadcasting
+        #[clap(long)]
+        dry_run: bool,
+
+        /// Ignore progress of previous runs and execute every row again
+        #[clap(long)]
+        restart: bool,
+
+        #[clap(flatten)]
+        base_tx_args: BaseTxArgs,
+    },
+    /// Interactively pick a contract and execute msg from its schema, fill the msg field by field,
+    /// preview the fee and broadcast
+    Interact {
+        /// Contract to execute, picked from contracts deployed on the network if not specified
+        contract_name: Option<String>,
+
+        /// Label of the contract, picked from its instances if not specified
+        #[clap(short, long)]
+        label: Option<String>,
+
         #[clap(flatten)]
         base_tx_args: BaseTxArgs,
     },
@@ -281,12 +503,216 @@ pub enum WasmCmd {
         #[clap(short, long, default_value = "default")]
         label: String,
 
-        #[clap(short, long)]
-        raw: Option<String>,
+        #[clap(flatten)]
+        msg_args: MsgArgs,
+
+        /// JMESPath expression to extract value from query response (eg. `balances[0].amount`),
+        /// string result is printed raw
+        #[clap(long)]
+        query_filter: Option<String>,
+
+        /// Print the response json as is. Otherwise, when the contract has a response schema,
+        /// amounts, timestamps, addresses and binaries are shown by their type
+        #[clap(long)]
+        raw_response: bool,
 
         #[clap(flatten)]
         base_tx_args: BaseTxArgs,
     },
+    /// Run queries from a file of `{ "contract", "label"?, "msg" }` (json array or json lines) concurrently,
+    /// printing each result as a json line
+    QueryBatch {
+        /// Path to the batch file
+        file: PathBuf,
+
+        /// Maximum number of queries in flight
+        #[clap(short, long, default_value = "16")]
+        concurrency: usize,
+
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Compare exports, required capabilities, imports, sizes and sections of two wasm
+    Diff {
+        /// Code id stored on chain or path to local .wasm file
+        a: String,
+
+        /// Code id stored on chain or path to local .wasm file
+        b: String,
+
+        /// Name of the network to fetch code from when code id is given
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Show creator, checksum and instantiate permission of stored code
+    CodeInfo {
+        code_id: u64,
+
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// List codes stored on chain
+    ListCodes {
+        /// Only show codes created by predefined accounts
+        #[clap(long)]
+        mine: bool,
+
+        #[clap(flatten)]
+        page_args: PageArgs,
+
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// List addresses of contracts instantiated from the code
+    ListContracts {
+        code_id: u64,
+
+        #[clap(flatten)]
+        page_args: PageArgs,
+
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Show code history of the contract recorded on chain, with instantiate and migrate msgs
+    History {
+        /// Contract name in state or contract address
+        contract: String,
+
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Show cw2 contract name and version of a contract, or of all contracts deployed on the network
+    Version {
+        /// Contract name in state or contract address, all deployed contracts if not specified
+        contract: Option<String>,
+
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Predict the address of a contract instantiated with instantiate2, so that init msgs of
+    /// interdependent contracts can refer to addresses before anything is deployed
+    PredictAddress {
+        /// Hex encoded sha256 checksum of the wasm code
+        #[clap(long)]
+        code_checksum: String,
+
+        /// Address of the account that will instantiate the contract
+        #[clap(long)]
+        creator: String,
+
+        /// Hex encoded salt, 1 to 64 bytes
+        #[clap(long)]
+        salt: String,
+    },
+    /// Show wasm module params of the network, including whether store-code requires a proposal
+    Params {
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Show build metadata (git commit, rustc version, optimizer image, build time) embedded in wasm
+    Inspect {
+        /// Code id stored on chain or path to local .wasm file
+        source: String,
+
+        /// Name of the network to fetch code from when code id is given
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Dump raw contract storage decoded as cw-storage-plus `Item` and `Map` entries
+    DumpState {
+        contract_name: String,
+
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        /// Storage key spec as `<namespace>[:<key_type>,...]`, eg. `config` for `Item`,
+        /// `balances:addr` or `allowances:addr,addr` for `Map`.
+        /// Key types: string, addr, bytes, u8, u16, u32, u64, u128, i32, i64.
+        /// Unspecified entries are decoded on a best effort basis
+        #[clap(short, long = "key-spec")]
+        key_specs: Vec<KeySpec>,
+
+        #[clap(flatten)]
+        page_args: PageArgs,
+
+        /// Name of the network to query, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Export raw contract storage, optionally only selected namespaces, to seed an equivalent contract elsewhere
+    ExportState {
+        contract_name: String,
+
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        /// Namespace of the cw-storage-plus `Item` or `Map` to export, all storage is exported if none is given
+        #[clap(long = "namespace")]
+        namespaces: Vec<String>,
+
+        /// File to write the export to, defaults to `<contract_name>-<label>-<network>.state.json`
+        #[clap(short, long)]
+        out: Option<PathBuf>,
+
+        /// Name of the network to export from, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
+    /// Turn a state export into a wasm genesis contract or into msgs importing its entries
+    ImportState {
+        /// State export created by `export-state`
+        file: PathBuf,
+
+        /// `genesis` for a contract entry of wasm genesis, eg. for localnet, or `msgs` for
+        /// execute / migrate msgs rendered from `--template`
+        #[clap(long = "as", default_value = "genesis")]
+        format: ops::state_transfer::ImportFormat,
+
+        /// Address of the contract in genesis, defaults to the source contract address
+        #[clap(long)]
+        address: Option<String>,
+
+        /// Code id of the contract in genesis, defaults to the source code id
+        #[clap(long)]
+        code_id: Option<u64>,
+
+        /// Json msg template, the `"{{entries}}"` string is replaced by a batch of
+        /// `{"key": <base64>, "value": <base64>}` entries
+        #[clap(long, default_value = ops::state_transfer::DEFAULT_IMPORT_TEMPLATE)]
+        template: String,
+
+        /// Number of entries per msg
+        #[clap(long, default_value = "50")]
+        batch_size: usize,
+    },
+    /// Print events emitted by the contract in recent txs, or stream them live with `--follow`
+    Events {
+        contract_name: String,
+
+        #[clap(short, long, default_value = "default")]
+        label: String,
+
+        /// Keep subscribing to new events until interrupted
+        #[clap(short, long)]
+        follow: bool,
+
+        /// Name of the network to subscribe to, the actual endpoint / chain-id are defined in config
+        #[clap(short, long, default_value = "local")]
+        network: String,
+    },
 }
 
 #[derive(new)]
@@ -294,20 +720,53 @@ pub struct WasmModule {}
 
 impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
     fn execute<Ctx: Context<'a, WasmConfig>>(ctx: Ctx, cmd: &WasmCmd) -> Result<(), anyhow::Error> {
+        // every cargo invocation and wasm lookup of this run uses the shared target dir
+        if let (Ok(root), Some(target_dir)) = (ctx.root(), ctx.config()?.target_dir) {
+            env::set_var("CARGO_TARGET_DIR", root.join(target_dir));
+        }
+
         match cmd {
             WasmCmd::New {
                 contract_name: name,
                 target_dir, // TODO: Rremove this
                 version,
-            } => ops::new(&ctx, name, version.to_owned(), target_dir.to_owned()),
+                ibc,
+                generator_args,
+            } => ops::new(
+                &ctx,
+                name,
+                version.to_owned(),
+                target_dir.to_owned(),
+                generator_args.to_owned(),
+                *ibc,
+            ),
             WasmCmd::Build {
                 no_wasm_opt,
                 aarch64,
-            } => ops::build(&ctx, no_wasm_opt, aarch64),
+                matrix,
+            } => {
+                if *matrix {
+                    ops::build_matrix(&ctx, no_wasm_opt, aarch64)?;
+                } else {
+                    ops::build(&ctx, no_wasm_opt, aarch64)?;
+                }
+                Ok(())
+            }
+            WasmCmd::PushArtifacts { tag } => {
+                ops::push_artifacts(&ctx, tag.as_deref())?;
+                Ok(())
+            }
+            WasmCmd::PullArtifacts { tag } => {
+                ops::pull_artifacts(&ctx, tag)?;
+                Ok(())
+            }
             WasmCmd::StoreCode {
                 contract_name,
                 no_wasm_opt,
                 permit_instantiate_only,
+                verify_tag,
+                from_url,
+                expect_checksum,
                 base_tx_args,
             } => {
                 let BaseTxArgs {
@@ -316,6 +775,9 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                     gas_args,
                     timeout_height,
                 }: &BaseTxArgs = base_tx_args;
+                if let Some(tag) = verify_tag {
+                    git::verify_tag(&ctx.root()?, tag)?;
+                }
 
                 ops::store_code(
                     &ctx,
@@ -327,12 +789,13 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                         let global_conf = ctx.global_config()?;
                         &Gas::from_args(
                             gas_args,
-                            global_conf.gas_price(),
+                            global_conf.network_gas_price(network),
                             global_conf.gas_adjustment(),
                         )?
                     },
                     timeout_height,
-                    signer_args.private_key(&ctx.global_config()?)?,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
+                    from_url.as_deref().zip(expect_checksum.as_deref()).into(),
                 )?;
                 Ok(())
             }
@@ -359,12 +822,12 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                         let global_conf = ctx.global_config()?;
                         &Gas::from_args(
                             gas_args,
-                            global_conf.gas_price(),
+                            global_conf.network_gas_price(network),
                             global_conf.gas_adjustment(),
                         )?
                     },
                     timeout_height,
-                    signer_args.private_key(&ctx.global_config()?)?,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
                 )?;
                 Ok(())
             }
@@ -389,12 +852,12 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                         let global_conf = ctx.global_config()?;
                         &Gas::from_args(
                             gas_args,
-                            global_conf.gas_price(),
+                            global_conf.network_gas_price(network),
                             global_conf.gas_adjustment(),
                         )?
                     },
                     timeout_height,
-                    signer_args.private_key(&ctx.global_config()?)?,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
                 )?;
                 Ok(())
             }
@@ -402,13 +865,20 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
             WasmCmd::Instantiate {
                 contract_name,
                 label,
-                raw,
+                msg_args,
                 admin,
+                no_admin,
+                salt,
                 no_proposal_sync,
-                yes,
                 funds,
+                chain_label,
+                strict,
+                yes,
                 base_tx_args,
             } => {
+                if *yes {
+                    prompt::set_assume_yes(true);
+                }
                 let BaseTxArgs {
                     network,
                     signer_args,
@@ -419,10 +889,13 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                     &ctx,
                     contract_name,
                     label.as_str(),
-                    raw.as_ref(),
-                    admin.as_ref(),
+                    chain_label.as_deref(),
+                    *strict,
+                    msg_args.raw_msg()?.as_ref(),
+                    admin_or_default(admin, *no_admin, &ctx.config()?.default_admin).as_ref(),
+                    salt.as_deref(),
                     *no_proposal_sync,
-                    *yes,
+                    prompt::assume_yes(),
                     funds.as_ref().map(|s| s.as_str()).try_into()?,
                     network,
                     timeout_height,
@@ -430,22 +903,26 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                         let global_conf = ctx.global_config()?;
                         &Gas::from_args(
                             gas_args,
-                            global_conf.gas_price(),
+                            global_conf.network_gas_price(network),
                             global_conf.gas_adjustment(),
                         )?
                     },
-                    signer_args.private_key(&ctx.global_config()?)?,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
                 )?;
                 Ok(())
             }
             WasmCmd::Migrate {
                 contract_name,
                 label,
-                raw,
+                msg_args,
                 no_proposal_sync,
+                dry_run,
                 yes,
                 base_tx_args,
             } => {
+                if *yes {
+                    prompt::set_assume_yes(true);
+                }
                 let BaseTxArgs {
                     network,
                     signer_args,
@@ -456,74 +933,198 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                     &ctx,
                     contract_name,
                     label.as_str(),
-                    raw.as_ref(),
+                    msg_args.raw_msg()?.as_ref(),
                     *no_proposal_sync,
-                    *yes,
+                    prompt::assume_yes(),
+                    network,
+                    timeout_height,
+                    {
+                        let global_conf = ctx.global_config()?;
+                        &Gas::from_args(
+                            gas_args,
+                            global_conf.network_gas_price(network),
+                            global_conf.gas_adjustment(),
+                        )?
+                    },
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
+                    *dry_run,
+                )?;
+                Ok(())
+            }
+            WasmCmd::MigratePlan {
+                contract_name,
+                label,
+                to,
+                network,
+            } => {
+                ops::migrate_plan(&ctx, contract_name, label, to.as_deref(), network)?;
+                Ok(())
+            }
+            WasmCmd::Rollback {
+                contract_name,
+                label,
+                msg_args,
+                base_tx_args,
+            } => {
+                let BaseTxArgs {
+                    network,
+                    signer_args,
+                    gas_args,
+                    timeout_height,
+                }: &BaseTxArgs = base_tx_args;
+                ops::rollback(
+                    &ctx,
+                    contract_name,
+                    label.as_str(),
+                    msg_args.raw_msg()?.as_ref(),
+                    network,
+                    timeout_height,
+                    {
+                        let global_conf = ctx.global_config()?;
+                        &Gas::from_args(
+                            gas_args,
+                            global_conf.network_gas_price(network),
+                            global_conf.gas_adjustment(),
+                        )?
+                    },
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
+                )?;
+                Ok(())
+            }
+            WasmCmd::MigrateAll {
+                from_code_id,
+                to_code_id,
+                msg_args,
+                batch_size,
+                dry_run,
+                base_tx_args,
+            } => {
+                let BaseTxArgs {
+                    network,
+                    signer_args,
+                    gas_args,
+                    timeout_height,
+                }: &BaseTxArgs = base_tx_args;
+                ops::migrate_all(
+                    &ctx,
+                    *from_code_id,
+                    *to_code_id,
+                    msg_args.raw_msg()?.as_ref(),
+                    *batch_size,
+                    *dry_run,
                     network,
                     timeout_height,
                     {
                         let global_conf = ctx.global_config()?;
                         &Gas::from_args(
                             gas_args,
-                            global_conf.gas_price(),
+                            global_conf.network_gas_price(network),
                             global_conf.gas_adjustment(),
                         )?
                     },
-                    signer_args.private_key(&ctx.global_config()?)?,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
                 )?;
                 Ok(())
             }
             WasmCmd::Deploy {
                 contract_name,
                 label,
-                raw,
+                msg_args,
                 permit_instantiate_only,
                 admin,
+                no_admin,
                 funds,
                 no_rebuild,
                 no_wasm_opt,
+                networks,
+                chain_label,
+                strict,
+                verify_tag,
+                locked,
+                yes,
                 base_tx_args,
             } => {
+                if *yes {
+                    prompt::set_assume_yes(true);
+                }
                 let BaseTxArgs {
                     network,
                     signer_args,
                     gas_args,
                     timeout_height,
                 }: &BaseTxArgs = base_tx_args;
+                if let Some(tag) = verify_tag {
+                    git::verify_tag(&ctx.root()?, tag)?;
+                }
+                let gas = {
+                    let global_conf = ctx.global_config()?;
+                    Gas::from_args(
+                        gas_args,
+                        global_conf.network_gas_price(network),
+                        global_conf.gas_adjustment(),
+                    )?
+                };
+                if !networks.is_empty() {
+                    ops::deploy_to_networks(
+                        &ctx,
+                        contract_name,
+                        label.as_str(),
+                        chain_label.as_deref(),
+                        *strict,
+                        msg_args.raw_msg()?.as_ref(),
+                        permit_instantiate_only,
+                        admin_or_default(admin, *no_admin, &ctx.config()?.default_admin).as_ref(),
+                        funds.as_deref(),
+                        networks,
+                        timeout_height,
+                        gas_args,
+                        |network| {
+                            signer_args.private_key(
+                                &ctx.chain_client(),
+                                &ctx.global_config()?,
+                                network,
+                            )
+                        },
+                        no_rebuild,
+                        no_wasm_opt,
+                        locked,
+                    )?;
+                    return Ok(());
+                }
                 ops::deploy(
                     &ctx,
                     contract_name,
                     label.as_str(),
-                    raw.as_ref(),
+                    chain_label.as_deref(),
+                    *strict,
+                    msg_args.raw_msg()?.as_ref(),
                     permit_instantiate_only,
-                    admin.as_ref(),
+                    admin_or_default(admin, *no_admin, &ctx.config()?.default_admin).as_ref(),
                     funds.as_ref().map(|s| s.as_str()).try_into()?,
                     network,
                     timeout_height,
-                    {
-                        let global_conf = ctx.global_config()?;
-                        &Gas::from_args(
-                            gas_args,
-                            global_conf.gas_price(),
-                            global_conf.gas_adjustment(),
-                        )?
-                    },
-                    signer_args.private_key(&ctx.global_config()?)?,
-                    signer_args.private_key(&ctx.global_config()?)?,
+                    &gas,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
                     no_rebuild,
                     no_wasm_opt,
+                    locked,
                 )?;
                 Ok(())
             }
             WasmCmd::Upgrade {
                 contract_name,
                 label,
-                raw,
+                msg_args,
                 no_rebuild,
                 no_wasm_opt,
                 permit_instantiate_only,
+                yes,
                 base_tx_args,
             } => {
+                if *yes {
+                    prompt::set_assume_yes(true);
+                }
                 let BaseTxArgs {
                     network,
                     signer_args,
@@ -534,7 +1135,7 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                     &ctx,
                     contract_name,
                     label.as_str(),
-                    raw.as_ref(),
+                    msg_args.raw_msg()?.as_ref(),
                     permit_instantiate_only,
                     network,
                     timeout_height,
@@ -542,12 +1143,12 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                         let global_conf = ctx.global_config()?;
                         &Gas::from_args(
                             gas_args,
-                            global_conf.gas_price(),
+                            global_conf.network_gas_price(network),
                             global_conf.gas_adjustment(),
                         )?
                     },
-                    signer_args.private_key(&ctx.global_config()?)?,
-                    signer_args.private_key(&ctx.global_config()?)?,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
                     no_rebuild,
                     no_wasm_opt,
                 )?;
@@ -559,9 +1160,17 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                 schema_gen_cmd,
                 out_dir,
                 node_package_manager,
+                react_query,
+                network,
             } => {
                 let root = ctx.root()?;
                 let sdk_path = root.join("ts/sdk");
+                let network_info = ctx
+                    .global_config()?
+                    .networks()
+                    .get(network)
+                    .with_context(|| format!("Unable to find network config: {network}"))?
+                    .clone();
                 env::set_current_dir(root.join("contracts").join(contract_name))?;
                 if let Some(gen) = schema_gen_cmd {
                     let gen = gen.replace("{contract_name}", contract_name);
@@ -583,6 +1192,12 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                     return Ok(());
                 }
 
+                let addresses = address_map(root, network, network_info)?;
+                fs::write(
+                    sdk_path.join("addresses.json"),
+                    serde_json::to_string_pretty(&addresses)?,
+                )?;
+
                 env::set_current_dir(sdk_path)?;
 
                 let node_pkg = || Command::new(String::from(node_package_manager));
@@ -595,15 +1210,21 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                 };
 
                 run_command(node_pkg().arg("install"))?;
-                run_command(node_pkg().arg("run").arg("codegen"))?; // TODO: pass schema dir here
+                let mut codegen = node_pkg();
+                if *react_query {
+                    codegen.env("BEAKER_TS_REACT_QUERY", "1");
+                }
+                run_command(codegen.arg("run").arg("codegen"))?; // TODO: pass schema dir here
                 run_command(node_pkg().arg("run").arg("build"))?;
                 Ok(())
             }
             WasmCmd::Execute {
                 contract_name,
                 label,
-                raw,
+                msg_args,
                 funds,
+                granter,
+                simulate,
                 base_tx_args,
             } => {
                 let BaseTxArgs {
@@ -616,32 +1237,235 @@ impl<'a> Module<'a, WasmConfig, WasmCmd, anyhow::Error> for WasmModule {
                     &ctx,
                     contract_name,
                     label.as_str(),
-                    raw.as_ref(),
+                    msg_args.raw_msg()?.as_ref(),
                     funds.as_ref().map(|s| s.as_str()).try_into()?,
+                    granter.as_ref(),
+                    network,
+                    timeout_height,
+                    {
+                        let global_conf = ctx.global_config()?;
+                        &Gas::from_args(
+                            gas_args,
+                            global_conf.network_gas_price(network),
+                            global_conf.gas_adjustment(),
+                        )?
+                    },
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
+                    *simulate,
+                )?;
+                Ok(())
+            }
+            WasmCmd::ExecuteBulk {
+                contract_name,
+                label,
+                csv,
+                template,
+                funds,
+                granter,
+                batch_size,
+                dry_run,
+                restart,
+                base_tx_args,
+            } => {
+                let BaseTxArgs {
+                    network,
+                    signer_args,
+                    gas_args,
+                    timeout_height,
+                }: &BaseTxArgs = base_tx_args;
+                ops::execute_bulk(
+                    &ctx,
+                    contract_name,
+                    label,
+                    csv,
+                    template,
+                    funds.as_deref().try_into()?,
+                    granter.as_ref(),
+                    *batch_size,
+                    *dry_run,
+                    *restart,
+                    network,
+                    timeout_height,
+                    {
+                        let global_conf = ctx.global_config()?;
+                        &Gas::from_args(
+                            gas_args,
+                            global_conf.network_gas_price(network),
+                            global_conf.gas_adjustment(),
+                        )?
+                    },
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
+                )?;
+                Ok(())
+            }
+            WasmCmd::Interact {
+                contract_name,
+                label,
+                base_tx_args,
+            } => {
+                let BaseTxArgs {
+                    network,
+                    signer_args,
+                    gas_args,
+                    timeout_height,
+                }: &BaseTxArgs = base_tx_args;
+                ops::interact(
+                    &ctx,
+                    contract_name.as_deref(),
+                    label.as_deref(),
                     network,
                     timeout_height,
                     {
                         let global_conf = ctx.global_config()?;
                         &Gas::from_args(
                             gas_args,
-                            global_conf.gas_price(),
+                            global_conf.network_gas_price(network),
                             global_conf.gas_adjustment(),
                         )?
                     },
-                    signer_args.private_key(&ctx.global_config()?)?,
+                    signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
                 )?;
                 Ok(())
             }
             WasmCmd::Query {
                 contract_name,
                 label,
-                raw,
+                msg_args,
+                query_filter,
+                raw_response,
                 base_tx_args,
             } => {
                 let BaseTxArgs { network, .. }: &BaseTxArgs = base_tx_args;
-                ops::query(&ctx, contract_name, label.as_str(), raw.as_ref(), network)?;
+                ops::query(
+                    &ctx,
+                    contract_name,
+                    label.as_str(),
+                    msg_args.raw_msg()?.as_ref(),
+                    query_filter.as_ref(),
+                    !raw_response,
+                    network,
+                )?;
+                Ok(())
+            }
+            WasmCmd::QueryBatch {
+                file,
+                concurrency,
+                network,
+            } => ops::query_batch(&ctx, file, *concurrency, network),
+            WasmCmd::Diff { a, b, network } => {
+                ops::diff(&ctx, a, b, network)?;
+                Ok(())
+            }
+            WasmCmd::CodeInfo { code_id, network } => {
+                ops::code_info(&ctx, *code_id, network)?;
+                Ok(())
+            }
+            WasmCmd::ListCodes {
+                mine,
+                page_args,
+                network,
+            } => {
+                ops::list_codes(&ctx, *mine, page_args.limit(), network)?;
+                Ok(())
+            }
+            WasmCmd::ListContracts {
+                code_id,
+                page_args,
+                network,
+            } => {
+                ops::list_contracts(&ctx, *code_id, page_args.limit(), network)?;
+                Ok(())
+            }
+            WasmCmd::Version {
+                contract,
+                label,
+                network,
+            } => {
+                ops::version(&ctx, contract.as_ref(), label, network)?;
+                Ok(())
+            }
+            WasmCmd::PredictAddress {
+                code_checksum,
+                creator,
+                salt,
+            } => {
+                ops::predict_address(code_checksum, creator, salt)?;
+                Ok(())
+            }
+            WasmCmd::History {
+                contract,
+                label,
+                network,
+            } => {
+                ops::history(&ctx, contract, label, network)?;
+                Ok(())
+            }
+            WasmCmd::Params { network } => {
+                ops::params(&ctx, network)?;
+                Ok(())
+            }
+            WasmCmd::Inspect { source, network } => {
+                ops::inspect(&ctx, source, network)?;
+                Ok(())
+            }
+            WasmCmd::DumpState {
+                contract_name,
+                label,
+                key_specs,
+                page_args,
+                network,
+            } => {
+                ops::dump_state(
+                    &ctx,
+                    contract_name,
+                    label.as_str(),
+                    key_specs,
+                    page_args.limit(),
+                    network,
+                )?;
                 Ok(())
             }
+            WasmCmd::ExportState {
+                contract_name,
+                label,
+                namespaces,
+                out,
+                network,
+            } => {
+                ops::export_state(
+                    &ctx,
+                    contract_name,
+                    label,
+                    namespaces,
+                    out.as_deref(),
+                    network,
+                )?;
+                Ok(())
+            }
+            WasmCmd::ImportState {
+                file,
+                format,
+                address,
+                code_id,
+                template,
+                batch_size,
+            } => {
+                ops::import_state(
+                    file,
+                    *format,
+                    address.as_deref(),
+                    *code_id,
+                    template,
+                    *batch_size,
+                )?;
+                Ok(())
+            }
+            WasmCmd::Events {
+                contract_name,
+                label,
+                follow,
+                network,
+            } => ops::events(&ctx, contract_name, label, *follow, network),
         }
     }
 }
@@ -677,6 +1501,8 @@ mod tests {
                 contract_name: "counter-1".to_string(),
                 version: None,
                 target_dir: None,
+                ibc: false,
+                generator_args: vec![],
             },
         )
         .unwrap();
@@ -692,6 +1518,8 @@ mod tests {
                 contract_name: "counter-2".to_string(),
                 target_dir: None,
                 version: None,
+                ibc: false,
+                generator_args: vec![],
             },
         )
         .unwrap();
@@ -718,6 +1546,8 @@ mod tests {
                 contract_name: "counter-1".to_string(),
                 target_dir: None,
                 version: None,
+                ibc: false,
+                generator_args: vec![],
             },
         )
         .unwrap();
@@ -730,6 +1560,8 @@ mod tests {
                 contract_name: "counter-2".to_string(),
                 target_dir: None,
                 version: None,
+                ibc: false,
+                generator_args: vec![],
             },
         )
         .unwrap();
@@ -766,6 +1598,8 @@ mod tests {
                 contract_name: "counter-1".to_string(),
                 target_dir: None,
                 version: Some("0.16".into()),
+                ibc: false,
+                generator_args: vec![],
             },
         )
         .unwrap();
@@ -779,6 +1613,8 @@ mod tests {
                 contract_name: "counter-2".to_string(),
                 target_dir: None,
                 version: Some("0.16".into()),
+                ibc: false,
+                generator_args: vec![],
             },
         )
         .unwrap();
@@ -807,6 +1643,8 @@ mod tests {
                 contract_name: "counter-1".to_string(),
                 target_dir: Some("custom-path".into()),
                 version: None,
+                ibc: false,
+                generator_args: vec![],
             },
         )
         .unwrap();
@@ -819,6 +1657,8 @@ mod tests {
                 contract_name: "counter-2".to_string(),
                 target_dir: Some("custom-path".into()),
                 version: None,
+                ibc: false,
+                generator_args: vec![],
             },
         )
         .unwrap();
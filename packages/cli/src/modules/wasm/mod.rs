@@ -1,3 +1,4 @@
+pub mod artifact_registry;
 pub mod config;
 pub mod entrypoint;
 pub mod ops;
@@ -5,5 +6,5 @@ pub mod ops;
 pub use crate::modules::wasm::config::WasmConfig;
 pub use crate::modules::wasm::entrypoint::{WasmCmd, WasmModule};
 
-mod args;
+pub mod args;
 mod proposal;
@@ -0,0 +1,143 @@
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context as _, Result};
+use config::Map;
+use serde::Serialize;
+
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::wasm::artifact_registry::{
+    pull, push, validate_tag, verify_checksums, write_manifest, MANIFEST_FILE, METADATA_FILE,
+};
+use crate::modules::wasm::config::{ArtifactRegistry, WasmConfig};
+use crate::support::git::head_commit;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::STATE_DIR;
+use crate::support::wasm::{checksum, wasm_dir};
+
+fn artifact_registry<'a, Ctx: Context<'a, WasmConfig>>(ctx: &Ctx) -> Result<ArtifactRegistry> {
+    ctx.config()?
+        .artifact_registry
+        .with_context(|| "`artifact_registry` is not configured under `[wasm]` in `Beaker.toml`")
+}
+
+/// Publish optimized artifacts with their checksums and build metadata under `tag`,
+/// which defaults to the current git commit
+pub fn push_artifacts<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    tag: Option<&str>,
+) -> Result<PushArtifactsResponse> {
+    let registry = artifact_registry(ctx)?;
+    let root = ctx.root()?;
+    let git_commit = head_commit(&root);
+    let tag = match tag {
+        Some(tag) => tag.to_string(),
+        None => git_commit
+            .clone()
+            .with_context(|| "Unable to determine git commit, please specify `--tag`")?,
+    };
+    validate_tag(&tag)?;
+
+    let dir = wasm_dir(&root, &false);
+    let mut wasm_files = fs::read_dir(&dir)
+        .with_context(|| "No artifacts found, please build and optimize contracts before pushing")?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().map(|ext| ext == "wasm").unwrap_or(false))
+        .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+        .collect::<Vec<String>>();
+    wasm_files.sort();
+
+    write_manifest(&dir, &wasm_files)?;
+    let mut artifacts = Map::new();
+    for file in &wasm_files {
+        artifacts.insert(file.clone(), checksum(&fs::read(dir.join(file))?));
+    }
+    let metadata = serde_json::json!({
+        "tag": tag,
+        "git_commit": git_commit,
+        "optimizer_version": ctx.config()?.optimizer_version,
+        "created_at": SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        "artifacts": artifacts,
+    });
+    fs::write(
+        dir.join(METADATA_FILE),
+        serde_json::to_string_pretty(&metadata)?,
+    )?;
+
+    let files = [
+        wasm_files.clone(),
+        vec![MANIFEST_FILE.to_string(), METADATA_FILE.to_string()],
+    ]
+    .concat();
+    push(&registry, &dir, &files, &tag)?;
+
+    let push_response = PushArtifactsResponse {
+        tag,
+        artifacts: wasm_files.join(", "),
+    };
+    push_response.log();
+
+    Ok(push_response)
+}
+
+/// Download artifacts published under `tag`, verify their checksums and place them where `store-code` reads them
+pub fn pull_artifacts<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    tag: &str,
+) -> Result<PullArtifactsResponse> {
+    validate_tag(tag)?;
+    let registry = artifact_registry(ctx)?;
+    let root = ctx.root()?;
+
+    let staging_dir = root.join(STATE_DIR).join("artifacts").join(tag);
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir)?;
+    }
+    pull(&registry, tag, &staging_dir)?;
+    let verified = verify_checksums(&staging_dir)?;
+
+    let dir = wasm_dir(&root, &false);
+    fs::create_dir_all(&dir)?;
+    for file in &verified {
+        fs::copy(staging_dir.join(file), dir.join(file))?;
+    }
+
+    let pull_response = PullArtifactsResponse {
+        tag: tag.to_string(),
+        artifacts: verified.join(", "),
+    };
+    pull_response.log();
+
+    Ok(pull_response)
+}
+
+#[derive(Serialize)]
+pub struct PushArtifactsResponse {
+    pub tag: String,
+    pub artifacts: String,
+}
+
+impl OpResponseDisplay for PushArtifactsResponse {
+    fn headline() -> &'static str {
+        "Artifacts pushed successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | tag, artifacts }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PullArtifactsResponse {
+    pub tag: String,
+    pub artifacts: String,
+}
+
+impl OpResponseDisplay for PullArtifactsResponse {
+    fn headline() -> &'static str {
+        "Artifacts pulled and verified successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | tag, artifacts }
+    }
+}
@@ -1,15 +1,24 @@
-use std::{env, process::Command};
+use std::{env, ffi::OsStr, fs, path::Path, process::Command};
 
-use anyhow::Result;
+use anyhow::{bail, Context as _, Result};
+use console::style;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 
+use crate::modules::wasm::artifact_registry::write_checksums;
+use crate::support::build_metadata::BuildMetadata;
 use crate::support::command::run_command;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::progress::Progress;
+use crate::support::wasm::wasm_dir;
+use crate::support::wasm_info::parse_wasm;
 use crate::{framework::Context, modules::wasm::WasmConfig};
 
 pub fn build<'a, Ctx: Context<'a, WasmConfig>>(
     ctx: &Ctx,
     no_wasm_opt: &bool,
     aarch64: &bool,
-) -> Result<()> {
+) -> Result<BuildResponse> {
     let root = ctx.root()?;
 
     let wp_name = root.file_name().unwrap().to_str().unwrap(); // handle properly
@@ -18,6 +27,70 @@ pub fn build<'a, Ctx: Context<'a, WasmConfig>>(
 
     let root_dir_str = root.to_str().unwrap();
 
+    ensure_wasm_target()?;
+
+    Progress::stream("Building wasm").end_with(run_command(
+        Command::new("cargo")
+            .env("RUSTFLAGS", "-C link-arg=-s")
+            .arg("build")
+            .arg("--lib")
+            .arg("--release")
+            .arg("--target")
+            .arg("wasm32-unknown-unknown"),
+    ))?;
+
+    if !*no_wasm_opt {
+        let optimizer_version = ctx.config()?.optimizer_version;
+
+        let progress = Progress::stream("Optimizing wasm");
+        progress.end_with(run_command(
+            Command::new("docker").args(&[
+                "run",
+                "--rm",
+                "-v",
+                format!("{root_dir_str}:/code").as_str(),
+                "--mount",
+                format!(
+                    "type=volume,source={},target=/code/target",
+                    cache_volume(wp_name, env::var_os("CARGO_TARGET_DIR").as_deref())
+                )
+                .as_str(),
+                "--mount",
+                "type=volume,source=registry_cache,target=/usr/local/cargo/registry",
+                optimizer_image(&optimizer_version, aarch64).as_str(),
+            ]),
+        ))?;
+    }
+
+    let mut artifacts = fs::read_dir(wasm_dir(&root, no_wasm_opt))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().map(|ext| ext == "wasm").unwrap_or(false))
+        .map(|path| path.to_string_lossy().to_string())
+        .collect::<Vec<String>>();
+    artifacts.sort();
+
+    let expects_migration = expects_migration(&ctx.config()?);
+    for artifact in &artifacts {
+        check_entry_points(Path::new(artifact), expects_migration)?;
+    }
+
+    if !*no_wasm_opt {
+        let optimizer_version = ctx.config()?.optimizer_version;
+        embed_build_metadata(
+            &root,
+            &artifacts,
+            optimizer_image(&optimizer_version, aarch64),
+        )?;
+    }
+
+    let build_response = BuildResponse { artifacts };
+    build_response.log();
+
+    Ok(build_response)
+}
+
+/// Install `wasm32-unknown-unknown` target if it is not installed yet
+pub fn ensure_wasm_target() -> Result<()> {
     let list_installed_target = Command::new("rustup")
         .arg("target")
         .arg("list")
@@ -35,36 +108,144 @@ pub fn build<'a, Ctx: Context<'a, WasmConfig>>(
                 .arg("add")
                 .arg("wasm32-unknown-unknown"),
         )?;
-    };
-
-    run_command(
-        Command::new("cargo")
-            .env("RUSTFLAGS", "-C link-arg=-s")
-            .arg("build")
-            .arg("--lib")
-            .arg("--release")
-            .arg("--target")
-            .arg("wasm32-unknown-unknown"),
-    )?;
+    }
+    Ok(())
+}
 
-    if !*no_wasm_opt {
-        println!("Optimizing wasm...");
-        let optimizer_version = ctx.config()?.optimizer_version;
+pub fn optimizer_image(optimizer_version: &str, aarch64: &bool) -> String {
+    let arch_suffix = if *aarch64 { "-arm64" } else { "" };
+    format!("cosmwasm/workspace-optimizer{arch_suffix}:{optimizer_version}")
+}
 
-        let arch_suffix = if *aarch64 { "-arm64" } else { "" };
-
-        run_command(Command::new("docker").args(&[
-            "run",
-            "--rm",
-            "-v",
-            format!("{root_dir_str}:/code").as_str(),
-            "--mount",
-            format!("type=volume,source={wp_name}_cache,target=/code/target").as_str(),
-            "--mount",
-            "type=volume,source=registry_cache,target=/usr/local/cargo/registry",
-            format!("cosmwasm/workspace-optimizer{arch_suffix}:{optimizer_version}").as_str(),
-        ]))?;
+/// Embed provenance of this build into optimized `artifacts`, then refresh checksums of the
+/// artifacts directory since embedding changes them
+pub fn embed_build_metadata(
+    root: &Path,
+    artifacts: &[String],
+    optimizer_image: String,
+) -> Result<()> {
+    let metadata = BuildMetadata::current(root, Some(optimizer_image))?;
+    for artifact in artifacts {
+        let wasm = fs::read(artifact)?;
+        fs::write(artifact, metadata.embed(&wasm)?)
+            .with_context(|| format!("Unable to embed build metadata into `{artifact}`"))?;
     }
 
+    let dir = wasm_dir(root, &false);
+    let mut wasm_files = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().map(|ext| ext == "wasm").unwrap_or(false))
+        .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+        .collect::<Vec<String>>();
+    wasm_files.sort();
+    write_checksums(&dir, &wasm_files)
+}
+
+const REQUIRED_ENTRY_POINTS: [&str; 3] = ["instantiate", "execute", "query"];
+
+/// Contracts get admin and are expected to be migrated unless configured with `default_admin = "none"`
+pub fn expects_migration(config: &WasmConfig) -> bool {
+    config.default_admin.as_deref() != Some("none")
+}
+
+/// Entry points which `exports` lacks, missing `migrate` is only reported when migrations are expected
+fn missing_entry_points(exports: &[String], expects_migration: bool) -> Vec<&'static str> {
+    REQUIRED_ENTRY_POINTS
+        .iter()
+        .chain(Some(&"migrate").filter(|_| expects_migration))
+        .filter(|entry_point| !exports.iter().any(|e| e == *entry_point))
+        .copied()
+        .collect()
+}
+
+/// Fail on artifact that can not be instantiated, warn on other missing entry points
+/// since those are only discovered once the code is already stored on chain
+pub fn check_entry_points(artifact: &Path, expects_migration: bool) -> Result<()> {
+    let name = artifact.file_name().unwrap_or_default().to_string_lossy();
+    let exports = parse_wasm(&fs::read(artifact)?)
+        .with_context(|| format!("Unable to read exports of `{name}`"))?
+        .exported_functions();
+
+    let missing = missing_entry_points(&exports, expects_migration);
+    if missing.contains(&"instantiate") {
+        bail!("`{name}` does not export `instantiate` entry point, it can not be instantiated");
+    }
+    for entry_point in missing {
+        let hint = if entry_point == "migrate" {
+            ", contract with admin will not be migratable to this code. \
+            Set `default_admin = \"none\"` in config if this is intended."
+        } else {
+            ""
+        };
+        eprintln!(
+            "  {} `{name}` does not export `{entry_point}` entry point{hint}",
+            style("WARNING:").yellow().bold()
+        );
+    }
     Ok(())
 }
+
+/// Docker volume used as optimizer's target dir, workspaces configured with the same shared
+/// target dir get the same volume so that optimized builds share compiled dependencies as well
+fn cache_volume(wp_name: &str, shared_target_dir: Option<&OsStr>) -> String {
+    match shared_target_dir {
+        Some(dir) => {
+            let digest = hex::encode(Sha256::digest(dir.to_string_lossy().as_bytes()));
+            format!("beaker_target_{}", &digest[..12])
+        }
+        None => format!("{wp_name}_cache"),
+    }
+}
+
+#[derive(Serialize)]
+pub struct BuildResponse {
+    pub artifacts: Vec<String>,
+}
+
+impl OpResponseDisplay for BuildResponse {
+    fn headline() -> &'static str {
+        "Wasm built successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        let last = self.artifacts.len().saturating_sub(1);
+        self.artifacts
+            .iter()
+            .enumerate()
+            .map(|(i, artifact)| {
+                let branch = if i == last { "└──" } else { "├──" };
+                format!("    {branch} {artifact}")
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_missing_entry_points() {
+        let exports = ["instantiate", "execute", "query", "reply"].map(String::from);
+        assert!(missing_entry_points(&exports, false).is_empty());
+        assert_eq!(missing_entry_points(&exports, true), vec!["migrate"]);
+        assert_eq!(
+            missing_entry_points(&exports[..1], false),
+            vec!["execute", "query"]
+        );
+    }
+
+    #[test]
+    fn share_cache_volume_by_target_dir() {
+        assert_eq!(cache_volume("counter", None), "counter_cache");
+
+        let shared = OsStr::new("/projects/target");
+        assert_eq!(
+            cache_volume("counter", Some(shared)),
+            cache_volume("amm", Some(shared))
+        );
+        assert_ne!(
+            cache_volume("counter", Some(shared)),
+            cache_volume("counter", Some(OsStr::new("/other/target")))
+        );
+    }
+}
@@ -2,6 +2,7 @@ use crate::attrs_format;
 use crate::modules::wasm::WasmConfig;
 use crate::support::future::block;
 use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
 use crate::support::ops_response::OpResponseDisplay;
 use anyhow::anyhow;
 use anyhow::Context as _;
@@ -16,6 +17,7 @@ use anyhow::Result;
 
 use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::tx::Msg;
+use serde::Serialize;
 
 #[allow(clippy::too_many_arguments)]
 pub fn clear_admin<'a, Ctx: Context<'a, WasmConfig>>(
@@ -36,7 +38,10 @@ pub fn clear_admin<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let client = ctx
+        .client(network_info.clone())
+        .to_signing_client(signing_key, account_prefix)
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, Some(contract_name)));
 
     let state = State::load_by_network(network_info, ctx.root()?)?;
     let contract = state
@@ -71,6 +76,7 @@ pub fn clear_admin<'a, Ctx: Context<'a, WasmConfig>>(
 }
 
 #[allow(dead_code)]
+#[derive(Serialize)]
 pub struct ClearAdminResponse {
     pub contract: String,
     pub admin: String,
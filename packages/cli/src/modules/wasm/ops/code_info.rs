@@ -0,0 +1,187 @@
+use anyhow::{Context as _, Result};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{AccessConfig, AccessType, CodeInfoResponse};
+use serde::Serialize;
+
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::cosmos::Client;
+use crate::support::future::block;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::output;
+use crate::support::pagination::note_more;
+use crate::support::signer::{network_account_key, network_address};
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct CodeInfo {
+    pub code_id: u64,
+    pub creator: String,
+    /// Hex encoded sha256 of the wasm, same as `checksum` of local artifacts
+    pub checksum: String,
+    pub instantiate_permission: String,
+}
+
+fn display_permission(permission: &Option<AccessConfig>) -> String {
+    match permission {
+        None => "–".to_string(),
+        Some(config) => match AccessType::from_i32(config.permission) {
+            Some(AccessType::Everybody) => "everybody".to_string(),
+            Some(AccessType::Nobody) => "nobody".to_string(),
+            Some(AccessType::OnlyAddress) => format!("only_address | {}", config.address),
+            _ => "unspecified".to_string(),
+        },
+    }
+}
+
+impl From<CodeInfoResponse> for CodeInfo {
+    fn from(info: CodeInfoResponse) -> Self {
+        CodeInfo {
+            code_id: info.code_id,
+            creator: info.creator,
+            checksum: hex::encode(&info.data_hash),
+            instantiate_permission: display_permission(&info.instantiate_permission),
+        }
+    }
+}
+
+fn network_client<'a, Ctx: Context<'a, WasmConfig>>(ctx: &Ctx, network: &str) -> Result<Client> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+    Ok(ctx.client(network_info))
+}
+
+pub fn code_info<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    code_id: u64,
+    network: &str,
+) -> Result<CodeInfo> {
+    let client = network_client(ctx, network)?;
+    let code_info = CodeInfo::from(block(client.code_info(code_id))?);
+    code_info.log();
+
+    Ok(code_info)
+}
+
+/// Addresses of predefined accounts on `network`, accounts that can not be derived (eg. unreachable remote signer) are skipped
+fn account_addresses<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    network: &str,
+) -> Result<Vec<String>> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?;
+    let chain = ctx.chain_client();
+    Ok(global_config
+        .accounts()
+        .values()
+        .filter_map(|account| {
+            network_account_key(&chain, &global_config, account, network_info).ok()
+        })
+        .filter_map(|key| network_address(&chain, &global_config, &key, network_info).ok())
+        .map(|address| address.to_string())
+        .collect())
+}
+
+/// Codes stored on chain, up to `limit` if given, only the ones created by predefined accounts if `mine` is set.
+/// The limit applies to codes fetched before filtering by creator
+pub fn list_codes<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    mine: bool,
+    limit: Option<u64>,
+    network: &str,
+) -> Result<Vec<CodeInfo>> {
+    let client = network_client(ctx, network)?;
+    let creators = if mine {
+        Some(account_addresses(ctx, network)?)
+    } else {
+        None
+    };
+
+    let page = block(client.codes(limit))?;
+    note_more(&page);
+    let codes = page
+        .items
+        .into_iter()
+        .map(CodeInfo::from)
+        .filter(|c| match &creators {
+            Some(creators) => creators.contains(&c.creator),
+            None => true,
+        })
+        .collect::<Vec<_>>();
+
+    output::print(&codes, || {
+        if codes.is_empty() {
+            return "No codes found".to_string();
+        }
+        codes
+            .iter()
+            .map(|c| {
+                format!(
+                    "{:>6}  {}  {}  {}",
+                    c.code_id, c.creator, c.checksum, c.instantiate_permission
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    Ok(codes)
+}
+
+/// Addresses of contracts instantiated from `code_id`, up to `limit` if given
+pub fn list_contracts<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    code_id: u64,
+    limit: Option<u64>,
+    network: &str,
+) -> Result<Vec<String>> {
+    let client = network_client(ctx, network)?;
+    let page = block(client.contracts_by_code(code_id, limit))?;
+    note_more(&page);
+    let contracts = page.items;
+
+    output::print(&contracts, || {
+        if contracts.is_empty() {
+            return format!("No contracts found for code id {code_id}");
+        }
+        contracts.join("\n")
+    });
+
+    Ok(contracts)
+}
+
+impl OpResponseDisplay for CodeInfo {
+    fn headline() -> &'static str {
+        "Code info"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | code_id, creator, checksum, instantiate_permission }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_code_info() {
+        let info = CodeInfo::from(CodeInfoResponse {
+            code_id: 3,
+            creator: "osmo1creator".to_string(),
+            data_hash: vec![0xab, 0xcd],
+            instantiate_permission: Some(AccessConfig {
+                permission: AccessType::OnlyAddress as i32,
+                address: "osmo1admin".to_string(),
+            }),
+        });
+        assert_eq!(info.checksum, "abcd");
+        assert_eq!(info.instantiate_permission, "only_address | osmo1admin");
+        assert_eq!(display_permission(&None), "–");
+    }
+}
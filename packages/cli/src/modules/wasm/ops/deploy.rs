@@ -1,8 +1,13 @@
 use crate::framework::Context;
 use crate::modules::wasm::WasmConfig;
 use crate::support::coin::Coins;
-use crate::support::gas::Gas;
-use anyhow::Result;
+use crate::support::gas::{Gas, GasArgs};
+use crate::support::lockfile::{Lock, LOCK_FILE};
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::progress;
+use crate::support::wasm::WasmSource;
+use anyhow::{anyhow, bail, Context as _, Result};
+use serde::Serialize;
 
 use cosmrs::crypto::secp256k1::SigningKey;
 
@@ -16,6 +21,8 @@ pub fn deploy<'a, Ctx: Context<'a, WasmConfig>>(
     ctx: &Ctx,
     contract_name: &str,
     label: &str,
+    chain_label: Option<&str>,
+    strict: bool,
     raw: Option<&String>,
     permit_instantiate_only: &Option<String>,
     admin: Option<&String>,
@@ -27,10 +34,73 @@ pub fn deploy<'a, Ctx: Context<'a, WasmConfig>>(
     instantiate_signing_key: SigningKey,
     no_rebuild: &bool,
     no_wasm_opt: &bool,
+    locked: &bool,
 ) -> Result<InstantiateResponse> {
     if !*no_rebuild {
         build(ctx, no_wasm_opt, &false)?;
     }
+
+    let (instantiate_response, lock) = deploy_built(
+        ctx,
+        contract_name,
+        label,
+        chain_label,
+        strict,
+        raw,
+        permit_instantiate_only,
+        admin,
+        funds,
+        network,
+        timeout_height,
+        gas,
+        store_code_signing_key,
+        instantiate_signing_key,
+        no_wasm_opt,
+        locked,
+    )?;
+    if !*locked {
+        lock.merge(Lock::load(&ctx.root()?)?).save(&ctx.root()?)?;
+    }
+
+    Ok(instantiate_response)
+}
+
+/// Store and instantiate the already built contract on `network`, returns the lock of the deployment
+/// which is left for the caller to save
+#[allow(clippy::too_many_arguments)]
+fn deploy_built<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    chain_label: Option<&str>,
+    strict: bool,
+    raw: Option<&String>,
+    permit_instantiate_only: &Option<String>,
+    admin: Option<&String>,
+    funds: Coins,
+    network: &str,
+    timeout_height: &u32,
+    gas: &Gas,
+    store_code_signing_key: SigningKey,
+    instantiate_signing_key: SigningKey,
+    no_wasm_opt: &bool,
+    locked: &bool,
+) -> Result<(InstantiateResponse, Lock)> {
+    let lock = current_lock(ctx, contract_name, network, no_wasm_opt)?;
+    let existing_lock = Lock::load(&ctx.root()?)?;
+    if *locked {
+        let existing_lock = existing_lock.as_ref().with_context(|| {
+            format!("`{LOCK_FILE}` not found, deploy without `--locked` to generate it")
+        })?;
+        let diffs = existing_lock.differences(&lock);
+        if !diffs.is_empty() {
+            bail!(
+                "Deployment does not match `{LOCK_FILE}`:\n  {}",
+                diffs.join("\n  ")
+            );
+        }
+    }
+
     store_code(
         ctx,
         contract_name,
@@ -40,13 +110,17 @@ pub fn deploy<'a, Ctx: Context<'a, WasmConfig>>(
         gas,
         timeout_height,
         store_code_signing_key,
+        WasmSource::Artifact,
     )?;
-    instantiate(
+    let instantiate_response = instantiate(
         ctx,
         contract_name,
         label,
+        chain_label,
+        strict,
         raw,
         admin,
+        None,
         // deploy command is not intended to use with the gov process
         true,
         true,
@@ -55,5 +129,187 @@ pub fn deploy<'a, Ctx: Context<'a, WasmConfig>>(
         timeout_height,
         gas,
         instantiate_signing_key,
+    )?;
+
+    Ok((instantiate_response, lock))
+}
+
+fn current_lock<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    network: &str,
+    no_wasm_opt: &bool,
+) -> Result<Lock> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?;
+    let config = ctx.config()?;
+    let optimizer_image = (!*no_wasm_opt)
+        .then(|| format!("cosmwasm/workspace-optimizer:{}", config.optimizer_version));
+
+    Lock::current(
+        &ctx.root()?,
+        &config.template_repo,
+        optimizer_image,
+        no_wasm_opt,
+        contract_name,
+        (network, network_info),
     )
 }
+
+/// Deploy the same build to each of the `networks` concurrently, with gas priced by each network's config.
+/// Failure on one network does not stop the rest, results of all networks are summarized at the end.
+#[allow(clippy::too_many_arguments)]
+pub fn deploy_to_networks<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    chain_label: Option<&str>,
+    strict: bool,
+    raw: Option<&String>,
+    permit_instantiate_only: &Option<String>,
+    admin: Option<&String>,
+    funds: Option<&str>,
+    networks: &[String],
+    timeout_height: &u32,
+    gas_args: &GasArgs,
+    signing_key: impl Fn(&str) -> Result<SigningKey>,
+    no_rebuild: &bool,
+    no_wasm_opt: &bool,
+    locked: &bool,
+) -> Result<MultiDeployResponse> {
+    if !*no_rebuild {
+        build(ctx, no_wasm_opt, &false)?;
+    }
+
+    // keys are resolved one network after another before deploying, since resolving them may prompt
+    let signing_keys = networks
+        .iter()
+        .map(|network| Ok((signing_key(network)?, signing_key(network)?)))
+        .collect::<Vec<Result<_>>>();
+
+    let global_config = ctx.global_config()?;
+    let deploy_to = &|network: &String, keys: Result<(SigningKey, SigningKey)>| {
+        let (store_code_signing_key, instantiate_signing_key) = keys?;
+        let gas = Gas::from_args(
+            gas_args,
+            global_config.network_gas_price(network),
+            global_config.gas_adjustment(),
+        )?;
+        deploy_built(
+            ctx,
+            contract_name,
+            label,
+            chain_label,
+            strict,
+            raw,
+            permit_instantiate_only,
+            admin,
+            funds.try_into()?,
+            network,
+            timeout_height,
+            &gas,
+            store_code_signing_key,
+            instantiate_signing_key,
+            no_wasm_opt,
+            locked,
+        )
+    };
+
+    // spinners of concurrent deploys would draw over each other
+    progress::set_plain(true);
+    let results = crossbeam_utils::thread::scope(|scope| {
+        let handles = networks
+            .iter()
+            .zip(signing_keys)
+            .map(|(network, keys)| scope.spawn(move |_| deploy_to(network, keys)))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| Err(anyhow!("Deployment panicked")))
+            })
+            .collect::<Vec<_>>()
+    })
+    .map_err(|_| anyhow!("Deployment panicked"))?;
+
+    let mut lock: Option<Lock> = None;
+    let mut deployments = vec![];
+    for (network, result) in networks.iter().zip(results) {
+        deployments.push(match result {
+            Ok((res, network_lock)) => {
+                lock = Some(network_lock.merge(lock));
+                NetworkDeployment {
+                    network: network.clone(),
+                    code_id: Some(res.code_id),
+                    contract_address: Some(res.contract_address),
+                    error: None,
+                }
+            }
+            Err(e) => NetworkDeployment {
+                network: network.clone(),
+                code_id: None,
+                contract_address: None,
+                error: Some(format!("{e:#}")),
+            },
+        });
+    }
+    if let (false, Some(lock)) = (*locked, lock) {
+        lock.merge(Lock::load(&ctx.root()?)?).save(&ctx.root()?)?;
+    }
+
+    let response = MultiDeployResponse { deployments };
+    response.log();
+
+    let failed = response
+        .deployments
+        .iter()
+        .filter(|d| d.error.is_some())
+        .map(|d| d.network.as_str())
+        .collect::<Vec<_>>();
+    if !failed.is_empty() {
+        bail!("Deployment failed on: {}", failed.join(", "));
+    }
+
+    Ok(response)
+}
+
+#[derive(Serialize)]
+pub struct NetworkDeployment {
+    pub network: String,
+    pub code_id: Option<u64>,
+    pub contract_address: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct MultiDeployResponse {
+    pub deployments: Vec<NetworkDeployment>,
+}
+
+impl OpResponseDisplay for MultiDeployResponse {
+    fn headline() -> &'static str {
+        "Deployment summary"
+    }
+    fn attrs(&self) -> Vec<String> {
+        let last = self.deployments.len().saturating_sub(1);
+        self.deployments
+            .iter()
+            .enumerate()
+            .map(|(i, d)| {
+                let branch = if i == last { "└──" } else { "├──" };
+                match (&d.error, d.code_id, &d.contract_address) {
+                    (Some(e), _, _) => format!("    {branch} {}: failed, {e}", d.network),
+                    (None, Some(code_id), Some(address)) => {
+                        format!("    {branch} {}: code_id {code_id}, {address}", d.network)
+                    }
+                    _ => format!("    {branch} {}: –", d.network),
+                }
+            })
+            .collect()
+    }
+}
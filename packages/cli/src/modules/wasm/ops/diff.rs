@@ -0,0 +1,185 @@
+use std::fs;
+
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::future::block;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::wasm_info::{parse_wasm, WasmInfo};
+use crate::{framework::Context, support::cosmos::Client};
+use anyhow::Context as _;
+use anyhow::Result;
+use serde::Serialize;
+
+/// Load wasm from code id stored on `network` if `source` is a number, otherwise from local file
+pub fn load_wasm<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    source: &str,
+    network: &str,
+) -> Result<Vec<u8>> {
+    match source.parse::<u64>() {
+        Ok(code_id) => {
+            let global_config = ctx.global_config()?;
+            let network_info = global_config
+                .networks()
+                .get(network)
+                .with_context(|| format!("Unable to find network config: {network}"))?
+                .to_owned();
+
+            block(ctx.client(network_info).code(code_id))
+        }
+        Err(_) => fs::read(source).with_context(|| format!("Unable to read wasm from `{source}`")),
+    }
+}
+
+fn difference(a: &[String], b: &[String]) -> Vec<String> {
+    a.iter().filter(|x| !b.contains(x)).cloned().collect()
+}
+
+pub fn diff<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    a: &str,
+    b: &str,
+    network: &str,
+) -> Result<WasmDiffResponse> {
+    let wasm_a = load_wasm(ctx, a, network)?;
+    let wasm_b = load_wasm(ctx, b, network)?;
+
+    let info_a = parse_wasm(&wasm_a).with_context(|| format!("Unable to parse `{a}`"))?;
+    let info_b = parse_wasm(&wasm_b).with_context(|| format!("Unable to parse `{b}`"))?;
+
+    let response = WasmDiffResponse::new(a, b, &info_a, &info_b, wasm_a == wasm_b);
+    response.log();
+
+    Ok(response)
+}
+
+#[derive(Serialize)]
+pub struct SectionDiff {
+    pub name: String,
+    pub size_a: Option<usize>,
+    pub size_b: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct WasmDiffResponse {
+    pub a: String,
+    pub b: String,
+    pub identical: bool,
+    pub size_a: usize,
+    pub size_b: usize,
+    pub added_exports: Vec<String>,
+    pub removed_exports: Vec<String>,
+    pub added_capabilities: Vec<String>,
+    pub removed_capabilities: Vec<String>,
+    pub added_imports: Vec<String>,
+    pub removed_imports: Vec<String>,
+    pub changed_sections: Vec<SectionDiff>,
+}
+
+impl WasmDiffResponse {
+    fn new(a: &str, b: &str, info_a: &WasmInfo, info_b: &WasmInfo, identical: bool) -> Self {
+        let mut section_names = info_a
+            .sections
+            .iter()
+            .chain(info_b.sections.iter())
+            .map(|s| s.name.clone())
+            .collect::<Vec<String>>();
+        let mut seen = vec![];
+        section_names.retain(|n| {
+            let new = !seen.contains(n);
+            seen.push(n.clone());
+            new
+        });
+
+        let changed_sections = section_names
+            .into_iter()
+            .filter_map(|name| {
+                let sa = info_a.sections.iter().find(|s| s.name == name);
+                let sb = info_b.sections.iter().find(|s| s.name == name);
+                match (sa, sb) {
+                    (Some(sa), Some(sb)) if sa.content == sb.content => None,
+                    _ => Some(SectionDiff {
+                        name,
+                        size_a: sa.map(|s| s.content.len()),
+                        size_b: sb.map(|s| s.content.len()),
+                    }),
+                }
+            })
+            .collect();
+
+        WasmDiffResponse {
+            a: a.to_string(),
+            b: b.to_string(),
+            identical,
+            size_a: info_a.size,
+            size_b: info_b.size,
+            added_exports: difference(&info_b.exports, &info_a.exports),
+            removed_exports: difference(&info_a.exports, &info_b.exports),
+            added_capabilities: difference(&info_b.capabilities(), &info_a.capabilities()),
+            removed_capabilities: difference(&info_a.capabilities(), &info_b.capabilities()),
+            added_imports: difference(&info_b.imports, &info_a.imports),
+            removed_imports: difference(&info_a.imports, &info_b.imports),
+            changed_sections,
+        }
+    }
+}
+
+fn list(items: &[String]) -> String {
+    if items.is_empty() {
+        "–".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+fn size(size: Option<usize>) -> String {
+    size.map(|s| s.to_string())
+        .unwrap_or_else(|| "–".to_string())
+}
+
+impl OpResponseDisplay for WasmDiffResponse {
+    fn headline() -> &'static str {
+        "Wasm compared!"
+    }
+    fn attrs(&self) -> Vec<String> {
+        let changed_sections = self
+            .changed_sections
+            .iter()
+            .map(|s| {
+                format!(
+                    "{} ({} -> {} bytes)",
+                    s.name,
+                    size(s.size_a),
+                    size(s.size_b)
+                )
+            })
+            .collect::<Vec<String>>();
+
+        vec![
+            format!("    ├── a: {}", self.a),
+            format!("    ├── b: {}", self.b),
+            format!(
+                "    ├── identical: {}{}",
+                self.identical,
+                if self.identical {
+                    " (migration is not necessary)"
+                } else {
+                    ""
+                }
+            ),
+            format!("    ├── size: {} -> {} bytes", self.size_a, self.size_b),
+            format!("    ├── added_exports: {}", list(&self.added_exports)),
+            format!("    ├── removed_exports: {}", list(&self.removed_exports)),
+            format!(
+                "    ├── added_capabilities: {}",
+                list(&self.added_capabilities)
+            ),
+            format!(
+                "    ├── removed_capabilities: {}",
+                list(&self.removed_capabilities)
+            ),
+            format!("    ├── added_imports: {}", list(&self.added_imports)),
+            format!("    ├── removed_imports: {}", list(&self.removed_imports)),
+            format!("    └── changed_sections: {}", list(&changed_sections)),
+        ]
+    }
+}
@@ -0,0 +1,47 @@
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::future::block;
+use crate::support::output;
+use crate::support::pagination::note_more;
+use crate::support::state::State;
+use crate::support::storage::{decode_state, KeySpec};
+use crate::{framework::Context, support::cosmos::Client};
+use anyhow::Context as _;
+use anyhow::Result;
+
+pub fn dump_state<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    key_specs: &[KeySpec],
+    limit: Option<u64>,
+    network: &str,
+) -> Result<serde_json::Value> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = ctx.client(network_info.clone());
+    let state = State::load_by_network(network_info, ctx.root()?)?;
+
+    let contract = state
+        .get_ref(network, contract_name)?
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?
+        .to_owned();
+
+    block(async {
+        let page = client.all_contract_state(contract, limit).await?;
+        note_more(&page);
+        let decoded = decode_state(&page.items, key_specs);
+
+        output::print(&decoded, || {
+            serde_json::to_string_pretty(&decoded).unwrap_or_default()
+        });
+
+        Ok(decoded)
+    })
+}
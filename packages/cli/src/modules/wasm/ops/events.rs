@@ -0,0 +1,245 @@
+use crate::framework::Context;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::future::block;
+use crate::support::output::{self, output_format, OutputFormat};
+use crate::support::state::State;
+use anyhow::{anyhow, Context as _, Result};
+use console::style;
+use cosmrs::rpc::endpoint::tx;
+use cosmrs::rpc::event::EventData;
+use cosmrs::rpc::query::{EventType, Query};
+use cosmrs::rpc::{Order, SubscriptionClient, WebSocketClient};
+use cosmrs::tendermint::abci::Event;
+use futures::StreamExt;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Max number of past txs to print when not following
+const PAST_TXS_LIMIT: u8 = 20;
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct ContractEvent {
+    pub height: u64,
+    pub tx_hash: String,
+    pub r#type: String,
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Events emitted by the contract, which are `wasm` and custom `wasm-*` events
+/// with matching `_contract_address`
+pub fn contract_events(
+    contract: &str,
+    height: u64,
+    tx_hash: &str,
+    events: &[Event],
+) -> Vec<ContractEvent> {
+    events
+        .iter()
+        .filter(|e| e.type_str == "wasm" || e.type_str.starts_with("wasm-"))
+        .map(|e| ContractEvent {
+            height,
+            tx_hash: tx_hash.to_string(),
+            r#type: e.type_str.clone(),
+            attributes: e
+                .attributes
+                .iter()
+                .map(|a| (a.key.to_string(), a.value.to_string()))
+                .collect(),
+        })
+        .filter(|e| {
+            e.attributes
+                .iter()
+                .any(|(k, v)| k == "_contract_address" && v == contract)
+        })
+        .collect()
+}
+
+/// Websocket endpoint of tendermint rpc, eg. `http://localhost:26657` -> `ws://localhost:26657/websocket`
+pub fn websocket_url(rpc_endpoint: &str) -> String {
+    let endpoint = rpc_endpoint.trim_end_matches('/');
+    let endpoint = if let Some(rest) = endpoint.strip_prefix("https://") {
+        format!("wss://{rest}")
+    } else if let Some(rest) = endpoint.strip_prefix("http://") {
+        format!("ws://{rest}")
+    } else {
+        endpoint.to_string()
+    };
+    format!("{endpoint}/websocket")
+}
+
+fn print_event(event: &ContractEvent) {
+    match output_format() {
+        // one event per line, for piping into other tools
+        OutputFormat::Json => match serde_json::to_string(event) {
+            Ok(s) => println!("{s}"),
+            Err(e) => eprintln!("Unable to serialize output: {e}"),
+        },
+        OutputFormat::Yaml => output::print(event, String::new),
+        OutputFormat::Table => {
+            let attributes = event
+                .attributes
+                .iter()
+                .filter(|(k, _)| k != "_contract_address")
+                .map(|(k, v)| format!("{}={v}", style(k).dim()))
+                .collect::<Vec<String>>()
+                .join(", ");
+            println!(
+                "{} {} {}: {attributes}",
+                style(format!("#{}", event.height)).bold(),
+                style(&event.tx_hash[..event.tx_hash.len().min(12)]).dim(),
+                style(&event.r#type).cyan().bold(),
+            );
+        }
+    }
+}
+
+pub fn events<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    follow: bool,
+    network: &str,
+) -> Result<()> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
+    let contract = state
+        .get_ref(network, contract_name)?
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?
+        .to_owned();
+
+    let query = Query::from(EventType::Tx).and_eq("wasm._contract_address", contract.as_str());
+    let rpc_endpoint = network_info.rpc_endpoint().as_str();
+
+    block(async {
+        if !follow {
+            let res = ctx
+                .client(network_info.clone())
+                .tx_search(query, PAST_TXS_LIMIT, Order::Descending)
+                .await?;
+            let mut txs: Vec<tx::Response> = res.txs;
+            txs.reverse();
+            for tx in txs {
+                contract_events(
+                    &contract,
+                    tx.height.value(),
+                    &tx.hash.to_string(),
+                    &tx.tx_result.events,
+                )
+                .iter()
+                .for_each(print_event);
+            }
+            return Ok(());
+        }
+
+        let (client, driver) = WebSocketClient::new(websocket_url(rpc_endpoint).as_str())
+            .await
+            .with_context(|| format!("Unable to connect to websocket of {rpc_endpoint}"))?;
+        let driver_handle = tokio::spawn(async move { driver.run().await });
+
+        let mut subscription = client.subscribe(query).await?;
+        eprintln!(
+            "  {} events of {contract_name}:{label} ({contract}), press Ctrl-C to stop",
+            style("Following").green().bold()
+        );
+
+        loop {
+            tokio::select! {
+                next = subscription.next() => match next {
+                    Some(event) => {
+                        if let EventData::Tx { tx_result } = event?.data {
+                            let tx_hash = hex::encode_upper(Sha256::digest(&tx_result.tx));
+                            contract_events(
+                                &contract,
+                                tx_result.height as u64,
+                                &tx_hash,
+                                &tx_result.result.events,
+                            )
+                            .iter()
+                            .for_each(print_event);
+                        }
+                    }
+                    None => break,
+                },
+                _ = tokio::signal::ctrl_c() => break,
+            }
+        }
+
+        client.close()?;
+        driver_handle
+            .await?
+            .map_err(|e| anyhow!("Websocket connection failed: {e}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmrs::tendermint::abci::tag::{Key, Tag, Value};
+    use std::str::FromStr;
+
+    fn event(r#type: &str, attributes: &[(&str, &str)]) -> Event {
+        Event {
+            type_str: r#type.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(k, v)| Tag {
+                    key: Key::from_str(k).unwrap(),
+                    value: Value::from_str(v).unwrap(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn pick_events_of_contract() {
+        let events = vec![
+            event("message", &[("action", "execute")]),
+            event(
+                "wasm",
+                &[
+                    ("_contract_address", "osmo1counter"),
+                    ("method", "increment"),
+                ],
+            ),
+            event(
+                "wasm",
+                &[("_contract_address", "osmo1other"), ("method", "x")],
+            ),
+            event(
+                "wasm-counted",
+                &[("_contract_address", "osmo1counter"), ("count", "2")],
+            ),
+        ];
+
+        let picked = contract_events("osmo1counter", 5, "ABC", &events);
+        assert_eq!(
+            picked.iter().map(|e| e.r#type.as_str()).collect::<Vec<_>>(),
+            vec!["wasm", "wasm-counted"]
+        );
+        assert_eq!(
+            picked[1].attributes[1],
+            ("count".to_string(), "2".to_string())
+        );
+        assert_eq!(picked[0].height, 5);
+    }
+
+    #[test]
+    fn websocket_url_from_rpc_endpoint() {
+        assert_eq!(
+            websocket_url("http://localhost:26657"),
+            "ws://localhost:26657/websocket"
+        );
+        assert_eq!(
+            websocket_url("https://rpc.osmosis.zone/"),
+            "wss://rpc.osmosis.zone/websocket"
+        );
+    }
+}
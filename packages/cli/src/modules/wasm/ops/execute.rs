@@ -1,10 +1,15 @@
 use crate::attrs_format;
 use crate::modules::wasm::config::WasmConfig;
+use crate::support::address_book::resolve_address;
+use crate::support::authz::wrap_exec;
 use crate::support::coin::Coins;
 use crate::support::cosmos::ResponseValuePicker;
 use crate::support::future::block;
-use crate::support::gas::Gas;
+use crate::support::gas::{Gas, GasPrice};
+use crate::support::gas_ledger::GasLedger;
+use crate::support::msg::msg_bytes;
 use crate::support::ops_response::OpResponseDisplay;
+use crate::support::simulation::SimulationResponse;
 use crate::support::state::State;
 use crate::{framework::Context, support::cosmos::Client};
 use anyhow::anyhow;
@@ -15,7 +20,8 @@ use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::tx::Msg;
 use cosmrs::AccountId;
 
-use std::{fs, vec};
+use serde::Serialize;
+use std::vec;
 
 #[allow(clippy::too_many_arguments)]
 pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
@@ -24,10 +30,12 @@ pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
     label: &str,
     raw: Option<&String>,
     funds: Coins,
+    granter: Option<&String>,
     network: &str,
     timeout_height: &u32,
     gas: &Gas,
     signing_key: SigningKey,
+    simulate: bool,
 ) -> Result<ExecuteResponse> {
     let global_config = ctx.global_config()?;
     let account_prefix = global_config.account_prefix().as_str();
@@ -38,7 +46,13 @@ pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let client = ctx
+        .client(network_info.clone())
+        .to_signing_client(signing_key, account_prefix)
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, Some(contract_name)));
+    let granter = granter
+        .map(|g| resolve_address(&network_info, g))
+        .transpose()?;
     let state = State::load_by_network(network_info, ctx.root()?)?;
 
     let contract = state
@@ -48,35 +62,50 @@ pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?
         .parse::<AccountId>()
         .map_err(|e| anyhow!(e))?;
+    let contract_address = contract.to_string();
 
-    let msg_instantiate_contract = MsgExecuteContract {
-        sender: client.signer_account_id(),
+    let msg_execute_contract = MsgExecuteContract {
+        sender: granter
+            .clone()
+            .unwrap_or_else(|| client.signer_account_id()),
         contract,
-        msg: raw
-            .map(|s| s.as_bytes().to_vec())
-            .map(Ok)
-            .unwrap_or_else(|| {
-                let path = ctx
-                    .root()?
-                    .join("contracts")
-                    .join(contract_name)
-                    .join("execute-msgs")
-                    .join(format!("{label}.json"));
-                fs::read_to_string(&path)
-                    .with_context(|| format!("Unable to execute with `{}`", path.to_string_lossy()))
-                    .map(|s| s.as_bytes().to_vec())
-            })?,
+        msg: msg_bytes(
+            raw,
+            ctx.root()?
+                .join("contracts")
+                .join(contract_name)
+                .join("execute-msgs")
+                .join(format!("{label}.json")),
+            "execute",
+        )?,
         funds: funds.into(),
     };
 
+    let msg = msg_execute_contract.to_any().unwrap();
+    let msg = if granter.is_some() {
+        wrap_exec(&client.signer_account_id(), vec![msg])?
+    } else {
+        msg
+    };
+
+    if simulate {
+        let fee_denom = global_config
+            .network_gas_price(network)
+            .parse::<GasPrice>()?
+            .denom;
+        let simulation: SimulationResponse =
+            block(client.simulate(vec![msg], "", timeout_height, fee_denom.as_ref()))?.into();
+        simulation.log();
+
+        return Ok(ExecuteResponse {
+            contract_address,
+            label: label.to_string(),
+        });
+    }
+
     block(async {
         let response = client
-            .sign_and_broadcast(
-                vec![msg_instantiate_contract.to_any().unwrap()],
-                gas,
-                "",
-                timeout_height,
-            )
+            .sign_and_broadcast(vec![msg], gas, "", timeout_height)
             .await?;
 
         let contract_address = response.pick("execute", "_contract_address").to_string();
@@ -93,6 +122,7 @@ pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
 }
 
 #[allow(dead_code)]
+#[derive(Serialize)]
 pub struct ExecuteResponse {
     pub label: String,
     pub contract_address: String,
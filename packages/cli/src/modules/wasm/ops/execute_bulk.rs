@@ -0,0 +1,308 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use console::style;
+use cosmrs::cosmwasm::MsgExecuteContract;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::Msg;
+use cosmrs::AccountId;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::address_book::resolve_address;
+use crate::support::authz::wrap_exec;
+use crate::support::coin::Coins;
+use crate::support::csv;
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::{State, STATE_DIR};
+
+/// Txs are split before their msgs exceed this many bytes, keeping them under common node tx size limits
+const MAX_BATCH_BYTES: usize = 500_000;
+
+/// Progress of a bulk execution, rows before `completed_rows` are already executed
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+struct BulkProgress {
+    csv: String,
+    total_rows: usize,
+    completed_rows: usize,
+    tx_hashes: Vec<String>,
+}
+
+/// Render `template` with `{{column}}` placeholders substituted by the row's values,
+/// escaped to be placed inside json strings
+pub fn render(template: &str, row: &[(String, String)]) -> Result<serde_json::Value> {
+    let mut rendered = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let end = rest[start..]
+            .find("}}")
+            .with_context(|| format!("Unclosed placeholder in template: `{}`", &rest[start..]))?;
+        let column = rest[start + 2..start + end].trim();
+        let value = row
+            .iter()
+            .find(|(k, _)| k == column)
+            .map(|(_, v)| v)
+            .with_context(|| format!("Template placeholder `{column}` is not a csv column"))?;
+        let escaped = serde_json::to_string(value)?;
+        rendered.push_str(&rest[..start]);
+        rendered.push_str(&escaped[1..escaped.len() - 1]);
+        rest = &rest[start + end + 2..];
+    }
+    rendered.push_str(rest);
+    serde_json::from_str(&rendered)
+        .with_context(|| format!("Rendered msg is not valid json: {rendered}"))
+}
+
+/// Consecutive ranges of `sizes` with at most `batch_size` items and `max_bytes` in total, but at least one item
+fn chunk(sizes: &[usize], batch_size: usize, max_bytes: usize) -> Vec<std::ops::Range<usize>> {
+    let mut chunks = vec![];
+    let mut start = 0;
+    let mut bytes = 0;
+    for (i, size) in sizes.iter().enumerate() {
+        if i > start && (i - start >= batch_size || bytes + size > max_bytes) {
+            chunks.push(start..i);
+            start = i;
+            bytes = 0;
+        }
+        bytes += size;
+    }
+    if start < sizes.len() {
+        chunks.push(start..sizes.len());
+    }
+    chunks
+}
+
+fn progress_path(root: &Path, network: &str, contract: &str, csv: &str, template: &str) -> PathBuf {
+    let digest = hex::encode(
+        Sha256::new()
+            .chain_update(contract)
+            .chain_update(csv)
+            .chain_update(template)
+            .finalize(),
+    );
+    root.join(STATE_DIR)
+        .join("bulk")
+        .join(format!("{network}-{}.json", &digest[..16]))
+}
+
+fn save_progress(path: &Path, progress: &BulkProgress) -> Result<()> {
+    fs::create_dir_all(path.parent().expect("progress file is in bulk dir"))?;
+    fs::write(path, serde_json::to_string_pretty(progress)?)
+        .with_context(|| format!("Unable to save progress to `{}`", path.display()))
+}
+
+/// Execute one msg rendered from `template` per row of `csv_path`, `batch_size` msgs per tx,
+/// on behalf of `granter` with the msgs of each tx wrapped in one `MsgExec` if specified.
+/// Progress is recorded after every tx, so rerunning the same command resumes after the last executed row.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_bulk<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    csv_path: &Path,
+    template: &str,
+    funds: Coins,
+    granter: Option<&String>,
+    batch_size: usize,
+    dry_run: bool,
+    restart: bool,
+    network: &str,
+    timeout_height: &u32,
+    gas: &Gas,
+    signing_key: SigningKey,
+) -> Result<ExecuteBulkResponse> {
+    if batch_size == 0 {
+        bail!("batch size must be greater than 0");
+    }
+
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+    let client = ctx
+        .client(network_info.clone())
+        .to_signing_client(signing_key, global_config.account_prefix())
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, Some(contract_name)));
+    let granter = granter
+        .map(|g| resolve_address(&network_info, g))
+        .transpose()?;
+    let state = State::load_by_network(network_info, ctx.root()?)?;
+    let contract = state
+        .get_ref(network, contract_name)?
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?
+        .parse::<AccountId>()
+        .map_err(|e| anyhow!(e))?;
+
+    let content = fs::read_to_string(csv_path)
+        .with_context(|| format!("Unable to read `{}`", csv_path.display()))?;
+    let msgs = csv::parse(&content)?
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            render(template, row)
+                .and_then(|msg| Ok(serde_json::to_vec(&msg)?))
+                .with_context(|| format!("Unable to render msg of row {}", i + 1))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let progress_path = progress_path(&ctx.root()?, network, contract.as_ref(), &content, template);
+    let mut progress = match fs::read_to_string(&progress_path) {
+        Ok(saved) if !restart => serde_json::from_str(&saved)
+            .with_context(|| format!("Invalid progress file `{}`", progress_path.display()))?,
+        _ => BulkProgress {
+            csv: csv_path.to_string_lossy().to_string(),
+            total_rows: msgs.len(),
+            ..Default::default()
+        },
+    };
+    let skipped = progress.completed_rows;
+    if skipped > 0 {
+        eprintln!(
+            "  {} resuming after {skipped} of {} rows executed by a previous run",
+            style("‣").cyan(),
+            msgs.len()
+        );
+    }
+
+    let pending = &msgs[skipped.min(msgs.len())..];
+    let chunks = chunk(
+        &pending.iter().map(Vec::len).collect::<Vec<_>>(),
+        batch_size,
+        MAX_BATCH_BYTES,
+    );
+
+    let funds: Vec<cosmrs::Coin> = funds.into();
+    let mut tx_hashes = vec![];
+    if !dry_run {
+        let signer = client.signer_account_id();
+        let sender = granter.clone().unwrap_or_else(|| signer.clone());
+        block(async {
+            for (i, range) in chunks.iter().enumerate() {
+                let tx_msgs = pending[range.clone()]
+                    .iter()
+                    .map(|msg| {
+                        MsgExecuteContract {
+                            sender: sender.clone(),
+                            contract: contract.clone(),
+                            msg: msg.clone(),
+                            funds: funds.clone(),
+                        }
+                        .to_any()
+                        .map_err(|e| anyhow!(e))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let tx_msgs = if granter.is_some() {
+                    vec![wrap_exec(&signer, tx_msgs)?]
+                } else {
+                    tx_msgs
+                };
+
+                let response = client
+                    .sign_and_broadcast(tx_msgs, gas, "", timeout_height)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Bulk execution stopped at tx {} of {}, rerun the command to resume after row {}",
+                            i + 1,
+                            chunks.len(),
+                            progress.completed_rows
+                        )
+                    })?;
+
+                let hash = response.hash.to_string();
+                progress.completed_rows += range.len();
+                progress.tx_hashes.push(hash.clone());
+                tx_hashes.push(hash);
+                save_progress(&progress_path, &progress)?;
+            }
+            anyhow::Ok(())
+        })?;
+    }
+
+    let response = ExecuteBulkResponse {
+        contract_address: contract.to_string(),
+        label: label.to_string(),
+        dry_run,
+        total_rows: msgs.len(),
+        skipped_rows: skipped,
+        txs: chunks.len(),
+        tx_hashes,
+    };
+    response.log();
+    Ok(response)
+}
+
+#[derive(Serialize)]
+pub struct ExecuteBulkResponse {
+    pub label: String,
+    pub contract_address: String,
+    pub dry_run: bool,
+    pub total_rows: usize,
+    /// Rows executed by a previous run
+    pub skipped_rows: usize,
+    /// Number of txs the pending rows are (or would be) broadcasted in
+    pub txs: usize,
+    pub tx_hashes: Vec<String>,
+}
+
+impl OpResponseDisplay for ExecuteBulkResponse {
+    fn headline() -> &'static str {
+        "Bulk execution completed successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | label, contract_address, dry_run, total_rows, skipped_rows, txs }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn render_rows_into_msgs() {
+        let row = vec![
+            ("addr".to_string(), "osmo1a".to_string()),
+            ("amt".to_string(), "100".to_string()),
+        ];
+        assert_eq!(
+            render(
+                r#"{"transfer":{"recipient":"{{addr}}","amount":"{{ amt }}"}}"#,
+                &row
+            )
+            .unwrap(),
+            json!({ "transfer": { "recipient": "osmo1a", "amount": "100" } })
+        );
+        assert_eq!(
+            render(
+                r#"{"memo":"{{addr}}"}"#,
+                &[("addr".to_string(), "say \"hi\"".to_string())]
+            )
+            .unwrap(),
+            json!({ "memo": "say \"hi\"" })
+        );
+        assert!(render(r#"{"a":"{{missing}}"}"#, &row).is_err());
+        assert!(render(r#"{"a":"{{addr"}"#, &row).is_err());
+    }
+
+    #[test]
+    fn chunk_by_count_and_size() {
+        assert_eq!(chunk(&[10; 5], 2, 100), vec![0..2, 2..4, 4..5]);
+        assert_eq!(
+            chunk(&[40, 40, 40, 200, 10], 10, 100),
+            vec![0..2, 2..3, 3..4, 4..5]
+        );
+        assert!(chunk(&[], 2, 100).is_empty());
+    }
+}
@@ -0,0 +1,165 @@
+use anyhow::{Context as _, Result};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    ContractCodeHistoryEntry, ContractCodeHistoryOperationType,
+};
+use cosmrs::AccountId;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::framework::Context;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::future::block;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::State;
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct CodeHistoryEntry {
+    pub operation: String,
+    pub code_id: u64,
+    /// Height of the block including the tx, `None` for genesis
+    pub height: Option<u64>,
+    /// Instantiate or migrate msg, json if possible, otherwise base64 encoded
+    pub msg: Value,
+}
+
+impl From<ContractCodeHistoryEntry> for CodeHistoryEntry {
+    fn from(entry: ContractCodeHistoryEntry) -> Self {
+        let operation = match ContractCodeHistoryOperationType::from_i32(entry.operation) {
+            Some(ContractCodeHistoryOperationType::Init) => "init",
+            Some(ContractCodeHistoryOperationType::Migrate) => "migrate",
+            Some(ContractCodeHistoryOperationType::Genesis) => "genesis",
+            _ => "unspecified",
+        };
+        CodeHistoryEntry {
+            operation: operation.to_string(),
+            code_id: entry.code_id,
+            height: entry
+                .updated
+                .map(|p| p.block_height)
+                .filter(|height| *height > 0),
+            msg: serde_json::from_slice(&entry.msg)
+                .unwrap_or_else(|_| Value::String(base64::encode(&entry.msg))),
+        }
+    }
+}
+
+/// Address of `contract`, which is either a bech32 address or contract name with `label` in state
+pub fn resolve_contract<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract: &str,
+    label: &str,
+    network: &str,
+) -> Result<String> {
+    if contract.parse::<AccountId>().is_ok() {
+        return Ok(contract.to_string());
+    }
+
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?;
+    State::load_by_network(network_info.clone(), ctx.root()?)?
+        .get_ref(network, contract)?
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract}:{label}"))
+        .map(|a| a.to_owned())
+}
+
+/// Code history of the contract recorded on chain
+pub fn history<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract: &str,
+    label: &str,
+    network: &str,
+) -> Result<ContractHistoryResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let address = resolve_contract(ctx, contract, label, network)?;
+    let entries = block(ctx.client(network_info).contract_history(address.clone()))?
+        .into_iter()
+        .map(CodeHistoryEntry::from)
+        .collect();
+
+    let response = ContractHistoryResponse { address, entries };
+    response.log();
+
+    Ok(response)
+}
+
+#[derive(Serialize)]
+pub struct ContractHistoryResponse {
+    pub address: String,
+    pub entries: Vec<CodeHistoryEntry>,
+}
+
+impl OpResponseDisplay for ContractHistoryResponse {
+    fn headline() -> &'static str {
+        "Contract history"
+    }
+    fn attrs(&self) -> Vec<String> {
+        let mut attrs = vec![format!("    ├── address: {}", self.address)];
+        if self.entries.is_empty() {
+            attrs.push("    └── entries: –".to_string());
+            return attrs;
+        }
+
+        attrs.push("    └── entries".to_string());
+        let last = self.entries.len() - 1;
+        attrs.extend(self.entries.iter().enumerate().map(|(i, e)| {
+            let branch = if i == last { "└──" } else { "├──" };
+            let height = e
+                .height
+                .map(|h| format!(" at height {h}"))
+                .unwrap_or_default();
+            format!(
+                "          {branch} {} code_id {}{height}: {}",
+                e.operation, e.code_id, e.msg
+            )
+        }));
+        attrs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmwasm::wasm::v1::AbsoluteTxPosition;
+
+    #[test]
+    fn decode_history_entry() {
+        let entry = CodeHistoryEntry::from(ContractCodeHistoryEntry {
+            operation: ContractCodeHistoryOperationType::Migrate as i32,
+            code_id: 7,
+            updated: Some(AbsoluteTxPosition {
+                block_height: 120,
+                tx_index: 0,
+            }),
+            msg: br#"{"version":"2"}"#.to_vec(),
+        });
+        assert_eq!(
+            entry,
+            CodeHistoryEntry {
+                operation: "migrate".to_string(),
+                code_id: 7,
+                height: Some(120),
+                msg: serde_json::json!({"version": "2"}),
+            }
+        );
+
+        let genesis = CodeHistoryEntry::from(ContractCodeHistoryEntry {
+            operation: ContractCodeHistoryOperationType::Genesis as i32,
+            code_id: 1,
+            updated: None,
+            msg: vec![0xff],
+        });
+        assert_eq!(genesis.height, None);
+        assert_eq!(genesis.msg, Value::String(base64::encode([0xff])));
+    }
+}
@@ -0,0 +1,79 @@
+use anyhow::{Context as _, Result};
+use serde::Serialize;
+
+use crate::framework::Context;
+use crate::modules::state::ops::history::display_time;
+use crate::modules::wasm::config::WasmConfig;
+use crate::modules::wasm::ops::diff::load_wasm;
+use crate::support::build_metadata::BuildMetadata;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::wasm::checksum;
+
+/// Read build metadata embedded in wasm from local file or code id stored on `network`
+pub fn inspect<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    source: &str,
+    network: &str,
+) -> Result<InspectResponse> {
+    let wasm = load_wasm(ctx, source, network)?;
+    let metadata =
+        BuildMetadata::read(&wasm).with_context(|| format!("Unable to parse `{source}`"))?;
+
+    let response = InspectResponse {
+        source: source.to_string(),
+        size: wasm.len(),
+        checksum: checksum(&wasm),
+        metadata,
+    };
+    response.log();
+
+    Ok(response)
+}
+
+#[derive(Serialize)]
+pub struct InspectResponse {
+    pub source: String,
+    pub size: usize,
+    pub checksum: String,
+    pub metadata: Option<BuildMetadata>,
+}
+
+impl OpResponseDisplay for InspectResponse {
+    fn headline() -> &'static str {
+        "Wasm build metadata"
+    }
+    fn attrs(&self) -> Vec<String> {
+        let mut attrs = vec![
+            format!("    ├── source: {}", self.source),
+            format!("    ├── size: {} bytes", self.size),
+        ];
+        let metadata = match &self.metadata {
+            Some(metadata) => metadata,
+            None => {
+                attrs.push(format!("    ├── checksum: {}", self.checksum));
+                attrs.push("    └── metadata: – (not built by beaker)".to_string());
+                return attrs;
+            }
+        };
+
+        let or_dash = |v: &Option<String>| v.clone().unwrap_or_else(|| "–".to_string());
+        attrs.extend([
+            format!("    ├── checksum: {}", self.checksum),
+            format!("    ├── git_commit: {}", or_dash(&metadata.git_commit)),
+            format!(
+                "    ├── rustc_version: {}",
+                or_dash(&metadata.rustc_version)
+            ),
+            format!(
+                "    ├── optimizer_image: {}",
+                or_dash(&metadata.optimizer_image)
+            ),
+            format!(
+                "    ├── optimizer_digest: {}",
+                or_dash(&metadata.optimizer_digest)
+            ),
+            format!("    └── built_at: {}", display_time(metadata.built_at)),
+        ]);
+        attrs
+    }
+}
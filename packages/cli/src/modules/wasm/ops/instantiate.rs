@@ -4,26 +4,40 @@ use crate::support::coin::Coins;
 use crate::support::cosmos::ResponseValuePicker;
 use crate::support::future::block;
 use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
 use crate::support::hooks::use_code_id;
+use crate::support::msg::msg_bytes;
+use crate::support::notify::{notify, DeployEvent};
 use crate::support::ops_response::OpResponseDisplay;
 use crate::support::permission::compute_admin;
-use crate::support::state::State;
+use crate::support::proto::MessageExt;
+use crate::support::state::{Action, HistoryEntry, State};
 use crate::{framework::Context, support::cosmos::Client};
 use anyhow::Context as _;
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
+use console::style;
 use cosmrs::cosmwasm::MsgInstantiateContract;
 use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::tx::Msg;
+use cosmrs::Any;
 
-use std::{fs, vec};
+use serde::Serialize;
+use std::path::Path;
+use std::vec;
+
+/// Contracts of the code checked for a duplicate label, on chains that can not list contracts by creator
+const LABEL_CHECK_LIMIT: u64 = 100;
 
 #[allow(clippy::too_many_arguments)]
 pub fn instantiate<'a, Ctx: Context<'a, WasmConfig>>(
     ctx: &Ctx,
     contract_name: &str,
     label: &str,
+    chain_label: Option<&str>,
+    strict: bool,
     raw: Option<&String>,
     admin: Option<&String>,
+    salt: Option<&str>,
     no_proposal_sync: bool,
     yes: bool,
     funds: Coins,
@@ -41,7 +55,10 @@ pub fn instantiate<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let query_client = ctx.client(network_info.clone());
+    let client = query_client
+        .to_signing_client(signing_key, account_prefix)
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, Some(contract_name)));
 
     let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
 
@@ -57,36 +74,83 @@ pub fn instantiate<'a, Ctx: Context<'a, WasmConfig>>(
 
     let msg_instantiate_contract = MsgInstantiateContract {
         sender: client.signer_account_id(),
-        admin: compute_admin(admin, client.signer_account_id())?,
+        admin: compute_admin(admin, client.signer_account_id(), &network_info)?,
         code_id,
-        label: Some(label.to_string()),
-        msg: raw
-            .map(|s| s.as_bytes().to_vec())
-            .map(Ok)
-            .unwrap_or_else(|| {
-                let path = ctx
-                    .root()?
-                    .join("contracts")
-                    .join(contract_name)
-                    .join("instantiate-msgs")
-                    .join(format!("{label}.json"));
-                fs::read_to_string(&path)
-                    .with_context(|| {
-                        format!("Unable to instantiate with `{}`", path.to_string_lossy())
-                    })
-                    .map(|s| s.as_bytes().to_vec())
-            })?,
+        label: Some(match chain_label {
+            Some(chain_label) => chain_label.to_string(),
+            None if label != "default" => label.to_string(),
+            None => generate_label(&ctx.root()?, contract_name, network),
+        }),
+        msg: msg_bytes(
+            raw,
+            ctx.root()?
+                .join("contracts")
+                .join(contract_name)
+                .join("instantiate-msgs")
+                .join(format!("{label}.json")),
+            "instantiate",
+        )?,
         funds: funds.into(),
     };
 
-    block(async {
+    let msg = match salt {
+        None => msg_instantiate_contract
+            .clone()
+            .to_any()
+            .map_err(|e| anyhow!(e))?,
+        Some(salt) => {
+            let salt = hex::decode(salt.trim_start_matches("0x"))
+                .with_context(|| "Salt must be hex encoded")?;
+            block(query_client.features())?.require_instantiate2()?;
+            instantiate2_msg(msg_instantiate_contract.clone(), salt)?
+        }
+    };
+
+    let event = DeployEvent::new(
+        Action::Instantiate,
+        network,
+        contract_name,
+        Some(label),
+        client.signer_account_id().as_ref(),
+    );
+    let result = block(async {
+        let chain_label = msg_instantiate_contract.label.clone().unwrap_or_default();
+        // best-effort unless `--strict`, the check must not stop an instantiation the chain would accept
+        let message = match contracts_with_label(
+            &query_client,
+            code_id,
+            msg_instantiate_contract.sender.as_ref(),
+            &chain_label,
+        )
+        .await
+        {
+            Ok((duplicates, partial)) => {
+                if partial {
+                    eprintln!(
+                        "  {} only the first {LABEL_CHECK_LIMIT} contracts of code {code_id} are checked for label `{chain_label}`, the chain can not list contracts by creator",
+                        style("WARNING:").yellow().bold()
+                    );
+                }
+                (!duplicates.is_empty()).then(|| {
+                    format!(
+                        "Contract with label `{chain_label}` is already instantiated by the same creator: {}",
+                        duplicates.join(", ")
+                    )
+                })
+            }
+            Err(e) => Some(format!(
+                "Unable to check for contracts with label `{chain_label}`: {e:#}"
+            )),
+        };
+        if let Some(message) = message {
+            if strict {
+                bail!(message);
+            }
+            eprintln!("  {} {message}", style("WARNING:").yellow().bold());
+        }
+
         let response = client
-            .sign_and_broadcast(
-                vec![msg_instantiate_contract.to_any().unwrap()],
-                gas,
-                "",
-                timeout_height,
-            )
+            .sign_and_broadcast(vec![msg], gas, "", timeout_height)
             .await?;
 
         let contract_address = response
@@ -113,14 +177,130 @@ pub fn instantiate<'a, Ctx: Context<'a, WasmConfig>>(
             ctx.root()?,
             &|s: &State| -> State {
                 s.update_address(network, contract_name, label, &contract_address)
+                    .record_history(
+                        network,
+                        contract_name,
+                        HistoryEntry::new(Action::Instantiate, label, code_id)
+                            .with_tx_hash(&response.hash.to_string()),
+                    )
             },
         )?;
 
-        Ok(instantiate_response)
+        Ok((instantiate_response, response.hash.to_string()))
+    });
+
+    notify(
+        &ctx.config()?.notifications,
+        &event.outcome(result.as_ref().map(|(r, tx_hash)| {
+            (
+                r.code_id,
+                Some(r.contract_address.as_str()),
+                tx_hash.as_str(),
+            )
+        })),
+    );
+    result.map(|(r, _)| r)
+}
+
+/// `cosmwasm.wasm.v1.MsgInstantiateContract2`, not part of cosmos-sdk-proto yet
+#[derive(Clone, PartialEq, prost::Message)]
+struct MsgInstantiateContract2 {
+    #[prost(string, tag = "1")]
+    sender: String,
+    #[prost(string, tag = "2")]
+    admin: String,
+    #[prost(uint64, tag = "3")]
+    code_id: u64,
+    #[prost(string, tag = "4")]
+    label: String,
+    #[prost(bytes = "vec", tag = "5")]
+    msg: Vec<u8>,
+    #[prost(message, repeated, tag = "6")]
+    funds: Vec<cosmrs::proto::cosmos::base::v1beta1::Coin>,
+    #[prost(bytes = "vec", tag = "7")]
+    salt: Vec<u8>,
+    #[prost(bool, tag = "8")]
+    fix_msg: bool,
+}
+
+/// Instantiate msg with a salt, so that the contract address is predictable with `predict-address`
+fn instantiate2_msg(msg: MsgInstantiateContract, salt: Vec<u8>) -> Result<Any> {
+    let msg: cosmrs::proto::cosmwasm::wasm::v1::MsgInstantiateContract = msg.into();
+    Ok(Any {
+        type_url: "/cosmwasm.wasm.v1.MsgInstantiateContract2".to_owned(),
+        value: MsgInstantiateContract2 {
+            sender: msg.sender,
+            admin: msg.admin,
+            code_id: msg.code_id,
+            label: msg.label,
+            msg: msg.msg,
+            funds: msg.funds,
+            salt,
+            fix_msg: false,
+        }
+        .to_bytes()?,
     })
 }
 
+/// Label to be recorded on chain when neither `--label` nor `--chain-label` is specified, `<contract>-<version>-<network>`,
+/// so that instances of different versions and networks are distinguishable in explorers
+fn generate_label(root: &Path, contract_name: &str, network: &str) -> String {
+    let version = std::fs::read_to_string(
+        root.join("contracts")
+            .join(contract_name)
+            .join("Cargo.toml"),
+    )
+    .ok()
+    .and_then(|manifest| manifest.parse::<toml::Value>().ok())
+    .and_then(|manifest| {
+        manifest
+            .get("package")?
+            .get("version")?
+            .as_str()
+            .map(|v| v.to_string())
+    });
+
+    let mut parts = vec![contract_name];
+    if let Some(version) = version.as_deref() {
+        parts.push(version);
+    }
+    parts.push(network);
+    parts.join("-")
+}
+
+/// Addresses of contracts from `code_id` that are instantiated by `creator` with the same `label`,
+/// and whether only the first [`LABEL_CHECK_LIMIT`] contracts of the code were checked.
+/// Chains without `ContractsByCreator` can only be scanned through all contracts of the code
+async fn contracts_with_label(
+    client: &Client,
+    code_id: u64,
+    creator: &str,
+    label: &str,
+) -> Result<(Vec<String>, bool)> {
+    let (candidates, partial) = if client.features().await?.contracts_by_creator() {
+        (
+            client.contracts_by_creator(creator, None).await?.items,
+            false,
+        )
+    } else {
+        let page = client
+            .contracts_by_code(code_id, Some(LABEL_CHECK_LIMIT))
+            .await?;
+        (page.items, page.more)
+    };
+
+    let mut matched = vec![];
+    for address in candidates {
+        let info = client.contract_info(address.clone()).await?;
+        if info.code_id == code_id && info.creator == creator && info.label == label {
+            matched.push(address);
+        }
+    }
+    Ok((matched, partial))
+}
+
 #[allow(dead_code)]
+#[derive(Serialize)]
 pub struct InstantiateResponse {
     pub label: String,
     pub contract_address: String,
@@ -137,3 +317,26 @@ impl OpResponseDisplay for InstantiateResponse {
         attrs_format! { self | label, contract_address, code_id, creator, admin }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn generate_label_from_contract_version_and_network() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.child("contracts/counter/Cargo.toml")
+            .write_str("[package]\nname = \"counter\"\nversion = \"0.2.1\"\n")
+            .unwrap();
+
+        assert_eq!(
+            generate_label(temp.path(), "counter", "testnet"),
+            "counter-0.2.1-testnet"
+        );
+        assert_eq!(
+            generate_label(temp.path(), "missing", "local"),
+            "missing-local"
+        );
+    }
+}
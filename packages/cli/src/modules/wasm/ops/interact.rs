@@ -0,0 +1,148 @@
+use anyhow::{anyhow, bail, Context as _, Result};
+use cosmrs::cosmwasm::MsgExecuteContract;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::Msg;
+use cosmrs::AccountId;
+use serde::Serialize;
+
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::wasm::WasmConfig;
+use crate::support::coin::Coins;
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
+use crate::support::msg_builder::prompt_msg;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::prompt;
+use crate::support::schema::{find_schema, validate_msg};
+use crate::support::state::State;
+use crate::support::tx_summary::summarize_fee;
+
+/// Pick one of `items`, without asking if there is only one
+fn pick(prompt: &str, items: Vec<String>) -> Result<String> {
+    match items.as_slice() {
+        [] => bail!("Nothing to choose for: {prompt}"),
+        [item] => Ok(item.clone()),
+        _ => prompt::select(prompt, &items, 0),
+    }
+}
+
+/// Walk through picking a contract and execute msg variant from its schema, filling the msg,
+/// previewing the fee and broadcasting it
+pub fn interact<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: Option<&str>,
+    label: Option<&str>,
+    network: &str,
+    timeout_height: &u32,
+    gas: &Gas,
+    signing_key: SigningKey,
+) -> Result<InteractResponse> {
+    if !prompt::interactive() {
+        bail!("`wasm interact` requires an interactive terminal, use `wasm execute` instead");
+    }
+
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+    let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
+
+    let contract_name = match contract_name {
+        Some(name) => name.to_string(),
+        None => pick(
+            "> Contract",
+            state
+                .contracts(network)
+                .into_iter()
+                .filter(|(_, wasm_ref)| !wasm_ref.addresses().is_empty())
+                .map(|(name, _)| name)
+                .collect(),
+        )?,
+    };
+    let wasm_ref = state.get_ref(network, &contract_name)?;
+    let label = match label {
+        Some(label) => label.to_string(),
+        None => pick("> Label", wasm_ref.addresses().keys().cloned().collect())?,
+    };
+    let contract = wasm_ref
+        .addresses()
+        .get(&label)
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?
+        .parse::<AccountId>()
+        .map_err(|e| anyhow!(e))?;
+
+    let contract_dir = ctx.root()?.join("contracts").join(&contract_name);
+    let schema = find_schema(&contract_dir, "execute")?.with_context(|| {
+        format!("No execute msg schema found for {contract_name}, generate it with `cargo schema`")
+    })?;
+    let msg = prompt_msg(&schema, "execute")?;
+    let msg_bytes = serde_json::to_vec(&msg)?;
+    validate_msg(&contract_dir, "execute", &msg_bytes)?;
+
+    let funds =
+        prompt::validated_input("> Funds (eg. 1000uosmo, empty for none)", true, |input| {
+            input.parse::<Coins>().map(|_| ())
+        })?;
+    let funds = Coins::try_from(Some(funds.trim()).filter(|f| !f.is_empty()))?;
+
+    let client = ctx
+        .client(network_info)
+        .to_signing_client(signing_key, global_config.account_prefix())
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, Some(&contract_name)));
+    let msg_execute_contract = MsgExecuteContract {
+        sender: client.signer_account_id(),
+        contract,
+        msg: msg_bytes,
+        funds: funds.into(),
+    }
+    .to_any()
+    .map_err(|e| anyhow!(e))?;
+
+    block(async {
+        let fee = client
+            .preview_fee(vec![msg_execute_contract.clone()], gas, "", timeout_height)
+            .await?;
+        eprintln!();
+        eprintln!("  {contract_name}:{label} <- {msg}");
+        eprintln!("  fee: {}", summarize_fee(&fee));
+        eprintln!();
+        if !prompt::confirm("> Broadcast?")? {
+            bail!("Execute msg was not broadcasted");
+        }
+
+        let response = client
+            .sign_and_broadcast(vec![msg_execute_contract], gas, "", timeout_height)
+            .await?;
+
+        let interact_response = InteractResponse {
+            contract_name: contract_name.clone(),
+            label: label.clone(),
+            msg: msg.to_string(),
+            tx_hash: response.hash.to_string(),
+        };
+        interact_response.log();
+
+        Ok(interact_response)
+    })
+}
+
+#[derive(Serialize)]
+pub struct InteractResponse {
+    pub contract_name: String,
+    pub label: String,
+    pub msg: String,
+    pub tx_hash: String,
+}
+
+impl OpResponseDisplay for InteractResponse {
+    fn headline() -> &'static str {
+        "Contract executed successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | contract_name, label, msg, tx_hash }
+    }
+}
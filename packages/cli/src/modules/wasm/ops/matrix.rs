@@ -0,0 +1,216 @@
+use std::{env, fs, path::Path, process::Command};
+
+use anyhow::{bail, Context as _, Result};
+use serde::Serialize;
+
+use crate::framework::Context;
+use crate::modules::wasm::config::{BuildVariant, WasmConfig};
+use crate::modules::wasm::ops::build::{
+    check_entry_points, embed_build_metadata, ensure_wasm_target, expects_migration,
+    optimizer_image,
+};
+use crate::support::command::run_command;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::progress::Progress;
+use crate::support::state::STATE_DIR;
+use crate::support::wasm::{checksum, wasm_dir};
+
+/// Artifact name of `contract` built with `variant`
+fn artifact_name(contract: &str, variant: &BuildVariant) -> String {
+    format!("{}-{}", contract.replace('-', "_"), variant.suffix)
+}
+
+fn cargo_build_args(contract: &str, variant: &BuildVariant) -> Vec<String> {
+    let mut args = [
+        "build",
+        "--lib",
+        "--release",
+        "--target",
+        "wasm32-unknown-unknown",
+        "--package",
+        contract,
+    ]
+    .map(String::from)
+    .to_vec();
+    if !variant.default_features {
+        args.push("--no-default-features".to_string());
+    }
+    if !variant.features.is_empty() {
+        args.extend(["--features".to_string(), variant.features.join(",")]);
+    }
+    args
+}
+
+/// Build every feature combination configured in `build_matrix`, each one into its own suffixed artifact.
+/// Unless `no_wasm_opt` is set, variants are optimized with `wasm-opt` from the rust-optimizer image.
+pub fn build_matrix<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    no_wasm_opt: &bool,
+    aarch64: &bool,
+) -> Result<MatrixBuildResponse> {
+    let root = ctx.root()?;
+    let config = ctx.config()?;
+    if config.build_matrix.is_empty() {
+        bail!("No build matrix configured, add variants to `wasm.build_matrix` in the config");
+    }
+
+    env::set_current_dir(&root)?;
+    ensure_wasm_target()?;
+
+    let staging_dir = root.join(STATE_DIR).join("matrix");
+    let artifacts_dir = wasm_dir(&root, no_wasm_opt);
+    fs::create_dir_all(&staging_dir)?;
+    fs::create_dir_all(&artifacts_dir)?;
+
+    let mut artifacts = vec![];
+    for (contract, variants) in &config.build_matrix {
+        for variant in variants {
+            let name = artifact_name(contract, variant);
+            Progress::stream(&format!("Building `{name}`")).end_with(run_command(
+                Command::new("cargo")
+                    .env("RUSTFLAGS", "-C link-arg=-s")
+                    .args(cargo_build_args(contract, variant)),
+            ))?;
+
+            // each variant overwrites the same cargo output, so it is moved aside before the next build
+            let built = wasm_dir(&root, &true).join(format!("{}.wasm", contract.replace('-', "_")));
+            let staged = staging_dir.join(format!("{name}.wasm"));
+            fs::copy(&built, &staged)
+                .with_context(|| format!("Unable to find built wasm `{}`", built.display()))?;
+
+            let artifact = artifacts_dir.join(format!("{name}.wasm"));
+            if *no_wasm_opt {
+                fs::rename(&staged, &artifact)?;
+            } else {
+                optimize(&root, &config.optimizer_version, aarch64, &name)?;
+            }
+
+            check_entry_points(&artifact, expects_migration(&config))?;
+            let wasm = fs::read(&artifact)?;
+            artifacts.push(MatrixArtifact {
+                contract: contract.clone(),
+                suffix: variant.suffix.clone(),
+                features: variant.features.clone(),
+                path: artifact.to_string_lossy().to_string(),
+                size: wasm.len() as u64,
+                checksum: checksum(&wasm),
+            });
+        }
+    }
+
+    if !*no_wasm_opt {
+        let paths = artifacts.iter().map(|a| a.path.clone()).collect::<Vec<_>>();
+        embed_build_metadata(
+            &root,
+            &paths,
+            optimizer_image(&config.optimizer_version, aarch64),
+        )?;
+        for artifact in artifacts.iter_mut() {
+            let wasm = fs::read(&artifact.path)?;
+            artifact.size = wasm.len() as u64;
+            artifact.checksum = checksum(&wasm);
+        }
+    }
+
+    let response = MatrixBuildResponse { artifacts };
+    response.log();
+
+    Ok(response)
+}
+
+/// Optimize staged `<name>.wasm` into `artifacts/<name>.wasm` with `wasm-opt` shipped in the optimizer image
+fn optimize(root: &Path, optimizer_version: &str, aarch64: &bool, name: &str) -> Result<()> {
+    Progress::stream(&format!("Optimizing `{name}`")).end_with(run_command(
+        Command::new("docker").args([
+            "run",
+            "--rm",
+            "-v",
+            &format!("{}:/code", root.to_string_lossy()),
+            "--entrypoint",
+            "wasm-opt",
+            &optimizer_image(optimizer_version, aarch64),
+            "-Os",
+            &format!("/code/{STATE_DIR}/matrix/{name}.wasm"),
+            "-o",
+            &format!("/code/artifacts/{name}.wasm"),
+        ]),
+    ))
+}
+
+#[derive(Serialize)]
+pub struct MatrixArtifact {
+    pub contract: String,
+    pub suffix: String,
+    pub features: Vec<String>,
+    pub path: String,
+    pub size: u64,
+    pub checksum: String,
+}
+
+#[derive(Serialize)]
+pub struct MatrixBuildResponse {
+    pub artifacts: Vec<MatrixArtifact>,
+}
+
+impl OpResponseDisplay for MatrixBuildResponse {
+    fn headline() -> &'static str {
+        "Build matrix built successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        let last = self.artifacts.len().saturating_sub(1);
+        self.artifacts
+            .iter()
+            .enumerate()
+            .map(|(i, a)| {
+                let branch = if i == last { "└──" } else { "├──" };
+                format!(
+                    "    {branch} {}-{} [{}]: {} bytes, {}",
+                    a.contract,
+                    a.suffix,
+                    a.features.join(", "),
+                    a.size,
+                    a.checksum
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(suffix: &str, features: &[&str], default_features: bool) -> BuildVariant {
+        BuildVariant {
+            suffix: suffix.to_string(),
+            features: features.iter().map(|f| f.to_string()).collect(),
+            default_features,
+        }
+    }
+
+    #[test]
+    fn name_artifact_with_suffix() {
+        assert_eq!(
+            artifact_name("cw-counter", &variant("juno", &[], true)),
+            "cw_counter-juno"
+        );
+    }
+
+    #[test]
+    fn pass_variant_features_to_cargo() {
+        assert_eq!(
+            cargo_build_args("counter", &variant("lib", &["library", "juno"], false))[5..],
+            [
+                "--package",
+                "counter",
+                "--no-default-features",
+                "--features",
+                "library,juno"
+            ]
+        );
+        assert_eq!(
+            cargo_build_args("counter", &variant("default", &[], true))[5..],
+            ["--package", "counter"]
+        );
+    }
+}
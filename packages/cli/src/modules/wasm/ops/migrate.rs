@@ -1,20 +1,26 @@
 use crate::attrs_format;
 use crate::modules::wasm::config::WasmConfig;
+use crate::modules::wasm::ops::migrate_check::{check_migration, MigrateCheckResponse, RiskLevel};
 use crate::support::cosmos::ResponseValuePicker;
 use crate::support::future::block;
-use crate::support::gas::Gas;
+use crate::support::gas::{Gas, GasPrice};
+use crate::support::gas_ledger::GasLedger;
 use crate::support::hooks::use_code_id;
+use crate::support::msg::msg_bytes;
+use crate::support::notify::{notify, DeployEvent};
 use crate::support::ops_response::OpResponseDisplay;
-use crate::support::state::State;
+use crate::support::output;
+use crate::support::state::{Action, HistoryEntry, State};
 use crate::{framework::Context, support::cosmos::Client};
 use anyhow::anyhow;
 use anyhow::Context as _;
 use anyhow::Result;
+use console::style;
 use cosmrs::cosmwasm::MsgMigrateContract;
 use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::tx::Msg;
 use cosmrs::AccountId;
-use std::fs;
+use serde::Serialize;
 
 #[allow(clippy::too_many_arguments)]
 pub fn migrate<'a, Ctx: Context<'a, WasmConfig>>(
@@ -28,6 +34,7 @@ pub fn migrate<'a, Ctx: Context<'a, WasmConfig>>(
     timeout_height: &u32,
     gas: &Gas,
     signing_key: SigningKey,
+    dry_run: bool,
 ) -> Result<MigrateResponse> {
     let global_config = ctx.global_config()?;
     let account_prefix = global_config.account_prefix().as_str();
@@ -38,9 +45,13 @@ pub fn migrate<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let client = ctx
+        .client(network_info.clone())
+        .to_signing_client(signing_key, account_prefix)
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, Some(contract_name)));
 
     let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
+    let wasm_ref = state.get_ref(network, contract_name)?;
     let code_id = use_code_id(
         ctx,
         network,
@@ -51,8 +62,7 @@ pub fn migrate<'a, Ctx: Context<'a, WasmConfig>>(
         yes,
     )?;
 
-    let contract = state
-        .get_ref(network, contract_name)?
+    let contract = wasm_ref
         .addresses()
         .get(label)
         .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?
@@ -63,23 +73,53 @@ pub fn migrate<'a, Ctx: Context<'a, WasmConfig>>(
         sender: client.signer_account_id(),
         contract,
         code_id,
-        msg: raw
-            .map(|s| s.as_bytes().to_vec())
-            .map(Ok)
-            .unwrap_or_else(|| {
-                let path = ctx
-                    .root()?
-                    .join("contracts")
-                    .join(contract_name)
-                    .join("migrate-msgs")
-                    .join(format!("{label}.json"));
-                fs::read_to_string(&path)
-                    .with_context(|| format!("Unable to migrate with `{}`", path.to_string_lossy()))
-                    .map(|s| s.as_bytes().to_vec())
-            })?,
+        msg: msg_bytes(
+            raw,
+            ctx.root()?
+                .join("contracts")
+                .join(contract_name)
+                .join("migrate-msgs")
+                .join(format!("{label}.json")),
+            "migrate",
+        )?,
     };
 
-    block(async {
+    if dry_run {
+        let fee_denom = global_config
+            .network_gas_price(network)
+            .parse::<GasPrice>()?
+            .denom;
+        let check = block(check_migration(
+            &ctx.client(network_info),
+            &client,
+            &ctx.root()?,
+            contract_name,
+            label,
+            &wasm_ref,
+            &msg_migrate_contract.contract.to_string(),
+            code_id,
+            msg_migrate_contract.to_any().unwrap(),
+            timeout_height,
+            fee_denom.as_ref(),
+        ))?;
+        print_check(&check);
+
+        return Ok(MigrateResponse {
+            code_id,
+            contract_address: msg_migrate_contract.contract.to_string(),
+            label: label.to_string(),
+            creator: msg_migrate_contract.sender.to_string(),
+        });
+    }
+
+    let event = DeployEvent::new(
+        Action::Migrate,
+        network,
+        contract_name,
+        Some(label),
+        client.signer_account_id().as_ref(),
+    );
+    let result = block(async {
         let response = client
             .sign_and_broadcast(
                 vec![msg_migrate_contract.to_any().unwrap()],
@@ -90,10 +130,10 @@ pub fn migrate<'a, Ctx: Context<'a, WasmConfig>>(
             .await?;
 
         let contract_address = response.pick("migrate", "_contract_address").to_string();
-        let code_id = response.pick("migrate", "code_id").to_string();
+        let code_id = response.pick("migrate", "code_id").to_string().parse()?;
 
         let migrate_response = MigrateResponse {
-            code_id: code_id.parse()?,
+            code_id,
             contract_address: contract_address.clone(),
             label: label.to_string(),
             creator: msg_migrate_contract.sender.to_string(),
@@ -106,14 +146,56 @@ pub fn migrate<'a, Ctx: Context<'a, WasmConfig>>(
             ctx.root()?,
             &|s: &State| -> State {
                 s.update_address(network, contract_name, label, &contract_address)
+                    .record_history(
+                        network,
+                        contract_name,
+                        HistoryEntry::new(Action::Migrate, label, code_id)
+                            .with_tx_hash(&response.hash.to_string()),
+                    )
             },
         )?;
 
-        Ok(migrate_response)
-    })
+        Ok((migrate_response, response.hash.to_string()))
+    });
+
+    notify(
+        &ctx.config()?.notifications,
+        &event.outcome(result.as_ref().map(|(r, tx_hash)| {
+            (
+                r.code_id,
+                Some(r.contract_address.as_str()),
+                tx_hash.as_str(),
+            )
+        })),
+    );
+    result.map(|(r, _)| r)
+}
+
+fn print_check(check: &MigrateCheckResponse) {
+    output::print(check, || {
+        let mut lines = vec![format!(
+            "Migration of {}:{} from code id {} to {} (dry run, nothing is broadcasted)",
+            check.contract, check.label, check.from_code_id, check.to_code_id
+        )];
+        if let Some(simulation) = &check.simulation {
+            lines.push(format!("  simulated gas used: {}", simulation.gas_used));
+        }
+        if check.risks.is_empty() {
+            lines.push("  no breaking-change risks found".to_string());
+        }
+        lines.extend(check.risks.iter().map(|r| {
+            let level = match r.level {
+                RiskLevel::Breaking => style("BREAKING").red().bold(),
+                RiskLevel::Warning => style("WARNING").yellow().bold(),
+            };
+            format!("  {level}: {}", r.message)
+        }));
+        lines.join("\n")
+    });
 }
 
 #[allow(dead_code)]
+#[derive(Serialize)]
 pub struct MigrateResponse {
     pub label: String,
     pub contract_address: String,
@@ -0,0 +1,223 @@
+use crate::framework::Context;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
+use crate::support::msg::to_json_bytes;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::{Action, HistoryEntry, State};
+use anyhow::{anyhow, bail, Context as _, Result};
+use config::Map;
+use console::style;
+use cosmrs::cosmwasm::MsgMigrateContract;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::Msg;
+use cosmrs::AccountId;
+use serde::Serialize;
+
+/// Migrate every contract instantiated from `from_code_id` that the signer is admin of to `to_code_id`,
+/// `batch_size` migrations per transaction. With `dry_run`, only list the contracts that would be migrated.
+/// Migrations of contracts recorded in state are appended to their history as each transaction is sent.
+#[allow(clippy::too_many_arguments)]
+pub fn migrate_all<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    from_code_id: u64,
+    to_code_id: u64,
+    raw: Option<&String>,
+    batch_size: usize,
+    dry_run: bool,
+    network: &str,
+    timeout_height: &u32,
+    gas: &Gas,
+    signing_key: SigningKey,
+) -> Result<MigrateAllResponse> {
+    if batch_size == 0 {
+        bail!("batch size must be greater than 0");
+    }
+
+    let global_config = ctx.global_config()?;
+    let account_prefix = global_config.account_prefix().as_str();
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    // `contract:label` recorded in state by address
+    let recorded = State::load_by_network(network_info.clone(), ctx.root()?)
+        .map(|state| recorded_labels(&state, network))
+        .unwrap_or_default();
+
+    let query_client = ctx.client(network_info.clone());
+    let client = query_client
+        .to_signing_client(signing_key, account_prefix)
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, None));
+    let signer = client.signer_account_id();
+
+    // contracts without migrate msg specified are migrated with empty msg
+    let msg = match raw {
+        Some(raw) => to_json_bytes(raw).with_context(|| "Unable to migrate with given msg")?,
+        None => b"{}".to_vec(),
+    };
+
+    block(async {
+        let contracts = query_client
+            .contracts_by_code(from_code_id, None)
+            .await?
+            .items;
+        let total = contracts.len();
+
+        let mut administered = vec![];
+        for address in contracts {
+            let info = query_client.contract_info(address.clone()).await?;
+            if info.admin == signer.as_ref() {
+                administered.push(address);
+            }
+        }
+        let skipped = total - administered.len();
+
+        let mut tx_hashes: Vec<String> = vec![];
+        if !dry_run {
+            let batches = (administered.len() + batch_size - 1) / batch_size;
+            for (i, batch) in administered.chunks(batch_size).enumerate() {
+                let msgs = batch
+                    .iter()
+                    .map(|address| {
+                        MsgMigrateContract {
+                            sender: signer.clone(),
+                            contract: address.parse::<AccountId>().map_err(|e| anyhow!(e))?,
+                            code_id: to_code_id,
+                            msg: msg.clone(),
+                        }
+                        .to_any()
+                        .map_err(|e| anyhow!(e))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                let response = client
+                    .sign_and_broadcast(msgs, gas, "", timeout_height)
+                    .await
+                    .with_context(|| {
+                        if tx_hashes.is_empty() {
+                            format!("Batch 1/{batches} failed, nothing is migrated")
+                        } else {
+                            format!(
+                                "Batch {}/{batches} failed, earlier batches are migrated by {}",
+                                i + 1,
+                                tx_hashes.join(", ")
+                            )
+                        }
+                    })?;
+                let tx_hash = response.hash.to_string();
+                eprintln!(
+                    "  {} batch {}/{batches}: migrated {} contracts, tx {tx_hash}",
+                    style("‣").cyan(),
+                    i + 1,
+                    batch.len()
+                );
+
+                State::update_state_file(
+                    network_info.network_variant(),
+                    ctx.root()?,
+                    &|s: &State| -> State {
+                        batch
+                            .iter()
+                            .filter_map(|address| recorded.get(address))
+                            .fold(s.clone(), |s, (contract, label)| {
+                                s.record_history(
+                                    network,
+                                    contract,
+                                    HistoryEntry::new(Action::Migrate, label, to_code_id)
+                                        .with_tx_hash(&tx_hash),
+                                )
+                            })
+                    },
+                )?;
+                tx_hashes.push(tx_hash);
+            }
+        }
+
+        let migrate_all_response = MigrateAllResponse {
+            from_code_id,
+            to_code_id,
+            dry_run,
+            contracts: administered,
+            skipped,
+            tx_hashes,
+        };
+        migrate_all_response.log();
+
+        Ok(migrate_all_response)
+    })
+}
+
+/// `(contract, label)` of every address recorded in `state` on `network`
+fn recorded_labels(state: &State, network: &str) -> Map<String, (String, String)> {
+    state
+        .contracts(network)
+        .into_iter()
+        .flat_map(|(contract, wasm_ref)| {
+            wasm_ref
+                .addresses()
+                .iter()
+                .map(|(label, address)| (address.clone(), (contract.clone(), label.clone())))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+pub struct MigrateAllResponse {
+    pub from_code_id: u64,
+    pub to_code_id: u64,
+    pub dry_run: bool,
+    /// Contracts migrated, or to be migrated on dry run
+    pub contracts: Vec<String>,
+    /// Number of contracts from `from_code_id` not administered by signer
+    pub skipped: usize,
+    pub tx_hashes: Vec<String>,
+}
+
+impl OpResponseDisplay for MigrateAllResponse {
+    fn headline() -> &'static str {
+        "Contracts migrated successfully!! 🎉 "
+    }
+    fn display_format(&self) -> String {
+        let headline = if self.dry_run {
+            "Contracts to migrate (dry run, nothing is broadcasted)"
+        } else {
+            Self::headline()
+        };
+        [
+            vec!["".to_string(), format!("  {headline}"), "    +".to_string()],
+            self.attrs(),
+            vec!["".to_string()],
+        ]
+        .concat()
+        .join("\n")
+    }
+    fn attrs(&self) -> Vec<String> {
+        let mut attrs = vec![];
+        attrs.push(format!(
+            "    ├── code_id: {} -> {}",
+            self.from_code_id, self.to_code_id
+        ));
+        attrs.push(format!("    ├── skipped (not admin): {}", self.skipped));
+        if !self.tx_hashes.is_empty() {
+            attrs.push(format!("    ├── tx_hashes: {}", self.tx_hashes.join(", ")));
+        }
+        if self.contracts.is_empty() {
+            attrs.push("    └── contracts: –".to_string());
+            return attrs;
+        }
+
+        attrs.push(format!("    └── contracts ({})", self.contracts.len()));
+        let last = self.contracts.len() - 1;
+        attrs.extend(self.contracts.iter().enumerate().map(|(i, c)| {
+            let branch = if i == last { "└──" } else { "├──" };
+            format!("          {branch} {c}")
+        }));
+        attrs
+    }
+}
@@ -0,0 +1,290 @@
+use std::path::Path;
+
+use anyhow::Result;
+use cosmrs::Any;
+use serde::Serialize;
+
+use crate::modules::wasm::ops::version::{Cw2ContractVersion, CW2_CONTRACT_INFO_KEY};
+use crate::support::cosmos::{Client, SigningClient};
+use crate::support::git;
+use crate::support::schema::{find_schema, find_schema_with, msg_variants};
+use crate::support::simulation::SimulationResponse;
+use crate::support::state::{Action, WasmRef};
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskLevel {
+    /// Migration can't succeed or existing clients will break
+    Breaking,
+    /// Worth a look before migrating
+    Warning,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct Risk {
+    pub level: RiskLevel,
+    pub message: String,
+}
+
+impl Risk {
+    pub fn breaking(message: String) -> Self {
+        Risk {
+            level: RiskLevel::Breaking,
+            message,
+        }
+    }
+
+    pub fn warning(message: String) -> Self {
+        Risk {
+            level: RiskLevel::Warning,
+            message,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct MigrateCheckResponse {
+    pub contract: String,
+    pub label: String,
+    pub contract_address: String,
+    pub from_code_id: u64,
+    pub to_code_id: u64,
+    /// cw2 contract info currently stored by the contract
+    pub cw2: Option<Cw2ContractVersion>,
+    /// Package name and version of the contract in the workspace
+    pub package: Option<Cw2ContractVersion>,
+    pub simulation: Option<SimulationResponse>,
+    pub risks: Vec<Risk>,
+}
+
+/// Compare the contract currently on chain with the code it is about to migrate to and
+/// simulate `MsgMigrateContract`, nothing is broadcasted.
+#[allow(clippy::too_many_arguments)]
+pub async fn check_migration(
+    client: &Client,
+    signing_client: &SigningClient,
+    root: &Path,
+    contract_name: &str,
+    label: &str,
+    wasm_ref: &WasmRef,
+    contract_address: &str,
+    to_code_id: u64,
+    msg: Any,
+    timeout_height: &u32,
+    fee_denom: &str,
+) -> Result<MigrateCheckResponse> {
+    let contract_dir = root.join("contracts").join(contract_name);
+    let mut risks = vec![];
+
+    let from_code_id = client
+        .contract_info(contract_address.to_string())
+        .await?
+        .code_id;
+
+    if from_code_id == to_code_id {
+        risks.push(Risk::warning(format!(
+            "contract is already running code id {to_code_id}"
+        )));
+    }
+
+    match source_commit(wasm_ref, from_code_id) {
+        Some(commit) => {
+            for kind in ["query", "execute"] {
+                let old = find_schema_with(contract_name, kind, |path| {
+                    Ok(git::show_file(&contract_dir, &commit, path))
+                })?;
+                let new = find_schema(&contract_dir, kind)?;
+                match (old, new) {
+                    (Some(old), Some(new)) => {
+                        let removed = removed_variants(&old, &new);
+                        if !removed.is_empty() {
+                            risks.push(Risk::breaking(format!(
+                                "{kind} msgs removed: {}",
+                                removed.join(", ")
+                            )));
+                        }
+                    }
+                    _ => risks.push(Risk::warning(format!(
+                        "{kind} schema not found at {commit} or in the workspace, run `cargo schema` to compare"
+                    ))),
+                }
+            }
+        }
+        None => risks.push(Risk::warning(format!(
+            "source commit of code id {from_code_id} is not recorded in state, schemas are not compared"
+        ))),
+    }
+
+    let cw2 = client
+        .query_raw(contract_address.to_string(), CW2_CONTRACT_INFO_KEY.to_vec())
+        .await?
+        .and_then(|raw| serde_json::from_slice::<Cw2ContractVersion>(&raw).ok());
+    let package = package_version(&contract_dir);
+    risks.extend(cw2_risks(cw2.as_ref(), package.as_ref()));
+
+    let simulation = match signing_client
+        .simulate(vec![msg], "", timeout_height, fee_denom)
+        .await
+    {
+        Ok(res) => Some(SimulationResponse::from(res)),
+        Err(e) => {
+            risks.push(Risk::breaking(format!(
+                "migration simulation failed: {e:#}"
+            )));
+            None
+        }
+    };
+
+    Ok(MigrateCheckResponse {
+        contract: contract_name.to_string(),
+        label: label.to_string(),
+        contract_address: contract_address.to_string(),
+        from_code_id,
+        to_code_id,
+        cw2,
+        package,
+        simulation,
+        risks,
+    })
+}
+
+/// Commit the wasm of `code_id` was built from, as recorded in state when it was stored.
+/// Schema of the code on chain is taken from there.
+pub fn source_commit(wasm_ref: &WasmRef, code_id: u64) -> Option<String> {
+    wasm_ref
+        .history()
+        .iter()
+        .rev()
+        .find(|e| matches!(e.action(), Action::Store) && *e.code_id() == code_id)
+        .and_then(|e| e.git_commit().clone())
+        .map(|c| c.trim_end_matches("-dirty").to_string())
+}
+
+/// Msg variants of `old` schema that are no longer accepted by `new`
+pub fn removed_variants(old: &serde_json::Value, new: &serde_json::Value) -> Vec<String> {
+    let new = msg_variants(new);
+    msg_variants(old)
+        .into_iter()
+        .filter(|v| !new.contains(v))
+        .collect()
+}
+
+/// `<package name>` and version from the contract's Cargo.toml, following the `crates.io:<name>`
+/// convention used for cw2 contract names
+pub fn package_version(contract_dir: &Path) -> Option<Cw2ContractVersion> {
+    let manifest = std::fs::read_to_string(contract_dir.join("Cargo.toml"))
+        .ok()?
+        .parse::<toml::Value>()
+        .ok()?;
+    let package = manifest.get("package")?;
+    Some(Cw2ContractVersion {
+        contract: format!("crates.io:{}", package.get("name")?.as_str()?),
+        version: package.get("version")?.as_str()?.to_string(),
+    })
+}
+
+/// Numeric `major.minor.patch`, pre-release and build metadata are ignored
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.split(|c| c == '-' || c == '+').next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+    Some((parts.next()??, parts.next()??, parts.next()??))
+}
+
+pub fn cw2_risks(
+    cw2: Option<&Cw2ContractVersion>,
+    package: Option<&Cw2ContractVersion>,
+) -> Vec<Risk> {
+    let (cw2, package) = match (cw2, package) {
+        (Some(cw2), Some(package)) => (cw2, package),
+        (None, _) => {
+            return vec![Risk::warning(
+                "contract has no cw2 contract info, state shape can't be checked".to_string(),
+            )]
+        }
+        (_, None) => return vec![],
+    };
+
+    let mut risks = vec![];
+    if cw2.contract != package.contract {
+        risks.push(Risk::breaking(format!(
+            "contract on chain is `{}` but the workspace builds `{}`",
+            cw2.contract, package.contract
+        )));
+    }
+    match (parse_version(&cw2.version), parse_version(&package.version)) {
+        (Some(on_chain), Some(new)) if new < on_chain => risks.push(Risk::breaking(format!(
+            "version downgrade from {} to {}",
+            cw2.version, package.version
+        ))),
+        (Some(on_chain), Some(new)) if new == on_chain => risks.push(Risk::warning(format!(
+            "version {} is not bumped, state written by the old code is assumed unchanged",
+            cw2.version
+        ))),
+        (Some((major, ..)), Some((new_major, ..))) if new_major > major => {
+            risks.push(Risk::warning(format!(
+                "major version bump from {} to {}, make sure the migrate entrypoint transforms state",
+                cw2.version, package.version
+            )))
+        }
+        _ => {}
+    }
+    risks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cw2(contract: &str, version: &str) -> Cw2ContractVersion {
+        Cw2ContractVersion {
+            contract: contract.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn detect_removed_variants() {
+        let old = json!({ "oneOf": [
+            { "type": "object", "required": ["get_count"] },
+            { "type": "object", "required": ["get_owner"] }
+        ]});
+        let new = json!({ "oneOf": [
+            { "type": "object", "required": ["get_count"] },
+            { "type": "object", "required": ["get_config"] }
+        ]});
+        assert_eq!(removed_variants(&old, &new), vec!["get_owner"]);
+        assert!(removed_variants(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn compare_cw2_versions() {
+        let levels = |cw2: Option<&Cw2ContractVersion>, package| {
+            cw2_risks(cw2, Some(&package))
+                .into_iter()
+                .map(|r| r.level)
+                .collect::<Vec<_>>()
+        };
+        let on_chain = cw2("crates.io:counter", "0.2.0");
+
+        assert!(levels(Some(&on_chain), cw2("crates.io:counter", "0.2.1")).is_empty());
+        assert_eq!(
+            levels(Some(&on_chain), cw2("crates.io:counter", "0.2.0")),
+            vec![RiskLevel::Warning]
+        );
+        assert_eq!(
+            levels(Some(&on_chain), cw2("crates.io:counter", "0.1.9")),
+            vec![RiskLevel::Breaking]
+        );
+        assert_eq!(
+            levels(Some(&on_chain), cw2("crates.io:other", "0.3.0")),
+            vec![RiskLevel::Breaking]
+        );
+        assert_eq!(
+            levels(None, cw2("crates.io:counter", "0.2.0")),
+            vec![RiskLevel::Warning]
+        );
+        assert_eq!(parse_version("1.2.3-rc.1"), Some((1, 2, 3)));
+        assert_eq!(parse_version("1.2"), None);
+    }
+}
@@ -0,0 +1,417 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use console::style;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::framework::Context;
+use crate::modules::wasm::config::WasmConfig;
+use crate::modules::wasm::ops::diff::load_wasm;
+use crate::modules::wasm::ops::migrate_check::{
+    cw2_risks, package_version, removed_variants, source_commit, Risk, RiskLevel,
+};
+use crate::modules::wasm::ops::version::{Cw2ContractVersion, CW2_CONTRACT_INFO_KEY};
+use crate::support::future::block;
+use crate::support::git;
+use crate::support::output;
+use crate::support::schema::{find_schema, find_schema_with};
+use crate::support::state::State;
+use crate::support::wasm::{checksum, wasm_dir};
+use crate::support::wasm_info::parse_wasm;
+
+/// Msg variants added and removed between the schema of the code on chain and the new one
+#[derive(Serialize, Debug, Default, PartialEq, Eq)]
+pub struct VariantDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct MigratePlanResponse {
+    pub contract: String,
+    pub label: String,
+    pub contract_address: String,
+    pub from_code_id: u64,
+    /// Code id or wasm file migrated to
+    pub to: String,
+    pub to_checksum: String,
+    /// Whether the new wasm exports the `migrate` entry point
+    pub migrate_entrypoint: bool,
+    /// `None` if either schema is unavailable
+    pub execute: Option<VariantDiff>,
+    pub query: Option<VariantDiff>,
+    /// cw2 contract info currently stored by the contract
+    pub cw2: Option<Cw2ContractVersion>,
+    /// Package name and version of the contract in the workspace
+    pub package: Option<Cw2ContractVersion>,
+    pub risks: Vec<Risk>,
+    /// Migrate msg stub, `None` if a migrate msg already exists and was left untouched
+    pub migrate_msg_stub: Option<String>,
+    pub checklist: String,
+}
+
+/// Pre-flight of migrating `contract_name` to `to`: diff schemas of the code on chain and the
+/// workspace, compare cw2 state version and scaffold a migrate msg stub and a checklist
+pub fn migrate_plan<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    to: Option<&str>,
+    network: &str,
+) -> Result<MigratePlanResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let root = ctx.root()?;
+    let contract_dir = root.join("contracts").join(contract_name);
+    let state = State::load_by_network(network_info.clone(), root.clone())?;
+    let wasm_ref = state.get_ref(network, contract_name)?;
+    let contract_address = wasm_ref
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?
+        .to_owned();
+
+    let to = to.map(str::to_string).unwrap_or_else(|| {
+        wasm_dir(&root, &false)
+            .join(format!("{}.wasm", contract_name.replace('-', "_")))
+            .to_string_lossy()
+            .to_string()
+    });
+    let wasm = load_wasm(ctx, &to, network)?;
+    let wasm_info = parse_wasm(&wasm).with_context(|| format!("Unable to parse `{to}`"))?;
+    let migrate_entrypoint = wasm_info
+        .exported_functions()
+        .iter()
+        .any(|f| f == "migrate");
+
+    let client = ctx.client(network_info);
+    let (from_code_id, cw2) = block(async {
+        let from_code_id = client
+            .contract_info(contract_address.clone())
+            .await?
+            .code_id;
+        let cw2 = client
+            .query_raw(contract_address.clone(), CW2_CONTRACT_INFO_KEY.to_vec())
+            .await?
+            .and_then(|raw| serde_json::from_slice::<Cw2ContractVersion>(&raw).ok());
+        anyhow::Ok((from_code_id, cw2))
+    })?;
+
+    let mut risks = vec![];
+    if !migrate_entrypoint {
+        risks.push(Risk::breaking(format!(
+            "`{to}` does not export the `migrate` entry point"
+        )));
+    }
+
+    let commit = source_commit(wasm_ref, from_code_id);
+    if commit.is_none() {
+        risks.push(Risk::warning(format!(
+            "source commit of code id {from_code_id} is not recorded in state, schemas are not compared"
+        )));
+    }
+    let mut variant_diff = |kind: &str| -> Result<Option<VariantDiff>> {
+        let commit = match &commit {
+            Some(commit) => commit,
+            None => return Ok(None),
+        };
+        let old = find_schema_with(contract_name, kind, |path| {
+            Ok(git::show_file(&contract_dir, commit, path))
+        })?;
+        let new = find_schema(&contract_dir, kind)?;
+        Ok(match (old, new) {
+            (Some(old), Some(new)) => {
+                let diff = VariantDiff {
+                    added: removed_variants(&new, &old),
+                    removed: removed_variants(&old, &new),
+                };
+                if !diff.removed.is_empty() {
+                    risks.push(Risk::breaking(format!(
+                        "{kind} msgs removed: {}",
+                        diff.removed.join(", ")
+                    )));
+                }
+                Some(diff)
+            }
+            _ => {
+                risks.push(Risk::warning(format!(
+                    "{kind} schema not found at {commit} or in the workspace, run `cargo schema` to compare"
+                )));
+                None
+            }
+        })
+    };
+    let execute = variant_diff("execute")?;
+    let query = variant_diff("query")?;
+
+    let package = package_version(&contract_dir);
+    risks.extend(cw2_risks(cw2.as_ref(), package.as_ref()));
+
+    let stub_path = contract_dir
+        .join("migrate-msgs")
+        .join(format!("{label}.json"));
+    let migrate_msg_stub = if stub_path.exists() {
+        None
+    } else {
+        let stub = find_schema(&contract_dir, "migrate")?
+            .map(|schema| msg_stub(&schema))
+            .unwrap_or_else(|| json!({}));
+        fs::create_dir_all(stub_path.parent().unwrap())?;
+        fs::write(&stub_path, serde_json::to_string_pretty(&stub)?)?;
+        Some(stub_path.to_string_lossy().to_string())
+    };
+
+    let checklist_path = contract_dir.join(format!("migrate-plan-{label}.md"));
+    let response = MigratePlanResponse {
+        contract: contract_name.to_string(),
+        label: label.to_string(),
+        contract_address,
+        from_code_id,
+        to_checksum: checksum(&wasm),
+        to,
+        migrate_entrypoint,
+        execute,
+        query,
+        cw2,
+        package,
+        risks,
+        migrate_msg_stub,
+        checklist: checklist_path.to_string_lossy().to_string(),
+    };
+    fs::write(&checklist_path, checklist(&response, &stub_path, network))?;
+
+    print_plan(&response);
+    Ok(response)
+}
+
+/// Placeholder value of `schema`, with `$ref`s resolved against `definitions` of `root`
+fn stub_value(root: &Value, schema: &Value, depth: usize) -> Value {
+    if depth > 8 {
+        return Value::Null;
+    }
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.rsplit('/').next().unwrap_or_default();
+        return match root.get("definitions").and_then(|d| d.get(name)) {
+            Some(definition) => stub_value(root, definition, depth + 1),
+            None => Value::Null,
+        };
+    }
+    if let Some(first) = ["oneOf", "anyOf", "allOf"].iter().find_map(|k| {
+        schema
+            .get(*k)
+            .and_then(Value::as_array)
+            .and_then(|v| v.first())
+    }) {
+        return stub_value(root, first, depth + 1);
+    }
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|v| v.first())
+    {
+        return first.clone();
+    }
+
+    let r#type = match schema.get("type") {
+        Some(Value::Array(types)) => types.iter().find(|t| *t != "null").cloned(),
+        t => t.cloned(),
+    };
+    match r#type.as_ref().and_then(Value::as_str) {
+        Some("object") => {
+            let required = schema
+                .get("required")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            let properties = schema.get("properties").and_then(Value::as_object);
+            Value::Object(
+                required
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|name| {
+                        let value = properties
+                            .and_then(|p| p.get(name))
+                            .map(|p| stub_value(root, p, depth + 1))
+                            .unwrap_or(Value::Null);
+                        (name.to_string(), value)
+                    })
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        Some("string") => json!(""),
+        Some("integer") | Some("number") => json!(0),
+        Some("boolean") => json!(false),
+        Some("array") => json!([]),
+        _ => Value::Null,
+    }
+}
+
+/// Migrate msg with placeholders for required fields, the first variant is picked for enums
+fn msg_stub(schema: &Value) -> Value {
+    stub_value(schema, schema, 0)
+}
+
+fn checklist(plan: &MigratePlanResponse, stub_path: &Path, network: &str) -> String {
+    let mut lines = vec![
+        format!("# Migration plan: {}:{}", plan.contract, plan.label),
+        String::new(),
+        format!(
+            "Migrating `{}` on `{network}` from code id {} to `{}` (checksum `{}`)",
+            plan.contract_address, plan.from_code_id, plan.to, plan.to_checksum
+        ),
+        String::new(),
+    ];
+
+    let check = |done: bool, item: String| format!("- [{}] {item}", if done { "x" } else { " " });
+    lines.push(check(
+        plan.migrate_entrypoint,
+        "new wasm exports the `migrate` entry point".to_string(),
+    ));
+
+    for (kind, diff) in [("execute", &plan.execute), ("query", &plan.query)] {
+        match diff {
+            Some(diff) => {
+                if !diff.removed.is_empty() {
+                    lines.push(check(
+                        false,
+                        format!(
+                            "clients no longer send removed {kind} msgs: {}",
+                            diff.removed.join(", ")
+                        ),
+                    ));
+                }
+                if !diff.added.is_empty() {
+                    lines.push(check(
+                        false,
+                        format!(
+                            "clients are updated for added {kind} msgs: {}",
+                            diff.added.join(", ")
+                        ),
+                    ));
+                }
+            }
+            None => lines.push(check(
+                false,
+                format!("review {kind} msg changes manually, schemas could not be compared"),
+            )),
+        }
+    }
+
+    match (&plan.cw2, &plan.package) {
+        (Some(cw2), Some(package)) => lines.push(check(
+            false,
+            format!(
+                "`migrate` transforms state written by {} {} and sets contract version to {}",
+                cw2.contract, cw2.version, package.version
+            ),
+        )),
+        _ => lines.push(check(
+            false,
+            "`migrate` handles state written by the old code".to_string(),
+        )),
+    }
+
+    lines.push(check(
+        false,
+        format!(
+            "fill in the migrate msg at `{}`",
+            stub_path.to_string_lossy()
+        ),
+    ));
+    lines.push(check(
+        false,
+        format!(
+            "simulate with `beaker wasm migrate {} -l {} -n {network} --dry-run`",
+            plan.contract, plan.label
+        ),
+    ));
+
+    if !plan.risks.is_empty() {
+        lines.push(String::new());
+        lines.push("## Risks".to_string());
+        lines.push(String::new());
+        lines.extend(plan.risks.iter().map(|r| {
+            let level = match r.level {
+                RiskLevel::Breaking => "breaking",
+                RiskLevel::Warning => "warning",
+            };
+            format!("- **{level}**: {}", r.message)
+        }));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn print_plan(plan: &MigratePlanResponse) {
+    output::print(plan, || {
+        let mut lines = vec![format!(
+            "\n  Migration plan of {}:{} from code id {} to `{}`",
+            plan.contract, plan.label, plan.from_code_id, plan.to
+        )];
+        for (kind, diff) in [("execute", &plan.execute), ("query", &plan.query)] {
+            if let Some(diff) = diff {
+                lines.push(format!(
+                    "    {kind}: +[{}] -[{}]",
+                    diff.added.join(", "),
+                    diff.removed.join(", ")
+                ));
+            }
+        }
+        lines.extend(plan.risks.iter().map(|r| match r.level {
+            RiskLevel::Breaking => format!("    {} {}", style("✗").red(), r.message),
+            RiskLevel::Warning => format!("    {} {}", style("‣").yellow(), r.message),
+        }));
+        if let Some(stub) = &plan.migrate_msg_stub {
+            lines.push(format!("    migrate msg stub: {stub}"));
+        }
+        lines.push(format!("    checklist: {}", plan.checklist));
+        lines.join("\n")
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stub_from_schema() {
+        let schema = json!({
+            "oneOf": [{
+                "type": "object",
+                "required": ["v2"],
+                "properties": {
+                    "v2": {
+                        "type": "object",
+                        "required": ["owner", "fee", "config"],
+                        "properties": {
+                            "owner": { "type": "string" },
+                            "fee": { "type": "integer" },
+                            "memo": { "type": ["string", "null"] },
+                            "config": { "$ref": "#/definitions/Config" }
+                        }
+                    }
+                }
+            }],
+            "definitions": {
+                "Config": {
+                    "type": "object",
+                    "required": ["paused"],
+                    "properties": { "paused": { "type": "boolean" } }
+                }
+            }
+        });
+        assert_eq!(
+            msg_stub(&schema),
+            json!({ "v2": { "owner": "", "fee": 0, "config": { "paused": false } } })
+        );
+        assert_eq!(msg_stub(&json!({ "type": "object" })), json!({}));
+    }
+}
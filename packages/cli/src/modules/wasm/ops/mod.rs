@@ -1,23 +1,60 @@
+pub mod artifacts;
 pub mod build;
 pub mod clear_admin;
+pub mod code_info;
 pub mod deploy;
+pub mod diff;
+pub mod dump_state;
+pub mod events;
 pub mod execute;
+pub mod execute_bulk;
+pub mod history;
+pub mod inspect;
 pub mod instantiate;
+pub mod interact;
+pub mod matrix;
 pub mod migrate;
+pub mod migrate_all;
+pub mod migrate_check;
+pub mod migrate_plan;
 pub mod new;
+pub mod params;
+pub mod predict_address;
 pub mod query;
+pub mod query_batch;
+pub mod rollback;
+pub mod state_transfer;
 pub mod store_code;
 pub mod update_admin;
 pub mod upgrade;
+pub mod version;
 
+pub use artifacts::{pull_artifacts, push_artifacts};
 pub use build::build;
 pub use clear_admin::clear_admin;
-pub use deploy::deploy;
+pub use code_info::{code_info, list_codes, list_contracts};
+pub use deploy::{deploy, deploy_to_networks};
+pub use diff::diff;
+pub use dump_state::dump_state;
+pub use events::events;
 pub use execute::execute;
+pub use execute_bulk::execute_bulk;
+pub use history::history;
+pub use inspect::inspect;
 pub use instantiate::instantiate;
+pub use interact::interact;
+pub use matrix::build_matrix;
 pub use migrate::migrate;
+pub use migrate_all::migrate_all;
+pub use migrate_plan::migrate_plan;
 pub use new::new;
+pub use params::params;
+pub use predict_address::predict_address;
 pub use query::query;
+pub use query_batch::query_batch;
+pub use rollback::rollback;
+pub use state_transfer::{export_state, import_state};
 pub use store_code::store_code;
 pub use update_admin::update_admin;
 pub use upgrade::upgrade;
+pub use version::version;
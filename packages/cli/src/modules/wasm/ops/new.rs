@@ -9,13 +9,26 @@ pub fn new<'a, Ctx: Context<'a, WasmConfig>>(
     name: &str,
     version: Option<String>,
     target_dir: Option<PathBuf>,
+    generator_args: Vec<String>,
+    ibc: bool,
 ) -> Result<()> {
     let cfg = ctx.config()?;
-    let repo = &cfg.template_repo;
-    let version = version.unwrap_or_else(|| "main".to_string());
+    let (repo, subfolder) = if ibc {
+        (&cfg.ibc_template_repo, cfg.ibc_template_subfolder.clone())
+    } else {
+        (&cfg.template_repo, None)
+    };
     let target_dir =
         target_dir.unwrap_or(ctx.root()?.join(PathBuf::from(cfg.contract_dir.as_str())));
 
-    let cw_template = Template::new(name.to_string(), repo.to_owned(), version, None, target_dir);
+    let cw_template = Template::new(
+        name.to_string(),
+        repo.to_owned(),
+        version,
+        subfolder,
+        target_dir,
+    )
+    .with_args(cfg.template_args.clone())
+    .with_args(generator_args);
     cw_template.generate()
 }
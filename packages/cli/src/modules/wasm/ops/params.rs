@@ -0,0 +1,154 @@
+use anyhow::{Context as _, Result};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::framework::Context;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::future::block;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::wasm_params::{access_config_json, access_type_name};
+
+const SUBSPACE: &str = "wasm";
+
+/// Who can store code according to `uploadAccess` param, eg. `{"permission":"OnlyAddress","address":"osmo1..."}`
+fn describe_upload_access(upload_access: &Value) -> String {
+    let addresses = match upload_access.get("addresses").and_then(|a| a.as_array()) {
+        Some(addresses) => addresses
+            .iter()
+            .filter_map(|a| a.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+        None => upload_access
+            .get("address")
+            .and_then(|a| a.as_str())
+            .unwrap_or_default()
+            .to_string(),
+    };
+
+    match upload_access.get("permission").and_then(|p| p.as_str()) {
+        Some("Everybody") => "allowed for everybody".to_string(),
+        Some("Nobody") => "governance proposal required".to_string(),
+        Some("OnlyAddress" | "AnyOfAddresses") => {
+            format!("only allowed for {addresses}, governance proposal required otherwise")
+        }
+        Some(permission) => format!("unknown permission `{permission}`"),
+        None => "unknown".to_string(),
+    }
+}
+
+/// Wasm module params of `network`, with whether direct store-code is allowed.
+/// Queried from `x/params` or the wasm module itself, depending on the chain's wasmd version
+pub fn params<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    network: &str,
+) -> Result<ParamsResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+    let client = ctx.client(network_info);
+
+    let response = block(async {
+        if client.features().await?.module_params() {
+            let params = client.wasm_params().await?;
+            let upload_access = params
+                .code_upload_access
+                .as_ref()
+                .map(access_config_json)
+                .unwrap_or(Value::Null);
+            return Ok(ParamsResponse {
+                store_code: describe_upload_access(&upload_access),
+                upload_access,
+                instantiate_default_permission: access_type_name(
+                    params.instantiate_default_permission,
+                )
+                .to_string(),
+                max_wasm_code_size: None,
+            });
+        }
+
+        let upload_access: Value = serde_json::from_str(
+            &client
+                .param(SUBSPACE, "uploadAccess")
+                .await?
+                .with_context(|| "Wasm module params are not available on this network")?,
+        )?;
+        let instantiate_default_permission = client
+            .param(SUBSPACE, "instantiateAccess")
+            .await?
+            .map(|p| serde_json::from_str::<String>(&p).unwrap_or(p))
+            .unwrap_or_default();
+        // removed from params in recent wasmd versions
+        let max_wasm_code_size = client
+            .param(SUBSPACE, "maxWasmCodeSize")
+            .await?
+            .and_then(|p| p.trim_matches('"').parse::<u64>().ok());
+
+        Ok(ParamsResponse {
+            store_code: describe_upload_access(&upload_access),
+            upload_access,
+            instantiate_default_permission,
+            max_wasm_code_size,
+        })
+    })?;
+    response.log();
+
+    Ok(response)
+}
+
+#[derive(Serialize)]
+pub struct ParamsResponse {
+    pub upload_access: Value,
+    pub instantiate_default_permission: String,
+    pub max_wasm_code_size: Option<u64>,
+    /// Whether store-code can be broadcasted directly or requires a proposal
+    pub store_code: String,
+}
+
+impl OpResponseDisplay for ParamsResponse {
+    fn headline() -> &'static str {
+        "Wasm module params"
+    }
+    fn attrs(&self) -> Vec<String> {
+        vec![
+            format!("    ├── upload_access: {}", self.upload_access),
+            format!(
+                "    ├── instantiate_default_permission: {}",
+                self.instantiate_default_permission
+            ),
+            format!(
+                "    ├── max_wasm_code_size: {}",
+                self.max_wasm_code_size
+                    .map(|s| format!("{s} bytes"))
+                    .unwrap_or_else(|| "–".to_string())
+            ),
+            format!("    └── store_code: {}", self.store_code),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn describe_store_code_permission() {
+        assert_eq!(
+            describe_upload_access(&json!({"permission": "Everybody", "address": ""})),
+            "allowed for everybody"
+        );
+        assert_eq!(
+            describe_upload_access(&json!({"permission": "Nobody"})),
+            "governance proposal required"
+        );
+        assert_eq!(
+            describe_upload_access(
+                &json!({"permission": "AnyOfAddresses", "addresses": ["osmo1a", "osmo1b"]})
+            ),
+            "only allowed for osmo1a, osmo1b, governance proposal required otherwise"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use anyhow::{anyhow, ensure, Context as _, Result};
+use cosmrs::AccountId;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::support::output;
+
+#[derive(Serialize, Debug)]
+pub struct PredictAddressResponse {
+    pub address: String,
+    pub code_checksum: String,
+    pub creator: String,
+    pub salt: String,
+}
+
+fn length_prefixed(bytes: &[u8]) -> Vec<u8> {
+    let mut prefixed = (bytes.len() as u64).to_be_bytes().to_vec();
+    prefixed.extend_from_slice(bytes);
+    prefixed
+}
+
+/// Address of a contract instantiated with `MsgInstantiateContract2` (without `fix_msg`),
+/// derived the same way as wasmd: `address.Module("wasm", key)` where key is the length prefixed
+/// checksum, canonical creator address, salt and (empty) init msg
+pub fn instantiate2_address(checksum: &[u8], creator: &[u8], salt: &[u8]) -> Result<Vec<u8>> {
+    ensure!(
+        !salt.is_empty() && salt.len() <= 64,
+        "Salt must be between 1 and 64 bytes, got {}",
+        salt.len()
+    );
+
+    let mut key = b"wasm\0".to_vec();
+    key.extend(length_prefixed(checksum));
+    key.extend(length_prefixed(creator));
+    key.extend(length_prefixed(salt));
+    key.extend(length_prefixed(&[]));
+
+    let mut hasher = Sha256::new();
+    hasher.update(Sha256::digest(b"module"));
+    hasher.update(&key);
+    Ok(hasher.finalize().to_vec())
+}
+
+/// Predict the address of a contract instantiated by `creator` from code with `code_checksum`
+/// with instantiate2, before anything is stored or instantiated
+pub fn predict_address(
+    code_checksum: &str,
+    creator: &str,
+    salt: &str,
+) -> Result<PredictAddressResponse> {
+    let checksum = hex::decode(code_checksum.trim_start_matches("0x"))
+        .with_context(|| "Code checksum must be hex encoded sha256 of the wasm")?;
+    ensure!(
+        checksum.len() == 32,
+        "Code checksum must be 32 bytes, got {}",
+        checksum.len()
+    );
+    let creator_id = creator.parse::<AccountId>().map_err(|e| anyhow!(e))?;
+    let salt_bytes =
+        hex::decode(salt.trim_start_matches("0x")).with_context(|| "Salt must be hex encoded")?;
+
+    let address = instantiate2_address(&checksum, &creator_id.to_bytes(), &salt_bytes)?;
+    let address = AccountId::new(creator_id.prefix(), &address).map_err(|e| anyhow!(e))?;
+
+    let response = PredictAddressResponse {
+        address: address.to_string(),
+        code_checksum: hex::encode(&checksum),
+        creator: creator.to_string(),
+        salt: hex::encode(&salt_bytes),
+    };
+    output::print(&response, || response.address.clone());
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derive_instantiate2_address() {
+        // test vector from cosmwasm-std's `instantiate2_address`
+        let checksum =
+            hex::decode("13a1fc994cc6d1c81b746ee0c0ff6f90043875e0bf1d9be6b7d779fc978dc2a5")
+                .unwrap();
+        let creator = hex::decode("9999999999aaaaaaaaaabbbbbbbbbbcccccccccc").unwrap();
+
+        assert_eq!(
+            hex::encode(instantiate2_address(&checksum, &creator, b"a").unwrap()),
+            "5e865d3e45ad3e961f77fd77d46543417ced44d924dc3e079b5415ff6775f847"
+        );
+        assert!(instantiate2_address(&checksum, &creator, b"").is_err());
+        assert!(instantiate2_address(&checksum, &creator, &[0; 65]).is_err());
+    }
+
+    #[test]
+    fn predict_bech32_address_with_creator_prefix() {
+        let creator = AccountId::new("osmo", &[0x99; 20]).unwrap().to_string();
+        let res = predict_address(
+            "0x13a1fc994cc6d1c81b746ee0c0ff6f90043875e0bf1d9be6b7d779fc978dc2a5",
+            &creator,
+            "61",
+        )
+        .unwrap();
+        assert!(res.address.starts_with("osmo1"));
+        assert!(predict_address("abcd", &creator, "61").is_err());
+    }
+}
@@ -1,15 +1,17 @@
-use crate::attrs_format;
 use crate::modules::wasm::config::WasmConfig;
 use crate::support::future::block;
+use crate::support::msg::msg_bytes;
 use crate::support::ops_response::OpResponseDisplay;
+use crate::support::query_filter::filter_raw;
 use crate::support::state::State;
+use crate::support::typed_response::{render_tree, response_schema, TypedRenderer};
 use crate::{framework::Context, support::cosmos::Client};
 use anyhow::anyhow;
 use anyhow::Context as _;
 use anyhow::Result;
+use config::Map;
 use cosmrs::AccountId;
-
-use std::fs;
+use serde::Serialize;
 
 #[allow(clippy::too_many_arguments)]
 pub fn query<'a, Ctx: Context<'a, WasmConfig>>(
@@ -17,6 +19,8 @@ pub fn query<'a, Ctx: Context<'a, WasmConfig>>(
     contract_name: &str,
     label: &str,
     raw: Option<&String>,
+    query_filter: Option<&String>,
+    typed: bool,
     network: &str,
 ) -> Result<QueryResponse> {
     let global_config = ctx.global_config()?;
@@ -26,8 +30,8 @@ pub fn query<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone());
-    let state = State::load_by_network(network_info, ctx.root()?)?;
+    let client = ctx.client(network_info.clone());
+    let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
 
     let contract = state
         .get_ref(network, contract_name)?
@@ -37,45 +41,63 @@ pub fn query<'a, Ctx: Context<'a, WasmConfig>>(
         .parse::<AccountId>()
         .map_err(|e| anyhow!(e))?;
 
-    let query_msg = raw
-        .map(|s| s.as_bytes().to_vec())
-        .map(Ok)
-        .unwrap_or_else(|| {
-            let path = ctx
-                .root()?
-                .join("contracts")
-                .join(contract_name)
-                .join("query-msgs")
-                .join(format!("{label}.json"));
-            fs::read_to_string(&path)
-                .with_context(|| format!("Unable to execute with `{}`", path.to_string_lossy()))
-                .map(|s| s.as_bytes().to_vec())
-        })?;
+    let contract_dir = ctx.root()?.join("contracts").join(contract_name);
+    let query_msg = msg_bytes(
+        raw,
+        contract_dir
+            .join("query-msgs")
+            .join(format!("{label}.json")),
+        "query",
+    )?;
+    let schema = if typed {
+        response_schema(&contract_dir, &serde_json::from_slice(&query_msg)?)?
+    } else {
+        None
+    };
 
     block(async {
         let response = client.query_smart(contract.to_string(), query_msg).await?;
-        let pretty_json_response = serde_json::to_string_pretty(
-            &serde_json::from_slice::<serde_json::Value>(&response)
-                .with_context(|| "Unable to deserialize response")?,
-        )?;
+        let data = serde_json::from_slice::<serde_json::Value>(&response)
+            .with_context(|| "Unable to deserialize response")?;
+
+        let rendered = schema.as_ref().map(|schema| {
+            let mut names = Map::new();
+            for (name, wasm_ref) in state.contracts(network) {
+                for (label, address) in wasm_ref.addresses() {
+                    names.insert(address.clone(), format!("{name}:{label}"));
+                }
+            }
+            for (name, address) in network_info.address_book() {
+                names.insert(address.clone(), format!("@{name}"));
+            }
+            render_tree(&TypedRenderer::new(schema, &names).decode(&data))
+        });
 
         let query_response = QueryResponse {
             label: label.to_string(),
             contract_address: contract.to_string(),
-            data: format!("\n{}", textwrap::indent(&pretty_json_response, "        ")),
+            data,
+            rendered,
         };
 
-        query_response.log();
+        match query_filter {
+            Some(expr) => println!("{}", filter_raw(&query_response.data, expr)?),
+            None => query_response.log(),
+        }
 
         Ok(query_response)
     })
 }
 
 #[allow(dead_code)]
+#[derive(Serialize)]
 pub struct QueryResponse {
     pub label: String,
     pub contract_address: String,
-    pub data: String,
+    pub data: serde_json::Value,
+    /// `data` decoded by the contract's response schema, for human readable output
+    #[serde(skip)]
+    pub rendered: Option<String>,
 }
 
 impl OpResponseDisplay for QueryResponse {
@@ -83,6 +105,17 @@ impl OpResponseDisplay for QueryResponse {
         "Succesffuly executed query!! 🎉 "
     }
     fn attrs(&self) -> Vec<String> {
-        attrs_format! { self | label, contract_address, data }
+        let pretty_data = match &self.rendered {
+            Some(rendered) => rendered.clone(),
+            None => serde_json::to_string_pretty(&self.data).unwrap_or_default(),
+        };
+        vec![
+            format!("    ├── label: {}", self.label),
+            format!("    ├── contract_address: {}", self.contract_address),
+            format!(
+                "    └── data: \n{}",
+                textwrap::indent(&pretty_data, "        ")
+            ),
+        ]
     }
 }
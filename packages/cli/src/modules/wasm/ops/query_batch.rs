@@ -0,0 +1,181 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use cosmrs::AccountId;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::framework::Context;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::cosmos::Client;
+use crate::support::future::block;
+use crate::support::state::State;
+
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct BatchQuery {
+    /// Contract name in state or contract address
+    pub contract: String,
+    #[serde(default = "default_label")]
+    pub label: String,
+    pub msg: serde_json::Value,
+}
+
+fn default_label() -> String {
+    "default".to_string()
+}
+
+/// One json line per query, in order of completion, `index` refers to the position in the batch file
+#[derive(Serialize, Debug)]
+pub struct BatchQueryResult {
+    pub index: usize,
+    pub contract: String,
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Queries from a json array or json lines of `{ "contract", "label"?, "msg" }`
+fn parse_batch(content: &str) -> Result<Vec<BatchQuery>> {
+    if content.trim_start().starts_with('[') {
+        return serde_json::from_str(content).with_context(|| "Invalid batch query array");
+    }
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid batch query at line {}", i + 1))
+        })
+        .collect()
+}
+
+/// Run all queries in `file` concurrently over a single connection, failed queries are reported
+/// in their result line without stopping the rest of the batch
+pub fn query_batch<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    file: &Path,
+    concurrency: usize,
+    network: &str,
+) -> Result<()> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let content = fs::read_to_string(file)
+        .with_context(|| format!("Unable to read `{}`", file.to_string_lossy()))?;
+    let queries = parse_batch(&content)?;
+
+    let client = ctx.client(network_info.clone());
+    let state = State::load_by_network(network_info, ctx.root()?).ok();
+
+    let resolve = |q: &BatchQuery| -> Result<String> {
+        if q.contract.parse::<AccountId>().is_ok() {
+            return Ok(q.contract.clone());
+        }
+        state
+            .as_ref()
+            .with_context(|| format!("Unable to load state of `{network}`"))?
+            .get_ref(network, &q.contract)?
+            .addresses()
+            .get(&q.label)
+            .cloned()
+            .with_context(|| format!("Unable to retrieve contract for {}:{}", q.contract, q.label))
+    };
+
+    let mut failed = 0;
+    block(async {
+        let mut results = futures::stream::iter(queries.iter().enumerate())
+            .map(|(index, q)| {
+                let client = &client;
+                let address = resolve(q);
+                async move {
+                    let res = match &address {
+                        Ok(address) => query_one(client, address, &q.msg).await,
+                        Err(e) => Err(anyhow::anyhow!("{e:#}")),
+                    };
+                    let (data, error) = match res {
+                        Ok(data) => (Some(data), None),
+                        Err(e) => (None, Some(format!("{e:#}"))),
+                    };
+                    BatchQueryResult {
+                        index,
+                        contract: q.contract.clone(),
+                        label: q.label.clone(),
+                        address: address.ok(),
+                        data,
+                        error,
+                    }
+                }
+            })
+            .buffer_unordered(concurrency.max(1));
+
+        while let Some(result) = results.next().await {
+            if result.error.is_some() {
+                failed += 1;
+            }
+            println!("{}", serde_json::to_string(&result)?);
+        }
+        anyhow::Ok(())
+    })?;
+
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} queries failed", queries.len());
+    }
+    Ok(())
+}
+
+async fn query_one(
+    client: &Client,
+    address: &str,
+    msg: &serde_json::Value,
+) -> Result<serde_json::Value> {
+    let response = client
+        .query_smart(address.to_string(), serde_json::to_vec(msg)?)
+        .await?;
+    serde_json::from_slice(&response).with_context(|| "Unable to deserialize response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_batch_array_and_json_lines() {
+        let expected = vec![
+            BatchQuery {
+                contract: "counter".to_string(),
+                label: "default".to_string(),
+                msg: serde_json::json!({ "get_count": {} }),
+            },
+            BatchQuery {
+                contract: "osmo14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9sq2r9g9"
+                    .to_string(),
+                label: "v2".to_string(),
+                msg: serde_json::json!({ "config": {} }),
+            },
+        ];
+
+        let array = r#"[
+            { "contract": "counter", "msg": { "get_count": {} } },
+            { "contract": "osmo14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9sq2r9g9", "label": "v2", "msg": { "config": {} } }
+        ]"#;
+        assert_eq!(parse_batch(array).unwrap(), expected);
+
+        let lines = r#"{ "contract": "counter", "msg": { "get_count": {} } }
+
+{ "contract": "osmo14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9sq2r9g9", "label": "v2", "msg": { "config": {} } }
+"#;
+        assert_eq!(parse_batch(lines).unwrap(), expected);
+
+        assert!(parse_batch("{ \"msg\": {} }").is_err());
+    }
+}
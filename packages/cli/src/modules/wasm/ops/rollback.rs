@@ -0,0 +1,143 @@
+use crate::attrs_format;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::future::block;
+use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
+use crate::support::msg::to_json_bytes;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::{Action, HistoryEntry, State};
+use crate::{framework::Context, support::cosmos::Client};
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::{anyhow, bail};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::ContractCodeHistoryOperationType;
+use cosmrs::cosmwasm::MsgMigrateContract;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::Msg;
+use cosmrs::AccountId;
+use serde::Serialize;
+
+/// Migrate contract back to the code id it had before its latest release and record the rollback in history.
+/// The release history in state is used if it has a previous release, otherwise the contract's code history on chain.
+#[allow(clippy::too_many_arguments)]
+pub fn rollback<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    raw: Option<&String>,
+    network: &str,
+    timeout_height: &u32,
+    gas: &Gas,
+    signing_key: SigningKey,
+) -> Result<RollbackResponse> {
+    let global_config = ctx.global_config()?;
+    let account_prefix = global_config.account_prefix().as_str();
+
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let query_client = ctx.client(network_info.clone());
+
+    let state = State::load_by_network(network_info.clone(), ctx.root()?)?;
+    let wasm_ref = state.get_ref(network, contract_name)?;
+    let address = wasm_ref
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?;
+
+    let (current_code_id, code_id) = match wasm_ref.rollback_code_ids(label) {
+        Ok(code_ids) => code_ids,
+        Err(_) => block(on_chain_rollback_code_ids(&query_client, address))
+            .with_context(|| format!("Unable to rollback {contract_name}:{label}"))?,
+    };
+
+    let contract = address.parse::<AccountId>().map_err(|e| anyhow!(e))?;
+    let client = query_client
+        .to_signing_client(signing_key, account_prefix)
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, Some(contract_name)));
+
+    let msg_migrate_contract = MsgMigrateContract {
+        sender: client.signer_account_id(),
+        contract: contract.clone(),
+        code_id,
+        msg: match raw {
+            Some(raw) => to_json_bytes(raw).with_context(|| "Unable to migrate with given msg")?,
+            None => b"{}".to_vec(),
+        },
+    };
+
+    block(async {
+        let response = client
+            .sign_and_broadcast(
+                vec![msg_migrate_contract.to_any().unwrap()],
+                gas,
+                "",
+                timeout_height,
+            )
+            .await?;
+
+        let rollback_response = RollbackResponse {
+            label: label.to_string(),
+            contract_address: contract.to_string(),
+            from_code_id: current_code_id,
+            to_code_id: code_id,
+            tx_hash: response.hash.to_string(),
+        };
+
+        rollback_response.log();
+
+        State::update_state_file(
+            network_info.network_variant(),
+            ctx.root()?,
+            &|s: &State| -> State {
+                s.record_history(
+                    network,
+                    contract_name,
+                    HistoryEntry::new(Action::Rollback, label, code_id)
+                        .with_tx_hash(&response.hash.to_string()),
+                )
+            },
+        )?;
+
+        Ok(rollback_response)
+    })
+}
+
+/// `(current, previous)` code ids of the contract at `address`, from its code history on chain
+async fn on_chain_rollback_code_ids(client: &Client, address: &str) -> Result<(u64, u64)> {
+    let history = client.contract_history(address.to_string()).await?;
+    let mut releases = history
+        .iter()
+        .rev()
+        .filter(|h| {
+            h.operation == ContractCodeHistoryOperationType::Init as i32
+                || h.operation == ContractCodeHistoryOperationType::Migrate as i32
+        })
+        .map(|h| h.code_id);
+
+    match (releases.next(), releases.next()) {
+        (Some(current), Some(previous)) => Ok((current, previous)),
+        _ => bail!("No previous release recorded in state or in the code history of `{address}`"),
+    }
+}
+
+#[derive(Serialize)]
+pub struct RollbackResponse {
+    pub label: String,
+    pub contract_address: String,
+    pub from_code_id: u64,
+    pub to_code_id: u64,
+    pub tx_hash: String,
+}
+
+impl OpResponseDisplay for RollbackResponse {
+    fn headline() -> &'static str {
+        "Contract rolled back successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | label, contract_address, from_code_id, to_code_id, tx_hash }
+    }
+}
@@ -0,0 +1,314 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{bail, Context as _, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::attrs_format;
+use crate::framework::Context;
+use crate::modules::wasm::config::WasmConfig;
+use crate::support::future::block;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::output;
+use crate::support::state::State;
+
+/// Placeholder in import msg templates, replaced by a batch of entries
+const ENTRIES_PLACEHOLDER: &str = "{{entries}}";
+
+pub const DEFAULT_IMPORT_TEMPLATE: &str = r#"{"import_state":{"entries":"{{entries}}"}}"#;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ExportedSource {
+    pub network: String,
+    pub contract_address: String,
+    pub code_id: u64,
+    pub creator: String,
+    pub admin: String,
+    pub label: String,
+}
+
+/// Raw storage entry, `key` hex encoded and `value` base64 encoded
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ExportedEntry {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StateExport {
+    pub source: ExportedSource,
+    /// Namespaces the entries are selected by, every entry is exported if empty
+    pub namespaces: Vec<String>,
+    pub entries: Vec<ExportedEntry>,
+}
+
+/// Whether raw `key` is cw-storage-plus `Item` named `namespace` or belongs to `Map` named `namespace`
+fn in_namespace(key: &[u8], namespace: &str) -> bool {
+    let ns = namespace.as_bytes();
+    if key == ns {
+        return true;
+    }
+    key.len() >= 2 + ns.len()
+        && u16::from_be_bytes([key[0], key[1]]) as usize == ns.len()
+        && &key[2..2 + ns.len()] == ns
+}
+
+fn select_entries(entries: Vec<(Vec<u8>, Vec<u8>)>, namespaces: &[String]) -> Vec<ExportedEntry> {
+    entries
+        .into_iter()
+        .filter(|(key, _)| {
+            namespaces.is_empty() || namespaces.iter().any(|ns| in_namespace(key, ns))
+        })
+        .map(|(key, value)| ExportedEntry {
+            key: hex::encode(key),
+            value: base64::encode(value),
+        })
+        .collect()
+}
+
+/// Export raw storage of the contract, only entries of `namespaces` if any, to `out`
+pub fn export_state<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    label: &str,
+    namespaces: &[String],
+    out: Option<&Path>,
+    network: &str,
+) -> Result<ExportStateResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let client = ctx.client(network_info.clone());
+    let state = State::load_by_network(network_info, ctx.root()?)?;
+    let contract = state
+        .get_ref(network, contract_name)?
+        .addresses()
+        .get(label)
+        .with_context(|| format!("Unable to retrieve contract for {contract_name}:{label}"))?
+        .to_owned();
+
+    let (info, entries) = block(async {
+        let info = client.contract_info(contract.clone()).await?;
+        let entries = client.all_contract_state(contract.clone(), None).await?;
+        anyhow::Ok((info, entries.items))
+    })?;
+
+    let export = StateExport {
+        source: ExportedSource {
+            network: network.to_string(),
+            contract_address: contract.clone(),
+            code_id: info.code_id,
+            creator: info.creator,
+            admin: info.admin,
+            label: info.label,
+        },
+        namespaces: namespaces.to_vec(),
+        entries: select_entries(entries, namespaces),
+    };
+
+    let path = out
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(format!("{contract_name}-{label}-{network}.state.json")));
+    fs::write(&path, serde_json::to_string_pretty(&export)?)
+        .with_context(|| format!("Unable to write state export to `{}`", path.display()))?;
+
+    let response = ExportStateResponse {
+        contract_address: contract,
+        entries: export.entries.len(),
+        file: path.to_string_lossy().to_string(),
+    };
+    response.log();
+    Ok(response)
+}
+
+#[derive(Serialize)]
+pub struct ExportStateResponse {
+    pub contract_address: String,
+    pub entries: usize,
+    pub file: String,
+}
+
+impl OpResponseDisplay for ExportStateResponse {
+    fn headline() -> &'static str {
+        "Contract state exported successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | contract_address, entries, file }
+    }
+}
+
+/// What exported state is turned into for seeding a contract elsewhere
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// Contract entry of wasm genesis (`app_state.wasm.contracts[]`), eg. for localnet `genesis_file`
+    Genesis,
+    /// Execute or migrate msgs carrying batches of entries, for contracts that accept them
+    Msgs,
+}
+
+impl FromStr for ImportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "genesis" => Ok(ImportFormat::Genesis),
+            "msgs" => Ok(ImportFormat::Msgs),
+            _ => bail!("Unknown import format `{s}`, expected one of: genesis, msgs"),
+        }
+    }
+}
+
+/// Wasm genesis contract with the exported state, `address` replaces the source contract address
+fn genesis_contract(export: &StateExport, address: Option<&str>, code_id: Option<u64>) -> Value {
+    let source = &export.source;
+    json!({
+        "contract_address": address.unwrap_or(&source.contract_address),
+        "contract_info": {
+            "code_id": code_id.unwrap_or(source.code_id).to_string(),
+            "creator": source.creator,
+            "admin": source.admin,
+            "label": source.label,
+        },
+        "contract_state": export.entries.iter().map(|e| json!({
+            "key": e.key.to_uppercase(),
+            "value": e.value,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+/// `template` with every `"{{entries}}"` string replaced by `entries`
+fn fill_template(template: &Value, entries: &Value) -> Value {
+    match template {
+        Value::String(s) if s == ENTRIES_PLACEHOLDER => entries.clone(),
+        Value::Array(values) => {
+            Value::Array(values.iter().map(|v| fill_template(v, entries)).collect())
+        }
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), fill_template(v, entries)))
+                .collect(),
+        ),
+        v => v.clone(),
+    }
+}
+
+/// One msg per `batch_size` entries rendered from `template`, entries as `{"key": <base64>, "value": <base64>}`
+/// since contracts usually take `Binary` for raw storage keys
+fn import_msgs(export: &StateExport, template: &str, batch_size: usize) -> Result<Vec<Value>> {
+    if batch_size == 0 {
+        bail!("batch size must be greater than 0");
+    }
+    let template: Value = serde_json::from_str(template)
+        .with_context(|| format!("Msg template is not valid json: {template}"))?;
+    if !template.to_string().contains(ENTRIES_PLACEHOLDER) {
+        bail!("Msg template must contain `\"{ENTRIES_PLACEHOLDER}\"` where entries are placed");
+    }
+
+    export
+        .entries
+        .chunks(batch_size)
+        .map(|batch| {
+            let entries = batch
+                .iter()
+                .map(|e| {
+                    Ok(json!({
+                        "key": base64::encode(hex::decode(&e.key).with_context(|| format!("Invalid hex key `{}`", e.key))?),
+                        "value": e.value,
+                    }))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(fill_template(&template, &Value::Array(entries)))
+        })
+        .collect()
+}
+
+/// Turn a state export into a wasm genesis contract or into msgs importing its entries
+pub fn import_state(
+    file: &Path,
+    format: ImportFormat,
+    address: Option<&str>,
+    code_id: Option<u64>,
+    template: &str,
+    batch_size: usize,
+) -> Result<Value> {
+    let export: StateExport = serde_json::from_str(
+        &fs::read_to_string(file)
+            .with_context(|| format!("Unable to read `{}`", file.display()))?,
+    )
+    .with_context(|| format!("`{}` is not a state export", file.display()))?;
+
+    let imported = match format {
+        ImportFormat::Genesis => genesis_contract(&export, address, code_id),
+        ImportFormat::Msgs => Value::Array(import_msgs(&export, template, batch_size)?),
+    };
+    output::print(&imported, || {
+        serde_json::to_string_pretty(&imported).unwrap_or_default()
+    });
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export() -> StateExport {
+        StateExport {
+            source: ExportedSource {
+                network: "testnet".to_string(),
+                contract_address: "osmo1source".to_string(),
+                code_id: 7,
+                creator: "osmo1creator".to_string(),
+                admin: String::new(),
+                label: "counter".to_string(),
+            },
+            namespaces: vec![],
+            entries: vec![
+                ExportedEntry {
+                    key: hex::encode("config"),
+                    value: base64::encode(r#"{"owner":"osmo1a"}"#),
+                },
+                ExportedEntry {
+                    key: hex::encode(b"\x00\x08balancesosmo1a"),
+                    value: base64::encode("100"),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn select_entries_by_namespace() {
+        let entries = vec![
+            (b"config".to_vec(), b"{}".to_vec()),
+            (b"\x00\x08balancesosmo1a".to_vec(), b"1".to_vec()),
+            (b"\x00\x07balanceosmo1a".to_vec(), b"2".to_vec()),
+        ];
+        let selected = select_entries(entries.clone(), &["balances".to_string()]);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].value, base64::encode("1"));
+        assert_eq!(select_entries(entries, &[]).len(), 3);
+    }
+
+    #[test]
+    fn import_as_genesis_and_msgs() {
+        let genesis = genesis_contract(&export(), Some("osmo1target"), None);
+        assert_eq!(genesis["contract_address"], "osmo1target");
+        assert_eq!(genesis["contract_info"]["code_id"], "7");
+        assert_eq!(genesis["contract_state"][0]["key"], "434F4E464947");
+
+        let msgs = import_msgs(&export(), DEFAULT_IMPORT_TEMPLATE, 1).unwrap();
+        assert_eq!(msgs.len(), 2);
+        assert_eq!(
+            msgs[0],
+            json!({ "import_state": { "entries": [
+                { "key": base64::encode("config"), "value": base64::encode(r#"{"owner":"osmo1a"}"#) }
+            ] } })
+        );
+        assert!(import_msgs(&export(), r#"{"import_state":{}}"#, 1).is_err());
+    }
+}
@@ -1,18 +1,27 @@
+use std::fs;
+use std::time::UNIX_EPOCH;
+
 use crate::attrs_format;
+use crate::modules::wasm::ops::build::optimizer_image;
 use crate::modules::wasm::WasmConfig;
+use crate::support::build_metadata::BuildMetadata;
 use crate::support::cosmos::ResponseValuePicker;
 use crate::support::future::block;
 use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
+use crate::support::git::head_commit;
+use crate::support::notify::{notify, DeployEvent};
 use crate::support::ops_response::OpResponseDisplay;
 use crate::support::permission::compute_instantiate_permission;
-use crate::support::state::State;
-use crate::support::wasm::read_wasm;
+use crate::support::state::{Action, HistoryEntry, State};
+use crate::support::wasm::{checksum, download_wasm, read_wasm, wasm_dir, WasmSource};
 use crate::{framework::Context, support::cosmos::Client};
 use anyhow::Context as _;
 use anyhow::Result;
 use cosmrs::cosmwasm::MsgStoreCode;
 use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::tx::Msg;
+use serde::Serialize;
 
 #[allow(clippy::too_many_arguments)]
 pub fn store_code<'a, Ctx: Context<'a, WasmConfig>>(
@@ -24,6 +33,7 @@ pub fn store_code<'a, Ctx: Context<'a, WasmConfig>>(
     gas: &Gas,
     timeout_height: &u32,
     signing_key: SigningKey,
+    source: WasmSource,
 ) -> Result<StoreCodeResponse> {
     let global_config = ctx.global_config()?;
     let account_prefix = global_config.account_prefix().as_str();
@@ -34,16 +44,38 @@ pub fn store_code<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
-
-    let instantiate_permission =
-        compute_instantiate_permission(permit_instantiate_only, client.signer_account_id())?;
+    let client = ctx
+        .client(network_info.clone())
+        .to_signing_client(signing_key, account_prefix)
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, Some(contract_name)));
 
-    let wasm = read_wasm(
-        ctx.root()?,
-        contract_name.replace('-', "_").as_str(), // Handles file name mismatch
-        no_wasm_opt,
+    let instantiate_permission = compute_instantiate_permission(
+        permit_instantiate_only,
+        client.signer_account_id(),
+        &network_info,
     )?;
+
+    let from_artifact = matches!(source, WasmSource::Artifact);
+    let (wasm, git_commit) = match source {
+        WasmSource::Artifact => (
+            read_wasm(
+                ctx.root()?,
+                contract_name.replace('-', "_").as_str(), // Handles file name mismatch
+                no_wasm_opt,
+            )?,
+            head_commit(&ctx.root()?),
+        ),
+        // downloaded wasm is not built from the local tree
+        WasmSource::Url { url, checksum } => (download_wasm(url, checksum)?, None),
+        WasmSource::Code { wasm, git_commit } => (wasm, git_commit),
+    };
+    let checksum = checksum(&wasm);
+    // embedded by `wasm build`, otherwise the local build environment is the best known
+    let build_env = match BuildMetadata::read(&wasm).ok().flatten() {
+        Some(metadata) => Some(metadata),
+        None if from_artifact => Some(local_build_env(ctx, contract_name, no_wasm_opt)?),
+        None => None,
+    };
     let msg_store_code = MsgStoreCode {
         sender: client.signer_account_id(),
         wasm_byte_code: wasm,
@@ -52,7 +84,14 @@ pub fn store_code<'a, Ctx: Context<'a, WasmConfig>>(
     .to_any()
     .unwrap();
 
-    block(async {
+    let event = DeployEvent::new(
+        Action::Store,
+        network,
+        contract_name,
+        None,
+        client.signer_account_id().as_ref(),
+    );
+    let result = block(async {
         let response = client
             .sign_and_broadcast(vec![msg_store_code], gas, "", timeout_height)
             .await?;
@@ -68,15 +107,58 @@ pub fn store_code<'a, Ctx: Context<'a, WasmConfig>>(
         State::update_state_file(
             network_info.network_variant(),
             ctx.root()?,
-            &|s: &State| -> State { s.update_code_id(network, contract_name, &code_id) },
+            &|s: &State| -> State {
+                s.update_code_id(network, contract_name, &code_id)
+                    .record_history(
+                        network,
+                        contract_name,
+                        HistoryEntry::new(Action::Store, "", code_id)
+                            .with_tx_hash(&response.hash.to_string())
+                            .with_build(&checksum, git_commit.clone())
+                            .with_build_env(build_env.clone()),
+                    )
+            },
         )?;
         store_code_response.log();
 
-        Ok(store_code_response)
-    })
+        Ok((store_code_response, response.hash.to_string()))
+    });
+
+    notify(
+        &ctx.config()?.notifications,
+        &event.outcome(
+            result
+                .as_ref()
+                .map(|(r, tx_hash)| (r.code_id, None, tx_hash.as_str())),
+        ),
+    );
+    result.map(|(r, _)| r)
+}
+
+/// Build environment of local wasm without embedded metadata, built at the modification time of the file
+fn local_build_env<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    no_wasm_opt: &bool,
+) -> Result<BuildMetadata> {
+    let root = ctx.root()?;
+    let optimizer_image = if *no_wasm_opt {
+        None
+    } else {
+        Some(optimizer_image(&ctx.config()?.optimizer_version, &false))
+    };
+    let mut build_env = BuildMetadata::current(&root, optimizer_image)?;
+
+    let path =
+        wasm_dir(&root, no_wasm_opt).join(format!("{}.wasm", contract_name.replace('-', "_")));
+    if let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) {
+        build_env.built_at = modified.duration_since(UNIX_EPOCH)?.as_secs();
+    }
+    Ok(build_env)
 }
 
 #[allow(dead_code)]
+#[derive(Serialize)]
 pub struct StoreCodeResponse {
     pub code_id: u64,
     pub instantiate_permission: String,
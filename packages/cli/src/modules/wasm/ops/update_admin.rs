@@ -1,7 +1,9 @@
 use crate::attrs_format;
 use crate::modules::wasm::WasmConfig;
+use crate::support::address_book::resolve_address;
 use crate::support::future::block;
 use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
 use crate::support::ops_response::OpResponseDisplay;
 use anyhow::anyhow;
 use anyhow::Context as _;
@@ -16,6 +18,7 @@ use cosmrs::cosmwasm::MsgUpdateAdmin;
 
 use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::tx::Msg;
+use serde::Serialize;
 
 #[allow(clippy::too_many_arguments)]
 pub fn update_admin<'a, Ctx: Context<'a, WasmConfig>>(
@@ -37,8 +40,12 @@ pub fn update_admin<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let client = ctx
+        .client(network_info.clone())
+        .to_signing_client(signing_key, account_prefix)
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, Some(contract_name)));
 
+    let new_admin = resolve_address(&network_info, new_admin)?;
     let state = State::load_by_network(network_info, ctx.root()?)?;
     let contract = state
         .get_ref(network, contract_name)?
@@ -50,9 +57,7 @@ pub fn update_admin<'a, Ctx: Context<'a, WasmConfig>>(
 
     let msg_update_admin = MsgUpdateAdmin {
         sender: client.signer_account_id(),
-        new_admin: new_admin
-            .parse()
-            .map_err(|e: cosmrs::ErrorReport| anyhow!(e))?,
+        new_admin: new_admin.clone(),
         contract: contract.clone(),
     }
     .to_any()
@@ -75,6 +80,7 @@ pub fn update_admin<'a, Ctx: Context<'a, WasmConfig>>(
 }
 
 #[allow(dead_code)]
+#[derive(Serialize)]
 pub struct UpdateAdminResponse {
     pub contract: String,
     pub new_admin: String,
@@ -1,6 +1,7 @@
 use crate::framework::Context;
 use crate::modules::wasm::WasmConfig;
 use crate::support::gas::Gas;
+use crate::support::wasm::WasmSource;
 use anyhow::Result;
 
 use cosmrs::crypto::secp256k1::SigningKey;
@@ -36,6 +37,7 @@ pub fn upgrade<'a, Ctx: Context<'a, WasmConfig>>(
         gas,
         timeout_height,
         store_code_signing_key,
+        WasmSource::Artifact,
     )?;
     migrate(
         ctx,
@@ -49,5 +51,6 @@ pub fn upgrade<'a, Ctx: Context<'a, WasmConfig>>(
         timeout_height,
         gas,
         instantiate_signing_key,
+        false,
     )
 }
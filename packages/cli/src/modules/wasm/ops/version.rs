@@ -0,0 +1,128 @@
+use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::framework::Context;
+use crate::modules::wasm::config::WasmConfig;
+use crate::modules::wasm::ops::history::resolve_contract;
+use crate::support::future::block;
+use crate::support::output;
+use crate::support::state::State;
+
+/// Storage key of cw2's `CONTRACT` item
+pub const CW2_CONTRACT_INFO_KEY: &[u8] = b"contract_info";
+
+/// Value of cw2's `contract_info` item
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
+pub struct Cw2ContractVersion {
+    pub contract: String,
+    pub version: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ContractVersion {
+    pub contract: String,
+    pub label: String,
+    pub address: String,
+    /// `None` if the contract does not set cw2 contract info
+    pub cw2: Option<Cw2ContractVersion>,
+}
+
+fn decode_cw2(raw: Option<Vec<u8>>) -> Result<Option<Cw2ContractVersion>> {
+    raw.map(|raw| {
+        serde_json::from_slice(&raw).with_context(|| "Unable to decode cw2 `contract_info`")
+    })
+    .transpose()
+}
+
+/// cw2 contract name and version of `contract`, or of every contract deployed on `network` if not given
+pub fn version<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract: Option<&String>,
+    label: &str,
+    network: &str,
+) -> Result<Vec<ContractVersion>> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+
+    let targets = match contract {
+        Some(contract) => vec![(
+            contract.clone(),
+            label.to_string(),
+            resolve_contract(ctx, contract, label, network)?,
+        )],
+        None => State::load_by_network(network_info.clone(), ctx.root()?)?
+            .contracts(network)
+            .into_iter()
+            .flat_map(|(name, wasm_ref)| {
+                wasm_ref
+                    .addresses()
+                    .iter()
+                    .map(|(label, address)| (name.clone(), label.clone(), address.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect(),
+    };
+
+    let client = ctx.client(network_info);
+    let versions = block(async {
+        let mut versions = vec![];
+        for (contract, label, address) in targets {
+            let raw = client
+                .query_raw(address.clone(), CW2_CONTRACT_INFO_KEY.to_vec())
+                .await
+                .with_context(|| format!("Unable to query contract info of {contract}:{label}"))?;
+            versions.push(ContractVersion {
+                contract,
+                label,
+                address,
+                cw2: decode_cw2(raw)?,
+            });
+        }
+        anyhow::Ok(versions)
+    })?;
+
+    output::print(&versions, || {
+        if versions.is_empty() {
+            return format!("No contracts deployed on `{network}`");
+        }
+        versions
+            .iter()
+            .map(|v| {
+                let cw2 = v
+                    .cw2
+                    .as_ref()
+                    .map(|c| format!("{} {}", c.contract, c.version))
+                    .unwrap_or_else(|| "– (no cw2 contract info)".to_string());
+                format!("{}:{}  {}  {cw2}", v.contract, v.label, v.address)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_cw2_contract_info() {
+        assert_eq!(
+            decode_cw2(Some(
+                br#"{"contract":"crates.io:counter","version":"0.2.0"}"#.to_vec()
+            ))
+            .unwrap(),
+            Some(Cw2ContractVersion {
+                contract: "crates.io:counter".to_string(),
+                version: "0.2.0".to_string(),
+            })
+        );
+        assert_eq!(decode_cw2(None).unwrap(), None);
+        assert!(decode_cw2(Some(b"not json".to_vec())).is_err());
+    }
+}
@@ -1,8 +1,9 @@
+use anyhow::{bail, Context as _};
 use clap::Subcommand;
 use std::path::PathBuf;
 
 use crate::{
-    framework::Context,
+    framework::{config::NetworkVariant, Context},
     modules::wasm::{args::BaseTxArgs, WasmConfig},
     support::gas::Gas,
 };
@@ -27,6 +28,10 @@ pub enum ProposalCmd {
         #[clap(flatten)]
         store_code_proposal: StoreCodeProposal,
 
+        /// Vote yes with the localnet validator and wait for the proposal to pass, only available on local networks
+        #[clap(long)]
+        auto_vote: bool,
+
         #[clap(flatten)]
         base_tx_args: BaseTxArgs,
     },
@@ -70,6 +75,7 @@ pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
             permit_instantiate_only,
             proposal,
             store_code_proposal,
+            auto_vote,
             base_tx_args,
         } => {
             let proposal = if let Some(p) = proposal {
@@ -98,6 +104,22 @@ pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
                 timeout_height,
             }: &BaseTxArgs = base_tx_args;
 
+            let global_conf = ctx.global_config()?;
+            if *auto_vote {
+                let network_info = global_conf
+                    .networks()
+                    .get(network)
+                    .with_context(|| format!("Unable to find network config: {network}"))?;
+                if *network_info.network_variant() != NetworkVariant::Local {
+                    bail!("`--auto-vote` is only available on local networks");
+                }
+            }
+            let gas = Gas::from_args(
+                gas_args,
+                global_conf.network_gas_price(network),
+                global_conf.gas_adjustment(),
+            )?;
+
             super::ops::propose_store_code(
                 &ctx,
                 contract_name,
@@ -105,18 +127,14 @@ pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
                 store_code_proposal.description_with_metadata()?.as_str(),
                 deposit.as_ref().map(|s| s.as_str()).try_into()?,
                 network,
-                {
-                    let global_conf = ctx.global_config()?;
-                    &Gas::from_args(
-                        gas_args,
-                        global_conf.gas_price(),
-                        global_conf.gas_adjustment(),
-                    )?
-                },
+                &gas,
                 permit_instantiate_only,
                 timeout_height,
-                signer_args.private_key(&ctx.global_config()?)?,
+                signer_args.private_key(&ctx.chain_client(), &global_conf, network)?,
             )?;
+            if *auto_vote {
+                super::ops::auto_vote(&ctx, contract_name, network, &gas, timeout_height)?;
+            }
             Ok(())
         }
         ProposalCmd::Query { cmd } => match cmd {
@@ -149,12 +167,12 @@ pub fn execute<'a, Ctx: Context<'a, WasmConfig>>(
                     let global_conf = ctx.global_config()?;
                     &Gas::from_args(
                         gas_args,
-                        global_conf.gas_price(),
+                        global_conf.network_gas_price(network),
                         global_conf.gas_adjustment(),
                     )?
                 },
                 timeout_height,
-                signer_args.private_key(&ctx.global_config()?)?,
+                signer_args.private_key(&ctx.chain_client(), &ctx.global_config()?, network)?,
             )?;
             Ok(())
         }
@@ -4,4 +4,4 @@ pub mod vote;
 
 pub use propose::propose_store_code;
 pub use query::query_proposal;
-pub use vote::vote;
+pub use vote::{auto_vote, vote};
@@ -3,6 +3,10 @@ use crate::support::coin::Coins;
 use crate::support::cosmos::ResponseValuePicker;
 use crate::support::future::block;
 use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
+use crate::support::gov::{
+    gov_module_address, MsgSubmitProposalV1, MSG_SUBMIT_PROPOSAL_V1_TYPE_URL,
+};
 use crate::support::ops_response::OpResponseDisplay;
 use crate::support::permission::compute_instantiate_permission;
 use crate::support::proto::MessageExt;
@@ -13,6 +17,7 @@ use anyhow::{Context as _, Result};
 use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgSubmitProposal;
 use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::Any;
+use serde::Serialize;
 use std::vec;
 
 #[allow(clippy::too_many_arguments)]
@@ -38,36 +43,70 @@ pub fn propose_store_code<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let query_client = ctx.client(network_info.clone());
+    let client = query_client
+        .to_signing_client(signing_key, account_prefix)
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, Some(contract_name)));
+    let features = block(query_client.features())?;
+    features.require_wasm_proposals()?;
 
     let wasm = read_wasm(
         ctx.root()?,
         contract_name.replace('-', "_").as_str(), // Handles file name mismatch
         no_wasm_opt,
     )?;
-    let instantiate_permission =
-        compute_instantiate_permission(permit_instantiate_only, client.signer_account_id())?;
-
-    let store_code_proposal = cosmrs::proto::cosmwasm::wasm::v1::StoreCodeProposal {
-        title: title.to_string(),
-        description: description.to_string(),
-        run_as: client.signer_account_id().to_string(),
-        wasm_byte_code: wasm,
-        instantiate_permission: instantiate_permission.clone().map(|ac| ac.into()),
-    };
+    let instantiate_permission = compute_instantiate_permission(
+        permit_instantiate_only,
+        client.signer_account_id(),
+        &network_info,
+    )?;
 
-    let msg_submit_proposal = MsgSubmitProposal {
-        content: Some(Any {
-            type_url: "/cosmwasm.wasm.v1.StoreCodeProposal".to_owned(),
-            value: store_code_proposal.to_bytes()?,
-        }),
-        initial_deposit: deposit.into(),
-        proposer: client.signer_account_id().to_string(),
-    };
+    let msg_submit_proposal = if features.legacy_wasm_proposals() {
+        let store_code_proposal = cosmrs::proto::cosmwasm::wasm::v1::StoreCodeProposal {
+            title: title.to_string(),
+            description: description.to_string(),
+            run_as: client.signer_account_id().to_string(),
+            wasm_byte_code: wasm,
+            instantiate_permission: instantiate_permission.clone().map(|ac| ac.into()),
+        };
+
+        let msg_submit_proposal = MsgSubmitProposal {
+            content: Some(Any {
+                type_url: "/cosmwasm.wasm.v1.StoreCodeProposal".to_owned(),
+                value: store_code_proposal.to_bytes()?,
+            }),
+            initial_deposit: deposit.into(),
+            proposer: client.signer_account_id().to_string(),
+        };
+
+        Any {
+            type_url: "/cosmos.gov.v1beta1.MsgSubmitProposal".to_owned(),
+            value: msg_submit_proposal.to_bytes()?,
+        }
+    } else {
+        // legacy proposal contents are removed, gov executes `MsgStoreCode` as its authority instead
+        let msg_store_code = cosmrs::proto::cosmwasm::wasm::v1::MsgStoreCode {
+            sender: gov_module_address(account_prefix)?.to_string(),
+            wasm_byte_code: wasm,
+            instantiate_permission: instantiate_permission.clone().map(|ac| ac.into()),
+        };
+
+        let msg_submit_proposal = MsgSubmitProposalV1 {
+            messages: vec![Any {
+                type_url: "/cosmwasm.wasm.v1.MsgStoreCode".to_owned(),
+                value: msg_store_code.to_bytes()?,
+            }],
+            initial_deposit: deposit.into(),
+            proposer: client.signer_account_id().to_string(),
+            metadata: String::new(),
+            title: title.to_string(),
+            summary: description.to_string(),
+        };
 
-    let msg_submit_proposal = Any {
-        type_url: "/cosmos.gov.v1beta1.MsgSubmitProposal".to_owned(),
-        value: msg_submit_proposal.to_bytes()?,
+        Any {
+            type_url: MSG_SUBMIT_PROPOSAL_V1_TYPE_URL.to_owned(),
+            value: msg_submit_proposal.to_bytes()?,
+        }
     };
 
     block(async {
@@ -110,6 +149,7 @@ pub fn propose_store_code<'a, Ctx: Context<'a, WasmConfig>>(
 }
 
 #[allow(dead_code)]
+#[derive(Serialize)]
 pub struct ProposeStoreCodeResponse {
     pub proposal_id: u64,
     pub deposit_amount: String,
@@ -1,4 +1,5 @@
 use crate::support::future::block;
+use crate::support::output;
 use crate::support::state::State;
 use crate::vars_format;
 use crate::{framework::Context, modules::wasm::WasmConfig, support::cosmos::Client};
@@ -21,7 +22,7 @@ pub fn query_proposal<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone());
+    let client = ctx.client(network_info.clone());
 
     let state = State::load_by_network(network_info, ctx.root()?)?;
     let wasm_ref = state.get_ref(network, contract_name)?;
@@ -117,8 +118,28 @@ pub fn query_proposal<'a, Ctx: Context<'a, WasmConfig>>(
             datetime_str(ts.seconds, ts.nanos)
         };
 
-        println!(
-            "{}",
+        let summary = serde_json::json!({
+            "proposal_id": proposal_id,
+            "title": title,
+            "description": description,
+            "run_as": run_as,
+            "total_deposit": total_deposit,
+            "status": status,
+            "tally_result": {
+                "yes": yes,
+                "no": no,
+                "no_with_veto": no_with_veto,
+                "abstain": abstain,
+            },
+            "time": {
+                "submit_time": submit_time,
+                "deposit_end_time": deposit_end_time,
+                "voting_start_time": voting_start_time,
+                "voting_end_time": voting_end_time,
+            },
+        });
+
+        output::print(&summary, || {
             vec![
                 vars_format!(
                     "Proposal found!",
@@ -140,7 +161,7 @@ pub fn query_proposal<'a, Ctx: Context<'a, WasmConfig>>(
             ]
             .concat()
             .join("\n")
-        );
+        });
 
         Ok(res)
     })
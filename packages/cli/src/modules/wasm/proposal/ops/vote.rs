@@ -1,18 +1,29 @@
 use crate::attrs_format;
+use crate::modules::localnet::genesis::LOCALNET_VOTING_PERIOD;
 use crate::support::cosmos::ResponseValuePicker;
 use crate::support::future::block;
 use crate::support::gas::Gas;
+use crate::support::gas_ledger::GasLedger;
+use crate::support::gov::{status_name, vote_option, wait_for_proposal};
 use crate::support::ops_response::OpResponseDisplay;
 use crate::support::proto::MessageExt;
+use crate::support::signer::network_account_key;
 use crate::support::state::State;
 use crate::{framework::Context, modules::wasm::WasmConfig, support::cosmos::Client};
-use anyhow::anyhow;
-use anyhow::{Context as _, Result};
+use anyhow::{bail, Context as _, Result};
+use cosmos_sdk_proto::cosmos::gov::v1beta1::ProposalStatus;
 use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::Any;
-use std::str::FromStr;
+use serde::Serialize;
+use std::time::Duration;
 use std::vec;
 
+/// Account that holds the voting power on localnet
+const LOCALNET_VALIDATOR_ACCOUNT: &str = "validator";
+
+/// Longest voting period to wait for on localnet, the generated genesis sets `LOCALNET_VOTING_PERIOD`
+const AUTO_VOTE_TIMEOUT: Duration = Duration::from_secs(LOCALNET_VOTING_PERIOD.as_secs() * 3);
+
 #[allow(clippy::too_many_arguments)]
 pub fn vote<'a, Ctx: Context<'a, WasmConfig>>(
     ctx: &Ctx,
@@ -32,7 +43,10 @@ pub fn vote<'a, Ctx: Context<'a, WasmConfig>>(
         .with_context(|| format!("Unable to find network config: {network}"))?
         .to_owned();
 
-    let client = Client::new(network_info.clone()).to_signing_client(signing_key, account_prefix);
+    let client = ctx
+        .client(network_info.clone())
+        .to_signing_client(signing_key, account_prefix)
+        .with_gas_ledger(GasLedger::new(ctx.root()?, network, Some(contract_name)));
 
     let state = State::load_by_network(network_info, ctx.root()?)?;
     let proposal_id = state
@@ -41,8 +55,7 @@ pub fn vote<'a, Ctx: Context<'a, WasmConfig>>(
         .store_code()
         .with_context(|| format!("Unable to retrieve proposal_id for {contract_name}"))?;
 
-    let option = option.parse::<VoteOptionImpl>()?;
-    let option = cosmrs::proto::cosmos::gov::v1beta1::VoteOption::from(option);
+    let option = vote_option(option)?;
 
     let msg_vote = cosmrs::proto::cosmos::gov::v1beta1::MsgVote {
         proposal_id,
@@ -73,37 +86,68 @@ pub fn vote<'a, Ctx: Context<'a, WasmConfig>>(
     })
 }
 
-struct VoteOptionImpl(cosmrs::proto::cosmos::gov::v1beta1::VoteOption);
-
-impl From<cosmrs::proto::cosmos::gov::v1beta1::VoteOption> for VoteOptionImpl {
-    fn from(v: cosmrs::proto::cosmos::gov::v1beta1::VoteOption) -> Self {
-        VoteOptionImpl(v)
-    }
-}
+/// Vote yes on the contract's latest proposal with the localnet validator account,
+/// then wait until the (short) voting period of the localnet ends
+pub fn auto_vote<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    contract_name: &str,
+    network: &str,
+    gas: &Gas,
+    timeout_height: &u32,
+) -> Result<AutoVoteResponse> {
+    let global_config = ctx.global_config()?;
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
 
-impl From<VoteOptionImpl> for cosmrs::proto::cosmos::gov::v1beta1::VoteOption {
-    fn from(v: VoteOptionImpl) -> Self {
-        let VoteOptionImpl(vo) = v;
-        vo
+    let validator = global_config
+        .accounts()
+        .get(LOCALNET_VALIDATOR_ACCOUNT)
+        .with_context(|| {
+            format!("Auto vote requires `{LOCALNET_VALIDATOR_ACCOUNT}` account holding the localnet voting power")
+        })?;
+    let validator_key = network_account_key(
+        &ctx.chain_client(),
+        &global_config,
+        validator,
+        &network_info,
+    )?;
+
+    let VoteResponse { proposal_id } = vote(
+        ctx,
+        contract_name,
+        "yes",
+        network,
+        gas,
+        timeout_height,
+        validator_key,
+    )?;
+
+    let proposal = block(wait_for_proposal(
+        &ctx.client(network_info),
+        proposal_id,
+        Duration::from_secs(1),
+        AUTO_VOTE_TIMEOUT,
+    ))?;
+    if ProposalStatus::from_i32(proposal.status) != Some(ProposalStatus::Passed) {
+        bail!(
+            "Proposal {proposal_id} did not pass: {}",
+            status_name(proposal.status)
+        );
     }
-}
 
-impl FromStr for VoteOptionImpl {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use cosmrs::proto::cosmos::gov::v1beta1::VoteOption;
+    let auto_vote_response = AutoVoteResponse {
+        proposal_id,
+        status: status_name(proposal.status).to_string(),
+    };
+    auto_vote_response.log();
 
-        match s {
-            "yes" => Ok(VoteOption::Yes.into()),
-            "no" => Ok(VoteOption::No.into()),
-            "no_with_veto" => Ok(VoteOption::NoWithVeto.into()),
-            "abstain" => Ok(VoteOption::Abstain.into()),
-            o => Err(anyhow!("Invalid vote option: {o}")),
-        }
-    }
+    Ok(auto_vote_response)
 }
 
+#[derive(Serialize)]
 pub struct VoteResponse {
     pub proposal_id: u64,
 }
@@ -116,3 +160,18 @@ impl OpResponseDisplay for VoteResponse {
         attrs_format! { self | proposal_id }
     }
 }
+
+#[derive(Serialize)]
+pub struct AutoVoteResponse {
+    pub proposal_id: u64,
+    pub status: String,
+}
+
+impl OpResponseDisplay for AutoVoteResponse {
+    fn headline() -> &'static str {
+        "Proposal passed on localnet!! 🎉"
+    }
+    fn attrs(&self) -> Vec<String> {
+        attrs_format! { self | proposal_id, status }
+    }
+}
@@ -16,7 +16,7 @@ impl Default for WorkspaceConfig {
             template: Template::new(
                 "workspace-template".to_string(),
                 "https://github.com/osmosis-labs/beaker.git".to_string(),
-                "main".to_string(),
+                Some("main".to_string()),
                 Some("templates/project".to_string()),
                 PathBuf::from("."),
             ),
@@ -19,6 +19,13 @@ pub enum WorkspaceCmd {
         #[clap(short, long)]
         branch: Option<String>,
     },
+    /// Create `Beaker.toml` for the project in the current directory
+    Init {
+        /// Scan the existing repository for contracts, schemas, optimizer version and deploy scripts to configure
+        /// beaker, and import contract addresses and code ids found in json and env files into beaker's state
+        #[clap(long)]
+        from_existing: bool,
+    },
 }
 
 #[derive(new)]
@@ -35,6 +42,10 @@ impl<'a> Module<'a, WorkspaceConfig, WorkspaceCmd, anyhow::Error> for WorkspaceM
                 target_dir,
                 branch,
             } => ops::new(ctx, name, branch, target_dir),
+            WorkspaceCmd::Init { from_existing } => {
+                ops::init(&ctx, *from_existing)?;
+                Ok(())
+            }
         }
     }
 }
@@ -0,0 +1,641 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context as _, Result};
+use cosmrs::AccountId;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::framework::config::Network;
+use crate::framework::Context;
+use crate::modules::wasm::WasmConfig;
+use crate::modules::workspace::config::WorkspaceConfig;
+use crate::support::cargo_workspace::member_dirs;
+use crate::support::ops_response::OpResponseDisplay;
+use crate::support::state::{State, STATE_DIR};
+
+/// Directories never scanned for addresses and scripts
+const SKIPPED_DIRS: &[&str] = &[
+    ".git",
+    "target",
+    "node_modules",
+    STATE_DIR,
+    "schema",
+    "dist",
+    "build",
+];
+
+/// Deepest directory level scanned below the repository root
+const MAX_SCAN_DEPTH: usize = 6;
+
+/// Files bigger than this are not scanned
+const MAX_SCANNED_FILE_BYTES: u64 = 2_000_000;
+
+/// Label of contracts found in the repository
+const DEFAULT_LABEL: &str = "default";
+
+const SCRIPT_EXTENSIONS: &[&str] = &["sh", "bash", "js", "mjs", "ts", "py"];
+const SCRIPT_NAMES: &[&str] = &["Makefile", "justfile", "Justfile"];
+
+/// Contract deployment found in the repository
+#[derive(Serialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct FoundDeployment {
+    pub network: String,
+    pub contract: String,
+    pub code_id: Option<u64>,
+    pub address: Option<String>,
+    /// File it is found in, relative to the repository root
+    pub source: String,
+}
+
+/// Contract crate of the workspace, built into wasm
+struct ContractCrate {
+    name: String,
+    dir: PathBuf,
+}
+
+/// `snake_case` of `camelCase`, `kebab-case` and `SCREAMING_CASE` names
+fn normalize(name: &str) -> String {
+    let mut normalized = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && prev_lower {
+            normalized.push('_');
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        normalized.push(if c == '-' {
+            '_'
+        } else {
+            c.to_ascii_lowercase()
+        });
+    }
+    normalized
+}
+
+/// Contract `key` refers to, eg. `counter`, `counterAddress`, `COUNTER_CONTRACT` or `NEXT_PUBLIC_COUNTER_ADDRESS`
+fn match_contract<'c>(key: &str, contracts: &'c [String]) -> Option<&'c str> {
+    let mut key = normalize(key);
+    for prefix in ["next_public_", "react_app_", "vite_"] {
+        if let Some(rest) = key.strip_prefix(prefix) {
+            key = rest.to_string();
+        }
+    }
+    for suffix in [
+        "_contract_address",
+        "_address",
+        "_addr",
+        "_contract",
+        "_code_id",
+    ] {
+        if let Some(rest) = key.strip_suffix(suffix) {
+            key = rest.to_string();
+            break;
+        }
+    }
+    contracts
+        .iter()
+        .find(|c| normalize(c) == key)
+        .map(String::as_str)
+}
+
+fn is_code_id_key(key: &str) -> bool {
+    matches!(normalize(key).as_str(), "code_id" | "code_ids" | "codes")
+        || normalize(key).ends_with("_code_id")
+}
+
+/// Contract addresses are 32 bytes, unlike 20 bytes account addresses
+fn is_contract_address(s: &str) -> bool {
+    s.parse::<AccountId>()
+        .map(|id| id.to_bytes().len() == 32)
+        .unwrap_or(false)
+}
+
+/// Configured network that any of `segments` (path components, json keys) names by its name or chain id
+fn match_network<'n>(segments: &[String], networks: &'n [(String, Network)]) -> Option<&'n str> {
+    segments.iter().rev().find_map(|segment| {
+        let segment = segment.to_lowercase();
+        networks
+            .iter()
+            .find(|(name, network)| {
+                segment == *name
+                    || segment
+                        .split(|c: char| c == '.' || c == '_')
+                        .any(|token| token == name || token == network.chain_id())
+                    || segment.contains(network.chain_id())
+            })
+            .map(|(name, _)| name.as_str())
+    })
+}
+
+/// Json keys leading to the value, contract name, code id and address
+type JsonFinding = (Vec<String>, String, Option<u64>, Option<String>);
+
+/// Collect addresses and code ids of `contracts` in json `value`, found under `path` of keys
+fn collect_json(
+    value: &Value,
+    path: &mut Vec<String>,
+    contract: Option<&str>,
+    contracts: &[String],
+    found: &mut Vec<JsonFinding>,
+) {
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                let contract = match_contract(key, contracts).or(contract);
+                let code_id_key = is_code_id_key(key) || path.iter().any(|p| is_code_id_key(p));
+                match (value, contract) {
+                    (Value::String(s), Some(contract)) if is_contract_address(s) => {
+                        found.push((path.clone(), contract.to_string(), None, Some(s.clone())))
+                    }
+                    (Value::Number(n), Some(contract)) if code_id_key => {
+                        found.push((path.clone(), contract.to_string(), n.as_u64(), None))
+                    }
+                    (Value::String(s), Some(contract)) if code_id_key => {
+                        if let Ok(code_id) = s.parse::<u64>() {
+                            found.push((path.clone(), contract.to_string(), Some(code_id), None))
+                        }
+                    }
+                    _ => {
+                        path.push(key.clone());
+                        collect_json(value, path, contract, contracts, found);
+                        path.pop();
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_json(item, path, contract, contracts, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collect `<CONTRACT>_ADDRESS=...` and `<CONTRACT>_CODE_ID=...` variables of an env file
+fn collect_env(content: &str, contracts: &[String]) -> Vec<(String, Option<u64>, Option<String>)> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().trim_start_matches("export ").split_once('='))
+        .filter_map(|(key, value)| {
+            let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+            let contract = match_contract(key.trim(), contracts)?.to_string();
+            if is_code_id_key(key.trim()) {
+                value
+                    .parse::<u64>()
+                    .ok()
+                    .map(|code_id| (contract, Some(code_id), None))
+            } else if is_contract_address(value) {
+                Some((contract, None, Some(value.to_string())))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn scanned_files(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    let mut entries = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .collect::<Vec<_>>(),
+        Err(_) => return,
+    };
+    entries.sort();
+    for path in entries {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if path.is_dir() {
+            if depth < MAX_SCAN_DEPTH && !SKIPPED_DIRS.contains(&name.as_str()) {
+                scanned_files(&path, depth + 1, files);
+            }
+        } else if fs::metadata(&path)
+            .map(|m| m.len() <= MAX_SCANNED_FILE_BYTES)
+            .unwrap_or(false)
+        {
+            files.push(path);
+        }
+    }
+}
+
+/// Contract crates of the workspace at `root`, ie. members built as `cdylib`
+fn contract_crates(root: &Path) -> Result<Vec<ContractCrate>> {
+    let mut contracts = vec![];
+    for dir in member_dirs(root)? {
+        let manifest: toml::Value = toml::from_str(&fs::read_to_string(dir.join("Cargo.toml"))?)
+            .with_context(|| format!("Invalid `{}/Cargo.toml`", dir.display()))?;
+        let is_cdylib = manifest
+            .get("lib")
+            .and_then(|l| l.get("crate-type"))
+            .and_then(|t| t.as_array())
+            .map(|types| types.iter().any(|t| t.as_str() == Some("cdylib")))
+            .unwrap_or(false);
+        let name = manifest
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str());
+        if let (true, Some(name)) = (is_cdylib, name) {
+            contracts.push(ContractCrate {
+                name: name.to_string(),
+                dir,
+            });
+        }
+    }
+    Ok(contracts)
+}
+
+/// Parent directory of most contracts, relative to `root`
+fn common_contract_dir(root: &Path, contracts: &[ContractCrate]) -> Option<String> {
+    let mut counts = BTreeMap::<String, usize>::new();
+    for parent in contracts
+        .iter()
+        .filter_map(|c| c.dir.parent()?.strip_prefix(root).ok())
+    {
+        *counts
+            .entry(parent.to_string_lossy().to_string())
+            .or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by_key(|(_, n)| *n)
+        .map(|(dir, _)| dir)
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Create `Beaker.toml` for the project in the current directory, with `from_existing`
+/// the repository is scanned for its contracts, build setup and deployed addresses
+pub fn init<'a, Ctx: Context<'a, WorkspaceConfig>>(
+    ctx: &Ctx,
+    from_existing: bool,
+) -> Result<InitResponse> {
+    let root = std::env::current_dir()?;
+    let config_file = root.join(ctx.config_file_name());
+    if config_file.exists() {
+        bail!("`{}` already exists", config_file.display());
+    }
+    let name = root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .with_context(|| "Unable to determine project name from the current directory")?;
+
+    let mut config = format!("name = {}\n", toml::Value::String(name.clone()));
+    let mut response = InitResponse {
+        config_file: config_file.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    if from_existing {
+        if !root.join("Cargo.toml").exists() {
+            bail!("No `Cargo.toml` found, run `beaker init --from-existing` at the root of a CosmWasm repository");
+        }
+        let contracts = contract_crates(&root)?;
+        if contracts.is_empty() {
+            bail!("No contract crates (with `crate-type = [\"cdylib\"]`) found in the workspace");
+        }
+        let contract_names = contracts.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+        let default_wasm = WasmConfig::default();
+        let mut wasm_config = vec![];
+
+        let contract_dir = common_contract_dir(&root, &contracts)
+            .unwrap_or_else(|| default_wasm.contract_dir.clone());
+        if contract_dir != default_wasm.contract_dir {
+            wasm_config.push(format!(
+                "contract_dir = {}",
+                toml::Value::String(contract_dir.clone())
+            ));
+        }
+        for contract in &contracts {
+            let parent = contract.dir.parent().filter(|p| p.starts_with(&root));
+            if parent.map(|p| relative(&root, p)).as_deref() != Some(contract_dir.as_str()) {
+                response.notes.push(format!(
+                    "`{}` is outside of `{contract_dir}`, move it there for beaker to find it",
+                    contract.name
+                ));
+            }
+            if contract.dir.join("schema").exists() {
+                response
+                    .schemas
+                    .push(relative(&root, &contract.dir.join("schema")));
+            } else {
+                response.notes.push(format!(
+                    "`{}` has no `schema` dir, generate it with `cargo schema` to validate and build msgs",
+                    contract.name
+                ));
+            }
+        }
+
+        let mut files = vec![];
+        scanned_files(&root, 0, &mut files);
+
+        let optimizer =
+            Regex::new(r"cosmwasm/(?:rust|workspace)-optimizer(?:-arm64)?:(\d+\.\d+\.\d+)")
+                .unwrap();
+        let deploy_script = Regex::new(
+            r"tx wasm (?:store|instantiate|migrate)|MsgStoreCode|MsgInstantiateContract|\.upload\(|\.instantiate\(",
+        )
+        .unwrap();
+        let global_config = ctx.global_config()?;
+        let networks = global_config
+            .networks()
+            .iter()
+            .map(|(name, network)| (name.clone(), network.clone()))
+            .collect::<Vec<_>>();
+
+        let mut found = vec![];
+        for file in &files {
+            let file_name = file
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let extension = file
+                .extension()
+                .map(|e| e.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let content = match fs::read_to_string(file) {
+                Ok(content) => content,
+                Err(_) => continue,
+            };
+            let source = relative(&root, file);
+            let segments = source.split('/').map(|s| s.to_string()).collect::<Vec<_>>();
+
+            if response.optimizer_version.is_none() {
+                response.optimizer_version = optimizer.captures(&content).map(|c| c[1].to_string());
+            }
+            if (SCRIPT_EXTENSIONS.contains(&extension.as_str())
+                || SCRIPT_NAMES.contains(&file_name.as_str()))
+                && deploy_script.is_match(&content)
+            {
+                response.deploy_scripts.push(source.clone());
+            }
+
+            let mut entries = vec![];
+            if extension == "json"
+                && !matches!(
+                    file_name.as_str(),
+                    "package.json" | "package-lock.json" | "tsconfig.json"
+                )
+            {
+                if let Ok(json) = serde_json::from_str::<Value>(&content) {
+                    let mut json_found = vec![];
+                    collect_json(&json, &mut vec![], None, &contract_names, &mut json_found);
+                    entries.extend(json_found.into_iter().map(
+                        |(path, contract, code_id, address)| {
+                            (
+                                [segments.clone(), path].concat(),
+                                contract,
+                                code_id,
+                                address,
+                            )
+                        },
+                    ));
+                }
+            } else if file_name.starts_with(".env") || extension == "env" {
+                entries.extend(collect_env(&content, &contract_names).into_iter().map(
+                    |(contract, code_id, address)| (segments.clone(), contract, code_id, address),
+                ));
+            }
+
+            for (segments, contract, code_id, address) in entries {
+                match match_network(&segments, &networks) {
+                    Some(network) => found.push(FoundDeployment {
+                        network: network.to_string(),
+                        contract,
+                        code_id,
+                        address,
+                        source: source.clone(),
+                    }),
+                    None => response.notes.push(format!(
+                        "`{contract}` deployment in `{source}` is skipped, its network is not recognized"
+                    )),
+                }
+            }
+        }
+        response.notes.dedup();
+
+        if let Some(version) = &response.optimizer_version {
+            if *version != default_wasm.optimizer_version {
+                wasm_config.push(format!(
+                    "optimizer_version = {}",
+                    toml::Value::String(version.clone())
+                ));
+            }
+        }
+        if !wasm_config.is_empty() {
+            config.push_str(&format!("\n[wasm]\n{}\n", wasm_config.join("\n")));
+        }
+
+        // first occurrence of an address or code id wins, later conflicting ones are reported
+        let mut deployments = BTreeMap::<(String, String), FoundDeployment>::new();
+        for f in found {
+            let entry = deployments
+                .entry((f.network.clone(), f.contract.clone()))
+                .or_insert_with(|| FoundDeployment {
+                    network: f.network.clone(),
+                    contract: f.contract.clone(),
+                    source: f.source.clone(),
+                    ..Default::default()
+                });
+            match (&entry.address, &f.address) {
+                (None, Some(_)) => entry.address = f.address.clone(),
+                (Some(a), Some(b)) if a != b => response.notes.push(format!(
+                    "`{}` on `{}` has another address `{b}` in `{}`, kept `{a}`",
+                    f.contract, f.network, f.source
+                )),
+                _ => {}
+            }
+            if entry.code_id.is_none() {
+                entry.code_id = f.code_id;
+            }
+        }
+
+        for deployment in deployments.values() {
+            let network_info = global_config
+                .networks()
+                .get(&deployment.network)
+                .with_context(|| {
+                    format!("Unable to find network config: {}", deployment.network)
+                })?;
+            State::update_state_file(
+                network_info.network_variant(),
+                root.clone(),
+                &|s: &State| {
+                    let wasm_ref = s
+                        .get_ref(&deployment.network, &deployment.contract)
+                        .unwrap_or_default();
+                    let wasm_ref = match deployment.code_id {
+                        Some(code_id) => wasm_ref.with_code_id(Some(code_id)),
+                        None => wasm_ref,
+                    };
+                    let wasm_ref = match &deployment.address {
+                        Some(address) => wasm_ref.with_address(DEFAULT_LABEL, address),
+                        None => wasm_ref,
+                    };
+                    s.insert_ref(&deployment.network, &deployment.contract, wasm_ref)
+                },
+            )?;
+        }
+
+        response.contracts = contract_names;
+        response.deployments = deployments.into_values().collect();
+    }
+
+    fs::write(&config_file, config)
+        .with_context(|| format!("Unable to write `{}`", config_file.display()))?;
+    response.log();
+
+    Ok(response)
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct InitResponse {
+    pub config_file: String,
+    pub contracts: Vec<String>,
+    pub schemas: Vec<String>,
+    pub optimizer_version: Option<String>,
+    /// Scripts that store, instantiate or migrate contracts, which beaker commands can replace
+    pub deploy_scripts: Vec<String>,
+    /// Deployments recorded in beaker's state
+    pub deployments: Vec<FoundDeployment>,
+    /// What to check or fix by hand
+    pub notes: Vec<String>,
+}
+
+impl OpResponseDisplay for InitResponse {
+    fn headline() -> &'static str {
+        "Beaker initialized successfully!! 🎉 "
+    }
+    fn attrs(&self) -> Vec<String> {
+        let list = |items: &[String]| {
+            if items.is_empty() {
+                "none".to_string()
+            } else {
+                items.join(", ")
+            }
+        };
+        let mut attrs = vec![
+            format!("    ├── config_file: {}", self.config_file),
+            format!("    ├── contracts: {}", list(&self.contracts)),
+            format!("    ├── schemas: {}", list(&self.schemas)),
+            format!(
+                "    ├── optimizer_version: {}",
+                self.optimizer_version.as_deref().unwrap_or("not found")
+            ),
+            format!("    ├── deploy_scripts: {}", list(&self.deploy_scripts)),
+            "    ├── deployments:".to_string(),
+        ];
+        attrs.extend(self.deployments.iter().map(|d| {
+            format!(
+                "          • {} on {}: code id {}, address {} (from {})",
+                d.contract,
+                d.network,
+                d.code_id
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                d.address.as_deref().unwrap_or("-"),
+                d.source
+            )
+        }));
+        attrs.push("    └── notes:".to_string());
+        attrs.extend(self.notes.iter().map(|n| format!("          • {n}")));
+        attrs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const COUNTER: &str = "osmo1al5fn369treqkz9mcxdlqg5vpsjmmkmcw8vqh562ezencqctx6vqqdh3f6";
+
+    #[test]
+    fn match_contract_keys() {
+        let contracts = vec!["cw20-base".to_string(), "counter".to_string()];
+        assert_eq!(match_contract("counter", &contracts), Some("counter"));
+        assert_eq!(
+            match_contract("counterAddress", &contracts),
+            Some("counter")
+        );
+        assert_eq!(
+            match_contract("NEXT_PUBLIC_CW20_BASE_CONTRACT", &contracts),
+            Some("cw20-base")
+        );
+        assert_eq!(
+            match_contract("COUNTER_CODE_ID", &contracts),
+            Some("counter")
+        );
+        assert_eq!(match_contract("router", &contracts), None);
+    }
+
+    #[test]
+    fn collect_deployments_from_json() {
+        let contracts = vec!["counter".to_string()];
+        let deployments = json!({
+            "osmosis": {
+                "osmo-test-4": {
+                    "code_ids": { "counter": 12 },
+                    "default": { "counter": COUNTER }
+                }
+            },
+            "mainnet": { "counter": { "codeId": "3", "address": COUNTER } },
+            "wallet": { "counter": "osmo1ar2yq5y88kagvk48c9c2knxwvnvsswdrerndlx" }
+        });
+        let mut found = vec![];
+        collect_json(&deployments, &mut vec![], None, &contracts, &mut found);
+        found.sort();
+
+        let path = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    path(&["mainnet", "counter"]),
+                    "counter".to_string(),
+                    None,
+                    Some(COUNTER.to_string())
+                ),
+                (
+                    path(&["mainnet", "counter"]),
+                    "counter".to_string(),
+                    Some(3),
+                    None
+                ),
+                (
+                    path(&["osmosis", "osmo-test-4", "code_ids"]),
+                    "counter".to_string(),
+                    Some(12),
+                    None
+                ),
+                (
+                    path(&["osmosis", "osmo-test-4", "default"]),
+                    "counter".to_string(),
+                    None,
+                    Some(COUNTER.to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn collect_deployments_from_env() {
+        let contracts = vec!["counter".to_string()];
+        let env = format!("export COUNTER_ADDRESS=\"{COUNTER}\"\nCOUNTER_CODE_ID=7\nOTHER=1\n");
+        assert_eq!(
+            collect_env(&env, &contracts),
+            vec![
+                ("counter".to_string(), None, Some(COUNTER.to_string())),
+                ("counter".to_string(), Some(7), None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod init;
+pub mod new;
+
+pub use init::init;
+pub use new::new;
@@ -1,5 +1,5 @@
-use super::config::WorkspaceConfig;
 use crate::framework::Context;
+use crate::modules::workspace::config::WorkspaceConfig;
 use anyhow::{Context as _, Result};
 use std::{fs, path::PathBuf};
 
@@ -0,0 +1,54 @@
+use anyhow::{anyhow, Context, Result};
+use cosmrs::AccountId;
+
+use crate::framework::config::{GlobalConfig, Network};
+use crate::support::eth_key;
+use crate::support::signer::{account_private_key, override_derivation_path};
+
+/// Resolve address that could be referenced by address book entry name (`@name`) of the network
+pub fn resolve_address(network: &Network, address: &str) -> Result<AccountId> {
+    let resolved = match address.strip_prefix('@') {
+        Some(name) => network.address_book().get(name).with_context(|| {
+            let available = network
+                .address_book()
+                .keys()
+                .map(|k| format!("@{k}"))
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!(
+                "`@{name}` not found in address book of chain `{}`, available entries: [{available}]",
+                network.chain_id()
+            )
+        })?,
+        None => address,
+    };
+
+    resolved
+        .parse::<AccountId>()
+        .map_err(|e: cosmrs::ErrorReport| anyhow!(e))
+        .with_context(|| format!("Invalid address `{address}`"))
+}
+
+/// Resolve predefined account name into its address, otherwise treat it as address or address book entry
+pub fn resolve_account_address(
+    global_config: &GlobalConfig,
+    network_info: &Network,
+    account: &str,
+) -> Result<AccountId> {
+    match global_config.accounts().get(account) {
+        Some(acc) => eth_key::account_id(
+            &account_private_key(
+                acc,
+                &override_derivation_path(
+                    global_config.derivation_path(),
+                    None,
+                    *network_info.coin_type(),
+                )?,
+            )?
+            .public_key(),
+            global_config.account_prefix(),
+            acc.key_algo().unwrap_or(*network_info.key_algo()),
+        ),
+        None => resolve_address(network_info, account),
+    }
+}
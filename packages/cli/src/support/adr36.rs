@@ -0,0 +1,128 @@
+use anyhow::{anyhow, bail, Context, Result};
+use cosmrs::crypto::{secp256k1::SigningKey, PublicKey};
+use cosmrs::AccountId;
+use k256::ecdsa::signature::Verifier;
+use k256::ecdsa::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+const PUB_KEY_TYPE: &str = "tendermint/PubKeySecp256k1";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StdPubKey {
+    pub r#type: String,
+    /// base64 encoded compressed secp256k1 public key
+    pub value: String,
+}
+
+/// Signature over arbitrary data as produced by Keplr's `signArbitrary`, see ADR-36
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SignedMessage {
+    pub signer: String,
+    pub pub_key: StdPubKey,
+    /// base64 encoded `r || s`
+    pub signature: String,
+}
+
+/// Amino json sign doc wrapping `data` into `MsgSignData` with empty chain id, fee and zero sequence,
+/// so that it can never be a valid transaction
+fn sign_doc(signer: &str, data: &[u8]) -> Result<Vec<u8>> {
+    // keys are listed in sorted order, as required for amino json sign bytes
+    Ok(serde_json::to_vec(&json!({
+        "account_number": "0",
+        "chain_id": "",
+        "fee": { "amount": [], "gas": "0" },
+        "memo": "",
+        "msgs": [{
+            "type": "sign/MsgSignData",
+            "value": { "data": base64::encode(data), "signer": signer },
+        }],
+        "sequence": "0",
+    }))?)
+}
+
+pub fn sign(signing_key: &SigningKey, account_prefix: &str, data: &[u8]) -> Result<SignedMessage> {
+    let public_key = signing_key.public_key();
+    let signer = public_key
+        .account_id(account_prefix)
+        .map_err(|e| anyhow!(e))?
+        .to_string();
+    let signature = signing_key
+        .sign(&sign_doc(&signer, data)?)
+        .map_err(|e| anyhow!(e))?;
+
+    Ok(SignedMessage {
+        pub_key: StdPubKey {
+            r#type: PUB_KEY_TYPE.to_string(),
+            value: base64::encode(public_key.to_bytes()),
+        },
+        signer,
+        signature: base64::encode(signature.as_ref()),
+    })
+}
+
+/// Verify that `signed` is a signature over `data` by the key of its signer
+pub fn verify(signed: &SignedMessage, data: &[u8]) -> Result<()> {
+    if signed.pub_key.r#type != PUB_KEY_TYPE {
+        bail!("Unsupported public key type `{}`", signed.pub_key.r#type);
+    }
+    let pub_key_bytes =
+        base64::decode(&signed.pub_key.value).with_context(|| "Invalid public key encoding")?;
+
+    let signer: AccountId = signed
+        .signer
+        .parse()
+        .map_err(|_| anyhow!("Invalid signer address `{}`", signed.signer))?;
+    let derived = PublicKey::from_raw_secp256k1(&pub_key_bytes)
+        .with_context(|| "Invalid secp256k1 public key")?
+        .account_id(signer.prefix())
+        .map_err(|e| anyhow!(e))?;
+    if derived != signer {
+        bail!("Public key does not belong to signer `{signer}`");
+    }
+
+    let signature = Signature::try_from(
+        base64::decode(&signed.signature)
+            .with_context(|| "Invalid signature encoding")?
+            .as_slice(),
+    )
+    .map_err(|_| anyhow!("Invalid secp256k1 signature"))?;
+    VerifyingKey::from_sec1_bytes(&pub_key_bytes)
+        .map_err(|_| anyhow!("Invalid secp256k1 public key"))?
+        .verify(&sign_doc(&signed.signer, data)?, &signature)
+        .map_err(|_| anyhow!("Signature does not match the message"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32]).unwrap()
+    }
+
+    #[test]
+    fn build_amino_sign_doc() {
+        assert_eq!(
+            String::from_utf8(sign_doc("osmo1abc", b"hello").unwrap()).unwrap(),
+            r#"{"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"","msgs":[{"type":"sign/MsgSignData","value":{"data":"aGVsbG8=","signer":"osmo1abc"}}],"sequence":"0"}"#
+        );
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let signed = sign(&signing_key(), "osmo", b"hello").unwrap();
+        assert!(signed.signer.starts_with("osmo1"));
+        assert!(verify(&signed, b"hello").is_ok());
+        assert!(verify(&signed, b"hello!").is_err());
+    }
+
+    #[test]
+    fn reject_mismatched_signer() {
+        let mut signed = sign(&signing_key(), "osmo", b"hello").unwrap();
+        signed.signer = sign(&SigningKey::from_bytes(&[8u8; 32]).unwrap(), "osmo", b"")
+            .unwrap()
+            .signer;
+        assert!(verify(&signed, b"hello").is_err());
+    }
+}
@@ -0,0 +1,246 @@
+use anyhow::{bail, Result};
+use config::Map;
+
+/// Expand user defined alias into full beaker invocation.
+/// `args` are the raw command line arguments including the program name,
+/// the first argument after the program name and options preceding it is looked up in `aliases`,
+/// eg. `myalias` of `beaker --network testnet myalias`. `is_switch` tells options that take no value.
+/// Options preceding the alias are moved after its expansion.
+///
+/// Placeholders `{0}`, `{1}`, ... in the alias are substituted with the arguments following the alias,
+/// arguments not referenced by any placeholder are appended at the end.
+pub fn expand_alias(
+    args: Vec<String>,
+    aliases: &Map<String, String>,
+    is_builtin: impl Fn(&str) -> bool,
+    is_switch: impl Fn(&str) -> bool,
+) -> Result<Vec<String>> {
+    let is_alias = |name: &str| aliases.contains_key(name) && !is_builtin(name);
+
+    let mut i = 1;
+    while let Some(arg) = args.get(i) {
+        if !arg.starts_with('-') {
+            break;
+        }
+        let next_is_alias = args.get(i + 1).map(|a| is_alias(a)).unwrap_or(false);
+        i += if arg.contains('=') || is_switch(arg) || next_is_alias {
+            1
+        } else {
+            2
+        };
+    }
+
+    let (program, options, name, rest) = match (args.first(), args.get(i)) {
+        (Some(program), Some(name)) => (program, &args[1..i], name, &args[i + 1..]),
+        _ => return Ok(args),
+    };
+
+    let template = match aliases.get(name) {
+        Some(template) if !is_builtin(name) => template,
+        _ => return Ok(args),
+    };
+
+    let mut used = vec![false; rest.len()];
+    let mut expanded = vec![program.clone()];
+
+    for token in split_args(template)? {
+        let mut token = token;
+        for (i, arg) in rest.iter().enumerate() {
+            let placeholder = format!("{{{i}}}");
+            if token.contains(&placeholder) {
+                token = token.replace(&placeholder, arg);
+                used[i] = true;
+            }
+        }
+        if let Some(missing) = find_placeholder(&token) {
+            bail!("Missing argument `{{{missing}}}` for alias `{name}` (`{template}`)");
+        }
+        expanded.push(token);
+    }
+
+    expanded.extend(options.iter().cloned());
+    expanded.extend(
+        rest.iter()
+            .zip(used)
+            .filter(|(_, used)| !used)
+            .map(|(arg, _)| arg.clone()),
+    );
+
+    Ok(expanded)
+}
+
+fn find_placeholder(token: &str) -> Option<&str> {
+    let start = token.find('{')?;
+    let end = start + token[start..].find('}')?;
+    let inner = &token[start + 1..end];
+    if !inner.is_empty() && inner.chars().all(|c| c.is_ascii_digit()) {
+        Some(inner)
+    } else {
+        None
+    }
+}
+
+/// Split alias into arguments by whitespace, respecting single and double quotes.
+fn split_args(s: &str) -> Result<Vec<String>> {
+    let mut args = vec![];
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quote: Option<char> = None;
+
+    for c in s.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_token = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_token {
+                    args.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        bail!("Unterminated quote in alias `{s}`");
+    }
+    if in_token {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    fn aliases() -> Map<String, String> {
+        Map::from([
+            (
+                "deploy-counter".to_string(),
+                "wasm store-code counter --network {0}".to_string(),
+            ),
+            (
+                "inc".to_string(),
+                r#"wasm execute counter --raw '{"increment": {}}' --signer-account {0}"#
+                    .to_string(),
+            ),
+            ("wasm".to_string(), "key list".to_string()),
+        ])
+    }
+
+    fn is_switch(arg: &str) -> bool {
+        matches!(arg, "-y" | "--yes" | "--ci")
+    }
+
+    #[test]
+    fn expand_alias_with_placeholder() {
+        let expanded = expand_alias(
+            args("beaker deploy-counter testnet --gas 1uosmo"),
+            &aliases(),
+            |_| false,
+            is_switch,
+        )
+        .unwrap();
+        assert_eq!(
+            expanded,
+            args("beaker wasm store-code counter --network testnet --gas 1uosmo")
+        );
+    }
+
+    #[test]
+    fn expand_alias_after_options() {
+        let expanded = expand_alias(
+            args("beaker --output json -y deploy-counter testnet"),
+            &aliases(),
+            |_| false,
+            is_switch,
+        )
+        .unwrap();
+        assert_eq!(
+            expanded,
+            args("beaker wasm store-code counter --network testnet --output json -y")
+        );
+
+        let expanded = expand_alias(
+            args("beaker --timeout=5m --signer-account test1 inc test2"),
+            &aliases(),
+            |_| false,
+            is_switch,
+        )
+        .unwrap();
+        assert_eq!(expanded[..4], args("beaker wasm execute counter")[..]);
+
+        let unchanged = args("beaker --ci wasm build");
+        assert_eq!(
+            expand_alias(
+                unchanged.clone(),
+                &aliases(),
+                |name| name == "wasm",
+                is_switch
+            )
+            .unwrap(),
+            unchanged
+        );
+    }
+
+    #[test]
+    fn expand_alias_keeps_quoted_arguments() {
+        let expanded =
+            expand_alias(args("beaker inc test1"), &aliases(), |_| false, is_switch).unwrap();
+        assert_eq!(
+            expanded,
+            vec![
+                "beaker",
+                "wasm",
+                "execute",
+                "counter",
+                "--raw",
+                r#"{"increment": {}}"#,
+                "--signer-account",
+                "test1"
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_alias_fails_on_missing_argument() {
+        assert!(expand_alias(
+            args("beaker deploy-counter"),
+            &aliases(),
+            |_| false,
+            is_switch
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn builtin_commands_are_not_shadowed() {
+        let expanded = expand_alias(
+            args("beaker wasm build"),
+            &aliases(),
+            |name| name == "wasm",
+            is_switch,
+        )
+        .unwrap();
+        assert_eq!(expanded, args("beaker wasm build"));
+    }
+
+    #[test]
+    fn non_alias_is_untouched() {
+        let expanded =
+            expand_alias(args("beaker key list"), &aliases(), |_| false, is_switch).unwrap();
+        assert_eq!(expanded, args("beaker key list"));
+    }
+}
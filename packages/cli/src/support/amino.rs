@@ -0,0 +1,259 @@
+//! Legacy amino json sign docs (`SIGN_MODE_LEGACY_AMINO_JSON`), signed instead of the protobuf sign doc
+//! by Ledger's Cosmos app and chains that predate sign mode direct
+
+use anyhow::{bail, Context, Result};
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::{MsgDeposit, MsgVote};
+use cosmos_sdk_proto::cosmos::tx::v1beta1::Fee;
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    AccessConfig, MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract,
+    MsgStoreCode, MsgUpdateAdmin,
+};
+use prost::Message;
+use serde_json::{json, Map, Value};
+
+use super::wasm_params::access_type_name;
+
+/// Canonical sign bytes of the amino json sign doc: keys sorted, no whitespace
+/// and html characters escaped the same way as go's `encoding/json`
+#[allow(clippy::too_many_arguments)]
+pub fn sign_doc_bytes(
+    chain_id: &str,
+    account_number: u64,
+    sequence: u64,
+    fee: &Fee,
+    memo: &str,
+    timeout_height: u64,
+    msgs: &[prost_types::Any],
+) -> Result<Vec<u8>> {
+    let mut doc = json!({
+        "account_number": account_number.to_string(),
+        "chain_id": chain_id,
+        "fee": fee_json(fee),
+        "memo": memo,
+        "msgs": msgs.iter().map(msg_json).collect::<Result<Vec<_>>>()?,
+        "sequence": sequence.to_string(),
+    });
+    if timeout_height > 0 {
+        doc["timeout_height"] = json!(timeout_height.to_string());
+    }
+
+    let json = serde_json::to_string(&sorted(doc))?
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+        .replace('\u{2028}', "\\u2028")
+        .replace('\u{2029}', "\\u2029");
+    Ok(json.into_bytes())
+}
+
+/// Objects rebuilt with sorted keys, the map type keeps insertion order
+fn sorted(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, sorted(v)))
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sorted).collect()),
+        v => v,
+    }
+}
+
+fn coins_json(coins: &[Coin]) -> Value {
+    coins
+        .iter()
+        .map(|c| json!({ "amount": c.amount, "denom": c.denom }))
+        .collect()
+}
+
+fn fee_json(fee: &Fee) -> Value {
+    let mut json = json!({
+        "amount": coins_json(&fee.amount),
+        "gas": fee.gas_limit.to_string(),
+    });
+    if !fee.payer.is_empty() {
+        json["payer"] = json!(fee.payer);
+    }
+    if !fee.granter.is_empty() {
+        json["granter"] = json!(fee.granter);
+    }
+    json
+}
+
+/// Contract msgs are embedded as json rather than bytes
+fn contract_msg(msg: &[u8]) -> Result<Value> {
+    serde_json::from_slice(msg).with_context(|| "Contract msg is not valid json")
+}
+
+fn access_config_json(config: &AccessConfig) -> Value {
+    let mut json = json!({ "permission": access_type_name(config.permission) });
+    if !config.address.is_empty() {
+        json["address"] = json!(config.address);
+    }
+    json
+}
+
+/// Amino json of `msg` as `{"type": <amino name>, "value": <fields>}`, empty fields are omitted like amino does
+fn msg_json(msg: &prost_types::Any) -> Result<Value> {
+    let value = msg.value.as_slice();
+    let (r#type, mut fields) = match msg.type_url.as_str() {
+        "/cosmos.bank.v1beta1.MsgSend" => {
+            let m = MsgSend::decode(value)?;
+            (
+                "cosmos-sdk/MsgSend",
+                json!({
+                    "from_address": m.from_address,
+                    "to_address": m.to_address,
+                    "amount": coins_json(&m.amount),
+                }),
+            )
+        }
+        "/cosmos.gov.v1beta1.MsgVote" => {
+            let m = MsgVote::decode(value)?;
+            (
+                "cosmos-sdk/MsgVote",
+                json!({
+                    "proposal_id": m.proposal_id.to_string(),
+                    "voter": m.voter,
+                    "option": m.option,
+                }),
+            )
+        }
+        "/cosmos.gov.v1beta1.MsgDeposit" => {
+            let m = MsgDeposit::decode(value)?;
+            (
+                "cosmos-sdk/MsgDeposit",
+                json!({
+                    "proposal_id": m.proposal_id.to_string(),
+                    "depositor": m.depositor,
+                    "amount": coins_json(&m.amount),
+                }),
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgStoreCode" => {
+            let m = MsgStoreCode::decode(value)?;
+            let mut fields = json!({
+                "sender": m.sender,
+                "wasm_byte_code": base64::encode(&m.wasm_byte_code),
+            });
+            if let Some(permission) = &m.instantiate_permission {
+                fields["instantiate_permission"] = access_config_json(permission);
+            }
+            ("wasm/MsgStoreCode", fields)
+        }
+        "/cosmwasm.wasm.v1.MsgInstantiateContract" => {
+            let m = MsgInstantiateContract::decode(value)?;
+            (
+                "wasm/MsgInstantiateContract",
+                json!({
+                    "sender": m.sender,
+                    "admin": m.admin,
+                    "code_id": m.code_id.to_string(),
+                    "label": m.label,
+                    "msg": contract_msg(&m.msg)?,
+                    "funds": coins_json(&m.funds),
+                }),
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgExecuteContract" => {
+            let m = MsgExecuteContract::decode(value)?;
+            (
+                "wasm/MsgExecuteContract",
+                json!({
+                    "sender": m.sender,
+                    "contract": m.contract,
+                    "msg": contract_msg(&m.msg)?,
+                    "funds": coins_json(&m.funds),
+                }),
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgMigrateContract" => {
+            let m = MsgMigrateContract::decode(value)?;
+            (
+                "wasm/MsgMigrateContract",
+                json!({
+                    "sender": m.sender,
+                    "contract": m.contract,
+                    "code_id": m.code_id.to_string(),
+                    "msg": contract_msg(&m.msg)?,
+                }),
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgUpdateAdmin" => {
+            let m = MsgUpdateAdmin::decode(value)?;
+            (
+                "wasm/MsgUpdateAdmin",
+                json!({ "sender": m.sender, "new_admin": m.new_admin, "contract": m.contract }),
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgClearAdmin" => {
+            let m = MsgClearAdmin::decode(value)?;
+            (
+                "wasm/MsgClearAdmin",
+                json!({ "sender": m.sender, "contract": m.contract }),
+            )
+        }
+        type_url => bail!(
+            "`{type_url}` can not be signed with `amino_json` sign mode, use `direct` sign mode instead"
+        ),
+    };
+
+    // `funds` is the only empty field wasmd keeps in amino json
+    if let Value::Object(map) = &mut fields {
+        map.retain(|k, v| k == "funds" || !matches!(v, Value::String(s) if s.is_empty()));
+    }
+    Ok(json!({ "type": r#type, "value": fields }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::support::proto::MessageExt;
+
+    #[test]
+    fn canonical_sign_doc() {
+        let msg = MsgExecuteContract {
+            sender: "osmo1sender".to_string(),
+            contract: "osmo1contract".to_string(),
+            msg: br#"{"say":{"b":"<hi>","a":1}}"#.to_vec(),
+            funds: vec![],
+        };
+        let fee = Fee {
+            amount: vec![Coin {
+                denom: "uosmo".to_string(),
+                amount: "5000".to_string(),
+            }],
+            gas_limit: 200000,
+            payer: String::new(),
+            granter: String::new(),
+        };
+        let msgs = vec![prost_types::Any {
+            type_url: "/cosmwasm.wasm.v1.MsgExecuteContract".to_string(),
+            value: msg.to_bytes().unwrap(),
+        }];
+
+        assert_eq!(
+            String::from_utf8(sign_doc_bytes("osmosis-1", 7, 3, &fee, "", 0, &msgs).unwrap())
+                .unwrap(),
+            concat!(
+                r#"{"account_number":"7","chain_id":"osmosis-1","#,
+                r#""fee":{"amount":[{"amount":"5000","denom":"uosmo"}],"gas":"200000"},"memo":"","#,
+                r#""msgs":[{"type":"wasm/MsgExecuteContract","value":{"contract":"osmo1contract","funds":[],"#,
+                r#""msg":{"say":{"a":1,"b":"\u003chi\u003e"}},"sender":"osmo1sender"}}],"sequence":"3"}"#
+            )
+        );
+
+        let unsupported = vec![prost_types::Any {
+            type_url: "/example.v1.MsgUnknown".to_string(),
+            value: vec![],
+        }];
+        assert!(sign_doc_bytes("osmosis-1", 7, 3, &fee, "", 0, &unsupported).is_err());
+    }
+}
@@ -0,0 +1,267 @@
+//! Tamper-evident record of every transaction beaker signs and broadcasts, kept as hash-chained json lines
+//! in the state dir. Every entry commits to the hash of the entry before it, so editing, removing or
+//! reordering entries breaks the chain from that entry on.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use cosmrs::tx::{self, Fee};
+use cosmrs::AccountId;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::cosmos::TxCommitResponse;
+use super::state::STATE_DIR;
+use super::tx_summary::summarize_msg;
+use crate::framework::config::Network;
+
+pub const AUDIT_LOG_FILE: &str = "audit-log.jsonl";
+
+/// `prev_hash` of the first entry
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuditMsg {
+    pub type_url: String,
+    pub summary: String,
+    /// Hex sha256 of the encoded msg
+    pub digest: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// Position in the log, starting from 0
+    pub seq: u64,
+    pub timestamp: u64,
+    /// Machine the tx is signed on
+    pub host: String,
+    /// OS user who ran beaker
+    pub user: String,
+    pub chain_id: String,
+    pub signer: String,
+    pub msgs: Vec<AuditMsg>,
+    pub memo: String,
+    pub fee: Vec<String>,
+    pub gas_limit: u64,
+    pub tx_hash: String,
+    pub height: u64,
+    /// `0` if the tx is committed successfully, otherwise the code it failed with
+    pub code: u32,
+    pub log: String,
+    pub prev_hash: String,
+    /// Hex sha256 of the entry's json with empty `hash`
+    pub hash: String,
+}
+
+impl AuditEntry {
+    pub fn compute_hash(&self) -> String {
+        let unhashed = AuditEntry {
+            hash: String::new(),
+            ..self.clone()
+        };
+        hex::encode(Sha256::digest(
+            serde_json::to_vec(&unhashed).expect("audit entry is serializable"),
+        ))
+    }
+}
+
+/// Audit log of the project containing the current dir, if any
+pub fn path() -> Option<PathBuf> {
+    let curr = std::env::current_dir().ok()?;
+    curr.ancestors()
+        .find(|p| p.join("Beaker.toml").exists())
+        .map(|root| root.join(STATE_DIR).join(AUDIT_LOG_FILE))
+}
+
+fn env_or(names: &[&str], fallback: impl FnOnce() -> Option<String>) -> String {
+    names
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+        .or_else(fallback)
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn host() -> String {
+    env_or(&["HOSTNAME", "COMPUTERNAME"], || {
+        fs::read_to_string("/etc/hostname")
+            .ok()
+            .map(|h| h.trim().to_string())
+    })
+}
+
+fn user() -> String {
+    env_or(&["USER", "USERNAME"], || None)
+}
+
+/// Entries of the log in order, an entry that can not be parsed is an error
+pub fn load(path: &Path) -> Result<Vec<AuditEntry>> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    fs::read_to_string(path)?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid entry at `{}:{}`", path.display(), i + 1))
+        })
+        .collect()
+}
+
+/// Check that every entry is hashed correctly and chained to the one before it, returns the number of entries
+pub fn verify(entries: &[AuditEntry]) -> Result<usize> {
+    let mut prev_hash = GENESIS_HASH.to_string();
+    for (i, entry) in entries.iter().enumerate() {
+        if entry.seq != i as u64 {
+            bail!(
+                "Entry #{i} has seq {}, entries are removed or reordered",
+                entry.seq
+            );
+        }
+        if entry.prev_hash != prev_hash {
+            bail!("Entry #{i} is not chained to the entry before it, entries are removed or reordered");
+        }
+        if entry.hash != entry.compute_hash() {
+            bail!(
+                "Entry #{i} (tx {}) does not match its hash, it is modified",
+                entry.tx_hash
+            );
+        }
+        prev_hash = entry.hash.clone();
+    }
+    Ok(entries.len())
+}
+
+/// Append the broadcasted tx to the audit log of the current project, failed txs are recorded as well
+pub fn record(
+    network: &Network,
+    signer: &AccountId,
+    tx_body: &tx::Body,
+    fee: &Fee,
+    response: &TxCommitResponse,
+) -> Result<Option<AuditEntry>> {
+    let path = match path() {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+    let entries = load(&path)
+        .with_context(|| "Audit log is corrupted, check it with `beaker audit-log verify`")?;
+
+    let result = if response.check_tx.code.is_err() {
+        &response.check_tx
+    } else {
+        &response.deliver_tx
+    };
+    let mut entry = AuditEntry {
+        seq: entries.len() as u64,
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        host: host(),
+        user: user(),
+        chain_id: network.chain_id().to_string(),
+        signer: signer.to_string(),
+        msgs: tx_body
+            .messages
+            .iter()
+            .map(|msg| AuditMsg {
+                type_url: msg.type_url.clone(),
+                summary: summarize_msg(msg),
+                digest: hex::encode(Sha256::digest(&msg.value)),
+            })
+            .collect(),
+        memo: tx_body.memo.clone(),
+        fee: fee
+            .amount
+            .iter()
+            .map(|c| format!("{}{}", c.amount, c.denom))
+            .collect(),
+        gas_limit: fee.gas_limit.into(),
+        tx_hash: response.hash.to_string(),
+        height: response.height.value(),
+        code: result.code.value(),
+        log: result.log.to_string(),
+        prev_hash: entries
+            .last()
+            .map(|e| e.hash.clone())
+            .unwrap_or_else(|| GENESIS_HASH.to_string()),
+        hash: String::new(),
+    };
+    entry.hash = entry.compute_hash();
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Unable to open `{}`", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+
+    Ok(Some(entry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(n: u64) -> Vec<AuditEntry> {
+        let mut prev_hash = GENESIS_HASH.to_string();
+        (0..n)
+            .map(|seq| {
+                let mut entry = AuditEntry {
+                    seq,
+                    timestamp: 1_700_000_000 + seq,
+                    host: "ci-runner".to_string(),
+                    user: "deployer".to_string(),
+                    chain_id: "osmo-test-4".to_string(),
+                    signer: "osmo1signer".to_string(),
+                    msgs: vec![AuditMsg {
+                        type_url: "/cosmwasm.wasm.v1.MsgStoreCode".to_string(),
+                        summary: "MsgStoreCode { wasm_size: 4 bytes }".to_string(),
+                        digest: hex::encode(Sha256::digest(seq.to_be_bytes())),
+                    }],
+                    memo: String::new(),
+                    fee: vec!["5000uosmo".to_string()],
+                    gas_limit: 200_000,
+                    tx_hash: format!("{seq:064X}"),
+                    height: 10 + seq,
+                    code: 0,
+                    log: String::new(),
+                    prev_hash: prev_hash.clone(),
+                    hash: String::new(),
+                };
+                entry.hash = entry.compute_hash();
+                prev_hash = entry.hash.clone();
+                entry
+            })
+            .collect()
+    }
+
+    #[test]
+    fn verify_detects_tampering() {
+        assert_eq!(verify(&chain(3)).unwrap(), 3);
+
+        let mut modified = chain(3);
+        modified[1].signer = "osmo1someoneelse".to_string();
+        assert!(verify(&modified)
+            .unwrap_err()
+            .to_string()
+            .contains("modified"));
+
+        let mut removed = chain(3);
+        removed.remove(1);
+        assert!(verify(&removed).is_err());
+
+        let mut rehashed = chain(3);
+        rehashed[1].code = 5;
+        rehashed[1].hash = rehashed[1].compute_hash();
+        assert!(verify(&rehashed)
+            .unwrap_err()
+            .to_string()
+            .contains("Entry #2 is not chained"));
+    }
+}
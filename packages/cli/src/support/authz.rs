@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use cosmos_sdk_proto::cosmos::authz::v1beta1::{GenericAuthorization, Grant, MsgExec, MsgGrant};
+use cosmrs::{AccountId, Any};
+
+use super::proto::MessageExt;
+
+/// Build `MsgGrant` with `GenericAuthorization` that allows `grantee` to execute `msg_type_url`
+/// on behalf of `granter` until `expiration` (duration since unix epoch).
+pub fn generic_grant(
+    granter: &AccountId,
+    grantee: &AccountId,
+    msg_type_url: &str,
+    expiration: Option<Duration>,
+) -> Result<Any> {
+    let authorization = GenericAuthorization {
+        msg: msg_type_url.to_string(),
+    };
+
+    let msg_grant = MsgGrant {
+        granter: granter.to_string(),
+        grantee: grantee.to_string(),
+        grant: Some(Grant {
+            authorization: Some(Any {
+                type_url: "/cosmos.authz.v1beta1.GenericAuthorization".to_owned(),
+                value: authorization.to_bytes()?,
+            }),
+            expiration: expiration.map(|d| prost_types::Timestamp {
+                seconds: d.as_secs() as i64,
+                nanos: d.subsec_nanos() as i32,
+            }),
+        }),
+    };
+
+    Ok(Any {
+        type_url: "/cosmos.authz.v1beta1.MsgGrant".to_owned(),
+        value: msg_grant.to_bytes()?,
+    })
+}
+
+/// Wrap `msgs` in `MsgExec` so that they are executed by `grantee` under previously granted authorization.
+pub fn wrap_exec(grantee: &AccountId, msgs: Vec<Any>) -> Result<Any> {
+    let msg_exec = MsgExec {
+        grantee: grantee.to_string(),
+        msgs,
+    };
+
+    Ok(Any {
+        type_url: "/cosmos.authz.v1beta1.MsgExec".to_owned(),
+        value: msg_exec.to_bytes()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    #[test]
+    fn wrap_exec_preserves_inner_msgs() {
+        let grantee: AccountId = "osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks"
+            .parse()
+            .unwrap();
+        let inner = Any {
+            type_url: "/cosmwasm.wasm.v1.MsgExecuteContract".to_owned(),
+            value: vec![1, 2, 3],
+        };
+
+        let wrapped = wrap_exec(&grantee, vec![inner.clone()]).unwrap();
+        assert_eq!(wrapped.type_url, "/cosmos.authz.v1beta1.MsgExec");
+
+        let msg_exec = MsgExec::decode(wrapped.value.as_slice()).unwrap();
+        assert_eq!(msg_exec.grantee, grantee.to_string());
+        assert_eq!(msg_exec.msgs, vec![inner]);
+    }
+}
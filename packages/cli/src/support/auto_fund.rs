@@ -0,0 +1,149 @@
+//! `--auto-fund`: signers missing on a local network are created in keyring and funded
+//! from the genesis validator, so that the first deploy works without setting up keys by hand.
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context, Result};
+use console::style;
+use cosmrs::bank::MsgSend;
+use cosmrs::bip32;
+use cosmrs::bip32::secp256k1::elliptic_curve::rand_core::OsRng;
+use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::tx::Msg;
+use keyring::Entry;
+
+use super::chain_client::ChainClient;
+use super::coin::Coins;
+use super::cosmos::Client;
+use super::eth_key;
+use super::future::block;
+use super::gas::{Gas, GasArgs, GasPrice};
+use super::prompt;
+use super::signer::{account_private_key_with, override_derivation_path};
+use crate::framework::config::{GlobalConfig, Network, NetworkVariant};
+use crate::modules::key::config::SERVICE;
+
+/// Account holding the genesis balance of the localnet's validator
+pub const GENESIS_VALIDATOR_ACCOUNT: &str = "validator";
+
+/// Amount funded to a signer, in the gas price denom
+const FUND_AMOUNT: u128 = 100_000_000_000;
+
+fn local_network(global_config: &GlobalConfig, network: &str) -> Result<Network> {
+    let network_info = global_config
+        .networks()
+        .get(network)
+        .with_context(|| format!("Unable to find network config: {network}"))?
+        .to_owned();
+    if *network_info.network_variant() != NetworkVariant::Local {
+        bail!("`--auto-fund` only provisions signers on local networks, `{network}` is shared");
+    }
+    Ok(network_info)
+}
+
+/// Generate a mnemonic for the missing signer `name` and store it in keyring, after confirmation
+pub fn create_key(global_config: &GlobalConfig, network: &str, name: &str) -> Result<String> {
+    local_network(global_config, network)?;
+    if !prompt::confirm(&format!(
+        "> Signer `{name}` does not exist, generate a fresh mnemonic, store it in the OS keyring as `{name}` and fund it on `{network}`?"
+    ))? {
+        bail!("Signer `{name}` does not exist");
+    }
+
+    let mnemonic = bip32::Mnemonic::random(OsRng, bip32::Language::English);
+    Entry::new(SERVICE, name)
+        .set_password(mnemonic.phrase())
+        .with_context(|| format!("Unable to store key `{name}` in keyring"))?;
+    println!(
+        "{} key `{name}` in keyring, sign with `--signer-keyring {name}` from now on",
+        style("Created").green().bold()
+    );
+    Ok(mnemonic.phrase().to_string())
+}
+
+/// Fund the account of `key` from the genesis validator if it holds none of the gas price denom on `network`,
+/// after confirmation
+pub fn fund_if_empty(
+    chain: &Arc<ChainClient>,
+    global_config: &GlobalConfig,
+    network: &str,
+    key: &SigningKey,
+) -> Result<()> {
+    let network_info = local_network(global_config, network)?;
+    let key_algo = *network_info.key_algo();
+    let account_prefix = global_config.account_prefix();
+    let address = eth_key::account_id(
+        &key.public_key(),
+        account_prefix,
+        chain
+            .key_algo(&key.public_key().to_bytes())
+            .unwrap_or(key_algo),
+    )?;
+
+    let gas_price = global_config.network_gas_price(network);
+    let denom = gas_price.parse::<GasPrice>()?.denom;
+    let client = Client::new(network_info.clone(), chain.clone());
+    let balances = block(client.all_balances(address.to_string()))?;
+    if balances
+        .iter()
+        .any(|(d, amount)| *d == denom && amount != "0")
+    {
+        return Ok(());
+    }
+
+    let validator = global_config
+        .accounts()
+        .get(GENESIS_VALIDATOR_ACCOUNT)
+        .with_context(|| {
+            format!("`--auto-fund` requires `{GENESIS_VALIDATOR_ACCOUNT}` account holding the localnet's genesis balance")
+        })?;
+    let validator_key = account_private_key_with(
+        chain,
+        validator,
+        &override_derivation_path(
+            global_config.derivation_path(),
+            None,
+            *network_info.coin_type(),
+        )?,
+        key_algo,
+    )?;
+    let validator_address = eth_key::account_id(
+        &validator_key.public_key(),
+        account_prefix,
+        validator.key_algo().unwrap_or(key_algo),
+    )?;
+    if validator_address == address {
+        return Ok(());
+    }
+
+    let amount = format!("{FUND_AMOUNT}{denom}");
+    if !prompt::confirm(&format!(
+        "> `{address}` has no {denom} on `{network}`, fund it with {amount} from `{GENESIS_VALIDATOR_ACCOUNT}`?"
+    ))? {
+        return Ok(());
+    }
+
+    let msg = MsgSend {
+        from_address: validator_address,
+        to_address: address.clone(),
+        amount: amount.parse::<Coins>()?.into(),
+    }
+    .to_any()
+    .map_err(|e| anyhow!(e))?;
+    let gas = Gas::from_args(
+        &GasArgs::default(),
+        gas_price,
+        global_config.gas_adjustment(),
+    )?;
+    block(
+        client
+            .to_signing_client(validator_key, account_prefix)
+            .sign_and_broadcast(vec![msg], &gas, "", &0),
+    )
+    .with_context(|| format!("Unable to fund `{address}` from `{GENESIS_VALIDATOR_ACCOUNT}`"))?;
+    println!(
+        "{} `{address}` with {amount}",
+        style("Funded").green().bold()
+    );
+    Ok(())
+}
@@ -0,0 +1,103 @@
+//! Where ops send their queries and txs: nodes of the configured networks, or with `--backend sim`
+//! an in-process chain that runs contracts with cosmwasm-vm and is saved in the state dir
+
+use std::path::Path;
+
+#[cfg(not(feature = "sim"))]
+use anyhow::bail;
+use anyhow::Result;
+
+use crate::framework::config::GlobalConfig;
+
+#[derive(clap::ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// Nodes of the configured networks
+    Node,
+    /// In-process simulated chain serving every network, no docker or node needed
+    Sim,
+}
+
+#[cfg(feature = "sim")]
+mod sim {
+    use std::path::{Path, PathBuf};
+
+    use anyhow::{anyhow, Result};
+    use once_cell::sync::OnceCell;
+
+    use crate::framework::config::GlobalConfig;
+    use crate::support::address_book::resolve_account_address;
+    use crate::support::gas::GasPrice;
+    use crate::support::mock_chain::MockChain;
+    use crate::support::state::STATE_DIR;
+
+    /// Saved simulated chain, relative to the state dir
+    const SIM_CHAIN_FILE: &str = "sim/chain.json";
+
+    const SIM_CHAIN_ID: &str = "beaker-sim";
+
+    /// Balance of every configured account on a new simulated chain, in the gas price denom
+    const SIM_ACCOUNT_BALANCE: u128 = 1_000_000_000_000;
+
+    static SIM: OnceCell<(MockChain, PathBuf)> = OnceCell::new();
+
+    pub fn start(root: &Path, global_config: &GlobalConfig) -> Result<()> {
+        let path = root.join(STATE_DIR).join(SIM_CHAIN_FILE);
+        let is_new = !path.exists();
+        let chain = MockChain::load(&path, SIM_CHAIN_ID, global_config.account_prefix())?;
+
+        if is_new {
+            let denom = global_config.gas_price().parse::<GasPrice>()?.denom;
+            let network = chain.network();
+            for name in global_config.accounts().keys() {
+                // accounts without local keys, eg. remote signers, are left unfunded
+                if let Ok(address) = resolve_account_address(global_config, &network, name) {
+                    chain.fund(address.as_ref(), denom.as_ref(), SIM_ACCOUNT_BALANCE);
+                }
+            }
+        }
+
+        SIM.set((chain, path))
+            .map_err(|_| anyhow!("Simulated chain is already started"))
+    }
+
+    pub fn apply(global_config: GlobalConfig) -> GlobalConfig {
+        match SIM.get() {
+            Some((chain, _)) => global_config.with_simulated_networks(&chain.network()),
+            None => global_config,
+        }
+    }
+
+    pub fn persist() -> Result<()> {
+        match SIM.get() {
+            Some((chain, path)) => chain.save(path),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Start the simulated chain of the project at `root`, from where the last invocation left it
+#[cfg(feature = "sim")]
+pub fn start_sim(root: &Path, global_config: &GlobalConfig) -> Result<()> {
+    sim::start(root, global_config)
+}
+
+#[cfg(not(feature = "sim"))]
+pub fn start_sim(_root: &Path, _global_config: &GlobalConfig) -> Result<()> {
+    bail!("`--backend sim` requires beaker built with the `sim` feature, eg. `cargo install beaker --features sim`")
+}
+
+/// Global config with networks served by the simulated chain if it is started
+pub fn apply(global_config: GlobalConfig) -> GlobalConfig {
+    #[cfg(feature = "sim")]
+    return sim::apply(global_config);
+    #[cfg(not(feature = "sim"))]
+    global_config
+}
+
+/// Save the simulated chain if it is started, so that the next invocation continues from it
+pub fn persist() -> Result<()> {
+    #[cfg(feature = "sim")]
+    return sim::persist();
+    #[cfg(not(feature = "sim"))]
+    Ok(())
+}
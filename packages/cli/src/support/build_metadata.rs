@@ -0,0 +1,71 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::git::head_commit;
+use super::lockfile::{command_output, image_digest};
+use super::wasm_info::{parse_wasm, with_custom_section};
+
+/// Name of wasm custom section holding build metadata
+pub const SECTION: &str = "beaker.build";
+
+/// Provenance of a wasm binary, embedded into the binary itself so that it survives storing on chain
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BuildMetadata {
+    pub git_commit: Option<String>,
+    pub rustc_version: Option<String>,
+    pub optimizer_image: Option<String>,
+    pub optimizer_digest: Option<String>,
+    /// Unix timestamp in seconds
+    pub built_at: u64,
+}
+
+impl BuildMetadata {
+    /// Metadata of a build happening now in workspace at `root`
+    pub fn current(root: &Path, optimizer_image: Option<String>) -> Result<Self> {
+        Ok(BuildMetadata {
+            git_commit: head_commit(root),
+            rustc_version: command_output("rustc", &["--version"]),
+            optimizer_digest: optimizer_image.as_deref().and_then(image_digest),
+            optimizer_image,
+            built_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        })
+    }
+
+    pub fn embed(&self, wasm: &[u8]) -> Result<Vec<u8>> {
+        with_custom_section(wasm, SECTION, &serde_json::to_vec(self)?)
+    }
+
+    /// Metadata embedded in `wasm`, `None` if it was not built by beaker
+    pub fn read(wasm: &[u8]) -> Result<Option<Self>> {
+        parse_wasm(wasm)?
+            .custom_section(SECTION)
+            .map(|payload| {
+                serde_json::from_slice(payload).with_context(|| "Invalid build metadata section")
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embed_and_read_back() {
+        let wasm = b"\0asm\x01\0\0\0".to_vec();
+        assert_eq!(BuildMetadata::read(&wasm).unwrap(), None);
+
+        let metadata = BuildMetadata {
+            git_commit: Some("abc123".to_string()),
+            rustc_version: Some("rustc 1.63.0".to_string()),
+            optimizer_image: Some("cosmwasm/workspace-optimizer:0.12.8".to_string()),
+            optimizer_digest: None,
+            built_at: 1660000000,
+        };
+        let embedded = metadata.embed(&wasm).unwrap();
+        assert_eq!(BuildMetadata::read(&embedded).unwrap(), Some(metadata));
+    }
+}
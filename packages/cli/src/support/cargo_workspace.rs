@@ -0,0 +1,41 @@
+//! Crates of a cargo workspace, as listed by its root manifest
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+/// Directories of the workspace members at `root`, expanding `dir/*` globs.
+/// A root manifest with a `[package]` but no `[workspace]` is a single member itself.
+pub fn member_dirs(root: &Path) -> Result<Vec<PathBuf>> {
+    let manifest: toml::Value = toml::from_str(
+        &fs::read_to_string(root.join("Cargo.toml"))
+            .with_context(|| format!("Unable to read `{}/Cargo.toml`", root.display()))?,
+    )?;
+    let members = match manifest
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+    {
+        Some(members) => members,
+        None if manifest.get("package").is_some() => return Ok(vec![root.to_path_buf()]),
+        None => bail!("`Cargo.toml` has no `[workspace] members`"),
+    };
+
+    let mut dirs = vec![];
+    for member in members.iter().filter_map(|m| m.as_str()) {
+        match member.strip_suffix("/*") {
+            Some(parent) => {
+                let mut children = fs::read_dir(root.join(parent))
+                    .with_context(|| format!("Unable to read workspace members `{member}`"))?
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|dir| dir.join("Cargo.toml").exists())
+                    .collect::<Vec<_>>();
+                children.sort();
+                dirs.extend(children);
+            }
+            None => dirs.push(root.join(member)),
+        }
+    }
+    Ok(dirs)
+}
@@ -0,0 +1,270 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use config::Map;
+use cosmrs::proto::cosmos::auth::v1beta1::BaseAccount;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::state::STATE_DIR;
+
+pub const CACHE_FILE: &str = "chain-cache.json";
+
+/// How long the latest known chain status is trusted, enough to skip the status round trip
+/// for commands run back to back
+pub const STATUS_TTL: Duration = Duration::from_secs(30);
+
+/// How long account numbers are kept, they only change when the chain is replaced.
+/// Sequences are never persisted, a tx sent by another invocation or tool would make them stale
+pub const ACCOUNT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long detected chain features are trusted, they only change with chain upgrades
+pub const FEATURES_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChainStatus {
+    pub chain_id: String,
+    pub latest_block_height: u64,
+    pub latest_block_time: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CachedAccount {
+    pub address: String,
+    pub account_number: u64,
+}
+
+impl From<&BaseAccount> for CachedAccount {
+    fn from(acc: &BaseAccount) -> Self {
+        CachedAccount {
+            address: acc.address.clone(),
+            account_number: acc.account_number,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Entry {
+    expires_at: u64,
+    value: serde_json::Value,
+}
+
+/// Chain metadata persisted in the state dir between invocations, each entry expires after its ttl
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ChainCache {
+    entries: Map<String, Entry>,
+    /// Entries were added or removed since last saved
+    #[serde(skip)]
+    changed: bool,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+pub fn status_key(rpc_endpoint: &str) -> String {
+    format!("status:{rpc_endpoint}")
+}
+
+pub fn account_key(grpc_endpoint: &str, address: &str) -> String {
+    format!("account:{grpc_endpoint}:{address}")
+}
+
+pub fn features_key(grpc_endpoint: &str) -> String {
+    format!("features:{grpc_endpoint}")
+}
+
+impl ChainCache {
+    /// Cache file of the project containing the current dir, if any
+    pub fn path() -> Option<PathBuf> {
+        let curr = std::env::current_dir().ok()?;
+        curr.ancestors()
+            .find(|p| p.join("Beaker.toml").exists())
+            .map(|root| root.join(STATE_DIR).join(CACHE_FILE))
+    }
+
+    /// Missing or unreadable cache is treated as empty
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the cache if it changed since loaded or last saved
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        self.changed = false;
+        Ok(())
+    }
+
+    /// Remove the cache, eg. when the local chain is reset
+    pub fn clear(root: &Path) -> Result<()> {
+        let path = root.join(STATE_DIR).join(CACHE_FILE);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.entries
+            .get(key)
+            .filter(|e| e.expires_at > now())
+            .and_then(|e| serde_json::from_value(e.value.clone()).ok())
+    }
+
+    pub fn set<T: Serialize>(&mut self, key: &str, value: &T, ttl: Duration) {
+        self.entries.retain(|_, e| e.expires_at > now());
+        self.changed = true;
+        if let Ok(value) = serde_json::to_value(value) {
+            self.entries.insert(
+                key.to_string(),
+                Entry {
+                    expires_at: now() + ttl.as_secs(),
+                    value,
+                },
+            );
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.changed |= self.entries.remove(key).is_some();
+    }
+
+    /// Record the account number of `account`, written only when it is not known yet.
+    /// A different number than last known means the chain behind `grpc_endpoint` was replaced,
+    /// so its detected features are dropped as well.
+    pub fn set_account(&mut self, grpc_endpoint: &str, account: &CachedAccount) {
+        let key = account_key(grpc_endpoint, &account.address);
+        match self.get::<CachedAccount>(&key) {
+            Some(known) if &known == account => {}
+            Some(_) => {
+                self.remove(&features_key(grpc_endpoint));
+                self.set(&key, account, ACCOUNT_TTL);
+            }
+            None => self.set(&key, account, ACCOUNT_TTL),
+        }
+    }
+
+    /// Record freshly queried chain status. A different chain id or a lower height than last known
+    /// means the chain behind the endpoint was replaced, so accounts and features cached for `grpc_endpoint` are dropped.
+    pub fn set_status(&mut self, rpc_endpoint: &str, grpc_endpoint: &str, status: &ChainStatus) {
+        let key = status_key(rpc_endpoint);
+        let replaced = self
+            .entries
+            .get(&key)
+            .and_then(|e| serde_json::from_value::<ChainStatus>(e.value.clone()).ok())
+            .map(|prev| {
+                prev.chain_id != status.chain_id
+                    || prev.latest_block_height > status.latest_block_height
+            })
+            .unwrap_or(false);
+        if replaced {
+            let prefix = account_key(grpc_endpoint, "");
+            self.entries.retain(|k, _| !k.starts_with(&prefix));
+            self.remove(&features_key(grpc_endpoint));
+        }
+        self.set(&key, status, STATUS_TTL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(chain_id: &str, height: u64) -> ChainStatus {
+        ChainStatus {
+            chain_id: chain_id.to_string(),
+            latest_block_height: height,
+            latest_block_time: "2022-09-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn expire_and_invalidate_entries() {
+        let mut cache = ChainCache::default();
+        let account = CachedAccount {
+            address: "osmo1abc".to_string(),
+            account_number: 7,
+        };
+        let key = account_key("http://localhost:9090", "osmo1abc");
+
+        cache.set(&key, &account, ACCOUNT_TTL);
+        assert_eq!(cache.get::<CachedAccount>(&key), Some(account.clone()));
+
+        cache.set(&key, &account, Duration::ZERO);
+        assert_eq!(cache.get::<CachedAccount>(&key), None);
+
+        cache.set(&key, &account, ACCOUNT_TTL);
+        cache.set_status(
+            "http://localhost:26657",
+            "http://localhost:9090",
+            &status("localosmosis", 100),
+        );
+        cache.set_status(
+            "http://localhost:26657",
+            "http://localhost:9090",
+            &status("localosmosis", 120),
+        );
+        assert!(cache.get::<CachedAccount>(&key).is_some());
+
+        // chain was reset
+        cache.set_status(
+            "http://localhost:26657",
+            "http://localhost:9090",
+            &status("localosmosis", 5),
+        );
+        assert_eq!(cache.get::<CachedAccount>(&key), None);
+        assert_eq!(
+            cache.get::<ChainStatus>(&status_key("http://localhost:26657")),
+            Some(status("localosmosis", 5))
+        );
+    }
+
+    #[test]
+    fn write_only_changed_accounts() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let path = temp.path().join(CACHE_FILE);
+        let mut cache = ChainCache::default();
+        let account = CachedAccount {
+            address: "osmo1abc".to_string(),
+            account_number: 7,
+        };
+        let features = features_key("http://localhost:9090");
+
+        cache.set_account("http://localhost:9090", &account);
+        cache.set(&features, &"features", FEATURES_TTL);
+        cache.save(&path).unwrap();
+        assert!(path.exists());
+
+        fs::remove_file(&path).unwrap();
+        cache.set_account("http://localhost:9090", &account);
+        assert!(cache.get::<String>(&features).is_some());
+        cache.save(&path).unwrap();
+        assert!(!path.exists());
+
+        // account number changed, the chain was replaced
+        cache.set_account(
+            "http://localhost:9090",
+            &CachedAccount {
+                account_number: 3,
+                ..account
+            },
+        );
+        assert_eq!(cache.get::<String>(&features), None);
+        cache.save(&path).unwrap();
+        assert!(path.exists());
+    }
+}
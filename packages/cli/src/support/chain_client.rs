@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use cosmrs::proto::cosmos::auth::v1beta1::BaseAccount;
+use cosmrs::rpc::HttpClient;
+use tonic::transport::Channel;
+
+use super::chain_cache::{
+    features_key, status_key, CachedAccount, ChainCache, ChainStatus, FEATURES_TTL,
+};
+use super::chain_features::ChainFeatures;
+use super::transport;
+use crate::framework::config::{KeyAlgo, Network, SignMode};
+
+/// Key of a network in the caches, networks sharing an endpoint but not the chain are kept apart
+fn network_key(network: &Network, endpoint: &str) -> String {
+    format!("{}@{endpoint}", network.chain_id())
+}
+
+/// Connections and key material shared by all operations of a context, held by the context
+/// so that batch deploys, console and tasks do not reconnect or re-derive keys for every call.
+#[derive(Default)]
+pub struct ChainClient {
+    /// gRPC channels by network
+    channels: Mutex<HashMap<String, Channel>>,
+    /// Tendermint RPC clients by network
+    rpc_clients: Mutex<HashMap<String, HttpClient>>,
+    /// Account info known from previous operations of this invocation by `(chain id, address)`
+    accounts: Mutex<HashMap<(String, String), BaseAccount>>,
+    /// Detected chain features by network
+    features: Mutex<HashMap<String, ChainFeatures>>,
+    /// Sign modes of signer accounts that set their own, by public key
+    sign_modes: Mutex<HashMap<Vec<u8>, SignMode>>,
+    /// Key algorithms of signer accounts that set their own, by public key
+    key_algos: Mutex<HashMap<Vec<u8>, KeyAlgo>>,
+    /// Derived private keys by digest of their derivation input
+    signing_keys: Mutex<HashMap<String, Vec<u8>>>,
+    /// Chain status, features and account numbers persisted between invocations, only within a project
+    persisted: Option<Mutex<(PathBuf, ChainCache)>>,
+}
+
+impl std::fmt::Debug for ChainClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChainClient").finish_non_exhaustive()
+    }
+}
+
+impl ChainClient {
+    /// Client persisting chain status, features and account numbers in the cache of the project containing the current dir, if any
+    pub fn new() -> Self {
+        ChainClient {
+            persisted: ChainCache::path().map(|path| {
+                let cache = ChainCache::load(&path);
+                Mutex::new((path, cache))
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Read or update the persisted cache, updates are written back right away while reads leave the file untouched
+    fn with_persisted<R>(&self, f: impl FnOnce(&mut ChainCache) -> R) -> Option<R> {
+        let mut persisted = self.persisted.as_ref()?.lock().unwrap();
+        let (path, cache) = &mut *persisted;
+        let r = f(cache);
+        // the cache only saves round trips, failing to write it must not fail the command
+        let _ = cache.save(path);
+        Some(r)
+    }
+
+    pub async fn channel(&self, network: &Network) -> Result<Channel> {
+        let key = network_key(network, network.grpc_endpoint());
+        if let Some(channel) = self.channels.lock().unwrap().get(&key) {
+            return Ok(channel.clone());
+        }
+
+        let channel = transport::grpc_channel(network).await?;
+        self.channels.lock().unwrap().insert(key, channel.clone());
+        Ok(channel)
+    }
+
+    pub fn rpc_client(&self, network: &Network) -> Result<HttpClient> {
+        let key = network_key(network, network.rpc_endpoint());
+        if let Some(rpc_client) = self.rpc_clients.lock().unwrap().get(&key) {
+            return Ok(rpc_client.clone());
+        }
+
+        let rpc_client = transport::rpc_client(network)?;
+        self.rpc_clients
+            .lock()
+            .unwrap()
+            .insert(key, rpc_client.clone());
+        Ok(rpc_client)
+    }
+
+    /// Account info known from previous operations of this invocation, its sequence is only trusted within one
+    pub fn cached_account(&self, network: &Network, address: &str) -> Option<BaseAccount> {
+        let key = (network.chain_id().to_string(), address.to_string());
+        self.accounts.lock().unwrap().get(&key).cloned()
+    }
+
+    /// Keep `account` for this invocation, only its account number is persisted
+    pub fn cache_account(&self, network: &Network, account: BaseAccount) {
+        self.with_persisted(|c| {
+            c.set_account(network.grpc_endpoint(), &CachedAccount::from(&account))
+        });
+        self.accounts.lock().unwrap().insert(
+            (network.chain_id().to_string(), account.address.clone()),
+            account,
+        );
+    }
+
+    /// Chain status last queried through the network's rpc endpoint, if still fresh
+    pub fn cached_status(&self, network: &Network) -> Option<ChainStatus> {
+        self.with_persisted(|c| c.get(&status_key(network.rpc_endpoint())))?
+    }
+
+    pub fn cache_status(&self, network: &Network, status: &ChainStatus) {
+        self.with_persisted(|c| {
+            c.set_status(network.rpc_endpoint(), network.grpc_endpoint(), status)
+        });
+    }
+
+    /// Features detected for the network by this or a recent invocation
+    pub fn cached_features(&self, network: &Network) -> Option<ChainFeatures> {
+        let endpoint = network.grpc_endpoint();
+        let key = network_key(network, endpoint);
+        if let Some(features) = self.features.lock().unwrap().get(&key) {
+            return Some(features.clone());
+        }
+
+        let features: ChainFeatures = self.with_persisted(|c| c.get(&features_key(endpoint)))??;
+        self.features.lock().unwrap().insert(key, features.clone());
+        Some(features)
+    }
+
+    pub fn cache_features(&self, network: &Network, features: &ChainFeatures) {
+        let endpoint = network.grpc_endpoint();
+        self.with_persisted(|c| c.set(&features_key(endpoint), features, FEATURES_TTL));
+        self.features
+            .lock()
+            .unwrap()
+            .insert(network_key(network, endpoint), features.clone());
+    }
+
+    pub fn set_sign_mode(&self, public_key: &[u8], sign_mode: SignMode) {
+        self.sign_modes
+            .lock()
+            .unwrap()
+            .insert(public_key.to_vec(), sign_mode);
+    }
+
+    /// Sign mode of the signer account with `public_key`, if the account sets its own
+    pub fn sign_mode(&self, public_key: &[u8]) -> Option<SignMode> {
+        self.sign_modes.lock().unwrap().get(public_key).copied()
+    }
+
+    pub fn set_key_algo(&self, public_key: &[u8], key_algo: KeyAlgo) {
+        self.key_algos
+            .lock()
+            .unwrap()
+            .insert(public_key.to_vec(), key_algo);
+    }
+
+    /// Key algorithm of the account with `public_key`, if the account sets its own
+    pub fn key_algo(&self, public_key: &[u8]) -> Option<KeyAlgo> {
+        self.key_algos.lock().unwrap().get(public_key).copied()
+    }
+
+    /// Private key bytes derived from `key_id`, only derived once per invocation
+    pub fn signing_key_bytes(
+        &self,
+        key_id: &str,
+        derive: impl FnOnce() -> Result<Vec<u8>>,
+    ) -> Result<Vec<u8>> {
+        if let Some(bytes) = self.signing_keys.lock().unwrap().get(key_id) {
+            return Ok(bytes.clone());
+        }
+
+        let bytes = derive()?;
+        self.signing_keys
+            .lock()
+            .unwrap()
+            .insert(key_id.to_string(), bytes.clone());
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derive_signing_key_once() {
+        let client = ChainClient::default();
+        let mut derived = 0;
+
+        for _ in 0..3 {
+            let bytes = client
+                .signing_key_bytes("key", || {
+                    derived += 1;
+                    Ok(vec![1, 2, 3])
+                })
+                .unwrap();
+            assert_eq!(bytes, vec![1, 2, 3]);
+        }
+        assert_eq!(derived, 1);
+    }
+
+    fn network(chain_id: &str, grpc_endpoint: &str) -> Network {
+        serde_json::from_value(serde_json::json!({
+            "chain_id": chain_id,
+            "network_variant": "Local",
+            "grpc_endpoint": grpc_endpoint,
+            "rpc_endpoint": "http://localhost:26657",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn cache_account_by_chain_and_address() {
+        let client = ChainClient::default();
+        let account = BaseAccount {
+            address: "osmo1abc".to_string(),
+            pub_key: None,
+            account_number: 1,
+            sequence: 5,
+        };
+
+        client.cache_account(
+            &network("localosmosis", "http://localhost:9090"),
+            account.clone(),
+        );
+        assert_eq!(
+            client.cached_account(&network("localosmosis", "http://other:9090"), "osmo1abc"),
+            Some(account)
+        );
+        assert_eq!(
+            client.cached_account(&network("osmo-test-5", "http://localhost:9090"), "osmo1abc"),
+            None
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! Detecting which wasmd / cosmos-sdk features a chain supports, so that msgs are built in a shape
+//! the chain accepts and unsupported features fail with a clear error instead of an opaque tx failure
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+pub const NODE_INFO_PATH: &str = "/cosmos.base.tendermint.v1beta1.Service/GetNodeInfo";
+pub const GOV_V1_PARAMS_PATH: &str = "/cosmos.gov.v1.Query/Params";
+
+const WASMD_MODULE: &str = "github.com/CosmWasm/wasmd";
+const WASMVM_MODULE: &str = "github.com/CosmWasm/wasmvm";
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetNodeInfoRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetNodeInfoResponse {
+    #[prost(message, optional, tag = "2")]
+    pub application_version: Option<VersionInfo>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct VersionInfo {
+    #[prost(string, tag = "3")]
+    pub version: String,
+    #[prost(message, repeated, tag = "7")]
+    pub build_deps: Vec<BuildDep>,
+    #[prost(string, tag = "8")]
+    pub cosmos_sdk_version: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BuildDep {
+    #[prost(string, tag = "1")]
+    pub path: String,
+    #[prost(string, tag = "2")]
+    pub version: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GovV1ParamsRequest {
+    #[prost(string, tag = "1")]
+    pub params_type: String,
+}
+
+/// Only used to tell whether the query is served, the content is not needed
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GovV1ParamsResponse {}
+
+/// Features of a chain, detected on first contact and cached per grpc endpoint.
+/// Versions are `None` when the node does not report them, features are then assumed
+/// to be whatever older chains support and left for the chain to reject.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ChainFeatures {
+    pub wasmd_version: Option<String>,
+    pub wasmvm_version: Option<String>,
+    pub cosmos_sdk_version: Option<String>,
+    /// Whether `cosmos.gov.v1` is served, which proposals with arbitrary msgs require
+    pub gov_v1: bool,
+}
+
+/// `(major, minor, patch)` of versions like `v0.29.2` or `0.45.11-rc1`
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split(|c| c == '-' || c == '+').next()?;
+    let mut parts = version.split('.').map(|p| p.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+impl ChainFeatures {
+    pub fn from_node_info(version_info: Option<&VersionInfo>, gov_v1: bool) -> Self {
+        // replaced deps are reported as `<path> => <replacement path>`, eg. for forks of wasmd
+        let dep_version = |path: &str| {
+            version_info?
+                .build_deps
+                .iter()
+                .find(|d| d.path == path || d.path.starts_with(&format!("{path} =>")))
+                .map(|d| d.version.clone())
+        };
+        ChainFeatures {
+            wasmd_version: dep_version(WASMD_MODULE),
+            wasmvm_version: dep_version(WASMVM_MODULE),
+            cosmos_sdk_version: version_info
+                .map(|v| v.cosmos_sdk_version.clone())
+                .filter(|v| !v.is_empty()),
+            gov_v1,
+        }
+    }
+
+    /// Whether wasmd is at least `min`, `None` if its version is unknown
+    fn wasmd_at_least(&self, min: (u64, u64, u64)) -> Option<bool> {
+        self.wasmd_version
+            .as_deref()
+            .and_then(parse_version)
+            .map(|v| v >= min)
+    }
+
+    /// `MsgInstantiateContract2`, added in wasmd 0.29
+    pub fn instantiate2(&self) -> bool {
+        self.wasmd_at_least((0, 29, 0)).unwrap_or(true)
+    }
+
+    /// Wasm params managed by the module itself rather than `x/params`, since wasmd 0.40
+    pub fn module_params(&self) -> bool {
+        self.wasmd_at_least((0, 40, 0)).unwrap_or(false)
+    }
+
+    /// `ContractsByCreator` query, added in wasmd 0.40
+    pub fn contracts_by_creator(&self) -> bool {
+        self.wasmd_at_least((0, 40, 0)).unwrap_or(false)
+    }
+
+    /// `StoreCodeProposal` and the other legacy wasm proposal contents, removed in wasmd 0.50
+    pub fn legacy_wasm_proposals(&self) -> bool {
+        !self.wasmd_at_least((0, 50, 0)).unwrap_or(false)
+    }
+
+    /// Error if the chain does not support instantiate2, eg. when instantiating with a salt
+    pub fn require_instantiate2(&self) -> Result<()> {
+        if !self.instantiate2() {
+            bail!(
+                "Instantiating with a salt requires instantiate2, which is not supported by this chain (wasmd {}, requires v0.29 or later)",
+                self.wasmd_version.as_deref().unwrap_or("unknown")
+            );
+        }
+        Ok(())
+    }
+
+    /// Error if proposals can be neither legacy wasm proposals nor gov v1 proposals with wasm msgs
+    pub fn require_wasm_proposals(&self) -> Result<()> {
+        if !self.legacy_wasm_proposals() && !self.gov_v1 {
+            bail!(
+                "This chain supports neither legacy wasm proposals (wasmd {}) nor `cosmos.gov.v1` proposals",
+                self.wasmd_version.as_deref().unwrap_or("unknown")
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features(wasmd: Option<&str>, gov_v1: bool) -> ChainFeatures {
+        let version_info = VersionInfo {
+            version: "v15.0.0".to_string(),
+            build_deps: wasmd
+                .map(|v| BuildDep {
+                    path: format!("{WASMD_MODULE} => github.com/osmosis-labs/wasmd"),
+                    version: v.to_string(),
+                })
+                .into_iter()
+                .collect(),
+            cosmos_sdk_version: "v0.45.11".to_string(),
+        };
+        ChainFeatures::from_node_info(Some(&version_info), gov_v1)
+    }
+
+    #[test]
+    fn parse_versions() {
+        assert_eq!(parse_version("v0.29.2"), Some((0, 29, 2)));
+        assert_eq!(parse_version("0.45.11-rc1"), Some((0, 45, 11)));
+        assert_eq!(parse_version("v1.2"), Some((1, 2, 0)));
+        assert_eq!(parse_version("unknown"), None);
+    }
+
+    #[test]
+    fn features_by_wasmd_version() {
+        let old = features(Some("v0.28.0"), false);
+        assert_eq!(old.cosmos_sdk_version.as_deref(), Some("v0.45.11"));
+        assert!(!old.instantiate2());
+        assert!(old.require_instantiate2().is_err());
+        assert!(!old.module_params());
+        assert!(!old.contracts_by_creator());
+        assert!(old.legacy_wasm_proposals());
+
+        let recent = features(Some("v0.50.0"), true);
+        assert!(recent.instantiate2());
+        assert!(recent.module_params());
+        assert!(recent.contracts_by_creator());
+        assert!(!recent.legacy_wasm_proposals());
+        assert!(recent.require_wasm_proposals().is_ok());
+        assert!(features(Some("v0.50.0"), false)
+            .require_wasm_proposals()
+            .is_err());
+
+        // unknown version is left for the chain to decide
+        let unknown = features(None, false);
+        assert!(unknown.instantiate2());
+        assert!(!unknown.module_params());
+        assert!(unknown.legacy_wasm_proposals());
+    }
+}
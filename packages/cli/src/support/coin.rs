@@ -1,8 +1,11 @@
 use std::{str::FromStr, vec};
 
 use anyhow::Context;
+use config::Map;
 use cosmrs::Coin;
+use data_doc_derive::GetDataDocs;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug)]
 pub struct CoinFromStr {
@@ -43,6 +46,7 @@ impl FromStr for CoinFromStr {
     }
 }
 
+#[derive(Default)]
 pub struct Coins(Vec<Coin>);
 
 impl FromStr for Coins {
@@ -82,6 +86,66 @@ impl From<Coins> for Vec<cosmrs::proto::cosmos::base::v1beta1::Coin> {
     }
 }
 
+/// Display denom and exponent of well-known base denoms. Other denoms are displayed as is, since a `u` or `a`
+/// prefix does not tell the exponent of a denom, eg. `uatom` is micro ATOM while `arch` is not atto RCH.
+const KNOWN_DENOMS: [(&str, &str, usize); 9] = [
+    ("uosmo", "OSMO", 6),
+    ("uion", "ION", 6),
+    ("uatom", "ATOM", 6),
+    ("ujuno", "JUNO", 6),
+    ("ustars", "STARS", 6),
+    ("uluna", "LUNA", 6),
+    ("uscrt", "SCRT", 6),
+    ("inj", "INJ", 18),
+    ("aevmos", "EVMOS", 18),
+];
+
+/// Format amount of base denom in `display_denom`, which is `exponent` decimal places larger
+fn display_amount(amount: &str, display_denom: &str, exponent: usize) -> String {
+    let padded = format!("{:0>width$}", amount, width = exponent + 1);
+    let (integer, fraction) = padded.split_at(padded.len() - exponent);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        format!("{integer} {display_denom}")
+    } else {
+        format!("{integer}.{fraction} {display_denom}")
+    }
+}
+
+/// Format amount of well-known base denom in its display denom, eg. `1500uosmo` → `0.0015 OSMO`.
+/// Amount of other denoms are displayed as is.
+pub fn display_coin(amount: &str, denom: &str) -> String {
+    match KNOWN_DENOMS.iter().find(|(base, ..)| *base == denom) {
+        Some((_, display_denom, exponent)) => display_amount(amount, display_denom, *exponent),
+        None => format!("{amount}{denom}"),
+    }
+}
+
+/// Display unit of a base denom
+#[derive(Serialize, Deserialize, Clone, Debug, GetDataDocs)]
+pub struct DenomMetadata {
+    /// Denom shown to the user, eg. `OSMO`
+    pub display: String,
+
+    /// Number of decimal places between base denom and display denom, eg. `6` for `uosmo` → `OSMO`
+    pub exponent: u32,
+}
+
+/// Format amount of base denom using its metadata if there is one, falls back to [`display_coin`]
+pub fn display_coin_with_metadata(
+    amount: &str,
+    denom: &str,
+    metadata: &Map<String, DenomMetadata>,
+) -> String {
+    match metadata.get(denom) {
+        Some(DenomMetadata { display, exponent }) => {
+            display_amount(amount, display, *exponent as usize)
+        }
+        None => display_coin(amount, denom),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,6 +163,53 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_display_coin() {
+        assert_eq!(display_coin("1500", "uosmo"), "0.0015 OSMO");
+        assert_eq!(display_coin("2000000", "uosmo"), "2 OSMO");
+        assert_eq!(display_coin("1", "aevmos"), "0.000000000000000001 EVMOS");
+        assert_eq!(display_coin("100", "stake"), "100stake");
+        assert_eq!(display_coin("100", "arch"), "100arch");
+        assert_eq!(display_coin("100", "uusdc"), "100uusdc");
+    }
+
+    #[test]
+    fn test_display_coin_with_metadata() {
+        let metadata = Map::from([(
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2".to_string(),
+            DenomMetadata {
+                display: "ATOM".to_string(),
+                exponent: 6,
+            },
+        )]);
+        assert_eq!(
+            display_coin_with_metadata(
+                "1230000",
+                "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+                &metadata
+            ),
+            "1.23 ATOM"
+        );
+        assert_eq!(
+            display_coin_with_metadata(
+                "42",
+                "factory/osmo1abc/points",
+                &Map::from([(
+                    "factory/osmo1abc/points".to_string(),
+                    DenomMetadata {
+                        display: "POINTS".to_string(),
+                        exponent: 0,
+                    },
+                )])
+            ),
+            "42 POINTS"
+        );
+        assert_eq!(
+            display_coin_with_metadata("1500", "uosmo", &metadata),
+            "0.0015 OSMO"
+        );
+    }
+
     #[test]
     fn test_coin_from_str_with_incorrect_denom() {
         let e = "uosmo1000".parse::<CoinFromStr>().unwrap_err();
@@ -1,7 +1,20 @@
 use anyhow::bail;
+use std::process::Stdio;
+
+use super::output::{output_format, OutputFormat};
 
 pub fn run_command(cmd: &mut std::process::Command) -> Result<(), anyhow::Error> {
-    let exit_status = cmd.spawn()?.wait()?;
+    // keep stdout clean for machine readable output, subprocess output goes to stderr instead
+    if output_format() != OutputFormat::Table {
+        cmd.stdout(Stdio::piped());
+    }
+
+    let mut child = cmd.spawn()?;
+    if let Some(mut stdout) = child.stdout.take() {
+        std::io::copy(&mut stdout, &mut std::io::stderr())?;
+    }
+
+    let exit_status = child.wait()?;
     if !exit_status.success() {
         bail!("Failed to execute: `{:#?}`", cmd)
     }
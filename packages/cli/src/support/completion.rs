@@ -0,0 +1,223 @@
+use std::fs;
+
+use anyhow::Result;
+use clap_complete::Shell;
+
+use crate::{framework::Context, support::state::State, WasmConfig};
+
+#[derive(clap::ArgEnum, Clone, Debug)]
+pub enum CompletionList {
+    Networks,
+    Accounts,
+    Contracts,
+}
+
+/// Candidates for dynamic completion, derived from the project's config and state files.
+/// Errors are swallowed since completion must never break the user's shell.
+pub fn list_candidates<'a, Ctx: Context<'a, WasmConfig>>(
+    ctx: &Ctx,
+    list: &CompletionList,
+) -> Vec<String> {
+    let global_config = match ctx.global_config() {
+        Ok(c) => c,
+        Err(_) => return vec![],
+    };
+
+    match list {
+        CompletionList::Networks => global_config.networks().keys().cloned().collect(),
+        CompletionList::Accounts => global_config.accounts().keys().cloned().collect(),
+        CompletionList::Contracts => {
+            let mut contracts = contract_dirs(ctx).unwrap_or_default();
+            if let Ok(root) = ctx.root() {
+                for (network, network_info) in global_config.networks() {
+                    if let Ok(state) = State::load_by_network(network_info.clone(), root.clone()) {
+                        contracts.extend(state.contracts(network).keys().cloned());
+                    }
+                }
+            }
+            contracts.sort();
+            contracts.dedup();
+            contracts
+        }
+    }
+}
+
+fn contract_dirs<'a, Ctx: Context<'a, WasmConfig>>(ctx: &Ctx) -> Result<Vec<String>> {
+    let contract_dir = ctx.root()?.join(ctx.config()?.contract_dir);
+    Ok(fs::read_dir(contract_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("Cargo.toml").exists())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect())
+}
+
+/// Options completed dynamically and the candidates listed for them
+const DYNAMIC_OPTIONS: [(&str, &str); 2] =
+    [("network", "networks"), ("signer_account", "accounts")];
+
+/// Spellings of options with `id` anywhere in `cmd`, eg. `--network` and `-n`
+fn option_flags(cmd: &clap::Command, id: &str) -> Vec<String> {
+    fn collect(cmd: &clap::Command, id: &str, flags: &mut Vec<String>) {
+        for arg in cmd.get_arguments().filter(|a| a.get_id() == id) {
+            let long = arg.get_long().map(|l| format!("--{l}"));
+            let short = arg.get_short().map(|s| format!("-{s}"));
+            for flag in long.into_iter().chain(short) {
+                if !flags.contains(&flag) {
+                    flags.push(flag);
+                }
+            }
+        }
+        for sc in cmd.get_subcommands() {
+            collect(sc, id, flags);
+        }
+    }
+    let mut flags = vec![];
+    collect(cmd, id, &mut flags);
+    flags
+}
+
+/// Subcommands of `wasm` taking a contract name as their first positional argument
+fn contract_subcommands(cmd: &clap::Command) -> Vec<String> {
+    cmd.find_subcommand("wasm")
+        .map(|wasm| {
+            wasm.get_subcommands()
+                .filter(|sc| {
+                    sc.get_positionals()
+                        .next()
+                        .map(|a| a.get_id() == "contract_name")
+                        .unwrap_or(false)
+                })
+                .map(|sc| sc.get_name().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Completion script for `shell`, static completion generated from the cli definition
+/// followed by the hooks that complete networks, accounts and contracts dynamically.
+pub fn completion_script(shell: Shell, cmd: &mut clap::Command) -> String {
+    let generate = |shell: Shell, cmd: &mut clap::Command| {
+        let bin_name = cmd.get_name().to_string();
+        let mut buf = Vec::new();
+        clap_complete::generate(shell, cmd, bin_name, &mut buf);
+        String::from_utf8_lossy(&buf).to_string()
+    };
+
+    match shell {
+        Shell::Bash => [generate(Shell::Bash, cmd), bash_dynamic(cmd)].join("\n"),
+        // zsh gets dynamic completion through its bash completion compatibility
+        Shell::Zsh => [
+            ZSH_BASH_COMPAT.to_string(),
+            generate(Shell::Bash, cmd),
+            bash_dynamic(cmd),
+        ]
+        .join("\n"),
+        Shell::Fish => [generate(Shell::Fish, cmd), fish_dynamic(cmd)].join("\n"),
+        _ => generate(shell, cmd),
+    }
+}
+
+fn bash_dynamic(cmd: &clap::Command) -> String {
+    let options = DYNAMIC_OPTIONS
+        .iter()
+        .map(|(id, list)| {
+            format!(
+                r#"        {})
+            COMPREPLY=($(compgen -W "$(beaker completion --list {list} 2>/dev/null)" -- "${{cur}}"))
+            return 0
+            ;;"#,
+                option_flags(cmd, id).join("|")
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    let contract_subcommands = contract_subcommands(cmd).join(" ");
+
+    format!(
+        r#"
+_beaker_dynamic() {{
+    local cur prev
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+
+    case "${{prev}}" in
+{options}
+    esac
+
+    if [[ ${{COMP_CWORD}} -eq 3 && "${{COMP_WORDS[1]}}" == "wasm" && " {contract_subcommands} " == *" ${{COMP_WORDS[2]}} "* && "${{cur}}" != -* ]]; then
+        COMPREPLY=($(compgen -W "$(beaker completion --list contracts 2>/dev/null)" -- "${{cur}}"))
+        return 0
+    fi
+
+    _beaker "$@"
+}}
+
+complete -F _beaker_dynamic -o bashdefault -o default beaker
+"#
+    )
+}
+
+const ZSH_BASH_COMPAT: &str = r#"
+autoload -U +X bashcompinit && bashcompinit
+"#;
+
+fn fish_dynamic(cmd: &clap::Command) -> String {
+    let mut lines = vec![String::new()];
+    for (id, list) in DYNAMIC_OPTIONS {
+        let flags = option_flags(cmd, id)
+            .iter()
+            .map(|flag| match flag.strip_prefix("--") {
+                Some(long) => format!("-l {long}"),
+                None => format!("-s {}", flag.trim_start_matches('-')),
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+        lines.push(format!(
+            r#"complete -c beaker {flags} -f -a "(beaker completion --list {list} 2>/dev/null)""#
+        ));
+    }
+    lines.push(format!(
+        r#"complete -c beaker -n "__fish_seen_subcommand_from wasm; and __fish_seen_subcommand_from {}" -f -a "(beaker completion --list contracts 2>/dev/null)""#,
+        contract_subcommands(cmd).join(" ")
+    ));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cli;
+    use clap::CommandFactory;
+
+    #[test]
+    fn find_dynamic_completion_targets_in_cli() {
+        let cmd = Cli::command();
+
+        let subcommands = contract_subcommands(&cmd);
+        for sc in ["store-code", "instantiate", "deploy", "upgrade", "query"] {
+            assert!(subcommands.contains(&sc.to_string()), "{sc}");
+        }
+        assert!(!subcommands.contains(&"build".to_string()));
+
+        let network = option_flags(&cmd, "network");
+        assert!(network.contains(&"--network".to_string()));
+        assert!(network.contains(&"-n".to_string()));
+        assert_eq!(
+            option_flags(&cmd, "signer_account"),
+            vec!["--signer-account"]
+        );
+    }
+
+    #[test]
+    fn generate_scripts_with_dynamic_hooks() {
+        let bash = completion_script(Shell::Bash, &mut Cli::command());
+        assert!(bash.contains("_beaker()"));
+        assert!(bash.contains("--list networks"));
+        assert!(bash.contains(" deploy "));
+
+        let fish = completion_script(Shell::Fish, &mut Cli::command());
+        assert!(fish.contains("-l signer-account -f -a \"(beaker completion --list accounts"));
+        assert!(fish.contains("__fish_seen_subcommand_from wasm; and __fish_seen_subcommand_from"));
+    }
+}
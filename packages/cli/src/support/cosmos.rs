@@ -1,9 +1,11 @@
 use std::str::FromStr;
+use std::sync::Arc;
 
-use crate::framework::config::Network;
-use anyhow::{anyhow, Ok};
+use crate::framework::config::{BroadcastMode, KeyAlgo, Network, SignMode};
+use anyhow::{anyhow, bail, Ok};
 use anyhow::{Context, Result};
-use cosmos_sdk_proto::cosmos::gov::v1beta1::Proposal;
+use console::style;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::{Proposal, TallyResult};
 use cosmrs::abci::GasInfo;
 use cosmrs::crypto::secp256k1::SigningKey;
 use cosmrs::proto::cosmos::auth::v1beta1::BaseAccount;
@@ -11,15 +13,62 @@ use cosmrs::tendermint::abci::tag::{Key, Value};
 
 use cosmrs::tx::{self, SignDoc, SignerInfo};
 use cosmrs::{dev, AccountId, Coin};
-use cosmrs::{rpc, tx::Fee, Any};
+use cosmrs::{rpc, rpc::Client as _, tx::Fee, tx::Raw, Any};
 use prost::Message;
+use tonic::transport::Channel;
 
-use super::gas::Gas;
+use super::amino;
+use super::audit_log;
+use super::chain_cache::ChainStatus;
+use super::chain_client::ChainClient;
+use super::chain_features::{
+    ChainFeatures, GetNodeInfoRequest, GetNodeInfoResponse, GovV1ParamsRequest,
+    GovV1ParamsResponse, GOV_V1_PARAMS_PATH, NODE_INFO_PATH,
+};
+use super::error::Error;
+use super::eth_key;
+use super::fee_abstraction::{
+    convert_gas_price, parse_dec, FeeToken, QueryBaseDenomRequest, QueryBaseDenomResponse,
+    QueryDenomSpotPriceRequest, QueryDenomSpotPriceResponse, QueryFeeTokensRequest,
+    QueryFeeTokensResponse, BASE_DENOM_PATH, DENOM_SPOT_PRICE_PATH, FEE_TOKENS_PATH,
+};
+use super::fee_market::{
+    live_gas_price, GasPriceRequest, GasPriceResponse, QueryEipBaseFeeRequest,
+    QueryEipBaseFeeResponse, EIP_BASE_FEE_PATH, FEEMARKET_GAS_PRICE_PATH,
+};
+use super::gas::{fee_gas_price, Gas, GasPrice};
+use super::gas_ledger::GasLedger;
+use super::pagination::{paginate, Page};
+use super::progress::Progress;
+use super::prompt;
+use super::throttle;
+use super::transport::rpc_request;
+use super::tx_summary::{summarize_fee, summarize_msg};
+use super::wasm_params::{
+    Params as WasmParams, QueryContractsByCreatorRequest, QueryContractsByCreatorResponse,
+    QueryParamsRequest as QueryWasmParamsRequest, QueryParamsResponse as QueryWasmParamsResponse,
+    CONTRACTS_BY_CREATOR_PATH, WASM_PARAMS_PATH,
+};
 
 pub type TxCommitResponse = rpc::endpoint::broadcast::tx_commit::Response;
+type TxResult = rpc::endpoint::broadcast::tx_commit::TxResult;
+
+/// Code of cosmos-sdk's `ErrWrongSequence`, in the `sdk` codespace
+const SEQUENCE_MISMATCH_CODE: u32 = 32;
+
+/// First and max wait for the node to report a new signer sequence after a sequence mismatch
+const SEQUENCE_BACKOFF_INITIAL: std::time::Duration = std::time::Duration::from_millis(250);
+const SEQUENCE_BACKOFF_MAX: std::time::Duration = std::time::Duration::from_secs(4);
+
+/// Interval and max attempts of polling for committed tx in `sync` broadcast mode
+const TX_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+const TX_POLL_ATTEMPTS: u32 = 120;
 
 pub trait ResponseValuePicker {
     fn pick(&self, event: &str, attribute: &str) -> Value;
+
+    /// Values of the attribute from all events of the type, in emitted order
+    fn pick_all(&self, event: &str, attribute: &str) -> Vec<Value>;
 }
 
 impl ResponseValuePicker for TxCommitResponse {
@@ -36,16 +85,29 @@ impl ResponseValuePicker for TxCommitResponse {
             .value
             .clone()
     }
+
+    fn pick_all(&self, event: &str, attribute: &str) -> Vec<Value> {
+        self.deliver_tx
+            .events
+            .iter()
+            .filter(|e| e.type_str == event)
+            .flat_map(|e| e.attributes.iter())
+            .filter(|a| a.key == Key::from_str(attribute).unwrap())
+            .map(|a| a.value.clone())
+            .collect()
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct Client {
     network: Network,
+    /// Connections and caches shared with the other clients of the context
+    chain: Arc<ChainClient>,
 }
 
 impl Client {
-    pub fn new(network: Network) -> Self {
-        Client { network }
+    pub fn new(network: Network, chain: Arc<ChainClient>) -> Self {
+        Client { network, chain }
     }
 
     pub fn to_signing_client(
@@ -57,16 +119,24 @@ impl Client {
             inner: self.clone(),
             signing_key,
             account_prefix: account_prefix.to_string(),
+            gas_ledger: None,
         }
     }
 
+    /// Tendermint RPC client of the network, shared with the other clients of the context
+    pub fn rpc_client(&self) -> Result<rpc::HttpClient> {
+        self.chain.rpc_client(&self.network)
+    }
+
+    async fn channel(&self) -> Result<Channel> {
+        throttle::request(&self.network, self.network.grpc_endpoint()).await;
+        self.chain.channel(&self.network).await
+    }
+
+    /// Query account info, the result is cached for later signing within the same invocation
     pub async fn account(&self, address: &str) -> Result<BaseAccount> {
         use cosmos_sdk_proto::cosmos::auth::v1beta1::*;
-        let grpc_endpoint = self.network.grpc_endpoint();
-
-        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
-            .await
-            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+        let mut c = query_client::QueryClient::new(self.channel().await?);
 
         let res = c
             .account(QueryAccountRequest {
@@ -77,23 +147,79 @@ impl Client {
             .account
             .context("Account not found")?;
 
-        BaseAccount::decode(res.value.as_slice()).context("Unable to decode BaseAccount")
+        let account =
+            BaseAccount::decode(res.value.as_slice()).context("Unable to decode BaseAccount")?;
+        self.chain.cache_account(&self.network, account.clone());
+        Ok(account)
     }
 
     #[allow(deprecated)]
-    pub async fn simulate(&self, tx_bytes: Vec<u8>) -> Result<GasInfo> {
+    pub async fn simulate_raw(
+        &self,
+        tx_bytes: Vec<u8>,
+    ) -> Result<cosmos_sdk_proto::cosmos::tx::v1beta1::SimulateResponse> {
         use cosmos_sdk_proto::cosmos::tx::v1beta1::*;
-        let grpc_endpoint = self.network.grpc_endpoint();
-
-        let mut c = service_client::ServiceClient::connect(self.network.grpc_endpoint().clone())
-            .await
-            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+        let mut c = service_client::ServiceClient::new(self.channel().await?);
 
         let res = c
             .simulate(SimulateRequest { tx: None, tx_bytes })
             .await?
-            .into_inner()
-            .gas_info;
+            .into_inner();
+
+        Ok(res)
+    }
+
+    /// Simulate msgs sent by `address` without its key. The signer info carries no public key,
+    /// for which the chain substitutes a placeholder key when charging gas of signature verification.
+    pub async fn simulate_unsigned(
+        &self,
+        msgs: Vec<Any>,
+        memo: &str,
+        timeout_height: &u32,
+        address: &str,
+        fee_denom: &str,
+    ) -> Result<cosmos_sdk_proto::cosmos::tx::v1beta1::SimulateResponse> {
+        use cosmrs::proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+        use cosmrs::proto::cosmos::tx::signing::v1beta1::SignMode as ProtoSignMode;
+        use cosmrs::proto::cosmos::tx::v1beta1::{
+            mode_info, AuthInfo, Fee as ProtoFee, ModeInfo, SignerInfo as ProtoSignerInfo, TxRaw,
+        };
+
+        let account = self.account(address).await?;
+        let auth_info = AuthInfo {
+            signer_infos: vec![ProtoSignerInfo {
+                public_key: None,
+                mode_info: Some(ModeInfo {
+                    sum: Some(mode_info::Sum::Single(mode_info::Single {
+                        mode: ProtoSignMode::Direct as i32,
+                    })),
+                }),
+                sequence: account.sequence,
+            }],
+            fee: Some(ProtoFee {
+                amount: vec![ProtoCoin {
+                    denom: fee_denom.to_string(),
+                    amount: "0".to_string(),
+                }],
+                gas_limit: 0,
+                payer: String::new(),
+                granter: String::new(),
+            }),
+        };
+        let tx_raw = TxRaw {
+            body_bytes: tx::Body::new(msgs, memo, *timeout_height)
+                .into_bytes()
+                .map_err(|e| anyhow!(e))?,
+            auth_info_bytes: auth_info.encode_to_vec(),
+            signatures: vec![vec![]],
+        };
+
+        let progress = Progress::spinner("Simulating transaction");
+        progress.end_with(self.simulate_raw(tx_raw.encode_to_vec()).await)
+    }
+
+    pub async fn simulate(&self, tx_bytes: Vec<u8>) -> Result<GasInfo> {
+        let res = self.simulate_raw(tx_bytes).await?.gas_info;
 
         let gas_info = res.with_context(|| "Unable to extract gas info")?;
 
@@ -104,11 +230,7 @@ impl Client {
 
     pub async fn query_smart(&self, address: String, query_data: Vec<u8>) -> Result<Vec<u8>> {
         use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
-        let grpc_endpoint = self.network.grpc_endpoint();
-
-        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
-            .await
-            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+        let mut c = query_client::QueryClient::new(self.channel().await?);
 
         let res = c
             .smart_contract_state(QuerySmartContractStateRequest {
@@ -122,13 +244,240 @@ impl Client {
         Ok(res)
     }
 
-    pub async fn proposal(&self, proposal_id: &u64) -> Result<Proposal> {
-        use cosmos_sdk_proto::cosmos::gov::v1beta1::*;
-        let grpc_endpoint = self.network.grpc_endpoint();
+    /// Raw value stored under `key` in the contract's storage, `None` if the key is not set
+    pub async fn query_raw(&self, address: String, key: Vec<u8>) -> Result<Option<Vec<u8>>> {
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let mut c = query_client::QueryClient::new(self.channel().await?);
+
+        let data = c
+            .raw_contract_state(QueryRawContractStateRequest {
+                address,
+                query_data: key,
+            })
+            .await?
+            .into_inner()
+            .data;
+
+        Ok(Some(data).filter(|d| !d.is_empty()))
+    }
+
+    /// Wasm byte code stored on chain with `code_id`
+    pub async fn code(&self, code_id: u64) -> Result<Vec<u8>> {
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let mut c = query_client::QueryClient::new(self.channel().await?);
+
+        let res = c
+            .code(QueryCodeRequest { code_id })
+            .await
+            .with_context(|| format!("Unable to find code with id {code_id}"))?
+            .into_inner()
+            .data;
+
+        Ok(res)
+    }
 
-        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
+    pub async fn code_info(
+        &self,
+        code_id: u64,
+    ) -> Result<cosmos_sdk_proto::cosmwasm::wasm::v1::CodeInfoResponse> {
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let mut c = query_client::QueryClient::new(self.channel().await?);
+
+        c.code(QueryCodeRequest { code_id })
             .await
-            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+            .with_context(|| format!("Unable to find code with id {code_id}"))?
+            .into_inner()
+            .code_info
+            .with_context(|| format!("Unable to find code info of code id {code_id}"))
+    }
+
+    /// Info of codes stored on chain, up to `limit` if given
+    pub async fn codes(
+        &self,
+        limit: Option<u64>,
+    ) -> Result<Page<cosmos_sdk_proto::cosmwasm::wasm::v1::CodeInfoResponse>> {
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let c = query_client::QueryClient::new(self.channel().await?);
+
+        paginate(limit, |pagination| {
+            let mut c = c.clone();
+            async move {
+                let res = c
+                    .codes(QueryCodesRequest {
+                        pagination: Some(pagination),
+                    })
+                    .await?
+                    .into_inner();
+                Ok((res.code_infos, res.pagination))
+            }
+        })
+        .await
+    }
+
+    /// Raw key-value pairs of the contract's storage, up to `limit` if given
+    pub async fn all_contract_state(
+        &self,
+        address: String,
+        limit: Option<u64>,
+    ) -> Result<Page<(Vec<u8>, Vec<u8>)>> {
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let c = query_client::QueryClient::new(self.channel().await?);
+
+        paginate(limit, |pagination| {
+            let mut c = c.clone();
+            let address = address.clone();
+            async move {
+                let res = c
+                    .all_contract_state(QueryAllContractStateRequest {
+                        address,
+                        pagination: Some(pagination),
+                    })
+                    .await?
+                    .into_inner();
+                let entries = res.models.into_iter().map(|m| (m.key, m.value)).collect();
+                Ok((entries, res.pagination))
+            }
+        })
+        .await
+    }
+
+    /// Addresses of contracts instantiated from `code_id`, up to `limit` if given
+    pub async fn contracts_by_code(
+        &self,
+        code_id: u64,
+        limit: Option<u64>,
+    ) -> Result<Page<String>> {
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let c = query_client::QueryClient::new(self.channel().await?);
+
+        paginate(limit, |pagination| {
+            let mut c = c.clone();
+            async move {
+                let res = c
+                    .contracts_by_code(QueryContractsByCodeRequest {
+                        code_id,
+                        pagination: Some(pagination),
+                    })
+                    .await?
+                    .into_inner();
+                Ok((res.contracts, res.pagination))
+            }
+        })
+        .await
+    }
+
+    /// Contracts instantiated by `creator`, requires wasmd 0.40 (see [`ChainFeatures::contracts_by_creator`])
+    pub async fn contracts_by_creator(
+        &self,
+        creator: &str,
+        limit: Option<u64>,
+    ) -> Result<Page<String>> {
+        paginate(limit, |pagination| async move {
+            let res: QueryContractsByCreatorResponse = self
+                .grpc_query(
+                    CONTRACTS_BY_CREATOR_PATH,
+                    QueryContractsByCreatorRequest {
+                        creator_address: creator.to_string(),
+                        pagination: Some(pagination),
+                    },
+                )
+                .await?;
+            Ok((res.contract_addresses, res.pagination))
+        })
+        .await
+    }
+
+    pub async fn contract_info(
+        &self,
+        address: String,
+    ) -> Result<cosmos_sdk_proto::cosmwasm::wasm::v1::ContractInfo> {
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let mut c = query_client::QueryClient::new(self.channel().await?);
+
+        c.contract_info(QueryContractInfoRequest {
+            address: address.clone(),
+        })
+        .await?
+        .into_inner()
+        .contract_info
+        .with_context(|| format!("Unable to find contract info of {address}"))
+    }
+
+    /// Code history of the contract, from instantiation (or genesis) through every migration
+    pub async fn contract_history(
+        &self,
+        address: String,
+    ) -> Result<Vec<cosmos_sdk_proto::cosmwasm::wasm::v1::ContractCodeHistoryEntry>> {
+        use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest;
+        use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+        let mut c = query_client::QueryClient::new(self.channel().await?);
+
+        let mut entries = vec![];
+        let mut next_key = vec![];
+        loop {
+            let res = c
+                .contract_history(QueryContractHistoryRequest {
+                    address: address.clone(),
+                    pagination: Some(PageRequest {
+                        key: next_key,
+                        offset: 0,
+                        limit: 0,
+                        count_total: false,
+                        reverse: false,
+                    }),
+                })
+                .await
+                .with_context(|| format!("Unable to find history of contract {address}"))?
+                .into_inner();
+
+            entries.extend(res.entries);
+
+            match res.pagination {
+                Some(p) if !p.next_key.is_empty() => next_key = p.next_key,
+                _ => break,
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// All balances of the address, in `(denom, amount)`
+    pub async fn all_balances(&self, address: String) -> Result<Vec<(String, String)>> {
+        use cosmos_sdk_proto::cosmos::bank::v1beta1::*;
+        use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest;
+        let mut c = query_client::QueryClient::new(self.channel().await?);
+
+        let mut balances = vec![];
+        let mut next_key = vec![];
+        loop {
+            let res = c
+                .all_balances(QueryAllBalancesRequest {
+                    address: address.clone(),
+                    pagination: Some(PageRequest {
+                        key: next_key,
+                        offset: 0,
+                        limit: 0,
+                        count_total: false,
+                        reverse: false,
+                    }),
+                })
+                .await?
+                .into_inner();
+
+            balances.extend(res.balances.into_iter().map(|c| (c.denom, c.amount)));
+
+            match res.pagination {
+                Some(p) if !p.next_key.is_empty() => next_key = p.next_key,
+                _ => break,
+            }
+        }
+
+        Ok(balances)
+    }
+
+    pub async fn proposal(&self, proposal_id: &u64) -> Result<Proposal> {
+        use cosmos_sdk_proto::cosmos::gov::v1beta1::*;
+        let mut c = query_client::QueryClient::new(self.channel().await?);
 
         let res = c
             .proposal(QueryProposalRequest {
@@ -141,16 +490,28 @@ impl Client {
         res.with_context(|| format!("Unable to find proposal with id {proposal_id}"))
     }
 
+    /// Current tally of the proposal, only meaningful while it is in voting period
+    pub async fn tally_result(&self, proposal_id: &u64) -> Result<TallyResult> {
+        use cosmos_sdk_proto::cosmos::gov::v1beta1::*;
+        let mut c = query_client::QueryClient::new(self.channel().await?);
+
+        let res = c
+            .tally_result(QueryTallyResultRequest {
+                proposal_id: *proposal_id,
+            })
+            .await?
+            .into_inner()
+            .tally;
+
+        res.with_context(|| format!("Unable to find tally of proposal {proposal_id}"))
+    }
+
     async fn gov_params(
         &self,
         params_type: &str,
     ) -> Result<cosmos_sdk_proto::cosmos::gov::v1beta1::QueryParamsResponse> {
         use cosmos_sdk_proto::cosmos::gov::v1beta1::*;
-        let grpc_endpoint = self.network.grpc_endpoint();
-
-        let mut c = query_client::QueryClient::connect(self.network.grpc_endpoint().clone())
-            .await
-            .context(format!("Unable to connect to {grpc_endpoint}"))?;
+        let mut c = query_client::QueryClient::new(self.channel().await?);
 
         let res = c
             .params(QueryParamsRequest {
@@ -169,18 +530,428 @@ impl Client {
             .deposit_params
             .with_context(|| "Deposit params is not available")
     }
+
+    /// Raw (amino json) value of `key` in params `subspace`, `None` if the chain does not have the param
+    pub async fn param(&self, subspace: &str, key: &str) -> Result<Option<String>> {
+        use cosmos_sdk_proto::cosmos::params::v1beta1::*;
+        let mut c = query_client::QueryClient::new(self.channel().await?);
+
+        let res = c
+            .params(QueryParamsRequest {
+                subspace: subspace.to_string(),
+                key: key.to_string(),
+            })
+            .await;
+
+        match res {
+            std::result::Result::Ok(res) => Ok(res
+                .into_inner()
+                .param
+                .map(|p| p.value)
+                .filter(|v| !v.is_empty())),
+            Err(status) if status.code() == tonic::Code::InvalidArgument => Ok(None),
+            Err(status) => Err(anyhow!(status))
+                .with_context(|| format!("Unable to query `{subspace}/{key}` param")),
+        }
+    }
+
+    /// Unary grpc query for services that are not part of cosmos-sdk-proto
+    async fn grpc_query<Req, Res>(&self, path: &'static str, req: Req) -> Result<Res>
+    where
+        Req: Message + Send + Sync + 'static,
+        Res: Message + Default + Send + Sync + 'static,
+    {
+        let mut grpc = tonic::client::Grpc::new(self.channel().await?);
+        grpc.ready().await?;
+        let res = grpc
+            .unary(
+                tonic::Request::new(req),
+                tonic::codegen::http::uri::PathAndQuery::from_static(path),
+                tonic::codec::ProstCodec::default(),
+            )
+            .await?;
+        Ok(res.into_inner())
+    }
+
+    /// Denoms accepted as fee through osmosis `x/txfees`, empty if the chain does not support it
+    pub async fn fee_tokens(&self) -> Result<Vec<FeeToken>> {
+        let res: Result<QueryFeeTokensResponse> = self
+            .grpc_query(FEE_TOKENS_PATH, QueryFeeTokensRequest {})
+            .await;
+        match res {
+            std::result::Result::Ok(res) => Ok(res.fee_tokens),
+            Err(e) => match e.downcast_ref::<tonic::Status>() {
+                Some(status) if status.code() == tonic::Code::Unimplemented => Ok(vec![]),
+                _ => Err(e).with_context(|| "Unable to query fee tokens"),
+            },
+        }
+    }
+
+    /// Spot price of fee token `denom` in `x/txfees` base denom, along with the base denom
+    pub async fn fee_token_spot_price(&self, denom: &str) -> Result<(String, f64)> {
+        let base: QueryBaseDenomResponse = self
+            .grpc_query(BASE_DENOM_PATH, QueryBaseDenomRequest {})
+            .await?;
+        let res: QueryDenomSpotPriceResponse = self
+            .grpc_query(
+                DENOM_SPOT_PRICE_PATH,
+                QueryDenomSpotPriceRequest {
+                    denom: denom.to_string(),
+                },
+            )
+            .await
+            .with_context(|| format!("Unable to query spot price of fee token `{denom}`"))?;
+        Ok((base.base_denom, parse_dec(&res.spot_price)?))
+    }
+
+    /// Current base fee per gas in `denom`, `None` if the chain has no fee market pricing `denom`
+    pub async fn base_fee(&self, denom: &str) -> Result<Option<f64>> {
+        let unimplemented = |e: &anyhow::Error| matches!(e.downcast_ref::<tonic::Status>(), Some(status) if status.code() == tonic::Code::Unimplemented);
+
+        let res: Result<QueryEipBaseFeeResponse> = self
+            .grpc_query(EIP_BASE_FEE_PATH, QueryEipBaseFeeRequest {})
+            .await;
+        match res {
+            std::result::Result::Ok(res) => {
+                let base: QueryBaseDenomResponse = self
+                    .grpc_query(BASE_DENOM_PATH, QueryBaseDenomRequest {})
+                    .await?;
+                return Ok(if base.base_denom == denom {
+                    Some(parse_dec(&res.base_fee)?)
+                } else {
+                    None
+                });
+            }
+            Err(e) if unimplemented(&e) => {}
+            Err(e) => return Err(e).with_context(|| "Unable to query EIP base fee"),
+        }
+
+        let res: Result<GasPriceResponse> = self
+            .grpc_query(
+                FEEMARKET_GAS_PRICE_PATH,
+                GasPriceRequest {
+                    denom: denom.to_string(),
+                },
+            )
+            .await;
+        match res {
+            std::result::Result::Ok(res) => res
+                .price
+                .filter(|p| p.denom == denom)
+                .map(|p| parse_dec(&p.amount))
+                .transpose(),
+            Err(e) if unimplemented(&e) => Ok(None),
+            Err(e) => {
+                Err(e).with_context(|| format!("Unable to query fee market gas price of `{denom}`"))
+            }
+        }
+    }
+
+    /// Wasm module params from wasmd's own `Params` query, see [`ChainFeatures::module_params`]
+    pub async fn wasm_params(&self) -> Result<WasmParams> {
+        let res: QueryWasmParamsResponse = self
+            .grpc_query(WASM_PARAMS_PATH, QueryWasmParamsRequest {})
+            .await
+            .with_context(|| "Unable to query wasm params")?;
+        res.params
+            .with_context(|| "Wasm module params are not available on this network")
+    }
+
+    /// Wasmd / cosmos-sdk features of the chain, detected on first contact and cached afterwards.
+    /// Queries the chain does not serve are treated as missing features rather than errors
+    pub async fn features(&self) -> Result<ChainFeatures> {
+        if let Some(features) = self.chain.cached_features(&self.network) {
+            return Ok(features);
+        }
+
+        let node_info: Option<GetNodeInfoResponse> = self
+            .grpc_query(NODE_INFO_PATH, GetNodeInfoRequest {})
+            .await
+            .ok();
+        let gov_v1 = self
+            .grpc_query::<_, GovV1ParamsResponse>(
+                GOV_V1_PARAMS_PATH,
+                GovV1ParamsRequest {
+                    params_type: "deposit".to_string(),
+                },
+            )
+            .await
+            .is_ok();
+
+        let features = ChainFeatures::from_node_info(
+            node_info
+                .as_ref()
+                .and_then(|n| n.application_version.as_ref()),
+            gov_v1,
+        );
+        self.chain.cache_features(&self.network, &features);
+        Ok(features)
+    }
+
+    /// `gas_price` replaced by the chain's current base fee times the network's `base_fee_multiplier`,
+    /// where the chain has a fee market
+    pub async fn live_gas_price(&self, gas_price: &GasPrice) -> Result<GasPrice> {
+        let multiplier = *self.network.base_fee_multiplier();
+        if multiplier <= 0.0 {
+            return Ok(gas_price.clone());
+        }
+        match self.base_fee(gas_price.denom.as_ref()).await? {
+            Some(base_fee) => live_gas_price(gas_price, base_fee, multiplier),
+            None => Ok(gas_price.clone()),
+        }
+    }
+
+    /// Gas price in `fee_denom`, from the network's `fee_denoms` or converted from `gas_price`
+    /// by the spot price of `fee_denom` if the chain accepts it as fee token through `x/txfees`
+    pub async fn fee_gas_price(
+        &self,
+        gas_price: &GasPrice,
+        fee_denom: Option<&str>,
+    ) -> Result<GasPrice> {
+        let gas_price = &self.live_gas_price(gas_price).await?;
+        let configured = fee_gas_price(gas_price, fee_denom, self.network.fee_denoms());
+        let fee_denom = match (configured, fee_denom) {
+            (std::result::Result::Ok(price), _) => return Ok(price),
+            (Err(e), None) => return Err(e),
+            (Err(e), Some(fee_denom)) => {
+                let accepted = self.fee_tokens().await?;
+                if !accepted.iter().any(|t| t.denom == fee_denom) {
+                    return Err(e);
+                }
+                fee_denom
+            }
+        };
+
+        let (base_denom, spot_price) = self.fee_token_spot_price(fee_denom).await?;
+        if base_denom != gas_price.denom.as_ref() {
+            bail!(
+                "`gas_price` must be in the fee base denom `{base_denom}` to convert it into `{fee_denom}`"
+            );
+        }
+        convert_gas_price(gas_price, fee_denom, spot_price)
+    }
+
+    /// Committed tx with its result, `hash` is hex encoded
+    pub async fn tx(&self, hash: &str) -> Result<rpc::endpoint::tx::Response> {
+        let rpc_client = self.chain.rpc_client(&self.network)?;
+        let tx_hash = hash
+            .to_uppercase()
+            .parse()
+            .map_err(|e| anyhow!("Invalid tx hash `{hash}`: {e}"))?;
+
+        throttle::request(&self.network, self.network.rpc_endpoint()).await;
+        rpc_request(&self.network, rpc_client.tx(tx_hash, false))
+            .await
+            .with_context(|| format!("Unable to find tx `{hash}`"))
+    }
+
+    /// Committed txs matching `query`, first page of `per_page` in `order`
+    pub async fn tx_search(
+        &self,
+        query: rpc::query::Query,
+        per_page: u8,
+        order: rpc::Order,
+    ) -> Result<rpc::endpoint::tx_search::Response> {
+        let rpc_client = self.chain.rpc_client(&self.network)?;
+        throttle::request(&self.network, self.network.rpc_endpoint()).await;
+        rpc_request(
+            &self.network,
+            rpc_client.tx_search(query, false, 1, per_page, order),
+        )
+        .await
+    }
 }
 
 pub struct SigningClient {
     inner: Client,
     account_prefix: String,
     signing_key: SigningKey,
+    gas_ledger: Option<GasLedger>,
 }
 
 impl SigningClient {
+    /// Record gas and fee of every broadcasted transaction into the gas ledger
+    pub fn with_gas_ledger(self, gas_ledger: GasLedger) -> Self {
+        SigningClient {
+            gas_ledger: Some(gas_ledger),
+            ..self
+        }
+    }
+
     pub fn signer_account_id(&self) -> AccountId {
-        let signer_pub = self.signing_key.public_key();
-        signer_pub.account_id(self.account_prefix.as_str()).unwrap()
+        eth_key::account_id(
+            &self.signing_key.public_key(),
+            self.account_prefix.as_str(),
+            self.key_algo(),
+        )
+        .unwrap()
+    }
+
+    /// Key algorithm of the signer account if it sets one, the network's otherwise
+    fn key_algo(&self) -> KeyAlgo {
+        self.inner
+            .chain
+            .key_algo(&self.signing_key.public_key().to_bytes())
+            .unwrap_or(*self.inner.network.key_algo())
+    }
+
+    /// Sign the tx with signer's public key encoded as its `key_algo` requires
+    fn sign_tx(&self, tx_body: &tx::Body, fee: Fee, account: &BaseAccount) -> Result<Raw> {
+        let auth_info =
+            SignerInfo::single_direct(Some(self.signing_key.public_key()), account.sequence)
+                .auth_info(fee);
+        let mut sign_doc = SignDoc::new(
+            tx_body,
+            &auth_info,
+            &self.inner.network.chain_id().parse().unwrap(),
+            account.account_number,
+        )
+        .map_err(|e| anyhow!(e))?;
+
+        if let Some(pub_key) = eth_key::signer_pub_key(
+            &self.signing_key.public_key(),
+            self.key_algo(),
+            &self.inner.network,
+        ) {
+            let mut auth_info = cosmrs::proto::cosmos::tx::v1beta1::AuthInfo::decode(
+                sign_doc.auth_info_bytes.as_slice(),
+            )?;
+            for signer_info in auth_info.signer_infos.iter_mut() {
+                signer_info.public_key = Some(pub_key.clone());
+            }
+            sign_doc.auth_info_bytes = auth_info.encode_to_vec();
+        }
+
+        match self.sign_mode() {
+            SignMode::Direct => sign_doc.sign(&self.signing_key).map_err(|e| anyhow!(e)),
+            SignMode::AminoJson => self.sign_amino_json(tx_body, sign_doc, account),
+        }
+    }
+
+    /// Sign mode of the signer account if it sets one, the network's otherwise
+    fn sign_mode(&self) -> SignMode {
+        self.inner
+            .chain
+            .sign_mode(&self.signing_key.public_key().to_bytes())
+            .unwrap_or(*self.inner.network.sign_mode())
+    }
+
+    /// Sign the amino json sign doc of the tx, with signer infos of the protobuf `sign_doc` marked as amino json
+    fn sign_amino_json(
+        &self,
+        tx_body: &tx::Body,
+        sign_doc: SignDoc,
+        account: &BaseAccount,
+    ) -> Result<Raw> {
+        use cosmrs::proto::cosmos::tx::signing::v1beta1::SignMode as ProtoSignMode;
+        use cosmrs::proto::cosmos::tx::v1beta1::{mode_info, AuthInfo, ModeInfo, TxRaw};
+
+        if self.key_algo() != KeyAlgo::Secp256k1 {
+            bail!("`amino_json` sign mode only supports `secp256k1` keys");
+        }
+
+        let mut auth_info = AuthInfo::decode(sign_doc.auth_info_bytes.as_slice())?;
+        for signer_info in auth_info.signer_infos.iter_mut() {
+            signer_info.mode_info = Some(ModeInfo {
+                sum: Some(mode_info::Sum::Single(mode_info::Single {
+                    mode: ProtoSignMode::LegacyAminoJson as i32,
+                })),
+            });
+        }
+        let fee = auth_info
+            .fee
+            .clone()
+            .with_context(|| "Missing fee in auth info")?;
+
+        let sign_bytes = amino::sign_doc_bytes(
+            self.inner.network.chain_id(),
+            account.account_number,
+            account.sequence,
+            &fee,
+            &tx_body.memo,
+            tx_body.timeout_height.value(),
+            &tx_body.messages,
+        )?;
+        let signature = self.signing_key.sign(&sign_bytes).map_err(|e| anyhow!(e))?;
+
+        Ok(TxRaw {
+            body_bytes: sign_doc.body_bytes,
+            auth_info_bytes: auth_info.encode_to_vec(),
+            signatures: vec![signature.as_ref().to_vec()],
+        }
+        .into())
+    }
+
+    fn zero_fee_tx_bytes(
+        &self,
+        denom: &str,
+        account: &BaseAccount,
+        tx_body: &tx::Body,
+    ) -> Result<Vec<u8>> {
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: denom.parse().map_err(|e| anyhow!("{e}"))?,
+                amount: 0u8.into(),
+            },
+            0u64,
+        );
+        let tx_raw = self.sign_tx(tx_body, fee, account)?;
+        Ok(tx_raw.to_bytes().unwrap())
+    }
+
+    /// Signer's account info, reusing the one known from previous operations of this invocation.
+    /// Otherwise it is queried, as the sequence may have moved since any earlier invocation
+    pub async fn signer_account(&self) -> Result<BaseAccount> {
+        let address = self.signer_account_id().to_string();
+        match self
+            .inner
+            .chain
+            .cached_account(&self.inner.network, &address)
+        {
+            Some(acc) => Ok(acc),
+            None => self
+                .inner
+                .account(&address)
+                .await
+                .with_context(|| "Account can't be initialized"),
+        }
+    }
+
+    /// Signer account queried again after a sequence mismatch. The node may not have caught up with
+    /// the tx that consumed `stale` yet, so it is re-queried with growing waits until the sequence moves,
+    /// using the last queried one once the wait exceeds [`SEQUENCE_BACKOFF_MAX`]
+    async fn refreshed_account(&self, stale: u64) -> Result<BaseAccount> {
+        let address = self.signer_account_id().to_string();
+        let mut backoff = SEQUENCE_BACKOFF_INITIAL;
+        loop {
+            let acc = self
+                .inner
+                .account(&address)
+                .await
+                .with_context(|| "Account can't be initialized")?;
+            if acc.sequence != stale || backoff > SEQUENCE_BACKOFF_MAX {
+                return Ok(acc);
+            }
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+
+    /// Run msgs through the simulate service without broadcasting
+    pub async fn simulate(
+        &self,
+        msgs: Vec<Any>,
+        memo: &str,
+        timeout_height: &u32,
+        fee_denom: &str,
+    ) -> Result<cosmos_sdk_proto::cosmos::tx::v1beta1::SimulateResponse> {
+        let acc = self.signer_account().await?;
+
+        let tx_body = tx::Body::new(msgs, memo, *timeout_height);
+        let tx_bytes = self.zero_fee_tx_bytes(fee_denom, &acc, &tx_body)?;
+
+        let progress = Progress::spinner("Simulating transaction");
+        progress.end_with(self.inner.simulate_raw(tx_bytes).await)
     }
 
     pub async fn estimate_fee(
@@ -194,27 +965,15 @@ impl SigningClient {
             Gas::Auto {
                 gas_price,
                 gas_adjustment,
+                fee_denom,
             } => {
-                let signer_info = SignerInfo::single_direct(
-                    Some(self.signing_key.public_key()),
-                    account.sequence,
-                );
-                let auth_info = signer_info.auth_info(Fee::from_amount_and_gas(
-                    Coin {
-                        denom: gas_price.denom.clone(),
-                        amount: 0u8.into(),
-                    },
-                    0u64,
-                ));
-                let sign_doc = SignDoc::new(
-                    &tx_body,
-                    &auth_info,
-                    &self.inner.network.chain_id().parse().unwrap(),
-                    account.account_number,
-                )
-                .unwrap();
-                let tx_raw = sign_doc.sign(&self.signing_key).unwrap();
-                let gas_info = self.inner.simulate(tx_raw.to_bytes().unwrap()).await?;
+                let gas_price = self
+                    .inner
+                    .fee_gas_price(&gas_price, fee_denom.as_deref())
+                    .await?;
+                let tx_bytes =
+                    self.zero_fee_tx_bytes(gas_price.denom.as_ref(), account, &tx_body)?;
+                let gas_info = self.inner.simulate(tx_bytes).await?;
                 let gas_limit: u64 = gas_info.gas_used.into();
                 let gas_limit = ((gas_limit as f64) * (gas_adjustment as f64)).ceil();
 
@@ -228,6 +987,52 @@ impl SigningClient {
         }
     }
 
+    /// Fee the msgs would be broadcasted with, nothing is signed
+    pub async fn preview_fee(
+        &self,
+        msgs: Vec<Any>,
+        gas: &Gas,
+        memo: &str,
+        timeout_height: &u32,
+    ) -> Result<Fee> {
+        let acc = self.signer_account().await?;
+        let tx_body = tx::Body::new(msgs, memo, *timeout_height);
+
+        let progress = Progress::spinner("Estimating fee");
+        progress.end_with(self.estimate_fee(gas.clone(), &acc, tx_body).await)
+    }
+
+    fn display_summary(&self, tx_body: &tx::Body, fee: &Fee) {
+        eprintln!();
+        eprintln!("  Transaction summary");
+        eprintln!("    +");
+        eprintln!("    ├── chain_id: {}", self.inner.network.chain_id());
+        eprintln!("    ├── signer: {}", self.signer_account_id());
+        eprintln!("    ├── fee: {}", summarize_fee(fee));
+        if !tx_body.memo.is_empty() {
+            eprintln!("    ├── memo: {}", tx_body.memo);
+        }
+        eprintln!("    └── messages:");
+        for msg in &tx_body.messages {
+            eprintln!("          • {}", summarize_msg(msg));
+        }
+        eprintln!();
+    }
+
+    fn confirm_production_broadcast(&self) -> Result<()> {
+        let chain_id = self.inner.network.chain_id();
+        eprintln!(
+            "  {} broadcasting to production network with chain id `{chain_id}`",
+            style("WARNING:").red().bold(),
+        );
+        eprintln!();
+
+        if !prompt::confirm("> Do you want to broadcast this transaction?")? {
+            bail!("Transaction broadcast to `{chain_id}` aborted");
+        }
+        Ok(())
+    }
+
     pub async fn sign_and_broadcast(
         &self,
         msgs: Vec<Any>,
@@ -235,52 +1040,238 @@ impl SigningClient {
         memo: &str,
         timeout_height: &u32,
     ) -> Result<TxCommitResponse> {
-        let acc = self
-            .inner
-            .account(self.signer_account_id().as_ref())
-            .await
-            .with_context(|| "Account can't be initialized")?;
+        let acc = self.signer_account().await?;
 
         let tx_body = tx::Body::new(msgs, memo, *timeout_height);
 
-        let fee = self
-            .estimate_fee(gas.clone(), &acc, tx_body.clone())
-            .await?;
+        let progress = Progress::spinner("Estimating fee");
+        let fee = progress.end_with(self.estimate_fee(gas.clone(), &acc, tx_body.clone()).await)?;
 
-        let auth_info =
-            SignerInfo::single_direct(Some(self.signing_key.public_key()), acc.sequence)
-                .auth_info(fee.clone());
+        prompt::exclusive(|| {
+            self.display_summary(&tx_body, &fee);
+            if *self.inner.network.production() {
+                self.confirm_production_broadcast()?;
+            }
+            anyhow::Ok(())
+        })?;
 
-        let sign_doc = SignDoc::new(
-            &tx_body,
-            &auth_info,
-            &self.inner.network.chain_id().parse().unwrap(),
-            acc.account_number,
-        )
-        .unwrap();
-        let tx_raw = sign_doc.sign(&self.signing_key).unwrap();
+        let rpc_client = self.inner.chain.rpc_client(&self.inner.network)?;
+        wait_for_first_block(&self.inner.chain, &self.inner.network, &rpc_client).await?;
+
+        let retries = *self.inner.network.sequence_mismatch_retries();
+        let mut acc = acc;
+        let mut attempt = 0;
+        let tx_commit_response = loop {
+            let tx_raw = self.sign_tx(&tx_body, fee.clone(), &acc)?;
 
-        let rpc_client = rpc::HttpClient::new(self.inner.network.rpc_endpoint().as_str()).unwrap();
-        dev::poll_for_first_block(&rpc_client).await;
+            let progress =
+                Progress::spinner("Broadcasting transaction and waiting for confirmation");
+            throttle::tx(&self.inner.network).await;
+            let tx_commit_response =
+                progress.end_with(broadcast(&self.inner.network, &rpc_client, tx_raw).await)?;
 
-        let tx_commit_response = tx_raw.broadcast_commit(&rpc_client).await.unwrap();
+            self.record_audit(&tx_body, &fee, &tx_commit_response);
+
+            // sequence is consumed once the tx passes check_tx, even if deliver_tx fails
+            if tx_commit_response.check_tx.code.is_ok() {
+                self.inner.chain.cache_account(
+                    &self.inner.network,
+                    BaseAccount {
+                        sequence: acc.sequence + 1,
+                        ..acc.clone()
+                    },
+                );
+            }
+
+            let sequence_mismatch = is_sequence_mismatch(&tx_commit_response.check_tx)
+                || is_sequence_mismatch(&tx_commit_response.deliver_tx);
+            if !sequence_mismatch || attempt >= retries {
+                break tx_commit_response;
+            }
+
+            attempt += 1;
+            eprintln!(
+                "  {} account sequence mismatch, retrying with refreshed sequence ({attempt}/{retries})",
+                style("WARNING:").yellow()
+            );
+            acc = self.refreshed_account(acc.sequence).await?;
+        };
 
         if tx_commit_response.check_tx.code.is_err() {
-            return Err(anyhow!(
-                "check_tx failed: {:?}",
-                tx_commit_response.check_tx
-            ));
+            return Err(anyhow!(Error::tx(
+                "check_tx",
+                tx_commit_response.hash,
+                &tx_commit_response.check_tx
+            )));
+        }
+
+        // fee is charged once the tx passes check_tx, so failed txs are recorded as well
+        if let Some(gas_ledger) = &self.gas_ledger {
+            if let Err(e) = gas_ledger.record(&self.inner.network, &tx_commit_response, &fee) {
+                eprintln!(
+                    "  {} unable to record gas usage: {e}",
+                    style("WARNING:").yellow()
+                );
+            }
         }
 
         if tx_commit_response.deliver_tx.code.is_err() {
-            return Err(anyhow!(
-                "deliver_tx failed: {:?}",
-                tx_commit_response.deliver_tx
-            ));
+            return Err(anyhow!(Error::tx(
+                "deliver_tx",
+                tx_commit_response.hash,
+                &tx_commit_response.deliver_tx
+            )));
         }
 
-        // dev::poll_for_tx(&rpc_client, tx_commit_response.hash).await;
+        Ok(tx_commit_response)
+    }
 
+    /// Sign with `fee` at the sequence of `account` and broadcast, without summary, confirmation, retries
+    /// or gas records, the tx is still recorded in the audit log.
+    /// For txs of throwaway accounts sent in bulk, eg. by `beaker bench`
+    pub async fn broadcast_unattended(
+        &self,
+        tx_body: &tx::Body,
+        fee: Fee,
+        account: &BaseAccount,
+    ) -> Result<TxCommitResponse> {
+        let tx_raw = self.sign_tx(tx_body, fee.clone(), account)?;
+        let rpc_client = self.inner.chain.rpc_client(&self.inner.network)?;
+        let tx_commit_response = broadcast(&self.inner.network, &rpc_client, tx_raw).await?;
+        self.record_audit(tx_body, &fee, &tx_commit_response);
         Ok(tx_commit_response)
     }
+
+    /// Append the broadcast tx to the audit log, failing to do so does not fail the tx that is already sent
+    fn record_audit(&self, tx_body: &tx::Body, fee: &Fee, tx_commit_response: &TxCommitResponse) {
+        if let Err(e) = audit_log::record(
+            &self.inner.network,
+            &self.signer_account_id(),
+            tx_body,
+            fee,
+            tx_commit_response,
+        ) {
+            eprintln!(
+                "  {} unable to record transaction in audit log: {e:#}",
+                style("WARNING:").yellow()
+            );
+        }
+    }
+}
+
+/// Only freshly started chain needs to wait, long-lived network passes with a single status check,
+/// which is skipped while the status cached by a recent invocation is fresh
+async fn wait_for_first_block(
+    chain_client: &ChainClient,
+    network: &Network,
+    rpc_client: &rpc::HttpClient,
+) -> Result<()> {
+    if let Some(status) = chain_client.cached_status(network) {
+        if status.latest_block_height > 0 {
+            return Ok(());
+        }
+    }
+
+    throttle::request(network, network.rpc_endpoint()).await;
+    let status = match rpc_request(network, rpc_client.status()).await {
+        std::result::Result::Ok(status) => status,
+        // node might still be starting up, eg. right after `localnet start`
+        Err(_) => {
+            dev::poll_for_first_block(rpc_client).await;
+            return Ok(());
+        }
+    };
+    chain_client.cache_status(
+        network,
+        &ChainStatus {
+            chain_id: status.node_info.network.to_string(),
+            latest_block_height: status.sync_info.latest_block_height.value(),
+            latest_block_time: status.sync_info.latest_block_time.to_rfc3339(),
+        },
+    );
+    if status.sync_info.latest_block_height.value() == 0 {
+        dev::poll_for_first_block(rpc_client).await;
+    }
+    Ok(())
+}
+
+/// Whether `result` failed with cosmos-sdk's `ErrWrongSequence`, modules reuse code 32 in their own codespaces.
+/// `broadcast_tx_sync` does not report the codespace, the sdk's error message is recognized there instead
+fn is_sequence_mismatch(result: &TxResult) -> bool {
+    if result.code.value() != SEQUENCE_MISMATCH_CODE {
+        return false;
+    }
+    match result.codespace.to_string().as_str() {
+        "sdk" => true,
+        "" => result.log.to_string().contains("account sequence mismatch"),
+        _ => false,
+    }
+}
+
+async fn broadcast(
+    network: &Network,
+    rpc_client: &rpc::HttpClient,
+    tx_raw: Raw,
+) -> Result<TxCommitResponse> {
+    throttle::request(network, network.rpc_endpoint()).await;
+    match network.broadcast_mode() {
+        BroadcastMode::Block => {
+            rpc_request(network, async {
+                tx_raw
+                    .broadcast_commit(rpc_client)
+                    .await
+                    .map_err(|e| anyhow!(e))
+            })
+            .await
+        }
+        BroadcastMode::Sync => {
+            let tx_bytes = tx_raw.to_bytes().map_err(|e| anyhow!(e))?;
+            let res = rpc_request(network, rpc_client.broadcast_tx_sync(tx_bytes.into())).await?;
+
+            let check_tx = TxResult {
+                code: res.code,
+                data: res.data,
+                log: res.log,
+                info: Default::default(),
+                gas_wanted: Default::default(),
+                gas_used: Default::default(),
+                events: vec![],
+                codespace: Default::default(),
+            };
+            if check_tx.code.is_err() {
+                return Ok(TxCommitResponse {
+                    deliver_tx: check_tx.clone(),
+                    check_tx,
+                    hash: res.hash,
+                    height: Default::default(),
+                });
+            }
+
+            for _ in 0..TX_POLL_ATTEMPTS {
+                throttle::request(network, network.rpc_endpoint()).await;
+                if let std::result::Result::Ok(tx) =
+                    rpc_request(network, rpc_client.tx(res.hash, false)).await
+                {
+                    let deliver_tx = tx.tx_result;
+                    return Ok(TxCommitResponse {
+                        check_tx,
+                        deliver_tx: TxResult {
+                            code: deliver_tx.code,
+                            data: deliver_tx.data,
+                            log: deliver_tx.log,
+                            info: deliver_tx.info,
+                            gas_wanted: deliver_tx.gas_wanted,
+                            gas_used: deliver_tx.gas_used,
+                            events: deliver_tx.events,
+                            codespace: deliver_tx.codespace,
+                        },
+                        hash: tx.hash,
+                        height: tx.height,
+                    });
+                }
+                tokio::time::sleep(TX_POLL_INTERVAL).await;
+            }
+            bail!("Transaction {} was not committed in time", res.hash)
+        }
+    }
 }
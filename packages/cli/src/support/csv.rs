@@ -0,0 +1,104 @@
+//! Reading csv files with a header row, fields may be double quoted with `""` escaping a quote
+
+use anyhow::{bail, Result};
+
+/// Rows of `content` as `(header, value)` pairs in column order, blank lines are skipped
+pub fn parse(content: &str) -> Result<Vec<Vec<(String, String)>>> {
+    let mut records = records(content)?.into_iter();
+    let header = match records.next() {
+        Some((_, header)) => header,
+        None => bail!("csv has no header row"),
+    };
+
+    records
+        .map(|(line, record)| {
+            if record.len() != header.len() {
+                bail!(
+                    "csv line {line} has {} fields, expected {} as in the header",
+                    record.len(),
+                    header.len()
+                );
+            }
+            Ok(header.iter().cloned().zip(record).collect())
+        })
+        .collect()
+}
+
+/// Records with the line they start at
+fn records(content: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if field.is_empty() => quoted = true,
+            (',', false) => record.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field));
+                let fields = std::mem::take(&mut record);
+                if !(fields.len() == 1 && fields[0].trim().is_empty()) {
+                    records.push((start, fields));
+                }
+                line += 1;
+                start = line;
+            }
+            (c, _) => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+    if quoted {
+        bail!("csv line {start} has an unterminated quoted field");
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((start, record));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rows_by_header() {
+        let content =
+            "addr,amt,memo\r\nosmo1a,100,\"hello, \"\"world\"\"\"\n\nosmo1b,200,\"multi\nline\"\n";
+        let row = |fields: [(&str, &str); 3]| {
+            fields
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            parse(content).unwrap(),
+            vec![
+                row([
+                    ("addr", "osmo1a"),
+                    ("amt", "100"),
+                    ("memo", "hello, \"world\"")
+                ]),
+                row([("addr", "osmo1b"), ("amt", "200"), ("memo", "multi\nline")]),
+            ]
+        );
+
+        assert!(parse("").is_err());
+        assert!(parse("addr,amt\nosmo1a").is_err());
+        assert!(parse("addr\n\"osmo1a").is_err());
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt;
+
+use cosmrs::rpc::endpoint::broadcast::tx_commit::TxResult;
+use serde::Serialize;
+
+use super::output;
+
+/// Class of failure, each mapped to a stable process exit code so that scripts can react to it.
+///
+/// | code | kind                 |
+/// |------|----------------------|
+/// | 1    | `unknown`            |
+/// | 2    | invalid arguments (reported by clap before any command runs) |
+/// | 3    | `input_required`     |
+/// | 4    | `config`             |
+/// | 5    | `rpc`                |
+/// | 6    | `insufficient_funds` |
+/// | 7    | `tx_failed`          |
+/// | 8    | `contract_error`     |
+/// | 9    | `timeout`            |
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    Unknown,
+    /// Interactive input was needed but prompting is disabled
+    InputRequired,
+    /// Missing or invalid project / network configuration
+    Config,
+    /// Chain node is unreachable or rejected the query
+    Rpc,
+    InsufficientFunds,
+    /// Transaction was rejected or failed on chain
+    TxFailed,
+    /// Contract returned an error while executing the transaction
+    ContractError,
+    /// Endpoint did not respond in time or the command exceeded its `--timeout`
+    Timeout,
+}
+
+impl ErrorKind {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorKind::Unknown => 1,
+            ErrorKind::InputRequired => 3,
+            ErrorKind::Config => 4,
+            ErrorKind::Rpc => 5,
+            ErrorKind::InsufficientFunds => 6,
+            ErrorKind::TxFailed => 7,
+            ErrorKind::ContractError => 8,
+            ErrorKind::Timeout => 9,
+        }
+    }
+}
+
+/// Error with known failure class, carried inside `anyhow::Error` and recovered by [`kind_of`]
+#[derive(Debug)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub tx_hash: Option<String>,
+    pub raw_log: Option<String>,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Error {
+            kind,
+            message: message.into(),
+            tx_hash: None,
+            raw_log: None,
+        }
+    }
+
+    /// Failed `check_tx` or `deliver_tx`, classified by its codespace and code
+    pub fn tx(stage: &str, hash: impl ToString, result: &TxResult) -> Self {
+        let kind = match (result.codespace.to_string().as_str(), result.code.value()) {
+            ("sdk", 5) => ErrorKind::InsufficientFunds,
+            ("wasm", _) => ErrorKind::ContractError,
+            _ => ErrorKind::TxFailed,
+        };
+        Error {
+            kind,
+            message: format!("{stage} failed: {}", result.log),
+            tx_hash: Some(hash.to_string()),
+            raw_log: Some(result.log.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The typed error closest to the cause, if any
+pub fn typed(err: &anyhow::Error) -> Option<&Error> {
+    err.chain().find_map(|e| e.downcast_ref::<Error>())
+}
+
+/// Failure class of `err`, transport failures are recognized even when not wrapped in a typed error
+pub fn kind_of(err: &anyhow::Error) -> ErrorKind {
+    if let Some(e) = typed(err) {
+        return e.kind;
+    }
+    if err.chain().any(|e| e.is::<config::ConfigError>()) {
+        return ErrorKind::Config;
+    }
+    let is_timeout = err.chain().any(|e| {
+        e.downcast_ref::<tonic::Status>()
+            .map(|s| s.code() == tonic::Code::DeadlineExceeded)
+            .unwrap_or(false)
+            || e.is::<tokio::time::error::Elapsed>()
+    });
+    if is_timeout {
+        return ErrorKind::Timeout;
+    }
+    let is_rpc = err.chain().any(|e| {
+        e.is::<tonic::Status>() || e.is::<tonic::transport::Error>() || e.is::<cosmrs::rpc::Error>()
+    });
+    if is_rpc {
+        ErrorKind::Rpc
+    } else {
+        ErrorKind::Unknown
+    }
+}
+
+/// Process exit code for `err`
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    kind_of(err).exit_code()
+}
+
+/// Failure as reported to orchestration systems when machine readable output is selected
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct ErrorReport {
+    pub code: i32,
+    pub kind: ErrorKind,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_log: Option<String>,
+}
+
+impl From<&anyhow::Error> for ErrorReport {
+    fn from(err: &anyhow::Error) -> Self {
+        let kind = kind_of(err);
+        let typed = typed(err);
+        ErrorReport {
+            code: kind.exit_code(),
+            kind,
+            message: format!("{err:#}"),
+            tx_hash: typed.and_then(|e| e.tx_hash.clone()),
+            raw_log: typed.and_then(|e| e.raw_log.clone()),
+        }
+    }
+}
+
+/// Print `err` to stderr, structured when machine readable output is selected
+pub fn report(err: &anyhow::Error) {
+    match output::to_machine_readable(&ErrorReport::from(err)) {
+        Ok(Some(s)) => eprintln!("{s}"),
+        _ => eprintln!("Error: {err:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{anyhow, Context as _};
+
+    use super::*;
+
+    #[test]
+    fn classify_wrapped_errors() {
+        let err = Err::<(), _>(anyhow!(Error::new(
+            ErrorKind::InputRequired,
+            "Input required"
+        )))
+        .context("Unable to sign")
+        .unwrap_err();
+        assert_eq!(kind_of(&err), ErrorKind::InputRequired);
+        assert_eq!(exit_code(&err), 3);
+
+        let err = anyhow!(tonic::Status::unavailable("connection refused")).context("query");
+        assert_eq!(kind_of(&err), ErrorKind::Rpc);
+
+        assert_eq!(kind_of(&anyhow!("boom")), ErrorKind::Unknown);
+        assert_eq!(exit_code(&anyhow!("boom")), 1);
+    }
+
+    #[test]
+    fn report_tx_failure() {
+        let err = anyhow!(Error {
+            kind: ErrorKind::ContractError,
+            message: "deliver_tx failed: execute wasm contract failed".to_string(),
+            tx_hash: Some("ABCD".to_string()),
+            raw_log: Some("execute wasm contract failed".to_string()),
+        })
+        .context("Unable to execute `counter`");
+
+        assert_eq!(
+            serde_json::to_value(ErrorReport::from(&err)).unwrap(),
+            serde_json::json!({
+                "code": 8,
+                "kind": "contract_error",
+                "message": "Unable to execute `counter`: deliver_tx failed: execute wasm contract failed",
+                "tx_hash": "ABCD",
+                "raw_log": "execute wasm contract failed",
+            })
+        );
+    }
+}
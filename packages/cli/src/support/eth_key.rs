@@ -0,0 +1,128 @@
+use anyhow::{anyhow, Result};
+use cosmrs::crypto::{secp256k1::SigningKey, PublicKey};
+use cosmrs::AccountId;
+use k256::ecdsa::signature::{self, DigestSigner, Signer};
+use k256::ecdsa::{Signature, VerifyingKey};
+use prost::Message;
+use sha3::{Digest, Keccak256};
+
+use crate::framework::config::{KeyAlgo, Network};
+
+/// Public key type of ethermint based chains, eg. Evmos
+pub const ETHERMINT_PUB_KEY_TYPE: &str = "/ethermint.crypto.v1.ethsecp256k1.PubKey";
+
+/// secp256k1 key signing keccak256 of the message, as `eth_secp256k1` accounts do
+pub struct EthKey {
+    signing_key: k256::ecdsa::SigningKey,
+    verifying_key: VerifyingKey,
+}
+
+impl EthKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let signing_key = k256::ecdsa::SigningKey::from_bytes(bytes)
+            .map_err(|e| anyhow!("Invalid private key: {e}"))?;
+        Ok(EthKey {
+            verifying_key: signing_key.verifying_key(),
+            signing_key,
+        })
+    }
+
+    pub fn into_signing_key(self) -> SigningKey {
+        SigningKey::new(Box::new(self))
+    }
+}
+
+impl AsRef<VerifyingKey> for EthKey {
+    fn as_ref(&self) -> &VerifyingKey {
+        &self.verifying_key
+    }
+}
+
+impl Signer<Signature> for EthKey {
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature, signature::Error> {
+        self.signing_key
+            .try_sign_digest(Keccak256::new_with_prefix(msg))
+    }
+}
+
+/// Signing key of `key_algo` from raw private key bytes
+pub fn signing_key_from_bytes(bytes: &[u8], key_algo: KeyAlgo) -> Result<SigningKey> {
+    match key_algo {
+        KeyAlgo::Secp256k1 => {
+            SigningKey::from_bytes(bytes).map_err(|e| anyhow!("Invalid private key: {e}"))
+        }
+        KeyAlgo::EthSecp256k1 => Ok(EthKey::from_bytes(bytes)?.into_signing_key()),
+    }
+}
+
+/// Address of `public_key` with `prefix`, derived as the accounts of `key_algo` are
+pub fn account_id(public_key: &PublicKey, prefix: &str, key_algo: KeyAlgo) -> Result<AccountId> {
+    match key_algo {
+        KeyAlgo::Secp256k1 => public_key.account_id(prefix).map_err(|e| anyhow!(e)),
+        KeyAlgo::EthSecp256k1 => {
+            let uncompressed = VerifyingKey::from_sec1_bytes(&public_key.to_bytes())
+                .map_err(|e| anyhow!("Invalid secp256k1 public key: {e}"))?
+                .to_encoded_point(false);
+            let hash = Keccak256::digest(&uncompressed.as_bytes()[1..]);
+            AccountId::new(prefix, &hash[12..]).map_err(|e| anyhow!(e))
+        }
+    }
+}
+
+/// Public key of the signer as it has to appear in signer info on `network` for `key_algo` accounts,
+/// `None` if the default cosmos secp256k1 encoding applies
+pub fn signer_pub_key(
+    public_key: &PublicKey,
+    key_algo: KeyAlgo,
+    network: &Network,
+) -> Option<prost_types::Any> {
+    match key_algo {
+        KeyAlgo::Secp256k1 => None,
+        KeyAlgo::EthSecp256k1 => Some(prost_types::Any {
+            type_url: network
+                .pub_key_type()
+                .clone()
+                .unwrap_or_else(|| ETHERMINT_PUB_KEY_TYPE.to_string()),
+            // same `bytes key = 1` layout as cosmos' secp256k1 `PubKey`
+            value: cosmrs::proto::cosmos::crypto::secp256k1::PubKey {
+                key: public_key.to_bytes(),
+            }
+            .encode_to_vec(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::signature::{DigestVerifier, Verifier};
+
+    #[test]
+    fn derive_eth_address_and_sign_keccak() {
+        // well known private key `1`, its ethereum address is 0x7e5f4552091a69125d5dfcb7b8c2659029395bdf
+        let mut bytes = [0u8; 32];
+        bytes[31] = 1;
+        let key = signing_key_from_bytes(&bytes, KeyAlgo::EthSecp256k1).unwrap();
+
+        let address = account_id(&key.public_key(), "inj", KeyAlgo::EthSecp256k1).unwrap();
+        assert_eq!(
+            hex::encode(address.to_bytes()),
+            "7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+        );
+        assert_ne!(
+            account_id(&key.public_key(), "inj", KeyAlgo::Secp256k1).unwrap(),
+            address
+        );
+
+        let eth_key = EthKey::from_bytes(&bytes).unwrap();
+        let signature: Signature = eth_key.sign(b"sign doc");
+        assert!(eth_key
+            .verifying_key
+            .verify_digest(Keccak256::new_with_prefix(b"sign doc"), &signature)
+            .is_ok());
+        assert!(eth_key
+            .verifying_key
+            .verify(b"sign doc", &signature)
+            .is_err());
+    }
+}
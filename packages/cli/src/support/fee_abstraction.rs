@@ -0,0 +1,92 @@
+//! Osmosis `x/txfees` queries, which let fees be paid in IBC denoms that have a pool with the base denom.
+//! Fee paid in such denom is valued at its spot price against the base denom.
+
+use anyhow::{bail, Context, Result};
+
+use super::gas::GasPrice;
+
+pub const FEE_TOKENS_PATH: &str = "/osmosis.txfees.v1beta1.Query/FeeTokens";
+pub const DENOM_SPOT_PRICE_PATH: &str = "/osmosis.txfees.v1beta1.Query/DenomSpotPrice";
+pub const BASE_DENOM_PATH: &str = "/osmosis.txfees.v1beta1.Query/BaseDenom";
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryFeeTokensRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryFeeTokensResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub fee_tokens: Vec<FeeToken>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FeeToken {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+    #[prost(uint64, tag = "2")]
+    pub pool_id: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryDenomSpotPriceRequest {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryDenomSpotPriceResponse {
+    #[prost(uint64, tag = "1")]
+    pub pool_id: u64,
+    /// `sdk.Dec`, encoded as integer scaled by 10^18
+    #[prost(string, tag = "2")]
+    pub spot_price: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryBaseDenomRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryBaseDenomResponse {
+    #[prost(string, tag = "1")]
+    pub base_denom: String,
+}
+
+/// Parse `sdk.Dec` from its protobuf encoding (integer scaled by 10^18) or its decimal string form
+pub fn parse_dec(dec: &str) -> Result<f64> {
+    let value = if dec.contains('.') {
+        dec.parse::<f64>()?
+    } else {
+        dec.parse::<f64>()? / 1e18
+    };
+    Ok(value)
+}
+
+/// Gas price in `denom`, equivalent to `base` gas price given `spot_price` of `denom` in base denom
+pub fn convert_gas_price(base: &GasPrice, denom: &str, spot_price: f64) -> Result<GasPrice> {
+    if spot_price <= 0.0 {
+        bail!("Spot price of `{denom}` is {spot_price}, unable to pay fee with it");
+    }
+    Ok(GasPrice {
+        amount: base.amount / spot_price,
+        denom: denom
+            .parse()
+            .map_err(|e| anyhow::anyhow!("{e}"))
+            .with_context(|| format!("Invalid fee denom `{denom}`"))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_gas_price_by_spot_price() {
+        assert_eq!(parse_dec("2500000000000000000").unwrap(), 2.5);
+        assert_eq!(parse_dec("0.5").unwrap(), 0.5);
+
+        let base = "0.025uosmo".parse::<GasPrice>().unwrap();
+        let price = convert_gas_price(&base, "ibc/ABCD", 0.5).unwrap();
+        assert_eq!(price.amount, 0.05);
+        assert_eq!(price.denom.as_ref(), "ibc/ABCD");
+        assert!(convert_gas_price(&base, "ibc/ABCD", 0.0).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+//! Current base fee of chains with a fee market, osmosis `x/txfees` EIP-1559 base fee or skip's `x/feemarket`,
+//! which rises above the static `gas_price` during congestion
+
+use anyhow::{bail, Result};
+
+use super::gas::GasPrice;
+
+pub const EIP_BASE_FEE_PATH: &str = "/osmosis.txfees.v1beta1.Query/GetEipBaseFee";
+pub const FEEMARKET_GAS_PRICE_PATH: &str = "/feemarket.feemarket.v1.Query/GasPrice";
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryEipBaseFeeRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryEipBaseFeeResponse {
+    /// `sdk.Dec`, encoded as integer scaled by 10^18
+    #[prost(string, tag = "1")]
+    pub base_fee: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GasPriceRequest {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GasPriceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub price: Option<DecCoin>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DecCoin {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+    /// `sdk.Dec`, encoded as integer scaled by 10^18
+    #[prost(string, tag = "2")]
+    pub amount: String,
+}
+
+/// Gas price of `base_fee` in the denom of `configured`, scaled by `multiplier` as a safety margin
+/// for the base fee rising before the tx is included
+pub fn live_gas_price(configured: &GasPrice, base_fee: f64, multiplier: f64) -> Result<GasPrice> {
+    if !(base_fee > 0.0 && base_fee.is_finite()) {
+        bail!("Invalid base fee {base_fee}");
+    }
+    Ok(GasPrice {
+        amount: base_fee * multiplier,
+        denom: configured.denom.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_base_fee() {
+        let configured = "0.0025uosmo".parse::<GasPrice>().unwrap();
+        let price = live_gas_price(&configured, 0.01, 1.5).unwrap();
+        assert!((price.amount - 0.015).abs() < 1e-12);
+        assert_eq!(price.denom.as_ref(), "uosmo");
+        assert!(live_gas_price(&configured, 0.0, 1.5).is_err());
+    }
+}
@@ -1,9 +1,45 @@
 use std::future::Future;
+use std::time::Duration;
 
-pub fn block<F: Future>(future: F) -> F::Output {
+use anyhow::{anyhow, Result};
+use once_cell::sync::{Lazy, OnceCell};
+use tokio::runtime::Runtime;
+use tokio::time::Instant;
+
+use super::error::{Error, ErrorKind};
+
+/// Single runtime for the whole invocation, connections opened in one `block` stay usable in the next
+static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
     tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()
         .unwrap()
-        .block_on(future)
+});
+
+static DEADLINE: OnceCell<(Instant, Duration)> = OnceCell::new();
+
+/// Fail every `block` still running once `timeout` has passed from now, so that hung endpoints
+/// and polling loops can not block the caller indefinitely
+pub fn set_deadline(timeout: Duration) {
+    let _ = DEADLINE.set((Instant::now() + timeout, timeout));
+}
+
+/// Run `future` to completion, failing with a timeout error once the deadline set by `--timeout` has passed
+pub fn block<T, F>(future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    match DEADLINE.get() {
+        None => RUNTIME.block_on(future),
+        Some((deadline, timeout)) => RUNTIME
+            .block_on(tokio::time::timeout_at(*deadline, future))
+            .unwrap_or_else(|_| {
+                Err(anyhow!(Error::new(
+                    ErrorKind::Timeout,
+                    format!(
+                        "Command did not finish within the {timeout:?} deadline set by `--timeout`"
+                    ),
+                )))
+            }),
+    }
 }
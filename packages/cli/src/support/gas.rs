@@ -1,14 +1,16 @@
 use std::str::FromStr;
 
-use anyhow::{Context, Error, Result};
+use anyhow::{bail, Context, Error, Result};
 use clap::Parser;
 use cosmrs::{tx::Fee, Denom};
 use getset::Getters;
 use regex::Regex;
+use serde::Deserialize;
 
 use super::coin::CoinFromStr;
-#[derive(Debug, Parser, Getters, Clone)]
+#[derive(Debug, Parser, Getters, Clone, Default, Deserialize)]
 #[get = "pub"]
+#[serde(default)]
 pub struct GasArgs {
     /// Coin (amount and denom) you are willing to pay as gas eg. `1000uosmo`
     #[clap(long)]
@@ -16,6 +18,12 @@ pub struct GasArgs {
     /// Limit to how much gas amount allowed to be consumed
     #[clap(long)]
     gas_limit: Option<u64>,
+    /// Denom to pay estimated fee in, eg. an IBC denom. Priced by the network's `fee_denoms`, or converted from `gas_price` if the chain accepts it as fee token (osmosis `x/txfees`)
+    #[clap(long, conflicts_with = "gas")]
+    fee_denom: Option<String>,
+    /// Multiplier applied to simulated gas to get the gas limit, overriding `gas_adjustment` in config
+    #[clap(long, conflicts_with = "gas_limit")]
+    gas_adjustment: Option<f64>,
 }
 
 impl TryFrom<GasArgs> for Fee {
@@ -86,18 +94,104 @@ pub enum Gas {
     Auto {
         gas_price: GasPrice,
         gas_adjustment: f64,
+        /// Pay in this denom instead of `gas_price`'s, priced by the network's `fee_denoms`
+        fee_denom: Option<String>,
     },
 }
 
 impl Gas {
+    /// Fee from `--gas` and `--gas-limit` if specified, otherwise estimated by simulation,
+    /// with `--gas-adjustment` taking precedence over the configured `gas_adjustment`
     pub fn from_args(args: &GasArgs, gas_price: &str, gas_adjustment: &f64) -> Result<Self> {
         if args.gas_limit.is_none() && args.gas.is_none() {
+            let gas_adjustment = args.gas_adjustment.unwrap_or(*gas_adjustment);
+            if !(gas_adjustment > 0.0 && gas_adjustment.is_finite()) {
+                bail!("Gas adjustment must be a positive number, got {gas_adjustment}");
+            }
             Ok(Self::Auto {
                 gas_price: gas_price.parse()?,
-                gas_adjustment: gas_adjustment.to_owned(),
+                gas_adjustment,
+                fee_denom: args.fee_denom.clone(),
             })
         } else {
             Ok(Self::Specified(Fee::try_from(args)?))
         }
     }
 }
+
+/// Gas price to pay fee with, `default` unless `fee_denom` asks for another denom accepted by the network.
+/// `accepted` are gas prices of the network's `fee_denoms`, eg. `0.0025ibc/27394FB092D2ECCD...`
+pub fn fee_gas_price(
+    default: &GasPrice,
+    fee_denom: Option<&str>,
+    accepted: &[String],
+) -> Result<GasPrice> {
+    let fee_denom = match fee_denom {
+        Some(denom) if denom != default.denom.as_ref() => denom,
+        _ => return Ok(default.clone()),
+    };
+
+    let accepted = accepted
+        .iter()
+        .map(|p| p.parse::<GasPrice>())
+        .collect::<Result<Vec<_>>>()?;
+    accepted
+        .iter()
+        .find(|p| p.denom.as_ref() == fee_denom)
+        .cloned()
+        .with_context(|| {
+            format!(
+                "Fee denom `{fee_denom}` is not accepted by the network, accepted fee denoms: {}",
+                std::iter::once(default.denom.to_string())
+                    .chain(accepted.iter().map(|p| p.denom.to_string()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pick_fee_gas_price() {
+        let default = "0.025uosmo".parse::<GasPrice>().unwrap();
+        let accepted = vec!["0.0025uion".to_string()];
+
+        assert_eq!(
+            fee_gas_price(&default, None, &accepted).unwrap().denom,
+            default.denom
+        );
+        assert_eq!(
+            fee_gas_price(&default, Some("uosmo"), &[]).unwrap().amount,
+            0.025
+        );
+        let price = fee_gas_price(&default, Some("uion"), &accepted).unwrap();
+        assert_eq!((price.amount, price.denom.as_ref()), (0.0025, "uion"));
+        assert!(fee_gas_price(&default, Some("uatom"), &accepted).is_err());
+    }
+
+    #[test]
+    fn override_gas_adjustment() {
+        let adjustment = |args: GasArgs| match Gas::from_args(&args, "0.025uosmo", &1.3)? {
+            Gas::Auto { gas_adjustment, .. } => Ok(gas_adjustment),
+            Gas::Specified(_) => bail!("fee is specified"),
+        };
+
+        assert_eq!(adjustment(GasArgs::default()).unwrap(), 1.3);
+        assert_eq!(
+            adjustment(GasArgs {
+                gas_adjustment: Some(1.8),
+                ..Default::default()
+            })
+            .unwrap(),
+            1.8
+        );
+        assert!(adjustment(GasArgs {
+            gas_adjustment: Some(0.0),
+            ..Default::default()
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use config::Map;
+use cosmrs::tx::Fee;
+use serde::{Deserialize, Serialize};
+
+use super::cosmos::TxCommitResponse;
+use super::state::STATE_DIR;
+use crate::framework::config::{Network, NetworkVariant};
+
+pub const GAS_LEDGER_FILE_LOCAL: &str = "gas_ledger.local.jsonl";
+pub const GAS_LEDGER_FILE_SHARED: &str = "gas_ledger.jsonl";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LedgerCoin {
+    pub denom: String,
+    pub amount: u128,
+}
+
+/// Gas and fee of a single transaction broadcasted by beaker
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LedgerEntry {
+    pub timestamp: u64,
+    pub network: String,
+    pub chain_id: String,
+    pub contract: Option<String>,
+    pub tx_hash: String,
+    pub height: u64,
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+    pub fee: Vec<LedgerCoin>,
+    /// Whether `deliver_tx` succeeded, the fee is charged either way
+    #[serde(default = "default_success")]
+    pub success: bool,
+}
+
+fn default_success() -> bool {
+    true
+}
+
+/// Records every broadcasted transaction that passed `check_tx` into the state dir as json lines,
+/// separated by network variant the same way as state files.
+#[derive(Debug, Clone)]
+pub struct GasLedger {
+    root: PathBuf,
+    network: String,
+    contract: Option<String>,
+}
+
+impl GasLedger {
+    pub fn new(root: PathBuf, network: &str, contract: Option<&str>) -> Self {
+        GasLedger {
+            root,
+            network: network.to_string(),
+            contract: contract.map(|c| c.to_string()),
+        }
+    }
+
+    pub fn record(
+        &self,
+        network_info: &Network,
+        response: &TxCommitResponse,
+        fee: &Fee,
+    ) -> Result<LedgerEntry> {
+        let entry = LedgerEntry {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            network: self.network.clone(),
+            chain_id: network_info.chain_id().to_string(),
+            contract: self.contract.clone(),
+            tx_hash: response.hash.to_string(),
+            height: response.height.value(),
+            gas_wanted: response.deliver_tx.gas_wanted.value(),
+            gas_used: response.deliver_tx.gas_used.value(),
+            fee: fee
+                .amount
+                .iter()
+                .map(|c| LedgerCoin {
+                    denom: c.denom.to_string(),
+                    amount: c.amount.to_string().parse().unwrap_or_default(),
+                })
+                .collect(),
+            success: response.deliver_tx.code.is_ok(),
+        };
+
+        let state_dir = self.root.join(STATE_DIR);
+        fs::create_dir_all(&state_dir)?;
+        let path = state_dir.join(ledger_file(network_info));
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Unable to open `{}`", path.to_string_lossy()))?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+
+        Ok(entry)
+    }
+}
+
+fn ledger_file(network_info: &Network) -> &'static str {
+    match network_info.network_variant() {
+        NetworkVariant::Local => GAS_LEDGER_FILE_LOCAL,
+        NetworkVariant::Shared => GAS_LEDGER_FILE_SHARED,
+    }
+}
+
+/// Load all ledger entries from both local and shared ledger files
+pub fn load_entries(root: &PathBuf) -> Result<Vec<LedgerEntry>> {
+    let mut entries = vec![];
+    for file in [GAS_LEDGER_FILE_SHARED, GAS_LEDGER_FILE_LOCAL] {
+        let path = root.join(STATE_DIR).join(file);
+        if !path.exists() {
+            continue;
+        }
+        let content = fs::read_to_string(&path)?;
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            entries.push(serde_json::from_str(line).with_context(|| {
+                format!("Invalid entry at `{}:{}`", path.to_string_lossy(), i + 1)
+            })?);
+        }
+    }
+    Ok(entries)
+}
+
+/// Aggregated gas usage and fees
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct GasSummary {
+    pub tx_count: u64,
+    pub failed_tx_count: u64,
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+    pub fees: Map<String, u128>,
+}
+
+impl GasSummary {
+    fn add(&mut self, entry: &LedgerEntry) {
+        self.tx_count += 1;
+        if !entry.success {
+            self.failed_tx_count += 1;
+        }
+        self.gas_wanted += entry.gas_wanted;
+        self.gas_used += entry.gas_used;
+        for coin in &entry.fee {
+            *self.fees.entry(coin.denom.clone()).or_default() += coin.amount;
+        }
+    }
+}
+
+/// Aggregate entries per network, then per contract (`-` for tx not related to any contract)
+pub fn summarize(entries: &[LedgerEntry]) -> Map<String, Map<String, GasSummary>> {
+    let mut summary: Map<String, Map<String, GasSummary>> = Map::new();
+    for entry in entries {
+        summary
+            .entry(entry.network.clone())
+            .or_default()
+            .entry(entry.contract.clone().unwrap_or_else(|| "-".to_string()))
+            .or_default()
+            .add(entry);
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(network: &str, contract: Option<&str>, gas_used: u64, fee: u128) -> LedgerEntry {
+        LedgerEntry {
+            timestamp: 0,
+            network: network.to_string(),
+            chain_id: "test-1".to_string(),
+            contract: contract.map(|c| c.to_string()),
+            tx_hash: "HASH".to_string(),
+            height: 1,
+            gas_wanted: gas_used * 2,
+            gas_used,
+            fee: vec![LedgerCoin {
+                denom: "uosmo".to_string(),
+                amount: fee,
+            }],
+            success: true,
+        }
+    }
+
+    #[test]
+    fn summarize_per_network_and_contract() {
+        let summary = summarize(&[
+            entry("testnet", Some("counter"), 100, 10),
+            LedgerEntry {
+                success: false,
+                ..entry("testnet", Some("counter"), 50, 5)
+            },
+            entry("testnet", None, 10, 1),
+            entry("mainnet", Some("counter"), 1, 1),
+        ]);
+
+        assert_eq!(
+            summary["testnet"]["counter"],
+            GasSummary {
+                tx_count: 2,
+                failed_tx_count: 1,
+                gas_wanted: 300,
+                gas_used: 150,
+                fees: Map::from([("uosmo".to_string(), 15)]),
+            }
+        );
+        assert_eq!(summary["testnet"]["-"].tx_count, 1);
+        assert_eq!(summary["mainnet"]["counter"].gas_used, 1);
+    }
+}
@@ -0,0 +1,105 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+/// Run git in `root` and return its trimmed stdout
+fn git(root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .with_context(|| "Unable to run `git`, make sure it is installed")?;
+    if !output.status.success() {
+        bail!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn is_dirty(root: &Path) -> Result<bool> {
+    Ok(!git(root, &["status", "--porcelain"])?.is_empty())
+}
+
+/// Fail unless the working tree has no uncommitted changes, `action` is what requires it
+pub fn ensure_clean(root: &Path, action: &str) -> Result<()> {
+    if is_dirty(root)? {
+        bail!("Working tree has uncommitted changes, commit them before {action}");
+    }
+    Ok(())
+}
+
+/// Paths under `path` (relative to `root`) with uncommitted changes, including untracked files
+pub fn changed_files(root: &Path, path: &Path) -> Result<Vec<String>> {
+    Ok(git(
+        root,
+        &[
+            "ls-files",
+            "--modified",
+            "--deleted",
+            "--others",
+            "--exclude-standard",
+            "--",
+            &path.to_string_lossy(),
+        ],
+    )?
+    .lines()
+    .map(|line| line.to_string())
+    .collect())
+}
+
+/// Commit all changes to tracked files, returning the new commit
+pub fn commit_tracked(root: &Path, message: &str) -> Result<String> {
+    git(root, &["commit", "--all", "--message", message])?;
+    git(root, &["rev-parse", "HEAD"])
+}
+
+/// Push the current branch and `tag` to `remote`
+pub fn push_with_tag(root: &Path, remote: &str, tag: &str) -> Result<()> {
+    git(root, &["push", remote, "HEAD"])?;
+    git(root, &["push", remote, tag])?;
+    Ok(())
+}
+
+/// Commit checked out in the repository at `root`, suffixed with `-dirty` if there are uncommitted changes.
+/// `None` if `root` is not in a git repository or git is not available.
+pub fn head_commit(root: &Path) -> Option<String> {
+    let commit = git(root, &["rev-parse", "HEAD"]).ok()?;
+    Some(if is_dirty(root).ok()? {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
+
+/// Content of `path` (relative to `dir`) at `commit`, `None` if the file does not exist there
+pub fn show_file(dir: &Path, commit: &str, path: &str) -> Option<String> {
+    git(dir, &["show", &format!("{commit}:./{path}")]).ok()
+}
+
+/// Create annotated `tag` on the current commit, working tree must be clean
+/// so that the tag reflects exactly what was built
+pub fn create_annotated_tag(root: &Path, tag: &str, message: &str) -> Result<String> {
+    if is_dirty(root)? {
+        bail!("Working tree has uncommitted changes, commit them before tagging `{tag}`");
+    }
+    git(root, &["tag", "--annotate", tag, "--message", message])?;
+    git(root, &["rev-parse", "HEAD"])
+}
+
+/// Ensure the working tree is clean and checked out at `tag`
+pub fn verify_tag(root: &Path, tag: &str) -> Result<()> {
+    let tagged = git(root, &["rev-parse", &format!("{tag}^{{commit}}")])
+        .with_context(|| format!("Unable to find tag `{tag}`"))?;
+    let head = git(root, &["rev-parse", "HEAD"])?;
+    if tagged != head {
+        bail!("HEAD ({head}) does not match tag `{tag}` ({tagged})");
+    }
+    if is_dirty(root)? {
+        bail!("Working tree has uncommitted changes, it does not match tag `{tag}`");
+    }
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Result};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::{Proposal, ProposalStatus, VoteOption};
+use cosmrs::AccountId;
+use sha2::{Digest, Sha256};
+
+use super::cosmos::Client;
+
+pub const MSG_SUBMIT_PROPOSAL_V1_TYPE_URL: &str = "/cosmos.gov.v1.MsgSubmitProposal";
+
+/// `cosmos.gov.v1.MsgSubmitProposal`, proposing arbitrary msgs executed by the gov module
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MsgSubmitProposalV1 {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: Vec<prost_types::Any>,
+    #[prost(message, repeated, tag = "2")]
+    pub initial_deposit: Vec<Coin>,
+    #[prost(string, tag = "3")]
+    pub proposer: String,
+    #[prost(string, tag = "4")]
+    pub metadata: String,
+    #[prost(string, tag = "5")]
+    pub title: String,
+    #[prost(string, tag = "6")]
+    pub summary: String,
+}
+
+/// Address of the gov module account, the authority of msgs executed by proposals
+pub fn gov_module_address(account_prefix: &str) -> Result<AccountId> {
+    AccountId::new(account_prefix, &Sha256::digest(b"gov")[..20]).map_err(|e| anyhow!(e))
+}
+
+pub fn status_name(status: i32) -> &'static str {
+    match ProposalStatus::from_i32(status) {
+        Some(ProposalStatus::DepositPeriod) => "DepositPeriod",
+        Some(ProposalStatus::VotingPeriod) => "VotingPeriod",
+        Some(ProposalStatus::Passed) => "Passed",
+        Some(ProposalStatus::Rejected) => "Rejected",
+        Some(ProposalStatus::Failed) => "Failed",
+        Some(ProposalStatus::Unspecified) | None => "Unspecified",
+    }
+}
+
+/// Parse vote option, one of: yes, no, no_with_veto, abstain
+pub fn vote_option(option: &str) -> Result<VoteOption> {
+    match option {
+        "yes" => Ok(VoteOption::Yes),
+        "no" => Ok(VoteOption::No),
+        "no_with_veto" => Ok(VoteOption::NoWithVeto),
+        "abstain" => Ok(VoteOption::Abstain),
+        o => bail!("Invalid vote option: {o}"),
+    }
+}
+
+/// Whether the proposal reached its final status
+pub fn is_final(status: i32) -> bool {
+    matches!(
+        ProposalStatus::from_i32(status),
+        Some(ProposalStatus::Passed | ProposalStatus::Rejected | ProposalStatus::Failed)
+    )
+}
+
+/// Poll the proposal every `interval` until it is passed, rejected or failed
+pub async fn wait_for_proposal(
+    client: &Client,
+    proposal_id: u64,
+    interval: Duration,
+    timeout: Duration,
+) -> Result<Proposal> {
+    let started = Instant::now();
+    loop {
+        let proposal = client.proposal(&proposal_id).await?;
+        if is_final(proposal.status) {
+            return Ok(proposal);
+        }
+        if started.elapsed() >= timeout {
+            bail!(
+                "Timed out waiting for proposal {proposal_id}, still in {}",
+                status_name(proposal.status)
+            );
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn final_statuses() {
+        assert!(!is_final(ProposalStatus::DepositPeriod as i32));
+        assert!(!is_final(ProposalStatus::VotingPeriod as i32));
+        assert!(is_final(ProposalStatus::Passed as i32));
+        assert!(is_final(ProposalStatus::Rejected as i32));
+        assert!(is_final(ProposalStatus::Failed as i32));
+        assert_eq!(status_name(99), "Unspecified");
+    }
+
+    #[test]
+    fn derive_gov_module_address() {
+        assert_eq!(
+            gov_module_address("osmo").unwrap().to_string(),
+            "osmo10d07y265gmmuvt4z0w9aw880jnsr700jjeq4qp"
+        );
+    }
+
+    #[test]
+    fn parse_vote_options() {
+        assert_eq!(vote_option("yes").unwrap(), VoteOption::Yes);
+        assert_eq!(vote_option("no_with_veto").unwrap(), VoteOption::NoWithVeto);
+        assert!(vote_option("maybe").is_err());
+    }
+}
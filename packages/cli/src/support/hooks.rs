@@ -1,8 +1,7 @@
-use super::{future::block, state::State, wasm::get_code_id};
+use super::{future::block, prompt, state::State, wasm::get_code_id};
 use crate::{framework::config::Network, Context, WasmConfig};
 use anyhow::Context as _;
 use console::style;
-use dialoguer::Confirm;
 
 pub fn use_code_id<'a, Ctx: Context<'a, WasmConfig>>(
     ctx: &Ctx,
@@ -29,7 +28,7 @@ pub fn use_code_id<'a, Ctx: Context<'a, WasmConfig>>(
             .store_code()
             .with_context(|| format!( "Proposal store code not found for contract `{contract_name}` on network `{network}`"))?;
 
-        match get_code_id(network_info.rpc_endpoint(), &proposal_id).await {
+        match get_code_id(network_info, &proposal_id).await {
             Ok(code_id_from_proposal) => {
                 let code_id_from_proposal = code_id_from_proposal.parse().with_context(|| {
                     format!(
@@ -63,11 +62,7 @@ pub fn use_code_id<'a, Ctx: Context<'a, WasmConfig>>(
 
                     println!();
 
-                    if yes
-                        || Confirm::new()
-                            .with_prompt("> Do you want to update `code_id`?")
-                            .interact()?
-                    {
+                    if yes || prompt::confirm("> Do you want to update `code_id`?")? {
                         State::update_state_file(
                             network_info.network_variant(),
                             ctx.root()?,
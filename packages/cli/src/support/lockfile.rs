@@ -0,0 +1,264 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use config::Map;
+use serde::{Deserialize, Serialize};
+
+use super::wasm::{checksum, wasm_dir};
+use crate::framework::config::Network;
+
+pub const LOCK_FILE: &str = "Beaker.lock";
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct LockedNetwork {
+    pub chain_id: String,
+    pub rpc_endpoint: String,
+    pub grpc_endpoint: String,
+}
+
+impl From<&Network> for LockedNetwork {
+    fn from(network: &Network) -> Self {
+        LockedNetwork {
+            chain_id: network.chain_id().clone(),
+            rpc_endpoint: network.rpc_endpoint().clone(),
+            grpc_endpoint: network.grpc_endpoint().clone(),
+        }
+    }
+}
+
+/// Everything that determines what gets deployed, recorded in `Beaker.lock` for reproducing a deployment
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default)]
+pub struct Lock {
+    pub template_repo: String,
+    pub rustc_version: Option<String>,
+    /// Optimizer image, not set if wasm is not optimized
+    pub optimizer_image: Option<String>,
+    pub optimizer_digest: Option<String>,
+    /// sha256 of artifacts by file name
+    pub artifacts: Map<String, String>,
+    pub networks: Map<String, LockedNetwork>,
+}
+
+/// Trimmed stdout of successful command, `None` if it fails or prints nothing
+pub fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|o| !o.is_empty())
+}
+
+/// Repo digest of locally pulled docker `image`
+pub fn image_digest(image: &str) -> Option<String> {
+    command_output(
+        "docker",
+        &[
+            "image",
+            "inspect",
+            "--format",
+            "{{index .RepoDigests 0}}",
+            image,
+        ],
+    )
+}
+
+impl Lock {
+    /// Lock reflecting current toolchain, artifact of `contract_name` and `network` config
+    pub fn current(
+        root: &Path,
+        template_repo: &str,
+        optimizer_image: Option<String>,
+        no_wasm_opt: &bool,
+        contract_name: &str,
+        network: (&str, &Network),
+    ) -> Result<Self> {
+        let name = format!("{}.wasm", contract_name.replace('-', "_"));
+        let path = wasm_dir(root, no_wasm_opt).join(&name);
+        let wasm = fs::read(&path)
+            .with_context(|| format!("Unable to read artifact `{}`", path.to_string_lossy()))?;
+        let artifacts = Map::from([(name, checksum(&wasm))]);
+
+        let optimizer_digest = optimizer_image.as_deref().and_then(image_digest);
+
+        let (network_name, network_info) = network;
+        Ok(Lock {
+            template_repo: template_repo.to_string(),
+            rustc_version: command_output("rustc", &["--version"]),
+            optimizer_image,
+            optimizer_digest,
+            artifacts,
+            networks: Map::from([(network_name.to_string(), network_info.into())]),
+        })
+    }
+
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        let path = root.join(LOCK_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("Unable to read `{LOCK_FILE}`"))?;
+        toml::from_str(&content)
+            .map(Some)
+            .with_context(|| format!("Unable to parse `{LOCK_FILE}`"))
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self)
+            .with_context(|| format!("Unable to serialize `{LOCK_FILE}`"))?;
+        fs::write(
+            root.join(LOCK_FILE),
+            format!("# Generated by beaker, used by `--locked` deployments\n{content}"),
+        )
+        .with_context(|| format!("Unable to write `{LOCK_FILE}`"))
+    }
+
+    /// Keep artifacts and networks of the existing lock that are not part of this lock
+    pub fn merge(self, existing: Option<Lock>) -> Self {
+        let (mut artifacts, mut networks) = existing
+            .map(|l| (l.artifacts, l.networks))
+            .unwrap_or_default();
+        artifacts.extend(self.artifacts);
+        networks.extend(self.networks);
+        Lock {
+            artifacts,
+            networks,
+            ..self
+        }
+    }
+
+    /// Human readable differences between this lock and `current`,
+    /// only artifacts and networks in `current` are compared
+    pub fn differences(&self, current: &Lock) -> Vec<String> {
+        let mut diffs = vec![];
+        let mut compare =
+            |what: &str, locked: &dyn std::fmt::Debug, current: &dyn std::fmt::Debug| {
+                let (locked, current) = (format!("{locked:?}"), format!("{current:?}"));
+                if locked != current {
+                    diffs.push(format!("{what}: locked {locked}, found {current}"));
+                }
+            };
+
+        compare("template_repo", &self.template_repo, &current.template_repo);
+        compare("rustc_version", &self.rustc_version, &current.rustc_version);
+        compare(
+            "optimizer_image",
+            &self.optimizer_image,
+            &current.optimizer_image,
+        );
+        compare(
+            "optimizer_digest",
+            &self.optimizer_digest,
+            &current.optimizer_digest,
+        );
+
+        for (name, checksum) in &current.artifacts {
+            compare(
+                &format!("artifact `{name}`"),
+                &self.artifacts.get(name),
+                &Some(checksum),
+            );
+        }
+
+        for (name, network) in &current.networks {
+            compare(
+                &format!("network `{name}`"),
+                &self.networks.get(name),
+                &Some(network),
+            );
+        }
+        diffs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock() -> Lock {
+        Lock {
+            template_repo: "https://github.com/osmosis-labs/cw-minimal-template".to_string(),
+            rustc_version: Some("rustc 1.63.0".to_string()),
+            optimizer_image: Some("cosmwasm/workspace-optimizer:0.12.8".to_string()),
+            optimizer_digest: Some("cosmwasm/workspace-optimizer@sha256:abcd".to_string()),
+            artifacts: Map::from([("counter.wasm".to_string(), "1234".to_string())]),
+            networks: Map::from([(
+                "testnet".to_string(),
+                LockedNetwork {
+                    chain_id: "osmo-test-4".to_string(),
+                    rpc_endpoint: "https://rpc.testnet.osmosis.zone:443".to_string(),
+                    grpc_endpoint: "https://grpc.testnet.osmosis.zone:443".to_string(),
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn same_lock_has_no_differences() {
+        assert!(lock().differences(&lock()).is_empty());
+    }
+
+    #[test]
+    fn ignore_artifacts_not_deployed() {
+        let mut locked = lock();
+        locked
+            .artifacts
+            .insert("other.wasm".to_string(), "abcd".to_string());
+        assert!(locked.differences(&lock()).is_empty());
+
+        let mut current = lock();
+        current.artifacts = Map::from([("other.wasm".to_string(), "ef01".to_string())]);
+        let diffs = locked.differences(&current);
+        assert_eq!(diffs.len(), 1);
+        assert!(diffs[0].starts_with("artifact `other.wasm`"));
+    }
+
+    #[test]
+    fn report_differences() {
+        let mut current = lock();
+        current.rustc_version = Some("rustc 1.64.0".to_string());
+        current
+            .artifacts
+            .insert("counter.wasm".to_string(), "5678".to_string());
+        current.networks = Map::from([(
+            "mainnet".to_string(),
+            LockedNetwork {
+                chain_id: "osmosis-1".to_string(),
+                rpc_endpoint: "https://rpc.osmosis.zone:443".to_string(),
+                grpc_endpoint: "https://grpc.osmosis.zone:443".to_string(),
+            },
+        )]);
+
+        let diffs = lock().differences(&current);
+        assert_eq!(diffs.len(), 3);
+        assert!(diffs[0].starts_with("rustc_version"));
+        assert!(diffs[1].starts_with("artifact `counter.wasm`"));
+        assert!(diffs[2].starts_with("network `mainnet`"));
+    }
+
+    #[test]
+    fn save_and_load() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let lock = lock().merge(Some(Lock {
+            artifacts: Map::from([("other.wasm".to_string(), "abcd".to_string())]),
+            networks: Map::from([(
+                "local".to_string(),
+                LockedNetwork {
+                    chain_id: "localosmosis".to_string(),
+                    rpc_endpoint: "http://localhost:26657".to_string(),
+                    grpc_endpoint: "http://localhost:9090".to_string(),
+                },
+            )]),
+            ..Default::default()
+        }));
+        assert_eq!(lock.artifacts.len(), 2);
+        assert_eq!(lock.networks.len(), 2);
+
+        lock.save(temp.path()).unwrap();
+        assert_eq!(Lock::load(temp.path()).unwrap(), Some(lock));
+    }
+}
@@ -0,0 +1,283 @@
+//! Grpc queries of the mock chain, unsupported methods respond with `Unimplemented`
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::TcpListener;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use anyhow::Result;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::{
+    BaseAccount, QueryAccountRequest, QueryAccountResponse,
+};
+use cosmos_sdk_proto::cosmos::bank::v1beta1::{QueryAllBalancesRequest, QueryAllBalancesResponse};
+use cosmos_sdk_proto::cosmos::base::abci::v1beta1::{GasInfo, Result as AbciResult};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{SimulateRequest, SimulateResponse};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::*;
+use futures::future;
+use prost::Message;
+use sha2::{Digest, Sha256};
+use tonic::body::BoxBody;
+use tonic::codec::ProstCodec;
+use tonic::codegen::{empty_body, http};
+use tonic::server::{Grpc, UnaryService};
+use tonic::transport::{Body, NamedService, Server};
+use tonic::Status;
+
+use super::{ChainState, MockCode, Shared};
+
+type ResponseFuture =
+    Pin<Box<dyn Future<Output = Result<http::Response<BoxBody>, Infallible>> + Send>>;
+
+type Handler<Req, Res> = fn(&mut ChainState, Req) -> Result<Res, Status>;
+
+struct Unary<Req, Res> {
+    state: Shared,
+    handle: Handler<Req, Res>,
+}
+
+impl<Req, Res> UnaryService<Req> for Unary<Req, Res> {
+    type Response = Res;
+    type Future = future::Ready<Result<tonic::Response<Res>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<Req>) -> Self::Future {
+        let mut state = self.state.lock().expect("mock chain state is not poisoned");
+        future::ready((self.handle)(&mut state, request.into_inner()).map(tonic::Response::new))
+    }
+}
+
+fn unary<Req, Res>(
+    state: Shared,
+    req: http::Request<Body>,
+    handle: Handler<Req, Res>,
+) -> ResponseFuture
+where
+    Req: Message + Default + Send + 'static,
+    Res: Message + Send + 'static,
+{
+    Box::pin(async move {
+        let mut grpc = Grpc::new(ProstCodec::<Res, Req>::default());
+        Ok(grpc.unary(Unary { state, handle }, req).await)
+    })
+}
+
+fn route(state: Shared, req: http::Request<Body>) -> ResponseFuture {
+    match req.uri().path() {
+        "/cosmos.auth.v1beta1.Query/Account" => unary(state, req, account),
+        "/cosmos.bank.v1beta1.Query/AllBalances" => unary(state, req, all_balances),
+        "/cosmos.tx.v1beta1.Service/Simulate" => unary(state, req, simulate),
+        "/cosmwasm.wasm.v1.Query/SmartContractState" => unary(state, req, smart_contract_state),
+        "/cosmwasm.wasm.v1.Query/RawContractState" => unary(state, req, raw_contract_state),
+        "/cosmwasm.wasm.v1.Query/ContractInfo" => unary(state, req, contract_info),
+        "/cosmwasm.wasm.v1.Query/ContractsByCode" => unary(state, req, contracts_by_code),
+        "/cosmwasm.wasm.v1.Query/Code" => unary(state, req, code),
+        "/cosmwasm.wasm.v1.Query/Codes" => unary(state, req, codes),
+        _ => Box::pin(async {
+            Ok(http::Response::builder()
+                .status(200)
+                .header("grpc-status", "12")
+                .header("content-type", "application/grpc")
+                .body(empty_body())
+                .expect("unimplemented response is valid"))
+        }),
+    }
+}
+
+macro_rules! mock_service {
+    ($service:ident, $name:literal) => {
+        #[derive(Clone)]
+        struct $service(Shared);
+
+        impl NamedService for $service {
+            const NAME: &'static str = $name;
+        }
+
+        impl tower::Service<http::Request<Body>> for $service {
+            type Response = http::Response<BoxBody>;
+            type Error = Infallible;
+            type Future = ResponseFuture;
+
+            fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn call(&mut self, req: http::Request<Body>) -> Self::Future {
+                route(self.0.clone(), req)
+            }
+        }
+    };
+}
+
+mock_service!(AuthQuery, "cosmos.auth.v1beta1.Query");
+mock_service!(BankQuery, "cosmos.bank.v1beta1.Query");
+mock_service!(TxService, "cosmos.tx.v1beta1.Service");
+mock_service!(WasmQuery, "cosmwasm.wasm.v1.Query");
+
+/// Serve grpc on `listener` from a dedicated thread, so that it is independent of the caller's runtime
+pub fn serve(state: Shared, listener: TcpListener) -> Result<()> {
+    listener.set_nonblocking(true)?;
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("mock chain runtime can be built");
+        runtime.block_on(async move {
+            let listener = tokio::net::TcpListener::from_std(listener)
+                .expect("mock chain grpc listener is non-blocking");
+            let incoming = Box::pin(futures::stream::unfold(listener, |listener| async {
+                let conn = listener.accept().await.map(|(stream, _)| stream);
+                Some((conn, listener))
+            }));
+            Server::builder()
+                .add_service(AuthQuery(state.clone()))
+                .add_service(BankQuery(state.clone()))
+                .add_service(TxService(state.clone()))
+                .add_service(WasmQuery(state))
+                .serve_with_incoming(incoming)
+                .await
+        })
+    });
+    Ok(())
+}
+
+fn account(
+    state: &mut ChainState,
+    req: QueryAccountRequest,
+) -> Result<QueryAccountResponse, Status> {
+    let account = state.account(&req.address);
+    let base_account = BaseAccount {
+        address: req.address,
+        pub_key: None,
+        account_number: account.account_number,
+        sequence: account.sequence,
+    };
+    Ok(QueryAccountResponse {
+        account: Some(prost_types::Any {
+            type_url: "/cosmos.auth.v1beta1.BaseAccount".to_string(),
+            value: base_account.encode_to_vec(),
+        }),
+    })
+}
+
+fn all_balances(
+    state: &mut ChainState,
+    req: QueryAllBalancesRequest,
+) -> Result<QueryAllBalancesResponse, Status> {
+    Ok(QueryAllBalancesResponse {
+        balances: state
+            .balances
+            .get(&req.address)
+            .into_iter()
+            .flatten()
+            .filter(|(_, amount)| *amount > 0)
+            .map(|(denom, amount)| Coin {
+                denom: denom.clone(),
+                amount: amount.to_string(),
+            })
+            .collect(),
+        pagination: None,
+    })
+}
+
+#[allow(deprecated)]
+fn simulate(state: &mut ChainState, req: SimulateRequest) -> Result<SimulateResponse, Status> {
+    let gas_used = state.simulate(&req.tx_bytes).map_err(Status::unknown)?;
+    Ok(SimulateResponse {
+        gas_info: Some(GasInfo {
+            gas_wanted: 0,
+            gas_used,
+        }),
+        result: Some(AbciResult::default()),
+    })
+}
+
+fn smart_contract_state(
+    state: &mut ChainState,
+    req: QuerySmartContractStateRequest,
+) -> Result<QuerySmartContractStateResponse, Status> {
+    let msg: serde_json::Value = serde_json::from_slice(&req.query_data)
+        .map_err(|e| Status::invalid_argument(format!("invalid query: {e}")))?;
+    let data = state
+        .smart_query(&req.address, &msg)
+        .map_err(|e| Status::unknown(format!("{e:#}")))?;
+    Ok(QuerySmartContractStateResponse { data })
+}
+
+fn raw_contract_state(
+    state: &mut ChainState,
+    req: QueryRawContractStateRequest,
+) -> Result<QueryRawContractStateResponse, Status> {
+    state
+        .contract(&req.address)
+        .map_err(|e| Status::not_found(e.to_string()))?;
+    let data = state
+        .storages
+        .get(&req.address)
+        .and_then(|storage| storage.get(&req.query_data))
+        .cloned()
+        .unwrap_or_default();
+    Ok(QueryRawContractStateResponse { data })
+}
+
+fn contract_info(
+    state: &mut ChainState,
+    req: QueryContractInfoRequest,
+) -> Result<QueryContractInfoResponse, Status> {
+    let contract = state
+        .contract(&req.address)
+        .map_err(|e| Status::not_found(e.to_string()))?;
+    Ok(QueryContractInfoResponse {
+        address: contract.address.clone(),
+        contract_info: Some(ContractInfo {
+            code_id: contract.code_id,
+            creator: contract.creator.clone(),
+            admin: contract.admin.clone(),
+            label: contract.label.clone(),
+            ..Default::default()
+        }),
+    })
+}
+
+fn contracts_by_code(
+    state: &mut ChainState,
+    req: QueryContractsByCodeRequest,
+) -> Result<QueryContractsByCodeResponse, Status> {
+    Ok(QueryContractsByCodeResponse {
+        contracts: state
+            .contracts
+            .iter()
+            .filter(|c| c.code_id == req.code_id)
+            .map(|c| c.address.clone())
+            .collect(),
+        pagination: None,
+    })
+}
+
+fn code_info(code: &MockCode) -> CodeInfoResponse {
+    CodeInfoResponse {
+        code_id: code.code_id,
+        creator: code.creator.clone(),
+        data_hash: Sha256::digest(&code.wasm).to_vec(),
+        ..Default::default()
+    }
+}
+
+fn code(state: &mut ChainState, req: QueryCodeRequest) -> Result<QueryCodeResponse, Status> {
+    let code = state
+        .codes
+        .iter()
+        .find(|c| c.code_id == req.code_id)
+        .ok_or_else(|| Status::not_found(format!("code id {}: no such code", req.code_id)))?;
+    Ok(QueryCodeResponse {
+        code_info: Some(code_info(code)),
+        data: code.wasm.clone(),
+    })
+}
+
+fn codes(state: &mut ChainState, _req: QueryCodesRequest) -> Result<QueryCodesResponse, Status> {
+    Ok(QueryCodesResponse {
+        code_infos: state.codes.iter().map(code_info).collect(),
+        pagination: None,
+    })
+}
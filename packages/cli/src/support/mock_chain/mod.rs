@@ -0,0 +1,757 @@
+//! In-memory chain serving the grpc and tendermint rpc endpoints beaker talks to, so that ops and
+//! deploy scripts can be tested without docker or a live chain.
+//!
+//! Accounts are created on first query so any signing key can be used, codes and contracts are
+//! recorded from broadcasted wasm msgs, while smart query responses are stubbed with [`MockChain::on_smart_query`].
+//!
+//! With the `sim` feature, [`MockChain::load`] starts a chain that runs contracts with cosmwasm-vm
+//! and is persisted between invocations, backing `--backend sim`.
+
+mod grpc;
+mod rpc;
+#[cfg(feature = "sim")]
+mod vm;
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use anyhow::{anyhow, bail, Context, Result};
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{AuthInfo, TxBody, TxRaw};
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    MsgClearAdmin, MsgExecuteContract, MsgExecuteContractResponse, MsgInstantiateContract,
+    MsgInstantiateContractResponse, MsgMigrateContract, MsgMigrateContractResponse, MsgStoreCode,
+    MsgStoreCodeResponse, MsgUpdateAdmin,
+};
+use cosmrs::crypto::PublicKey;
+use cosmrs::AccountId;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::framework::config::Network;
+
+/// Gas reported by simulation before the per byte cost of the tx
+const SIMULATED_GAS_BASE: u64 = 100_000;
+
+/// Code of cosmos-sdk's `ErrWrongSequence`, as returned by a live chain
+const SEQUENCE_MISMATCH_CODE: u32 = 32;
+
+/// Code of cosmos-sdk's `ErrTxDecode`
+const TX_DECODE_CODE: u32 = 2;
+
+/// Code of failed msg execution
+const MSG_FAILED_CODE: u32 = 5;
+
+type Shared = Arc<Mutex<ChainState>>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MockAccount {
+    pub account_number: u64,
+    pub sequence: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MockCode {
+    pub code_id: u64,
+    pub creator: String,
+    #[serde(with = "base64_bytes")]
+    pub wasm: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MockContract {
+    pub address: String,
+    pub code_id: u64,
+    pub creator: String,
+    pub admin: String,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MockEvent {
+    pub r#type: String,
+    pub attributes: Vec<(String, String)>,
+}
+
+impl MockEvent {
+    fn new(r#type: &str, attributes: &[(&str, &str)]) -> Self {
+        MockEvent {
+            r#type: r#type.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+}
+
+/// Broadcasted tx with the result of delivering it, failed txs are recorded as well
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MockTx {
+    /// Upper case hex sha256 of the tx bytes
+    pub hash: String,
+    pub height: u64,
+    #[serde(with = "base64_bytes")]
+    pub tx: Vec<u8>,
+    pub code: u32,
+    pub log: String,
+    pub gas_used: u64,
+    pub events: Vec<MockEvent>,
+}
+
+/// Binary fields persisted as base64 strings
+mod base64_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        base64::decode(String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Contract storages persisted with hex keys and base64 values
+mod storages {
+    use std::collections::{BTreeMap, HashMap};
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::ContractStorage;
+
+    pub fn serialize<S: Serializer>(
+        storages: &HashMap<String, ContractStorage>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        storages
+            .iter()
+            .map(|(address, storage)| {
+                let entries = storage
+                    .iter()
+                    .map(|(k, v)| (hex::encode(k), base64::encode(v)))
+                    .collect::<BTreeMap<_, _>>();
+                (address, entries)
+            })
+            .collect::<BTreeMap<_, _>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<String, ContractStorage>, D::Error> {
+        use serde::de::Error;
+        BTreeMap::<String, BTreeMap<String, String>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(address, entries)| {
+                let storage = entries
+                    .into_iter()
+                    .map(|(k, v)| {
+                        Ok((
+                            hex::decode(k).map_err(D::Error::custom)?,
+                            base64::decode(v).map_err(D::Error::custom)?,
+                        ))
+                    })
+                    .collect::<Result<ContractStorage, D::Error>>()?;
+                Ok((address, storage))
+            })
+            .collect()
+    }
+}
+
+/// Raw storage of a contract
+type ContractStorage = BTreeMap<Vec<u8>, Vec<u8>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ChainState {
+    chain_id: String,
+    account_prefix: String,
+    height: u64,
+    accounts: HashMap<String, MockAccount>,
+    /// Balances of addresses in `(denom, amount)`, sorted by denom
+    balances: HashMap<String, Vec<(String, u128)>>,
+    codes: Vec<MockCode>,
+    /// Contracts in order of instantiation
+    contracts: Vec<MockContract>,
+    /// Stubbed responses keyed by contract address and query msg serialized by `serde_json`
+    #[serde(skip)]
+    smart_queries: HashMap<(String, String), Value>,
+    /// Storage of contracts by address, only written when contracts are executed
+    #[serde(default, with = "storages")]
+    storages: HashMap<String, ContractStorage>,
+    txs: Vec<MockTx>,
+    /// Whether wasm msgs run the contracts instead of being only recorded
+    #[serde(skip)]
+    execute_contracts: bool,
+    /// Gas used by contract executions of the tx being run
+    #[serde(skip)]
+    contract_gas: u64,
+}
+
+impl ChainState {
+    fn new(chain_id: &str, account_prefix: &str) -> Self {
+        ChainState {
+            chain_id: chain_id.to_string(),
+            account_prefix: account_prefix.to_string(),
+            height: 1,
+            accounts: HashMap::new(),
+            balances: HashMap::new(),
+            codes: vec![],
+            contracts: vec![],
+            smart_queries: HashMap::new(),
+            storages: HashMap::new(),
+            txs: vec![],
+            execute_contracts: false,
+            contract_gas: 0,
+        }
+    }
+
+    fn account(&mut self, address: &str) -> &mut MockAccount {
+        let account_number = self.accounts.len() as u64;
+        self.accounts
+            .entry(address.to_string())
+            .or_insert(MockAccount {
+                account_number,
+                sequence: 0,
+            })
+    }
+
+    fn contract(&mut self, address: &str) -> Result<&mut MockContract> {
+        self.contracts
+            .iter_mut()
+            .find(|c| c.address == address)
+            .with_context(|| format!("contract {address}: not found"))
+    }
+
+    fn ensure_code(&self, code_id: u64) -> Result<()> {
+        if code_id == 0 || code_id > self.codes.len() as u64 {
+            bail!("code id {code_id}: no such code");
+        }
+        Ok(())
+    }
+
+    fn transfer(&mut self, from: &str, to: &str, coins: &[(String, u128)]) -> Result<()> {
+        for (denom, amount) in coins {
+            let balance = self
+                .balances
+                .entry(from.to_string())
+                .or_default()
+                .iter_mut()
+                .find(|(d, _)| d == denom)
+                .filter(|(_, a)| a >= amount)
+                .with_context(|| format!("{amount}{denom}: insufficient funds of {from}"))?;
+            balance.1 -= amount;
+            add_balance(
+                self.balances.entry(to.to_string()).or_default(),
+                denom,
+                *amount,
+            );
+        }
+        Ok(())
+    }
+
+    /// Address of the next contract instantiated from `code_id`, deterministic for the order of instantiation
+    fn contract_address(&self, code_id: u64) -> Result<String> {
+        let hash = Sha256::new()
+            .chain_update(b"mock_contract")
+            .chain_update(code_id.to_be_bytes())
+            .chain_update((self.contracts.len() as u64).to_be_bytes())
+            .finalize();
+        AccountId::new(&self.account_prefix, &hash)
+            .map(|a| a.to_string())
+            .map_err(|e| anyhow!(e))
+    }
+
+    /// Events of the msg along with its encoded msg response
+    fn execute_msg(
+        &mut self,
+        signer: &str,
+        msg: &prost_types::Any,
+    ) -> Result<(Vec<MockEvent>, Vec<u8>)> {
+        let mut events = vec![MockEvent::new(
+            "message",
+            &[("action", &msg.type_url), ("sender", signer)],
+        )];
+        let mut data = vec![];
+        match msg.type_url.as_str() {
+            "/cosmwasm.wasm.v1.MsgStoreCode" => {
+                let msg = MsgStoreCode::decode(msg.value.as_slice())?;
+                let code_id = self.codes.len() as u64 + 1;
+                self.codes.push(MockCode {
+                    code_id,
+                    creator: msg.sender,
+                    wasm: msg.wasm_byte_code,
+                });
+                events.push(MockEvent::new(
+                    "store_code",
+                    &[("code_id", &code_id.to_string())],
+                ));
+                data = MsgStoreCodeResponse { code_id }.encode_to_vec();
+            }
+            "/cosmwasm.wasm.v1.MsgInstantiateContract" => {
+                let msg = MsgInstantiateContract::decode(msg.value.as_slice())?;
+                self.ensure_code(msg.code_id)?;
+                let address = self.contract_address(msg.code_id)?;
+                let funds = coins(&msg.funds)?;
+                self.transfer(&msg.sender, &address, &funds)?;
+                self.contracts.push(MockContract {
+                    address: address.clone(),
+                    code_id: msg.code_id,
+                    creator: msg.sender.clone(),
+                    admin: msg.admin,
+                    label: msg.label,
+                });
+                events.push(MockEvent::new(
+                    "instantiate",
+                    &[
+                        ("_contract_address", &address),
+                        ("code_id", &msg.code_id.to_string()),
+                    ],
+                ));
+                let mut response = MsgInstantiateContractResponse {
+                    address: address.clone(),
+                    data: vec![],
+                };
+                #[cfg(feature = "sim")]
+                if self.execute_contracts {
+                    let (contract_events, contract_data) =
+                        self.instantiate_contract(&address, &msg.sender, &funds, &msg.msg)?;
+                    events.extend(contract_events);
+                    response.data = contract_data;
+                }
+                data = response.encode_to_vec();
+            }
+            "/cosmwasm.wasm.v1.MsgMigrateContract" => {
+                let msg = MsgMigrateContract::decode(msg.value.as_slice())?;
+                self.ensure_code(msg.code_id)?;
+                let contract = self.contract(&msg.contract)?;
+                if contract.admin != msg.sender {
+                    bail!("{}: unauthorized, sender is not the admin", msg.contract);
+                }
+                contract.code_id = msg.code_id;
+                events.push(MockEvent::new(
+                    "migrate",
+                    &[
+                        ("_contract_address", &msg.contract),
+                        ("code_id", &msg.code_id.to_string()),
+                    ],
+                ));
+                let mut response = MsgMigrateContractResponse { data: vec![] };
+                #[cfg(feature = "sim")]
+                if self.execute_contracts {
+                    let (contract_events, contract_data) =
+                        self.migrate_contract(&msg.contract, &msg.msg)?;
+                    events.extend(contract_events);
+                    response.data = contract_data;
+                }
+                data = response.encode_to_vec();
+            }
+            "/cosmwasm.wasm.v1.MsgExecuteContract" => {
+                let msg = MsgExecuteContract::decode(msg.value.as_slice())?;
+                self.contract(&msg.contract)?;
+                let funds = coins(&msg.funds)?;
+                self.transfer(&msg.sender, &msg.contract, &funds)?;
+                events.push(MockEvent::new(
+                    "execute",
+                    &[("_contract_address", &msg.contract)],
+                ));
+                let mut response = MsgExecuteContractResponse { data: vec![] };
+                #[cfg(feature = "sim")]
+                if self.execute_contracts {
+                    let (contract_events, contract_data) =
+                        self.execute_contract(&msg.contract, &msg.sender, &funds, &msg.msg)?;
+                    events.extend(contract_events);
+                    response.data = contract_data;
+                }
+                data = response.encode_to_vec();
+            }
+            "/cosmwasm.wasm.v1.MsgUpdateAdmin" => {
+                let msg = MsgUpdateAdmin::decode(msg.value.as_slice())?;
+                self.set_admin(&msg.sender, &msg.contract, &msg.new_admin)?;
+                events.push(MockEvent::new(
+                    "update_contract_admin",
+                    &[
+                        ("_contract_address", &msg.contract),
+                        ("new_admin_address", &msg.new_admin),
+                    ],
+                ));
+            }
+            "/cosmwasm.wasm.v1.MsgClearAdmin" => {
+                let msg = MsgClearAdmin::decode(msg.value.as_slice())?;
+                self.set_admin(&msg.sender, &msg.contract, "")?;
+                events.push(MockEvent::new(
+                    "update_contract_admin",
+                    &[
+                        ("_contract_address", &msg.contract),
+                        ("new_admin_address", ""),
+                    ],
+                ));
+            }
+            "/cosmos.bank.v1beta1.MsgSend" => {
+                let msg = MsgSend::decode(msg.value.as_slice())?;
+                let amount = coins(&msg.amount)?;
+                self.transfer(&msg.from_address, &msg.to_address, &amount)?;
+                events.push(MockEvent::new(
+                    "transfer",
+                    &[
+                        ("recipient", &msg.to_address),
+                        ("sender", &msg.from_address),
+                        ("amount", &format_coins(&amount)),
+                    ],
+                ));
+            }
+            // other msgs are accepted without effect
+            _ => {}
+        }
+        Ok((events, data))
+    }
+
+    /// Stubbed response of the smart query, otherwise the contract's response if contracts are executed
+    fn smart_query(&self, address: &str, msg: &Value) -> Result<Vec<u8>> {
+        if let Some(response) = self
+            .smart_queries
+            .get(&(address.to_string(), msg.to_string()))
+        {
+            return Ok(response.to_string().into_bytes());
+        }
+        #[cfg(feature = "sim")]
+        if self.execute_contracts {
+            return self.query_contract(address, msg.to_string().as_bytes());
+        }
+        bail!("no mock response for query `{msg}` of {address}: query wasm contract failed")
+    }
+
+    fn set_admin(&mut self, sender: &str, contract: &str, admin: &str) -> Result<()> {
+        let contract = self.contract(contract)?;
+        if contract.admin != sender {
+            bail!(
+                "{}: unauthorized, sender is not the admin",
+                contract.address
+            );
+        }
+        contract.admin = admin.to_string();
+        Ok(())
+    }
+
+    /// Run the tx against a copy of the state, returning the state after it along with its events
+    fn run(
+        &self,
+        tx_bytes: &[u8],
+        check_sequence: bool,
+    ) -> Result<(Self, Vec<MockEvent>), (u32, String)> {
+        let decode_err =
+            |e: &dyn std::fmt::Display| (TX_DECODE_CODE, format!("{e}: tx parse error"));
+        let raw = TxRaw::decode(tx_bytes).map_err(|e| decode_err(&e))?;
+        let body = TxBody::decode(raw.body_bytes.as_slice()).map_err(|e| decode_err(&e))?;
+        let auth_info =
+            AuthInfo::decode(raw.auth_info_bytes.as_slice()).map_err(|e| decode_err(&e))?;
+
+        let mut next = self.clone();
+        next.contract_gas = 0;
+        let signer_info = auth_info.signer_infos.first();
+        let signer = signer_info
+            .and_then(|s| s.public_key.as_ref())
+            .and_then(|k| PublicKey::try_from(k).ok())
+            .and_then(|k| k.account_id(&self.account_prefix).ok())
+            .map(|a| a.to_string())
+            .unwrap_or_default();
+
+        if !signer.is_empty() {
+            let sequence = signer_info.map(|s| s.sequence).unwrap_or_default();
+            let account = next.account(&signer);
+            if check_sequence && account.sequence != sequence {
+                return Err((
+                    SEQUENCE_MISMATCH_CODE,
+                    format!(
+                        "account sequence mismatch, expected {}, got {sequence}: incorrect account sequence",
+                        account.sequence
+                    ),
+                ));
+            }
+            account.sequence += 1;
+        }
+
+        let mut events = vec![];
+        for (i, msg) in body.messages.iter().enumerate() {
+            let (msg_events, _) = next.execute_msg(&signer, msg).map_err(|e| {
+                (
+                    MSG_FAILED_CODE,
+                    format!("failed to execute message; message index: {i}: {e:#}"),
+                )
+            })?;
+            events.extend(msg_events);
+        }
+        Ok((next, events))
+    }
+
+    /// Gas the tx is simulated to use
+    fn simulate(&self, tx_bytes: &[u8]) -> Result<u64, String> {
+        let (next, _) = self.run(tx_bytes, false).map_err(|(_, log)| log)?;
+        Ok(SIMULATED_GAS_BASE + tx_bytes.len() as u64 + next.contract_gas)
+    }
+
+    /// Commit the tx in a new block, failed txs are recorded without changing the state
+    fn deliver(&mut self, tx_bytes: &[u8]) -> MockTx {
+        let mut gas_used = SIMULATED_GAS_BASE + tx_bytes.len() as u64;
+        let (code, log, events) = match self.run(tx_bytes, true) {
+            Ok((next, events)) => {
+                gas_used += next.contract_gas;
+                *self = next;
+                (0, String::new(), events)
+            }
+            Err((code, log)) => (code, log, vec![]),
+        };
+        self.height += 1;
+        let tx = MockTx {
+            hash: hex::encode_upper(Sha256::digest(tx_bytes)),
+            height: self.height,
+            tx: tx_bytes.to_vec(),
+            code,
+            log,
+            gas_used,
+            events,
+        };
+        self.txs.push(tx.clone());
+        tx
+    }
+}
+
+fn add_balance(balances: &mut Vec<(String, u128)>, denom: &str, amount: u128) {
+    match balances.iter_mut().find(|(d, _)| d == denom) {
+        Some(balance) => balance.1 += amount,
+        None => {
+            balances.push((denom.to_string(), amount));
+            balances.sort();
+        }
+    }
+}
+
+fn coins(coins: &[cosmos_sdk_proto::cosmos::base::v1beta1::Coin]) -> Result<Vec<(String, u128)>> {
+    coins
+        .iter()
+        .map(|c| {
+            let amount = c
+                .amount
+                .parse()
+                .with_context(|| format!("Invalid amount `{}`", c.amount))?;
+            Ok((c.denom.clone(), amount))
+        })
+        .collect()
+}
+
+fn format_coins(coins: &[(String, u128)]) -> String {
+    coins
+        .iter()
+        .map(|(denom, amount)| format!("{amount}{denom}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Mock chain served on local ports until the process exits
+pub struct MockChain {
+    state: Shared,
+    grpc_endpoint: String,
+    rpc_endpoint: String,
+}
+
+impl MockChain {
+    /// Start serving a chain with `chain_id`, deriving addresses with `account_prefix`
+    pub fn start(chain_id: &str, account_prefix: &str) -> Result<Self> {
+        Self::serve(ChainState::new(chain_id, account_prefix))
+    }
+
+    /// Start serving a chain that executes contracts, from the state saved at `path` if it exists
+    #[cfg(feature = "sim")]
+    pub fn load(path: &std::path::Path, chain_id: &str, account_prefix: &str) -> Result<Self> {
+        let state = match std::fs::read(path) {
+            Ok(saved) => serde_json::from_slice(&saved).with_context(|| {
+                format!("Unable to load simulated chain from `{}`", path.display())
+            })?,
+            Err(_) => ChainState::new(chain_id, account_prefix),
+        };
+        Self::serve(ChainState {
+            execute_contracts: true,
+            ..state
+        })
+    }
+
+    /// Save the chain so that [`MockChain::load`] continues from it
+    #[cfg(feature = "sim")]
+    pub fn save(&self, path: &std::path::Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let state = serde_json::to_vec(&*self.state())?;
+        std::fs::write(path, state)
+            .with_context(|| format!("Unable to save simulated chain to `{}`", path.display()))
+    }
+
+    fn serve(state: ChainState) -> Result<Self> {
+        let state = Arc::new(Mutex::new(state));
+
+        let grpc_listener = TcpListener::bind("127.0.0.1:0")?;
+        let rpc_listener = TcpListener::bind("127.0.0.1:0")?;
+        let grpc_endpoint = format!("http://{}", grpc_listener.local_addr()?);
+        let rpc_endpoint = format!("http://{}", rpc_listener.local_addr()?);
+        grpc::serve(state.clone(), grpc_listener)?;
+        rpc::serve(state.clone(), rpc_listener);
+
+        Ok(MockChain {
+            state,
+            grpc_endpoint,
+            rpc_endpoint,
+        })
+    }
+
+    fn state(&self) -> MutexGuard<'_, ChainState> {
+        self.state.lock().expect("mock chain state is not poisoned")
+    }
+
+    pub fn grpc_endpoint(&self) -> &str {
+        &self.grpc_endpoint
+    }
+
+    pub fn rpc_endpoint(&self) -> &str {
+        &self.rpc_endpoint
+    }
+
+    /// Network config of the chain, to be used in place of a configured network
+    pub fn network(&self) -> Network {
+        let state = self.state();
+        serde_json::from_value(json!({
+            "chain_id": state.chain_id,
+            "network_variant": "Local",
+            "grpc_endpoint": self.grpc_endpoint,
+            "rpc_endpoint": self.rpc_endpoint,
+            "account_prefix": state.account_prefix,
+        }))
+        .expect("mock network config is valid")
+    }
+
+    /// Credit `amount` of `denom` to `address`
+    pub fn fund(&self, address: &str, denom: &str, amount: u128) {
+        let mut state = self.state();
+        state.account(address);
+        add_balance(
+            state.balances.entry(address.to_string()).or_default(),
+            denom,
+            amount,
+        );
+    }
+
+    /// Respond to smart query `msg` of contract at `address` with `response`
+    pub fn on_smart_query(&self, address: &str, msg: Value, response: Value) {
+        self.state()
+            .smart_queries
+            .insert((address.to_string(), msg.to_string()), response);
+    }
+
+    pub fn height(&self) -> u64 {
+        self.state().height
+    }
+
+    pub fn account(&self, address: &str) -> Option<MockAccount> {
+        self.state().accounts.get(address).cloned()
+    }
+
+    pub fn balance(&self, address: &str, denom: &str) -> u128 {
+        self.state()
+            .balances
+            .get(address)
+            .and_then(|b| b.iter().find(|(d, _)| d == denom))
+            .map(|(_, amount)| *amount)
+            .unwrap_or_default()
+    }
+
+    pub fn codes(&self) -> Vec<MockCode> {
+        self.state().codes.clone()
+    }
+
+    pub fn contracts(&self) -> Vec<MockContract> {
+        self.state().contracts.clone()
+    }
+
+    pub fn txs(&self) -> Vec<MockTx> {
+        self.state().txs.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::support::chain_client::ChainClient;
+    use crate::support::cosmos::{Client, ResponseValuePicker};
+    use crate::support::future::block;
+    use crate::support::gas::Gas;
+    use cosmrs::cosmwasm::MsgStoreCode;
+    use cosmrs::crypto::secp256k1::SigningKey;
+    use cosmrs::tx::Msg;
+
+    #[test]
+    fn store_code_and_query_contract() {
+        let chain = MockChain::start("mock-1", "osmo").unwrap();
+        let client = Client::new(chain.network(), Arc::new(ChainClient::default()));
+        let signing_key = SigningKey::from_bytes(&[1; 32]).unwrap();
+        let sender = signing_key.public_key().account_id("osmo").unwrap();
+        chain.fund(sender.as_ref(), "uosmo", 1_000_000);
+
+        let msg = MsgStoreCode {
+            sender: sender.clone(),
+            wasm_byte_code: b"\0asm".to_vec(),
+            instantiate_permission: None,
+        }
+        .to_any()
+        .unwrap();
+        let gas = Gas::Auto {
+            gas_price: "0.025uosmo".parse().unwrap(),
+            gas_adjustment: 1.3,
+            fee_denom: None,
+        };
+        let response = block(
+            client
+                .to_signing_client(signing_key, "osmo")
+                .sign_and_broadcast(vec![msg], &gas, "", &0),
+        )
+        .unwrap();
+
+        assert_eq!(response.pick("store_code", "code_id").to_string(), "1");
+        assert_eq!(chain.codes()[0].creator, sender.to_string());
+        assert_eq!(chain.account(sender.as_ref()).unwrap().sequence, 1);
+
+        chain.on_smart_query(
+            "osmo1contract",
+            json!({ "count": {} }),
+            json!({ "count": 1 }),
+        );
+        let res =
+            block(client.query_smart("osmo1contract".to_string(), br#"{"count":{}}"#.to_vec()))
+                .unwrap();
+        assert_eq!(res, br#"{"count":1}"#);
+    }
+
+    #[cfg(feature = "sim")]
+    #[test]
+    fn saved_chain_is_loaded() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("sim/chain.json");
+        let chain = MockChain::load(&path, "sim-1", "osmo").unwrap();
+        chain.fund("osmo1funded", "uosmo", 42);
+        chain.state().storages.insert(
+            "osmo1contract".to_string(),
+            BTreeMap::from([(b"config".to_vec(), b"{}".to_vec())]),
+        );
+        chain.save(&path).unwrap();
+
+        let loaded = MockChain::load(&path, "ignored-1", "ignored").unwrap();
+        assert_eq!(loaded.balance("osmo1funded", "uosmo"), 42);
+        assert_eq!(loaded.network().chain_id(), "sim-1");
+        assert_eq!(
+            loaded.state().storages["osmo1contract"][b"config".as_slice()],
+            b"{}".to_vec()
+        );
+        assert!(loaded.state().execute_contracts);
+    }
+}
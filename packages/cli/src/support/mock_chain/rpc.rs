@@ -0,0 +1,219 @@
+//! Tendermint json-rpc of the mock chain, serving the methods beaker's rpc client calls
+
+use std::net::{TcpListener, TcpStream};
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use super::{ChainState, MockEvent, MockTx, Shared};
+use crate::modules::serve::http;
+
+/// Fixed time of every block, the mock chain has no clock
+const BLOCK_TIME: &str = "2022-01-01T00:00:00Z";
+
+/// Compressed ed25519 base point, a valid public key for the mock validator
+const VALIDATOR_PUB_KEY: [u8; 32] = [
+    0x58, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+    0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+];
+
+/// Serve json-rpc on `listener` from a dedicated thread, one request per connection
+pub fn serve(state: Shared, listener: TcpListener) {
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            // a client hanging up is no reason to stop serving
+            let _ = handle_connection(&state, stream);
+        }
+    });
+}
+
+fn handle_connection(state: &Shared, mut stream: TcpStream) -> Result<()> {
+    let req = http::read_request(&stream)?;
+    let req: Value = serde_json::from_slice(&req.body)?;
+    let id = req.get("id").cloned().unwrap_or(Value::Null);
+    let method = req
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let params = req.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = {
+        let mut state = state.lock().expect("mock chain state is not poisoned");
+        handle(&mut state, method, &params)
+    };
+    let res = match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": -32603, "message": "Internal error", "data": format!("{e:#}") },
+        }),
+    };
+    http::write_response(&mut stream, "200 OK", &serde_json::to_vec(&res)?)
+}
+
+fn handle(state: &mut ChainState, method: &str, params: &Value) -> Result<Value> {
+    match method {
+        "status" => Ok(status(state)),
+        "broadcast_tx_commit" => {
+            let tx = state.deliver(&tx_param(params)?);
+            let check_tx = result_json(&MockTx {
+                events: vec![],
+                ..tx.clone()
+            });
+            Ok(json!({
+                "check_tx": check_tx,
+                "deliver_tx": result_json(&tx),
+                "hash": tx.hash,
+                "height": tx.height.to_string(),
+            }))
+        }
+        "broadcast_tx_sync" | "broadcast_tx_async" => {
+            let tx = state.deliver(&tx_param(params)?);
+            Ok(json!({
+                "code": tx.code,
+                "data": "",
+                "log": tx.log,
+                "codespace": "",
+                "hash": tx.hash,
+            }))
+        }
+        "tx" => {
+            let hash = hash_param(params)?;
+            state
+                .txs
+                .iter()
+                .find(|tx| tx.hash == hash)
+                .map(tx_json)
+                .with_context(|| format!("tx ({hash}) not found"))
+        }
+        "tx_search" => {
+            let query = params
+                .get("query")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let txs = state
+                .txs
+                .iter()
+                .filter(|tx| tx.code == 0 && matches_query(tx, query))
+                .collect::<Vec<_>>();
+            let txs = match params.get("order_by").and_then(Value::as_str) {
+                Some("desc") => txs.into_iter().rev().collect(),
+                _ => txs,
+            };
+            Ok(json!({
+                "txs": txs.iter().copied().map(tx_json).collect::<Vec<_>>(),
+                "total_count": txs.len().to_string(),
+            }))
+        }
+        _ => Err(anyhow!("method `{method}` is not supported by mock chain")),
+    }
+}
+
+fn tx_param(params: &Value) -> Result<Vec<u8>> {
+    let tx = params
+        .get("tx")
+        .and_then(Value::as_str)
+        .context("Missing `tx` param")?;
+    base64::decode(tx).context("Invalid `tx` param")
+}
+
+/// Hash of `tx` request as upper case hex, clients send it either hex or base64 encoded
+fn hash_param(params: &Value) -> Result<String> {
+    let hash = params
+        .get("hash")
+        .and_then(Value::as_str)
+        .context("Missing `hash` param")?;
+    match hex::decode(hash) {
+        Ok(bytes) if bytes.len() == 32 => Ok(hex::encode_upper(bytes)),
+        _ => Ok(hex::encode_upper(
+            base64::decode(hash).context("Invalid `hash` param")?,
+        )),
+    }
+}
+
+/// Whether every quoted value of the tx search `query` is an attribute value of the tx,
+/// eg. `tx.height>0 AND wasm._contract_address='osmo1...'` matches txs with events of the contract
+fn matches_query(tx: &MockTx, query: &str) -> bool {
+    query.split('\'').skip(1).step_by(2).all(|value| {
+        value == tx.hash
+            || tx
+                .events
+                .iter()
+                .flat_map(|e| e.attributes.iter())
+                .any(|(_, v)| v == value)
+    })
+}
+
+fn events_json(events: &[MockEvent]) -> Value {
+    events
+        .iter()
+        .map(|e| {
+            json!({
+                "type": e.r#type,
+                "attributes": e.attributes.iter().map(|(k, v)| json!({
+                    "key": base64::encode(k),
+                    "value": base64::encode(v),
+                    "index": true,
+                })).collect::<Vec<_>>(),
+            })
+        })
+        .collect()
+}
+
+fn result_json(tx: &MockTx) -> Value {
+    json!({
+        "code": tx.code,
+        "data": "",
+        "log": tx.log,
+        "info": "",
+        "gas_wanted": tx.gas_used.to_string(),
+        "gas_used": tx.gas_used.to_string(),
+        "events": events_json(&tx.events),
+        "codespace": if tx.code == 0 { "" } else { "sdk" },
+    })
+}
+
+fn tx_json(tx: &MockTx) -> Value {
+    json!({
+        "hash": tx.hash,
+        "height": tx.height.to_string(),
+        "index": 0,
+        "tx_result": result_json(tx),
+        "tx": base64::encode(&tx.tx),
+        "proof": null,
+    })
+}
+
+fn status(state: &ChainState) -> Value {
+    let block_hash = hex::encode_upper(Sha256::digest(state.height.to_be_bytes()));
+    json!({
+        "node_info": {
+            "protocol_version": { "p2p": "8", "block": "11", "app": "0" },
+            "id": "0000000000000000000000000000000000000000",
+            "listen_addr": "tcp://127.0.0.1:26656",
+            "network": state.chain_id,
+            "version": "0.34.21",
+            "channels": "40202122233038606100",
+            "moniker": "mock",
+            "other": { "tx_index": "on", "rpc_address": "tcp://127.0.0.1:26657" },
+        },
+        "sync_info": {
+            "latest_block_hash": block_hash,
+            "latest_app_hash": "00",
+            "latest_block_height": state.height.to_string(),
+            "latest_block_time": BLOCK_TIME,
+            "catching_up": false,
+        },
+        "validator_info": {
+            "address": "0000000000000000000000000000000000000000",
+            "pub_key": {
+                "type": "tendermint/PubKeyEd25519",
+                "value": base64::encode(VALIDATOR_PUB_KEY),
+            },
+            "voting_power": "10",
+            "proposer_priority": "0",
+        },
+    })
+}
@@ -0,0 +1,548 @@
+//! Contract execution of the simulated chain with cosmwasm-vm. Wasm msgs run the stored code against
+//! the contract's storage and dispatch the messages of its response, replying to sub-messages as wasmd does
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, bail, Context, Result};
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract, MsgUpdateAdmin,
+};
+use cosmrs::AccountId;
+use cosmwasm_std::{
+    Addr, BankMsg, BankQuery, Binary, BlockInfo, Coin, ContractInfo, ContractResult, CosmosMsg,
+    Empty, Env, Event, MessageInfo, Order, QueryRequest, Record, Reply, ReplyOn, Response,
+    SubMsgResponse, SubMsgResult, SystemError, SystemResult, Timestamp, TransactionInfo, Uint128,
+    WasmMsg, WasmQuery,
+};
+use cosmwasm_vm::{
+    call_execute, call_instantiate, call_migrate, call_query, call_reply, Backend, BackendApi,
+    BackendError, BackendResult, GasInfo, Instance, InstanceOptions, Querier, Size, Storage,
+    VmResult,
+};
+use once_cell::sync::Lazy;
+use prost::Message;
+use serde_json::json;
+
+use super::{ChainState, ContractStorage, MockEvent};
+
+/// Time of every block, the same as reported by the chain's rpc
+const BLOCK_TIME_SECONDS: u64 = 1_640_995_200;
+
+/// Vm gas per sdk gas, wasmd's default multiplier
+const GAS_MULTIPLIER: u64 = 140_000_000;
+
+/// Sdk gas limit of a single contract call
+const CALL_GAS_LIMIT: u64 = 100_000_000;
+
+const MEMORY_LIMIT_MIB: usize = 32;
+
+type SimInstance = Instance<SimApi, SimStorage, SimQuerier>;
+
+/// Account prefixes leaked once each, since the vm requires its api to be `Copy`
+static PREFIXES: Lazy<Mutex<HashSet<&'static str>>> = Lazy::new(Default::default);
+
+fn intern(prefix: &str) -> &'static str {
+    let mut prefixes = PREFIXES.lock().expect("prefixes are not poisoned");
+    match prefixes.get(prefix) {
+        Some(prefix) => prefix,
+        None => {
+            let prefix: &'static str = Box::leak(prefix.to_string().into_boxed_str());
+            prefixes.insert(prefix);
+            prefix
+        }
+    }
+}
+
+/// Bech32 addresses of the chain's account prefix
+#[derive(Clone, Copy)]
+struct SimApi {
+    prefix: &'static str,
+}
+
+impl BackendApi for SimApi {
+    fn canonical_address(&self, human: &str) -> BackendResult<Vec<u8>> {
+        let canonical = match human.parse::<AccountId>() {
+            Ok(address) if address.prefix() == self.prefix => Ok(address.to_bytes()),
+            _ => Err(BackendError::user_err(format!("Invalid address `{human}`"))),
+        };
+        (canonical, GasInfo::free())
+    }
+
+    fn human_address(&self, canonical: &[u8]) -> BackendResult<String> {
+        let human = AccountId::new(self.prefix, canonical)
+            .map(|address| address.to_string())
+            .map_err(|e| BackendError::user_err(e.to_string()));
+        (human, GasInfo::free())
+    }
+}
+
+struct SimStorage {
+    data: ContractStorage,
+    /// Records of open iterators, collected when the iterator is created
+    iterators: HashMap<u32, std::vec::IntoIter<Record>>,
+}
+
+impl Storage for SimStorage {
+    fn get(&self, key: &[u8]) -> BackendResult<Option<Vec<u8>>> {
+        (Ok(self.data.get(key).cloned()), GasInfo::free())
+    }
+
+    fn scan(
+        &mut self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> BackendResult<u32> {
+        let records = self
+            .data
+            .iter()
+            .filter(|(k, _)| start.map_or(true, |start| k.as_slice() >= start))
+            .filter(|(k, _)| end.map_or(true, |end| k.as_slice() < end))
+            .map(|(k, v)| (k.clone(), v.clone()));
+        let records: Vec<Record> = match order {
+            Order::Ascending => records.collect(),
+            Order::Descending => records.rev().collect(),
+        };
+        let id = self.iterators.len() as u32 + 1;
+        self.iterators.insert(id, records.into_iter());
+        (Ok(id), GasInfo::free())
+    }
+
+    fn next(&mut self, iterator_id: u32) -> BackendResult<Option<Record>> {
+        let next = match self.iterators.get_mut(&iterator_id) {
+            Some(records) => Ok(records.next()),
+            None => Err(BackendError::iterator_does_not_exist(iterator_id)),
+        };
+        (next, GasInfo::free())
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) -> BackendResult<()> {
+        self.data.insert(key.to_vec(), value.to_vec());
+        (Ok(()), GasInfo::free())
+    }
+
+    fn remove(&mut self, key: &[u8]) -> BackendResult<()> {
+        self.data.remove(key);
+        (Ok(()), GasInfo::free())
+    }
+}
+
+/// Bank and wasm queries of contracts, answered from the chain as it was when the call started
+struct SimQuerier {
+    state: ChainState,
+}
+
+impl Querier for SimQuerier {
+    fn query_raw(
+        &self,
+        request: &[u8],
+        _gas_limit: u64,
+    ) -> BackendResult<SystemResult<ContractResult<Binary>>> {
+        let result = match cosmwasm_std::from_slice::<QueryRequest<Empty>>(request) {
+            Ok(request) => self.state.system_query(request),
+            Err(e) => SystemResult::Err(SystemError::InvalidRequest {
+                error: e.to_string(),
+                request: Binary::from(request),
+            }),
+        };
+        (Ok(result), GasInfo::free())
+    }
+}
+
+fn message_info(sender: &str, funds: &[(String, u128)]) -> MessageInfo {
+    MessageInfo {
+        sender: Addr::unchecked(sender),
+        funds: funds
+            .iter()
+            .map(|(denom, amount)| Coin {
+                denom: denom.clone(),
+                amount: Uint128::new(*amount),
+            })
+            .collect(),
+    }
+}
+
+fn proto_coins(coins: &[Coin]) -> Vec<ProtoCoin> {
+    coins
+        .iter()
+        .map(|c| ProtoCoin {
+            denom: c.denom.clone(),
+            amount: c.amount.to_string(),
+        })
+        .collect()
+}
+
+fn any<M: Message>(type_url: &str, msg: M) -> prost_types::Any {
+    prost_types::Any {
+        type_url: type_url.to_string(),
+        value: msg.encode_to_vec(),
+    }
+}
+
+/// `wasm` event of the response attributes and `wasm-<type>` events of its custom events
+fn contract_events(contract: &str, response: &Response<Empty>) -> Vec<MockEvent> {
+    let contract_attribute = ("_contract_address".to_string(), contract.to_string());
+    let mut events = vec![];
+    if !response.attributes.is_empty() {
+        events.push(MockEvent {
+            r#type: "wasm".to_string(),
+            attributes: std::iter::once(contract_attribute.clone())
+                .chain(
+                    response
+                        .attributes
+                        .iter()
+                        .map(|a| (a.key.clone(), a.value.clone())),
+                )
+                .collect(),
+        });
+    }
+    events.extend(response.events.iter().map(|e| {
+        MockEvent {
+            r#type: format!("wasm-{}", e.ty),
+            attributes: std::iter::once(contract_attribute.clone())
+                .chain(
+                    e.attributes
+                        .iter()
+                        .map(|a| (a.key.clone(), a.value.clone())),
+                )
+                .collect(),
+        }
+    }));
+    events
+}
+
+fn to_event(event: &MockEvent) -> Event {
+    Event::new(&event.r#type).add_attributes(
+        event
+            .attributes
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str())),
+    )
+}
+
+impl ChainState {
+    fn env(&self, contract: &str) -> Env {
+        Env {
+            block: BlockInfo {
+                // contracts run while the tx is delivered in the next block
+                height: self.height + 1,
+                time: Timestamp::from_seconds(BLOCK_TIME_SECONDS),
+                chain_id: self.chain_id.clone(),
+            },
+            transaction: Some(TransactionInfo { index: 0 }),
+            contract: ContractInfo {
+                address: Addr::unchecked(contract),
+            },
+        }
+    }
+
+    fn instance(&self, contract: &str) -> Result<SimInstance> {
+        let code_id = self
+            .contracts
+            .iter()
+            .find(|c| c.address == contract)
+            .with_context(|| format!("contract {contract}: not found"))?
+            .code_id;
+        let code = self
+            .codes
+            .iter()
+            .find(|c| c.code_id == code_id)
+            .with_context(|| format!("code id {code_id}: no such code"))?;
+
+        let backend = Backend {
+            api: SimApi {
+                prefix: intern(&self.account_prefix),
+            },
+            storage: SimStorage {
+                data: self.storages.get(contract).cloned().unwrap_or_default(),
+                iterators: HashMap::new(),
+            },
+            querier: SimQuerier {
+                state: self.clone(),
+            },
+        };
+        let options = InstanceOptions {
+            gas_limit: CALL_GAS_LIMIT * GAS_MULTIPLIER,
+            print_debug: false,
+        };
+        Instance::from_code(
+            &code.wasm,
+            backend,
+            options,
+            Some(Size::mebi(MEMORY_LIMIT_MIB)),
+        )
+        .map_err(|e| anyhow!("code id {code_id}: unable to instantiate wasm code: {e}"))
+    }
+
+    /// Run `call` on the contract, keeping the storage it writes and counting the gas it uses.
+    /// Storage of failed calls is discarded along with the rest of the failed tx or sub-message
+    fn call<T>(
+        &mut self,
+        contract: &str,
+        call: impl FnOnce(&mut SimInstance, &Env) -> VmResult<ContractResult<T>>,
+    ) -> Result<T> {
+        let env = self.env(contract);
+        let mut instance = self.instance(contract)?;
+        let result = call(&mut instance, &env);
+
+        let gas_report = instance.create_gas_report();
+        self.contract_gas += (gas_report.limit - gas_report.remaining) / GAS_MULTIPLIER;
+        if let Some(backend) = instance.recycle() {
+            self.storages
+                .insert(contract.to_string(), backend.storage.data);
+        }
+
+        match result.map_err(|e| anyhow!("{e}: wasm contract failed"))? {
+            ContractResult::Ok(response) => Ok(response),
+            ContractResult::Err(e) => bail!("{e}: wasm contract failed"),
+        }
+    }
+
+    pub(super) fn instantiate_contract(
+        &mut self,
+        contract: &str,
+        sender: &str,
+        funds: &[(String, u128)],
+        msg: &[u8],
+    ) -> Result<(Vec<MockEvent>, Vec<u8>)> {
+        let info = message_info(sender, funds);
+        let response = self.call(contract, |instance, env| {
+            call_instantiate::<_, _, _, Empty>(instance, env, &info, msg)
+        })?;
+        let (events, data) = self.handle_response(contract, response)?;
+        Ok((events, data.unwrap_or_default()))
+    }
+
+    pub(super) fn execute_contract(
+        &mut self,
+        contract: &str,
+        sender: &str,
+        funds: &[(String, u128)],
+        msg: &[u8],
+    ) -> Result<(Vec<MockEvent>, Vec<u8>)> {
+        let info = message_info(sender, funds);
+        let response = self.call(contract, |instance, env| {
+            call_execute::<_, _, _, Empty>(instance, env, &info, msg)
+        })?;
+        let (events, data) = self.handle_response(contract, response)?;
+        Ok((events, data.unwrap_or_default()))
+    }
+
+    pub(super) fn migrate_contract(
+        &mut self,
+        contract: &str,
+        msg: &[u8],
+    ) -> Result<(Vec<MockEvent>, Vec<u8>)> {
+        let response = self.call(contract, |instance, env| {
+            call_migrate::<_, _, _, Empty>(instance, env, msg)
+        })?;
+        let (events, data) = self.handle_response(contract, response)?;
+        Ok((events, data.unwrap_or_default()))
+    }
+
+    /// Smart query of the contract, run against a copy so that nothing it does is kept
+    pub(super) fn query_contract(&self, contract: &str, msg: &[u8]) -> Result<Vec<u8>> {
+        let response = self
+            .clone()
+            .call(contract, |instance, env| call_query(instance, env, msg))?;
+        Ok(response.to_vec())
+    }
+
+    fn system_query(&self, request: QueryRequest<Empty>) -> SystemResult<ContractResult<Binary>> {
+        let response = match request {
+            QueryRequest::Bank(BankQuery::Balance { address, denom }) => {
+                let amount = self
+                    .balances
+                    .get(&address)
+                    .and_then(|b| b.iter().find(|(d, _)| *d == denom))
+                    .map(|(_, amount)| *amount)
+                    .unwrap_or_default();
+                Ok(
+                    json!({ "amount": { "denom": denom, "amount": amount.to_string() } })
+                        .to_string()
+                        .into_bytes(),
+                )
+            }
+            QueryRequest::Bank(BankQuery::AllBalances { address }) => {
+                let amount = self
+                    .balances
+                    .get(&address)
+                    .into_iter()
+                    .flatten()
+                    .filter(|(_, amount)| *amount > 0)
+                    .map(|(denom, amount)| json!({ "denom": denom, "amount": amount.to_string() }))
+                    .collect::<Vec<_>>();
+                Ok(json!({ "amount": amount }).to_string().into_bytes())
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                self.query_contract(&contract_addr, msg.as_slice())
+            }
+            QueryRequest::Wasm(WasmQuery::Raw { contract_addr, key }) => Ok(self
+                .storages
+                .get(&contract_addr)
+                .and_then(|storage| storage.get(key.as_slice()))
+                .cloned()
+                .unwrap_or_default()),
+            QueryRequest::Wasm(WasmQuery::ContractInfo { contract_addr }) => {
+                match self.contracts.iter().find(|c| c.address == contract_addr) {
+                    Some(contract) => Ok(json!({
+                        "code_id": contract.code_id,
+                        "creator": contract.creator,
+                        "admin": (!contract.admin.is_empty()).then(|| &contract.admin),
+                        "pinned": false,
+                    })
+                    .to_string()
+                    .into_bytes()),
+                    None => {
+                        return SystemResult::Err(SystemError::NoSuchContract {
+                            addr: contract_addr,
+                        })
+                    }
+                }
+            }
+            _ => {
+                return SystemResult::Err(SystemError::UnsupportedRequest {
+                    kind: "only bank and wasm queries are simulated".to_string(),
+                })
+            }
+        };
+        match response {
+            Ok(data) => SystemResult::Ok(ContractResult::Ok(Binary(data))),
+            Err(e) => SystemResult::Ok(ContractResult::Err(format!("{e:#}"))),
+        }
+    }
+
+    /// Events of the contract's response after dispatching its messages, and its data unless a reply overrides it
+    fn handle_response(
+        &mut self,
+        contract: &str,
+        response: Response<Empty>,
+    ) -> Result<(Vec<MockEvent>, Option<Vec<u8>>)> {
+        let mut events = contract_events(contract, &response);
+        let mut data = response.data.map(|d| d.to_vec());
+
+        for sub_msg in response.messages {
+            // failed sub-messages replied on error are reverted without failing the tx
+            let reverted =
+                matches!(sub_msg.reply_on, ReplyOn::Always | ReplyOn::Error).then(|| self.clone());
+            let result = match (self.dispatch(contract, sub_msg.msg), reverted) {
+                (Ok((sub_events, sub_data)), _) => {
+                    events.extend(sub_events.clone());
+                    if !matches!(sub_msg.reply_on, ReplyOn::Always | ReplyOn::Success) {
+                        continue;
+                    }
+                    SubMsgResult::Ok(SubMsgResponse {
+                        events: sub_events.iter().map(to_event).collect(),
+                        data: (!sub_data.is_empty()).then(|| Binary(sub_data)),
+                    })
+                }
+                (Err(e), Some(reverted)) => {
+                    let contract_gas = self.contract_gas;
+                    *self = reverted;
+                    self.contract_gas = contract_gas;
+                    SubMsgResult::Err(format!("{e:#}"))
+                }
+                (Err(e), None) => return Err(e),
+            };
+
+            let reply = Reply {
+                id: sub_msg.id,
+                result,
+            };
+            let response = self.call(contract, |instance, env| {
+                call_reply::<_, _, _, Empty>(instance, env, &reply)
+            })?;
+            let (reply_events, reply_data) = self.handle_response(contract, response)?;
+            events.extend(reply_events);
+            if reply_data.is_some() {
+                data = reply_data;
+            }
+        }
+        Ok((events, data))
+    }
+
+    /// Execute msg sent by the contract as the equivalent sdk msg
+    fn dispatch(
+        &mut self,
+        contract: &str,
+        msg: CosmosMsg<Empty>,
+    ) -> Result<(Vec<MockEvent>, Vec<u8>)> {
+        let sender = contract.to_string();
+        let msg = match msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => any(
+                "/cosmos.bank.v1beta1.MsgSend",
+                MsgSend {
+                    from_address: sender,
+                    to_address,
+                    amount: proto_coins(&amount),
+                },
+            ),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) => any(
+                "/cosmwasm.wasm.v1.MsgExecuteContract",
+                MsgExecuteContract {
+                    sender,
+                    contract: contract_addr,
+                    msg: msg.to_vec(),
+                    funds: proto_coins(&funds),
+                },
+            ),
+            CosmosMsg::Wasm(WasmMsg::Instantiate {
+                admin,
+                code_id,
+                msg,
+                funds,
+                label,
+            }) => any(
+                "/cosmwasm.wasm.v1.MsgInstantiateContract",
+                MsgInstantiateContract {
+                    sender,
+                    admin: admin.unwrap_or_default(),
+                    code_id,
+                    label,
+                    msg: msg.to_vec(),
+                    funds: proto_coins(&funds),
+                },
+            ),
+            CosmosMsg::Wasm(WasmMsg::Migrate {
+                contract_addr,
+                new_code_id,
+                msg,
+            }) => any(
+                "/cosmwasm.wasm.v1.MsgMigrateContract",
+                MsgMigrateContract {
+                    sender,
+                    contract: contract_addr,
+                    code_id: new_code_id,
+                    msg: msg.to_vec(),
+                },
+            ),
+            CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+                contract_addr,
+                admin,
+            }) => any(
+                "/cosmwasm.wasm.v1.MsgUpdateAdmin",
+                MsgUpdateAdmin {
+                    sender,
+                    new_admin: admin,
+                    contract: contract_addr,
+                },
+            ),
+            CosmosMsg::Wasm(WasmMsg::ClearAdmin { contract_addr }) => any(
+                "/cosmwasm.wasm.v1.MsgClearAdmin",
+                MsgClearAdmin {
+                    sender,
+                    contract: contract_addr,
+                },
+            ),
+            msg => bail!("{msg:?} is not supported by the simulated chain"),
+        };
+        self.execute_msg(contract, &msg)
+    }
+}
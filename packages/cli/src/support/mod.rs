@@ -1,15 +1,59 @@
+pub mod address_book;
+pub mod adr36;
+pub mod alias;
+pub mod amino;
+pub mod audit_log;
+pub mod authz;
+pub mod auto_fund;
+pub mod backend;
+pub mod build_metadata;
+pub mod cargo_workspace;
+pub mod chain_cache;
+pub mod chain_client;
+pub mod chain_features;
 pub mod coin;
 pub mod command;
+pub mod completion;
 pub mod cosmos;
+pub mod csv;
+pub mod error;
+pub mod eth_key;
+pub mod fee_abstraction;
+pub mod fee_market;
 pub mod future;
 pub mod gas;
+pub mod gas_ledger;
+pub mod git;
+pub mod gov;
 pub mod hooks;
+pub mod keystore;
+pub mod lockfile;
+#[cfg(feature = "mock-chain")]
+pub mod mock_chain;
+pub mod msg;
+pub mod msg_builder;
 pub mod node;
+pub mod notify;
 pub mod ops_response;
+pub mod output;
+pub mod pagination;
 pub mod permission;
+pub mod progress;
+pub mod prompt;
 pub mod proto;
+pub mod query_filter;
+pub mod remote_signer;
+pub mod schema;
 pub mod signer;
+pub mod simulation;
 pub mod state;
+pub mod storage;
 pub mod string;
 pub mod template;
+pub mod throttle;
+pub mod transport;
+pub mod tx_summary;
+pub mod typed_response;
 pub mod wasm;
+pub mod wasm_info;
+pub mod wasm_params;
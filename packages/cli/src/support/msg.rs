@@ -0,0 +1,98 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use super::msg_builder::prompt_msg;
+use super::prompt::{interactive, json_input};
+use super::schema::{find_schema, validate_msg};
+
+/// Resolve json msg bytes from `raw` if specified, otherwise from `default_msg_path`.
+/// When neither is available, prompt for it if running interactively,
+/// field by field when the contract has `<action>` msg schema or as json otherwise.
+/// Msg could be written in json, json5 or yaml, it is always converted to json.
+///
+/// `default_msg_path` is expected to be `<contract_dir>/<action>-msgs/<label>.json`,
+/// the msg is validated against `<action>` msg schema of the contract if available.
+/// Query msgs are not validated, a malformed query costs nothing and the contract reports it.
+pub fn msg_bytes(raw: Option<&String>, default_msg_path: PathBuf, action: &str) -> Result<Vec<u8>> {
+    let msg = read_msg(raw, &default_msg_path, action)?;
+
+    if action != "query" {
+        if let Some(contract_dir) = default_msg_path.parent().and_then(|p| p.parent()) {
+            validate_msg(contract_dir, action, &msg)?;
+        }
+    }
+
+    Ok(msg)
+}
+
+fn read_msg(raw: Option<&String>, default_msg_path: &Path, action: &str) -> Result<Vec<u8>> {
+    if let Some(raw) = raw {
+        return to_json_bytes(raw).with_context(|| format!("Unable to {action} with given msg"));
+    }
+
+    let path_str = default_msg_path.to_string_lossy();
+    if default_msg_path.exists() || !interactive() {
+        return fs::read_to_string(default_msg_path)
+            .map_err(|e| anyhow!(e))
+            .and_then(|s| to_json_bytes(&s))
+            .with_context(|| format!("Unable to {action} with `{path_str}`"));
+    }
+
+    let schema = match default_msg_path.parent().and_then(|p| p.parent()) {
+        Some(contract_dir) => find_schema(contract_dir, action)?,
+        None => None,
+    };
+    if let Some(schema) = schema {
+        eprintln!(
+            "  No msg specified and `{path_str}` not found, building {action} msg from schema"
+        );
+        return Ok(serde_json::to_vec(&prompt_msg(&schema, action)?)?);
+    }
+
+    let json = json_input(&format!(
+        "> No msg specified and `{path_str}` not found, enter json msg to {action}"
+    ))?;
+    if json.trim().is_empty() {
+        bail!("Unable to {action} with empty msg");
+    }
+    Ok(json.as_bytes().to_vec())
+}
+
+/// Convert msg written in json, json5 or yaml to json bytes
+pub fn to_json_bytes(msg: &str) -> Result<Vec<u8>> {
+    let value = serde_json::from_str::<serde_json::Value>(msg)
+        .or_else(|_| json5::from_str::<serde_json::Value>(msg))
+        .or_else(|_| serde_yaml::from_str::<serde_json::Value>(msg))
+        .with_context(|| "Msg is neither valid json, json5 nor yaml")?;
+
+    Ok(serde_json::to_vec(&value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_msg_to_json() {
+        let expected = br#"{"increment":{"by":1}}"#.to_vec();
+
+        assert_eq!(
+            to_json_bytes(r#"{ "increment": { "by": 1 } }"#).unwrap(),
+            expected
+        );
+        assert_eq!(
+            to_json_bytes("{ increment: { by: 1, }, // json5\n}").unwrap(),
+            expected
+        );
+        assert_eq!(to_json_bytes("increment:\n  by: 1\n").unwrap(), expected);
+    }
+
+    #[test]
+    fn reject_invalid_msg() {
+        assert!(to_json_bytes("{ increment: ").is_err());
+    }
+}
@@ -0,0 +1,387 @@
+//! Walking msg json schema generated by `cargo schema` to build msgs field by field
+
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+
+use super::prompt;
+use super::schema::msg_variants;
+
+/// cosmwasm-std math types, serialized as numeric strings
+const NUMERIC_STRING_TYPES: &[&str] = &[
+    "Uint64",
+    "Uint128",
+    "Uint256",
+    "Uint512",
+    "Int64",
+    "Int128",
+    "Decimal",
+    "Decimal256",
+];
+
+/// Input expected for a field of the msg
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+    Text,
+    Integer,
+    Number,
+    Boolean,
+    /// Number serialized as string, eg. `Uint128`
+    NumericString,
+    /// One of the listed strings
+    Choice(Vec<String>),
+    /// Nested fields as `(name, schema, required)`
+    Object(Vec<(String, Value, bool)>),
+    /// Anything else is entered as json
+    Json,
+}
+
+impl Field {
+    /// Short description of the expected input
+    pub fn hint(&self) -> &'static str {
+        match self {
+            Field::Text => "text",
+            Field::Integer => "integer",
+            Field::Number | Field::NumericString => "number",
+            Field::Boolean => "true / false",
+            Field::Choice(_) => "choice",
+            Field::Object(_) | Field::Json => "json",
+        }
+    }
+}
+
+fn ref_name(schema: &Value) -> Option<&str> {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| r.rsplit('/').next())
+}
+
+/// Schema without `$ref`, nullable wrappers and single item `allOf` that schemars puts around refs
+pub fn resolve<'s>(root: &'s Value, schema: &'s Value) -> &'s Value {
+    if let Some(name) = ref_name(schema) {
+        let definition = ["definitions", "$defs"]
+            .iter()
+            .find_map(|k| root.get(*k).and_then(|d| d.get(name)));
+        if let Some(definition) = definition {
+            return resolve(root, definition);
+        }
+    }
+    for key in ["allOf", "anyOf"] {
+        if let Some(items) = schema.get(key).and_then(Value::as_array) {
+            let non_null = items
+                .iter()
+                .filter(|s| s.get("type").and_then(Value::as_str) != Some("null"))
+                .collect::<Vec<_>>();
+            if non_null.len() == 1 {
+                return resolve(root, non_null[0]);
+            }
+        }
+    }
+    schema
+}
+
+fn numeric_string(root: &Value, schema: &Value) -> bool {
+    let inner = ["allOf", "anyOf"]
+        .iter()
+        .filter_map(|k| schema.get(*k).and_then(Value::as_array))
+        .flatten()
+        .find_map(ref_name);
+    ref_name(schema)
+        .or(inner)
+        .map(|name| NUMERIC_STRING_TYPES.contains(&name))
+        .unwrap_or(false)
+        && resolve(root, schema).get("type").and_then(Value::as_str) == Some("string")
+}
+
+/// `type` of the schema ignoring `null` of nullable types, eg. `["string", "null"]`
+fn schema_type(schema: &Value) -> Option<&str> {
+    match schema.get("type")? {
+        Value::String(t) => Some(t),
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null"),
+        _ => None,
+    }
+}
+
+fn object_fields(schema: &Value) -> Vec<(String, Value, bool)> {
+    let required = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).collect::<Vec<_>>())
+        .unwrap_or_default();
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, s)| (name.clone(), s.clone(), required.contains(&name.as_str())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// What to ask for to fill a field with `schema`
+pub fn field(root: &Value, schema: &Value) -> Field {
+    if numeric_string(root, schema) {
+        return Field::NumericString;
+    }
+    let schema = resolve(root, schema);
+    if let Some(choices) = schema.get("enum").and_then(Value::as_array) {
+        return Field::Choice(
+            choices
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+        );
+    }
+    match schema_type(schema) {
+        Some("string") => Field::Text,
+        Some("integer") => Field::Integer,
+        Some("number") => Field::Number,
+        Some("boolean") => Field::Boolean,
+        Some("object") if schema.get("properties").is_some() => {
+            Field::Object(object_fields(schema))
+        }
+        _ => Field::Json,
+    }
+}
+
+/// Fields of the `variant` of msg enum `schema`, `None` for unit variants serialized as plain strings
+pub fn variant_fields(schema: &Value, variant: &str) -> Result<Option<Vec<(String, Value, bool)>>> {
+    let items = ["oneOf", "anyOf"]
+        .iter()
+        .filter_map(|k| schema.get(*k).and_then(Value::as_array))
+        .flatten();
+    for item in items {
+        if let Some(variant_schema) = item.get("properties").and_then(|p| p.get(variant)) {
+            return Ok(Some(match field(schema, variant_schema) {
+                Field::Object(fields) => fields,
+                _ => vec![],
+            }));
+        }
+        let is_unit = item
+            .get("enum")
+            .and_then(Value::as_array)
+            .map(|e| e.iter().any(|v| v.as_str() == Some(variant)))
+            .unwrap_or(false);
+        if is_unit {
+            return Ok(None);
+        }
+    }
+    bail!("Variant `{variant}` not found in msg schema")
+}
+
+/// Parse and validate user input for the field, empty input is rejected
+pub fn parse_input(field: &Field, input: &str) -> Result<Value> {
+    let input = input.trim();
+    if input.is_empty() {
+        bail!("Value is required");
+    }
+    Ok(match field {
+        Field::Text => Value::String(input.to_string()),
+        Field::Integer => Value::from(
+            input
+                .parse::<i64>()
+                .with_context(|| format!("`{input}` is not an integer"))?,
+        ),
+        Field::Number => Value::from(
+            input
+                .parse::<f64>()
+                .with_context(|| format!("`{input}` is not a number"))?,
+        ),
+        Field::Boolean => match input {
+            "true" | "yes" | "y" => Value::Bool(true),
+            "false" | "no" | "n" => Value::Bool(false),
+            _ => bail!("`{input}` is not a boolean, use true or false"),
+        },
+        Field::NumericString => {
+            let (int, frac) = input.split_once('.').unwrap_or((input, "0"));
+            let digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+            if !(digits(int.trim_start_matches('-')) && digits(frac)) {
+                bail!("`{input}` is not a number");
+            }
+            Value::String(input.to_string())
+        }
+        Field::Choice(choices) => {
+            if !choices.iter().any(|c| c == input) {
+                bail!("`{input}` is not one of: {}", choices.join(", "));
+            }
+            Value::String(input.to_string())
+        }
+        Field::Object(_) | Field::Json => {
+            serde_json::from_str(input).with_context(|| format!("`{input}` is not valid json"))?
+        }
+    })
+}
+
+/// Msg of the `variant` with its filled `fields`
+pub fn variant_msg(variant: &str, fields: Option<Map<String, Value>>) -> Value {
+    match fields {
+        Some(fields) => Value::Object(Map::from_iter([(
+            variant.to_string(),
+            Value::Object(fields),
+        )])),
+        None => Value::String(variant.to_string()),
+    }
+}
+
+/// Ask for every field of the object, nested required objects are filled field by field
+pub fn prompt_fields(
+    schema: &Value,
+    fields: &[(String, Value, bool)],
+    path: &str,
+) -> Result<Map<String, Value>> {
+    let mut filled = Map::new();
+    for (name, field_schema, required) in fields {
+        let path = format!("{path}.{name}");
+        let value = match field(schema, field_schema) {
+            Field::Object(nested) if *required => {
+                Some(Value::Object(prompt_fields(schema, &nested, &path)?))
+            }
+            Field::Choice(choices) => {
+                let skip = "(skip)".to_string();
+                let items = if *required {
+                    choices
+                } else {
+                    [vec![skip.clone()], choices].concat()
+                };
+                Some(prompt::select(&format!("> {path}"), &items, 0)?)
+                    .filter(|c| c != &skip)
+                    .map(Value::String)
+            }
+            f => {
+                let optional = if *required { "" } else { ", empty to skip" };
+                let input = prompt::validated_input(
+                    &format!("> {path} ({}{optional})", f.hint()),
+                    !*required,
+                    |input| parse_input(&f, input).map(|_| ()),
+                )?;
+                if input.trim().is_empty() {
+                    None
+                } else {
+                    Some(parse_input(&f, &input)?)
+                }
+            }
+        };
+        if let Some(value) = value {
+            filled.insert(name.clone(), value);
+        }
+    }
+    Ok(filled)
+}
+
+/// Build `kind` msg by prompting for it according to its `schema`. Variant of msg enums is picked first,
+/// without asking if there is only one, while fields of struct msgs (eg. instantiate msg) are asked right away
+pub fn prompt_msg(schema: &Value, kind: &str) -> Result<Value> {
+    let variants = msg_variants(schema);
+    let variant = match variants.as_slice() {
+        [] => {
+            let fields = object_fields(resolve(schema, schema));
+            return Ok(Value::Object(prompt_fields(schema, &fields, kind)?));
+        }
+        [variant] => variant.clone(),
+        _ => prompt::select(&format!("> {kind} msg"), &variants, 0)?,
+    };
+    let fields = variant_fields(schema, &variant)?
+        .map(|fields| prompt_fields(schema, &fields, &variant))
+        .transpose()?;
+    Ok(variant_msg(&variant, fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn execute_schema() -> Value {
+        json!({
+            "oneOf": [
+                { "type": "string", "enum": ["reset"] },
+                {
+                    "type": "object",
+                    "required": ["transfer"],
+                    "properties": {
+                        "transfer": {
+                            "type": "object",
+                            "required": ["amount", "recipient"],
+                            "properties": {
+                                "amount": { "$ref": "#/definitions/Uint128" },
+                                "recipient": { "type": "string" },
+                                "memo": { "type": ["string", "null"] },
+                                "kind": { "allOf": [{ "$ref": "#/definitions/Kind" }] }
+                            }
+                        }
+                    }
+                }
+            ],
+            "definitions": {
+                "Uint128": { "type": "string" },
+                "Kind": { "type": "string", "enum": ["fast", "slow"] }
+            }
+        })
+    }
+
+    #[test]
+    fn collect_variant_fields() {
+        let schema = execute_schema();
+        assert_eq!(variant_fields(&schema, "reset").unwrap(), None);
+        assert!(variant_fields(&schema, "burn").is_err());
+
+        let fields = variant_fields(&schema, "transfer").unwrap().unwrap();
+        let kinds = fields
+            .iter()
+            .map(|(name, s, required)| (name.as_str(), field(&schema, s), *required))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                ("amount", Field::NumericString, true),
+                (
+                    "kind",
+                    Field::Choice(vec!["fast".to_string(), "slow".to_string()]),
+                    false
+                ),
+                ("memo", Field::Text, false),
+                ("recipient", Field::Text, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_inputs() {
+        assert_eq!(
+            parse_input(&Field::NumericString, "100").unwrap(),
+            json!("100")
+        );
+        assert_eq!(
+            parse_input(&Field::NumericString, "0.5").unwrap(),
+            json!("0.5")
+        );
+        assert!(parse_input(&Field::NumericString, "1e5").is_err());
+        assert_eq!(parse_input(&Field::Integer, "42").unwrap(), json!(42));
+        assert!(parse_input(&Field::Integer, "4.2").is_err());
+        assert_eq!(parse_input(&Field::Boolean, "yes").unwrap(), json!(true));
+        assert!(parse_input(&Field::Choice(vec!["fast".to_string()]), "slow").is_err());
+        assert!(parse_input(&Field::Json, "{").is_err());
+        assert!(parse_input(&Field::Text, " ").is_err());
+
+        assert_eq!(variant_msg("reset", None), json!("reset"));
+        assert_eq!(
+            prompt_msg(&json!({ "oneOf": [{ "enum": ["reset"] }] }), "execute").unwrap(),
+            json!("reset")
+        );
+        assert_eq!(
+            prompt_msg(&json!({ "type": "object" }), "instantiate").unwrap(),
+            json!({})
+        );
+        let fields = Map::from_iter([("by".to_string(), json!(1))]);
+        assert_eq!(
+            variant_msg("increment", Some(fields)),
+            json!({ "increment": { "by": 1 } })
+        );
+    }
+}
@@ -0,0 +1,236 @@
+//! Posting deployment events to configured webhooks
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+use console::style;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::modules::wasm::config::{Notification, NotificationFormat};
+use crate::support::state::Action;
+
+/// Seconds to wait for a webhook before giving up on it
+const WEBHOOK_TIMEOUT: u64 = 10;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeployStatus {
+    Succeeded,
+    Failed,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct DeployEvent {
+    pub action: Action,
+    pub status: DeployStatus,
+    pub network: String,
+    pub contract: String,
+    pub label: Option<String>,
+    pub code_id: Option<u64>,
+    pub address: Option<String>,
+    pub tx_hash: Option<String>,
+    /// Address of the tx signer
+    pub actor: String,
+    pub error: Option<String>,
+}
+
+impl DeployEvent {
+    pub fn new(
+        action: Action,
+        network: &str,
+        contract: &str,
+        label: Option<&str>,
+        actor: &str,
+    ) -> Self {
+        Self {
+            action,
+            status: DeployStatus::Succeeded,
+            network: network.to_string(),
+            contract: contract.to_string(),
+            label: label.map(str::to_string),
+            code_id: None,
+            address: None,
+            tx_hash: None,
+            actor: actor.to_string(),
+            error: None,
+        }
+    }
+
+    /// Event completed with `(code_id, address, tx_hash)` of the succeeded tx or the error it failed with
+    pub fn outcome(self, result: Result<(u64, Option<&str>, &str), &anyhow::Error>) -> Self {
+        match result {
+            Ok((code_id, address, tx_hash)) => Self {
+                code_id: Some(code_id),
+                address: address.map(str::to_string),
+                tx_hash: Some(tx_hash.to_string()),
+                ..self
+            },
+            Err(e) => Self {
+                status: DeployStatus::Failed,
+                error: Some(format!("{e:#}")),
+                ..self
+            },
+        }
+    }
+}
+
+fn subscribed(notification: &Notification, event: &DeployEvent) -> bool {
+    (notification.on.is_empty() || notification.on.contains(&event.action))
+        && (notification.networks.is_empty() || notification.networks.contains(&event.network))
+}
+
+fn action_name(action: Action) -> &'static str {
+    match action {
+        Action::Store => "store",
+        Action::Instantiate => "instantiate",
+        Action::Migrate => "migrate",
+        Action::Rollback => "rollback",
+    }
+}
+
+/// Body posted to the webhook in `format`
+pub fn payload(format: NotificationFormat, event: &DeployEvent) -> Value {
+    match format {
+        NotificationFormat::Json => json!(event),
+        NotificationFormat::Slack => {
+            let (icon, status) = match event.status {
+                DeployStatus::Succeeded => (":white_check_mark:", "succeeded"),
+                DeployStatus::Failed => (":x:", "failed"),
+            };
+            let target = match &event.label {
+                Some(label) => format!("{}:{label}", event.contract),
+                None => event.contract.clone(),
+            };
+            let mut details = vec![];
+            if let Some(code_id) = event.code_id {
+                details.push(format!("code id: `{code_id}`"));
+            }
+            if let Some(address) = &event.address {
+                details.push(format!("address: `{address}`"));
+            }
+            if let Some(tx_hash) = &event.tx_hash {
+                details.push(format!("tx: `{tx_hash}`"));
+            }
+            details.push(format!("by: `{}`", event.actor));
+
+            let mut text = format!(
+                "{icon} *{}* of `{target}` on `{}` {status}\n{}",
+                action_name(event.action),
+                event.network,
+                details.join(" · ")
+            );
+            if let Some(error) = &event.error {
+                text.push_str(&format!("\n```{error}```"));
+            }
+            json!({ "text": text })
+        }
+    }
+}
+
+fn post(url: &str, body: &Value) -> Result<()> {
+    let mut child = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location"])
+        .args(["--max-time", &WEBHOOK_TIMEOUT.to_string()])
+        .args(["--header", "Content-Type: application/json"])
+        .args(["--data-binary", "@-", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| "Unable to run `curl`, make sure it is installed")?;
+    child
+        .stdin
+        .take()
+        .context("Unable to write to `curl`")?
+        .write_all(body.to_string().as_bytes())?;
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+/// Post `event` to every subscribed webhook, failing webhooks only print a warning
+/// so that notifications never fail a deployment
+pub fn notify(notifications: &[Notification], event: &DeployEvent) {
+    for notification in notifications.iter().filter(|n| subscribed(n, event)) {
+        if let Err(e) = post(&notification.url, &payload(notification.format, event)) {
+            eprintln!(
+                "  {} unable to notify webhook: {e:#}",
+                style("WARNING:").yellow().bold()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> DeployEvent {
+        DeployEvent::new(
+            Action::Instantiate,
+            "testnet",
+            "counter",
+            Some("default"),
+            "osmo1actor",
+        )
+    }
+
+    #[test]
+    fn filter_subscribed_notifications() {
+        let notification = |on: Vec<Action>, networks: Vec<&str>| Notification {
+            url: "https://example.com".to_string(),
+            format: NotificationFormat::Json,
+            on,
+            networks: networks.into_iter().map(str::to_string).collect(),
+        };
+
+        assert!(subscribed(&notification(vec![], vec![]), &event()));
+        assert!(subscribed(
+            &notification(vec![Action::Instantiate], vec!["testnet"]),
+            &event()
+        ));
+        assert!(!subscribed(
+            &notification(vec![Action::Store, Action::Migrate], vec![]),
+            &event()
+        ));
+        assert!(!subscribed(
+            &notification(vec![], vec!["mainnet"]),
+            &event()
+        ));
+    }
+
+    #[test]
+    fn format_payloads() {
+        let succeeded = event().outcome(Ok((3, Some("osmo1contract"), "ABCD")));
+        assert_eq!(
+            payload(NotificationFormat::Json, &succeeded),
+            json!({
+                "action": "instantiate",
+                "status": "succeeded",
+                "network": "testnet",
+                "contract": "counter",
+                "label": "default",
+                "code_id": 3,
+                "address": "osmo1contract",
+                "tx_hash": "ABCD",
+                "actor": "osmo1actor",
+                "error": null,
+            })
+        );
+        assert_eq!(
+            payload(NotificationFormat::Slack, &succeeded),
+            json!({ "text": ":white_check_mark: *instantiate* of `counter:default` on `testnet` succeeded\ncode id: `3` · address: `osmo1contract` · tx: `ABCD` · by: `osmo1actor`" })
+        );
+
+        let failed = event().outcome(Err(&anyhow::anyhow!("out of gas")));
+        assert_eq!(
+            payload(NotificationFormat::Slack, &failed),
+            json!({ "text": ":x: *instantiate* of `counter:default` on `testnet` failed\nby: `osmo1actor`\n```out of gas```" })
+        );
+    }
+}
@@ -1,4 +1,8 @@
-pub trait OpResponseDisplay {
+use serde::Serialize;
+
+use super::output;
+
+pub trait OpResponseDisplay: Serialize {
     fn headline() -> &'static str;
     fn attrs(&self) -> Vec<String>;
     fn display_format(&self) -> String {
@@ -11,7 +15,7 @@ pub trait OpResponseDisplay {
         .join("\n")
     }
     fn log(&self) {
-        println!("{}", self.display_format())
+        output::print(self, || self.display_format())
     }
 }
 
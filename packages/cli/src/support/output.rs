@@ -0,0 +1,60 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use anyhow::Result;
+use serde::Serialize;
+
+#[derive(clap::ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable output
+    Table,
+    Json,
+    Yaml,
+}
+
+static OUTPUT_FORMAT: AtomicU8 = AtomicU8::new(OutputFormat::Table as u8);
+
+pub fn set_output_format(format: OutputFormat) {
+    OUTPUT_FORMAT.store(format as u8, Ordering::Relaxed);
+}
+
+pub fn output_format() -> OutputFormat {
+    match OUTPUT_FORMAT.load(Ordering::Relaxed) {
+        f if f == OutputFormat::Json as u8 => OutputFormat::Json,
+        f if f == OutputFormat::Yaml as u8 => OutputFormat::Yaml,
+        _ => OutputFormat::Table,
+    }
+}
+
+/// Serialize `value` in the selected machine readable format,
+/// `None` if human readable output is selected.
+pub fn to_machine_readable<T: Serialize + ?Sized>(value: &T) -> Result<Option<String>> {
+    Ok(match output_format() {
+        OutputFormat::Table => None,
+        OutputFormat::Json => Some(serde_json::to_string_pretty(value)?),
+        OutputFormat::Yaml => Some(serde_yaml::to_string(value)?),
+    })
+}
+
+/// Print the result of a command, `table` is only rendered when human readable output is selected.
+pub fn print<T: Serialize + ?Sized>(value: &T, table: impl FnOnce() -> String) {
+    match to_machine_readable(value) {
+        Ok(Some(s)) => println!("{s}"),
+        Ok(None) => println!("{}", table()),
+        Err(e) => eprintln!("Unable to serialize output: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn output_format_roundtrip() {
+        for format in [OutputFormat::Json, OutputFormat::Yaml, OutputFormat::Table] {
+            set_output_format(format);
+            assert_eq!(output_format(), format);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Following `pagination.next_key` of cosmos-sdk list queries
+
+use std::future::Future;
+
+use anyhow::Result;
+use console::style;
+use cosmos_sdk_proto::cosmos::base::query::v1beta1::{PageRequest, PageResponse};
+
+/// Items of a list query, up to the requested limit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// More items are left on chain beyond the limit
+    pub more: bool,
+}
+
+/// Collect pages returned by `query` until `limit` items are collected, every page if `None`
+pub async fn paginate<T, F, Fut>(limit: Option<u64>, mut query: F) -> Result<Page<T>>
+where
+    F: FnMut(PageRequest) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<PageResponse>)>>,
+{
+    let mut items = vec![];
+    let mut next_key = vec![];
+    loop {
+        let remaining = limit.map(|l| l.saturating_sub(items.len() as u64));
+        if remaining == Some(0) {
+            return Ok(Page { items, more: true });
+        }
+
+        let (page, pagination) = query(PageRequest {
+            key: next_key,
+            offset: 0,
+            // `0` lets the node use its default page size
+            limit: remaining.unwrap_or(0),
+            count_total: false,
+            reverse: false,
+        })
+        .await?;
+        items.extend(page);
+
+        match pagination {
+            Some(p) if !p.next_key.is_empty() => next_key = p.next_key,
+            _ => {
+                if let Some(limit) = limit {
+                    items.truncate(limit as usize);
+                }
+                return Ok(Page { items, more: false });
+            }
+        }
+    }
+}
+
+/// Tell on stderr that `page` stops short of every entry, keeping stdout parseable
+pub fn note_more<T>(page: &Page<T>) {
+    if page.more {
+        eprintln!(
+            "  {} showing the first {} entries, more are available, use `--all` to fetch every page",
+            style("‣").cyan(),
+            page.items.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::support::future::block;
+
+    /// Query over `0..total` serving at most `max_page` items per page, keyed by the next item
+    async fn query(
+        total: u8,
+        max_page: u64,
+        req: PageRequest,
+    ) -> Result<(Vec<u8>, Option<PageResponse>)> {
+        let start = req.key.first().copied().unwrap_or(0);
+        let size = if req.limit == 0 {
+            max_page
+        } else {
+            req.limit.min(max_page)
+        };
+        let end = (start as u64 + size).min(total as u64) as u8;
+        let next_key = if end < total { vec![end] } else { vec![] };
+        Ok((
+            (start..end).collect(),
+            Some(PageResponse { next_key, total: 0 }),
+        ))
+    }
+
+    #[test]
+    fn follow_next_key_up_to_limit() {
+        let all = block(paginate(None, |req| query(25, 10, req))).unwrap();
+        assert_eq!(all.items, (0..25).collect::<Vec<_>>());
+        assert!(!all.more);
+
+        let limited = block(paginate(Some(15), |req| query(25, 10, req))).unwrap();
+        assert_eq!(limited.items, (0..15).collect::<Vec<_>>());
+        assert!(limited.more);
+
+        let exact = block(paginate(Some(25), |req| query(25, 10, req))).unwrap();
+        assert_eq!(exact.items.len(), 25);
+        assert!(!exact.more);
+    }
+}
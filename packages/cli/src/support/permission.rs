@@ -1,34 +1,56 @@
-use std::str::FromStr;
-
-use anyhow::anyhow;
+use anyhow::Result;
+use console::style;
 use cosmrs::{cosmwasm::AccessConfig, AccountId};
 
+use super::address_book::resolve_address;
+use crate::framework::config::Network;
+
+/// Admin specified for the command, falls back to `default_admin` from config.
+/// `no_admin` explicitly opts out of having admin.
+pub fn admin_or_default(
+    admin: &Option<String>,
+    no_admin: bool,
+    default_admin: &Option<String>,
+) -> Option<String> {
+    if no_admin {
+        Some("none".to_string())
+    } else {
+        admin.clone().or_else(|| default_admin.clone())
+    }
+}
+
 pub fn compute_admin(
     admin: Option<&String>,
     signer_account_id: AccountId,
-) -> Result<Option<AccountId>, anyhow::Error> {
-    Ok(if admin == Some(&"signer".to_string()) {
-        Some(signer_account_id)
-    } else if let Some(addr) = admin {
-        Some(AccountId::from_str(addr).map_err(|e: cosmrs::ErrorReport| anyhow!(e))?)
-    } else {
-        None
+    network: &Network,
+) -> Result<Option<AccountId>> {
+    Ok(match admin.map(|a| a.as_str()) {
+        Some("signer") => Some(signer_account_id),
+        Some("none") => None,
+        Some(addr) => Some(resolve_address(network, addr)?),
+        None => {
+            eprintln!(
+                "  {} instantiating contract without admin, it will not be able to be migrated.\n  \
+                Specify `--admin`, or `--no-admin` / `default_admin = \"none\"` in config if this is intended.",
+                style("WARNING:").yellow().bold()
+            );
+            None
+        }
     })
 }
 
 pub fn compute_instantiate_permission(
     permit_instantiate_only: &Option<String>,
     signer_account_id: AccountId,
-) -> Result<Option<AccessConfig>, anyhow::Error> {
+    network: &Network,
+) -> Result<Option<AccessConfig>> {
     let instantiate_permission = permit_instantiate_only
         .as_ref()
         .map(|permitted_account| {
             let address = if permitted_account == "signer" {
                 signer_account_id
             } else {
-                permitted_account
-                    .parse()
-                    .map_err(|e: cosmrs::ErrorReport| anyhow::anyhow!(e))?
+                resolve_address(network, permitted_account)?
             };
 
             anyhow::Ok(AccessConfig {
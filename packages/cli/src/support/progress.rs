@@ -0,0 +1,110 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use console::style;
+use indicatif::{ProgressBar, ProgressStyle};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Never animate progress for the rest of the process, only plain log lines are printed.
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// A phase of a long running operation.
+/// Shows a spinner while in progress when attended by a user, degrades to plain log lines otherwise.
+pub struct Progress {
+    message: String,
+    bar: Option<ProgressBar>,
+    started: Instant,
+}
+
+impl Progress {
+    /// Phase without output of its own, eg. key derivation or waiting for tx confirmation, shown as a spinner.
+    pub fn spinner(message: &str) -> Self {
+        let bar = if !PLAIN.load(Ordering::Relaxed) && console::user_attended_stderr() {
+            let bar = ProgressBar::new_spinner();
+            bar.set_style(
+                ProgressStyle::default_spinner().template("  {spinner:.cyan} {msg} {elapsed:.dim}"),
+            );
+            bar.set_message(message.to_string());
+            bar.enable_steady_tick(100);
+            Some(bar)
+        } else {
+            eprintln!("  {} {message}...", style("‣").cyan());
+            None
+        };
+
+        Progress {
+            message: message.to_string(),
+            bar,
+            started: Instant::now(),
+        }
+    }
+
+    /// Phase that streams output of a subprocess, eg. cargo build or docker, only the headline is shown
+    /// since a spinner would interleave with the subprocess output.
+    pub fn stream(message: &str) -> Self {
+        eprintln!("  {} {message}...", style("‣").cyan().bold());
+        Progress {
+            message: message.to_string(),
+            bar: None,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn finish(self) {
+        self.end(style("✔").green().to_string());
+    }
+
+    pub fn fail(self) {
+        self.end(style("✘").red().to_string());
+    }
+
+    /// Fail the phase showing why, for errors that can not be passed on to the caller
+    pub fn fail_with(mut self, reason: &str) {
+        self.message = format!("{}: {reason}", self.message);
+        self.fail();
+    }
+
+    /// Finish or fail the phase according to the result of the phase.
+    pub fn end_with<T, E>(self, result: Result<T, E>) -> Result<T, E> {
+        if result.is_ok() {
+            self.finish();
+        } else {
+            self.fail();
+        }
+        result
+    }
+
+    fn end(&self, mark: String) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+        eprintln!(
+            "  {mark} {} {}",
+            self.message,
+            style(format_elapsed(self.started.elapsed())).dim()
+        );
+    }
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs >= 60 {
+        format!("({}m {}s)", secs / 60, secs % 60)
+    } else {
+        format!("({}.{}s)", secs, elapsed.subsec_millis() / 100)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_elapsed_time() {
+        assert_eq!(format_elapsed(Duration::from_millis(1234)), "(1.2s)");
+        assert_eq!(format_elapsed(Duration::from_secs(133)), "(2m 13s)");
+    }
+}
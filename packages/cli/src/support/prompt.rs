@@ -0,0 +1,123 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use dialoguer::{Confirm, Input, Password, Select};
+use once_cell::sync::Lazy;
+
+use super::error::{Error, ErrorKind};
+
+static NO_INPUT: AtomicBool = AtomicBool::new(false);
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+static EXCLUSIVE: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Disable all interactive prompts for the rest of the process, eg. when running in CI.
+pub fn set_no_input(no_input: bool) {
+    NO_INPUT.store(no_input, Ordering::Relaxed);
+}
+
+/// Prompting is only allowed when it is not disabled and there is a user attending the terminal.
+pub fn interactive() -> bool {
+    !NO_INPUT.load(Ordering::Relaxed) && console::user_attended()
+}
+
+/// Agree to all confirmation prompts for the rest of the process.
+pub fn set_assume_yes(yes: bool) {
+    ASSUME_YES.store(yes, Ordering::Relaxed);
+}
+
+pub fn assume_yes() -> bool {
+    ASSUME_YES.load(Ordering::Relaxed)
+}
+
+/// Run `f` while no other thread is within `exclusive`, so that output and prompts of
+/// concurrent operations (eg. multi-network deploys) do not interleave
+pub fn exclusive<T>(f: impl FnOnce() -> T) -> T {
+    let _guard = EXCLUSIVE.lock().unwrap_or_else(|e| e.into_inner());
+    f()
+}
+
+/// Ask for confirmation unless `--yes` is set, refuse when confirmation is impossible.
+pub fn confirm(prompt: &str) -> Result<bool> {
+    if assume_yes() {
+        return Ok(true);
+    }
+    if !interactive() {
+        return Err(input_required(format!(
+            "Confirmation required: {prompt}, rerun with `--yes` to agree"
+        )));
+    }
+    Ok(Confirm::new().with_prompt(prompt).interact()?)
+}
+
+/// Ask for secret input, `env_var` takes precedence so that it can be provided non-interactively
+pub fn password(prompt: &str, env_var: &str) -> Result<String> {
+    if let Ok(password) = std::env::var(env_var) {
+        return Ok(password);
+    }
+    if !interactive() {
+        return Err(input_required(format!(
+            "Input required: {prompt}, set `{env_var}` to provide it non-interactively"
+        )));
+    }
+    Ok(Password::new().with_prompt(prompt).interact()?)
+}
+
+pub fn select(prompt: &str, items: &[String], default: usize) -> Result<String> {
+    if !interactive() {
+        return Err(input_required(format!(
+            "Selection required: {prompt}, provide it via flags instead"
+        )));
+    }
+    let selected = Select::new()
+        .with_prompt(prompt)
+        .items(items)
+        .default(default)
+        .interact()?;
+    Ok(items[selected].clone())
+}
+
+pub fn json_input(prompt: &str) -> Result<String> {
+    if !interactive() {
+        return Err(input_required(format!(
+            "Input required: {prompt}, provide it via flags instead"
+        )));
+    }
+    let json = Input::<String>::new()
+        .with_prompt(prompt)
+        .validate_with(|s: &String| {
+            serde_json::from_str::<serde_json::Value>(s)
+                .map(|_| ())
+                .map_err(|e| format!("Invalid json: {e}"))
+        })
+        .interact_text()?;
+    Ok(json)
+}
+
+/// Ask for text input checked with `validate`, empty input is accepted only if `allow_empty`
+pub fn validated_input(
+    prompt: &str,
+    allow_empty: bool,
+    validate: impl Fn(&str) -> Result<()>,
+) -> Result<String> {
+    if !interactive() {
+        return Err(input_required(format!(
+            "Input required: {prompt}, provide it via flags instead"
+        )));
+    }
+    let input = Input::<String>::new()
+        .with_prompt(prompt)
+        .allow_empty(allow_empty)
+        .validate_with(|s: &String| {
+            if allow_empty && s.trim().is_empty() {
+                return Ok(());
+            }
+            validate(s).map_err(|e| e.to_string())
+        })
+        .interact_text()?;
+    Ok(input)
+}
+
+fn input_required(message: String) -> anyhow::Error {
+    anyhow!(Error::new(ErrorKind::InputRequired, message))
+}
@@ -0,0 +1,41 @@
+use anyhow::{anyhow, Result};
+use jmespath::Variable;
+
+/// Apply JMESPath expression (eg. `balances[0].amount`) to `data`.
+/// Strings are returned raw (without quotes) for easy shell consumption, other values as json.
+pub fn filter_raw(data: &serde_json::Value, expr: &str) -> Result<String> {
+    let compiled = jmespath::compile(expr).map_err(|e| anyhow!("Invalid query filter: {e}"))?;
+    let result = compiled
+        .search(data)
+        .map_err(|e| anyhow!("Unable to apply query filter `{expr}`: {e}"))?;
+
+    Ok(match result.as_ref() {
+        Variable::String(s) => s.to_string(),
+        v => serde_json::to_string(v)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn filter_raw_values() {
+        let data = json!({
+            "owner": "osmo1owner",
+            "balances": [{ "denom": "uosmo", "amount": "100" }],
+            "count": 3
+        });
+
+        assert_eq!(filter_raw(&data, "owner").unwrap(), "osmo1owner");
+        assert_eq!(filter_raw(&data, "balances[0].amount").unwrap(), "100");
+        assert_eq!(filter_raw(&data, "count").unwrap(), "3");
+        assert_eq!(
+            filter_raw(&data, "balances[].denom").unwrap(),
+            r#"["uosmo"]"#
+        );
+        assert_eq!(filter_raw(&data, "missing").unwrap(), "null");
+        assert!(filter_raw(&data, "balances[").is_err());
+    }
+}
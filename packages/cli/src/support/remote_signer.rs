@@ -0,0 +1,292 @@
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use cosmrs::crypto::secp256k1::SigningKey;
+use k256::ecdsa::signature::{self, Signer};
+use k256::ecdsa::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+use super::progress::Progress;
+use crate::framework::config::RemoteSigner;
+
+/// Order of secp256k1 curve, used for normalizing signature to low-S form as required by cosmos-sdk
+const SECP256K1_N: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// Signer that keeps private key in remote service and only ever sees the public key and signatures.
+/// Remote services are called through their official clis (`aws`, `vault`) so that
+/// credentials are resolved exactly the same way as the rest of the user's tooling.
+pub struct RemoteKey {
+    backend: RemoteSigner,
+    verifying_key: VerifyingKey,
+}
+
+impl RemoteKey {
+    pub fn connect(backend: &RemoteSigner) -> Result<Self> {
+        let public_key = match backend {
+            RemoteSigner::AwsKms { key_id, region } => {
+                let res = cli_json(
+                    Command::new("aws")
+                        .args(["kms", "get-public-key", "--key-id", key_id])
+                        .args(region_args(region))
+                        .args(["--output", "json"]),
+                )?;
+                base64::decode(json_str(&res, "/PublicKey")?)?
+            }
+            RemoteSigner::VaultTransit { key, mount } => {
+                let res = cli_json(Command::new("vault").args([
+                    "read",
+                    "-format=json",
+                    &format!("{}/keys/{key}", vault_mount(mount)),
+                ]))?;
+                let latest_version = res
+                    .pointer("/data/latest_version")
+                    .with_context(|| "Unable to find latest version of vault key")?;
+                let pem = json_str(&res, &format!("/data/keys/{latest_version}/public_key"))?;
+                base64::decode(
+                    pem.lines()
+                        .filter(|l| !l.starts_with("-----"))
+                        .collect::<String>(),
+                )?
+            }
+        };
+
+        Ok(RemoteKey {
+            backend: backend.clone(),
+            verifying_key: VerifyingKey::from_sec1_bytes(spki_point(&public_key)?)
+                .map_err(|_| anyhow::anyhow!("Remote key is not a secp256k1 key"))?,
+        })
+    }
+
+    pub fn into_signing_key(self) -> SigningKey {
+        SigningKey::new(Box::new(self))
+    }
+
+    fn sign_digest(&self, digest: &[u8]) -> Result<Signature> {
+        let digest = base64::encode(digest);
+        let der = match &self.backend {
+            RemoteSigner::AwsKms { key_id, region } => {
+                let res = cli_json(
+                    Command::new("aws")
+                        .args(["kms", "sign", "--key-id", key_id])
+                        .args(["--message", &digest, "--message-type", "DIGEST"])
+                        .args(["--signing-algorithm", "ECDSA_SHA_256"])
+                        .args(region_args(region))
+                        .args(["--output", "json"]),
+                )?;
+                base64::decode(json_str(&res, "/Signature")?)?
+            }
+            RemoteSigner::VaultTransit { key, mount } => {
+                let res = cli_json(Command::new("vault").args([
+                    "write",
+                    "-format=json",
+                    &format!("{}/sign/{key}/sha2-256", vault_mount(mount)),
+                    &format!("input={digest}"),
+                    "prehashed=true",
+                    "marshaling_algorithm=asn1",
+                ]))?;
+                let signature = json_str(&res, "/data/signature")?;
+                base64::decode(
+                    signature
+                        .rsplit(':')
+                        .next()
+                        .with_context(|| format!("Unexpected vault signature `{signature}`"))?,
+                )?
+            }
+        };
+
+        let compact = der_to_low_s_compact(&der)?;
+        Signature::try_from(compact.as_slice()).map_err(|e| anyhow::anyhow!("{e}"))
+    }
+}
+
+impl AsRef<VerifyingKey> for RemoteKey {
+    fn as_ref(&self) -> &VerifyingKey {
+        &self.verifying_key
+    }
+}
+
+impl Signer<Signature> for RemoteKey {
+    /// `signature::Error` carries no cause, so the failure is reported through progress before it is returned
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature, signature::Error> {
+        let progress = Progress::spinner("Signing with remote signer");
+        match self.sign_digest(&Sha256::digest(msg)) {
+            Ok(signature) => {
+                progress.finish();
+                Ok(signature)
+            }
+            Err(e) => {
+                progress.fail_with(&format!("{e:#}"));
+                Err(signature::Error::new())
+            }
+        }
+    }
+}
+
+fn region_args(region: &Option<String>) -> Vec<String> {
+    region
+        .as_ref()
+        .map(|r| vec!["--region".to_string(), r.clone()])
+        .unwrap_or_default()
+}
+
+fn vault_mount(mount: &Option<String>) -> &str {
+    mount.as_deref().unwrap_or("transit")
+}
+
+fn cli_json(cmd: &mut Command) -> Result<serde_json::Value> {
+    let program = cmd.get_program().to_string_lossy().to_string();
+    let output = cmd
+        .output()
+        .with_context(|| format!("Unable to run `{program}`, make sure it is installed"))?;
+    if !output.status.success() {
+        bail!(
+            "`{program}` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    serde_json::from_slice(&output.stdout)
+        .with_context(|| format!("Unable to parse output of `{program}`"))
+}
+
+fn json_str<'a>(value: &'a serde_json::Value, pointer: &str) -> Result<&'a str> {
+    value
+        .pointer(pointer)
+        .and_then(|v| v.as_str())
+        .with_context(|| format!("Unable to find `{pointer}` in remote signer response"))
+}
+
+/// Extract uncompressed EC point from DER encoded SubjectPublicKeyInfo,
+/// the point is the content of the trailing bit string
+fn spki_point(spki: &[u8]) -> Result<&[u8]> {
+    const UNCOMPRESSED_POINT_LEN: usize = 65;
+    if spki.len() < UNCOMPRESSED_POINT_LEN || spki[spki.len() - UNCOMPRESSED_POINT_LEN] != 0x04 {
+        bail!("Unable to parse remote public key");
+    }
+    Ok(&spki[spki.len() - UNCOMPRESSED_POINT_LEN..])
+}
+
+fn der_integer(der: &[u8], pos: &mut usize) -> Result<[u8; 32]> {
+    if der.get(*pos) != Some(&0x02) {
+        bail!("Invalid DER signature");
+    }
+    let len = *der.get(*pos + 1).with_context(|| "Invalid DER signature")? as usize;
+    let start = *pos + 2;
+    let bytes = der
+        .get(start..start + len)
+        .with_context(|| "Invalid DER signature")?;
+    *pos = start + len;
+
+    // strip sign padding and left pad to 32 bytes
+    let bytes = match bytes.iter().position(|b| *b != 0) {
+        Some(i) => &bytes[i..],
+        None => &[],
+    };
+    if bytes.len() > 32 {
+        bail!("Invalid DER signature");
+    }
+    let mut out = [0u8; 32];
+    out[32 - bytes.len()..].copy_from_slice(bytes);
+    Ok(out)
+}
+
+fn is_high(s: &[u8; 32]) -> bool {
+    // compare against n / 2
+    let mut half_n = [0u8; 32];
+    let mut carry = 0u8;
+    for (i, b) in SECP256K1_N.iter().enumerate() {
+        half_n[i] = (carry << 7) | (b >> 1);
+        carry = b & 1;
+    }
+    s > &half_n
+}
+
+fn sub_from_n(s: &[u8; 32]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let mut d = SECP256K1_N[i] as i16 - s[i] as i16 - borrow;
+        borrow = if d < 0 {
+            d += 256;
+            1
+        } else {
+            0
+        };
+        out[i] = d as u8;
+    }
+    out
+}
+
+/// Convert DER encoded ECDSA signature into 64 bytes `r || s` with low-S,
+/// remote services do not guarantee low-S which cosmos-sdk rejects
+pub fn der_to_low_s_compact(der: &[u8]) -> Result<Vec<u8>> {
+    if der.first() != Some(&0x30) {
+        bail!("Invalid DER signature");
+    }
+    let mut pos = 2;
+    let r = der_integer(der, &mut pos)?;
+    let s = der_integer(der, &mut pos)?;
+    let s = if is_high(&s) { sub_from_n(&s) } else { s };
+    Ok([r, s].concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn der(r: &[u8], s: &[u8]) -> Vec<u8> {
+        let body = [
+            vec![0x02, r.len() as u8],
+            r.to_vec(),
+            vec![0x02, s.len() as u8],
+            s.to_vec(),
+        ]
+        .concat();
+        [vec![0x30, body.len() as u8], body].concat()
+    }
+
+    #[test]
+    fn keep_low_s() {
+        let compact = der_to_low_s_compact(&der(&[0x00, 0x80, 0x01], &[0x05])).unwrap();
+        let mut expected = [0u8; 64];
+        expected[30] = 0x80;
+        expected[31] = 0x01;
+        expected[63] = 0x05;
+        assert_eq!(compact, expected.to_vec());
+    }
+
+    #[test]
+    fn normalize_high_s() {
+        // s = n - 1 is high, normalized to 1
+        let mut s = SECP256K1_N.to_vec();
+        s[31] -= 1;
+        let s = [vec![0x00], s].concat();
+
+        let compact = der_to_low_s_compact(&der(&[0x01], &s)).unwrap();
+        assert_eq!(compact[31], 0x01);
+        assert_eq!(&compact[32..63], &[0u8; 31]);
+        assert_eq!(compact[63], 0x01);
+    }
+
+    #[test]
+    fn reject_invalid_der() {
+        assert!(der_to_low_s_compact(&[0x02, 0x01]).is_err());
+        assert!(der_to_low_s_compact(&der(&[1u8; 33], &[1])).is_err());
+    }
+
+    #[test]
+    fn extract_point_from_spki() {
+        let point = [vec![0x04], vec![7u8; 64]].concat();
+        let spki = [
+            vec![0x30, 0x56, 0x30, 0x10],
+            vec![0u8; 18],
+            vec![0x03, 0x42, 0x00],
+            point.clone(),
+        ]
+        .concat();
+        assert_eq!(spki_point(&spki).unwrap(), point.as_slice());
+        assert!(spki_point(&[0x30, 0x01]).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, bail, Context, Result};
+use jsonschema::JSONSchema;
+use serde_json::Value;
+
+/// Find json schema of `<kind>` msg (eg. `execute`) generated by `cargo schema` in the contract directory.
+/// Supports both combined `schema/<contract_name>.json` and per msg `schema/<kind>_msg.json` layouts.
+pub fn find_schema(contract_dir: &Path, kind: &str) -> Result<Option<Value>> {
+    let contract_name = contract_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    find_schema_with(&contract_name, kind, |path| {
+        let path = contract_dir.join(path);
+        Ok(if path.exists() {
+            Some(fs::read_to_string(&path)?)
+        } else {
+            None
+        })
+    })
+}
+
+/// Same as [`find_schema`], reading files relative to the contract directory with `read`,
+/// eg. from a past git commit
+pub fn find_schema_with(
+    contract_name: &str,
+    kind: &str,
+    read: impl Fn(&str) -> Result<Option<String>>,
+) -> Result<Option<Value>> {
+    if !contract_name.is_empty() {
+        if let Some(combined) = read(&format!("schema/{contract_name}.json"))? {
+            let schema: Value = serde_json::from_str(&combined)?;
+            if let Some(s) = schema.get(kind).filter(|s| !s.is_null()) {
+                return Ok(Some(s.clone()));
+            }
+        }
+    }
+
+    if let Some(per_msg) = read(&format!("schema/{kind}_msg.json"))? {
+        return Ok(Some(serde_json::from_str(&per_msg)?));
+    }
+
+    Ok(None)
+}
+
+/// Names of the variants of a msg enum schema, eg. `get_count` of `{ "get_count": {} }`
+/// and unit variants serialized as plain strings
+pub fn msg_variants(schema: &Value) -> Vec<String> {
+    let mut variants = ["oneOf", "anyOf"]
+        .iter()
+        .filter_map(|k| schema.get(*k).and_then(Value::as_array))
+        .flatten()
+        .flat_map(|v| {
+            let required = v
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten();
+            let enums = v
+                .get("enum")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten();
+            required.chain(enums).filter_map(Value::as_str)
+        })
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    variants.sort();
+    variants.dedup();
+    variants
+}
+
+/// Validate `msg` against the contract's `<kind>` msg schema, skipped if no schema is found.
+pub fn validate_msg(contract_dir: &Path, kind: &str, msg: &[u8]) -> Result<()> {
+    let schema = match find_schema(contract_dir, kind)? {
+        Some(schema) => schema,
+        None => return Ok(()),
+    };
+    validate_against(&schema, kind, msg)
+}
+
+fn validate_against(schema: &Value, kind: &str, msg: &[u8]) -> Result<()> {
+    let compiled =
+        JSONSchema::compile(schema).map_err(|e| anyhow!("Invalid {kind} msg schema: {e}"))?;
+    let instance: Value = serde_json::from_slice(msg).with_context(|| "Msg is not valid json")?;
+
+    if let Err(errors) = compiled.validate(&instance) {
+        let details = errors
+            .map(|e| {
+                let path = e.instance_path.to_string();
+                let path = if path.is_empty() {
+                    "/".to_string()
+                } else {
+                    path
+                };
+                format!("  • `{path}`: {e}")
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        bail!(
+            "Msg does not match {kind} msg schema:\n{details}\n\
+            If the schema is outdated, regenerate it with `cargo schema`"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn execute_schema() -> Value {
+        json!({
+            "oneOf": [{
+                "type": "object",
+                "required": ["increment"],
+                "properties": {
+                    "increment": {
+                        "type": "object",
+                        "required": ["by"],
+                        "properties": { "by": { "type": "integer" } },
+                        "additionalProperties": false
+                    }
+                },
+                "additionalProperties": false
+            }]
+        })
+    }
+
+    #[test]
+    fn valid_msg_passes() {
+        assert!(
+            validate_against(&execute_schema(), "execute", br#"{"increment":{"by":1}}"#).is_ok()
+        );
+    }
+
+    #[test]
+    fn invalid_msg_is_reported() {
+        let err = validate_against(&execute_schema(), "execute", br#"{"incremnt":{"by":1}}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("does not match execute msg schema"));
+    }
+
+    #[test]
+    fn list_msg_variants() {
+        let schema = json!({
+            "oneOf": [
+                { "type": "string", "enum": ["reset"] },
+                { "type": "object", "required": ["get_count"], "properties": { "get_count": {} } },
+                execute_schema()["oneOf"][0].clone()
+            ]
+        });
+        assert_eq!(
+            msg_variants(&schema),
+            vec!["get_count", "increment", "reset"]
+        );
+    }
+
+    #[test]
+    fn missing_schema_is_skipped() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        assert!(validate_msg(dir.path(), "execute", b"{}").is_ok());
+    }
+}
@@ -1,9 +1,23 @@
-use anyhow::bail;
+use std::sync::Arc;
+
+use anyhow::{bail, Context};
 use clap::Parser;
-use cosmrs::{bip32, crypto::secp256k1::SigningKey};
+use cosmrs::{bip32, crypto::secp256k1::SigningKey, AccountId};
 use keyring::Entry;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
 
-use crate::{framework::config::Account, modules::key::config::SERVICE};
+use super::auto_fund;
+use super::chain_client::ChainClient;
+use super::eth_key::{self, signing_key_from_bytes};
+use super::keystore::{decode_private_key, PASSPHRASE_ENV, PRIVATE_KEY_PREFIX};
+use super::progress::Progress;
+use super::prompt;
+use super::remote_signer::RemoteKey;
+use crate::{
+    framework::config::{Account, GlobalConfig, KeyAlgo, MnemonicLanguage, Network},
+    modules::key::config::SERVICE,
+};
 
 // TODO:
 // - [x] make this a group
@@ -11,8 +25,9 @@ use crate::{framework::config::Account, modules::key::config::SERVICE};
 
 const SIGNER_GROUP: &str = "signer";
 
-#[derive(Debug, Parser, Clone)]
+#[derive(Debug, Parser, Clone, Default, Deserialize)]
 #[clap(group = clap::ArgGroup::new(SIGNER_GROUP).multiple(false))]
+#[serde(default)]
 pub struct SignerArgs {
     /// Specifies predefined account as a tx signer
     #[clap(long, group = SIGNER_GROUP)]
@@ -26,54 +41,384 @@ pub struct SignerArgs {
     #[clap(long, group = SIGNER_GROUP)]
     pub signer_mnemonic: Option<String>,
 
-    /// Specifies private_key as a tx signer (base64 encoded string)
+    /// Specifies private_key as a tx signer (hex, base64 or armored)
     #[clap(long, group = SIGNER_GROUP)]
     pub signer_private_key: Option<String>,
+
+    /// On local networks, offer to create the signer in keyring if it does not exist
+    /// and to fund it from the genesis validator if it has no balance
+    #[clap(long)]
+    pub auto_fund: bool,
 }
 
 impl SignerArgs {
+    /// Signing key for transactions on `network`, derived with the network's `coin_type` and `key_algo`
+    /// unless the signer account sets its own
     pub fn private_key(
         &self,
-        global_config: &crate::framework::config::GlobalConfig,
+        chain: &Arc<ChainClient>,
+        global_config: &GlobalConfig,
+        network: &str,
     ) -> Result<SigningKey, anyhow::Error> {
         let Self {
             signer_account,
             signer_keyring,
             signer_mnemonic,
             signer_private_key,
+            auto_fund,
         } = self;
-        let derivation_path = global_config.derivation_path();
+        let network_info = global_config.networks().get(network);
+        let derivation_path = &override_derivation_path(
+            global_config.derivation_path(),
+            None,
+            network_info.and_then(|n| *n.coin_type()),
+        )?;
+        let key_algo = network_info.map(|n| *n.key_algo()).unwrap_or_default();
         let signer_priv = if let Some(signer_account) = signer_account {
             match global_config.accounts().get(signer_account) {
-                None => bail!("signer account: `{signer_account}` is not defined"),
-                Some(Account::FromMnemonic { mnemonic }) => {
-                    SigningKey::from_mnemonic(mnemonic.as_str(), derivation_path)
+                None if *auto_fund => {
+                    let secret = auto_fund::create_key(global_config, network, signer_account)?;
+                    keyring_private_key_with(chain, &secret, derivation_path, key_algo)
                 }
-                Some(Account::FromPrivateKey { private_key }) => {
-                    Ok(SigningKey::from_bytes(&base64::decode(private_key)?).unwrap())
+                None => bail!("signer account: `{signer_account}` is not defined"),
+                Some(account) => {
+                    account_private_key_with(chain, account, derivation_path, key_algo)
+                        .map(|key| register_sign_mode(chain, key, account))
                 }
             }
         } else if let Some(signer_keyring) = signer_keyring {
-            let mnemonic = Entry::new(SERVICE, signer_keyring).get_password()?;
-            SigningKey::from_mnemonic(&mnemonic, derivation_path)
+            let secret = match Entry::new(SERVICE, signer_keyring).get_password() {
+                Err(keyring::KeyringError::NoPasswordFound) if *auto_fund => {
+                    auto_fund::create_key(global_config, network, signer_keyring)?
+                }
+                secret => secret?,
+            };
+            keyring_private_key_with(chain, &secret, derivation_path, key_algo)
         } else if let Some(signer_mnemonic) = signer_mnemonic {
-            SigningKey::from_mnemonic(signer_mnemonic, derivation_path)
+            mnemonic_key(chain, signer_mnemonic, derivation_path, None, key_algo)
         } else if let Some(signer_private_key) = signer_private_key {
-            Ok(SigningKey::from_bytes(&base64::decode(signer_private_key)?).unwrap())
+            signing_key_from_bytes(&private_key_bytes(signer_private_key, key_algo)?, key_algo)
+        } else if prompt::interactive() && !global_config.accounts().is_empty() {
+            let accounts = global_config.accounts().keys().cloned().collect::<Vec<_>>();
+            let signer_account = prompt::select("> Select signer account", &accounts, 0)?;
+            let account = &global_config.accounts()[&signer_account];
+            account_private_key_with(chain, account, derivation_path, key_algo)
+                .map(|key| register_sign_mode(chain, key, account))
         } else {
-            bail!("Unable to retrive signer private key")
+            bail!("Unable to retrive signer private key, please specify one of `--signer-account`, `--signer-keyring`, `--signer-mnemonic` or `--signer-private-key`")
         }?;
+        if *auto_fund {
+            auto_fund::fund_if_empty(chain, global_config, network, &signer_priv)?;
+        }
         Ok(signer_priv)
     }
 }
 
+/// Raw bytes of hex, base64 or armored private key, passphrase of armored key is prompted for
+fn private_key_bytes(private_key: &str, key_algo: KeyAlgo) -> Result<Vec<u8>, anyhow::Error> {
+    decode_private_key(private_key, key_algo, || {
+        prompt::password("> Passphrase of the armored private key", PASSPHRASE_ENV)
+    })
+}
+
+/// Record the account's own sign mode for transactions signed with `key`
+fn register_sign_mode(chain: &ChainClient, key: SigningKey, account: &Account) -> SigningKey {
+    if let Some(sign_mode) = account.sign_mode() {
+        chain.set_sign_mode(&key.public_key().to_bytes(), sign_mode);
+    }
+    key
+}
+
+/// Signing key from keyring entry, which is either a mnemonic or an imported private key
+pub fn keyring_private_key(
+    secret: &str,
+    derivation_path: &str,
+) -> Result<SigningKey, anyhow::Error> {
+    keyring_private_key_with(
+        &ChainClient::default(),
+        secret,
+        derivation_path,
+        KeyAlgo::Secp256k1,
+    )
+}
+
+/// Same as [`keyring_private_key`] with `key_algo`, keys derived from a mnemonic are cached on `chain`
+pub fn keyring_private_key_with(
+    chain: &ChainClient,
+    secret: &str,
+    derivation_path: &str,
+    key_algo: KeyAlgo,
+) -> Result<SigningKey, anyhow::Error> {
+    match secret.strip_prefix(PRIVATE_KEY_PREFIX) {
+        Some(private_key) => signing_key_from_bytes(&base64::decode(private_key)?, key_algo),
+        None => mnemonic_key(chain, secret, derivation_path, None, key_algo),
+    }
+}
+
+pub fn account_private_key(
+    account: &Account,
+    derivation_path: &str,
+) -> Result<SigningKey, anyhow::Error> {
+    account_private_key_with(
+        &ChainClient::default(),
+        account,
+        derivation_path,
+        KeyAlgo::Secp256k1,
+    )
+}
+
+/// Same as [`account_private_key`] with `key_algo`, keys derived from a mnemonic are cached on `chain`.
+/// The account's own `key_algo` takes precedence and is recorded on `chain` for the key's address and signer info
+pub fn account_private_key_with(
+    chain: &ChainClient,
+    account: &Account,
+    derivation_path: &str,
+    key_algo: KeyAlgo,
+) -> Result<SigningKey, anyhow::Error> {
+    match account.key_algo() {
+        Some(key_algo) => {
+            let key = account_key(chain, account, derivation_path, key_algo)?;
+            chain.set_key_algo(&key.public_key().to_bytes(), key_algo);
+            Ok(key)
+        }
+        None => account_key(chain, account, derivation_path, key_algo),
+    }
+}
+
+fn account_key(
+    chain: &ChainClient,
+    account: &Account,
+    derivation_path: &str,
+    key_algo: KeyAlgo,
+) -> Result<SigningKey, anyhow::Error> {
+    match account {
+        Account::FromMnemonic {
+            mnemonic,
+            language,
+            derivation_path: account_derivation_path,
+            coin_type,
+            ..
+        } => mnemonic_key(
+            chain,
+            mnemonic.as_str(),
+            &override_derivation_path(
+                derivation_path,
+                account_derivation_path.as_deref(),
+                *coin_type,
+            )?,
+            *language,
+            key_algo,
+        ),
+        Account::FromPrivateKey { private_key, .. } => {
+            signing_key_from_bytes(&private_key_bytes(private_key, key_algo)?, key_algo)
+        }
+        Account::FromRemoteSigner { remote_signer, .. } => {
+            if key_algo != KeyAlgo::Secp256k1 {
+                bail!("Remote signers only support `secp256k1` keys");
+            }
+            let progress = Progress::spinner("Connecting to remote signer");
+            progress.end_with(RemoteKey::connect(remote_signer).map(RemoteKey::into_signing_key))
+        }
+    }
+}
+
+/// Derivation path of an account, its own `derivation_path` takes precedence,
+/// otherwise `coin_type` (of the account or its network) replaces the coin type segment of the global derivation path
+pub fn override_derivation_path(
+    global: &str,
+    derivation_path: Option<&str>,
+    coin_type: Option<u32>,
+) -> Result<String, anyhow::Error> {
+    match (derivation_path, coin_type) {
+        (Some(path), _) => Ok(path.to_string()),
+        (None, Some(coin_type)) => {
+            let mut segments = global.split('/').collect::<Vec<&str>>();
+            let coin_type_segment = format!("{coin_type}'");
+            *segments.get_mut(2).with_context(|| {
+                format!("Global derivation path `{global}` does not contain coin type")
+            })? = coin_type_segment.as_str();
+            Ok(segments.join("/"))
+        }
+        (None, None) => Ok(global.to_string()),
+    }
+}
+
+/// Signing key of predefined `account` on `network`, derived with the network's `coin_type` and `key_algo`
+pub fn network_account_key(
+    chain: &ChainClient,
+    global_config: &GlobalConfig,
+    account: &Account,
+    network: &Network,
+) -> Result<SigningKey, anyhow::Error> {
+    account_private_key_with(
+        chain,
+        account,
+        &override_derivation_path(global_config.derivation_path(), None, *network.coin_type())?,
+        *network.key_algo(),
+    )
+}
+
+/// Signing key of keyring `secret` on `network`, derived with the network's `coin_type` and `key_algo`
+pub fn network_keyring_key(
+    chain: &ChainClient,
+    global_config: &GlobalConfig,
+    secret: &str,
+    network: &Network,
+) -> Result<SigningKey, anyhow::Error> {
+    keyring_private_key_with(
+        chain,
+        secret,
+        &override_derivation_path(global_config.derivation_path(), None, *network.coin_type())?,
+        *network.key_algo(),
+    )
+}
+
+/// Address of `key` on `network`, with the network's `account_prefix` and `key_algo`,
+/// unless the key's account sets its own `key_algo` as recorded on `chain`
+pub fn network_address(
+    chain: &ChainClient,
+    global_config: &GlobalConfig,
+    key: &SigningKey,
+    network: &Network,
+) -> Result<AccountId, anyhow::Error> {
+    let prefix = network
+        .account_prefix()
+        .as_deref()
+        .unwrap_or(global_config.account_prefix());
+    let public_key = key.public_key();
+    let key_algo = chain
+        .key_algo(&public_key.to_bytes())
+        .unwrap_or(*network.key_algo());
+    eth_key::account_id(&public_key, prefix, key_algo)
+}
+
 pub trait SigningKeyExt {
     fn from_mnemonic(phrase: &str, derivation_path: &str) -> Result<SigningKey, anyhow::Error> {
-        let seed = bip32::Mnemonic::new(phrase, bip32::Language::English)?.to_seed("");
-        let xprv = bip32::XPrv::derive_from_path(seed, &derivation_path.parse()?)?;
-        let signer_priv: SigningKey = xprv.into();
-        Ok(signer_priv)
+        Self::from_mnemonic_in(phrase, derivation_path, None)
+    }
+
+    /// Derive signing key from mnemonic of the BIP-39 wordlist `language`, detected from the phrase if not specified
+    fn from_mnemonic_in(
+        phrase: &str,
+        derivation_path: &str,
+        language: Option<MnemonicLanguage>,
+    ) -> Result<SigningKey, anyhow::Error> {
+        Self::from_mnemonic_with(phrase, derivation_path, language, KeyAlgo::Secp256k1)
+    }
+
+    /// Same as [`SigningKeyExt::from_mnemonic_in`], signing as `key_algo` accounts do
+    fn from_mnemonic_with(
+        phrase: &str,
+        derivation_path: &str,
+        language: Option<MnemonicLanguage>,
+        key_algo: KeyAlgo,
+    ) -> Result<SigningKey, anyhow::Error> {
+        signing_key_from_bytes(
+            &mnemonic_key_bytes(phrase, derivation_path, language)?,
+            key_algo,
+        )
     }
 }
 
 impl SigningKeyExt for SigningKey {}
+
+fn mnemonic_key_bytes(
+    phrase: &str,
+    derivation_path: &str,
+    language: Option<MnemonicLanguage>,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let progress = Progress::spinner("Deriving signing key");
+    progress.end_with((|| -> Result<Vec<u8>, anyhow::Error> {
+        let mnemonic = match language {
+            Some(language) => bip39::Mnemonic::parse_in(language.into(), phrase)?,
+            None => bip39::Mnemonic::parse(phrase)?,
+        };
+        let seed = mnemonic.to_seed("");
+        let xprv = bip32::XPrv::derive_from_path(seed, &derivation_path.parse()?)?;
+        Ok(xprv.private_key().to_bytes().to_vec())
+    })())
+}
+
+/// Same as [`SigningKeyExt::from_mnemonic_with`], derived only once per `chain`
+fn mnemonic_key(
+    chain: &ChainClient,
+    phrase: &str,
+    derivation_path: &str,
+    language: Option<MnemonicLanguage>,
+    key_algo: KeyAlgo,
+) -> Result<SigningKey, anyhow::Error> {
+    let key_id = hex::encode(Sha256::digest(
+        format!("{language:?}\0{derivation_path}\0{phrase}").as_bytes(),
+    ));
+    let bytes = chain.signing_key_bytes(&key_id, || {
+        mnemonic_key_bytes(phrase, derivation_path, language)
+    })?;
+    signing_key_from_bytes(&bytes, key_algo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_override_derivation_path() {
+        let global = "m/44'/118'/0'/0/0";
+        assert_eq!(
+            override_derivation_path(global, None, None).unwrap(),
+            global
+        );
+        assert_eq!(
+            override_derivation_path(global, Some("m/44'/118'/0'/0/3"), Some(330)).unwrap(),
+            "m/44'/118'/0'/0/3"
+        );
+        assert_eq!(
+            override_derivation_path(global, None, Some(330)).unwrap(),
+            "m/44'/330'/0'/0/0"
+        );
+        assert!(override_derivation_path("m", None, Some(330)).is_err());
+    }
+
+    #[test]
+    fn test_from_mnemonic_in_other_language() {
+        let path = "m/44'/118'/0'/0/0";
+        let french = "abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abaisser abeille";
+
+        let detected = SigningKey::from_mnemonic(french, path).unwrap();
+        let explicit =
+            SigningKey::from_mnemonic_in(french, path, Some(MnemonicLanguage::French)).unwrap();
+        assert_eq!(
+            detected.public_key().to_bytes(),
+            explicit.public_key().to_bytes()
+        );
+        assert!(
+            SigningKey::from_mnemonic_in(french, path, Some(MnemonicLanguage::English)).is_err()
+        );
+    }
+
+    #[test]
+    fn test_account_key_algo_overrides_network() {
+        let chain = ChainClient::default();
+        let path = "m/44'/118'/0'/0/0";
+        let account = |key_algo| Account::FromPrivateKey {
+            private_key: "SNI8xBejBnTpB6JAPxCfCC2S4ZeCPQLmpCPGrrjkEgQ=".to_string(),
+            sign_mode: None,
+            key_algo,
+        };
+
+        let key =
+            account_private_key_with(&chain, &account(None), path, KeyAlgo::Secp256k1).unwrap();
+        assert_eq!(chain.key_algo(&key.public_key().to_bytes()), None);
+
+        let key = account_private_key_with(
+            &chain,
+            &account(Some(KeyAlgo::EthSecp256k1)),
+            path,
+            KeyAlgo::Secp256k1,
+        )
+        .unwrap();
+        assert_eq!(
+            chain.key_algo(&key.public_key().to_bytes()),
+            Some(KeyAlgo::EthSecp256k1)
+        );
+    }
+}
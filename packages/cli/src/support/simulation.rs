@@ -0,0 +1,141 @@
+use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxMsgData;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::SimulateResponse;
+use cosmos_sdk_proto::cosmwasm::wasm::v1::MsgExecuteContractResponse;
+use prost::Message;
+use serde::Serialize;
+
+use super::ops_response::OpResponseDisplay;
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct SimulatedEvent {
+    pub r#type: String,
+    pub attributes: Vec<(String, String)>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SimulationResponse {
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+    pub events: Vec<SimulatedEvent>,
+    /// Response data returned by the contract, json if possible, otherwise base64 encoded
+    pub data: Option<serde_json::Value>,
+}
+
+/// Decode data set by contract's execute response from `TxMsgData` of simulation result
+pub fn decode_execute_data(tx_msg_data: &[u8]) -> Option<serde_json::Value> {
+    let msg_data = TxMsgData::decode(tx_msg_data).ok()?;
+    let data = MsgExecuteContractResponse::decode(msg_data.data.first()?.data.as_slice())
+        .ok()?
+        .data;
+    if data.is_empty() {
+        return None;
+    }
+
+    Some(
+        serde_json::from_slice(&data)
+            .unwrap_or_else(|_| serde_json::Value::String(base64::encode(&data))),
+    )
+}
+
+impl From<SimulateResponse> for SimulationResponse {
+    fn from(res: SimulateResponse) -> Self {
+        let gas_info = res.gas_info.unwrap_or_default();
+        let result = res.result.unwrap_or_default();
+
+        SimulationResponse {
+            gas_wanted: gas_info.gas_wanted,
+            gas_used: gas_info.gas_used,
+            events: result
+                .events
+                .iter()
+                .map(|e| SimulatedEvent {
+                    r#type: e.r#type.clone(),
+                    attributes: e
+                        .attributes
+                        .iter()
+                        .map(|a| {
+                            (
+                                String::from_utf8_lossy(a.key.as_ref()).to_string(),
+                                String::from_utf8_lossy(a.value.as_ref()).to_string(),
+                            )
+                        })
+                        .collect(),
+                })
+                .collect(),
+            data: decode_execute_data(&result.data),
+        }
+    }
+}
+
+impl OpResponseDisplay for SimulationResponse {
+    fn headline() -> &'static str {
+        "Simulated successfully, nothing is broadcasted"
+    }
+    fn attrs(&self) -> Vec<String> {
+        let mut attrs = vec![
+            format!("    ├── gas_wanted: {}", self.gas_wanted),
+            format!("    ├── gas_used: {}", self.gas_used),
+            format!(
+                "    ├── data: {}",
+                self.data
+                    .as_ref()
+                    .map(|d| d.to_string())
+                    .unwrap_or_else(|| "–".to_string())
+            ),
+            "    └── events".to_string(),
+        ];
+        let last = self.events.len().saturating_sub(1);
+        attrs.extend(self.events.iter().enumerate().map(|(i, e)| {
+            let attributes = e
+                .attributes
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<String>>()
+                .join(", ");
+            let branch = if i == last { "└──" } else { "├──" };
+            format!("          {branch} {}: {attributes}", e.r#type)
+        }));
+        attrs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::MsgData;
+
+    fn tx_msg_data(data: &[u8]) -> Vec<u8> {
+        let execute_response = MsgExecuteContractResponse {
+            data: data.to_vec(),
+        };
+        TxMsgData {
+            data: vec![MsgData {
+                msg_type: "/cosmwasm.wasm.v1.MsgExecuteContract".to_string(),
+                data: execute_response.encode_to_vec(),
+            }],
+        }
+        .encode_to_vec()
+    }
+
+    #[test]
+    fn decode_json_data() {
+        assert_eq!(
+            decode_execute_data(&tx_msg_data(br#"{"count":1}"#)),
+            Some(serde_json::json!({"count": 1}))
+        );
+    }
+
+    #[test]
+    fn decode_non_json_data_as_base64() {
+        assert_eq!(
+            decode_execute_data(&tx_msg_data(&[0xff, 0x01])),
+            Some(serde_json::Value::String(base64::encode([0xff, 0x01])))
+        );
+    }
+
+    #[test]
+    fn decode_empty_data() {
+        assert_eq!(decode_execute_data(&tx_msg_data(&[])), None);
+        assert_eq!(decode_execute_data(&[]), None);
+    }
+}
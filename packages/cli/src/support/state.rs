@@ -1,10 +1,15 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-use anyhow::{Context as _, Result};
+use anyhow::{bail, Context as _, Result};
 use config::Map;
 use getset::Getters;
 use serde::{Deserialize, Serialize};
 
+use super::build_metadata::BuildMetadata;
 use crate::framework::config::{Network, NetworkVariant};
 
 pub const STATE_DIR: &str = ".beaker";
@@ -17,12 +22,149 @@ pub struct Proposal {
     store_code: Option<u64>,
 }
 
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Store,
+    Instantiate,
+    Migrate,
+    Rollback,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Getters)]
+#[get = "pub"]
+pub struct HistoryEntry {
+    action: Action,
+    /// Label of the instance, empty for `store`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    label: String,
+    code_id: u64,
+    /// Unix timestamp in seconds
+    #[serde(default)]
+    timestamp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tx_hash: Option<String>,
+    /// sha256 of the stored wasm
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+    /// Git commit the stored wasm was built from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    git_commit: Option<String>,
+    /// Toolchain and optimizer the stored wasm was built with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    build_env: Option<BuildMetadata>,
+}
+
+impl HistoryEntry {
+    pub fn new(action: Action, label: &str, code_id: u64) -> Self {
+        HistoryEntry {
+            action,
+            label: label.to_string(),
+            code_id,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            tx_hash: None,
+            checksum: None,
+            git_commit: None,
+            build_env: None,
+        }
+    }
+
+    pub fn with_tx_hash(self, tx_hash: &str) -> Self {
+        HistoryEntry {
+            tx_hash: Some(tx_hash.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_build(self, checksum: &str, git_commit: Option<String>) -> Self {
+        HistoryEntry {
+            checksum: Some(checksum.to_string()),
+            git_commit,
+            ..self
+        }
+    }
+
+    pub fn with_build_env(self, build_env: Option<BuildMetadata>) -> Self {
+        HistoryEntry { build_env, ..self }
+    }
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default, Getters)]
 #[get = "pub"]
 pub struct WasmRef {
     code_id: Option<u64>,
     addresses: Map<String, String>, // TODO: considering removing this since it could be retrive from chain
     proposal: Proposal,
+    /// Append-only record of instantiations and migrations of the contract
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    history: Vec<HistoryEntry>,
+}
+
+impl WasmRef {
+    pub fn with_code_id(&self, code_id: Option<u64>) -> WasmRef {
+        WasmRef {
+            code_id,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_address(&self, label: &str, address: &str) -> WasmRef {
+        let mut addresses = self.addresses.clone();
+        addresses.insert(label.to_string(), address.to_string());
+        WasmRef {
+            addresses,
+            ..self.clone()
+        }
+    }
+
+    /// Indices of the history entries still part of the release chain of every label, oldest first.
+    /// Instantiations and migrations are pushed onto the chain, each rollback undoes the latest release.
+    fn release_chains(&self) -> Map<&str, Vec<usize>> {
+        let mut chains: Map<&str, Vec<usize>> = Map::new();
+        for (i, entry) in self.history.iter().enumerate() {
+            let chain = chains.entry(entry.label.as_str()).or_default();
+            match entry.action {
+                Action::Instantiate | Action::Migrate => chain.push(i),
+                Action::Rollback => {
+                    chain.pop();
+                }
+                Action::Store => {}
+            }
+        }
+        chains
+    }
+
+    /// Whether the history entry at `index` is a release undone by a later rollback
+    pub fn is_rolled_back(&self, index: usize) -> bool {
+        match self.history.get(index) {
+            Some(entry) if matches!(entry.action, Action::Instantiate | Action::Migrate) => !self
+                .release_chains()
+                .get(entry.label.as_str())
+                .map(|chain| chain.contains(&index))
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    /// `(current, previous)` code ids of the contract with `label`, according to the release history
+    pub fn rollback_code_ids(&self, label: &str) -> Result<(u64, u64)> {
+        let chains = self.release_chains();
+        let mut releases = chains
+            .get(label)
+            .map(|chain| chain.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .rev()
+            .map(|i| self.history[*i].code_id);
+
+        match (releases.next(), releases.next()) {
+            (Some(current), Some(previous)) => Ok((current, previous)),
+            _ => bail!("No previous release recorded for label `{label}`"),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Default)]
@@ -75,6 +217,20 @@ impl State {
         })
     }
 
+    pub fn contracts(&self, network: &str) -> Map<String, WasmRef> {
+        let State(m) = self;
+        m.get(network).cloned().unwrap_or_default()
+    }
+
+    pub fn insert_ref(&self, network: &str, contract_name: &str, wasm_ref: WasmRef) -> Self {
+        let State(m) = self;
+        let mut m = m.clone();
+        m.entry(network.to_string())
+            .or_default()
+            .insert(contract_name.to_string(), wasm_ref);
+        State(m)
+    }
+
     pub fn load(path: &PathBuf) -> Result<Self> {
         let path_str = path.to_string_lossy();
         let content = fs::read_to_string(path)
@@ -97,6 +253,19 @@ impl State {
         }))
     }
 
+    /// Same as [`State::load_by_network`] but empty if nothing has been deployed yet,
+    /// a state file that exists but can not be read is still an error
+    pub fn load_by_network_if_exists(network: Network, root: PathBuf) -> Result<Self> {
+        let state_file = root.join(STATE_DIR).join(match network.network_variant() {
+            NetworkVariant::Local => STATE_FILE_LOCAL,
+            NetworkVariant::Shared => STATE_FILE_SHARED,
+        });
+        if !state_file.exists() {
+            return Ok(State::default());
+        }
+        Self::load(&state_file)
+    }
+
     pub fn update_state_file(
         network_variant: &NetworkVariant,
         root: PathBuf,
@@ -140,6 +309,20 @@ impl State {
         State(m)
     }
 
+    /// Append `entry` to the contract's history, contract must already exist in state
+    pub fn record_history(&self, network: &str, contract_name: &str, entry: HistoryEntry) -> Self {
+        let State(m) = self;
+        let mut m = m.clone();
+        if let Some(wasm_ref) = m
+            .get_mut(network)
+            .and_then(|contracts| contracts.get_mut(contract_name))
+        {
+            wasm_ref.history.push(entry);
+        }
+
+        State(m)
+    }
+
     impl_update! { fn update_code_id(.., code_id) ~ { code_id: Some(*code_id) } }
     impl_update! { fn update_proposal_store_code_id(.., id) ~ { proposal: Proposal { store_code: Some(*id) }}}
 }
@@ -462,6 +645,7 @@ mod tests {
                 WasmRef {
                     code_id: Some(1),
                     proposal: Proposal { store_code: None },
+                    history: vec![],
                     addresses: Map::from([(
                         "default".to_string(),
                         "osmo1252netaxc2c0n4g4zm428d75gkl0dplrksd32g35yfylldu66nzqjtjn85"
@@ -498,6 +682,7 @@ mod tests {
                         ),
                     ]),
                     proposal: Proposal { store_code: None },
+                    history: vec![],
                 },
             )]),
         )]));
@@ -530,10 +715,97 @@ mod tests {
                                 .to_string(),
                         ),
                     ]),
+                    history: vec![],
                 },
             )]),
         )]));
 
         assert_eq!(updated_state, state);
     }
+
+    #[test]
+    fn rollback_through_release_history() {
+        let state = State(Map::new())
+            .update_code_id("localosmosis", "counter", &1)
+            .record_history(
+                "localosmosis",
+                "counter",
+                HistoryEntry::new(Action::Instantiate, "default", 1),
+            )
+            .record_history(
+                "localosmosis",
+                "counter",
+                HistoryEntry::new(Action::Instantiate, "other", 1),
+            )
+            .record_history(
+                "localosmosis",
+                "counter",
+                HistoryEntry::new(Action::Migrate, "default", 2),
+            )
+            .record_history(
+                "localosmosis",
+                "counter",
+                HistoryEntry::new(Action::Migrate, "default", 3),
+            );
+
+        let wasm_ref = state.get_ref("localosmosis", "counter").unwrap();
+        assert_eq!(wasm_ref.rollback_code_ids("default").unwrap(), (3, 2));
+        assert!(wasm_ref.rollback_code_ids("other").is_err());
+
+        let state = state.record_history(
+            "localosmosis",
+            "counter",
+            HistoryEntry::new(Action::Rollback, "default", 2),
+        );
+        let wasm_ref = state.get_ref("localosmosis", "counter").unwrap();
+        assert_eq!(wasm_ref.rollback_code_ids("default").unwrap(), (2, 1));
+        assert_eq!(wasm_ref.history().len(), 5);
+        assert!(wasm_ref.is_rolled_back(3));
+        assert!(!wasm_ref.is_rolled_back(2));
+        assert!(!wasm_ref.is_rolled_back(4));
+
+        let state = state.record_history(
+            "localosmosis",
+            "counter",
+            HistoryEntry::new(Action::Rollback, "default", 1),
+        );
+        let wasm_ref = state.get_ref("localosmosis", "counter").unwrap();
+        assert!(wasm_ref.is_rolled_back(2));
+        assert!(wasm_ref.rollback_code_ids("default").is_err());
+    }
+
+    #[test]
+    fn load_history_entries() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let path = setup(
+            &temp,
+            r#"
+        {
+            "testnet": {
+                "counter": {
+                    "code_id": 2,
+                    "addresses": {},
+                    "proposal": {},
+                    "history": [
+                        { "action": "store", "code_id": 2, "timestamp": 1660000000, "checksum": "abcd", "git_commit": "1234" },
+                        { "action": "migrate", "label": "default", "code_id": 2, "tx_hash": "F00D" },
+                        { "action": "rollback", "label": "default", "code_id": 1 }
+                    ]
+                }
+            }
+        }
+        "#,
+        );
+
+        let state = State::load(&path).unwrap();
+        let history = state.get_ref("testnet", "counter").unwrap().history;
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].action, Action::Store);
+        assert_eq!(history[0].label, "");
+        assert_eq!(history[0].checksum.as_deref(), Some("abcd"));
+        assert_eq!(history[1].timestamp, 0);
+
+        state.save(&path).unwrap();
+        assert_eq!(State::load(&path).unwrap(), state);
+    }
 }
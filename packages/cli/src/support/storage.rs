@@ -0,0 +1,284 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+use serde_json::{Map, Value};
+
+/// Type of a key component of cw-storage-plus `Map`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyType {
+    String,
+    Bytes,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I32,
+    I64,
+}
+
+impl FromStr for KeyType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "string" | "str" | "addr" => KeyType::String,
+            "bytes" => KeyType::Bytes,
+            "u8" => KeyType::U8,
+            "u16" => KeyType::U16,
+            "u32" => KeyType::U32,
+            "u64" => KeyType::U64,
+            "u128" => KeyType::U128,
+            "i32" => KeyType::I32,
+            "i64" => KeyType::I64,
+            _ => bail!(
+                "Unknown key type `{s}`, expected one of: string, addr, bytes, u8, u16, u32, u64, u128, i32, i64"
+            ),
+        })
+    }
+}
+
+/// Spec of a storage entry, `Item` when `key_types` is empty, `Map` otherwise.
+/// Parsed from `<namespace>[:<key_type>,...]`, eg. `config` or `allowances:addr,addr`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySpec {
+    pub namespace: String,
+    pub key_types: Vec<KeyType>,
+}
+
+impl FromStr for KeySpec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (namespace, key_types) = match s.split_once(':') {
+            Some((namespace, key_types)) => (
+                namespace,
+                key_types
+                    .split(',')
+                    .map(KeyType::from_str)
+                    .collect::<Result<Vec<KeyType>>>()?,
+            ),
+            None => (s, vec![]),
+        };
+        if namespace.is_empty() {
+            bail!("Namespace of key spec `{s}` must not be empty");
+        }
+        Ok(KeySpec {
+            namespace: namespace.to_string(),
+            key_types,
+        })
+    }
+}
+
+impl KeySpec {
+    /// Decode raw key into key components if it belongs to this spec
+    fn decode_key(&self, key: &[u8]) -> Option<Vec<String>> {
+        let ns = self.namespace.as_bytes();
+        if self.key_types.is_empty() {
+            return (key == ns).then(Vec::new);
+        }
+
+        let (prefix, mut rest) = split_length_prefixed(key)?;
+        if prefix != ns {
+            return None;
+        }
+
+        let (last, init) = self.key_types.split_last()?;
+        let mut components = vec![];
+        for key_type in init {
+            let (component, r) = split_length_prefixed(rest)?;
+            components.push(decode_component(component, key_type)?);
+            rest = r;
+        }
+        components.push(decode_component(rest, last)?);
+        Some(components)
+    }
+}
+
+fn split_length_prefixed(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    if bytes.len() < 2 {
+        return None;
+    }
+    let len = u16::from_be_bytes([bytes[0], bytes[1]]) as usize;
+    let rest = &bytes[2..];
+    (rest.len() >= len).then(|| rest.split_at(len))
+}
+
+fn decode_component(bytes: &[u8], key_type: &KeyType) -> Option<String> {
+    Some(match key_type {
+        KeyType::String => String::from_utf8(bytes.to_vec()).ok()?,
+        KeyType::Bytes => to_hex(bytes),
+        KeyType::U8 => u8::from_be_bytes(bytes.try_into().ok()?).to_string(),
+        KeyType::U16 => u16::from_be_bytes(bytes.try_into().ok()?).to_string(),
+        KeyType::U32 => u32::from_be_bytes(bytes.try_into().ok()?).to_string(),
+        KeyType::U64 => u64::from_be_bytes(bytes.try_into().ok()?).to_string(),
+        KeyType::U128 => u128::from_be_bytes(bytes.try_into().ok()?).to_string(),
+        // signed integers are stored with flipped sign bit to keep ordering
+        KeyType::I32 => {
+            ((u32::from_be_bytes(bytes.try_into().ok()?) ^ (1 << 31)) as i32).to_string()
+        }
+        KeyType::I64 => {
+            ((u64::from_be_bytes(bytes.try_into().ok()?) ^ (1 << 63)) as i64).to_string()
+        }
+    })
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn printable(bytes: &[u8]) -> Option<String> {
+    String::from_utf8(bytes.to_vec())
+        .ok()
+        .filter(|s| !s.is_empty() && s.chars().all(|c| !c.is_control()))
+}
+
+/// Best effort guess of key components when no spec matched:
+/// length prefixed printable namespace is treated as `Map`, printable key as `Item`
+fn guess_key(key: &[u8]) -> Option<Vec<String>> {
+    if let Some((ns, rest)) = split_length_prefixed(key) {
+        if let (Some(ns), false) = (printable(ns), rest.is_empty()) {
+            let rest = printable(rest).unwrap_or_else(|| format!("0x{}", to_hex(rest)));
+            return Some(vec![ns, rest]);
+        }
+    }
+    printable(key).map(|k| vec![k])
+}
+
+fn decode_value(value: &[u8]) -> Value {
+    serde_json::from_slice::<Value>(value)
+        .unwrap_or_else(|_| Value::String(format!("base64:{}", base64::encode(value))))
+}
+
+fn insert_nested(root: &mut Map<String, Value>, path: &[String], value: Value) {
+    match path {
+        [] => {}
+        [last] => {
+            root.insert(last.clone(), value);
+        }
+        [first, rest @ ..] => {
+            let entry = root
+                .entry(first.clone())
+                .or_insert_with(|| Value::Object(Map::new()));
+            if !entry.is_object() {
+                *entry = Value::Object(Map::new());
+            }
+            if let Value::Object(inner) = entry {
+                insert_nested(inner, rest, value);
+            }
+        }
+    }
+}
+
+/// Render raw contract state as structured json, keyed by namespace then by map key components.
+/// Entries that match neither the given specs nor the guessing heuristic are put under `_raw` keyed by hex key.
+pub fn decode_state(entries: &[(Vec<u8>, Vec<u8>)], specs: &[KeySpec]) -> Value {
+    let mut root = Map::new();
+
+    for (key, value) in entries {
+        let value = decode_value(value);
+        let path = specs
+            .iter()
+            .find_map(|spec| {
+                spec.decode_key(key)
+                    .map(|components| [vec![spec.namespace.clone()], components].concat())
+            })
+            .or_else(|| guess_key(key))
+            .unwrap_or_else(|| vec!["_raw".to_string(), to_hex(key)]);
+
+        insert_nested(&mut root, &path, value);
+    }
+
+    Value::Object(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn map_key(ns: &str, components: &[&[u8]]) -> Vec<u8> {
+        let mut key = vec![];
+        key.extend((ns.len() as u16).to_be_bytes());
+        key.extend(ns.as_bytes());
+        let (last, init) = components.split_last().unwrap();
+        for c in init {
+            key.extend((c.len() as u16).to_be_bytes());
+            key.extend(*c);
+        }
+        key.extend(*last);
+        key
+    }
+
+    #[test]
+    fn parse_key_spec() {
+        assert_eq!(
+            "allowances:addr,u64".parse::<KeySpec>().unwrap(),
+            KeySpec {
+                namespace: "allowances".to_string(),
+                key_types: vec![KeyType::String, KeyType::U64]
+            }
+        );
+        assert!("config".parse::<KeySpec>().unwrap().key_types.is_empty());
+        assert!("balances:float".parse::<KeySpec>().is_err());
+    }
+
+    #[test]
+    fn decode_item_and_maps() {
+        let entries = vec![
+            (b"config".to_vec(), br#"{"owner":"osmo1owner"}"#.to_vec()),
+            (map_key("balances", &[b"osmo1a"]), br#""100""#.to_vec()),
+            (
+                map_key("allowances", &[b"osmo1a", b"osmo1b"]),
+                br#"{"allowance":"5"}"#.to_vec(),
+            ),
+            (
+                map_key("proposals", &[&7u64.to_be_bytes()]),
+                b"true".to_vec(),
+            ),
+            (
+                map_key("deltas", &[&(-3i64 as u64 ^ (1 << 63)).to_be_bytes()]),
+                b"1".to_vec(),
+            ),
+        ];
+
+        let specs = vec![
+            "config".parse().unwrap(),
+            "balances:addr".parse().unwrap(),
+            "allowances:addr,addr".parse().unwrap(),
+            "proposals:u64".parse().unwrap(),
+            "deltas:i64".parse().unwrap(),
+        ];
+
+        assert_eq!(
+            decode_state(&entries, &specs),
+            json!({
+                "config": { "owner": "osmo1owner" },
+                "balances": { "osmo1a": "100" },
+                "allowances": { "osmo1a": { "osmo1b": { "allowance": "5" } } },
+                "proposals": { "7": true },
+                "deltas": { "-3": 1 }
+            })
+        );
+    }
+
+    #[test]
+    fn decode_without_spec() {
+        let entries = vec![
+            (b"config".to_vec(), b"{}".to_vec()),
+            (map_key("balances", &[b"osmo1a"]), br#""100""#.to_vec()),
+            (map_key("counts", &[&1u32.to_be_bytes()]), b"2".to_vec()),
+            (vec![0xff, 0x00], vec![0x01]),
+        ];
+
+        assert_eq!(
+            decode_state(&entries, &[]),
+            json!({
+                "config": {},
+                "balances": { "osmo1a": "100" },
+                "counts": { "0x00000001": 2 },
+                "_raw": { "ff00": "base64:AQ==" }
+            })
+        );
+    }
+}
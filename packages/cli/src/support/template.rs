@@ -8,6 +8,8 @@ use getset::Getters;
 use serde::Deserialize;
 use serde::Serialize;
 use std::path::PathBuf;
+
+use super::progress::Progress;
 use std::{env, fs};
 
 #[derive(Clone, Deserialize, Serialize, Getters, new, GetDataDocs)]
@@ -16,17 +18,52 @@ pub struct Template {
     /// Name of the generated directory
     name: String,
 
-    /// Git repo url to be used as template
+    /// Template to generate from: git repo url, local path,
+    /// or name of a favorite defined in the user's cargo-generate config
     repo: String,
 
-    /// Brance of the repo to be used as template
-    branch: String,
+    /// Branch of the repo to be used as template, use default branch of the repo
+    /// (or the one set by the favorite) if not specified
+    branch: Option<String>,
 
     /// Subfolder of the repo to be used as template, use root of the repo if not specified
     subfolder: Option<String>,
 
     /// Target directory for generating code from template to take place
     target_dir: PathBuf,
+
+    /// Extra flags passed through to cargo-generate, eg. `["--define", "authors=me"]`
+    #[serde(default)]
+    #[new(default)]
+    args: Vec<String>,
+}
+
+/// How cargo-generate should locate the template
+#[derive(Debug, PartialEq, Eq)]
+enum Source<'a> {
+    Git(&'a str),
+    Path(&'a str),
+    Favorite(&'a str),
+}
+
+impl<'a> Source<'a> {
+    fn of(repo: &'a str) -> Self {
+        let is_git = repo.contains("://")
+            || repo.starts_with("git@")
+            || repo.ends_with(".git")
+            || ["gh:", "gl:", "bb:", "sr:"]
+                .iter()
+                .any(|p| repo.starts_with(p));
+        let is_path = repo.starts_with('.') || repo.starts_with('/') || repo.starts_with('~');
+
+        if is_git {
+            Source::Git(repo)
+        } else if is_path {
+            Source::Path(repo)
+        } else {
+            Source::Favorite(repo)
+        }
+    }
 }
 
 impl Template {
@@ -38,7 +75,13 @@ impl Template {
     }
     pub fn with_branch(&self, branch: Option<String>) -> Template {
         Template {
-            branch: branch.unwrap_or_else(|| self.branch.clone()),
+            branch: branch.or_else(|| self.branch.clone()),
+            ..self.clone()
+        }
+    }
+    pub fn with_args(&self, args: Vec<String>) -> Template {
+        Template {
+            args: [self.args.clone(), args].concat(),
             ..self.clone()
         }
     }
@@ -49,6 +92,23 @@ impl Template {
         }
     }
 
+    fn argv(&self) -> Vec<&str> {
+        let mut argv = vec!["cargo", "generate", "--name", &self.name];
+        match Source::of(&self.repo) {
+            Source::Git(repo) => argv.extend(["--git", repo]),
+            Source::Path(path) => argv.extend(["--path", path]),
+            Source::Favorite(favorite) => argv.push(favorite),
+        }
+        if let Some(branch) = &self.branch {
+            argv.extend(["--branch", branch]);
+        }
+        argv.extend(self.args.iter().map(String::as_str));
+        if let Some(subfolder) = &self.subfolder {
+            argv.extend(["--", subfolder]);
+        }
+        argv
+    }
+
     pub fn generate(&self) -> Result<()> {
         let target_dir_display = self.target_dir.display();
         let current_dir = env::current_dir().with_context(|| "Unable to get current directory.")?;
@@ -58,34 +118,71 @@ impl Template {
             format!("Unable to set current directory to {target_dir_display}`.")
         })?;
 
-        let argv = vec![
-            "cargo",
-            "generate",
-            "--name",
-            &self.name,
-            "--git",
-            &self.repo,
-            "--branch",
-            &self.branch,
-        ];
-
-        let argv = if let Some(subfolder) = &self.subfolder {
-            [argv, vec!["--", subfolder]].concat()
-        } else {
-            argv
-        };
-
-        let CargoGen::Generate(args) = CargoGen::parse_from(argv.iter());
+        let CargoGen::Generate(args) = CargoGen::try_parse_from(self.argv().iter())
+            .with_context(|| "Invalid cargo-generate arguments")?;
 
         let name = &self.name;
-        let repo = &self.repo;
-        let branch = &self.branch;
+        let template = match &self.branch {
+            Some(branch) => format!("{}:{branch}", self.repo),
+            None => self.repo.clone(),
+        };
 
-        cargo_generate(args)
-        .with_context(|| format!("Unable to generate contract `{name}` with template `{repo}:{branch}` to `{target_dir_display}`."))?;
+        Progress::stream(&format!("Generating `{name}` from template `{template}`"))
+        .end_with(cargo_generate(args))
+        .with_context(|| format!("Unable to generate contract `{name}` with template `{template}` to `{target_dir_display}`."))?;
 
         env::set_current_dir(current_dir.as_path()).with_context(|| {
             format!("Unable to set current directory back to current directory after changed to `{target_dir_display}`.")
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_template_source() {
+        assert_eq!(
+            Source::of("https://github.com/osmosis-labs/cw-minimal-template"),
+            Source::Git("https://github.com/osmosis-labs/cw-minimal-template")
+        );
+        assert_eq!(Source::of("gh:org/repo"), Source::Git("gh:org/repo"));
+        assert_eq!(
+            Source::of("git@github.com:org/repo.git"),
+            Source::Git("git@github.com:org/repo.git")
+        );
+        assert_eq!(
+            Source::of("./templates/counter"),
+            Source::Path("./templates/counter")
+        );
+        assert_eq!(Source::of("cw-counter"), Source::Favorite("cw-counter"));
+    }
+
+    #[test]
+    fn pass_through_generator_args() {
+        let template = Template::new(
+            "counter".to_string(),
+            "cw-counter".to_string(),
+            None,
+            Some("contracts".to_string()),
+            PathBuf::from("."),
+        )
+        .with_args(vec!["--define".to_string(), "minimal=true".to_string()]);
+
+        assert_eq!(
+            template.argv(),
+            vec![
+                "cargo",
+                "generate",
+                "--name",
+                "counter",
+                "cw-counter",
+                "--define",
+                "minimal=true",
+                "--",
+                "contracts"
+            ]
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! Spacing requests and transactions sent to a network by its `rate_limit` and `tx_delay`
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use tokio::time::Instant;
+
+use crate::framework::config::Network;
+
+/// Earliest time the next request may be sent, by endpoint
+static NEXT_REQUEST: Lazy<Mutex<HashMap<String, Instant>>> = Lazy::new(Default::default);
+
+/// Time of the last broadcasted transaction, by chain id
+static LAST_TX: Lazy<Mutex<HashMap<String, Instant>>> = Lazy::new(Default::default);
+
+/// Reserve the next free slot of `endpoint`, `interval` apart from the previous one, returning when it starts
+fn reserve(
+    slots: &mut HashMap<String, Instant>,
+    endpoint: &str,
+    interval: Duration,
+    now: Instant,
+) -> Instant {
+    let slot = slots
+        .get(endpoint)
+        .copied()
+        .filter(|next| *next > now)
+        .unwrap_or(now);
+    slots.insert(endpoint.to_string(), slot + interval);
+    slot
+}
+
+/// Wait until a request to `endpoint` of `network` is within the network's `rate_limit`
+pub async fn request(network: &Network, endpoint: &str) {
+    let rate = match network.rate_limit() {
+        Some(rate) if *rate > 0.0 => *rate,
+        _ => return,
+    };
+    let slot = reserve(
+        &mut NEXT_REQUEST.lock().unwrap(),
+        endpoint,
+        Duration::from_secs_f64(1.0 / rate),
+        Instant::now(),
+    );
+    tokio::time::sleep_until(slot).await;
+}
+
+/// Wait until `tx_delay` has passed since the last transaction broadcasted to `network`
+pub async fn tx(network: &Network) {
+    let delay = Duration::from_millis(*network.tx_delay());
+    if delay.is_zero() {
+        return;
+    }
+    let last = LAST_TX.lock().unwrap().get(network.chain_id()).copied();
+    if let Some(last) = last {
+        tokio::time::sleep_until(last + delay).await;
+    }
+    LAST_TX
+        .lock()
+        .unwrap()
+        .insert(network.chain_id().to_string(), Instant::now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserve_slots_by_interval() {
+        let mut slots = HashMap::new();
+        let now = Instant::now();
+        let interval = Duration::from_millis(500);
+
+        assert_eq!(reserve(&mut slots, "grpc", interval, now), now);
+        assert_eq!(reserve(&mut slots, "grpc", interval, now), now + interval);
+        assert_eq!(
+            reserve(&mut slots, "grpc", interval, now),
+            now + interval * 2
+        );
+        assert_eq!(reserve(&mut slots, "rpc", interval, now), now);
+
+        // a slot that passed is not waited for
+        let later = now + interval * 10;
+        assert_eq!(reserve(&mut slots, "grpc", interval, later), later);
+    }
+}
@@ -0,0 +1,182 @@
+use std::future::Future;
+use std::time::Duration;
+use std::{env, fs, io};
+
+use anyhow::{anyhow, bail, Context, Result};
+use cosmrs::rpc::HttpClient;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Uri};
+
+use crate::framework::config::Network;
+use crate::support::error::{Error, ErrorKind};
+
+/// Proxy to reach `endpoint` through: the network's `proxy` if configured, otherwise
+/// `HTTPS_PROXY` / `HTTP_PROXY` by scheme of the endpoint unless the host is excluded by `NO_PROXY`
+pub fn proxy_for(network: &Network, endpoint: &str) -> Option<String> {
+    if let Some(proxy) = network.proxy() {
+        return Some(proxy.clone());
+    }
+
+    let uri = endpoint.parse::<Uri>().ok()?;
+    let no_proxy = env_var(&["NO_PROXY", "no_proxy"]).unwrap_or_default();
+    if is_excluded(uri.host()?, &no_proxy) {
+        return None;
+    }
+    match uri.scheme_str() {
+        Some("https") => env_var(&["HTTPS_PROXY", "https_proxy"]),
+        _ => env_var(&["HTTP_PROXY", "http_proxy"]),
+    }
+}
+
+fn env_var(names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .find_map(|n| env::var(n).ok())
+        .filter(|v| !v.is_empty())
+}
+
+/// `NO_PROXY` is a comma separated list of hosts, matching the host itself and its subdomains, or `*`
+fn is_excluded(host: &str, no_proxy: &str) -> bool {
+    no_proxy
+        .split(',')
+        .map(|h| h.trim().trim_start_matches('.'))
+        .filter(|h| !h.is_empty())
+        .any(|h| h == "*" || host == h || host.ends_with(&format!(".{h}")))
+}
+
+/// Tendermint RPC client of the network, through proxy if any.
+/// Custom CA certificate is not supported by the RPC client, only webpki roots are trusted,
+/// which is why `ca_cert` with an https rpc endpoint is rejected when loading config.
+pub fn rpc_client(network: &Network) -> Result<HttpClient> {
+    let endpoint = network.rpc_endpoint().as_str();
+    match proxy_for(network, endpoint) {
+        Some(proxy) => HttpClient::new_with_proxy(endpoint, proxy.as_str())
+            .with_context(|| format!("Unable to create rpc client for {endpoint} via {proxy}")),
+        None => HttpClient::new(endpoint)
+            .with_context(|| format!("Unable to create rpc client for {endpoint}")),
+    }
+}
+
+/// Wait for rpc `request` for at most the network's `request_timeout`, the rpc client has no timeout of its own
+pub async fn rpc_request<T, E>(
+    network: &Network,
+    request: impl Future<Output = std::result::Result<T, E>>,
+) -> Result<T>
+where
+    E: Into<anyhow::Error>,
+{
+    let timeout = Duration::from_secs(*network.request_timeout());
+    match tokio::time::timeout(timeout, request).await {
+        Ok(res) => res.map_err(Into::into),
+        Err(_) => Err(anyhow!(Error::new(
+            ErrorKind::Timeout,
+            format!(
+                "{} did not respond within {timeout:?}",
+                network.rpc_endpoint()
+            ),
+        ))),
+    }
+}
+
+/// gRPC channel of the network, trusting the network's `ca_cert` in addition to native roots
+/// and tunneled through proxy if any
+pub async fn grpc_channel(network: &Network) -> Result<Channel> {
+    let endpoint = network.grpc_endpoint().as_str();
+    let mut channel = Channel::from_shared(endpoint.to_string())?
+        .connect_timeout(Duration::from_secs(*network.connect_timeout()))
+        .timeout(Duration::from_secs(*network.request_timeout()));
+
+    if endpoint.starts_with("https") {
+        let mut tls = ClientTlsConfig::new();
+        if let Some(ca_cert) = network.ca_cert() {
+            let pem = fs::read(ca_cert)
+                .with_context(|| format!("Unable to read CA certificate `{ca_cert}`"))?;
+            tls = tls.ca_certificate(Certificate::from_pem(pem));
+        }
+        channel = channel.tls_config(tls)?;
+    }
+
+    connect(channel, proxy_for(network, endpoint))
+        .await
+        .with_context(|| format!("Unable to connect to {endpoint}"))
+}
+
+async fn connect(channel: Endpoint, proxy: Option<String>) -> Result<Channel> {
+    match proxy {
+        None => Ok(channel.connect().await?),
+        Some(proxy) => {
+            let proxy = proxy.parse::<Uri>()?;
+            if proxy.scheme_str() != Some("http") {
+                bail!("Only `http://` proxies are supported for gRPC, got `{proxy}`");
+            }
+            let connector = tower::service_fn(move |target: Uri| tunnel(proxy.clone(), target));
+            Ok(channel.connect_with_connector(connector).await?)
+        }
+    }
+}
+
+/// Open a TCP tunnel to `target` through `proxy` with HTTP `CONNECT`
+async fn tunnel(proxy: Uri, target: Uri) -> io::Result<TcpStream> {
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+    let proxy_host = proxy
+        .host()
+        .ok_or_else(|| invalid(format!("Proxy `{proxy}` has no host")))?;
+    let target_host = target
+        .host()
+        .ok_or_else(|| invalid(format!("Endpoint `{target}` has no host")))?;
+    let target_port = target
+        .port_u16()
+        .unwrap_or(if target.scheme_str() == Some("https") {
+            443
+        } else {
+            80
+        });
+    let authority = format!("{target_host}:{target_port}");
+
+    let mut stream = TcpStream::connect((proxy_host, proxy.port_u16().unwrap_or(80))).await?;
+    stream
+        .write_all(format!("CONNECT {authority} HTTP/1.1\r\nHost: {authority}\r\n\r\n").as_bytes())
+        .await?;
+
+    let mut response = vec![];
+    let mut byte = [0u8; 1];
+    while !response.ends_with(b"\r\n\r\n") {
+        if stream.read(&mut byte).await? == 0 || response.len() > 8 * 1024 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("Proxy `{proxy}` closed the tunnel to {authority}"),
+            ));
+        }
+        response.push(byte[0]);
+    }
+
+    let status_line = String::from_utf8_lossy(&response);
+    let status_line = status_line.lines().next().unwrap_or_default();
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        return Err(io::Error::new(
+            io::ErrorKind::ConnectionRefused,
+            format!("Proxy `{proxy}` refused tunnel to {authority}: {status_line}"),
+        ));
+    }
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exclude_hosts_from_proxy() {
+        let no_proxy = "localhost, .internal.corp,10.0.0.1";
+
+        assert!(is_excluded("localhost", no_proxy));
+        assert!(is_excluded("grpc.internal.corp", no_proxy));
+        assert!(is_excluded("internal.corp", no_proxy));
+        assert!(is_excluded("10.0.0.1", no_proxy));
+        assert!(!is_excluded("grpc.osmosis.zone", no_proxy));
+        assert!(!is_excluded("notinternal.corp", no_proxy));
+        assert!(is_excluded("grpc.osmosis.zone", "*"));
+        assert!(!is_excluded("localhost", ""));
+    }
+}
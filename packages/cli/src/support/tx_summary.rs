@@ -0,0 +1,121 @@
+use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+    MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract, MsgStoreCode,
+    MsgUpdateAdmin,
+};
+use cosmrs::{tx::Fee, Any};
+use prost::Message;
+
+use super::coin::display_coin;
+
+const MAX_MSG_LEN: usize = 80;
+
+/// Short human readable description of a message, showing its type and key fields if the type is known.
+pub fn summarize_msg(msg: &Any) -> String {
+    let type_name = msg.type_url.rsplit('.').next().unwrap_or(&msg.type_url);
+    let fields = match msg.type_url.as_str() {
+        "/cosmwasm.wasm.v1.MsgStoreCode" => MsgStoreCode::decode(msg.value.as_slice())
+            .ok()
+            .map(|m| vec![format!("wasm_size: {} bytes", m.wasm_byte_code.len())]),
+        "/cosmwasm.wasm.v1.MsgInstantiateContract" => {
+            MsgInstantiateContract::decode(msg.value.as_slice())
+                .ok()
+                .map(|m| {
+                    vec![
+                        format!("code_id: {}", m.code_id),
+                        format!("label: {}", m.label),
+                        format!("admin: {}", if m.admin.is_empty() { "–" } else { &m.admin }),
+                        format!("msg: {}", truncate(&String::from_utf8_lossy(&m.msg))),
+                    ]
+                })
+        }
+        "/cosmwasm.wasm.v1.MsgExecuteContract" => MsgExecuteContract::decode(msg.value.as_slice())
+            .ok()
+            .map(|m| {
+                vec![
+                    format!("contract: {}", m.contract),
+                    format!("msg: {}", truncate(&String::from_utf8_lossy(&m.msg))),
+                ]
+            }),
+        "/cosmwasm.wasm.v1.MsgMigrateContract" => MsgMigrateContract::decode(msg.value.as_slice())
+            .ok()
+            .map(|m| {
+                vec![
+                    format!("contract: {}", m.contract),
+                    format!("code_id: {}", m.code_id),
+                    format!("msg: {}", truncate(&String::from_utf8_lossy(&m.msg))),
+                ]
+            }),
+        "/cosmwasm.wasm.v1.MsgUpdateAdmin" => {
+            MsgUpdateAdmin::decode(msg.value.as_slice()).ok().map(|m| {
+                vec![
+                    format!("contract: {}", m.contract),
+                    format!("new_admin: {}", m.new_admin),
+                ]
+            })
+        }
+        "/cosmwasm.wasm.v1.MsgClearAdmin" => MsgClearAdmin::decode(msg.value.as_slice())
+            .ok()
+            .map(|m| vec![format!("contract: {}", m.contract)]),
+        _ => None,
+    };
+
+    match fields {
+        Some(fields) => format!("{type_name} {{ {} }}", fields.join(", ")),
+        None => type_name.to_string(),
+    }
+}
+
+pub fn summarize_fee(fee: &Fee) -> String {
+    let gas_limit: u64 = fee.gas_limit.into();
+    let amount = fee
+        .amount
+        .iter()
+        .map(|c| display_coin(&c.amount.to_string(), c.denom.as_ref()))
+        .collect::<Vec<String>>()
+        .join(", ");
+    format!("{amount} (gas_limit: {gas_limit})")
+}
+
+fn truncate(s: &str) -> String {
+    if s.chars().count() > MAX_MSG_LEN {
+        format!("{}…", s.chars().take(MAX_MSG_LEN).collect::<String>())
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::support::proto::MessageExt;
+
+    #[test]
+    fn summarize_known_msg() {
+        let msg = Any {
+            type_url: "/cosmwasm.wasm.v1.MsgExecuteContract".to_owned(),
+            value: MsgExecuteContract {
+                sender: "osmo1sender".to_string(),
+                contract: "osmo1contract".to_string(),
+                msg: br#"{"increment":{}}"#.to_vec(),
+                funds: vec![],
+            }
+            .to_bytes()
+            .unwrap(),
+        };
+
+        assert_eq!(
+            summarize_msg(&msg),
+            r#"MsgExecuteContract { contract: osmo1contract, msg: {"increment":{}} }"#
+        );
+    }
+
+    #[test]
+    fn summarize_unknown_msg() {
+        let msg = Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_owned(),
+            value: vec![],
+        };
+
+        assert_eq!(summarize_msg(&msg), "MsgSend");
+    }
+}
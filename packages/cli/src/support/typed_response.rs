@@ -0,0 +1,387 @@
+//! Rendering of query responses by the contract's response schema, showing amounts with digit grouping,
+//! timestamps as dates, addresses with their known names and binary fields decoded.
+
+use std::path::Path;
+
+use anyhow::Result;
+use config::Map;
+use cosmrs::tendermint::Time;
+use serde_json::Value;
+
+use super::schema::find_schema;
+
+const UINT_TYPES: [&str; 8] = [
+    "Uint64", "Uint128", "Uint256", "Uint512", "Int64", "Int128", "Int256", "Int512",
+];
+
+/// Name of the query variant of `msg`, eg. `get_count` of `{ "get_count": {} }`
+fn query_variant(msg: &Value) -> Option<&str> {
+    match msg {
+        Value::Object(map) if map.len() == 1 => map.keys().next().map(String::as_str),
+        Value::String(s) => Some(s),
+        _ => None,
+    }
+}
+
+/// Schema of the response to `query_msg`, from `responses` of the combined schema
+/// or `schema/raw/response_to_<query>.json` generated by cosmwasm-schema
+pub fn response_schema(contract_dir: &Path, query_msg: &Value) -> Result<Option<Value>> {
+    let variant = match query_variant(query_msg) {
+        Some(variant) => variant,
+        None => return Ok(None),
+    };
+    if let Some(schema) = find_schema(contract_dir, "responses")?
+        .as_ref()
+        .and_then(|responses| responses.get(variant))
+    {
+        return Ok(Some(schema.clone()));
+    }
+    let per_response = contract_dir
+        .join("schema")
+        .join("raw")
+        .join(format!("response_to_{variant}.json"));
+    Ok(if per_response.exists() {
+        Some(serde_json::from_str(&std::fs::read_to_string(
+            per_response,
+        )?)?)
+    } else {
+        None
+    })
+}
+
+/// Group digits of an integer string by thousands, eg. `1,000,000`
+fn group_digits(digits: &str) -> String {
+    let (sign, digits) = match digits.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", digits),
+    };
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    format!("{sign}{grouped}")
+}
+
+/// RFC 3339 date of cosmwasm `Timestamp`, nanoseconds since epoch as string
+fn display_timestamp(nanos: &str) -> Option<String> {
+    let nanos = nanos.parse::<u64>().ok()?;
+    Time::from_unix_timestamp(
+        (nanos / 1_000_000_000) as i64,
+        (nanos % 1_000_000_000) as u32,
+    )
+    .ok()
+    .map(|t| t.to_rfc3339())
+}
+
+pub struct TypedRenderer<'a> {
+    /// Response schema, `$ref`s are resolved against its `definitions`
+    schema: &'a Value,
+    /// Known names of addresses, eg. `counter:default` or `@treasury`
+    names: &'a Map<String, String>,
+}
+
+impl<'a> TypedRenderer<'a> {
+    pub fn new(schema: &'a Value, names: &'a Map<String, String>) -> Self {
+        TypedRenderer { schema, names }
+    }
+
+    /// Name of the first definition `schema` refers to and its content, following `$ref` and single element `allOf`.
+    /// eg. `Timestamp` defined as `Uint64` is named `Timestamp`
+    fn resolve(&self, mut schema: &'a Value) -> (Option<&'a str>, &'a Value) {
+        let mut name = None;
+        loop {
+            if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+                let definition = reference.rsplit('/').next().unwrap_or(reference);
+                match self
+                    .schema
+                    .get("definitions")
+                    .and_then(|d| d.get(definition))
+                {
+                    Some(resolved) => {
+                        name = name.or(Some(definition));
+                        schema = resolved;
+                        continue;
+                    }
+                    None => return (name.or(Some(definition)), schema),
+                }
+            }
+            match schema.get("allOf").and_then(Value::as_array) {
+                Some(all_of) if all_of.len() == 1 => schema = &all_of[0],
+                _ => return (name, schema),
+            }
+        }
+    }
+
+    /// Branch of `oneOf` / `anyOf` describing `value`
+    fn branch(&self, schema: &'a Value, value: &Value) -> Option<&'a Value> {
+        let branches = ["oneOf", "anyOf"]
+            .iter()
+            .filter_map(|k| schema.get(*k).and_then(Value::as_array))
+            .flatten();
+        let mut fallback = None;
+        for branch in branches {
+            let (_, resolved) = self.resolve(branch);
+            let is_null = resolved.get("type").and_then(Value::as_str) == Some("null");
+            match value {
+                Value::Null if is_null => return Some(branch),
+                Value::Object(map) => {
+                    let key = map.keys().next();
+                    let properties = resolved.get("properties").and_then(Value::as_object);
+                    if let (Some(key), Some(properties)) = (key, properties) {
+                        if map.len() == 1 && properties.contains_key(key) {
+                            return Some(branch);
+                        }
+                    }
+                }
+                Value::String(s) => {
+                    let enums = resolved.get("enum").and_then(Value::as_array);
+                    if enums.map(|e| e.contains(&Value::String(s.clone()))) == Some(true) {
+                        return Some(branch);
+                    }
+                }
+                _ => {}
+            }
+            if !is_null {
+                fallback = fallback.or(Some(branch));
+            }
+        }
+        fallback
+    }
+
+    /// `value` with typed fields replaced by their display strings
+    pub fn decode(&self, value: &Value) -> Value {
+        self.decode_with(value, self.schema)
+    }
+
+    fn decode_with(&self, value: &Value, schema: &'a Value) -> Value {
+        let (name, schema) = self.resolve(schema);
+        match (name, value) {
+            (Some(n), Value::String(s)) if UINT_TYPES.contains(&n) => {
+                return Value::String(group_digits(s))
+            }
+            (Some("Timestamp"), Value::String(s)) => {
+                if let Some(time) = display_timestamp(s) {
+                    return Value::String(time);
+                }
+            }
+            (Some("Addr"), Value::String(s)) => {
+                return Value::String(match self.names.get(s) {
+                    Some(known) => format!("{s} ({known})"),
+                    None => s.clone(),
+                })
+            }
+            (Some("Binary"), Value::String(s)) => {
+                if let Ok(bytes) = base64::decode(s) {
+                    return match serde_json::from_slice::<Value>(&bytes) {
+                        Ok(json) => json,
+                        Err(_) => Value::String(format!("0x{}", hex::encode(bytes))),
+                    };
+                }
+            }
+            (Some("Coin"), Value::Object(coin)) => {
+                if let (Some(Value::String(amount)), Some(Value::String(denom))) =
+                    (coin.get("amount"), coin.get("denom"))
+                {
+                    return Value::String(format!("{} {denom}", group_digits(amount)));
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(branch) = self.branch(schema, value) {
+            return self.decode_with(value, branch);
+        }
+        match value {
+            Value::Object(map) => {
+                let properties = schema.get("properties");
+                Value::Object(
+                    map.iter()
+                        .map(|(k, v)| {
+                            let decoded = match properties.and_then(|p| p.get(k)) {
+                                Some(property) => self.decode_with(v, property),
+                                None => v.clone(),
+                            };
+                            (k.clone(), decoded)
+                        })
+                        .collect(),
+                )
+            }
+            Value::Array(items) => {
+                let item_schema = |i: usize| match schema.get("items") {
+                    Some(Value::Array(tuple)) => tuple.get(i),
+                    items => items,
+                };
+                Value::Array(
+                    items
+                        .iter()
+                        .enumerate()
+                        .map(|(i, v)| match item_schema(i) {
+                            Some(s) => self.decode_with(v, s),
+                            None => v.clone(),
+                        })
+                        .collect(),
+                )
+            }
+            _ => value.clone(),
+        }
+    }
+}
+
+fn display_scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Object(_) => "{}".to_string(),
+        Value::Array(_) => "[]".to_string(),
+        v => v.to_string(),
+    }
+}
+
+fn is_nested(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => !map.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        _ => false,
+    }
+}
+
+/// Indented `key: value` tree of decoded value, strings unquoted
+pub fn render_tree(value: &Value) -> String {
+    fn lines(value: &Value, indent: usize, out: &mut Vec<String>) {
+        let pad = " ".repeat(indent);
+        match value {
+            Value::Object(map) => {
+                for (k, v) in map {
+                    if is_nested(v) {
+                        out.push(format!("{pad}{k}:"));
+                        lines(v, indent + 2, out);
+                    } else {
+                        out.push(format!("{pad}{k}: {}", display_scalar(v)));
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    if is_nested(item) {
+                        out.push(format!("{pad}-"));
+                        lines(item, indent + 2, out);
+                    } else {
+                        out.push(format!("{pad}- {}", display_scalar(item)));
+                    }
+                }
+            }
+            v => out.push(format!("{pad}{}", display_scalar(v))),
+        }
+    }
+    let mut out = vec![];
+    lines(value, 0, &mut out);
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "title": "StatusResponse",
+            "type": "object",
+            "required": ["owner", "balance", "expires", "total", "data"],
+            "properties": {
+                "owner": { "$ref": "#/definitions/Addr" },
+                "balance": { "$ref": "#/definitions/Coin" },
+                "total": { "$ref": "#/definitions/Uint128" },
+                "expires": {
+                    "anyOf": [{ "$ref": "#/definitions/Expiration" }, { "type": "null" }]
+                },
+                "data": { "allOf": [{ "$ref": "#/definitions/Binary" }] },
+                "history": { "type": "array", "items": { "$ref": "#/definitions/Uint128" } }
+            },
+            "definitions": {
+                "Addr": { "type": "string" },
+                "Binary": { "type": "string" },
+                "Coin": {
+                    "type": "object",
+                    "properties": {
+                        "amount": { "$ref": "#/definitions/Uint128" },
+                        "denom": { "type": "string" }
+                    }
+                },
+                "Expiration": {
+                    "oneOf": [
+                        {
+                            "type": "object",
+                            "required": ["at_height"],
+                            "properties": { "at_height": { "type": "integer" } }
+                        },
+                        {
+                            "type": "object",
+                            "required": ["at_time"],
+                            "properties": { "at_time": { "$ref": "#/definitions/Timestamp" } }
+                        },
+                        { "type": "string", "enum": ["never"] }
+                    ]
+                },
+                "Timestamp": { "$ref": "#/definitions/Uint64" },
+                "Uint64": { "type": "string" },
+                "Uint128": { "type": "string" }
+            }
+        })
+    }
+
+    #[test]
+    fn decode_by_schema() {
+        let schema = schema();
+        let names = Map::from([("osmo1owner".to_string(), "@treasury".to_string())]);
+        let renderer = TypedRenderer::new(&schema, &names);
+
+        let decoded = renderer.decode(&json!({
+            "owner": "osmo1owner",
+            "balance": { "amount": "1500000", "denom": "uosmo" },
+            "total": "1234567890123456789012",
+            "expires": { "at_time": "1672531200000000000" },
+            "data": base64::encode(br#"{"count":1}"#),
+            "history": ["1000", "-42"]
+        }));
+        assert_eq!(
+            decoded,
+            json!({
+                "owner": "osmo1owner (@treasury)",
+                "balance": "1,500,000 uosmo",
+                "total": "1,234,567,890,123,456,789,012",
+                "expires": { "at_time": "2023-01-01T00:00:00Z" },
+                "data": { "count": 1 },
+                "history": ["1,000", "-42"]
+            })
+        );
+
+        let never = renderer.decode(&json!({ "expires": "never", "data": "AAE=" }));
+        assert_eq!(never, json!({ "expires": "never", "data": "0x0001" }));
+    }
+
+    #[test]
+    fn render_nested_tree() {
+        let rendered = render_tree(&json!({
+            "config": { "limits": [], "paused": false },
+            "count": "1,000",
+            "owners": ["a", "b"]
+        }));
+        assert_eq!(
+            rendered,
+            "config:\n  limits: []\n  paused: false\ncount: 1,000\nowners:\n  - a\n  - b"
+        );
+    }
+
+    #[test]
+    fn find_query_variant() {
+        assert_eq!(
+            query_variant(&json!({ "get_count": {} })),
+            Some("get_count")
+        );
+        assert_eq!(query_variant(&json!("config")), Some("config"));
+        assert_eq!(query_variant(&json!({})), None);
+    }
+}
@@ -1,27 +1,63 @@
 use std::{
+    env,
     fs::File,
     io::{BufReader, Read},
-    path::PathBuf,
+    path::{Path, PathBuf},
+    process::Command,
 };
 
 use anyhow::Context;
 use cosmrs::tendermint::abci::Event;
-use tendermint_rpc::{endpoint::block_results, Client, HttpClient, Order};
+use sha2::{Digest, Sha256};
+use tendermint_rpc::{endpoint::block_results, Client, Order};
+
+use super::transport;
+use crate::framework::config::Network;
+
+/// Cargo target directory of the workspace, `CARGO_TARGET_DIR` if set (eg. shared across contracts)
+pub fn target_dir(root: &Path) -> PathBuf {
+    env::var_os("CARGO_TARGET_DIR")
+        .map(|dir| root.join(dir))
+        .unwrap_or_else(|| root.join("target"))
+}
+
+/// Directory containing built wasm, optimized artifacts unless `no_wasm_opt` is set.
+pub fn wasm_dir(root: &Path, no_wasm_opt: &bool) -> PathBuf {
+    if *no_wasm_opt {
+        target_dir(root).join("wasm32-unknown-unknown/release")
+    } else {
+        root.join("artifacts")
+    }
+}
+
+/// Where the wasm to store comes from
+pub enum WasmSource<'a> {
+    /// Built artifact of the contract in the workspace
+    Artifact,
+    /// Downloaded from `url`, only stored if its sha256 matches `checksum`
+    Url { url: &'a str, checksum: &'a str },
+    /// Code fetched from another chain, built from `git_commit` if known
+    Code {
+        wasm: Vec<u8>,
+        git_commit: Option<String>,
+    },
+}
+
+impl<'a> From<Option<(&'a str, &'a str)>> for WasmSource<'a> {
+    fn from(from_url: Option<(&'a str, &'a str)>) -> Self {
+        match from_url {
+            Some((url, checksum)) => WasmSource::Url { url, checksum },
+            None => WasmSource::Artifact,
+        }
+    }
+}
 
 pub fn read_wasm(
     root: PathBuf,
     contract_name: &str,
     no_wasm_opt: &bool,
 ) -> Result<Vec<u8>, anyhow::Error> {
-    let wasm_path = if *no_wasm_opt {
-        root.as_path()
-            .join("target/wasm32-unknown-unknown/release")
-            .join(format!("{contract_name}.wasm"))
-    } else {
-        root.as_path()
-            .join("artifacts")
-            .join(format!("{contract_name}.wasm"))
-    };
+    let wasm_path = wasm_dir(&root, no_wasm_opt).join(format!("{contract_name}.wasm"));
 
     let wasm_path_str = &wasm_path.as_os_str().to_string_lossy();
     let f = File::open(&wasm_path).with_context(|| {
@@ -35,19 +71,55 @@ pub fn read_wasm(
     Ok(wasm)
 }
 
-pub async fn get_code_id(rpc_endpoint: &str, proposal_id: &u64) -> Result<String, anyhow::Error> {
-    let client = HttpClient::new(rpc_endpoint)?;
-    let blocks_response = client
-        .block_search(
+/// Hex encoded sha256 of wasm byte code, same as checksum reported by `wasmd`
+pub fn checksum(wasm: &[u8]) -> String {
+    hex::encode(Sha256::digest(wasm))
+}
+
+/// Download wasm from `url` with the installed `curl`, only returned if its checksum matches `expected_checksum`
+pub fn download_wasm(url: &str, expected_checksum: &str) -> Result<Vec<u8>, anyhow::Error> {
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location", url])
+        .output()
+        .with_context(|| "Unable to run `curl`, make sure it is installed")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Unable to download `{url}`: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    verify_checksum(&output.stdout, expected_checksum)
+        .with_context(|| format!("Downloaded wasm from `{url}` is rejected"))?;
+    Ok(output.stdout)
+}
+
+fn verify_checksum(wasm: &[u8], expected_checksum: &str) -> Result<(), anyhow::Error> {
+    let actual = checksum(wasm);
+    if !actual.eq_ignore_ascii_case(expected_checksum.trim()) {
+        anyhow::bail!("checksum mismatch, expected {expected_checksum}, got {actual}");
+    }
+    if !wasm.starts_with(b"\0asm") {
+        anyhow::bail!("content is not a wasm binary");
+    }
+    Ok(())
+}
+
+pub async fn get_code_id(network: &Network, proposal_id: &u64) -> Result<String, anyhow::Error> {
+    let client = transport::rpc_client(network)?;
+    let blocks_response = transport::rpc_request(
+        network,
+        client.block_search(
             format!("active_proposal.proposal_id = {}", proposal_id)
                 .parse()
                 .unwrap(),
             1,
             1,
             Order::Descending,
-        )
-        .await?
-        .blocks;
+        ),
+    )
+    .await?
+    .blocks;
 
     let proposal_passed_block_height = blocks_response
         .first()
@@ -61,7 +133,7 @@ pub async fn get_code_id(rpc_endpoint: &str, proposal_id: &u64) -> Result<String
         .height;
 
     let block_results: block_results::Response =
-        client.block_results(proposal_passed_block_height).await?;
+        transport::rpc_request(network, client.block_results(proposal_passed_block_height)).await?;
 
     extract_code_id_for_proposal(
         proposal_id,
@@ -110,6 +182,17 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn verify_downloaded_wasm() {
+        let wasm = b"\0asm\x01\0\0\0".to_vec();
+        let expected = checksum(&wasm);
+
+        assert!(verify_checksum(&wasm, &expected).is_ok());
+        assert!(verify_checksum(&wasm, &expected.to_uppercase()).is_ok());
+        assert!(verify_checksum(&wasm, &checksum(b"other")).is_err());
+        assert!(verify_checksum(b"<html>", &checksum(b"<html>")).is_err());
+    }
+
     #[test]
     fn extract_code_id_from_single_proposal_exec_on_the_block() {
         let code_id = extract_code_id_for_proposal(
@@ -0,0 +1,355 @@
+use anyhow::{bail, Context, Result};
+
+const WASM_MAGIC: &[u8] = b"\0asm";
+
+/// Section of wasm module, custom sections are named `custom:<name>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub name: String,
+    pub content: Vec<u8>,
+}
+
+/// Summary of wasm module's structure, enough for comparing two builds of a contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmInfo {
+    pub size: usize,
+    /// Exported item as `<kind> <name>`, eg. `func instantiate`
+    pub exports: Vec<String>,
+    /// Imported item as `<module>.<name>`, eg. `env.db_read`
+    pub imports: Vec<String>,
+    pub sections: Vec<Section>,
+}
+
+impl WasmInfo {
+    /// Names of exported functions, which include the contract's entry points
+    pub fn exported_functions(&self) -> Vec<String> {
+        self.exports
+            .iter()
+            .filter_map(|e| e.strip_prefix("func "))
+            .map(|f| f.to_string())
+            .collect()
+    }
+
+    /// Payload of custom section `name`, without its name
+    pub fn custom_section(&self, name: &str) -> Option<&[u8]> {
+        let section = self
+            .sections
+            .iter()
+            .find(|s| s.name == format!("custom:{name}"))?;
+        let mut r = Reader::new(&section.content);
+        r.name().ok()?;
+        Some(&section.content[r.pos..])
+    }
+
+    /// CosmWasm capabilities required by the contract, declared by exporting `requires_<capability>`
+    pub fn capabilities(&self) -> Vec<String> {
+        self.exports
+            .iter()
+            .filter_map(|e| e.strip_prefix("func requires_"))
+            .map(|c| c.to_string())
+            .collect()
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, pos: 0 }
+    }
+
+    fn eof(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        let b = *self
+            .bytes
+            .get(self.pos)
+            .with_context(|| "Unexpected end of wasm")?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.pos + len;
+        if end > self.bytes.len() {
+            bail!("Unexpected end of wasm");
+        }
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn leb_u32(&mut self) -> Result<u32> {
+        let mut result: u32 = 0;
+        for shift in (0..35).step_by(7) {
+            let b = self.byte()?;
+            result |= ((b & 0x7f) as u32) << shift;
+            if b & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+        bail!("Invalid LEB128 integer in wasm")
+    }
+
+    fn name(&mut self) -> Result<String> {
+        let len = self.leb_u32()? as usize;
+        Ok(String::from_utf8_lossy(self.take(len)?).to_string())
+    }
+
+    fn limits(&mut self) -> Result<()> {
+        let flags = self.leb_u32()?;
+        self.leb_u32()?;
+        if flags & 1 == 1 {
+            self.leb_u32()?;
+        }
+        Ok(())
+    }
+}
+
+fn kind_name(kind: u8) -> &'static str {
+    match kind {
+        0 => "func",
+        1 => "table",
+        2 => "memory",
+        3 => "global",
+        _ => "unknown",
+    }
+}
+
+fn section_name(id: u8) -> &'static str {
+    match id {
+        1 => "type",
+        2 => "import",
+        3 => "function",
+        4 => "table",
+        5 => "memory",
+        6 => "global",
+        7 => "export",
+        8 => "start",
+        9 => "element",
+        10 => "code",
+        11 => "data",
+        12 => "data_count",
+        _ => "unknown",
+    }
+}
+
+fn parse_imports(content: &[u8]) -> Result<Vec<String>> {
+    let mut r = Reader::new(content);
+    let count = r.leb_u32()?;
+    let mut imports = vec![];
+    for _ in 0..count {
+        let module = r.name()?;
+        let field = r.name()?;
+        match r.byte()? {
+            0 => {
+                r.leb_u32()?;
+            }
+            1 => {
+                r.byte()?;
+                r.limits()?;
+            }
+            2 => r.limits()?,
+            3 => {
+                r.byte()?;
+                r.byte()?;
+            }
+            k => bail!("Unknown import kind `{k}`"),
+        }
+        imports.push(format!("{module}.{field}"));
+    }
+    Ok(imports)
+}
+
+fn parse_exports(content: &[u8]) -> Result<Vec<String>> {
+    let mut r = Reader::new(content);
+    let count = r.leb_u32()?;
+    let mut exports = vec![];
+    for _ in 0..count {
+        let name = r.name()?;
+        let kind = r.byte()?;
+        r.leb_u32()?;
+        exports.push(format!("{} {name}", kind_name(kind)));
+    }
+    Ok(exports)
+}
+
+fn leb_bytes(mut value: u32) -> Vec<u8> {
+    let mut bytes = vec![];
+    loop {
+        let b = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(b);
+            return bytes;
+        }
+        bytes.push(b | 0x80);
+    }
+}
+
+/// Replace custom section `name` of wasm with `payload`, appended as the last section.
+/// Custom sections are ignored on execution, so this does not change the contract's behavior.
+pub fn with_custom_section(bytes: &[u8], name: &str, payload: &[u8]) -> Result<Vec<u8>> {
+    let custom_name = format!("custom:{name}");
+    let info = parse_wasm(bytes)?;
+
+    let mut out = bytes[..8].to_vec();
+    let mut r = Reader::new(&bytes[8..]);
+    for section in &info.sections {
+        let start = r.pos;
+        r.byte()?;
+        let len = r.leb_u32()? as usize;
+        r.take(len)?;
+        if section.name != custom_name {
+            out.extend_from_slice(&bytes[8 + start..8 + r.pos]);
+        }
+    }
+
+    let content = [
+        leb_bytes(name.len() as u32),
+        name.as_bytes().to_vec(),
+        payload.to_vec(),
+    ]
+    .concat();
+    out.push(0);
+    out.extend(leb_bytes(content.len() as u32));
+    out.extend(content);
+    Ok(out)
+}
+
+pub fn parse_wasm(bytes: &[u8]) -> Result<WasmInfo> {
+    if bytes.len() < 8 || &bytes[0..4] != WASM_MAGIC {
+        bail!("Not a wasm binary");
+    }
+
+    let mut r = Reader::new(&bytes[8..]);
+    let mut info = WasmInfo {
+        size: bytes.len(),
+        exports: vec![],
+        imports: vec![],
+        sections: vec![],
+    };
+
+    while !r.eof() {
+        let id = r.byte()?;
+        let len = r.leb_u32()? as usize;
+        let content = r.take(len)?;
+
+        let name = match id {
+            0 => format!("custom:{}", Reader::new(content).name()?),
+            2 => {
+                info.imports = parse_imports(content)?;
+                section_name(id).to_string()
+            }
+            7 => {
+                info.exports = parse_exports(content)?;
+                section_name(id).to_string()
+            }
+            _ => section_name(id).to_string(),
+        };
+
+        info.sections.push(Section {
+            name,
+            content: content.to_vec(),
+        });
+    }
+
+    info.exports.sort();
+    info.imports.sort();
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(s: &str) -> Vec<u8> {
+        [vec![s.len() as u8], s.as_bytes().to_vec()].concat()
+    }
+
+    fn section(id: u8, content: Vec<u8>) -> Vec<u8> {
+        [vec![id, content.len() as u8], content].concat()
+    }
+
+    #[test]
+    fn parse_minimal_contract() {
+        let imports = [
+            vec![1],
+            name("env"),
+            name("db_read"),
+            vec![0, 0], // func, type index 0
+        ]
+        .concat();
+        let exports = [
+            vec![3],
+            name("instantiate"),
+            vec![0, 1],
+            name("requires_staking"),
+            vec![0, 2],
+            name("memory"),
+            vec![2, 0],
+        ]
+        .concat();
+
+        let wasm = [
+            b"\0asm".to_vec(),
+            vec![1, 0, 0, 0],
+            section(2, imports),
+            section(7, exports),
+            section(0, name("producers")),
+        ]
+        .concat();
+
+        let info = parse_wasm(&wasm).unwrap();
+        assert_eq!(info.size, wasm.len());
+        assert_eq!(info.imports, vec!["env.db_read"]);
+        assert_eq!(
+            info.exports,
+            vec!["func instantiate", "func requires_staking", "memory memory"]
+        );
+        assert_eq!(
+            info.exported_functions(),
+            vec!["instantiate", "requires_staking"]
+        );
+        assert_eq!(info.capabilities(), vec!["staking"]);
+        assert_eq!(
+            info.sections
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["import", "export", "custom:producers"]
+        );
+    }
+
+    #[test]
+    fn replace_custom_section() {
+        let wasm = [
+            b"\0asm".to_vec(),
+            vec![1, 0, 0, 0],
+            section(7, vec![0]),
+            section(0, [name("beaker.build"), b"old".to_vec()].concat()),
+        ]
+        .concat();
+
+        let payload = vec![b'x'; 200];
+        let updated = with_custom_section(&wasm, "beaker.build", &payload).unwrap();
+        let info = parse_wasm(&updated).unwrap();
+
+        assert_eq!(info.sections.len(), 2);
+        assert_eq!(
+            info.custom_section("beaker.build"),
+            Some(payload.as_slice())
+        );
+        assert_eq!(info.custom_section("producers"), None);
+    }
+
+    #[test]
+    fn reject_non_wasm() {
+        assert!(parse_wasm(b"not wasm").is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! Wasm module params as served by wasmd's own `Params` query, which replaced the `x/params`
+//! subspace in wasmd 0.40. The access config here also has the `addresses` field of `AnyOfAddresses`.
+//! `ContractsByCreator`, added in the same wasmd version, is here as well.
+
+use cosmos_sdk_proto::cosmos::base::query::v1beta1::{PageRequest, PageResponse};
+use serde_json::{json, Value};
+
+pub const WASM_PARAMS_PATH: &str = "/cosmwasm.wasm.v1.Query/Params";
+pub const CONTRACTS_BY_CREATOR_PATH: &str = "/cosmwasm.wasm.v1.Query/ContractsByCreator";
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryParamsRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryParamsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub params: Option<Params>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Params {
+    #[prost(message, optional, tag = "1")]
+    pub code_upload_access: Option<AccessConfig>,
+    #[prost(int32, tag = "2")]
+    pub instantiate_default_permission: i32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AccessConfig {
+    #[prost(int32, tag = "1")]
+    pub permission: i32,
+    #[prost(string, tag = "2")]
+    pub address: String,
+    #[prost(string, repeated, tag = "3")]
+    pub addresses: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryContractsByCreatorRequest {
+    #[prost(string, tag = "1")]
+    pub creator_address: String,
+    #[prost(message, optional, tag = "2")]
+    pub pagination: Option<PageRequest>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryContractsByCreatorResponse {
+    #[prost(string, repeated, tag = "1")]
+    pub contract_addresses: Vec<String>,
+    #[prost(message, optional, tag = "2")]
+    pub pagination: Option<PageResponse>,
+}
+
+/// Name of `AccessType`, as it appears in the amino json of `x/params`
+pub fn access_type_name(permission: i32) -> &'static str {
+    match permission {
+        1 => "Nobody",
+        2 => "OnlyAddress",
+        3 => "Everybody",
+        4 => "AnyOfAddresses",
+        _ => "Unspecified",
+    }
+}
+
+/// Access config in the same json shape as the `uploadAccess` param of `x/params`
+pub fn access_config_json(config: &AccessConfig) -> Value {
+    json!({
+        "permission": access_type_name(config.permission),
+        "address": config.address,
+        "addresses": config.addresses,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_access_config() {
+        let config = AccessConfig {
+            permission: 4,
+            address: String::new(),
+            addresses: vec!["osmo1a".to_string()],
+        };
+        assert_eq!(
+            access_config_json(&config),
+            json!({ "permission": "AnyOfAddresses", "address": "", "addresses": ["osmo1a"] })
+        );
+        assert_eq!(access_type_name(0), "Unspecified");
+    }
+}
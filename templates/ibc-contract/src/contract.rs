@@ -0,0 +1,92 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, IbcMsg, IbcTimeout, MessageInfo, Order, Response,
+    StdResult,
+};
+use cw2::set_contract_version;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ChannelResponse, ExecuteMsg, InstantiateMsg, ListChannelsResponse, PacketMsg, QueryMsg,
+};
+use crate::state::CHANNELS;
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:{{project-name}}";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Seconds until a sent packet times out if it is not relayed
+pub const PACKET_LIFETIME: u64 = 60 * 60;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new().add_attribute("method", "instantiate"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Ping { channel } => execute::ping(deps, env, channel),
+    }
+}
+
+pub mod execute {
+    use super::*;
+
+    pub fn ping(deps: DepsMut, env: Env, channel: String) -> Result<Response, ContractError> {
+        let mut info = CHANNELS.may_load(deps.storage, &channel)?.ok_or_else(|| {
+            ContractError::UnknownChannel {
+                channel: channel.clone(),
+            }
+        })?;
+        info.pings_sent += 1;
+        CHANNELS.save(deps.storage, &channel, &info)?;
+
+        let packet = IbcMsg::SendPacket {
+            channel_id: channel.clone(),
+            data: to_binary(&PacketMsg::Ping {})?,
+            timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(PACKET_LIFETIME)),
+        };
+        Ok(Response::new()
+            .add_message(packet)
+            .add_attribute("action", "ping")
+            .add_attribute("channel_id", channel))
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Channel { id } => to_binary(&query::channel(deps, id)?),
+        QueryMsg::ListChannels {} => to_binary(&query::list_channels(deps)?),
+    }
+}
+
+pub mod query {
+    use super::*;
+
+    pub fn channel(deps: Deps, id: String) -> StdResult<ChannelResponse> {
+        let info = CHANNELS.load(deps.storage, &id)?;
+        Ok(ChannelResponse { info })
+    }
+
+    pub fn list_channels(deps: Deps) -> StdResult<ListChannelsResponse> {
+        let channels = CHANNELS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, info)| info))
+            .collect::<StdResult<_>>()?;
+        Ok(ListChannelsResponse { channels })
+    }
+}
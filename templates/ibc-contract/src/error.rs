@@ -0,0 +1,19 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Only unordered channels are supported")]
+    InvalidOrder {},
+
+    #[error("Invalid IBC channel version: got {actual}, expected {expected}")]
+    InvalidVersion { actual: String, expected: String },
+
+    #[error("Unknown channel: {channel}")]
+    UnknownChannel { channel: String },
+    // Add any other custom errors you like here.
+    // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
+}
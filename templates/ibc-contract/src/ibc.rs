@@ -0,0 +1,170 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Binary, DepsMut, Env, IbcBasicResponse, IbcChannel,
+    IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcChannelOpenResponse, IbcOrder,
+    IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse,
+};
+
+use crate::error::ContractError;
+use crate::msg::{PacketAck, PacketMsg, PingResponse};
+use crate::state::{ChannelInfo, CHANNELS};
+
+/// Version of the packet protocol, both ends of the channel must agree on it
+pub const IBC_VERSION: &str = "ping-1";
+pub const IBC_ORDER: IbcOrder = IbcOrder::Unordered;
+
+fn validate_order_and_version(
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    if channel.order != IBC_ORDER {
+        return Err(ContractError::InvalidOrder {});
+    }
+    for version in std::iter::once(channel.version.as_str()).chain(counterparty_version) {
+        if version != IBC_VERSION {
+            return Err(ContractError::InvalidVersion {
+                actual: version.to_string(),
+                expected: IBC_VERSION.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<IbcChannelOpenResponse, ContractError> {
+    validate_order_and_version(msg.channel(), msg.counterparty_version())?;
+    Ok(None)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel = msg.channel();
+    validate_order_and_version(channel, msg.counterparty_version())?;
+
+    let info = ChannelInfo {
+        id: channel.endpoint.channel_id.clone(),
+        counterparty_endpoint: channel.counterparty_endpoint.clone(),
+        connection_id: channel.connection_id.clone(),
+        pings_sent: 0,
+        pings_received: 0,
+        pongs_received: 0,
+        timeouts: 0,
+    };
+    CHANNELS.save(deps.storage, &info.id, &info)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ibc_channel_connect")
+        .add_attribute("channel_id", &info.id))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel_id = &msg.channel().endpoint.channel_id;
+    CHANNELS.remove(deps.storage, channel_id);
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ibc_channel_close")
+        .add_attribute("channel_id", channel_id))
+}
+
+/// Errors are written as `PacketAck::Error` instead of being returned,
+/// a failing receive would abort the relayer's whole tx and the packet could never be acknowledged
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, ContractError> {
+    let channel_id = msg.packet.dest.channel_id.clone();
+    let res = from_slice::<PacketMsg>(&msg.packet.data)
+        .map_err(ContractError::from)
+        .and_then(|packet| match packet {
+            PacketMsg::Ping {} => receive_ping(deps, &channel_id),
+        });
+
+    Ok(match res {
+        Ok(ack) => IbcReceiveResponse::new()
+            .set_ack(to_binary(&PacketAck::Result(ack))?)
+            .add_attribute("method", "ibc_packet_receive")
+            .add_attribute("channel_id", channel_id),
+        Err(e) => IbcReceiveResponse::new()
+            .set_ack(to_binary(&PacketAck::Error(e.to_string()))?)
+            .add_attribute("method", "ibc_packet_receive")
+            .add_attribute("channel_id", channel_id)
+            .add_attribute("error", e.to_string()),
+    })
+}
+
+fn receive_ping(deps: DepsMut, channel_id: &str) -> Result<Binary, ContractError> {
+    let mut pings_received = 0;
+    update_channel(deps, channel_id, |info| {
+        info.pings_received += 1;
+        pings_received = info.pings_received;
+    })?;
+    Ok(to_binary(&PingResponse { pings_received })?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel_id = msg.original_packet.src.channel_id.clone();
+    let ack: PacketAck = from_binary(&msg.acknowledgement.data)?;
+    let mut res = IbcBasicResponse::new()
+        .add_attribute("method", "ibc_packet_ack")
+        .add_attribute("channel_id", &channel_id);
+
+    match ack {
+        PacketAck::Result(_) => {
+            update_channel(deps, &channel_id, |info| info.pongs_received += 1)?;
+        }
+        // the counterparty failed to handle the packet, nothing was changed on its side
+        PacketAck::Error(e) => res = res.add_attribute("error", e),
+    }
+    Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let channel_id = msg.packet.src.channel_id;
+    update_channel(deps, &channel_id, |info| info.timeouts += 1)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ibc_packet_timeout")
+        .add_attribute("channel_id", channel_id))
+}
+
+fn update_channel(
+    deps: DepsMut,
+    channel_id: &str,
+    update: impl FnOnce(&mut ChannelInfo),
+) -> Result<(), ContractError> {
+    let mut info = CHANNELS
+        .may_load(deps.storage, channel_id)?
+        .ok_or_else(|| ContractError::UnknownChannel {
+            channel: channel_id.to_string(),
+        })?;
+    update(&mut info);
+    CHANNELS.save(deps.storage, channel_id, &info)?;
+    Ok(())
+}
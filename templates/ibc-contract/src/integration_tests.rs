@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_ibc_channel_close_init, mock_ibc_channel_connect_ack,
+        mock_ibc_channel_open_init, mock_ibc_channel_open_try, mock_ibc_packet_ack,
+        mock_ibc_packet_recv, mock_ibc_packet_timeout, mock_info, MockApi, MockQuerier,
+        MockStorage,
+    };
+    use cosmwasm_std::{from_binary, CosmosMsg, IbcAcknowledgement, IbcMsg, IbcOrder, OwnedDeps};
+
+    use crate::contract::{execute, instantiate, query};
+    use crate::ibc::{
+        ibc_channel_close, ibc_channel_connect, ibc_channel_open, ibc_packet_ack,
+        ibc_packet_receive, ibc_packet_timeout, IBC_ORDER, IBC_VERSION,
+    };
+    use crate::msg::{
+        ChannelResponse, ExecuteMsg, InstantiateMsg, ListChannelsResponse, PacketAck, PacketMsg,
+        PingResponse, QueryMsg,
+    };
+    use crate::ContractError;
+
+    const CHANNEL: &str = "channel-0";
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+        deps
+    }
+
+    /// Run the channel handshake from this side up to the connected channel
+    fn connect(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, channel: &str) {
+        let open = mock_ibc_channel_open_init(channel, IBC_ORDER, IBC_VERSION);
+        ibc_channel_open(deps.as_mut(), mock_env(), open).unwrap();
+        let connect = mock_ibc_channel_connect_ack(channel, IBC_ORDER, IBC_VERSION);
+        ibc_channel_connect(deps.as_mut(), mock_env(), connect).unwrap();
+    }
+
+    fn channel(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> ChannelResponse {
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Channel {
+                id: CHANNEL.to_string(),
+            },
+        )
+        .unwrap();
+        from_binary(&res).unwrap()
+    }
+
+    #[test]
+    fn reject_unexpected_channels() {
+        let mut deps = setup();
+
+        let ordered = mock_ibc_channel_open_init(CHANNEL, IbcOrder::Ordered, IBC_VERSION);
+        assert!(matches!(
+            ibc_channel_open(deps.as_mut(), mock_env(), ordered),
+            Err(ContractError::InvalidOrder {})
+        ));
+
+        let other_version = mock_ibc_channel_open_try(CHANNEL, IBC_ORDER, "other-1");
+        assert!(matches!(
+            ibc_channel_open(deps.as_mut(), mock_env(), other_version),
+            Err(ContractError::InvalidVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn handshake_records_channel() {
+        let mut deps = setup();
+        connect(&mut deps, CHANNEL);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ListChannels {}).unwrap();
+        let list: ListChannelsResponse = from_binary(&res).unwrap();
+        assert_eq!(list.channels.len(), 1);
+        assert_eq!(channel(&deps).info.id, CHANNEL);
+
+        let close = mock_ibc_channel_close_init(CHANNEL, IBC_ORDER, IBC_VERSION);
+        ibc_channel_close(deps.as_mut(), mock_env(), close).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::ListChannels {}).unwrap();
+        let list: ListChannelsResponse = from_binary(&res).unwrap();
+        assert!(list.channels.is_empty());
+    }
+
+    #[test]
+    fn ping_sends_packet() {
+        let mut deps = setup();
+
+        let unknown = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::Ping {
+                channel: CHANNEL.to_string(),
+            },
+        );
+        assert!(matches!(unknown, Err(ContractError::UnknownChannel { .. })));
+
+        connect(&mut deps, CHANNEL);
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::Ping {
+                channel: CHANNEL.to_string(),
+            },
+        )
+        .unwrap();
+
+        match &res.messages[0].msg {
+            CosmosMsg::Ibc(IbcMsg::SendPacket {
+                channel_id, data, ..
+            }) => {
+                assert_eq!(channel_id, CHANNEL);
+                assert_eq!(from_binary::<PacketMsg>(data).unwrap(), PacketMsg::Ping {});
+            }
+            msg => panic!("Unexpected msg: {msg:?}"),
+        }
+        assert_eq!(channel(&deps).info.pings_sent, 1);
+    }
+
+    #[test]
+    fn receive_ping_acks_pong() {
+        let mut deps = setup();
+        connect(&mut deps, CHANNEL);
+
+        let recv = mock_ibc_packet_recv(CHANNEL, &PacketMsg::Ping {}).unwrap();
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), recv).unwrap();
+        let ack: PacketAck = from_binary(&res.acknowledgement).unwrap();
+        match ack {
+            PacketAck::Result(data) => {
+                let pong: PingResponse = from_binary(&data).unwrap();
+                assert_eq!(pong.pings_received, 1);
+            }
+            PacketAck::Error(e) => panic!("Unexpected error ack: {e}"),
+        }
+
+        // unknown packets are acknowledged with error instead of failing the relay
+        let recv = mock_ibc_packet_recv(CHANNEL, &"unknown").unwrap();
+        let res = ibc_packet_receive(deps.as_mut(), mock_env(), recv).unwrap();
+        let ack: PacketAck = from_binary(&res.acknowledgement).unwrap();
+        assert!(matches!(ack, PacketAck::Error(_)));
+    }
+
+    #[test]
+    fn ack_and_timeout_are_counted() {
+        let mut deps = setup();
+        connect(&mut deps, CHANNEL);
+
+        let pong = PacketAck::Result(
+            cosmwasm_std::to_binary(&PingResponse { pings_received: 1 }).unwrap(),
+        );
+        let ack = mock_ibc_packet_ack(
+            CHANNEL,
+            &PacketMsg::Ping {},
+            IbcAcknowledgement::encode_json(&pong).unwrap(),
+        )
+        .unwrap();
+        ibc_packet_ack(deps.as_mut(), mock_env(), ack).unwrap();
+
+        let timeout = mock_ibc_packet_timeout(CHANNEL, &PacketMsg::Ping {}).unwrap();
+        ibc_packet_timeout(deps.as_mut(), mock_env(), timeout).unwrap();
+
+        let info = channel(&deps).info;
+        assert_eq!(info.pongs_received, 1);
+        assert_eq!(info.timeouts, 1);
+    }
+}
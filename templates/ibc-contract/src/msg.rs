@@ -0,0 +1,52 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Binary;
+
+use crate::state::ChannelInfo;
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Send ping packet over the channel
+    Ping { channel: String },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ChannelResponse)]
+    Channel { id: String },
+    #[returns(ListChannelsResponse)]
+    ListChannels {},
+}
+
+#[cw_serde]
+pub struct ChannelResponse {
+    pub info: ChannelInfo,
+}
+
+#[cw_serde]
+pub struct ListChannelsResponse {
+    pub channels: Vec<ChannelInfo>,
+}
+
+/// Packets sent between instances of the contract
+#[cw_serde]
+pub enum PacketMsg {
+    Ping {},
+}
+
+/// Acknowledgement written for every received packet
+#[cw_serde]
+pub enum PacketAck {
+    Result(Binary),
+    Error(String),
+}
+
+/// Result of a received ping
+#[cw_serde]
+pub struct PingResponse {
+    /// Number of pings received over the channel, including this one
+    pub pings_received: u64,
+}
@@ -0,0 +1,18 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::IbcEndpoint;
+use cw_storage_plus::Map;
+
+#[cw_serde]
+pub struct ChannelInfo {
+    /// Id of the channel on this chain
+    pub id: String,
+    /// Port and channel of the contract on the other chain
+    pub counterparty_endpoint: IbcEndpoint,
+    pub connection_id: String,
+    pub pings_sent: u64,
+    pub pings_received: u64,
+    pub pongs_received: u64,
+    pub timeouts: u64,
+}
+
+pub const CHANNELS: Map<&str, ChannelInfo> = Map::new("channels");
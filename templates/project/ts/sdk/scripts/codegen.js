@@ -4,6 +4,7 @@ const fs = require("fs");
 
 const pkgRoot = path.join(__dirname, "..");
 const contractsDir = path.join(pkgRoot, "..", "..", "contracts");
+const reactQuery = !!process.env.BEAKER_TS_REACT_QUERY;
 
 const contracts = fs
   .readdirSync(contractsDir, { withFileTypes: true })
@@ -16,6 +17,56 @@ const contracts = fs
 const outPath = path.join(pkgRoot, "src", "contracts");
 fs.rmSync(outPath, { recursive: true, force: true });
 
+// written by `beaker wasm ts-gen` from deployed state
+const addressesJson = path.join(pkgRoot, "addresses.json");
+const addresses = fs.existsSync(addressesJson)
+  ? fs.readFileSync(addressesJson, "utf8")
+  : "{}";
+
+const pascal = (name) =>
+  name
+    .split(/[^a-zA-Z0-9]/)
+    .filter((s) => s.length > 0)
+    .map((s) => s[0].toUpperCase() + s.slice(1))
+    .join("");
+
+// `use<Contract>Query` and `use<Contract>Execute` hooks, bound to addresses
+// deployed on the network `beaker wasm ts-gen` was run with
+const hooks = (contracts) =>
+  [
+    `import { useMemo } from "react";`,
+    `import type { CosmWasmClient, SigningCosmWasmClient } from "@cosmjs/cosmwasm-stargate";`,
+    `import * as contracts from "./contracts";`,
+    `import { addresses } from "./addresses";`,
+    ``,
+    `const addressOf = (contract: string, label: string): string | undefined =>`,
+    `  addresses[contract]?.addresses[label];`,
+    ...contracts.flatMap(({ name }) => {
+      const n = pascal(name);
+      return [
+        ``,
+        `export const use${n}Query = (client: CosmWasmClient | undefined, label = "default") =>`,
+        `  useMemo(() => {`,
+        `    const address = addressOf("${name}", label);`,
+        `    return client && address ? new contracts.${n}.${n}QueryClient(client, address) : undefined;`,
+        `  }, [client, label]);`,
+        ``,
+        `export const use${n}Execute = (`,
+        `  client: SigningCosmWasmClient | undefined,`,
+        `  sender: string | undefined,`,
+        `  label = "default"`,
+        `) =>`,
+        `  useMemo(() => {`,
+        `    const address = addressOf("${name}", label);`,
+        `    return client && sender && address`,
+        `      ? new contracts.${n}.${n}Client(client, sender, address)`,
+        `      : undefined;`,
+        `  }, [client, sender, label]);`,
+      ];
+    }),
+    ``,
+  ].join("\n");
+
 codegen({
   contracts,
   outPath,
@@ -24,7 +75,40 @@ codegen({
       bundleFile: "index.ts",
       scope: "contracts",
     },
+    reactQuery: {
+      enabled: reactQuery,
+      optionalClient: true,
+      version: "v4",
+      mutations: true,
+    },
   },
 }).then(() => {
+  const hooksPath = path.join(pkgRoot, "src", "hooks.ts");
+  const index = [
+    `export * as contracts from "./contracts";`,
+    `export * from "./addresses";`,
+  ];
+
+  fs.writeFileSync(
+    path.join(pkgRoot, "src", "addresses.ts"),
+    [
+      `export type Addresses = Record<`,
+      `  string,`,
+      `  { code_id: number | null; addresses: Record<string, string> }`,
+      `>;`,
+      ``,
+      `export const addresses: Addresses = ${addresses.trim()};`,
+      ``,
+    ].join("\n")
+  );
+
+  if (reactQuery) {
+    fs.writeFileSync(hooksPath, hooks(contracts));
+    index.push(`export * from "./hooks";`);
+  } else {
+    fs.rmSync(hooksPath, { force: true });
+  }
+
+  fs.writeFileSync(path.join(pkgRoot, "src", "index.ts"), index.join("\n") + "\n");
   console.log("✨ Typescript code is generated successfully!");
 });
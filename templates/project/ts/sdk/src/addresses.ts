@@ -0,0 +1,6 @@
+export type Addresses = Record<
+  string,
+  { code_id: number | null; addresses: Record<string, string> }
+>;
+
+export const addresses: Addresses = {};
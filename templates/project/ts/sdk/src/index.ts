@@ -1 +1,2 @@
 export * as contracts from "./contracts";
+export * from "./addresses";